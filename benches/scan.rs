@@ -0,0 +1,128 @@
+use chunk::{IncludeDelim, OwnedChunker, chunk, split_at_delimiters};
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+
+/// Synthetic prose built from `words`, deterministic so runs are comparable.
+fn corpus(words: &[&str], len: usize) -> Vec<u8> {
+    let punctuation = [". ", ", ", "? ", "! ", "; ", ": ", "\n"];
+    let mut text = Vec::with_capacity(len + 64);
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    while text.len() < len {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        text.extend_from_slice(words[state as usize % words.len()].as_bytes());
+        let p = (state >> 32) as usize % 24;
+        match punctuation.get(p) {
+            Some(p) => text.extend_from_slice(p.as_bytes()),
+            None => text.push(b' '),
+        }
+    }
+    text.truncate(len);
+    text
+}
+
+fn ascii(len: usize) -> Vec<u8> {
+    corpus(
+        &[
+            "the", "quick", "brown", "fox", "jumps", "over", "lazy", "dog", "chunking",
+        ],
+        len,
+    )
+}
+
+fn multibyte(len: usize) -> Vec<u8> {
+    corpus(
+        &[
+            "über",
+            "naïve",
+            "café",
+            "日本語",
+            "テキスト",
+            "русский",
+            "текст",
+            "😀",
+        ],
+        len,
+    )
+}
+
+const SIZES: [(&str, usize); 2] = [("64KB", 64 << 10), ("16MB", 16 << 20)];
+
+/// Chunking across input sizes and delimiter set sizes (memchr vs table paths).
+fn bench_delimiters(c: &mut Criterion) {
+    let delimiter_sets: [(&str, &[u8]); 4] = [
+        ("1 delim", b"."),
+        ("3 delims", b"\n.?"),
+        ("6 delims", b"\n.?!;:"),
+        ("8 delims", b"\n.?!;:, "),
+    ];
+
+    for (size_name, len) in SIZES {
+        let text = ascii(len);
+        let mut group = c.benchmark_group(format!("delimiters/{size_name}"));
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        for (name, delimiters) in delimiter_sets {
+            group.bench_with_input(BenchmarkId::new("chunk", name), &delimiters, |b, d| {
+                b.iter(|| chunk(black_box(&text)).size(4096).delimiters(d).count())
+            });
+            group.bench_with_input(BenchmarkId::new("split", name), &delimiters, |b, d| {
+                b.iter(|| split_at_delimiters(black_box(&text), d, IncludeDelim::Prev, 0))
+            });
+        }
+        group.finish();
+    }
+}
+
+/// ASCII vs multi-byte text, with and without UTF-8-safe splitting.
+fn bench_encoding(c: &mut Criterion) {
+    let (_, len) = SIZES[1];
+    let inputs = [("ascii", ascii(len)), ("multibyte", multibyte(len))];
+
+    let mut group = c.benchmark_group("encoding");
+    group.throughput(Throughput::Bytes(len as u64));
+    for (name, text) in &inputs {
+        group.bench_with_input(BenchmarkId::new("bytes", name), text, |b, t| {
+            b.iter(|| chunk(black_box(t)).size(4096).count())
+        });
+        group.bench_with_input(BenchmarkId::new("utf8_safe", name), text, |b, t| {
+            b.iter(|| chunk(black_box(t)).size(4096).utf8_safe(true).count())
+        });
+        group.bench_with_input(BenchmarkId::new("no_delimiters", name), text, |b, t| {
+            b.iter(|| {
+                chunk(black_box(t))
+                    .size(4096)
+                    .delimiters(b"")
+                    .utf8_safe(true)
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Offsets only vs materializing chunks, borrowed and owned.
+fn bench_output(c: &mut Criterion) {
+    for (size_name, len) in SIZES {
+        let text = ascii(len);
+        let mut group = c.benchmark_group(format!("output/{size_name}"));
+        group.throughput(Throughput::Bytes(text.len() as u64));
+
+        group.bench_function("collect_offsets", |b| {
+            b.iter(|| chunk(black_box(&text)).size(1024).collect_offsets())
+        });
+        group.bench_function("collect_slices", |b| {
+            b.iter(|| chunk(black_box(&text)).size(1024).collect::<Vec<_>>())
+        });
+        let mut owned = OwnedChunker::new(text.clone()).size(1024);
+        group.bench_function("owned_collect_offsets", |b| {
+            b.iter(|| black_box(owned.collect_offsets()))
+        });
+        group.bench_function("owned_collect_chunks", |b| {
+            b.iter(|| black_box(owned.collect_chunks()))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_delimiters, bench_encoding, bench_output);
+criterion_main!(benches);
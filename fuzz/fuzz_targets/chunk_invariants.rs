@@ -0,0 +1,85 @@
+//! Boundary invariants on arbitrary input, run with
+//! `cargo +nightly fuzz run chunk_invariants`.
+//!
+//! The first bytes pick the settings, the rest is the text.
+
+#![no_main]
+
+use chunk::{StreamChunker, chunk};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let [size, flags, max_size, overlap, text @ ..] = data else {
+        return;
+    };
+    let size = *size as usize + 1;
+    let utf8_safe = flags & 1 != 0;
+    let max_size = (flags & 2 != 0).then_some((*max_size as usize).max(4));
+    let overlap = if flags & 4 != 0 {
+        *overlap as usize % 16
+    } else {
+        0
+    };
+    let delimiters: &[u8] = if flags & 8 != 0 { b" \n" } else { b".?!" };
+
+    let mut chunker = chunk(text)
+        .size(size)
+        .delimiters(delimiters)
+        .utf8_safe(utf8_safe)
+        .overlap(overlap);
+    if flags & 16 != 0 {
+        chunker = chunker.prefix();
+    }
+    if flags & 32 != 0 {
+        chunker = chunker.consecutive();
+    }
+    if let Some(max_size) = max_size {
+        chunker = chunker.max_size(max_size);
+    }
+    let offsets = chunker.collect_offsets();
+
+    // Contiguous (up to overlap) and covering
+    assert_eq!(
+        offsets.first().map(|o| o.0),
+        (!text.is_empty()).then_some(0)
+    );
+    assert_eq!(
+        offsets.last().map(|o| o.1),
+        (!text.is_empty()).then_some(text.len())
+    );
+    let mut rebuilt = Vec::with_capacity(text.len());
+    for &(start, end) in &offsets {
+        assert!(start < end && start <= rebuilt.len());
+        if let Some(max_size) = max_size {
+            assert!(end - start <= max_size);
+        }
+        if utf8_safe && std::str::from_utf8(text).is_ok() {
+            assert!(std::str::from_utf8(&text[start..end]).is_ok());
+        }
+        let from = rebuilt.len().max(start);
+        rebuilt.extend_from_slice(&text[from..end]);
+    }
+    assert_eq!(rebuilt, text);
+
+    // Streaming in two pieces gives the same chunks
+    let mut stream = StreamChunker::new()
+        .size(size)
+        .delimiters(delimiters.to_vec())
+        .utf8_safe(utf8_safe)
+        .overlap(overlap);
+    if flags & 16 != 0 {
+        stream = stream.prefix();
+    }
+    if flags & 32 != 0 {
+        stream = stream.consecutive();
+    }
+    if let Some(max_size) = max_size {
+        stream = stream.max_size(max_size);
+    }
+    let (head, tail) = text.split_at(text.len() / 2);
+    let mut streamed = stream.push(head);
+    streamed.extend(stream.push(tail));
+    streamed.extend(stream.finish());
+    let expected: Vec<_> = offsets.iter().map(|&(s, e)| text[s..e].to_vec()).collect();
+    assert_eq!(streamed, expected);
+});
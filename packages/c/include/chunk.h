@@ -0,0 +1,174 @@
+#ifndef CHUNK_H
+#define CHUNK_H
+
+/* Generated by cbindgen from packages/c/src/lib.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result of a call into the library.
+ */
+typedef enum ChunkStatus {
+  /**
+   * Success.
+   */
+  CHUNK_STATUS_OK = 0,
+  /**
+   * A required pointer was NULL (or a buffer pointer was NULL with a
+   * non-zero length).
+   */
+  CHUNK_STATUS_NULL_POINTER = 1,
+  /**
+   * A delimiter class name wasn't one of the known classes.
+   */
+  CHUNK_STATUS_UNKNOWN_DELIMITERS = 2,
+} ChunkStatus;
+
+/**
+ * Chunking options. Start from [`chunk_config_default`] and override fields.
+ *
+ * Buffers (`delimiters`, `pattern`, `delimiter_class`) are borrowed for the
+ * duration of each call only.
+ */
+typedef struct ChunkConfig {
+  /**
+   * Target chunk size in bytes.
+   */
+  size_t size;
+  /**
+   * Single-byte delimiters; NULL uses the default `\n.?`.
+   */
+  const uint8_t *delimiters;
+  /**
+   * Length of `delimiters`.
+   */
+  size_t delimiters_len;
+  /**
+   * Multi-byte pattern to split at instead of delimiters; NULL for none.
+   */
+  const uint8_t *pattern;
+  /**
+   * Length of `pattern`.
+   */
+  size_t pattern_len;
+  /**
+   * Put the delimiter at the start of the next chunk.
+   */
+  bool prefix;
+  /**
+   * Split at the start of runs of the same delimiter.
+   */
+  bool consecutive;
+  /**
+   * Search forward when no delimiter is found before the target size.
+   */
+  bool forward_fallback;
+  /**
+   * Bytes of overlap between consecutive chunks.
+   */
+  size_t overlap;
+  /**
+   * Never split inside a multi-byte UTF-8 sequence.
+   */
+  bool utf8_safe;
+  /**
+   * Merge a trailing chunk smaller than this into the previous one.
+   */
+  size_t min_size;
+  /**
+   * Hard cap on chunk length; 0 for none.
+   */
+  size_t max_size;
+  /**
+   * NUL-terminated name of a delimiter class (see
+   * [`chunk_config_delimiter_class`]) used instead of `delimiters`,
+   * `pattern` and `prefix`; NULL for none.
+   */
+  const char *delimiter_class;
+} ChunkConfig;
+
+/**
+ * A chunk's byte range in the input, `[start, end)`.
+ */
+typedef struct ChunkSpan {
+  size_t start;
+  size_t end;
+} ChunkSpan;
+
+/**
+ * Chunk offsets owned by the library; free with [`chunk_offsets_free`].
+ */
+typedef struct ChunkOffsets {
+  /**
+   * `len` spans, or NULL when there are none.
+   */
+  struct ChunkSpan *spans;
+  size_t len;
+} ChunkOffsets;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Default config: 4KB chunks split at `\n.?`.
+ */
+struct ChunkConfig chunk_config_default(void);
+
+/**
+ * Check that `name` is a delimiter class (`sentences`, `whitespace`,
+ * `newlines`, `markdown_headings`, `cjk` or `multilingual`) and select it
+ * as `config`'s `delimiter_class`.
+ *
+ * `name` is borrowed like the other buffers, so it must stay valid while
+ * `config` is used.
+ *
+ * # Safety
+ *
+ * `config` must be valid for reads and writes, and `name` must be a
+ * NUL-terminated string.
+ */
+enum ChunkStatus chunk_config_delimiter_class(struct ChunkConfig *config, const char *name);
+
+/**
+ * Chunk `text`, writing the offsets to `out`.
+ *
+ * `config` may be NULL for the defaults. On error `out` is set to an empty
+ * result, which is also safe to pass to [`chunk_offsets_free`].
+ *
+ * # Safety
+ *
+ * `text` must point to `len` readable bytes (it may be NULL if `len` is 0),
+ * `config` must be NULL or point to a valid [`ChunkConfig`] whose buffers
+ * are readable for their lengths, and `out` must be valid for writes.
+ */
+enum ChunkStatus chunk_offsets(const uint8_t *text,
+                               size_t len,
+                               const struct ChunkConfig *config,
+                               struct ChunkOffsets *out);
+
+/**
+ * Free offsets returned by [`chunk_offsets`] and reset them to empty.
+ *
+ * Passing NULL, or offsets that are already empty, is a no-op.
+ *
+ * # Safety
+ *
+ * `offsets` must be NULL or point to a [`ChunkOffsets`] filled in by
+ * [`chunk_offsets`] and not modified or freed since.
+ */
+void chunk_offsets_free(struct ChunkOffsets *offsets);
+
+/**
+ * The library version, as a static NUL-terminated string.
+ */
+const char *chunk_version(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CHUNK_H */
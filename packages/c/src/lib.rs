@@ -0,0 +1,385 @@
+//! C ABI for the chunk library.
+//!
+//! Every function takes plain pointers and lengths, so the library can be
+//! used from C, C++, Go (cgo) or anything else with a C FFI. The header in
+//! `include/chunk.h` is generated from this file with cbindgen.
+//!
+//! # Ownership
+//!
+//! Offsets returned in a [`ChunkOffsets`] are allocated by this library and
+//! must be released with [`chunk_offsets_free`], exactly once. Input text and
+//! config buffers are only borrowed for the duration of the call.
+
+use std::ffi::{CStr, c_char};
+use std::{ptr, slice};
+
+use chunk::{ChunkerConfig, DEFAULT_TARGET_SIZE, Delimiters};
+
+/// Result of a call into the library.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkStatus {
+    /// Success.
+    Ok = 0,
+    /// A required pointer was NULL (or a buffer pointer was NULL with a
+    /// non-zero length).
+    NullPointer = 1,
+    /// A delimiter class name wasn't one of the known classes.
+    UnknownDelimiters = 2,
+}
+
+/// Chunking options. Start from [`chunk_config_default`] and override fields.
+///
+/// Buffers (`delimiters`, `pattern`, `delimiter_class`) are borrowed for the
+/// duration of each call only.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkConfig {
+    /// Target chunk size in bytes.
+    pub size: usize,
+    /// Single-byte delimiters; NULL uses the default `\n.?`.
+    pub delimiters: *const u8,
+    /// Length of `delimiters`.
+    pub delimiters_len: usize,
+    /// Multi-byte pattern to split at instead of delimiters; NULL for none.
+    pub pattern: *const u8,
+    /// Length of `pattern`.
+    pub pattern_len: usize,
+    /// Put the delimiter at the start of the next chunk.
+    pub prefix: bool,
+    /// Split at the start of runs of the same delimiter.
+    pub consecutive: bool,
+    /// Search forward when no delimiter is found before the target size.
+    pub forward_fallback: bool,
+    /// Bytes of overlap between consecutive chunks.
+    pub overlap: usize,
+    /// Never split inside a multi-byte UTF-8 sequence.
+    pub utf8_safe: bool,
+    /// Merge a trailing chunk smaller than this into the previous one.
+    pub min_size: usize,
+    /// Hard cap on chunk length; 0 for none.
+    pub max_size: usize,
+    /// NUL-terminated name of a delimiter class (see
+    /// [`chunk_config_delimiter_class`]) used instead of `delimiters`,
+    /// `pattern` and `prefix`; NULL for none.
+    pub delimiter_class: *const c_char,
+}
+
+/// A chunk's byte range in the input, `[start, end)`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Chunk offsets owned by the library; free with [`chunk_offsets_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct ChunkOffsets {
+    /// `len` spans, or NULL when there are none.
+    pub spans: *mut ChunkSpan,
+    pub len: usize,
+}
+
+impl ChunkOffsets {
+    const EMPTY: Self = Self {
+        spans: ptr::null_mut(),
+        len: 0,
+    };
+}
+
+/// Default config: 4KB chunks split at `\n.?`.
+#[unsafe(no_mangle)]
+pub extern "C" fn chunk_config_default() -> ChunkConfig {
+    ChunkConfig {
+        size: DEFAULT_TARGET_SIZE,
+        delimiters: ptr::null(),
+        delimiters_len: 0,
+        pattern: ptr::null(),
+        pattern_len: 0,
+        prefix: false,
+        consecutive: false,
+        forward_fallback: false,
+        overlap: 0,
+        utf8_safe: false,
+        min_size: 0,
+        max_size: 0,
+        delimiter_class: ptr::null(),
+    }
+}
+
+/// Check that `name` is a delimiter class (`sentences`, `whitespace`,
+/// `newlines`, `markdown_headings`, `cjk` or `multilingual`) and select it
+/// as `config`'s `delimiter_class`.
+///
+/// `name` is borrowed like the other buffers, so it must stay valid while
+/// `config` is used.
+///
+/// # Safety
+///
+/// `config` must be valid for reads and writes, and `name` must be a
+/// NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chunk_config_delimiter_class(
+    config: *mut ChunkConfig,
+    name: *const c_char,
+) -> ChunkStatus {
+    if name.is_null() {
+        return ChunkStatus::NullPointer;
+    }
+    // SAFETY: per the contract, `config` is NULL or valid
+    let Some(config) = (unsafe { config.as_mut() }) else {
+        return ChunkStatus::NullPointer;
+    };
+    // SAFETY: `name` is non-null and NUL-terminated per the contract
+    if unsafe { delimiter_class(name) }.is_none() {
+        return ChunkStatus::UnknownDelimiters;
+    }
+    config.delimiter_class = name;
+    ChunkStatus::Ok
+}
+
+/// Chunk `text`, writing the offsets to `out`.
+///
+/// `config` may be NULL for the defaults. On error `out` is set to an empty
+/// result, which is also safe to pass to [`chunk_offsets_free`].
+///
+/// # Safety
+///
+/// `text` must point to `len` readable bytes (it may be NULL if `len` is 0),
+/// `config` must be NULL or point to a valid [`ChunkConfig`] whose buffers
+/// are readable for their lengths, and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chunk_offsets(
+    text: *const u8,
+    len: usize,
+    config: *const ChunkConfig,
+    out: *mut ChunkOffsets,
+) -> ChunkStatus {
+    if out.is_null() {
+        return ChunkStatus::NullPointer;
+    }
+    // SAFETY: `out` is non-null and valid for writes per the contract
+    unsafe { out.write(ChunkOffsets::EMPTY) };
+
+    let config = match unsafe { config.as_ref() } {
+        Some(config) => *config,
+        None => chunk_config_default(),
+    };
+    // SAFETY: the caller guarantees each buffer is readable for its length
+    let (Some(text), Some(delimiters), Some(pattern)) = (unsafe {
+        (
+            borrow(text, len),
+            borrow(config.delimiters, config.delimiters_len),
+            borrow(config.pattern, config.pattern_len),
+        )
+    }) else {
+        return ChunkStatus::NullPointer;
+    };
+
+    let mut chunker_config = ChunkerConfig::new()
+        .size(config.size)
+        .prefix(config.prefix)
+        .consecutive(config.consecutive)
+        .forward_fallback(config.forward_fallback)
+        .overlap(config.overlap)
+        .utf8_safe(config.utf8_safe)
+        .min_size(config.min_size)
+        // 0 means no cap
+        .max_size((config.max_size > 0).then_some(config.max_size));
+    if !config.pattern.is_null() {
+        chunker_config = chunker_config.pattern(pattern);
+    } else if !config.delimiters.is_null() {
+        chunker_config = chunker_config.delimiters(delimiters);
+    }
+    if !config.delimiter_class.is_null() {
+        // SAFETY: the caller guarantees the name is NUL-terminated
+        let Some(class) = (unsafe { delimiter_class(config.delimiter_class) }) else {
+            return ChunkStatus::UnknownDelimiters;
+        };
+        chunker_config = chunker_config.delimiter_class(class);
+    }
+
+    let spans: Box<[ChunkSpan]> = chunker_config
+        .chunker(text)
+        .collect_offsets()
+        .into_iter()
+        .map(|(start, end)| ChunkSpan { start, end })
+        .collect();
+    if !spans.is_empty() {
+        let len = spans.len();
+        let spans = Box::into_raw(spans) as *mut ChunkSpan;
+        // SAFETY: checked non-null above
+        unsafe { out.write(ChunkOffsets { spans, len }) };
+    }
+    ChunkStatus::Ok
+}
+
+/// Free offsets returned by [`chunk_offsets`] and reset them to empty.
+///
+/// Passing NULL, or offsets that are already empty, is a no-op.
+///
+/// # Safety
+///
+/// `offsets` must be NULL or point to a [`ChunkOffsets`] filled in by
+/// [`chunk_offsets`] and not modified or freed since.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chunk_offsets_free(offsets: *mut ChunkOffsets) {
+    // SAFETY: per the contract, `offsets` is NULL or valid
+    let Some(offsets) = (unsafe { offsets.as_mut() }) else {
+        return;
+    };
+    if !offsets.spans.is_null() {
+        // SAFETY: `spans` came from `Box::into_raw` on a slice of `len` spans
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(offsets.spans, offsets.len)) });
+    }
+    *offsets = ChunkOffsets::EMPTY;
+}
+
+/// The library version, as a static NUL-terminated string.
+#[unsafe(no_mangle)]
+pub extern "C" fn chunk_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Parse the delimiter class named by the NUL-terminated string at `name`.
+///
+/// # Safety
+///
+/// `name` must be non-null and NUL-terminated.
+unsafe fn delimiter_class(name: *const c_char) -> Option<Delimiters> {
+    // SAFETY: forwarded from the caller
+    let name = unsafe { CStr::from_ptr(name) };
+    name.to_str().ok()?.parse().ok()
+}
+
+/// Borrow `len` bytes at `data`, treating NULL as empty only when `len` is 0.
+///
+/// # Safety
+///
+/// A non-null `data` must be readable for `len` bytes.
+unsafe fn borrow<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    match (data.is_null(), len) {
+        (true, 0) => Some(&[]),
+        (true, _) => None,
+        // SAFETY: forwarded from the caller
+        (false, _) => Some(unsafe { slice::from_raw_parts(data, len) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chunk::chunk;
+
+    fn offsets(text: &[u8], config: Option<&ChunkConfig>) -> Vec<(usize, usize)> {
+        let mut out = ChunkOffsets::EMPTY;
+        let config = config.map_or(ptr::null(), |c| c as *const _);
+        let status = unsafe { chunk_offsets(text.as_ptr(), text.len(), config, &mut out) };
+        assert_eq!(status, ChunkStatus::Ok);
+        let spans = match out.spans.is_null() {
+            true => Vec::new(),
+            false => unsafe { slice::from_raw_parts(out.spans, out.len) }
+                .iter()
+                .map(|s| (s.start, s.end))
+                .collect(),
+        };
+        unsafe { chunk_offsets_free(&mut out) };
+        assert!(out.spans.is_null());
+        spans
+    }
+
+    #[test]
+    fn test_c_matches_rust_api() {
+        let text = b"Hello. World. Test.";
+        let config = ChunkConfig {
+            size: 10,
+            delimiters: b".".as_ptr(),
+            delimiters_len: 1,
+            overlap: 2,
+            ..chunk_config_default()
+        };
+        let expected = chunk(text)
+            .size(10)
+            .delimiters(b".")
+            .overlap(2)
+            .collect_offsets();
+        assert_eq!(offsets(text, Some(&config)), expected);
+        assert_eq!(offsets(text, None), chunk(text).collect_offsets());
+    }
+
+    #[test]
+    fn test_c_pattern() {
+        let text = "a▁b▁c".as_bytes();
+        let pattern = "▁".as_bytes();
+        let config = ChunkConfig {
+            size: 7,
+            pattern: pattern.as_ptr(),
+            pattern_len: pattern.len(),
+            prefix: true,
+            ..chunk_config_default()
+        };
+        assert_eq!(offsets(text, Some(&config)), vec![(0, 1), (1, 5), (5, 9)]);
+    }
+
+    #[test]
+    fn test_c_delimiter_class() {
+        let mut config = ChunkConfig {
+            size: 10,
+            ..chunk_config_default()
+        };
+        let status = unsafe { chunk_config_delimiter_class(&mut config, c"whitespace".as_ptr()) };
+        assert_eq!(status, ChunkStatus::Ok);
+        let text = b"Split these words";
+        assert_eq!(
+            offsets(text, Some(&config)),
+            vec![(0, 6), (6, 12), (12, 17)]
+        );
+
+        let status =
+            unsafe { chunk_config_delimiter_class(&mut config, c"markdown_headings".as_ptr()) };
+        assert_eq!(status, ChunkStatus::Ok);
+        config.size = 20;
+        let text = b"# One\nIntro.\n## Two\nMore text.\n# Three\n";
+        assert_eq!(
+            offsets(text, Some(&config)),
+            vec![(0, 12), (12, 30), (30, 39)]
+        );
+
+        let status = unsafe { chunk_config_delimiter_class(&mut config, c"cjk".as_ptr()) };
+        assert_eq!(status, ChunkStatus::Ok);
+        let text = "今日は晴れ。明日は雨！".as_bytes();
+        assert_eq!(offsets(text, Some(&config)), vec![(0, 18), (18, 33)]);
+
+        let status = unsafe { chunk_config_delimiter_class(&mut config, c"words".as_ptr()) };
+        assert_eq!(status, ChunkStatus::UnknownDelimiters);
+        // Set directly, an unknown name fails the call instead
+        config.delimiter_class = c"words".as_ptr();
+        let mut out = ChunkOffsets::EMPTY;
+        let status = unsafe { chunk_offsets(text.as_ptr(), text.len(), &config, &mut out) };
+        assert_eq!(status, ChunkStatus::UnknownDelimiters);
+        assert!(out.spans.is_null());
+        let status = unsafe { chunk_config_delimiter_class(ptr::null_mut(), c"newlines".as_ptr()) };
+        assert_eq!(status, ChunkStatus::NullPointer);
+    }
+
+    #[test]
+    fn test_c_null_pointers() {
+        assert!(offsets(&[], None).is_empty());
+        unsafe {
+            let mut out = ChunkOffsets::EMPTY;
+            assert_eq!(
+                chunk_offsets(ptr::null(), 3, ptr::null(), &mut out),
+                ChunkStatus::NullPointer
+            );
+            assert!(out.spans.is_null());
+            assert_eq!(
+                chunk_offsets(b"x".as_ptr(), 1, ptr::null(), ptr::null_mut()),
+                ChunkStatus::NullPointer
+            );
+            chunk_offsets_free(ptr::null_mut());
+            chunk_offsets_free(&mut out);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+/* Build the library first: cargo build --release -p chunk-c */
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "chunk.h"
+
+static void test_defaults(void) {
+    const char *text = "Hello. World. Test.";
+    ChunkOffsets offsets;
+    assert(chunk_offsets((const uint8_t *)text, strlen(text), NULL, &offsets) == CHUNK_STATUS_OK);
+    assert(offsets.len == 1);
+    assert(offsets.spans[0].start == 0 && offsets.spans[0].end == strlen(text));
+    chunk_offsets_free(&offsets);
+    assert(offsets.spans == NULL && offsets.len == 0);
+}
+
+static void test_config(void) {
+    const char *text = "Hello. World. Test.";
+    ChunkConfig config = chunk_config_default();
+    config.size = 10;
+    config.delimiters = (const uint8_t *)".";
+    config.delimiters_len = 1;
+
+    ChunkOffsets offsets;
+    assert(chunk_offsets((const uint8_t *)text, strlen(text), &config, &offsets) == CHUNK_STATUS_OK);
+    assert(offsets.len == 3);
+    const size_t expected[3][2] = {{0, 6}, {6, 13}, {13, 19}};
+    for (size_t i = 0; i < offsets.len; i++) {
+        assert(offsets.spans[i].start == expected[i][0]);
+        assert(offsets.spans[i].end == expected[i][1]);
+    }
+    chunk_offsets_free(&offsets);
+}
+
+static void test_delimiter_class(void) {
+    const char *text = "Split these words";
+    ChunkConfig config = chunk_config_default();
+    config.size = 10;
+    assert(chunk_config_delimiter_class(&config, "whitespace") == CHUNK_STATUS_OK);
+
+    ChunkOffsets offsets;
+    assert(chunk_offsets((const uint8_t *)text, strlen(text), &config, &offsets) == CHUNK_STATUS_OK);
+    assert(offsets.len == 3);
+    assert(offsets.spans[1].start == 6 && offsets.spans[1].end == 12);
+    chunk_offsets_free(&offsets);
+
+    const char *cjk = "今日は晴れ。明日は雨！";
+    config.size = 20;
+    assert(chunk_config_delimiter_class(&config, "cjk") == CHUNK_STATUS_OK);
+    assert(chunk_offsets((const uint8_t *)cjk, strlen(cjk), &config, &offsets) == CHUNK_STATUS_OK);
+    assert(offsets.len == 2);
+    assert(offsets.spans[0].end == 18);
+    chunk_offsets_free(&offsets);
+
+    assert(chunk_config_delimiter_class(&config, "words") == CHUNK_STATUS_UNKNOWN_DELIMITERS);
+}
+
+static void test_errors(void) {
+    ChunkOffsets offsets;
+    assert(chunk_offsets(NULL, 5, NULL, &offsets) == CHUNK_STATUS_NULL_POINTER);
+    assert(offsets.spans == NULL);
+    chunk_offsets_free(&offsets);
+    chunk_offsets_free(NULL);
+
+    assert(chunk_offsets(NULL, 0, NULL, &offsets) == CHUNK_STATUS_OK);
+    assert(offsets.len == 0);
+}
+
+int main(void) {
+    test_defaults();
+    test_config();
+    test_delimiter_class();
+    test_errors();
+    printf("chunk %s: all tests passed\n", chunk_version());
+    return 0;
+}
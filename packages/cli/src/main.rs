@@ -0,0 +1,1125 @@
+//! `chunk`: split a file or stdin into chunks from the command line.
+//!
+//! ```text
+//! chunk corpus.txt --size 1024 --delimiters '\n.?' > chunks.ndjson
+//! cat corpus.txt | chunk --offsets-only --format json
+//! chunk corpus.txt --config profiles.toml --profile rag-default
+//! chunk stats corpus.txt --size 1024
+//! chunk pdf report.pdf --size 1024 > chunks.ndjson
+//! chunk dir ./docs --glob '**/*.md' --size 1024 > chunks.ndjson
+//! ```
+
+use std::borrow::Cow;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use chunk::{
+    ChunkPipeline, ChunkStats, ChunkerConfig, ConfigError, Decompressed, Delimiters, LineCounter,
+    PagedText, Position, Progress, chunk_archive, chunk_dir, detect_language,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+/// Split text into chunks at delimiter boundaries.
+#[derive(Debug, Parser)]
+#[command(name = "chunk", version, args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    options: Options,
+
+    #[command(flatten)]
+    output: Output,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print chunk size statistics instead of the chunks.
+    Stats {
+        #[command(flatten)]
+        options: Options,
+
+        /// Print the statistics as a JSON object.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Chunk the text of a PDF, with the first and last page of every chunk.
+    ///
+    /// The text of each page is read from the PDF's page tree; pages with no
+    /// text (e.g. scans) are empty.
+    Pdf {
+        #[command(flatten)]
+        options: Options,
+
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Chunk every matching file under a directory (the input, default `.`),
+    /// with each record's path first.
+    ///
+    /// Writes ndjson or jsonl. Gzip files are decompressed. A file that
+    /// can't be read is reported and skipped, and the exit status is then an
+    /// error.
+    Dir {
+        #[command(flatten)]
+        options: Options,
+
+        #[command(flatten)]
+        output: Output,
+
+        /// Files to chunk, relative to the directory; `**` spans directories.
+        #[arg(short, long, default_value = "**/*")]
+        glob: String,
+    },
+    /// Chunk every text file in a tar, tar.gz or zip archive (the input),
+    /// with each record's entry name as its path.
+    ///
+    /// Writes ndjson or jsonl. Binary files are skipped.
+    Archive {
+        #[command(flatten)]
+        options: Options,
+
+        #[command(flatten)]
+        output: Output,
+    },
+}
+
+/// How chunks are written, shared by the commands that write them.
+#[derive(Debug, clap::Args)]
+struct Output {
+    /// Output format.
+    #[arg(short, long, value_enum, default_value_t = Format::Ndjson)]
+    format: Format,
+
+    /// Emit only start/end offsets, not the chunk text.
+    #[arg(long)]
+    offsets_only: bool,
+
+    /// Add the line and column (1-based) where each chunk starts.
+    #[arg(long)]
+    positions: bool,
+
+    /// Add the detected language of each chunk, as an ISO 639-3 code
+    /// (`und` when there's no text to tell by).
+    #[arg(long)]
+    lang: bool,
+
+    /// Report how much of the input has been chunked on stderr.
+    #[arg(long)]
+    progress: bool,
+
+    /// Leave out chunks in which this regex matches, e.g. a cookie notice or
+    /// a navigation menu; may be given more than once.
+    #[arg(long, value_name = "REGEX")]
+    drop_matching: Vec<String>,
+
+    /// Leave out chunks with less than this share (0 to 1) of letters and
+    /// digits among their characters, e.g. 0.2 for rules and link lists.
+    #[arg(long, value_name = "RATIO")]
+    min_density: Option<f64>,
+}
+
+impl Output {
+    /// The filters chunks have to pass to be written, or the error for a
+    /// `--drop-matching` regex that doesn't compile.
+    fn filters(&self) -> Result<ChunkPipeline, String> {
+        let mut filters = ChunkPipeline::new();
+        if let Some(ratio) = self.min_density {
+            filters = filters.min_density(ratio);
+        }
+        for pattern in &self.drop_matching {
+            filters = filters
+                .drop_matching(pattern)
+                .map_err(|err| format!("--drop-matching: {err}"))?;
+        }
+        Ok(filters)
+    }
+}
+
+/// Input and chunker options, shared by all commands.
+#[derive(Debug, clap::Args)]
+struct Options {
+    /// File to read; reads stdin when omitted or `-`. Gzip and zstd input
+    /// is decompressed.
+    input: Option<PathBuf>,
+
+    /// Read options from a TOML (or .json) config file; flags override it.
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Use the named profile (a `[table]`) from the config file.
+    #[arg(long, requires = "config")]
+    profile: Option<String>,
+
+    /// Target chunk size in bytes [default: 4096].
+    #[arg(short, long)]
+    size: Option<usize>,
+
+    /// Single-byte delimiters; escapes like \n, \t and \\ are understood.
+    /// Or a named class: sentences, whitespace, newlines,
+    /// markdown_headings, cjk or multilingual.
+    #[arg(short, long)]
+    delimiters: Option<String>,
+
+    /// Multi-byte pattern to split at instead of delimiters (same escapes).
+    #[arg(short, long, conflicts_with = "delimiters")]
+    pattern: Option<String>,
+
+    /// Put the delimiter at the start of the next chunk.
+    #[arg(long)]
+    prefix: bool,
+
+    /// Leave the delimiter out of both chunks.
+    #[arg(long, conflicts_with = "prefix")]
+    drop_delimiters: bool,
+
+    /// Split at the start of runs of the same delimiter.
+    #[arg(long)]
+    consecutive: bool,
+
+    /// Search forward when no delimiter is found before the target size.
+    #[arg(long)]
+    forward_fallback: bool,
+
+    /// Prefer earlier delimiters over later ones, in the order given.
+    #[arg(long)]
+    delimiter_priority: bool,
+
+    /// Split at the boundary nearest the target size, up to this many bytes
+    /// either side of it [default: 0].
+    #[arg(long)]
+    slack: Option<usize>,
+
+    /// Even out chunk sizes instead of filling chunks greedily.
+    #[arg(long)]
+    balanced: bool,
+
+    /// Group up to this many complete lines per chunk, never splitting a
+    /// line; the size is still a cap.
+    #[arg(long)]
+    lines: Option<usize>,
+
+    /// Bytes of trailing context carried into the next chunk [default: 0].
+    #[arg(short, long)]
+    overlap: Option<usize>,
+
+    /// Merge a trailing chunk smaller than this into the previous one.
+    #[arg(long)]
+    min_size: Option<usize>,
+
+    /// Strict cap on chunk length in bytes.
+    #[arg(long)]
+    max_size: Option<usize>,
+
+    /// Allow splits inside multi-byte UTF-8 characters.
+    #[arg(long)]
+    no_utf8_safe: bool,
+
+    /// Strip leading and trailing whitespace from chunks.
+    #[arg(long)]
+    trim: bool,
+
+    /// Leave out empty and whitespace-only chunks.
+    #[arg(long)]
+    skip_empty: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// One JSON object per line: {"start":..,"end":..,"text":..}
+    Ndjson,
+    /// NDJSON with each chunk's index first:
+    /// {"index":..,"start":..,"end":..,"text":..}
+    Jsonl,
+    /// A single JSON array of those objects.
+    Json,
+    /// Raw chunks, each followed by a NUL byte ("start\tend" lines with
+    /// --offsets-only, plus "\tfirst_page\tlast_page" for PDFs and "\tlang"
+    /// with --lang).
+    Text,
+}
+
+/// One output record. Invalid UTF-8 in `text` is replaced with U+FFFD.
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    start: usize,
+    end: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_page: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_page: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lang: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<Cow<'a, str>>,
+}
+
+/// Expand `\n`, `\r`, `\t`, `\0` and `\\` in a delimiter argument.
+fn unescape(arg: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(arg.len());
+    let mut bytes = arg.bytes();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            out.push(b);
+            continue;
+        }
+        match bytes.next() {
+            Some(b'n') => out.push(b'\n'),
+            Some(b'r') => out.push(b'\r'),
+            Some(b't') => out.push(b'\t'),
+            Some(b'0') => out.push(0),
+            Some(b'\\') => out.push(b'\\'),
+            Some(other) => out.extend([b'\\', other]),
+            None => out.push(b'\\'),
+        }
+    }
+    out
+}
+
+/// The chunker options: the config file (if any), then the flags in `args`.
+///
+/// Without a config file, splits are UTF-8 safe unless `--no-utf8-safe`.
+fn config(args: &Options) -> Result<ChunkerConfig, ConfigError> {
+    let mut config = match &args.config {
+        Some(path) => ChunkerConfig::from_file(path, args.profile.as_deref())?,
+        None => ChunkerConfig::new().utf8_safe(true),
+    };
+    if let Some(size) = args.size {
+        config = config.size(size);
+    }
+    if let Some(delimiters) = &args.delimiters {
+        config = match delimiters.parse::<Delimiters>() {
+            Ok(class) => config.delimiter_class(class),
+            Err(_) => config.delimiters(unescape(delimiters)),
+        };
+    }
+    if let Some(pattern) = &args.pattern {
+        config = config.pattern(unescape(pattern));
+    }
+    config.prefix |= args.prefix;
+    config.drop_delimiters |= args.drop_delimiters;
+    config.consecutive |= args.consecutive;
+    config.forward_fallback |= args.forward_fallback;
+    config.delimiter_priority |= args.delimiter_priority;
+    config.balanced |= args.balanced;
+    config.utf8_safe &= !args.no_utf8_safe;
+    config.trim |= args.trim;
+    config.skip_empty |= args.skip_empty;
+    if let Some(slack) = args.slack {
+        config = config.slack(slack);
+    }
+    if let Some(overlap) = args.overlap {
+        config = config.overlap(overlap);
+    }
+    if let Some(min_size) = args.min_size {
+        config = config.min_size(min_size);
+    }
+    if args.max_size.is_some() {
+        config = config.max_size(args.max_size);
+    }
+    if args.lines.is_some() {
+        config = config.lines_per_chunk(args.lines);
+    }
+    Ok(config)
+}
+
+/// Write the chunks of `text` that pass `filters` to `out` in the requested
+/// format, with their pages when `text` is the text of `pages` and the file's
+/// `path` when chunking a directory.
+fn write_chunks(
+    output: &Output,
+    config: &ChunkerConfig,
+    filters: &ChunkPipeline,
+    text: &[u8],
+    pages: Option<&PagedText>,
+    path: Option<&str>,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    if output.format == Format::Jsonl
+        && !output.offsets_only
+        && !output.positions
+        && !output.lang
+        && pages.is_none()
+        && path.is_none()
+        && !output.progress
+        && filters.is_empty()
+    {
+        // Streamed as the chunks are found
+        return config.chunker(text).write_jsonl(out).map(drop);
+    }
+    let mut offsets: Vec<(usize, usize)> = if output.progress {
+        let name = path.unwrap_or("input");
+        config
+            .chunker(text)
+            .iter_chunks()
+            .with_progress(PROGRESS_EVERY, |progress| {
+                eprint!("{}", progress_line(name, progress, text.len()));
+            })
+            .map(|chunk| (chunk.start, chunk.end))
+            .collect()
+    } else {
+        config.chunker(text).collect_offsets()
+    };
+    if !filters.is_empty() {
+        offsets.retain(|&(start, end)| filters.apply(&text[start..end]).is_some());
+    }
+    let mut lines = LineCounter::new(text);
+    let positions: Vec<Option<Position>> = offsets
+        .iter()
+        .map(|&(start, _)| output.positions.then(|| lines.position(start)))
+        .collect();
+    let pages_of = |start, end| pages.map(|doc| doc.page_chunk(start, end));
+    let lang_of = |start, end| {
+        output
+            .lang
+            .then(|| detect_language(&text[start..end]).unwrap_or("und"))
+    };
+    let record =
+        |(index, (&(start, end), position)): (usize, (&(usize, usize), &Option<Position>))| {
+            Record {
+                path,
+                index: (output.format == Format::Jsonl).then_some(index),
+                start,
+                end,
+                line: position.map(|p| p.line),
+                column: position.map(|p| p.column),
+                first_page: pages_of(start, end).map(|chunk| chunk.first_page),
+                last_page: pages_of(start, end).map(|chunk| chunk.last_page),
+                lang: lang_of(start, end),
+                text: (!output.offsets_only).then(|| String::from_utf8_lossy(&text[start..end])),
+            }
+        };
+
+    match output.format {
+        Format::Ndjson | Format::Jsonl => {
+            for span in offsets.iter().zip(&positions).enumerate() {
+                serde_json::to_writer(&mut *out, &record(span))?;
+                out.write_all(b"\n")?;
+            }
+        }
+        Format::Json => {
+            let records: Vec<_> = offsets
+                .iter()
+                .zip(&positions)
+                .enumerate()
+                .map(record)
+                .collect();
+            serde_json::to_writer(&mut *out, &records)?;
+            out.write_all(b"\n")?;
+        }
+        Format::Text if output.offsets_only => {
+            for (&(start, end), position) in offsets.iter().zip(&positions) {
+                write!(out, "{start}\t{end}")?;
+                if let Some(position) = position {
+                    write!(out, "\t{}\t{}", position.line, position.column)?;
+                }
+                if let Some(chunk) = pages_of(start, end) {
+                    write!(out, "\t{}\t{}", chunk.first_page, chunk.last_page)?;
+                }
+                if let Some(lang) = lang_of(start, end) {
+                    write!(out, "\t{lang}")?;
+                }
+                writeln!(out)?;
+            }
+        }
+        Format::Text => {
+            for &(start, end) in &offsets {
+                out.write_all(&text[start..end])?;
+                out.write_all(b"\0")?;
+            }
+        }
+    }
+    out.flush()
+}
+
+/// Chunks between progress reports with `--progress`.
+const PROGRESS_EVERY: usize = 10_000;
+
+/// A progress report on `name`, of `total` bytes, redrawn in place with a
+/// carriage return and ended with a newline once all of it is chunked.
+fn progress_line(name: &str, progress: Progress, total: usize) -> String {
+    let percent = match total {
+        0 => 100,
+        _ => progress.bytes * 100 / total,
+    };
+    let end = if progress.bytes >= total { "\n" } else { "" };
+    format!(
+        "\rchunk: {name}: {percent}% ({} chunks){end}",
+        progress.chunks
+    )
+}
+
+/// Write the chunks of every file under `root` matching `glob`, reporting
+/// files that can't be read. Returns whether every file was chunked.
+fn write_dir(
+    output: &Output,
+    config: &ChunkerConfig,
+    filters: &ChunkPipeline,
+    root: &Path,
+    glob: &str,
+    out: &mut impl Write,
+) -> io::Result<bool> {
+    let files = chunk_dir(root, glob, config).map_err(io::Error::other)?;
+    let mut ok = true;
+    for file in files {
+        match file {
+            Ok(file) => {
+                let path = file.path.to_string_lossy();
+                write_chunks(output, config, filters, &file.text, None, Some(&path), out)?;
+            }
+            Err(err) => {
+                eprintln!("chunk: {err}");
+                ok = false;
+            }
+        }
+    }
+    Ok(ok)
+}
+
+/// Write the chunks of every text file in the archive at `path`, with the
+/// entry names as paths. Returns whether every entry could be read.
+fn write_archive(
+    output: &Output,
+    config: &ChunkerConfig,
+    filters: &ChunkPipeline,
+    path: &Path,
+    out: &mut impl Write,
+) -> io::Result<bool> {
+    let entries = chunk_archive(path, config)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))?;
+    let mut ok = true;
+    for entry in entries {
+        match entry {
+            Ok(entry) => {
+                write_chunks(
+                    output,
+                    config,
+                    filters,
+                    &entry.text,
+                    None,
+                    Some(&entry.name),
+                    out,
+                )?;
+            }
+            Err(err) => {
+                eprintln!("chunk: {}: {err}", path.display());
+                ok = false;
+            }
+        }
+    }
+    Ok(ok)
+}
+
+/// Write `stats` as `name: value` lines, or as JSON.
+fn write_stats(stats: &ChunkStats, json: bool, out: &mut impl Write) -> io::Result<()> {
+    if json {
+        serde_json::to_writer(&mut *out, stats)?;
+        out.write_all(b"\n")?;
+    } else {
+        writeln!(out, "chunks: {}", stats.count)?;
+        writeln!(out, "min: {}", stats.min)?;
+        writeln!(out, "max: {}", stats.max)?;
+        writeln!(out, "mean: {:.1}", stats.mean)?;
+        writeln!(out, "median: {:.1}", stats.median)?;
+        writeln!(out, "forced splits: {}", stats.forced_splits)?;
+    }
+    out.flush()
+}
+
+/// Read the file or stdin, decompressing gzip and zstd input.
+fn read_input(input: Option<&PathBuf>) -> io::Result<Vec<u8>> {
+    let mut text = Vec::new();
+    match input {
+        Some(path) if path.as_os_str() != "-" => {
+            Decompressed::open(path)?.read_to_end(&mut text)?
+        }
+        _ => Decompressed::new(io::stdin().lock())?.read_to_end(&mut text)?,
+    };
+    Ok(text)
+}
+
+/// Read the text of a PDF from the file or stdin, page by page.
+fn read_pdf(input: Option<&PathBuf>) -> io::Result<PagedText> {
+    PagedText::from_pdf(&read_input(input)?)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let (options, output) = match &args.command {
+        Some(Command::Stats { options, .. }) => (options, None),
+        Some(
+            Command::Pdf { options, output }
+            | Command::Dir {
+                options, output, ..
+            }
+            | Command::Archive { options, output },
+        ) => (options, Some(output)),
+        None => (&args.options, Some(&args.output)),
+    };
+    let config = match config(options) {
+        Ok(config) => config,
+        // Only loading the config file can fail
+        Err(err) => {
+            let path = options.config.as_deref().unwrap_or(Path::new("config"));
+            eprintln!("chunk: {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(err) = config.validate() {
+        eprintln!("chunk: {err}");
+        return ExitCode::FAILURE;
+    }
+    let filters = match output.map(Output::filters).transpose() {
+        Ok(filters) => filters.unwrap_or_default(),
+        Err(err) => {
+            eprintln!("chunk: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(Command::Dir { output, .. } | Command::Archive { output, .. }) = &args.command
+        && !matches!(output.format, Format::Ndjson | Format::Jsonl)
+    {
+        eprintln!("chunk: dir and archive write ndjson or jsonl");
+        return ExitCode::FAILURE;
+    }
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut ok = true;
+    let result = match &args.command {
+        Some(Command::Stats { json, .. }) => read_input(options.input.as_ref())
+            .and_then(|text| write_stats(&config.chunker(&text).stats(), *json, &mut out)),
+        Some(Command::Pdf { output, .. }) => read_pdf(options.input.as_ref()).and_then(|doc| {
+            write_chunks(
+                output,
+                &config,
+                &filters,
+                doc.text(),
+                Some(&doc),
+                None,
+                &mut out,
+            )
+        }),
+        Some(Command::Dir { output, glob, .. }) => {
+            let root = options.input.as_deref().unwrap_or(Path::new("."));
+            write_dir(output, &config, &filters, root, glob, &mut out).map(|all| ok = all)
+        }
+        Some(Command::Archive { output, .. }) => match &options.input {
+            Some(path) if path.as_os_str() != "-" => {
+                write_archive(output, &config, &filters, path, &mut out).map(|all| ok = all)
+            }
+            _ => Err(io::Error::other("archive reads a file, not stdin")),
+        },
+        None => read_input(options.input.as_ref()).and_then(|text| {
+            write_chunks(&args.output, &config, &filters, &text, None, None, &mut out)
+        }),
+    };
+    match result {
+        Ok(()) if ok => ExitCode::SUCCESS,
+        Ok(()) => ExitCode::FAILURE,
+        // Downstream closed the pipe (e.g. `| head`): not an error
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("chunk: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(argv: &[&str], text: &[u8]) -> String {
+        let args =
+            Args::try_parse_from(std::iter::once("chunk").chain(argv.iter().copied())).unwrap();
+        let mut out = Vec::new();
+        let config = config(&args.options).unwrap();
+        let filters = args.output.filters().unwrap();
+        write_chunks(&args.output, &config, &filters, text, None, None, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_cli_unescape() {
+        assert_eq!(unescape(r"\n.?"), b"\n.?");
+        assert_eq!(unescape(r"\t\\\0"), b"\t\\\0");
+        assert_eq!(unescape(r"a\qb\"), b"a\\qb\\");
+    }
+
+    #[test]
+    fn test_cli_ndjson() {
+        let out = run(
+            &["--size", "10", "--delimiters", "."],
+            b"Hello. World. Test.",
+        );
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], r#"{"start":0,"end":6,"text":"Hello."}"#);
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_cli_delimiter_class() {
+        let out = run(
+            &[
+                "-s",
+                "12",
+                "-d",
+                "whitespace",
+                "-f",
+                "text",
+                "--offsets-only",
+            ],
+            b"Split these words",
+        );
+        assert_eq!(out, "0\t12\n12\t17\n");
+    }
+
+    #[test]
+    fn test_cli_jsonl() {
+        let text = b"Hello. \"World\".\tTest.";
+        let out = run(&["-s", "10", "-d", ".", "-f", "jsonl"], text);
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], r#"{"index":0,"start":0,"end":6,"text":"Hello."}"#);
+        assert_eq!(
+            lines[1],
+            r#"{"index":1,"start":6,"end":15,"text":" \"World\"."}"#
+        );
+        // The streamed records match the ones built alongside other fields
+        let ndjson = run(&["-s", "10", "-d", ".", "-f", "ndjson"], text);
+        for (index, (jsonl, ndjson)) in out.lines().zip(ndjson.lines()).enumerate() {
+            let prefix = format!("{{\"index\":{index},");
+            assert_eq!(jsonl.replacen(&prefix, "{", 1), ndjson);
+        }
+        let out = run(
+            &["-s", "10", "-d", ".", "-f", "jsonl", "--offsets-only"],
+            text,
+        );
+        assert!(out.starts_with("{\"index\":0,\"start\":0,\"end\":6}\n"));
+    }
+
+    #[test]
+    fn test_cli_dir() {
+        let dir = std::env::temp_dir().join(format!("chunk-cli-dir-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("guide")).unwrap();
+        std::fs::write(dir.join("a.md"), "One. Two.").unwrap();
+        std::fs::write(dir.join("guide/b.md"), "Six.").unwrap();
+        std::fs::write(dir.join("skip.txt"), "Skip.").unwrap();
+        let root = dir.to_str().unwrap();
+        let args = Args::try_parse_from([
+            "chunk", "dir", root, "--glob", "**/*.md", "-s", "5", "-d", ".", "-f", "jsonl",
+        ])
+        .unwrap();
+        let Some(Command::Dir {
+            options,
+            output,
+            glob,
+        }) = &args.command
+        else {
+            panic!("not the dir command");
+        };
+        let mut out = Vec::new();
+        let config = config(options).unwrap();
+        assert!(write_dir(output, &config, &ChunkPipeline::new(), &dir, glob, &mut out).unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        let a = dir.join("a.md");
+        let path = serde_json::to_string(a.to_str().unwrap()).unwrap();
+        assert_eq!(
+            lines[0],
+            format!(r#"{{"path":{path},"index":0,"start":0,"end":4,"text":"One."}}"#)
+        );
+        assert_eq!(lines.len(), 3);
+        assert!(lines[2].contains(r#""index":0"#) && lines[2].contains("b.md"));
+    }
+
+    #[test]
+    fn test_cli_progress() {
+        let progress = Progress {
+            bytes: 512,
+            chunks: 3,
+        };
+        assert_eq!(
+            progress_line("a.txt", progress, 2048),
+            "\rchunk: a.txt: 25% (3 chunks)"
+        );
+        assert_eq!(
+            progress_line("a.txt", progress, 512),
+            "\rchunk: a.txt: 100% (3 chunks)\n"
+        );
+        // Reporting doesn't change the chunks
+        let text = b"Hello. World. Test.";
+        let argv = ["-s", "10", "-d", ".", "-f", "jsonl"];
+        let with_progress = run(&[&argv[..], &["--progress"]].concat(), text);
+        assert_eq!(with_progress, run(&argv, text));
+    }
+
+    #[test]
+    fn test_cli_archive() {
+        // A ustar header: name, mode, size, checksum, type and magic
+        let mut tar = vec![0; 512];
+        tar[..9].copy_from_slice(b"wiki/a.md");
+        tar[100..107].copy_from_slice(b"0000644");
+        tar[124..135].copy_from_slice(b"00000000011");
+        tar[156] = b'0';
+        tar[257..263].copy_from_slice(b"ustar\0");
+        tar[148..156].fill(b' ');
+        let sum: u32 = tar.iter().map(|&b| u32::from(b)).sum();
+        tar[148..155].copy_from_slice(format!("{sum:06o}\0").as_bytes());
+        tar.extend(b"One. Two.\n");
+        tar.resize(3 * 512, 0);
+        let path = std::env::temp_dir().join(format!("chunk-cli-{}.tar", std::process::id()));
+        std::fs::write(&path, tar).unwrap();
+
+        let args = Args::try_parse_from(["chunk", "archive", "-s", "5", "-d", "."]).unwrap();
+        let Some(Command::Archive { options, output }) = &args.command else {
+            panic!("not the archive command");
+        };
+        let mut out = Vec::new();
+        let config = config(options).unwrap();
+        assert!(write_archive(output, &config, &ChunkPipeline::new(), &path, &mut out).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"{"path":"wiki/a.md","start":0,"end":4,"text":"One."}"#
+        );
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_cli_compressed_input() {
+        use std::io::Write;
+
+        let text = b"Hello. World. Test.";
+        let dir = std::env::temp_dir().join(format!("chunk-cli-compressed-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gz.write_all(text).unwrap();
+        let path = dir.join("doc.txt.gz");
+        std::fs::write(&path, gz.finish().unwrap()).unwrap();
+        assert_eq!(read_input(Some(&path)).unwrap(), text);
+
+        let compressed = ruzstd::encoding::compress_to_vec(
+            &text[..],
+            ruzstd::encoding::CompressionLevel::Fastest,
+        );
+        let path = dir.join("doc.txt.zst");
+        std::fs::write(&path, &compressed).unwrap();
+        assert_eq!(read_input(Some(&path)).unwrap(), text);
+        std::fs::write(&path, &compressed[..6]).unwrap();
+        assert!(read_input(Some(&path)).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cli_offsets_only_json() {
+        let out = run(
+            &["-s", "10", "-d", ".", "--offsets-only", "--format", "json"],
+            b"Hello. World. Test.",
+        );
+        assert_eq!(
+            out,
+            "[{\"start\":0,\"end\":6},{\"start\":6,\"end\":13},{\"start\":13,\"end\":19}]\n"
+        );
+    }
+
+    #[test]
+    fn test_cli_text_format() {
+        let out = run(&["-s", "10", "-d", ".", "-f", "text"], b"Hello. World.");
+        assert_eq!(out, "Hello.\0 World.\0");
+        let out = run(&["-s", "4", "-f", "text", "--offsets-only"], b"abcdef");
+        assert_eq!(out, "0\t4\n4\t6\n");
+        let out = run(
+            &["-s", "10", "-d", ".", "-f", "text", "--drop-delimiters"],
+            b"Hello. World.",
+        );
+        assert_eq!(out, "Hello\0 World\0");
+        let out = run(
+            &["-s", "10", "-d", ".", "-f", "text", "--trim"],
+            b"Hello. World.",
+        );
+        assert_eq!(out, "Hello.\0World.\0");
+        let out = run(
+            &["-s", "3", "-d", "\\n", "--skip-empty", "--offsets-only"],
+            b"a\n\n\n\nb",
+        );
+        assert_eq!(out.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_cli_lines() {
+        let text = b"a 1\nb 2\nc 3\nd 4\ne 5\n";
+        let out = run(&["--lines", "2", "--offsets-only"], text);
+        assert_eq!(out.lines().count(), 3);
+        let out = run(&["--lines", "2", "-s", "5", "-f", "text"], text);
+        assert_eq!(out, "a 1\n\0b 2\n\0c 3\n\0d 4\n\0e 5\n\0");
+    }
+
+    #[test]
+    fn test_cli_positions() {
+        let text = b"INFO a\nWARN b\nINFO c\n";
+        let out = run(
+            &["-s", "7", "-d", "\\n", "--positions", "--offsets-only"],
+            text,
+        );
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[1], r#"{"start":7,"end":14,"line":2,"column":1}"#);
+        let out = run(
+            &[
+                "-s",
+                "5",
+                "-d",
+                " ",
+                "--positions",
+                "--offsets-only",
+                "-f",
+                "text",
+            ],
+            text,
+        );
+        assert!(out.starts_with("0\t5\t1\t1\n5\t10\t1\t6\n"), "{out}");
+    }
+
+    #[test]
+    fn test_cli_lang() {
+        let text = "The weather is lovely today.\nDas Wetter ist heute wirklich schön.\n42\n";
+        let out = run(
+            &["-s", "40", "-d", "\\n", "--lang", "--offsets-only"],
+            text.as_bytes(),
+        );
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], r#"{"start":0,"end":29,"lang":"eng"}"#);
+        assert!(lines[1].ends_with(r#""lang":"deu"}"#), "{out}");
+        assert!(lines[2].ends_with(r#""lang":"und"}"#), "{out}");
+        let out = run(
+            &[
+                "-s",
+                "40",
+                "-d",
+                "\\n",
+                "--lang",
+                "--offsets-only",
+                "-f",
+                "text",
+            ],
+            text.as_bytes(),
+        );
+        assert!(out.starts_with("0\t29\teng\n"), "{out}");
+    }
+
+    #[test]
+    fn test_cli_filters() {
+        let text = b"Home | About | Contact\n----------\nReal content here.\n";
+        let out = run(
+            &[
+                "-s",
+                "24",
+                "-d",
+                "\\n",
+                "--min-density",
+                "0.3",
+                "--drop-matching",
+                r"^Home \|",
+                "-f",
+                "jsonl",
+                "--offsets-only",
+            ],
+            text,
+        );
+        assert_eq!(out, "{\"index\":0,\"start\":34,\"end\":53}\n");
+
+        let args = Args::try_parse_from(["chunk", "--drop-matching", "("]).unwrap();
+        let err = args.output.filters().unwrap_err();
+        assert!(err.starts_with("--drop-matching: "), "{err}");
+    }
+
+    #[test]
+    fn test_cli_utf8_safe_by_default() {
+        let out = run(&["-s", "3", "--offsets-only"], "ééé".as_bytes());
+        assert_eq!(out.lines().count(), 3);
+        let out = run(
+            &["-s", "3", "--offsets-only", "--no-utf8-safe"],
+            "ééé".as_bytes(),
+        );
+        assert_eq!(out.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_cli_config_profile() {
+        let dir = std::env::temp_dir().join(format!("chunk-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("profiles.toml");
+        std::fs::write(&path, "[logs]\nsize = 7\ndelimiters = \".\"\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let text = b"Hello. World. Test.";
+        let out = run(&["-c", path, "--profile", "logs", "--offsets-only"], text);
+        assert_eq!(out.lines().count(), 3);
+        // Flags override the profile
+        let out = run(
+            &[
+                "-c",
+                path,
+                "--profile",
+                "logs",
+                "-s",
+                "100",
+                "--offsets-only",
+            ],
+            text,
+        );
+        assert_eq!(out.lines().count(), 1);
+
+        let args = Args::try_parse_from(["chunk", "-c", path, "--profile", "code"]).unwrap();
+        assert_eq!(
+            config(&args.options).unwrap_err().to_string(),
+            "no profile named `code`"
+        );
+        assert!(Args::try_parse_from(["chunk", "--profile", "logs"]).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cli_stats() {
+        let args = Args::try_parse_from(["chunk", "stats", "-", "-s", "8", "-d", "."]).unwrap();
+        let Some(Command::Stats { options, json }) = &args.command else {
+            panic!("expected the stats command");
+        };
+        let stats = config(options)
+            .unwrap()
+            .chunker(b"Hello. Worldwide!")
+            .stats();
+        let mut out = Vec::new();
+        write_stats(&stats, *json, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "chunks: 3\nmin: 3\nmax: 8\nmean: 5.7\nmedian: 6.0\nforced splits: 1\n"
+        );
+
+        let mut out = Vec::new();
+        write_stats(&stats, true, &mut out).unwrap();
+        assert!(
+            String::from_utf8(out)
+                .unwrap()
+                .contains(r#""forced_splits":1"#)
+        );
+        // A file named like the command still works as input
+        assert!(
+            Args::try_parse_from(["chunk", "./stats"])
+                .unwrap()
+                .command
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_cli_pattern_conflicts_with_delimiters() {
+        assert!(Args::try_parse_from(["chunk", "-d", ".", "-p", "▁"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_pdf_pages() {
+        let argv = [
+            "chunk",
+            "pdf",
+            "doc.pdf",
+            "-s",
+            "8",
+            "-d",
+            ".",
+            "--offsets-only",
+        ];
+        let Some(Command::Pdf { options, output }) = Args::try_parse_from(argv).unwrap().command
+        else {
+            panic!("expected the pdf command");
+        };
+        assert_eq!(options.input.as_deref(), Some(Path::new("doc.pdf")));
+        let doc = PagedText::new(&["Aaa. Bbb.", "Ccc."]);
+        let mut out = Vec::new();
+        write_chunks(
+            &output,
+            &config(&options).unwrap(),
+            &ChunkPipeline::new(),
+            doc.text(),
+            Some(&doc),
+            None,
+            &mut out,
+        )
+        .unwrap();
+        let lines: Vec<_> = std::str::from_utf8(&out).unwrap().lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"{"start":0,"end":4,"first_page":1,"last_page":1}"#
+        );
+        assert_eq!(
+            lines[2],
+            r#"{"start":9,"end":15,"first_page":2,"last_page":2}"#
+        );
+
+        let argv = [
+            "chunk",
+            "pdf",
+            "-s",
+            "8",
+            "-d",
+            ".",
+            "-f",
+            "text",
+            "--offsets-only",
+        ];
+        let Some(Command::Pdf { options, output }) = Args::try_parse_from(argv).unwrap().command
+        else {
+            panic!("expected the pdf command");
+        };
+        let mut out = Vec::new();
+        write_chunks(
+            &output,
+            &config(&options).unwrap(),
+            &ChunkPipeline::new(),
+            doc.text(),
+            Some(&doc),
+            None,
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(out, b"0\t4\t1\t1\n4\t9\t1\t1\n9\t15\t2\t2\n");
+    }
+
+    #[test]
+    fn test_cli_pdf_not_a_pdf() {
+        let path = std::env::temp_dir().join(format!("chunk-cli-not-{}.pdf", std::process::id()));
+        std::fs::write(&path, b"Hello. World.").unwrap();
+        let err = read_pdf(Some(&path)).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("not a readable PDF"));
+    }
+
+    #[test]
+    fn test_cli_invalid_options() {
+        let config = |argv: &[&str]| {
+            let args = Args::try_parse_from(std::iter::once("chunk").chain(argv.iter().copied()));
+            config(&args.unwrap().options).unwrap()
+        };
+        assert_eq!(
+            config(&["-s", "0"]).validate(),
+            Err(chunk::ChunkError::ZeroSize)
+        );
+        assert!(config(&["-s", "8", "--overlap", "8"]).validate().is_err());
+        assert!(config(&["-s", "8", "--overlap", "7"]).validate().is_ok());
+    }
+}
@@ -0,0 +1,36 @@
+plugins {
+    `java-library`
+}
+
+group = "ai.chonkie"
+version = "0.10.1"
+
+java {
+    toolchain {
+        languageVersion = JavaLanguageVersion.of(11)
+    }
+}
+
+repositories {
+    mavenCentral()
+}
+
+dependencies {
+    testImplementation("org.junit.jupiter:junit-jupiter:5.10.2")
+    testRuntimeOnly("org.junit.platform:junit-platform-launcher")
+}
+
+tasks.withType<JavaCompile> {
+    options.encoding = "UTF-8"
+}
+
+val cargoBuild by tasks.registering(Exec::class) {
+    description = "Builds the native library with cargo."
+    commandLine("cargo", "build", "--release")
+}
+
+tasks.test {
+    dependsOn(cargoBuild)
+    useJUnitPlatform()
+    systemProperty("java.library.path", layout.projectDirectory.dir("target/release").asFile.path)
+}
@@ -0,0 +1,150 @@
+//! JNI bindings for `ai.chonkie.chunk.Chunker`.
+//!
+//! The Java side passes its options as primitives (with the boolean flags
+//! packed into one `int`) and gets back a flat `long[]` of
+//! `[start1, end1, start2, end2, ...]` byte offsets.
+
+use chunk::ChunkerConfig;
+use jni::JNIEnv;
+use jni::objects::{JByteArray, JByteBuffer, JClass, ReleaseMode};
+use jni::sys::{jint, jlong, jlongArray};
+
+// Must match the flag constants in Chunker.java
+const PREFIX: jint = 1;
+const CONSECUTIVE: jint = 1 << 1;
+const FORWARD_FALLBACK: jint = 1 << 2;
+const UTF8_SAFE: jint = 1 << 3;
+
+/// The chunker config given by the JNI arguments.
+#[allow(clippy::too_many_arguments)]
+fn read_config(
+    env: &JNIEnv<'_>,
+    size: jlong,
+    delimiters: &JByteArray<'_>,
+    pattern: &JByteArray<'_>,
+    flags: jint,
+    overlap: jlong,
+    min_size: jlong,
+    max_size: jlong,
+) -> jni::errors::Result<ChunkerConfig> {
+    let bytes = |array: &JByteArray<'_>| {
+        (!array.is_null())
+            .then(|| env.convert_byte_array(array))
+            .transpose()
+    };
+    let mut config = ChunkerConfig::new()
+        .size(size.max(1) as usize)
+        .prefix(flags & PREFIX != 0)
+        .consecutive(flags & CONSECUTIVE != 0)
+        .forward_fallback(flags & FORWARD_FALLBACK != 0)
+        .overlap(overlap.max(0) as usize)
+        .utf8_safe(flags & UTF8_SAFE != 0)
+        .min_size(min_size.max(0) as usize)
+        // 0 means no cap
+        .max_size((max_size > 0).then_some(max_size as usize));
+    if let Some(pattern) = bytes(pattern)? {
+        config = config.pattern(pattern);
+    } else if let Some(delimiters) = bytes(delimiters)? {
+        config = config.delimiters(delimiters);
+    }
+    Ok(config)
+}
+
+/// Flat (start, end) offsets for `text`.
+fn offsets(config: &ChunkerConfig, text: &[u8]) -> Vec<jlong> {
+    config
+        .chunker(text)
+        .collect_offsets()
+        .into_iter()
+        .flat_map(|(start, end)| [start as jlong, end as jlong])
+        .collect()
+}
+
+/// Copy offsets into a new `long[]`.
+fn to_long_array(env: &mut JNIEnv<'_>, flat: &[jlong]) -> jni::errors::Result<jlongArray> {
+    let array = env.new_long_array(flat.len() as i32)?;
+    env.set_long_array_region(&array, 0, flat)?;
+    Ok(array.into_raw())
+}
+
+/// Return the result, or throw (unless an exception is already pending) and return null.
+fn or_throw(env: &mut JNIEnv<'_>, result: jni::errors::Result<jlongArray>) -> jlongArray {
+    result.unwrap_or_else(|err| {
+        if !env.exception_check().unwrap_or(true) {
+            let _ = env.throw_new("java/lang/RuntimeException", err.to_string());
+        }
+        std::ptr::null_mut()
+    })
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::too_many_arguments)]
+pub extern "system" fn Java_ai_chonkie_chunk_Chunker_nativeOffsets<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    text: JByteArray<'local>,
+    size: jlong,
+    delimiters: JByteArray<'local>,
+    pattern: JByteArray<'local>,
+    flags: jint,
+    overlap: jlong,
+    min_size: jlong,
+    max_size: jlong,
+) -> jlongArray {
+    let result = (|| {
+        let config = read_config(
+            &env,
+            size,
+            &delimiters,
+            &pattern,
+            flags,
+            overlap,
+            min_size,
+            max_size,
+        )?;
+        // SAFETY: no other JNI calls are made while the critical section is held
+        let flat = unsafe {
+            let elements = env.get_array_elements_critical(&text, ReleaseMode::NoCopyBack)?;
+            let text = std::slice::from_raw_parts(elements.as_ptr().cast::<u8>(), elements.len());
+            offsets(&config, text)
+        };
+        to_long_array(&mut env, &flat)
+    })();
+    or_throw(&mut env, result)
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::too_many_arguments)]
+pub extern "system" fn Java_ai_chonkie_chunk_Chunker_nativeOffsetsDirect<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    text: JByteBuffer<'local>,
+    size: jlong,
+    delimiters: JByteArray<'local>,
+    pattern: JByteArray<'local>,
+    flags: jint,
+    overlap: jlong,
+    min_size: jlong,
+    max_size: jlong,
+) -> jlongArray {
+    let result = (|| {
+        let config = read_config(
+            &env,
+            size,
+            &delimiters,
+            &pattern,
+            flags,
+            overlap,
+            min_size,
+            max_size,
+        )?;
+        let ptr = env.get_direct_buffer_address(&text)?;
+        let len = env.get_direct_buffer_capacity(&text)?;
+        // SAFETY: the JVM guarantees a direct buffer's address is valid for its
+        // capacity, and the Java caller keeps the buffer alive during the call
+        let text = unsafe { std::slice::from_raw_parts(ptr, len) };
+        let flat = offsets(&config, text);
+        to_long_array(&mut env, &flat)
+    })();
+    or_throw(&mut env, result)
+}
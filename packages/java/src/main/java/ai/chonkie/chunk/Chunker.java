@@ -0,0 +1,151 @@
+package ai.chonkie.chunk;
+
+import java.nio.ByteBuffer;
+import java.nio.charset.StandardCharsets;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.List;
+
+/**
+ * Splits text into chunks at delimiter boundaries.
+ *
+ * <p>Configure with the builder-style setters, then call {@link #offsets},
+ * {@link #chunks} or {@link #texts}. A configured chunker is immutable in use
+ * and safe to share between threads.
+ *
+ * <pre>{@code
+ * Chunker chunker = new Chunker().size(1024).delimiters(".\n?").overlap(64);
+ * long[] offsets = chunker.offsets(bytes);   // [start1, end1, start2, end2, ...]
+ * List<String> chunks = chunker.texts(text); // decoded strings
+ * }</pre>
+ */
+public final class Chunker {
+    /** Default target chunk size in bytes (4KB). */
+    public static final int DEFAULT_TARGET_SIZE = 4096;
+
+    // Must match the flag constants in src/lib.rs
+    private static final int PREFIX = 1;
+    private static final int CONSECUTIVE = 1 << 1;
+    private static final int FORWARD_FALLBACK = 1 << 2;
+    private static final int UTF8_SAFE = 1 << 3;
+
+    static {
+        System.loadLibrary("chunk_java");
+    }
+
+    private long size = DEFAULT_TARGET_SIZE;
+    private byte[] delimiters;
+    private byte[] pattern;
+    private int flags;
+    private long overlap;
+    private long minSize;
+    private long maxSize;
+
+    /** Target chunk size in bytes (default: 4096). */
+    public Chunker size(long size) {
+        this.size = size;
+        return this;
+    }
+
+    /** Single-byte delimiter characters (default: {@code "\n.?"}). */
+    public Chunker delimiters(String delimiters) {
+        this.delimiters = delimiters.getBytes(StandardCharsets.UTF_8);
+        return this;
+    }
+
+    /** Multi-byte pattern to split at instead of delimiters. */
+    public Chunker pattern(String pattern) {
+        this.pattern = pattern.getBytes(StandardCharsets.UTF_8);
+        return this;
+    }
+
+    /** Put the delimiter at the start of the next chunk. */
+    public Chunker prefix() {
+        flags |= PREFIX;
+        return this;
+    }
+
+    /** Split at the start of runs of the same delimiter. */
+    public Chunker consecutive() {
+        flags |= CONSECUTIVE;
+        return this;
+    }
+
+    /** Search forward when no delimiter is found before the target size. */
+    public Chunker forwardFallback() {
+        flags |= FORWARD_FALLBACK;
+        return this;
+    }
+
+    /** Bytes of trailing context carried into the next chunk (default: 0). */
+    public Chunker overlap(long overlap) {
+        this.overlap = overlap;
+        return this;
+    }
+
+    /**
+     * Never split inside a multi-byte UTF-8 sequence. Always on for
+     * {@link #texts}; off by default for bytes.
+     */
+    public Chunker utf8Safe() {
+        flags |= UTF8_SAFE;
+        return this;
+    }
+
+    /** Merge a trailing chunk smaller than this into the previous one. */
+    public Chunker minSize(long minSize) {
+        this.minSize = minSize;
+        return this;
+    }
+
+    /** Strict cap on chunk length in bytes (default: none). */
+    public Chunker maxSize(long maxSize) {
+        this.maxSize = maxSize;
+        return this;
+    }
+
+    /** Chunk offsets as a flat array {@code [start1, end1, start2, end2, ...]}. */
+    public long[] offsets(byte[] text) {
+        return nativeOffsets(text, size, delimiters, pattern, flags, overlap, minSize, maxSize);
+    }
+
+    /**
+     * Chunk offsets for the remaining bytes of a direct buffer, relative to its
+     * position. The buffer is read in place, without copying.
+     */
+    public long[] offsets(ByteBuffer text) {
+        if (!text.isDirect()) {
+            throw new IllegalArgumentException("buffer must be direct");
+        }
+        return nativeOffsetsDirect(text.slice(), size, delimiters, pattern, flags, overlap, minSize, maxSize);
+    }
+
+    /** Chunks of {@code text} as byte arrays. */
+    public List<byte[]> chunks(byte[] text) {
+        long[] flat = offsets(text);
+        List<byte[]> chunks = new ArrayList<>(flat.length / 2);
+        for (int i = 0; i < flat.length; i += 2) {
+            chunks.add(Arrays.copyOfRange(text, (int) flat[i], (int) flat[i + 1]));
+        }
+        return chunks;
+    }
+
+    /** Chunks of {@code text} as strings, never splitting a character. */
+    public List<String> texts(String text) {
+        byte[] bytes = text.getBytes(StandardCharsets.UTF_8);
+        long[] flat = nativeOffsets(bytes, size, delimiters, pattern, flags | UTF8_SAFE, overlap, minSize, maxSize);
+        List<String> texts = new ArrayList<>(flat.length / 2);
+        for (int i = 0; i < flat.length; i += 2) {
+            texts.add(new String(bytes, (int) flat[i], (int) (flat[i + 1] - flat[i]), StandardCharsets.UTF_8));
+        }
+        return texts;
+    }
+
+    private static native long[] nativeOffsets(
+            byte[] text, long size, byte[] delimiters, byte[] pattern,
+            int flags, long overlap, long minSize, long maxSize);
+
+    private static native long[] nativeOffsetsDirect(
+            ByteBuffer text, long size, byte[] delimiters, byte[] pattern,
+            int flags, long overlap, long minSize, long maxSize);
+}
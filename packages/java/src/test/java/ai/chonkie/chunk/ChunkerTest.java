@@ -0,0 +1,55 @@
+package ai.chonkie.chunk;
+
+import static org.junit.jupiter.api.Assertions.assertArrayEquals;
+import static org.junit.jupiter.api.Assertions.assertEquals;
+import static org.junit.jupiter.api.Assertions.assertThrows;
+
+import java.nio.ByteBuffer;
+import java.nio.charset.StandardCharsets;
+import java.util.List;
+import org.junit.jupiter.api.Test;
+
+class ChunkerTest {
+    private static final byte[] TEXT = "Hello. World. Test.".getBytes(StandardCharsets.UTF_8);
+
+    @Test
+    void offsetsAtDelimiters() {
+        long[] offsets = new Chunker().size(10).delimiters(".").offsets(TEXT);
+        assertArrayEquals(new long[] {0, 6, 6, 13, 13, 19}, offsets);
+    }
+
+    @Test
+    void defaultsKeepShortTextWhole() {
+        assertArrayEquals(new long[] {0, 19}, new Chunker().offsets(TEXT));
+        assertArrayEquals(new long[] {}, new Chunker().offsets(new byte[0]));
+    }
+
+    @Test
+    void overlapAndMaxSize() {
+        long[] offsets = new Chunker().size(10).delimiters(".").overlap(5).maxSize(9).offsets(TEXT);
+        assertArrayEquals(new long[] {0, 6, 4, 13, 10, 19}, offsets);
+    }
+
+    @Test
+    void directBufferFromPosition() {
+        ByteBuffer buffer = ByteBuffer.allocateDirect(TEXT.length + 3);
+        buffer.put(new byte[] {'x', 'y', 'z'}).put(TEXT).flip().position(3);
+        long[] offsets = new Chunker().size(10).delimiters(".").offsets(buffer);
+        assertArrayEquals(new long[] {0, 6, 6, 13, 13, 19}, offsets);
+        assertThrows(IllegalArgumentException.class, () -> new Chunker().offsets(ByteBuffer.wrap(TEXT)));
+    }
+
+    @Test
+    void textsNeverSplitCharacters() {
+        assertEquals(List.of("é", "é", "é"), new Chunker().size(3).texts("ééé"));
+        List<String> texts = new Chunker().size(8).pattern("▁").prefix().texts("Hello▁World▁Test");
+        assertEquals("Hello▁World▁Test", String.join("", texts));
+        assertEquals('▁', texts.get(1).charAt(0));
+    }
+
+    @Test
+    void chunksAsBytes() {
+        List<byte[]> chunks = new Chunker().size(10).delimiters(".").chunks(TEXT);
+        assertEquals(" World.", new String(chunks.get(1), StandardCharsets.UTF_8));
+    }
+}
@@ -0,0 +1,212 @@
+/**
+ * @chonkiejs/chunk-node - The fastest semantic text chunking library, as a
+ * native Node.js addon.
+ *
+ * Same surface as the WASM package (`chunk`, `chunk_offsets`, `Chunker`),
+ * but Buffers and Uint8Arrays are read in place instead of being copied into
+ * WASM memory, and there is no `init()` step.
+ *
+ * @example
+ * ```javascript
+ * import { chunk } from '@chonkiejs/chunk-node';
+ *
+ * for (const slice of chunk("Hello. World. Test.", { size: 10 })) {
+ *     console.log(slice);
+ * }
+ *
+ * // Buffers are chunked zero-copy and yield subarrays of the input
+ * const buf = await readFile("corpus.txt");
+ * for (const slice of chunk(buf, { size: 4096 })) {
+ *     console.log(slice.length);
+ * }
+ * ```
+ */
+
+import { createRequire } from 'node:module';
+
+const require = createRequire(import.meta.url);
+const native = require('./binding.cjs');
+
+/**
+ * Get the default target size (4096 bytes).
+ * @returns {number}
+ */
+export function default_target_size() {
+    return native.defaultTargetSize();
+}
+
+/**
+ * Get the default delimiters ("\n.?").
+ * @returns {Uint8Array}
+ */
+export function default_delimiters() {
+    return native.defaultDelimiters();
+}
+
+const decoder = new TextDecoder();
+
+/**
+ * Turn a flat [start1, end1, start2, end2, ...] array into pairs.
+ * @param {ArrayLike<number>} flat
+ * @returns {Array<[number, number]>}
+ */
+function toPairs(flat) {
+    const pairs = [];
+    for (let i = 0; i < flat.length; i += 2) {
+        pairs.push([flat[i], flat[i + 1]]);
+    }
+    return pairs;
+}
+
+/**
+ * Convert input to bytes if it's a string.
+ * @param {string | Uint8Array} input
+ * @returns {Uint8Array}
+ */
+function toBytes(input) {
+    return typeof input === 'string' ? Buffer.from(input, 'utf8') : input;
+}
+
+/**
+ * Native options for `text`, with utf8Safe defaulting to true for strings.
+ */
+function nativeOptions(text, options) {
+    const { utf8Safe, ...rest } = options;
+    return { ...rest, utf8Safe: utf8Safe ?? typeof text === 'string' };
+}
+
+/**
+ * Split text into chunks at delimiter boundaries.
+ * Accepts strings, Buffers or Uint8Arrays. Returns the same type as input.
+ *
+ * @param {string | Uint8Array} text - The text to chunk
+ * @param {Object} [options] - Same options as the WASM package's `chunk()`
+ * @param {number} [options.size=4096] - Target chunk size in bytes
+ * @param {string} [options.delimiters="\n.?"] - Delimiter characters, or a class name such as "sentences"
+ * @param {string | Uint8Array} [options.pattern] - Multi-byte pattern to split on
+ * @param {Array<string | Uint8Array>} [options.patterns] - Multi-byte patterns, composable with delimiters (longest match wins)
+ * @param {boolean} [options.prefix=false] - Put delimiter/pattern at start of next chunk
+ * @param {boolean} [options.consecutive=false] - Split at START of consecutive runs
+ * @param {boolean} [options.forwardFallback=false] - Search forward if no pattern in backward window
+ * @param {boolean} [options.delimiterPriority=false] - Earlier delimiters win over later ones in the window
+ * @param {number} [options.slack=0] - Units either side of the size to look for the nearest boundary
+ * @param {boolean} [options.balanced=false] - Even out chunk sizes over the text
+ * @param {number} [options.linesPerChunk] - Complete lines per chunk, replacing the delimiter search
+ * @param {number} [options.overlap=0] - Bytes of trailing context carried into the next chunk
+ * @param {number} [options.overlapRatio] - Overlap as a share of the size, replacing `overlap`
+ * @param {boolean} [options.utf8Safe] - Never split inside a multi-byte character (default: true for strings)
+ * @param {"bytes" | "chars"} [options.unit="bytes"] - Unit the size is measured in
+ * @param {number} [options.minSize=0] - Merge a trailing chunk smaller than this into the previous one
+ * @param {number} [options.maxSize] - Strict cap on chunk length in bytes
+ * @param {boolean} [options.trim=false] - Leave leading and trailing whitespace out of chunks
+ * @param {boolean} [options.skipEmpty=false] - Don't emit whitespace-only chunks
+ * @yields {string | Uint8Array} Chunks (same type as input)
+ */
+export function* chunk(text, options = {}) {
+    const isString = typeof text === 'string';
+    const bytes = toBytes(text);
+    const flat = native.chunkOffsets(bytes, nativeOptions(text, options));
+    for (let i = 0; i < flat.length; i += 2) {
+        const slice = bytes.subarray(flat[i], flat[i + 1]);
+        yield isString ? decoder.decode(slice) : slice;
+    }
+}
+
+/**
+ * Get chunk offsets without creating views.
+ *
+ * @param {string | Uint8Array} text - The text to chunk
+ * @param {Object} [options] - Same options as `chunk()`
+ * @returns {Array<[number, number]>} Array of [start, end] byte offset pairs
+ */
+export function chunk_offsets(text, options = {}) {
+    return toPairs(native.chunkOffsets(toBytes(text), nativeOptions(text, options)));
+}
+
+/**
+ * Chunker with the same interface as the WASM package's `Chunker`.
+ *
+ * Offsets are computed in one native call on first use; chunks are
+ * subarrays of the input (or decoded strings for string input).
+ *
+ * @example
+ * const chunker = new Chunker(buffer, { size: 4096 });
+ * for (const slice of chunker) {
+ *     console.log(slice);
+ * }
+ */
+export class Chunker {
+    /**
+     * Create a new Chunker.
+     * @param {string | Uint8Array} text - The text to chunk
+     * @param {Object} [options] - Same options as `chunk()`
+     */
+    constructor(text, options = {}) {
+        this._isString = typeof text === 'string';
+        this._bytes = toBytes(text);
+        this._options = nativeOptions(text, options);
+        this._offsets = null;
+        this._index = 0;
+    }
+
+    /** Flat offsets, computed on first use. */
+    _flat() {
+        this._offsets ??= native.chunkOffsets(this._bytes, this._options);
+        return this._offsets;
+    }
+
+    /** Chunk `i` as a subarray or decoded string. */
+    _slice(flat, i) {
+        const slice = this._bytes.subarray(flat[2 * i], flat[2 * i + 1]);
+        return this._isString ? decoder.decode(slice) : slice;
+    }
+
+    /**
+     * Get the next chunk, or undefined if exhausted.
+     * @returns {string | Uint8Array | undefined}
+     */
+    next() {
+        const flat = this._flat();
+        if (2 * this._index >= flat.length) return undefined;
+        return this._slice(flat, this._index++);
+    }
+
+    /**
+     * Reset the chunker to iterate from the beginning.
+     */
+    reset() {
+        this._index = 0;
+    }
+
+    /**
+     * Collect all chunk offsets as an array of [start, end] pairs.
+     * @returns {Array<[number, number]>}
+     */
+    collectOffsets() {
+        return toPairs(this._flat());
+    }
+
+    /**
+     * Collect all chunks.
+     * @returns {Array<string | Uint8Array>} Chunks (same type as input)
+     */
+    collectChunks() {
+        const flat = this._flat();
+        return Array.from({ length: flat.length / 2 }, (_, i) => this._slice(flat, i));
+    }
+
+    /**
+     * No-op, kept so code written for the WASM package works unchanged.
+     */
+    free() {}
+
+    /**
+     * Iterator protocol - allows use in for...of loops.
+     */
+    *[Symbol.iterator]() {
+        let chunk;
+        while ((chunk = this.next()) !== undefined) {
+            yield chunk;
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use chunk::{ChunkerConfig, DEFAULT_DELIMITERS, DEFAULT_TARGET_SIZE, Delimiters, SizeUnit};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Options for `chunkOffsets`. Field names are camelCase on the JS side.
+#[napi(object)]
+#[derive(Default)]
+pub struct ChunkOptions {
+    /// Target chunk size (default: 4096)
+    pub size: Option<u32>,
+    /// Delimiter characters, or a class name such as "sentences" (default: "\n.?")
+    pub delimiters: Option<String>,
+    /// Multi-byte pattern to split on instead of delimiters
+    pub pattern: Option<Either<String, Buffer>>,
+    /// Multi-byte patterns, composable with delimiters; longest match wins
+    pub patterns: Option<Vec<Either<String, Buffer>>>,
+    /// Put delimiter/pattern at start of next chunk (default: false)
+    pub prefix: Option<bool>,
+    /// Split at START of consecutive runs (default: false)
+    pub consecutive: Option<bool>,
+    /// Search forward if no delimiter in backward window (default: false)
+    pub forward_fallback: Option<bool>,
+    /// Earlier delimiters win over later ones in the window (default: false)
+    pub delimiter_priority: Option<bool>,
+    /// Units either side of the size to look for the nearest boundary (default: 0)
+    pub slack: Option<u32>,
+    /// Even out chunk sizes over the text (default: false)
+    pub balanced: Option<bool>,
+    /// Complete lines per chunk, replacing the delimiter search (default: none)
+    pub lines_per_chunk: Option<u32>,
+    /// Bytes of trailing context carried into the next chunk (default: 0)
+    pub overlap: Option<u32>,
+    /// Overlap as a share of the size, replacing `overlap` (default: none)
+    pub overlap_ratio: Option<f64>,
+    /// Never split inside a multi-byte UTF-8 sequence (default: false)
+    pub utf8_safe: Option<bool>,
+    /// Unit of size: "bytes" (default) or "chars"
+    pub unit: Option<String>,
+    /// Merge a trailing chunk smaller than this into the previous one (default: 0)
+    pub min_size: Option<u32>,
+    /// Strict cap on chunk length in bytes (default: none)
+    pub max_size: Option<u32>,
+    /// Leave leading and trailing whitespace out of chunks (default: false)
+    pub trim: Option<bool>,
+    /// Don't emit whitespace-only chunks (default: false)
+    pub skip_empty: Option<bool>,
+}
+
+fn into_bytes(value: Either<String, Buffer>) -> Vec<u8> {
+    match value {
+        Either::A(s) => s.into_bytes(),
+        Either::B(b) => b.to_vec(),
+    }
+}
+
+impl ChunkOptions {
+    /// Convert into the config shared with the other bindings.
+    fn into_config(self) -> Result<ChunkerConfig> {
+        let unit = match self.unit.as_deref() {
+            None | Some("bytes") => SizeUnit::Bytes,
+            Some("chars") => SizeUnit::Chars,
+            Some(_) => {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    "unit must be 'bytes' or 'chars'",
+                ));
+            }
+        };
+        let mut config =
+            ChunkerConfig::new().size(self.size.map_or(DEFAULT_TARGET_SIZE, |s| s as usize));
+        if let Some(delimiters) = self.delimiters {
+            // A class name such as "sentences" picks that class
+            config = match delimiters.parse::<Delimiters>() {
+                Ok(class) => config.delimiter_class(class),
+                Err(_) => config.delimiters(delimiters.into_bytes()),
+            };
+        }
+        // A delimiter class may have turned prefix mode on already
+        let prefix = config.prefix || self.prefix.unwrap_or(false);
+        config = config
+            .prefix(prefix)
+            .consecutive(self.consecutive.unwrap_or(false))
+            .forward_fallback(self.forward_fallback.unwrap_or(false))
+            .delimiter_priority(self.delimiter_priority.unwrap_or(false))
+            .slack(self.slack.unwrap_or(0) as usize)
+            .balanced(self.balanced.unwrap_or(false))
+            .lines_per_chunk(self.lines_per_chunk.map(|n| n as usize))
+            .overlap(self.overlap.unwrap_or(0) as usize)
+            .utf8_safe(self.utf8_safe.unwrap_or(false))
+            .unit(unit)
+            .min_size(self.min_size.unwrap_or(0) as usize)
+            .max_size(self.max_size.map(|m| m as usize))
+            .trim(self.trim.unwrap_or(false))
+            .skip_empty(self.skip_empty.unwrap_or(false));
+        if let Some(ratio) = self.overlap_ratio {
+            config = config.overlap_ratio(ratio);
+        }
+        // Pattern (singular) takes precedence over delimiters if both specified
+        if let Some(pattern) = self.pattern {
+            config = config.pattern(into_bytes(pattern));
+        }
+        if let Some(pats) = self.patterns {
+            let pats: Vec<Vec<u8>> = pats.into_iter().map(into_bytes).collect();
+            config = config.patterns(&pats);
+        }
+        config
+            .validate()
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))?;
+        Ok(config)
+    }
+}
+
+/// Get chunk offsets as a flat array [start1, end1, start2, end2, ...].
+///
+/// `text` is read in place (Buffers and Uint8Arrays are not copied).
+#[napi]
+pub fn chunk_offsets(text: Uint8Array, options: Option<ChunkOptions>) -> Result<Uint32Array> {
+    if text.len() > u32::MAX as usize {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "text must be smaller than 4 GiB",
+        ));
+    }
+    let config = options.unwrap_or_default().into_config()?;
+
+    let flat: Vec<u32> = config
+        .chunker(&text)
+        .collect_offsets()
+        .into_iter()
+        .flat_map(|(start, end)| [start as u32, end as u32])
+        .collect();
+    Ok(Uint32Array::new(flat))
+}
+
+/// Get the default target size (4096 bytes).
+#[napi]
+pub fn default_target_size() -> u32 {
+    DEFAULT_TARGET_SIZE as u32
+}
+
+/// Get the default delimiters ("\n.?").
+#[napi]
+pub fn default_delimiters() -> Buffer {
+    DEFAULT_DELIMITERS.to_vec().into()
+}
@@ -0,0 +1,61 @@
+import { test } from 'node:test';
+import assert from 'node:assert';
+
+import { chunk, chunk_offsets, Chunker, default_target_size, default_delimiters } from '../index.js';
+
+const decoder = new TextDecoder();
+
+test('node: string in, strings out', () => {
+    const chunks = [...chunk("Hello. World. Test.", { size: 10, delimiters: "." })];
+    assert.deepStrictEqual(chunks, ["Hello.", " World.", " Test."]);
+});
+
+test('node: Buffer chunks are zero-copy subarrays', () => {
+    const buf = Buffer.from("Hello. World. Test.");
+    const chunks = [...chunk(buf, { size: 10, delimiters: "." })];
+    assert.strictEqual(chunks.length, 3);
+    assert.strictEqual(chunks[1].buffer, buf.buffer);
+    chunks[0][0] = 'J'.charCodeAt(0);
+    assert.strictEqual(buf.toString(), "Jello. World. Test.");
+});
+
+test('node: chunk_offsets matches slicing', () => {
+    const text = "Hello. World. Test.";
+    assert.deepStrictEqual(chunk_offsets(text, { size: 10, delimiters: "." }), [[0, 6], [6, 13], [13, 19]]);
+});
+
+test('node: pattern and options', () => {
+    const text = "Hello▁World▁Test";
+    const chunks = [...chunk(text, { size: 15, pattern: "▁", prefix: true })];
+    assert.strictEqual(chunks.join(""), text);
+    assert.ok(chunks[1].startsWith("▁"));
+
+    const overlapped = chunk_offsets("Hello. World. Test.", { size: 10, delimiters: ".", overlap: 5, maxSize: 9 });
+    assert.deepStrictEqual(overlapped, [[0, 6], [4, 13], [10, 19]]);
+});
+
+test('node: utf8Safe defaults to true for strings', () => {
+    assert.deepStrictEqual(chunk_offsets("ééé", { size: 3 }), [[0, 2], [2, 4], [4, 6]]);
+    assert.deepStrictEqual(chunk_offsets(Buffer.from("ééé"), { size: 3 }), [[0, 3], [3, 6]]);
+});
+
+test('node: invalid unit throws', () => {
+    assert.throws(() => chunk_offsets("abc", { unit: "words" }));
+});
+
+test('node: Chunker mirrors the WASM interface', () => {
+    const chunker = new Chunker("Hello. World. Test.", { size: 10, delimiters: "." });
+    assert.strictEqual(chunker.next(), "Hello.");
+    assert.deepStrictEqual([...chunker], [" World.", " Test."]);
+    assert.strictEqual(chunker.next(), undefined);
+
+    chunker.reset();
+    assert.deepStrictEqual(chunker.collectChunks(), ["Hello.", " World.", " Test."]);
+    assert.deepStrictEqual(chunker.collectOffsets(), [[0, 6], [6, 13], [13, 19]]);
+    chunker.free();
+});
+
+test('node: defaults', () => {
+    assert.strictEqual(default_target_size(), 4096);
+    assert.strictEqual(decoder.decode(default_delimiters()), "\n.?");
+});
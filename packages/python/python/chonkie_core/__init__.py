@@ -1,55 +1,124 @@
 """chonkie-core - The fastest semantic text chunking library."""
 
+import inspect
+
 from chonkie_core._chunk import (
+    ArrowChunks,
+    CancelToken,
+    Chunk,
+    ChunkPipeline,
     Chunker,
+    ChunkerConfig,
+    CodeChunker,
+    Delimiters,
     MergeResult,
     PatternSplitter,
+    RecursiveChunker,
+    Redactor,
+    RegexChunker,
+    SentenceChunker,
+    StreamChunker,
+    chunk_arrow,
+    chunk_hash,
     chunk_offsets,
+    chunk_offsets_batch,
+    chunk_str_offsets,
+    chunk_text,
+    count_tokens,
+    detect_language,
+    estimate_tokens,
+    regex_chunk_offsets,
+    find_batch_duplicates,
+    find_duplicates,
     find_merge_indices,
+    merge_chunks,
     merge_splits,
     split_offsets,
     split_pattern_offsets,
-    # Savitzky-Golay filter functions
-    savgol_filter,
-    find_local_minima_interpolated,
-    windowed_cross_similarity,
-    filter_split_indices,
     DEFAULT_TARGET_SIZE,
     DEFAULT_DELIMITERS,
 )
 
+# Savitzky-Golay filter functions, left out of builds without the numpy feature
+try:
+    from chonkie_core._chunk import (
+        savgol_filter,
+        find_local_minima_interpolated,
+        windowed_cross_similarity,
+        filter_split_indices,
+    )
+except ImportError:
+    _SAVGOL = []
+else:
+    _SAVGOL = [
+        "savgol_filter",
+        "find_local_minima_interpolated",
+        "windowed_cross_similarity",
+        "filter_split_indices",
+    ]
+
 __all__ = [
+    "aiter_chunks",
     "chunk",
+    "ArrowChunks",
+    "CancelToken",
+    "Chunk",
+    "ChunkPipeline",
     "Chunker",
+    "ChunkerConfig",
+    "CodeChunker",
+    "Delimiters",
     "MergeResult",
     "PatternSplitter",
+    "RecursiveChunker",
+    "Redactor",
+    "RegexChunker",
+    "SentenceChunker",
+    "StreamChunker",
+    "chunk_arrow",
+    "chunk_hash",
     "chunk_offsets",
+    "chunk_offsets_batch",
+    "chunk_str_offsets",
+    "chunk_text",
+    "count_tokens",
+    "detect_language",
+    "estimate_tokens",
+    "regex_chunk_offsets",
+    "find_batch_duplicates",
+    "find_duplicates",
     "find_merge_indices",
+    "merge_chunks",
     "merge_splits",
     "split_offsets",
     "split_pattern_offsets",
-    # Savitzky-Golay filter functions
-    "savgol_filter",
-    "find_local_minima_interpolated",
-    "windowed_cross_similarity",
-    "filter_split_indices",
     "DEFAULT_TARGET_SIZE",
     "DEFAULT_DELIMITERS",
+    *_SAVGOL,
 ]
 __version__ = "0.10.1"
 
 
-def chunk(text, *, size=DEFAULT_TARGET_SIZE, delimiters=None, patterns=None):
+def chunk(
+    text, *, size=DEFAULT_TARGET_SIZE, delimiters=None, patterns=None, overlap=0, utf8_safe=None, unit="bytes", min_size=0,
+    max_size=None,
+):
     """
     Split text into chunks at delimiter boundaries.
     Returns an iterator of zero-copy memoryview slices.
 
     Args:
-        text: bytes or str to chunk
+        text: str or a bytes-like object (bytes, bytearray, memoryview, ...) to chunk
         size: Target chunk size in bytes (default: 4096)
         delimiters: bytes or str of delimiter characters (default: "\\n.?")
         patterns: list of str or bytes for multi-byte delimiters (e.g. ["。", "，"])
             Composable with delimiters — both can be active simultaneously.
+        overlap: Bytes of trailing context carried into the next chunk (default: 0)
+        utf8_safe: Never split inside a multi-byte character
+            (default: True for str, False for bytes)
+        unit: Unit of size - "bytes" (default) or "chars"
+        min_size: Merge a trailing chunk smaller than this into the previous one (default: 0)
+        max_size: Strict cap on chunk length in bytes (default: None)
 
     Yields:
         memoryview slices of the original text
@@ -65,11 +134,78 @@ def chunk(text, *, size=DEFAULT_TARGET_SIZE, delimiters=None, patterns=None):
     # Convert str to bytes if needed
     if isinstance(text, str):
         text = text.encode("utf-8")
+        if utf8_safe is None:
+            utf8_safe = True
 
     # Get offsets from Rust (single FFI call)
-    offsets = chunk_offsets(text, size=size, delimiters=delimiters, patterns=patterns)
+    offsets = chunk_offsets(
+        text,
+        size=size,
+        delimiters=delimiters,
+        patterns=patterns,
+        overlap=overlap,
+        utf8_safe=utf8_safe,
+        unit=unit,
+        min_size=min_size,
+        max_size=max_size,
+    )
 
     # Return memoryview slices (zero-copy)
     mv = memoryview(text)
     for start, end in offsets:
         yield mv[start:end]
+
+
+async def aiter_chunks(
+    stream, *, size=DEFAULT_TARGET_SIZE, delimiters=None, patterns=None, overlap=0, utf8_safe=None, min_size=0,
+    max_size=None, read_size=65536,
+):
+    """
+    Chunk an async stream or file as it is read.
+    Boundaries are found in Rust by a StreamChunker; only the reads are awaited here.
+
+    Args:
+        stream: anything with a read(n) method returning bytes or str, awaitable
+            (asyncio.StreamReader, aiofiles, ...) or not (a plain file object)
+        size: Target chunk size in bytes (default: 4096)
+        delimiters: bytes or str of delimiter characters (default: "\\n.?")
+        patterns: list of str or bytes for multi-byte delimiters
+        overlap: Bytes of trailing context carried into the next chunk (default: 0)
+        utf8_safe: Never split inside a multi-byte character
+            (default: True when the stream yields str, False for bytes)
+        min_size: Merge a trailing chunk smaller than this into the previous one (default: 0)
+        max_size: Strict cap on chunk length in bytes (default: None)
+        read_size: Bytes (or characters) requested per read (default: 65536)
+
+    Yields:
+        bytes, the same chunks Chunker gives for the whole input
+
+    Example:
+        >>> reader, writer = await asyncio.open_connection(host, port)
+        >>> async for chunk in aiter_chunks(reader, size=1024):
+        ...     await index(chunk)
+    """
+    chunker = None
+    while True:
+        data = stream.read(read_size)
+        if inspect.isawaitable(data):
+            data = await data
+        if not data:
+            break
+        if chunker is None:
+            if utf8_safe is None:
+                utf8_safe = isinstance(data, str)
+            chunker = StreamChunker(
+                size=size,
+                delimiters=delimiters,
+                patterns=patterns,
+                overlap=overlap,
+                utf8_safe=utf8_safe,
+                min_size=min_size,
+                max_size=max_size,
+            )
+        for piece in chunker.push(data):
+            yield piece
+    if chunker is not None:
+        for piece in chunker.finish():
+            yield piece
@@ -1,27 +1,734 @@
 use chunk::{
-    DEFAULT_DELIMITERS, DEFAULT_TARGET_SIZE, IncludeDelim, OwnedChunker,
-    PatternSplitter as RustPatternSplitter, filter_split_indices as rust_filter_split_indices,
-    find_local_minima_interpolated as rust_find_local_minima,
-    find_merge_indices as rust_find_merge_indices, merge_splits as rust_merge_splits,
-    savgol_filter as rust_savgol_filter, split_at_delimiters, split_at_patterns,
+    ArrowArray, ArrowArrayStream, ArrowError, ArrowLayout, ArrowSchema, BoundaryScorer,
+    CancelToken as RustCancelToken, Checkpoint, CheckpointMismatch, ChunkError,
+    ChunkPipeline as RustChunkPipeline, ChunkTemplate, ChunkerConfig as RustChunkerConfig,
+    CodeChunker as RustCodeChunker, ConfigError, DEFAULT_DELIMITERS, DEFAULT_TARGET_SIZE,
+    Delimiters as RustDelimiters, IncludeDelim, MappedText, OwnedChunker,
+    PatternSplitter as RustPatternSplitter, Progress, RecursiveChunker as RustRecursiveChunker,
+    Redactor as RustRedactor, RegexChunker as RustRegexChunker,
+    SentenceChunker as RustSentenceChunker, SizeUnit, StreamChunker as RustStreamChunker,
+    TextColumn, chunk_batch, chunk_batch_until, chunk_hash as rust_chunk_hash,
+    detect_language as rust_detect_language, estimate_tokens as rust_estimate_tokens,
+    find_batch_duplicates as rust_find_batch_duplicates, find_duplicates as rust_find_duplicates,
+    find_merge_indices as rust_find_merge_indices, merge_chunks as rust_merge_chunks,
+    merge_splits as rust_merge_splits, split_at_delimiters, split_at_patterns,
+};
+#[cfg(feature = "numpy")]
+use chunk::{
+    filter_split_indices as rust_filter_split_indices,
+    find_local_minima_interpolated as rust_find_local_minima, savgol_filter as rust_savgol_filter,
     windowed_cross_similarity as rust_windowed_cross_similarity,
 };
-use numpy::{PyArray1, PyReadonlyArray1, PyReadonlyArray2, PyUntypedArrayMethods};
+#[cfg(feature = "numpy")]
+use numpy::{
+    PyArray1, PyArray2, PyArrayMethods, PyReadonlyArray1, PyReadonlyArray2, PyUntypedArrayMethods,
+};
+use pyo3::buffer::PyBuffer;
 use pyo3::prelude::*;
-use pyo3::types::{PyBytes, PyString};
+use pyo3::types::{PyBytes, PyCapsule, PyDict, PyList, PyMemoryView, PySlice, PyString};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
 
-/// Extract bytes from either bytes or str Python object.
-fn extract_bytes(obj: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
-    if obj.is_instance_of::<PyBytes>() {
-        Ok(obj.extract::<Vec<u8>>()?)
-    } else if obj.is_instance_of::<PyString>() {
+/// Text input, borrowed from a Python buffer where possible.
+///
+/// Anything supporting the buffer protocol (bytes, bytearray, memoryview,
+/// mmap, uint8 numpy arrays) is read in place, and the buffer is held until
+/// this is dropped, so e.g. a bytearray can't be resized underneath it. str
+/// input is encoded to UTF-8.
+enum Text {
+    Buffer(PyBuffer<u8>),
+    Str(Vec<u8>),
+}
+
+impl Text {
+    /// The text as UTF-8, validated in place.
+    fn to_str(&self) -> PyResult<&str> {
+        std::str::from_utf8(self.as_ref()).map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("text must be valid UTF-8")
+        })
+    }
+}
+
+impl AsRef<[u8]> for Text {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Text::Buffer(buf) if buf.len_bytes() == 0 => &[],
+            // SAFETY: extract_text only accepts C-contiguous byte buffers, and
+            // the memory stays valid while the buffer is held
+            Text::Buffer(buf) => unsafe {
+                std::slice::from_raw_parts(buf.buf_ptr() as *const u8, buf.len_bytes())
+            },
+            Text::Str(bytes) => bytes,
+        }
+    }
+}
+
+/// Extract text from a str or any bytes-like object, without copying buffers.
+fn extract_text(obj: &Bound<'_, PyAny>) -> PyResult<Text> {
+    if obj.is_instance_of::<PyString>() {
         let s: String = obj.extract()?;
-        Ok(s.into_bytes())
-    } else {
-        Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-            "expected bytes or str",
+        return Ok(Text::Str(s.into_bytes()));
+    }
+    let buf = PyBuffer::<u8>::get(obj).map_err(|err| {
+        if err.is_instance_of::<pyo3::exceptions::PyTypeError>(obj.py()) {
+            PyErr::new::<pyo3::exceptions::PyTypeError, _>("expected str or a bytes-like object")
+        } else {
+            err
+        }
+    })?;
+    if !buf.is_c_contiguous() {
+        return Err(PyErr::new::<pyo3::exceptions::PyBufferError, _>(
+            "text buffer must be C-contiguous",
+        ));
+    }
+    Ok(Text::Buffer(buf))
+}
+
+/// A flat byte memoryview of a bytes-like object, for slicing chunks from.
+fn byte_view(obj: &Bound<'_, PyAny>) -> PyResult<Py<PyMemoryView>> {
+    Ok(PyMemoryView::from(obj)?
+        .call_method1("cast", ("B",))?
+        .cast_into::<PyMemoryView>()?
+        .unbind())
+}
+
+/// A chunk as a memoryview slice of `view`, or as a bytes copy without one.
+fn chunk_object(
+    py: Python<'_>,
+    view: Option<&Py<PyMemoryView>>,
+    chunk: &chunk::Chunk<'_>,
+) -> PyResult<Py<PyAny>> {
+    match view {
+        Some(view) => {
+            let slice = PySlice::new(py, chunk.start as isize, chunk.end as isize, 1);
+            Ok(view.bind(py).get_item(slice)?.unbind())
+        }
+        None => Ok(PyBytes::new(py, chunk.bytes()).into_any().unbind()),
+    }
+}
+
+/// Extract an owned copy of str or bytes-like input (delimiters, patterns).
+fn extract_bytes(obj: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    Ok(extract_text(obj)?.as_ref().to_vec())
+}
+
+/// Parse a size unit name ("bytes" or "chars").
+fn parse_unit(unit: &str) -> PyResult<SizeUnit> {
+    match unit {
+        "bytes" => Ok(SizeUnit::Bytes),
+        "chars" => Ok(SizeUnit::Chars),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "unit must be 'bytes' or 'chars'",
+        )),
+    }
+}
+
+/// The delimiter class `obj` names, if it's a Delimiters or a class name
+/// such as "sentences" rather than the delimiters themselves.
+fn delimiter_class(obj: &Bound<'_, PyAny>) -> Option<RustDelimiters> {
+    if let Ok(class) = obj.cast::<Delimiters>() {
+        return Some(class.get().inner);
+    }
+    obj.extract::<&str>().ok()?.parse().ok()
+}
+
+/// Raise invalid chunker settings as ValueError.
+fn invalid_config(err: ChunkError) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(err.to_string())
+}
+
+/// Maximum number of compiled patterns kept in the regex cache.
+const REGEX_CACHE_SIZE: usize = 64;
+
+/// Compile a regex pattern, reusing earlier compilations of the same pattern.
+///
+/// The cache is cleared once it holds `REGEX_CACHE_SIZE` patterns, so callers
+/// cycling through many one-off patterns can't grow it without bound.
+fn compile_regex(pattern: &str) -> PyResult<RustRegexChunker> {
+    static CACHE: OnceLock<Mutex<HashMap<String, RustRegexChunker>>> = OnceLock::new();
+    let mut cache = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(chunker) = cache.get(pattern) {
+        return Ok(chunker.clone());
+    }
+    let chunker = RustRegexChunker::new(pattern)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    if cache.len() >= REGEX_CACHE_SIZE {
+        cache.clear();
+    }
+    cache.insert(pattern.to_string(), chunker.clone());
+    Ok(chunker)
+}
+
+/// Build a regex chunker with the given options from a cached pattern.
+fn regex_chunker(
+    pattern: &str,
+    size: usize,
+    prefix: bool,
+    forward_fallback: bool,
+    utf8_safe: bool,
+) -> PyResult<RustRegexChunker> {
+    let mut chunker = compile_regex(pattern)?.size(size).utf8_safe(utf8_safe);
+    if prefix {
+        chunker = chunker.prefix();
+    }
+    if forward_fallback {
+        chunker = chunker.forward_fallback();
+    }
+    Ok(chunker)
+}
+
+/// A chunk with its position in the text.
+///
+/// Attributes:
+///     index: Zero-based position of the chunk in the sequence.
+///     start: Byte offset where the chunk starts.
+///     end: Byte offset where the chunk ends (exclusive).
+///     bytes: The chunk bytes.
+#[pyclass(frozen)]
+pub struct Chunk {
+    #[pyo3(get)]
+    index: usize,
+    #[pyo3(get)]
+    start: usize,
+    #[pyo3(get)]
+    end: usize,
+    #[pyo3(get)]
+    bytes: Py<PyBytes>,
+}
+
+impl Chunk {
+    fn from_info(py: Python<'_>, chunk: chunk::Chunk<'_>) -> Self {
+        Self {
+            index: chunk.index,
+            start: chunk.start,
+            end: chunk.end,
+            bytes: PyBytes::new(py, chunk.bytes()).unbind(),
+        }
+    }
+}
+
+#[pymethods]
+impl Chunk {
+    /// The chunk decoded as UTF-8 (raises ValueError if it isn't valid).
+    #[getter]
+    fn text(&self, py: Python<'_>) -> PyResult<String> {
+        std::str::from_utf8(self.bytes.as_bytes(py))
+            .map(str::to_owned)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
+    fn __len__(&self) -> usize {
+        self.end - self.start
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Chunk(index={}, start={}, end={})",
+            self.index, self.start, self.end
+        )
+    }
+}
+
+/// A named class of delimiters, to pass as delimiters= instead of a byte set.
+///
+/// The class names work as strings too, e.g. delimiters="sentences".
+///
+/// Example:
+///     >>> list(Chunker(b"Split these words", size=10, delimiters=Delimiters.WHITESPACE))
+///     [b'Split ', b'these ', b'words']
+///     >>> Delimiters.NEWLINES.name
+///     'newlines'
+#[pyclass(frozen, eq, hash, module = "chonkie_core._chunk")]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Delimiters {
+    inner: RustDelimiters,
+}
+
+#[pymethods]
+impl Delimiters {
+    /// Sentence ends and line breaks: `.`, `?`, `!` and `\n`.
+    #[classattr]
+    const SENTENCES: Delimiters = Delimiters {
+        inner: RustDelimiters::SENTENCES,
+    };
+
+    /// Spaces, tabs and line breaks, so chunks end between words.
+    #[classattr]
+    const WHITESPACE: Delimiters = Delimiters {
+        inner: RustDelimiters::WHITESPACE,
+    };
+
+    /// Line breaks.
+    #[classattr]
+    const NEWLINES: Delimiters = Delimiters {
+        inner: RustDelimiters::NEWLINES,
+    };
+
+    /// Markdown headings: chunks start at a line beginning with `#`.
+    #[classattr]
+    const MARKDOWN_HEADINGS: Delimiters = Delimiters {
+        inner: RustDelimiters::MARKDOWN_HEADINGS,
+    };
+
+    /// Chinese and Japanese sentence ends and line breaks: `。`, `！`, `？`,
+    /// `、` and `\n`.
+    #[classattr]
+    const CJK: Delimiters = Delimiters {
+        inner: RustDelimiters::CJK,
+    };
+
+    /// SENTENCES and CJK together, for text that mixes scripts.
+    #[classattr]
+    const MULTILINGUAL: Delimiters = Delimiters {
+        inner: RustDelimiters::MULTILINGUAL,
+    };
+
+    /// Look up a class by name (raises ValueError for an unknown one).
+    #[new]
+    fn new(name: &str) -> PyResult<Self> {
+        let inner = name.parse().map_err(|e: chunk::UnknownDelimiters| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string())
+        })?;
+        Ok(Self { inner })
+    }
+
+    /// The class name, e.g. "sentences".
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Delimiters.{}", self.inner.name().to_ascii_uppercase())
+    }
+}
+
+/// Chunker options as one reusable, immutable value.
+///
+/// Takes the same keyword arguments as Chunker (without the text). Pass it
+/// as config= to Chunker, chunk_offsets or chunk_offsets_batch in place of
+/// the individual options.
+///
+/// Raises ValueError when size is 0, or when overlap isn't smaller than a
+/// size in bytes.
+///
+/// Example:
+///     >>> config = ChunkerConfig(size=1024, delimiters=".\n", overlap=64)
+///     >>> offsets = chunk_offsets(text, config=config)
+///     >>> chunks = Chunker(text, config=config).collect_chunks()
+#[pyclass(frozen, eq, module = "chonkie_core._chunk")]
+#[derive(Clone, PartialEq)]
+pub struct ChunkerConfig {
+    inner: RustChunkerConfig,
+    /// Unset means true for str input, like the keyword argument
+    utf8_safe: Option<bool>,
+}
+
+/// A callable and its arguments, as __reduce__ returns for pickling.
+type Reduced<'py, Args> = (Bound<'py, PyAny>, Args);
+
+impl ChunkerConfig {
+    /// The core config, with utf8_safe defaulting to `str_input`.
+    fn resolved(&self, str_input: bool) -> RustChunkerConfig {
+        let mut config = self.inner.clone();
+        config.utf8_safe = self.utf8_safe.unwrap_or(str_input);
+        config
+    }
+
+    /// Wrap an already resolved config, keeping its utf8_safe for any input.
+    fn pinned(inner: RustChunkerConfig) -> Self {
+        let utf8_safe = Some(inner.utf8_safe);
+        Self { inner, utf8_safe }
+    }
+
+    /// Wrap a config loaded from a file or string.
+    ///
+    /// A file can't leave utf8_safe unset, so only true is kept; false falls
+    /// back to the per-input default.
+    fn loaded(config: Result<RustChunkerConfig, ConfigError>) -> PyResult<Self> {
+        let inner = config.map_err(|e| match e {
+            ConfigError::Io(err) => PyErr::from(err),
+            err => PyErr::new::<pyo3::exceptions::PyValueError, _>(err.to_string()),
+        })?;
+        inner.validate().map_err(invalid_config)?;
+        let utf8_safe = inner.utf8_safe.then_some(true);
+        Ok(Self { inner, utf8_safe })
+    }
+
+    /// The options as keyword arguments, leaving out defaults.
+    fn options_repr(&self) -> String {
+        let bytes = |b: &[u8]| format!("b\"{}\"", b.escape_ascii());
+        let mut repr = format!(
+            "size={}, delimiters={}",
+            self.inner.size,
+            bytes(&self.inner.delimiters)
+        );
+        if let Some(pattern) = &self.inner.pattern {
+            repr += &format!(", pattern={}", bytes(pattern));
+        }
+        if !self.inner.patterns.is_empty() {
+            let patterns: Vec<_> = self.inner.patterns.iter().map(|p| bytes(p)).collect();
+            repr += &format!(", patterns=[{}]", patterns.join(", "));
+        }
+        for (name, enabled) in [
+            ("prefix", self.inner.prefix),
+            ("consecutive", self.inner.consecutive),
+            ("forward_fallback", self.inner.forward_fallback),
+        ] {
+            if enabled {
+                repr += &format!(", {name}=True");
+            }
+        }
+        match self.inner.overlap_ratio {
+            Some(ratio) => repr += &format!(", overlap_ratio={ratio}"),
+            None if self.inner.overlap > 0 => repr += &format!(", overlap={}", self.inner.overlap),
+            None => {}
+        }
+        if let Some(utf8_safe) = self.utf8_safe {
+            repr += &format!(", utf8_safe={}", if utf8_safe { "True" } else { "False" });
+        }
+        if self.inner.unit != SizeUnit::Bytes {
+            repr += &format!(", unit=\"{}\"", self.unit());
+        }
+        if self.inner.min_size > 0 {
+            repr += &format!(", min_size={}", self.inner.min_size);
+        }
+        if let Some(max_size) = self.inner.max_size {
+            repr += &format!(", max_size={max_size}");
+        }
+        for (name, enabled) in [
+            ("trim", self.inner.trim),
+            ("skip_empty", self.inner.skip_empty),
+            ("delimiter_priority", self.inner.delimiter_priority),
+            ("balanced", self.inner.balanced),
+        ] {
+            if enabled {
+                repr += &format!(", {name}=True");
+            }
+        }
+        if self.inner.slack > 0 {
+            repr += &format!(", slack={}", self.inner.slack);
+        }
+        if let Some(lines) = self.inner.lines_per_chunk {
+            repr += &format!(", lines_per_chunk={lines}");
+        }
+        repr
+    }
+
+    /// `config` if given, else one built from the other keyword arguments.
+    #[allow(clippy::too_many_arguments)]
+    fn resolve(
+        config: Option<&Self>,
+        size: usize,
+        delimiters: Option<&Bound<'_, PyAny>>,
+        pattern: Option<&Bound<'_, PyAny>>,
+        patterns: Option<Vec<Bound<'_, PyAny>>>,
+        prefix: bool,
+        consecutive: bool,
+        forward_fallback: bool,
+        overlap: usize,
+        overlap_ratio: Option<f64>,
+        utf8_safe: Option<bool>,
+        unit: &str,
+        min_size: usize,
+        max_size: Option<usize>,
+        trim: bool,
+        skip_empty: bool,
+        delimiter_priority: bool,
+        slack: usize,
+        balanced: bool,
+        lines_per_chunk: Option<usize>,
+    ) -> PyResult<Self> {
+        match config {
+            Some(config) => Ok(config.clone()),
+            None => Self::new(
+                size,
+                delimiters,
+                pattern,
+                patterns,
+                prefix,
+                consecutive,
+                forward_fallback,
+                overlap,
+                overlap_ratio,
+                utf8_safe,
+                unit,
+                min_size,
+                max_size,
+                trim,
+                skip_empty,
+                delimiter_priority,
+                slack,
+                balanced,
+                lines_per_chunk,
+            ),
+        }
+    }
+}
+
+#[pymethods]
+impl ChunkerConfig {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (size=DEFAULT_TARGET_SIZE, delimiters=None, pattern=None, patterns=None, prefix=false, consecutive=false, forward_fallback=false, overlap=0, overlap_ratio=None, utf8_safe=None, unit="bytes", min_size=0, max_size=None, trim=false, skip_empty=false, delimiter_priority=false, slack=0, balanced=false, lines_per_chunk=None))]
+    fn new(
+        size: usize,
+        delimiters: Option<&Bound<'_, PyAny>>,
+        pattern: Option<&Bound<'_, PyAny>>,
+        patterns: Option<Vec<Bound<'_, PyAny>>>,
+        prefix: bool,
+        consecutive: bool,
+        forward_fallback: bool,
+        overlap: usize,
+        overlap_ratio: Option<f64>,
+        utf8_safe: Option<bool>,
+        unit: &str,
+        min_size: usize,
+        max_size: Option<usize>,
+        trim: bool,
+        skip_empty: bool,
+        delimiter_priority: bool,
+        slack: usize,
+        balanced: bool,
+        lines_per_chunk: Option<usize>,
+    ) -> PyResult<Self> {
+        let mut inner = RustChunkerConfig::new()
+            .size(size)
+            .prefix(prefix)
+            .consecutive(consecutive)
+            .forward_fallback(forward_fallback)
+            .overlap(overlap)
+            .unit(parse_unit(unit)?)
+            .min_size(min_size)
+            .max_size(max_size)
+            .trim(trim)
+            .skip_empty(skip_empty)
+            .delimiter_priority(delimiter_priority)
+            .slack(slack)
+            .balanced(balanced)
+            .lines_per_chunk(lines_per_chunk);
+        if let Some(d) = delimiters {
+            inner = match delimiter_class(d) {
+                Some(class) => inner.delimiter_class(class),
+                None => inner.delimiters(extract_bytes(d)?),
+            };
+        }
+        // Pattern (singular) takes precedence over delimiters if both specified
+        if let Some(p) = pattern {
+            inner = inner.pattern(extract_bytes(p)?);
+        }
+        // Patterns (plural) is composable with delimiters
+        if let Some(pats) = patterns {
+            let pattern_bytes = pats
+                .iter()
+                .map(|p| extract_bytes(p))
+                .collect::<PyResult<Vec<Vec<u8>>>>()?;
+            inner = inner.patterns(&pattern_bytes);
+        }
+        if let Some(ratio) = overlap_ratio {
+            inner = inner.overlap_ratio(ratio);
+        }
+        inner.validate().map_err(invalid_config)?;
+        Ok(Self { inner, utf8_safe })
+    }
+
+    /// Load a config from a TOML or JSON file (by extension: .json is JSON).
+    ///
+    /// With profile, read the table of that name, e.g. [rag-default].
+    #[staticmethod]
+    #[pyo3(signature = (path, profile=None))]
+    fn from_file(path: std::path::PathBuf, profile: Option<&str>) -> PyResult<Self> {
+        Self::loaded(RustChunkerConfig::from_file(path, profile))
+    }
+
+    /// Parse a config (or the named profile) from a TOML string.
+    #[staticmethod]
+    #[pyo3(signature = (text, profile=None))]
+    fn from_toml(text: &str, profile: Option<&str>) -> PyResult<Self> {
+        Self::loaded(match profile {
+            Some(name) => RustChunkerConfig::from_toml_profile(text, name),
+            None => RustChunkerConfig::from_toml(text),
+        })
+    }
+
+    /// Parse a config (or the named profile) from a JSON string.
+    #[staticmethod]
+    #[pyo3(signature = (text, profile=None))]
+    fn from_json(text: &str, profile: Option<&str>) -> PyResult<Self> {
+        Self::loaded(match profile {
+            Some(name) => RustChunkerConfig::from_json_profile(text, name),
+            None => RustChunkerConfig::from_json(text),
+        })
+    }
+
+    /// Pickle support: the config as JSON, plus whether utf8_safe is set.
+    fn __reduce__<'py>(slf: &Bound<'py, Self>) -> PyResult<Reduced<'py, (String, Option<bool>)>> {
+        let config = slf.get();
+        Ok((
+            slf.get_type().getattr("_unpickle")?,
+            (config.inner.to_json(), config.utf8_safe),
         ))
     }
+
+    #[classmethod]
+    fn _unpickle(
+        _cls: &Bound<'_, pyo3::types::PyType>,
+        json: &str,
+        utf8_safe: Option<bool>,
+    ) -> PyResult<Self> {
+        let inner = Self::loaded(RustChunkerConfig::from_json(json))?.inner;
+        Ok(Self { inner, utf8_safe })
+    }
+
+    #[getter]
+    fn size(&self) -> usize {
+        self.inner.size
+    }
+
+    #[getter]
+    fn delimiters<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.delimiters)
+    }
+
+    #[getter]
+    fn pattern<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyBytes>> {
+        self.inner.pattern.as_deref().map(|p| PyBytes::new(py, p))
+    }
+
+    #[getter]
+    fn patterns<'py>(&self, py: Python<'py>) -> Vec<Bound<'py, PyBytes>> {
+        self.inner
+            .patterns
+            .iter()
+            .map(|p| PyBytes::new(py, p))
+            .collect()
+    }
+
+    #[getter]
+    fn prefix(&self) -> bool {
+        self.inner.prefix
+    }
+
+    #[getter]
+    fn consecutive(&self) -> bool {
+        self.inner.consecutive
+    }
+
+    #[getter]
+    fn forward_fallback(&self) -> bool {
+        self.inner.forward_fallback
+    }
+
+    #[getter]
+    fn overlap(&self) -> usize {
+        self.inner.overlap
+    }
+
+    /// The overlap as a share of size, if set that way.
+    #[getter]
+    fn overlap_ratio(&self) -> Option<f64> {
+        self.inner.overlap_ratio
+    }
+
+    /// None means True for str input and False for bytes.
+    #[getter]
+    fn utf8_safe(&self) -> Option<bool> {
+        self.utf8_safe
+    }
+
+    #[getter]
+    fn unit(&self) -> &'static str {
+        match self.inner.unit {
+            SizeUnit::Bytes => "bytes",
+            SizeUnit::Chars => "chars",
+        }
+    }
+
+    #[getter]
+    fn min_size(&self) -> usize {
+        self.inner.min_size
+    }
+
+    #[getter]
+    fn max_size(&self) -> Option<usize> {
+        self.inner.max_size
+    }
+
+    #[getter]
+    fn trim(&self) -> bool {
+        self.inner.trim
+    }
+
+    #[getter]
+    fn skip_empty(&self) -> bool {
+        self.inner.skip_empty
+    }
+
+    #[getter]
+    fn delimiter_priority(&self) -> bool {
+        self.inner.delimiter_priority
+    }
+
+    #[getter]
+    fn slack(&self) -> usize {
+        self.inner.slack
+    }
+
+    #[getter]
+    fn balanced(&self) -> bool {
+        self.inner.balanced
+    }
+
+    #[getter]
+    fn lines_per_chunk(&self) -> Option<usize> {
+        self.inner.lines_per_chunk
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ChunkerConfig({})", self.options_repr())
+    }
+}
+
+/// A Python callable ranking split points, called as `scorer(start, end)`.
+struct PyScorer {
+    callback: Py<PyAny>,
+    /// First exception the callback raised, for the Chunker to re-raise
+    error: Arc<Mutex<Option<PyErr>>>,
+}
+
+impl BoundaryScorer for PyScorer {
+    fn score(&self, _text: &[u8], start: usize, at: usize) -> f64 {
+        let mut error = self.error.lock().unwrap();
+        if error.is_some() {
+            return f64::NEG_INFINITY;
+        }
+        Python::attach(|py| {
+            self.callback
+                .call1(py, (start, at))
+                .and_then(|score| score.extract::<f64>(py))
+                .unwrap_or_else(|err| {
+                    *error = Some(err);
+                    f64::NEG_INFINITY
+                })
+        })
+    }
+}
+
+/// Raise the first exception a scorer callback left in `error`, if any.
+fn check_scorer(error: Option<&Mutex<Option<PyErr>>>) -> PyResult<()> {
+    match error.and_then(|error| error.lock().unwrap().take()) {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+fn chunk_index_error() -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyIndexError, _>("chunk index out of range")
 }
 
 /// Chunker splits text at delimiter boundaries.
@@ -38,24 +745,148 @@ fn extract_bytes(obj: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
 ///     >>> for chunk in Chunker(text, size=15, pattern=metaspace, prefix=True):
 ///     ...     print(chunk)
 ///
+/// Example with multi-byte delimiters (the longest match wins):
+///     >>> text = "Para one.\n\nPara two。More"
+///     >>> for chunk in Chunker(text, size=12, delimiters="", patterns=["\n", "\n\n", "。"]):
+///     ...     print(chunk)
+///
 /// Example with consecutive pattern handling:
 ///     >>> text = "word   next"  # Three spaces
 ///     >>> for chunk in Chunker(text, pattern=" ", consecutive=True):
 ///     ...     print(chunk)  # Splits at START of "   ", not middle
 ///
+/// Example with overlapping chunks (trailing context carried into the next chunk):
+///     >>> for chunk in Chunker(text, size=4096, overlap=256):
+///     ...     print(chunk)
+///
 /// Also accepts str (encoded as UTF-8):
 ///     >>> text = "Hello. World. Test."
 ///     >>> for chunk in Chunker(text, size=10, delimiters="."):
 ///     ...     print(chunk)
-#[pyclass]
+///
+/// Example merging a tiny trailing chunk into the previous one:
+///     >>> for chunk in Chunker(text, size=4096, min_size=64):
+///     ...     print(chunk)
+///
+/// Example with a strict byte cap (size is only a target):
+///     >>> for chunk in Chunker(text, size=4096, forward_fallback=True, max_size=8192):
+///     ...     print(chunk)
+///
+/// Example measuring size in characters instead of bytes:
+///     >>> for chunk in Chunker("日本語のテキスト。", size=1024, unit="chars"):
+///     ...     print(chunk)
+///
+/// Example with options from a reusable ChunkerConfig:
+///     >>> config = ChunkerConfig(size=4096, overlap=256)
+///     >>> for chunk in Chunker(text, config=config):
+///     ...     print(chunk)
+///
+/// Example with a named profile from a TOML (or .json) file:
+///     >>> for chunk in Chunker.from_config("profiles.toml", text, profile="rag-default"):
+///     ...     print(chunk)
+///
+/// Example chunking a large file without reading it into Python first:
+///     >>> for chunk in Chunker.from_file("corpus.txt", size=4096):
+///     ...     print(chunk)
+///
+/// Example yielding memoryview slices instead of bytes copies:
+///     >>> for chunk in Chunker(huge_document, size=4096, copy=False):
+///     ...     print(bytes(chunk))
+///
+/// Example choosing split points with a scorer, e.g. an embedding
+/// similarity drop: scorer(start, end) is called for every delimiter in
+/// the window, with the byte offsets of the chunk splitting there would
+/// give, and the highest score wins:
+///     >>> def score(start, end):
+///     ...     return 1.0 - similarity(data[end - 200:end], data[end:end + 200])
+///     >>> for chunk in Chunker(data, size=4096, scorer=score):
+///     ...     print(chunk)
+///
+/// For str input, forced splits never land inside a multi-byte character,
+/// so every chunk decodes cleanly. Pass utf8_safe=True to get the same
+/// behavior for bytes, or utf8_safe=False to turn it off.
+///
+/// With copy=False, iterating and collect_chunks() return memoryviews into
+/// the original buffer (str input is encoded once up front), so no chunk is
+/// copied. The views stay valid after the chunker is gone.
+#[pyclass(module = "chonkie_core._chunk")]
 pub struct Chunker {
     inner: OwnedChunker,
+    /// The resolved config, copy and scorer, to rebuild the chunker when
+    /// unpickling
+    config: ChunkerConfig,
+    copy: bool,
+    scorer: Option<Py<PyAny>>,
+    /// Byte view of the text that chunks are sliced from, when copy=False
+    view: Option<Py<PyMemoryView>>,
+    /// Iterating yields chunks from the end, after reversed()
+    backward: bool,
+    /// Total chunk count, once len() has computed it
+    len: Option<usize>,
+    /// Where the scorer callback leaves its first exception
+    scorer_error: Option<Arc<Mutex<Option<PyErr>>>>,
+}
+
+impl Chunker {
+    /// Chunk `index` as iteration yields it, or IndexError.
+    fn item(&mut self, py: Python<'_>, index: usize) -> PyResult<Py<PyAny>> {
+        let chunk = self.inner.chunk_at(index);
+        check_scorer(self.scorer_error.as_deref())?;
+        let chunk = chunk.ok_or_else(chunk_index_error)?;
+        chunk_object(py, self.view.as_ref(), &chunk)
+    }
+
+    /// A chunker over `text` with `config`, yielding views when `copy` is false.
+    fn with_config(
+        text: &Bound<'_, PyAny>,
+        config: &ChunkerConfig,
+        copy: bool,
+        scorer: Option<Py<PyAny>>,
+    ) -> PyResult<Self> {
+        // Views need a buffer to point into, so str is encoded to bytes once
+        let source = match text.cast::<PyString>() {
+            Ok(s) if !copy => s.encode_utf8()?.into_any(),
+            _ => text.clone(),
+        };
+        let view = if copy {
+            None
+        } else {
+            Some(byte_view(&source)?)
+        };
+        let resolved = config.resolved(text.is_instance_of::<PyString>());
+        let inner = resolved.owned_chunker(extract_text(&source)?);
+        let config = ChunkerConfig::pinned(resolved);
+        let kept_scorer = scorer.as_ref().map(|s| s.clone_ref(text.py()));
+        let (inner, scorer_error) = match scorer {
+            Some(callback) => {
+                let error = Arc::new(Mutex::new(None));
+                let scorer = PyScorer {
+                    callback,
+                    error: error.clone(),
+                };
+                (inner.boundary_scorer(scorer), Some(error))
+            }
+            None => (inner, None),
+        };
+
+        Ok(Self {
+            inner,
+            config,
+            copy,
+            scorer: kept_scorer,
+            view,
+            backward: false,
+            len: None,
+            scorer_error,
+        })
+    }
 }
 
 #[pymethods]
 impl Chunker {
     #[new]
-    #[pyo3(signature = (text, size=DEFAULT_TARGET_SIZE, delimiters=None, pattern=None, patterns=None, prefix=false, consecutive=false, forward_fallback=false))]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (text, size=DEFAULT_TARGET_SIZE, delimiters=None, pattern=None, patterns=None, prefix=false, consecutive=false, forward_fallback=false, overlap=0, overlap_ratio=None, utf8_safe=None, unit="bytes", min_size=0, max_size=None, trim=false, skip_empty=false, delimiter_priority=false, slack=0, balanced=false, lines_per_chunk=None, copy=true, config=None, scorer=None))]
     fn new(
         text: &Bound<'_, PyAny>,
         size: usize,
@@ -65,74 +896,414 @@ impl Chunker {
         prefix: bool,
         consecutive: bool,
         forward_fallback: bool,
+        overlap: usize,
+        overlap_ratio: Option<f64>,
+        utf8_safe: Option<bool>,
+        unit: &str,
+        min_size: usize,
+        max_size: Option<usize>,
+        trim: bool,
+        skip_empty: bool,
+        delimiter_priority: bool,
+        slack: usize,
+        balanced: bool,
+        lines_per_chunk: Option<usize>,
+        copy: bool,
+        config: Option<&ChunkerConfig>,
+        scorer: Option<Py<PyAny>>,
     ) -> PyResult<Self> {
-        let text_bytes = extract_bytes(text)?;
+        let config = ChunkerConfig::resolve(
+            config,
+            size,
+            delimiters,
+            pattern,
+            patterns,
+            prefix,
+            consecutive,
+            forward_fallback,
+            overlap,
+            overlap_ratio,
+            utf8_safe,
+            unit,
+            min_size,
+            max_size,
+            trim,
+            skip_empty,
+            delimiter_priority,
+            slack,
+            balanced,
+            lines_per_chunk,
+        )?;
+        Self::with_config(text, &config, copy, scorer)
+    }
 
-        let mut inner = OwnedChunker::new(text_bytes).size(size);
+    /// Create a chunker over text with options from a TOML or JSON file.
+    ///
+    /// Same as Chunker(text, config=ChunkerConfig.from_file(path, profile)).
+    #[staticmethod]
+    #[pyo3(signature = (path, text, profile=None, copy=true))]
+    fn from_config(
+        path: std::path::PathBuf,
+        text: &Bound<'_, PyAny>,
+        profile: Option<&str>,
+        copy: bool,
+    ) -> PyResult<Self> {
+        let config = ChunkerConfig::from_file(path, profile)?;
+        Self::with_config(text, &config, copy, None)
+    }
 
-        // Pattern (singular) takes precedence over delimiters if both specified
-        if let Some(p) = pattern {
-            let pattern_bytes = extract_bytes(p)?;
-            inner = inner.pattern(pattern_bytes);
-        } else {
-            let delims = match delimiters {
-                Some(d) => extract_bytes(d)?,
-                None => DEFAULT_DELIMITERS.to_vec(),
-            };
-            inner = inner.delimiters(delims);
+    /// Create a chunker over a file, given its path or an open file object.
+    ///
+    /// A path is memory-mapped in Rust, so even huge files are chunked
+    /// straight from the page cache without being read into Python. A file
+    /// object is read once with its read() method; text-mode files are
+    /// utf8_safe by default, like str input. Takes the same options as
+    /// Chunker. The file must not change while the chunker is in use.
+    ///
+    /// Example:
+    ///     >>> offsets = Chunker.from_file("corpus.txt", size=4096).collect_offsets()
+    ///     >>> with open("notes.md", encoding="utf-8") as f:
+    ///     ...     chunks = Chunker.from_file(f, size=1024).collect_chunks()
+    #[staticmethod]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (source, size=DEFAULT_TARGET_SIZE, delimiters=None, pattern=None, patterns=None, prefix=false, consecutive=false, forward_fallback=false, overlap=0, overlap_ratio=None, utf8_safe=None, unit="bytes", min_size=0, max_size=None, trim=false, skip_empty=false, delimiter_priority=false, slack=0, balanced=false, lines_per_chunk=None, config=None))]
+    fn from_file(
+        source: &Bound<'_, PyAny>,
+        size: usize,
+        delimiters: Option<&Bound<'_, PyAny>>,
+        pattern: Option<&Bound<'_, PyAny>>,
+        patterns: Option<Vec<Bound<'_, PyAny>>>,
+        prefix: bool,
+        consecutive: bool,
+        forward_fallback: bool,
+        overlap: usize,
+        overlap_ratio: Option<f64>,
+        utf8_safe: Option<bool>,
+        unit: &str,
+        min_size: usize,
+        max_size: Option<usize>,
+        trim: bool,
+        skip_empty: bool,
+        delimiter_priority: bool,
+        slack: usize,
+        balanced: bool,
+        lines_per_chunk: Option<usize>,
+        config: Option<&ChunkerConfig>,
+    ) -> PyResult<Self> {
+        let config = ChunkerConfig::resolve(
+            config,
+            size,
+            delimiters,
+            pattern,
+            patterns,
+            prefix,
+            consecutive,
+            forward_fallback,
+            overlap,
+            overlap_ratio,
+            utf8_safe,
+            unit,
+            min_size,
+            max_size,
+            trim,
+            skip_empty,
+            delimiter_priority,
+            slack,
+            balanced,
+            lines_per_chunk,
+        )?;
+        if source.hasattr("read")? {
+            let text = source.call_method0("read")?;
+            return Self::with_config(&text, &config, true, None);
         }
+        let path: std::path::PathBuf = source.extract()?;
+        let mapped = MappedText::open(path)?;
+        let resolved = config.resolved(false);
+        Ok(Self {
+            inner: resolved.owned_chunker(mapped),
+            config: ChunkerConfig::pinned(resolved),
+            copy: true,
+            scorer: None,
+            view: None,
+            backward: false,
+            len: None,
+            scorer_error: None,
+        })
+    }
 
-        // Patterns (plural) is composable with delimiters — applied after
-        if let Some(pats) = patterns {
-            let pattern_strings: Vec<String> = pats
-                .iter()
-                .map(|p| {
-                    extract_bytes(p).and_then(|b| {
-                        String::from_utf8(b).map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                                "patterns must be valid UTF-8: {}",
-                                e
-                            ))
-                        })
-                    })
-                })
-                .collect::<PyResult<Vec<String>>>()?;
-            let pattern_refs: Vec<&str> = pattern_strings.iter().map(|s| s.as_str()).collect();
-            inner = inner.patterns(&pattern_refs);
-        }
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
 
-        if prefix {
-            inner = inner.prefix();
+    /// Pickle support: rebuilt as Chunker(text, config=..., copy=...,
+    /// scorer=...) over a copy of the text, then moved to the same position
+    /// by __setstate__.
+    fn __getnewargs_ex__<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<((Bound<'py, PyBytes>,), Bound<'py, PyDict>)> {
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("config", self.config.clone())?;
+        kwargs.set_item("copy", self.copy)?;
+        kwargs.set_item("scorer", self.scorer.as_ref().map(|s| s.clone_ref(py)))?;
+        Ok(((PyBytes::new(py, self.inner.text()),), kwargs))
+    }
+
+    /// Pickle support: chunks taken from the front and from the back, and
+    /// whether iteration is reversed.
+    fn __getstate__(&self) -> (usize, usize, bool) {
+        let (front, back) = self.inner.cursor();
+        (front, back, self.backward)
+    }
+
+    fn __setstate__(&mut self, state: (usize, usize, bool)) -> PyResult<()> {
+        let (front, back, backward) = state;
+        self.inner.set_cursor(front, back);
+        self.backward = backward;
+        check_scorer(self.scorer_error.as_deref())
+    }
+
+    /// Get a chunk by index (negative counts from the end), or a list of
+    /// chunks by slice, without moving the iteration position.
+    ///
+    /// Negative indices and slices count all chunks first, like len().
+    /// Raises IndexError for an index out of range.
+    ///
+    /// Example:
+    ///     >>> chunker = Chunker(b"One. Two. Three.", size=7, delimiters=b".")
+    ///     >>> chunker[0], chunker[-1], chunker[1:]
+    ///     >>> # (b'One.', b' Three.', [b' Two.', b' Three.'])
+    fn __getitem__(&mut self, py: Python<'_>, index: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        if let Ok(slice) = index.cast::<PySlice>() {
+            let indices = slice.indices(self.__len__()? as isize)?;
+            let chunks = (0..indices.slicelength)
+                .map(|i| self.item(py, (indices.start + i as isize * indices.step) as usize))
+                .collect::<PyResult<Vec<_>>>()?;
+            return Ok(PyList::new(py, chunks)?.into_any().unbind());
         }
-        if consecutive {
-            inner = inner.consecutive();
+        let index: isize = index.extract()?;
+        let index = if index < 0 {
+            index + self.__len__()? as isize
+        } else {
+            index
+        };
+        if index < 0 {
+            return Err(chunk_index_error());
         }
-        if forward_fallback {
-            inner = inner.forward_fallback();
+        self.item(py, index as usize)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Chunker(text=<{} bytes>, {})",
+            self.inner.text().len(),
+            self.config.options_repr()
+        )
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Release the text, and the buffer it pins, on leaving a with block.
+    ///
+    /// The chunker has no chunks afterwards; chunks already taken with
+    /// copy=False keep the buffer alive until they're gone too.
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, _args: &Bound<'_, pyo3::types::PyTuple>) -> bool {
+        self.inner = OwnedChunker::new(Vec::new());
+        self.view = None;
+        self.len = None;
+        self.backward = false;
+        false
+    }
+
+    /// Total number of chunks in the text, however far iteration has got.
+    ///
+    /// Counted without creating any chunk objects, then cached.
+    fn __len__(&mut self) -> PyResult<usize> {
+        if let Some(len) = self.len {
+            return Ok(len);
         }
+        let len = self.inner.count_chunks();
+        check_scorer(self.scorer_error.as_deref())?;
+        Ok(*self.len.insert(len))
+    }
 
-        Ok(Self { inner })
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<Py<PyAny>>> {
+        let chunk = if self.backward {
+            self.inner.next_chunk_back()
+        } else {
+            self.inner.next_chunk_info()
+        };
+        check_scorer(self.scorer_error.as_deref())?;
+        chunk
+            .map(|chunk| chunk_object(py, self.view.as_ref(), &chunk))
+            .transpose()
     }
 
-    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+    /// Iterate the remaining chunks from the last one backwards.
+    ///
+    /// Chunks are the same as iterating forward, in reverse order. Returns
+    /// the chunker itself, so reversed(chunker) works too; reset() goes back
+    /// to forward order from the beginning.
+    fn reversed(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.backward = true;
         slf
     }
 
-    fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<Py<PyBytes>> {
-        slf.inner
-            .next_chunk()
-            .map(|chunk| PyBytes::new(slf.py(), &chunk).unbind())
+    fn __reversed__(slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        Self::reversed(slf)
+    }
+
+    /// Get the next chunk as a Chunk (with index and offsets), or None.
+    ///
+    /// Advances the same position as iterating.
+    fn next_chunk_info(&mut self, py: Python<'_>) -> PyResult<Option<Chunk>> {
+        let chunk = self.inner.next_chunk_info();
+        check_scorer(self.scorer_error.as_deref())?;
+        Ok(chunk.map(|chunk| Chunk::from_info(py, chunk)))
+    }
+
+    /// Return all chunks as a list of Chunk objects, from the beginning.
+    fn iter_chunks(&mut self, py: Python<'_>) -> PyResult<Vec<Chunk>> {
+        let chunks: Vec<_> = self
+            .inner
+            .iter_chunks()
+            .map(|chunk| Chunk::from_info(py, chunk))
+            .collect();
+        check_scorer(self.scorer_error.as_deref())?;
+        Ok(chunks)
     }
 
     /// Reset the chunker to iterate from the beginning.
     fn reset(&mut self) {
         self.inner.reset();
+        self.backward = false;
+    }
+
+    /// Get chunk `index` as a Chunk without iterating to it, or None.
+    ///
+    /// Boundaries are cached as far as lookups reach; the iteration
+    /// position doesn't move.
+    fn chunk_at(&mut self, py: Python<'_>, index: usize) -> PyResult<Option<Chunk>> {
+        let chunk = self.inner.chunk_at(index);
+        check_scorer(self.scorer_error.as_deref())?;
+        Ok(chunk.map(|chunk| Chunk::from_info(py, chunk)))
+    }
+
+    /// Move to the chunk containing byte `offset` and return its index.
+    ///
+    /// Iterating then continues from that chunk. Returns None (and doesn't
+    /// move) if the offset is past the end.
+    fn seek(&mut self, offset: usize) -> PyResult<Option<usize>> {
+        let index = self.inner.seek(offset);
+        check_scorer(self.scorer_error.as_deref())?;
+        Ok(index)
     }
 
     /// Collect all chunk offsets as a list of (start, end) tuples.
     /// This is faster than iterating as it makes a single Rust call.
-    fn collect_offsets(&mut self) -> Vec<(usize, usize)> {
-        self.inner.collect_offsets()
+    fn collect_offsets(&mut self) -> PyResult<Vec<(usize, usize)>> {
+        let offsets = self.inner.collect_offsets();
+        check_scorer(self.scorer_error.as_deref())?;
+        Ok(offsets)
+    }
+
+    /// Collect all chunk offsets as an (N, 2) uint64 numpy array of
+    /// [start, end] rows.
+    ///
+    /// Much faster and smaller than collect_offsets() for millions of
+    /// chunks, and ready for pandas or arrow.
+    ///
+    /// Example:
+    ///     >>> offsets = Chunker(text, size=4096).collect_offsets_np()
+    ///     >>> df = pandas.DataFrame(offsets, columns=["start", "end"])
+    #[cfg(feature = "numpy")]
+    fn collect_offsets_np<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<u64>>> {
+        let offsets = self.inner.collect_offsets();
+        check_scorer(self.scorer_error.as_deref())?;
+        let flat: Vec<u64> = offsets
+            .iter()
+            .flat_map(|&(start, end)| [start as u64, end as u64])
+            .collect();
+        PyArray1::from_vec(py, flat).reshape([offsets.len(), 2])
+    }
+
+    /// Write every chunk to the file at path as JSON lines,
+    /// {"index":..,"start":..,"end":..,"text":..}, and return how many were
+    /// written.
+    ///
+    /// Records stream to the file from Rust as the chunks are found. Offsets
+    /// are byte offsets, and invalid UTF-8 in text is replaced with U+FFFD.
+    /// Like collect_offsets(), this covers the whole text and doesn't move
+    /// the iterator.
+    #[pyo3(name = "to_jsonl")]
+    fn write_jsonl(&mut self, path: std::path::PathBuf) -> PyResult<usize> {
+        let file = std::fs::File::create(path)?;
+        let count = self.inner.write_jsonl(std::io::BufWriter::new(file))?;
+        check_scorer(self.scorer_error.as_deref())?;
+        Ok(count)
+    }
+
+    /// Collect all chunk offsets with a stable 64-bit content hash of each
+    /// chunk, as a list of (start, end, hash) tuples.
+    ///
+    /// Byte-identical chunks get the same hash across texts and runs (see
+    /// chunk_hash), for deduplicating and upserting into vector stores.
+    fn collect_hashed_offsets(&mut self) -> PyResult<Vec<(usize, usize, u64)>> {
+        let hashed = self.inner.collect_hashed_offsets();
+        check_scorer(self.scorer_error.as_deref())?;
+        Ok(hashed)
+    }
+
+    /// Collect all chunk offsets with the detected language of each chunk,
+    /// as a list of (start, end, language) tuples.
+    ///
+    /// Languages are ISO 639-3 codes such as "eng" (see detect_language), or
+    /// None for a chunk without any text to tell by, so chunks of a
+    /// multilingual corpus can be routed to the right embedding model.
+    fn collect_language_offsets(&mut self) -> PyResult<Vec<(usize, usize, Option<&'static str>)>> {
+        let langs = self.inner.collect_language_offsets();
+        check_scorer(self.scorer_error.as_deref())?;
+        Ok(langs)
+    }
+
+    /// Collect the chunks as children grouped into parents of at most
+    /// parent_size bytes, for parent document retrieval: search the small
+    /// children, then hand the model the parent holding each hit.
+    ///
+    /// The children are the chunks collect_offsets gives, and every parent
+    /// is a run of whole children.
+    ///
+    /// Returns:
+    ///     (parents, children, parent_of): the (start, end) offsets of the
+    ///     parents and of the children, and for every child the index of
+    ///     its parent.
+    ///
+    /// Example:
+    ///     >>> chunker = Chunker(b"One. Two. Three. Four.", size=8, delimiters=b".")
+    ///     >>> parents, children, parent_of = chunker.collect_parent_child(10)
+    ///     >>> parents
+    ///     [(0, 9), (9, 16), (16, 22)]
+    ///     >>> parent_of
+    ///     [0, 0, 1, 2]
+    fn collect_parent_child(&mut self, parent_size: usize) -> PyResult<ParentChild> {
+        let chunks = self.inner.collect_parent_child(parent_size);
+        check_scorer(self.scorer_error.as_deref())?;
+        Ok((chunks.parents, chunks.children, chunks.parent_of))
+    }
+
+    /// Collect all chunks as a list of bytes (memoryviews with copy=False)
+    /// in a single Rust call.
+    fn collect_chunks(&mut self, py: Python<'_>) -> PyResult<Vec<Py<PyAny>>> {
+        let chunks = self
+            .inner
+            .iter_chunks()
+            .map(|chunk| chunk_object(py, self.view.as_ref(), &chunk))
+            .collect();
+        check_scorer(self.scorer_error.as_deref())?;
+        chunks
     }
 }
 
@@ -148,8 +1319,17 @@ impl Chunker {
 ///     >>> text = "Hello▁World▁Test".encode()
 ///     >>> offsets = chunk_offsets(text, size=15, pattern="▁", prefix=True)
 ///     >>> chunks = [text[start:end] for start, end in offsets]
+///
+/// For long texts, pass a tqdm bar as progress and it's updated with the
+/// bytes chunked every progress_every chunks (its total is set to the text's
+/// length if it has none), or any callable to have it called as
+/// progress(bytes, chunks):
+///     >>> from tqdm import tqdm
+///     >>> with tqdm(unit="B", unit_scale=True) as bar:
+///     ...     offsets = chunk_offsets(corpus, size=4096, progress=bar)
 #[pyfunction]
-#[pyo3(signature = (text, size=DEFAULT_TARGET_SIZE, delimiters=None, pattern=None, patterns=None, prefix=false, consecutive=false, forward_fallback=false))]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (text, size=DEFAULT_TARGET_SIZE, delimiters=None, pattern=None, patterns=None, prefix=false, consecutive=false, forward_fallback=false, overlap=0, overlap_ratio=None, utf8_safe=None, unit="bytes", min_size=0, max_size=None, trim=false, skip_empty=false, delimiter_priority=false, slack=0, balanced=false, lines_per_chunk=None, config=None, progress=None, progress_every=DEFAULT_PROGRESS_EVERY))]
 fn chunk_offsets(
     text: &Bound<'_, PyAny>,
     size: usize,
@@ -159,53 +1339,628 @@ fn chunk_offsets(
     prefix: bool,
     consecutive: bool,
     forward_fallback: bool,
+    overlap: usize,
+    overlap_ratio: Option<f64>,
+    utf8_safe: Option<bool>,
+    unit: &str,
+    min_size: usize,
+    max_size: Option<usize>,
+    trim: bool,
+    skip_empty: bool,
+    delimiter_priority: bool,
+    slack: usize,
+    balanced: bool,
+    lines_per_chunk: Option<usize>,
+    config: Option<&ChunkerConfig>,
+    progress: Option<&Bound<'_, PyAny>>,
+    progress_every: usize,
 ) -> PyResult<Vec<(usize, usize)>> {
-    let text_bytes = extract_bytes(text)?;
+    let config = ChunkerConfig::resolve(
+        config,
+        size,
+        delimiters,
+        pattern,
+        patterns,
+        prefix,
+        consecutive,
+        forward_fallback,
+        overlap,
+        overlap_ratio,
+        utf8_safe,
+        unit,
+        min_size,
+        max_size,
+        trim,
+        skip_empty,
+        delimiter_priority,
+        slack,
+        balanced,
+        lines_per_chunk,
+    )?;
+    let text_bytes = extract_text(text)?;
+    let config = config.resolved(text.is_instance_of::<PyString>());
+    let mut chunker = config.chunker(text_bytes.as_ref());
+    match progress {
+        Some(hook) => {
+            collect_with_progress(chunker, hook, progress_every, text_bytes.as_ref().len())
+        }
+        None => Ok(chunker.collect_offsets()),
+    }
+}
 
-    let mut chunker = OwnedChunker::new(text_bytes).size(size);
+/// Chunks between progress reports by default.
+const DEFAULT_PROGRESS_EVERY: usize = 1000;
 
-    // Pattern (singular) takes precedence over delimiters if both specified
-    if let Some(p) = pattern {
-        let pattern_bytes = extract_bytes(p)?;
-        chunker = chunker.pattern(pattern_bytes);
-    } else {
-        let delims = match delimiters {
-            Some(d) => extract_bytes(d)?,
-            None => DEFAULT_DELIMITERS.to_vec(),
+/// Collect the offsets of `chunker`'s chunks of a `len`-byte text, reporting
+/// progress to `hook` every `every` chunks: a tqdm-style bar (anything with
+/// `update`) is updated with the new bytes, anything else is called with
+/// (bytes, chunks). An exception from the hook stops the chunking.
+fn collect_with_progress(
+    chunker: chunk::Chunker<'_>,
+    hook: &Bound<'_, PyAny>,
+    every: usize,
+    len: usize,
+) -> PyResult<Vec<(usize, usize)>> {
+    let bar = hook.hasattr("update")?;
+    if bar && hook.hasattr("total")? && hook.getattr("total")?.is_none() {
+        hook.setattr("total", len)?;
+    }
+    let error = RefCell::new(None);
+    let mut updated = 0;
+    let report = |progress: Progress| {
+        if error.borrow().is_some() {
+            return;
+        }
+        let result = if bar {
+            let delta = progress.bytes - updated;
+            updated = progress.bytes;
+            hook.call_method1("update", (delta,))
+        } else {
+            hook.call1((progress.bytes, progress.chunks))
         };
-        chunker = chunker.delimiters(delims);
+        if let Err(err) = result {
+            *error.borrow_mut() = Some(err);
+        }
+    };
+    let mut offsets = Vec::new();
+    for chunk in chunker.iter_chunks().with_progress(every, report) {
+        if error.borrow().is_some() {
+            break;
+        }
+        offsets.push((chunk.start, chunk.end));
     }
+    match error.into_inner() {
+        Some(err) => Err(err),
+        None => Ok(offsets),
+    }
+}
 
-    // Patterns (plural) is composable with delimiters — applied after
-    if let Some(pats) = patterns {
-        let pattern_strings: Vec<String> = pats
-            .iter()
-            .map(|p| {
-                extract_bytes(p).and_then(|b| {
-                    String::from_utf8(b).map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                            "patterns must be valid UTF-8: {}",
-                            e
-                        ))
-                    })
-                })
+/// Chunk a str, returning character offsets for slicing it directly.
+///
+/// chunk_offsets() returns byte offsets into the UTF-8 encoding, which only
+/// match str indices for ASCII text. This takes the same options (chunks
+/// never split a character, so there's no utf8_safe) and converts the
+/// offsets, so `text[start:end]` is each chunk.
+///
+/// Example:
+///     >>> text = "Café. Thé. Fin."
+///     >>> offsets = chunk_str_offsets(text, size=7, delimiters=".")
+///     >>> [text[start:end] for start, end in offsets]
+///     ['Café.', ' Thé.', ' Fin.']
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (text, size=DEFAULT_TARGET_SIZE, delimiters=None, pattern=None, patterns=None, prefix=false, consecutive=false, forward_fallback=false, overlap=0, overlap_ratio=None, unit="bytes", min_size=0, max_size=None, trim=false, skip_empty=false, delimiter_priority=false, slack=0, balanced=false, lines_per_chunk=None))]
+fn chunk_str_offsets(
+    text: &Bound<'_, PyString>,
+    size: usize,
+    delimiters: Option<&Bound<'_, PyAny>>,
+    pattern: Option<&Bound<'_, PyAny>>,
+    patterns: Option<Vec<Bound<'_, PyAny>>>,
+    prefix: bool,
+    consecutive: bool,
+    forward_fallback: bool,
+    overlap: usize,
+    overlap_ratio: Option<f64>,
+    unit: &str,
+    min_size: usize,
+    max_size: Option<usize>,
+    trim: bool,
+    skip_empty: bool,
+    delimiter_priority: bool,
+    slack: usize,
+    balanced: bool,
+    lines_per_chunk: Option<usize>,
+) -> PyResult<Vec<(usize, usize)>> {
+    let offsets = chunk_offsets(
+        text.as_any(),
+        size,
+        delimiters,
+        pattern,
+        patterns,
+        prefix,
+        consecutive,
+        forward_fallback,
+        overlap,
+        overlap_ratio,
+        Some(true),
+        unit,
+        min_size,
+        max_size,
+        trim,
+        skip_empty,
+        delimiter_priority,
+        slack,
+        balanced,
+        lines_per_chunk,
+        None,
+        None,
+        DEFAULT_PROGRESS_EVERY,
+    )?;
+    Ok(char_offsets(text.to_str()?.as_bytes(), offsets))
+}
+
+/// Chunk a str, returning the chunks as strs.
+///
+/// Encoding, chunking and decoding all happen in Rust in one call, instead
+/// of chunk_offsets() and slicing. Takes the same options as
+/// chunk_str_offsets() or a config; chunks never split a character (even
+/// with a config turning utf8_safe off), so each decodes cleanly.
+///
+/// With a template, each chunk starts with the template rendered for it:
+/// {i} is the chunk number from 1, {n} the number of chunks, {start} and
+/// {end} the chunk's UTF-8 byte offsets, any other {name} is str() of that
+/// metadata value, and {{ and }} are literal braces.
+///
+/// Example:
+///     >>> chunk_text("Café. Thé. Fin.", size=7, delimiters=".")
+///     ['Café.', ' Thé.', ' Fin.']
+///     >>> chunk_text("One. Two.", size=5, template="[{title} {i}/{n}]", metadata={"title": "Notes"})
+///     ['[Notes 1/2]One.', '[Notes 2/2] Two.']
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (text, size=DEFAULT_TARGET_SIZE, delimiters=None, pattern=None, patterns=None, prefix=false, consecutive=false, forward_fallback=false, overlap=0, overlap_ratio=None, unit="bytes", min_size=0, max_size=None, trim=false, skip_empty=false, delimiter_priority=false, slack=0, balanced=false, lines_per_chunk=None, config=None, template=None, metadata=None))]
+fn chunk_text<'t>(
+    text: &'t Bound<'_, PyString>,
+    size: usize,
+    delimiters: Option<&Bound<'_, PyAny>>,
+    pattern: Option<&Bound<'_, PyAny>>,
+    patterns: Option<Vec<Bound<'_, PyAny>>>,
+    prefix: bool,
+    consecutive: bool,
+    forward_fallback: bool,
+    overlap: usize,
+    overlap_ratio: Option<f64>,
+    unit: &str,
+    min_size: usize,
+    max_size: Option<usize>,
+    trim: bool,
+    skip_empty: bool,
+    delimiter_priority: bool,
+    slack: usize,
+    balanced: bool,
+    lines_per_chunk: Option<usize>,
+    config: Option<&ChunkerConfig>,
+    template: Option<&str>,
+    metadata: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Vec<Cow<'t, str>>> {
+    let mut config = ChunkerConfig::resolve(
+        config,
+        size,
+        delimiters,
+        pattern,
+        patterns,
+        prefix,
+        consecutive,
+        forward_fallback,
+        overlap,
+        overlap_ratio,
+        Some(true),
+        unit,
+        min_size,
+        max_size,
+        trim,
+        skip_empty,
+        delimiter_priority,
+        slack,
+        balanced,
+        lines_per_chunk,
+    )?
+    .resolved(true);
+    config.utf8_safe = true;
+    let template = template
+        .map(|template| {
+            let mut fields = Vec::new();
+            for (key, value) in metadata.into_iter().flat_map(|dict| dict.iter()) {
+                fields.push((key.extract::<String>()?, value.str()?.to_string()));
+            }
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect();
+            ChunkTemplate::new(template, &fields)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+        })
+        .transpose()?;
+    let text = text.to_str()?.as_bytes();
+    let offsets = config.chunker(text).collect_offsets();
+    // Only a non-ASCII byte given as a delimiter can cut a character, which
+    // then decodes to U+FFFD rather than raising
+    let chunks = offsets
+        .iter()
+        .map(|&(start, end)| String::from_utf8_lossy(&text[start..end]));
+    Ok(match template {
+        Some(template) => chunks
+            .enumerate()
+            .map(|(index, chunk)| {
+                let (start, end) = offsets[index];
+                Cow::Owned(template.header(index, offsets.len(), start, end) + &chunk)
             })
-            .collect::<PyResult<Vec<String>>>()?;
-        let pattern_refs: Vec<&str> = pattern_strings.iter().map(|s| s.as_str()).collect();
-        chunker = chunker.patterns(&pattern_refs);
+            .collect(),
+        None => chunks.collect(),
+    })
+}
+
+/// Convert byte offsets at character boundaries of UTF-8 text to character
+/// offsets.
+///
+/// Chunk starts and ends each only move forward, so a cursor per side
+/// counts every byte once.
+fn char_offsets(text: &[u8], offsets: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    let (mut starts, mut ends) = (CharCursor::default(), CharCursor::default());
+    offsets
+        .into_iter()
+        .map(|(start, end)| (starts.seek(text, start), ends.seek(text, end)))
+        .collect()
+}
+
+/// Running byte-to-character position in UTF-8 text.
+#[derive(Default)]
+struct CharCursor {
+    byte: usize,
+    char: usize,
+}
+
+impl CharCursor {
+    /// Character index of byte offset `to`.
+    fn seek(&mut self, text: &[u8], to: usize) -> usize {
+        if to < self.byte {
+            *self = Self::default();
+        }
+        // Every byte except continuation bytes (0b10xxxxxx) starts a character
+        self.char += text[self.byte..to]
+            .iter()
+            .filter(|&&b| (b as i8) >= -0x40)
+            .count();
+        self.byte = to;
+        self.char
     }
+}
 
-    if prefix {
-        chunker = chunker.prefix();
+/// A flag for stopping a long chunk_offsets_batch() run part way.
+///
+/// Call cancel() from another thread or a signal handler and the batch
+/// raises InterruptedError promptly, without finishing the texts left.
+///
+/// Example:
+///     >>> import signal
+///     >>> token = CancelToken()
+///     >>> signal.signal(signal.SIGINT, lambda *_: token.cancel())
+///     >>> offsets = chunk_offsets_batch(texts, size=1024, cancel=token)
+#[pyclass(frozen)]
+#[derive(Default)]
+struct CancelToken {
+    token: RustCancelToken,
+}
+
+#[pymethods]
+impl CancelToken {
+    #[new]
+    fn new() -> Self {
+        Self::default()
     }
-    if consecutive {
-        chunker = chunker.consecutive();
+
+    /// Ask the runs holding this token to stop.
+    fn cancel(&self) {
+        self.token.cancel();
     }
-    if forward_fallback {
-        chunker = chunker.forward_fallback();
+
+    /// Whether cancel() has been called.
+    #[getter]
+    fn cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+/// How often a cancellable batch lets Python signal handlers run.
+const SIGNAL_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(20);
+
+/// Chunk many texts in parallel, returning each one's offsets.
+///
+/// Takes the same options as chunk_offsets(), applied to every text. The GIL
+/// is released while chunking, so other Python threads keep running.
+///
+/// utf8_safe defaults to True when every text is a str.
+///
+/// With cancel=CancelToken(), the batch stops and raises InterruptedError
+/// once the token is cancelled. Python signal handlers get to run while it
+/// works, so one can cancel it, and an exception a handler raises (such as
+/// KeyboardInterrupt on Ctrl-C) stops the batch and is raised instead.
+///
+/// Example:
+///     >>> texts = [b"Hello. World.", b"One. Two. Three."]
+///     >>> offsets = chunk_offsets_batch(texts, size=8, delimiters=b".")
+///     >>> # [[(0, 6), (6, 13)], [(0, 4), (4, 9), (9, 16)]]
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (texts, size=DEFAULT_TARGET_SIZE, delimiters=None, pattern=None, patterns=None, prefix=false, consecutive=false, forward_fallback=false, overlap=0, overlap_ratio=None, utf8_safe=None, unit="bytes", min_size=0, max_size=None, trim=false, skip_empty=false, delimiter_priority=false, slack=0, balanced=false, lines_per_chunk=None, config=None, cancel=None))]
+fn chunk_offsets_batch(
+    py: Python<'_>,
+    texts: Vec<Bound<'_, PyAny>>,
+    size: usize,
+    delimiters: Option<&Bound<'_, PyAny>>,
+    pattern: Option<&Bound<'_, PyAny>>,
+    patterns: Option<Vec<Bound<'_, PyAny>>>,
+    prefix: bool,
+    consecutive: bool,
+    forward_fallback: bool,
+    overlap: usize,
+    overlap_ratio: Option<f64>,
+    utf8_safe: Option<bool>,
+    unit: &str,
+    min_size: usize,
+    max_size: Option<usize>,
+    trim: bool,
+    skip_empty: bool,
+    delimiter_priority: bool,
+    slack: usize,
+    balanced: bool,
+    lines_per_chunk: Option<usize>,
+    config: Option<&ChunkerConfig>,
+    cancel: Option<&CancelToken>,
+) -> PyResult<Vec<Vec<(usize, usize)>>> {
+    let config = ChunkerConfig::resolve(
+        config,
+        size,
+        delimiters,
+        pattern,
+        patterns,
+        prefix,
+        consecutive,
+        forward_fallback,
+        overlap,
+        overlap_ratio,
+        utf8_safe,
+        unit,
+        min_size,
+        max_size,
+        trim,
+        skip_empty,
+        delimiter_priority,
+        slack,
+        balanced,
+        lines_per_chunk,
+    )?
+    .resolved(texts.iter().all(|t| t.is_instance_of::<PyString>()));
+    let docs = texts
+        .iter()
+        .map(extract_text)
+        .collect::<PyResult<Vec<Text>>>()?;
+
+    let refs: Vec<&[u8]> = docs.iter().map(|d| d.as_ref()).collect();
+    let Some(cancel) = cancel else {
+        return Ok(py.detach(|| chunk_batch(&refs, |chunker| config.apply(chunker))));
+    };
+    let token = &cancel.token;
+    // The batch runs on another thread so this one can run signal handlers
+    let (offsets, signal) = py.detach(|| {
+        std::thread::scope(|scope| {
+            let batch =
+                scope.spawn(|| chunk_batch_until(&refs, |chunker| config.apply(chunker), token));
+            let mut signal = Ok(());
+            while !batch.is_finished() {
+                std::thread::sleep(SIGNAL_CHECK_INTERVAL);
+                if signal.is_ok() {
+                    signal = Python::attach(|py| py.check_signals());
+                    if signal.is_err() {
+                        token.cancel();
+                    }
+                }
+            }
+            (batch.join().expect("batch panicked"), signal)
+        })
+    });
+    signal?;
+    offsets.map_err(|err| PyErr::new::<pyo3::exceptions::PyInterruptedError, _>(err.to_string()))
+}
+
+/// Chunks of an Arrow column from chunk_arrow().
+///
+/// Implements the Arrow PyCapsule interface, so pyarrow, Polars and DuckDB
+/// read it directly, e.g. with pyarrow.array(), pyarrow.table() or
+/// polars.DataFrame().
+#[pyclass(frozen)]
+struct ArrowChunks {
+    table: chunk::ChunkTable,
+    layout: ArrowLayout,
+}
+
+#[pymethods]
+impl ArrowChunks {
+    /// Export the chunks as an (ArrowSchema, ArrowArray) capsule pair.
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_array__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<(Bound<'py, PyCapsule>, Bound<'py, PyCapsule>)> {
+        // Only one schema is on offer, so a requested one is ignored
+        let _ = requested_schema;
+        let (schema, array) = self.table.to_arrow(self.layout);
+        Ok((
+            PyCapsule::new(py, schema, Some(c"arrow_schema".to_owned()))?,
+            PyCapsule::new(py, array, Some(c"arrow_array".to_owned()))?,
+        ))
+    }
+
+    /// Export the chunks as an ArrowArrayStream capsule.
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        let _ = requested_schema;
+        let stream = self.table.to_arrow_stream(self.layout);
+        PyCapsule::new(py, stream, Some(c"arrow_array_stream".to_owned()))
+    }
+
+    /// Number of chunks across all documents.
+    fn __len__(&self) -> usize {
+        self.table.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ArrowChunks(docs={}, chunks={})",
+            self.table.docs,
+            self.table.len()
+        )
+    }
+}
+
+fn arrow_error(err: ArrowError) -> PyErr {
+    match err {
+        ArrowError::UnsupportedType(_) => {
+            PyErr::new::<pyo3::exceptions::PyTypeError, _>(err.to_string())
+        }
+        _ => PyErr::new::<pyo3::exceptions::PyValueError, _>(err.to_string()),
+    }
+}
+
+/// Move the value out of the Arrow PyCapsule `capsule` named `name`.
+///
+/// # Safety
+///
+/// A capsule named `name` must hold a `T`, as the Arrow PyCapsule interface
+/// requires.
+unsafe fn take_capsule<T>(
+    capsule: &Bound<'_, PyAny>,
+    name: &std::ffi::CStr,
+    take: unsafe fn(*mut T) -> T,
+) -> PyResult<T> {
+    let pointer = capsule.cast::<PyCapsule>()?.pointer_checked(Some(name))?;
+    // SAFETY: the caller guarantees the capsule's contents
+    Ok(unsafe { take(pointer.cast().as_ptr()) })
+}
+
+/// Chunk every string or binary value of an Arrow column, without Python
+/// loops.
+///
+/// column is any object implementing the Arrow PyCapsule interface
+/// (__arrow_c_stream__ or __arrow_c_array__): a pyarrow Array or
+/// ChunkedArray, a Polars Series, a DuckDB result column, ... of utf8,
+/// large_utf8, binary, large_binary or view type. Takes the same options as
+/// chunk_offsets(), applied to every value; utf8_safe defaults to True for
+/// string columns. A null value has no chunks. The GIL is released while
+/// chunking.
+///
+/// Returns an ArrowChunks for Arrow libraries to import: by default one
+/// large_list<struct<start, end>> row of byte offsets per document, or with
+/// explode=True one struct<doc_id, start, end> row per chunk (a table).
+///
+/// Example:
+///     >>> docs = pyarrow.array(["Hello. World.", None, "One. Two. Three."])
+///     >>> pyarrow.array(chunk_arrow(docs, size=8, delimiters=b".")).to_pylist()
+///     >>> # [[{'start': 0, 'end': 6}, {'start': 6, 'end': 13}], [], ...]
+///     >>> polars.DataFrame(chunk_arrow(docs, size=8, explode=True))
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (column, size=DEFAULT_TARGET_SIZE, delimiters=None, pattern=None, patterns=None, prefix=false, consecutive=false, forward_fallback=false, overlap=0, overlap_ratio=None, utf8_safe=None, unit="bytes", min_size=0, max_size=None, trim=false, skip_empty=false, delimiter_priority=false, slack=0, balanced=false, lines_per_chunk=None, config=None, explode=false))]
+fn chunk_arrow(
+    py: Python<'_>,
+    column: &Bound<'_, PyAny>,
+    size: usize,
+    delimiters: Option<&Bound<'_, PyAny>>,
+    pattern: Option<&Bound<'_, PyAny>>,
+    patterns: Option<Vec<Bound<'_, PyAny>>>,
+    prefix: bool,
+    consecutive: bool,
+    forward_fallback: bool,
+    overlap: usize,
+    overlap_ratio: Option<f64>,
+    utf8_safe: Option<bool>,
+    unit: &str,
+    min_size: usize,
+    max_size: Option<usize>,
+    trim: bool,
+    skip_empty: bool,
+    delimiter_priority: bool,
+    slack: usize,
+    balanced: bool,
+    lines_per_chunk: Option<usize>,
+    config: Option<&ChunkerConfig>,
+    explode: bool,
+) -> PyResult<ArrowChunks> {
+    let config = ChunkerConfig::resolve(
+        config,
+        size,
+        delimiters,
+        pattern,
+        patterns,
+        prefix,
+        consecutive,
+        forward_fallback,
+        overlap,
+        overlap_ratio,
+        utf8_safe,
+        unit,
+        min_size,
+        max_size,
+        trim,
+        skip_empty,
+        delimiter_priority,
+        slack,
+        balanced,
+        lines_per_chunk,
+    )?;
+    let mut table = chunk::ChunkTable::new();
+    let mut push = |array: &ArrowArray, schema: &ArrowSchema| -> PyResult<()> {
+        let column = TextColumn::new(array, schema).map_err(arrow_error)?;
+        let config = config.resolved(column.is_utf8());
+        py.detach(|| table.push_column(&column, |chunker| config.apply(chunker)));
+        Ok(())
+    };
+
+    if column.hasattr("__arrow_c_stream__")? {
+        let capsule = column.call_method0("__arrow_c_stream__")?;
+        // SAFETY: the interface puts an ArrowArrayStream in this capsule
+        let mut stream =
+            unsafe { take_capsule(&capsule, c"arrow_array_stream", ArrowArrayStream::take)? };
+        let schema = stream.schema().map_err(arrow_error)?;
+        while let Some(array) = stream.next_array().map_err(arrow_error)? {
+            push(&array, &schema)?;
+        }
+    } else if column.hasattr("__arrow_c_array__")? {
+        let (schema, array): (Bound<'_, PyAny>, Bound<'_, PyAny>) =
+            column.call_method0("__arrow_c_array__")?.extract()?;
+        // SAFETY: the interface puts an ArrowSchema and an ArrowArray in these
+        let schema = unsafe { take_capsule(&schema, c"arrow_schema", ArrowSchema::take)? };
+        let array = unsafe { take_capsule(&array, c"arrow_array", ArrowArray::take)? };
+        push(&array, &schema)?;
+    } else {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+            "expected an Arrow array or stream (an object with __arrow_c_array__ or __arrow_c_stream__)",
+        ));
     }
 
-    Ok(chunker.collect_offsets())
+    Ok(ArrowChunks {
+        table,
+        layout: if explode {
+            ArrowLayout::Exploded
+        } else {
+            ArrowLayout::Lists
+        },
+    })
 }
 
 /// Split text at every delimiter occurrence, returning offsets.
@@ -215,7 +1970,7 @@ fn chunk_offsets(
 /// **every** delimiter occurrence.
 ///
 /// Args:
-///     text: bytes or str to split
+///     text: str or a bytes-like object to split
 ///     delimiters: bytes or str of delimiter characters (default: "\\n.?")
 ///     include_delim: Where to attach delimiter - "prev" (default), "next", or "none"
 ///     min_chars: Minimum characters per segment (default: 0). Shorter segments are merged.
@@ -241,7 +1996,7 @@ fn split_offsets(
     include_delim: &str,
     min_chars: usize,
 ) -> PyResult<Vec<(usize, usize)>> {
-    let text_bytes = extract_bytes(text)?;
+    let text = extract_text(text)?;
 
     let delims = match delimiters {
         Some(d) => extract_bytes(d)?,
@@ -260,7 +2015,7 @@ fn split_offsets(
     };
 
     Ok(split_at_delimiters(
-        &text_bytes,
+        text.as_ref(),
         &delims,
         include,
         min_chars,
@@ -274,7 +2029,7 @@ fn split_offsets(
 /// using the Aho-Corasick algorithm for efficient matching.
 ///
 /// Args:
-///     text: bytes or str to split
+///     text: str or a bytes-like object to split
 ///     patterns: List of bytes or str patterns to split on (e.g., [". ", "? ", "! "])
 ///     include_delim: Where to attach pattern - "prev" (default), "next", or "none"
 ///     min_chars: Minimum characters per segment (default: 0). Shorter segments are merged.
@@ -301,7 +2056,7 @@ fn split_pattern_offsets(
     include_delim: &str,
     min_chars: usize,
 ) -> PyResult<Vec<(usize, usize)>> {
-    let text_bytes = extract_bytes(text)?;
+    let text = extract_text(text)?;
 
     // Convert Python patterns to Vec<Vec<u8>>
     let pattern_bytes: Vec<Vec<u8>> = patterns
@@ -324,13 +2079,255 @@ fn split_pattern_offsets(
     };
 
     Ok(split_at_patterns(
-        &text_bytes,
+        text.as_ref(),
         &pattern_slices,
         include,
         min_chars,
     ))
 }
 
+/// Transforms and filters run on every chunk in Rust, before the chunks are
+/// handed back, instead of in a Python loop.
+///
+/// The steps run in this order: strip_html, trim, lowercase, redact, redactor,
+/// then the filters: min_length (in bytes), min_density (the share of
+/// characters that are letters or digits), drop_matching (regexes for
+/// boilerplate like navigation menus) and languages (ISO 639-3 codes like
+/// "eng"). Each pattern in redact is replaced with replacement; a Redactor
+/// also takes out emails, phone numbers or regex matches.
+///
+/// Example:
+///     >>> from chonkie_core import ChunkPipeline, chunk_offsets
+///     >>> text = b"<p>One</p>\n<p>TWO</p>\n<br>\n"
+///     >>> pipeline = ChunkPipeline(strip_html=True, trim=True, lowercase=True, min_length=1)
+///     >>> [c.bytes for c in pipeline.run(text, chunk_offsets(text, size=12, delimiters="\n"))]
+///     [b'one', b'two']
+#[pyclass(frozen)]
+pub struct ChunkPipeline {
+    inner: RustChunkPipeline,
+}
+
+#[pymethods]
+impl ChunkPipeline {
+    #[new]
+    #[pyo3(signature = (*, strip_html=false, trim=false, lowercase=false, redact=None, replacement="[REDACTED]", redactor=None, min_length=0, min_density=None, drop_matching=None, languages=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        strip_html: bool,
+        trim: bool,
+        lowercase: bool,
+        redact: Option<Vec<Bound<'_, PyAny>>>,
+        replacement: &str,
+        redactor: Option<PyRef<'_, Redactor>>,
+        min_length: usize,
+        min_density: Option<f64>,
+        drop_matching: Option<Vec<String>>,
+        languages: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let mut inner = RustChunkPipeline::new();
+        if strip_html {
+            inner = inner.strip_html();
+        }
+        if trim {
+            inner = inner.trim();
+        }
+        if lowercase {
+            inner = inner.lowercase();
+        }
+        for pattern in redact.unwrap_or_default() {
+            inner = inner.redact(&extract_bytes(&pattern)?, replacement.as_bytes());
+        }
+        if let Some(redactor) = redactor {
+            inner = inner.redactor(redactor.inner.clone());
+        }
+        if min_length > 0 {
+            inner = inner.min_length(min_length);
+        }
+        if let Some(ratio) = min_density {
+            inner = inner.min_density(ratio);
+        }
+        for pattern in drop_matching.unwrap_or_default() {
+            inner = inner
+                .drop_matching(&pattern)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        }
+        if let Some(languages) = languages {
+            inner = inner.languages(&languages);
+        }
+        Ok(Self { inner })
+    }
+
+    /// Run the pipeline on one chunk.
+    ///
+    /// Returns:
+    ///     The transformed chunk as bytes, or None if a filter drops it.
+    fn apply(&self, py: Python<'_>, chunk: &Bound<'_, PyAny>) -> PyResult<Option<Py<PyBytes>>> {
+        let chunk = extract_text(chunk)?;
+        Ok(self
+            .inner
+            .apply(chunk.as_ref())
+            .map(|chunk| PyBytes::new(py, &chunk).unbind()))
+    }
+
+    /// Run the pipeline on the chunks of a text.
+    ///
+    /// Args:
+    ///     text: str or a bytes-like object
+    ///     offsets: (start, end) byte offsets of the chunks, e.g. from chunk_offsets
+    ///
+    /// Returns:
+    ///     List of Chunk for the chunks that are kept, with their index, start
+    ///     and end in the text and their transformed bytes.
+    fn run(
+        &self,
+        py: Python<'_>,
+        text: &Bound<'_, PyAny>,
+        offsets: Vec<(usize, usize)>,
+    ) -> PyResult<Vec<Chunk>> {
+        let text = extract_text(text)?;
+        let text = text.as_ref();
+        if offsets
+            .iter()
+            .any(|&(start, end)| start > end || end > text.len())
+        {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "offsets out of range for their text",
+            ));
+        }
+        let chunks = py.detach(|| {
+            offsets
+                .iter()
+                .enumerate()
+                .filter_map(|(index, &(start, end))| {
+                    let chunk = self.inner.apply(&text[start..end])?;
+                    Some((index, start, end, chunk))
+                })
+                .collect::<Vec<_>>()
+        });
+        Ok(chunks
+            .into_iter()
+            .map(|(index, start, end, chunk)| Chunk {
+                index,
+                start,
+                end,
+                bytes: PyBytes::new(py, &chunk).unbind(),
+            })
+            .collect())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ChunkPipeline({:?})", self.inner)
+    }
+}
+
+/// Replaces personal data in chunks with placeholders, reporting what it took
+/// out.
+///
+/// Emails become "[EMAIL]" (rule "email") and phone numbers "[PHONE]" (rule
+/// "phone"). Each of patterns is a (name, regex, replacement) tuple. Where
+/// matches overlap, the one starting first wins, then the longest.
+///
+/// Example:
+///     >>> from chonkie_core import Redactor
+///     >>> redactor = Redactor(emails=True, phone_numbers=True)
+///     >>> redactor.redact("Mail ana@example.com or call 555-123-4567.")
+///     (b'Mail [EMAIL] or call [PHONE].', [(5, 20, 'email'), (29, 41, 'phone')])
+#[pyclass(frozen)]
+pub struct Redactor {
+    inner: RustRedactor,
+}
+
+#[pymethods]
+impl Redactor {
+    #[new]
+    #[pyo3(signature = (*, emails=false, phone_numbers=false, patterns=None))]
+    fn new(
+        emails: bool,
+        phone_numbers: bool,
+        patterns: Option<Vec<(String, String, String)>>,
+    ) -> PyResult<Self> {
+        let mut inner = RustRedactor::new();
+        if emails {
+            inner = inner.emails();
+        }
+        if phone_numbers {
+            inner = inner.phone_numbers();
+        }
+        for (name, pattern, replacement) in patterns.unwrap_or_default() {
+            inner = inner
+                .pattern(&name, &pattern, &replacement)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        }
+        Ok(Self { inner })
+    }
+
+    /// Redact a text.
+    ///
+    /// Returns:
+    ///     (redacted bytes, list of (start, end, rule) for each redacted span,
+    ///     with byte offsets into the text).
+    fn redact(
+        &self,
+        py: Python<'_>,
+        text: &Bound<'_, PyAny>,
+    ) -> PyResult<(Py<PyBytes>, Vec<RedactedSpan>)> {
+        let text = extract_text(text)?;
+        let (redacted, redactions) = self.inner.redact(text.as_ref());
+        Ok((PyBytes::new(py, &redacted).unbind(), spans(redactions)))
+    }
+
+    /// Redact the chunks of a text, each on its own.
+    ///
+    /// Args:
+    ///     text: str or a bytes-like object
+    ///     offsets: (start, end) byte offsets of the chunks, e.g. from chunk_offsets
+    ///
+    /// Returns:
+    ///     List of (start, end, redacted bytes, spans) per chunk, the spans as
+    ///     (start, end, rule) with byte offsets into the whole text.
+    fn redact_chunks(
+        &self,
+        py: Python<'_>,
+        text: &Bound<'_, PyAny>,
+        offsets: Vec<(usize, usize)>,
+    ) -> PyResult<Vec<RedactedChunkTuple>> {
+        let text = extract_text(text)?;
+        let text = text.as_ref();
+        if offsets
+            .iter()
+            .any(|&(start, end)| start > end || end > text.len())
+        {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "offsets out of range for their text",
+            ));
+        }
+        let chunks = py.detach(|| self.inner.redact_chunks(text, &offsets));
+        Ok(chunks
+            .into_iter()
+            .map(|chunk| {
+                let bytes = PyBytes::new(py, &chunk.text).unbind();
+                (chunk.start, chunk.end, bytes, spans(chunk.redactions))
+            })
+            .collect())
+    }
+}
+
+/// Parent and child chunks as (parents, children, parent_of).
+type ParentChild = (Vec<(usize, usize)>, Vec<(usize, usize)>, Vec<usize>);
+
+/// A redacted span as (start, end, rule).
+type RedactedSpan = (usize, usize, String);
+
+/// A redacted chunk as (start, end, redacted bytes, spans).
+type RedactedChunkTuple = (usize, usize, Py<PyBytes>, Vec<RedactedSpan>);
+
+fn spans(redactions: Vec<chunk::Redaction>) -> Vec<RedactedSpan> {
+    redactions
+        .into_iter()
+        .map(|r| (r.start, r.end, r.rule))
+        .collect()
+}
+
 /// A compiled multi-pattern splitter for efficient repeated splitting.
 ///
 /// Unlike split_pattern_offsets() which rebuilds the automaton on each call,
@@ -365,7 +2362,7 @@ impl PatternSplitter {
     /// Split text using the compiled patterns.
     ///
     /// Args:
-    ///     text: bytes or str to split
+    ///     text: str or a bytes-like object to split
     ///     include_delim: Where to attach pattern - "prev" (default), "next", or "none"
     ///     min_chars: Minimum characters per segment (default: 0)
     ///
@@ -378,7 +2375,7 @@ impl PatternSplitter {
         include_delim: &str,
         min_chars: usize,
     ) -> PyResult<Vec<(usize, usize)>> {
-        let text_bytes = extract_bytes(text)?;
+        let text = extract_text(text)?;
 
         let include = match include_delim {
             "prev" => IncludeDelim::Prev,
@@ -391,10 +2388,440 @@ impl PatternSplitter {
             }
         };
 
-        Ok(self.inner.split(&text_bytes, include, min_chars))
+        Ok(self.inner.split(text.as_ref(), include, min_chars))
+    }
+}
+
+/// A compiled recursive chunker over ordered delimiter levels.
+///
+/// Text is split at the first level (e.g. paragraphs) and adjacent pieces are
+/// packed up to `size`. Only pieces that are still too large descend to the
+/// next level; below the last level they are hard split at `size`.
+///
+/// Example:
+///     >>> from chonkie_core import RecursiveChunker
+///     >>> chunker = RecursiveChunker([["\n\n"], ["\n"], [". ", "? ", "! "]], size=1024)
+///     >>> offsets = chunker.chunk_offsets(text)
+///     >>> chunks = [text[start:end] for start, end in offsets]
+#[pyclass]
+pub struct RecursiveChunker {
+    inner: RustRecursiveChunker,
+}
+
+#[pymethods]
+impl RecursiveChunker {
+    #[new]
+    #[pyo3(signature = (levels, size=DEFAULT_TARGET_SIZE, prefix=false, utf8_safe=false))]
+    fn new(
+        levels: Vec<Vec<Bound<'_, PyAny>>>,
+        size: usize,
+        prefix: bool,
+        utf8_safe: bool,
+    ) -> PyResult<Self> {
+        let mut inner = RustRecursiveChunker::new().size(size).utf8_safe(utf8_safe);
+        for level in &levels {
+            let patterns = level
+                .iter()
+                .map(|p| extract_bytes(p))
+                .collect::<PyResult<Vec<Vec<u8>>>>()?;
+            inner = inner.level(&patterns);
+        }
+        if prefix {
+            inner = inner.prefix();
+        }
+        Ok(Self { inner })
+    }
+
+    /// Create a paragraph-priority chunker.
+    ///
+    /// Splits at blank lines first, falls back to sentence ends for paragraphs
+    /// that don't fit, and hard splits anything left over.
+    ///
+    /// Example:
+    ///     >>> chunker = RecursiveChunker.paragraphs(size=1024)
+    ///     >>> offsets = chunker.chunk_offsets(text)
+    #[staticmethod]
+    #[pyo3(signature = (size=DEFAULT_TARGET_SIZE, utf8_safe=false))]
+    fn paragraphs(size: usize, utf8_safe: bool) -> Self {
+        let inner = RustRecursiveChunker::paragraphs()
+            .size(size)
+            .utf8_safe(utf8_safe);
+        Self { inner }
+    }
+
+    /// Chunk text using the compiled levels.
+    ///
+    /// Args:
+    ///     text: str or a bytes-like object to chunk
+    ///
+    /// Returns:
+    ///     List of (start, end) byte offsets for each chunk.
+    fn chunk_offsets(&self, text: &Bound<'_, PyAny>) -> PyResult<Vec<(usize, usize)>> {
+        let text = extract_text(text)?;
+        Ok(self.inner.chunk_offsets(text.as_ref()))
+    }
+}
+
+/// A chunker whose boundaries are matches of a regular expression.
+///
+/// Same size semantics as `Chunker`: each chunk ends at the last match within
+/// `size` bytes. By default the match stays with the current chunk; with
+/// `prefix=True` it starts the next one. Compiled patterns are cached, so
+/// constructing many chunkers with the same pattern is cheap.
+///
+/// Example:
+///     >>> from chonkie_core import RegexChunker
+///     >>> chunker = RegexChunker(r"(?m)^\d{4}-\d{2}-\d{2}", size=4096, prefix=True)
+///     >>> offsets = chunker.chunk_offsets(log)
+///     >>> chunks = [log[start:end] for start, end in offsets]
+#[pyclass]
+pub struct RegexChunker {
+    inner: RustRegexChunker,
+}
+
+#[pymethods]
+impl RegexChunker {
+    #[new]
+    #[pyo3(signature = (pattern, size=DEFAULT_TARGET_SIZE, prefix=false, forward_fallback=false, utf8_safe=false))]
+    fn new(
+        pattern: &str,
+        size: usize,
+        prefix: bool,
+        forward_fallback: bool,
+        utf8_safe: bool,
+    ) -> PyResult<Self> {
+        let inner = regex_chunker(pattern, size, prefix, forward_fallback, utf8_safe)?;
+        Ok(Self { inner })
+    }
+
+    /// The regex pattern this chunker splits at.
+    #[getter]
+    fn pattern(&self) -> &str {
+        self.inner.regex().as_str()
+    }
+
+    /// Chunk text at matches of the compiled pattern.
+    ///
+    /// Args:
+    ///     text: str or a bytes-like object to chunk
+    ///
+    /// Returns:
+    ///     List of (start, end) byte offsets for each chunk.
+    fn chunk_offsets(&self, text: &Bound<'_, PyAny>) -> PyResult<Vec<(usize, usize)>> {
+        let text = extract_text(text)?;
+        Ok(self.inner.chunk_offsets(text.as_ref()))
+    }
+}
+
+/// A chunker for data that arrives in pieces (sockets, pipes, async files).
+///
+/// Feed it bytes with push(), which returns the chunks whose boundaries are
+/// known so far, then call finish() for the rest. The chunks are the same as
+/// Chunker would give for the whole input, however it is split across push()
+/// calls; only the unfinished tail is buffered. See aiter_chunks for reading
+/// an asyncio stream.
+///
+/// save_state() and restore_state() checkpoint a long stream, so a job can
+/// pick up where it was after a crash.
+///
+/// Example:
+///     >>> from chonkie_core import StreamChunker
+///     >>> stream = StreamChunker(size=10, delimiters=".")
+///     >>> stream.push(b"Hello. Wor") + stream.push(b"ld. Test.") + stream.finish()
+///     [b'Hello.', b' World.', b' Test.']
+#[pyclass]
+pub struct StreamChunker {
+    inner: RustStreamChunker,
+}
+
+#[pymethods]
+impl StreamChunker {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (size=DEFAULT_TARGET_SIZE, delimiters=None, pattern=None, patterns=None, prefix=false, consecutive=false, forward_fallback=false, overlap=0, utf8_safe=false, min_size=0, max_size=None))]
+    fn new(
+        size: usize,
+        delimiters: Option<&Bound<'_, PyAny>>,
+        pattern: Option<&Bound<'_, PyAny>>,
+        patterns: Option<Vec<Bound<'_, PyAny>>>,
+        prefix: bool,
+        consecutive: bool,
+        forward_fallback: bool,
+        overlap: usize,
+        utf8_safe: bool,
+        min_size: usize,
+        max_size: Option<usize>,
+    ) -> PyResult<Self> {
+        let mut inner = RustStreamChunker::new()
+            .size(size)
+            .overlap(overlap)
+            .utf8_safe(utf8_safe)
+            .min_size(min_size);
+        if let Some(d) = delimiters {
+            inner = match delimiter_class(d) {
+                Some(class) => inner.delimiter_class(class),
+                None => inner.delimiters(extract_bytes(d)?),
+            };
+        }
+        // Pattern (singular) takes precedence over delimiters if both specified
+        if let Some(p) = pattern {
+            inner = inner.pattern(extract_bytes(p)?);
+        }
+        if let Some(pats) = patterns {
+            let pattern_bytes = pats
+                .iter()
+                .map(|p| extract_bytes(p))
+                .collect::<PyResult<Vec<Vec<u8>>>>()?;
+            inner = inner.patterns(&pattern_bytes);
+        }
+        if prefix {
+            inner = inner.prefix();
+        }
+        if consecutive {
+            inner = inner.consecutive();
+        }
+        if forward_fallback {
+            inner = inner.forward_fallback();
+        }
+        if let Some(max_size) = max_size {
+            inner = inner.max_size(max_size);
+        }
+        Ok(Self { inner })
+    }
+
+    /// Bytes buffered but not yet emitted.
+    #[getter]
+    fn pending(&self) -> usize {
+        self.inner.pending()
+    }
+
+    /// Add data and return the chunks that are now complete.
+    ///
+    /// Args:
+    ///     data: str or a bytes-like object
+    ///
+    /// Returns:
+    ///     List of bytes, possibly empty.
+    fn push(&mut self, py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Vec<Py<PyBytes>>> {
+        let data = extract_text(data)?;
+        let chunks = self.inner.push(data.as_ref());
+        Ok(chunks
+            .iter()
+            .map(|c| PyBytes::new(py, c).unbind())
+            .collect())
+    }
+
+    /// Emit whatever is left at the end of the input.
+    ///
+    /// Returns:
+    ///     List of bytes, possibly empty. The chunker can be reused afterwards.
+    fn finish(&mut self, py: Python<'_>) -> Vec<Py<PyBytes>> {
+        let chunks = self.inner.finish();
+        chunks
+            .iter()
+            .map(|c| PyBytes::new(py, c).unbind())
+            .collect()
+    }
+
+    /// Where the stream has got to, covering every chunk returned so far.
+    ///
+    /// Returns:
+    ///     Dict of ints, safe to store as JSON: "position" (where the next
+    ///     chunk starts), "resume_from" (where input has to be pushed again
+    ///     from), "chunks" (returned so far) and "config" (a checksum of the
+    ///     settings).
+    fn save_state(&self) -> HashMap<&'static str, u64> {
+        let checkpoint = self.inner.save_state();
+        HashMap::from([
+            ("position", checkpoint.position),
+            ("resume_from", checkpoint.resume_from),
+            ("chunks", checkpoint.chunks),
+            ("config", checkpoint.config),
+        ])
+    }
+
+    /// Pick up from a save_state() dict, dropping anything pushed so far.
+    ///
+    /// Push the input again from state["resume_from"] and the chunks carry
+    /// on as if the stream had never stopped.
+    ///
+    /// Raises:
+    ///     KeyError: if a field is missing.
+    ///     ValueError: if the state was saved with different settings.
+    fn restore_state(&mut self, state: HashMap<String, u64>) -> PyResult<()> {
+        let field = |name: &str| {
+            state
+                .get(name)
+                .copied()
+                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>(name.to_string()))
+        };
+        let checkpoint = Checkpoint {
+            position: field("position")?,
+            resume_from: field("resume_from")?,
+            chunks: field("chunks")?,
+            config: field("config")?,
+        };
+        // Checked first, as a failed restore would drop the settings
+        let chunker = self.inner.save_state().config;
+        if checkpoint.config != chunker {
+            let err = CheckpointMismatch {
+                checkpoint: checkpoint.config,
+                chunker,
+            };
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                err.to_string(),
+            ));
+        }
+        let inner = std::mem::take(&mut self.inner);
+        self.inner = inner.restore_state(&checkpoint).expect("checksums match");
+        Ok(())
+    }
+}
+
+/// A chunker that splits source code at function/class boundaries.
+///
+/// Chunks end at the shallowest line start (bracket nesting, or indentation
+/// for Python) within `size` bytes, ignoring brackets inside strings and
+/// comments, so top-level definitions stay whole when they fit.
+///
+/// Args:
+///     language: "rust", "python", "javascript", "typescript", "go", "java",
+///         "c" or "cpp" (common extensions like "py" or "ts" also work)
+///
+/// Example:
+///     >>> from chonkie_core import CodeChunker
+///     >>> chunker = CodeChunker("python", size=2048)
+///     >>> offsets = chunker.chunk_offsets(source)
+///     >>> chunks = [source[start:end] for start, end in offsets]
+#[pyclass]
+pub struct CodeChunker {
+    inner: RustCodeChunker,
+}
+
+#[pymethods]
+impl CodeChunker {
+    #[new]
+    #[pyo3(signature = (language, size=DEFAULT_TARGET_SIZE, utf8_safe=false))]
+    fn new(language: &str, size: usize, utf8_safe: bool) -> PyResult<Self> {
+        let language = language.parse().map_err(|e: chunk::UnknownLanguage| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string())
+        })?;
+        let inner = RustCodeChunker::new(language)
+            .size(size)
+            .utf8_safe(utf8_safe);
+        Ok(Self { inner })
+    }
+
+    /// Chunk source code at definition boundaries.
+    ///
+    /// Args:
+    ///     text: str or a bytes-like object to chunk
+    ///
+    /// Returns:
+    ///     List of (start, end) byte offsets for each chunk.
+    fn chunk_offsets(&self, text: &Bound<'_, PyAny>) -> PyResult<Vec<(usize, usize)>> {
+        let text = extract_text(text)?;
+        Ok(self.inner.chunk_offsets(text.as_ref()))
+    }
+}
+
+/// A chunker that ends chunks only at real sentence boundaries.
+///
+/// Sentences come from Unicode sentence segmentation (UAX #29), so "3.14"
+/// never splits, and an abbreviation list keeps "Dr. Smith" together. Whole
+/// sentences are packed up to `size` bytes; a longer sentence is hard split at
+/// a character boundary. With `sentences_per_chunk`, every chunk holds that
+/// many sentences instead, and `sentence_overlap` repeats the last few
+/// sentences of a chunk at the start of the next.
+///
+/// Example:
+///     >>> from chonkie_core import SentenceChunker
+///     >>> chunker = SentenceChunker(size=1024)
+///     >>> offsets = chunker.chunk_offsets(text)
+///     >>> chunks = [text.encode()[start:end] for start, end in offsets]
+#[pyclass]
+pub struct SentenceChunker {
+    inner: RustSentenceChunker,
+}
+
+#[pymethods]
+impl SentenceChunker {
+    #[new]
+    #[pyo3(signature = (size=DEFAULT_TARGET_SIZE, abbreviations=None, sentences_per_chunk=None, sentence_overlap=0))]
+    fn new(
+        size: usize,
+        abbreviations: Option<Vec<String>>,
+        sentences_per_chunk: Option<usize>,
+        sentence_overlap: usize,
+    ) -> Self {
+        let mut inner = RustSentenceChunker::new()
+            .size(size)
+            .sentence_overlap(sentence_overlap);
+        if let Some(abbreviations) = abbreviations {
+            inner = inner.abbreviations(&abbreviations);
+        }
+        if let Some(count) = sentences_per_chunk {
+            inner = inner.sentences_per_chunk(count);
+        }
+        Self { inner }
+    }
+
+    /// The abbreviations that don't end a sentence.
+    #[getter]
+    fn abbreviations(&self) -> Vec<String> {
+        self.inner.abbreviation_list().to_vec()
+    }
+
+    /// Chunk text at sentence boundaries.
+    ///
+    /// Args:
+    ///     text: str, or UTF-8 encoded bytes
+    ///
+    /// Returns:
+    ///     List of (start, end) byte offsets for each chunk.
+    fn chunk_offsets(&self, text: &Bound<'_, PyAny>) -> PyResult<Vec<(usize, usize)>> {
+        let text = extract_text(text)?;
+        Ok(self.inner.chunk_offsets(text.to_str()?))
+    }
+
+    /// Find sentence boundaries without packing them into chunks.
+    ///
+    /// Args:
+    ///     text: str, or UTF-8 encoded bytes
+    ///
+    /// Returns:
+    ///     List of (start, end) byte offsets for each sentence.
+    fn sentence_offsets(&self, text: &Bound<'_, PyAny>) -> PyResult<Vec<(usize, usize)>> {
+        let text = extract_text(text)?;
+        Ok(self.inner.sentence_offsets(text.to_str()?))
     }
 }
 
+/// Chunk text at regex matches and return offsets.
+///
+/// One-shot version of `RegexChunker`; the compiled pattern is cached between
+/// calls. `utf8_safe` defaults to True for `str` input and False for `bytes`.
+///
+/// Example:
+///     >>> text = b"Intro. Part 1: a. Part 2: b."
+///     >>> offsets = regex_chunk_offsets(text, r"Part \d+:", size=12, prefix=True)
+///     >>> chunks = [text[start:end] for start, end in offsets]
+#[pyfunction]
+#[pyo3(signature = (text, pattern, size=DEFAULT_TARGET_SIZE, prefix=false, forward_fallback=false, utf8_safe=None))]
+fn regex_chunk_offsets(
+    text: &Bound<'_, PyAny>,
+    pattern: &str,
+    size: usize,
+    prefix: bool,
+    forward_fallback: bool,
+    utf8_safe: Option<bool>,
+) -> PyResult<Vec<(usize, usize)>> {
+    let utf8_safe = utf8_safe.unwrap_or_else(|| text.is_instance_of::<PyString>());
+    let text = extract_text(text)?;
+    let chunker = regex_chunker(pattern, size, prefix, forward_fallback, utf8_safe)?;
+    Ok(chunker.chunk_offsets(text.as_ref()))
+}
+
 /// Result of merge_splits operation.
 ///
 /// Attributes:
@@ -448,6 +2875,166 @@ fn find_merge_indices(token_counts: Vec<usize>, chunk_size: usize) -> Vec<usize>
     rust_find_merge_indices(&token_counts, chunk_size)
 }
 
+/// Coalesce runs of adjacent chunks into chunks of at most max_size bytes.
+///
+/// For a second pass over fine-grained chunks (e.g. sentences). Each merged
+/// chunk spans from the start of its first chunk to the end of its last,
+/// gaps included; a chunk longer than max_size alone is kept as it is.
+///
+/// Args:
+///     offsets: (start, end) byte offsets of the chunks, in order.
+///     max_size: Maximum bytes per merged chunk.
+///
+/// Returns:
+///     List of (start, end) byte offsets of the merged chunks.
+///
+/// Example:
+///     >>> from chonkie_core import chunk_offsets, merge_chunks
+///     >>> sentences = chunk_offsets(b"One. Two. Three. Four.", size=8, delimiters=".")
+///     >>> merge_chunks(sentences, max_size=10)
+///     [(0, 9), (9, 16), (16, 22)]
+#[pyfunction]
+fn merge_chunks(offsets: Vec<(usize, usize)>, max_size: usize) -> Vec<(usize, usize)> {
+    rust_merge_chunks(&offsets, max_size)
+}
+
+/// Stable 64-bit content hash (XXH3, seed 0) of a chunk.
+///
+/// The same hash Chunker.collect_hashed_offsets returns, so chunks hashed
+/// later (or in another process) match.
+///
+/// Example:
+///     >>> from chonkie_core import chunk_hash
+///     >>> chunk_hash(b"Hello.") == chunk_hash("Hello.")
+///     True
+#[pyfunction]
+fn chunk_hash(data: &Bound<'_, PyAny>) -> PyResult<u64> {
+    Ok(rust_chunk_hash(extract_text(data)?.as_ref()))
+}
+
+/// Tokens in a text as counted by a tokenizer, e.g. to budget a prompt.
+///
+/// tokenizer is anything with an encode method (a tiktoken Encoding, a
+/// HuggingFace tokenizers.Tokenizer or transformers tokenizer), or a
+/// callable returning the tokens or their count. Bytes are decoded as UTF-8
+/// first.
+///
+/// Example:
+///     >>> from chonkie_core import count_tokens
+///     >>> count_tokens("one two three", str.split)
+///     3
+#[pyfunction]
+fn count_tokens(text: &Bound<'_, PyAny>, tokenizer: &Bound<'_, PyAny>) -> PyResult<usize> {
+    let text = match text.cast::<PyString>() {
+        Ok(text) => text.clone(),
+        Err(_) => PyString::new(
+            text.py(),
+            &String::from_utf8_lossy(extract_text(text)?.as_ref()),
+        ),
+    };
+    let tokens = match tokenizer.hasattr("encode")? {
+        true => tokenizer.call_method1("encode", (text,))?,
+        false => tokenizer.call1((text,))?,
+    };
+    match tokens.extract::<usize>() {
+        Ok(count) => Ok(count),
+        Err(_) => tokens.len(),
+    }
+}
+
+/// A fast estimate of the tokens in a text, without a tokenizer.
+///
+/// The larger of one token per 4 bytes and 1.3 tokens per word, rounded up:
+/// close to BPE counts for English prose, so leave some headroom elsewhere.
+///
+/// Example:
+///     >>> from chonkie_core import estimate_tokens
+///     >>> estimate_tokens("Hello, world! How are you?")
+///     7
+#[pyfunction]
+fn estimate_tokens(text: &Bound<'_, PyAny>) -> PyResult<usize> {
+    Ok(rust_estimate_tokens(extract_text(text)?.as_ref()))
+}
+
+/// ISO 639-3 code of the language a chunk is most likely written in, e.g.
+/// "eng", or None if it has no text to tell by.
+///
+/// The same detection Chunker.collect_language_offsets uses. Short chunks
+/// give weaker guesses.
+///
+/// Example:
+///     >>> from chonkie_core import detect_language
+///     >>> detect_language("Das Wetter ist heute wirklich schön.")
+///     'deu'
+#[pyfunction]
+fn detect_language(data: &Bound<'_, PyAny>) -> PyResult<Option<&'static str>> {
+    Ok(rust_detect_language(extract_text(data)?.as_ref()))
+}
+
+/// Find byte-identical chunks, mapping each chunk to the first one like it.
+///
+/// Args:
+///     chunks: list of str or bytes
+///
+/// Returns:
+///     For every chunk, the index of the first chunk with the same bytes (its
+///     own index when it is the first). Keep the chunks where
+///     `canonical[i] == i` to drop duplicates.
+///
+/// Example:
+///     >>> from chonkie_core import find_duplicates
+///     >>> find_duplicates([b"Header.", b"Body.", b"Header."])
+///     [0, 1, 0]
+#[pyfunction]
+fn find_duplicates(chunks: Vec<Bound<'_, PyAny>>) -> PyResult<Vec<usize>> {
+    let chunks = chunks
+        .iter()
+        .map(extract_text)
+        .collect::<PyResult<Vec<_>>>()?;
+    let chunks: Vec<&[u8]> = chunks.iter().map(|c| c.as_ref()).collect();
+    Ok(rust_find_duplicates(&chunks).canonical)
+}
+
+/// Find byte-identical chunks across documents, from their chunk offsets.
+///
+/// Chunks are numbered across the batch in document order, as if the offset
+/// lists (e.g. from chunk_offsets_batch) were concatenated.
+///
+/// Args:
+///     texts: list of str or bytes
+///     offsets: one list of (start, end) byte offsets per text
+///
+/// Returns:
+///     For every chunk, the index of the first chunk with the same bytes.
+///
+/// Example:
+///     >>> texts = [b"Intro. Alpha.", b"Intro. Beta."]
+///     >>> offsets = chunk_offsets_batch(texts, size=7, delimiters=".")
+///     >>> find_batch_duplicates(texts, offsets)
+///     [0, 1, 0, 3]
+#[pyfunction]
+fn find_batch_duplicates(
+    texts: Vec<Bound<'_, PyAny>>,
+    offsets: Vec<Vec<(usize, usize)>>,
+) -> PyResult<Vec<usize>> {
+    let texts = texts
+        .iter()
+        .map(extract_text)
+        .collect::<PyResult<Vec<_>>>()?;
+    for (text, offsets) in texts.iter().zip(&offsets) {
+        if offsets
+            .iter()
+            .any(|&(start, end)| start > end || end > text.as_ref().len())
+        {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "offsets out of range for their text",
+            ));
+        }
+    }
+    let texts: Vec<&[u8]> = texts.iter().map(|t| t.as_ref()).collect();
+    Ok(rust_find_batch_duplicates(&texts, &offsets).canonical)
+}
+
 /// Merge text segments based on token counts, respecting chunk size limits.
 ///
 /// This is the Rust equivalent of Chonkie's Cython `_merge_splits` function.
@@ -485,6 +3072,10 @@ fn merge_splits(splits: Vec<String>, token_counts: Vec<usize>, chunk_size: usize
 // Savitzky-Golay Filter Functions (NumPy-optimized)
 // =============================================================================
 
+/// Pair of (indices, values) numpy arrays returned by the minima/filter functions.
+#[cfg(feature = "numpy")]
+type IndicesAndValues<'py> = (Bound<'py, PyArray1<i64>>, Bound<'py, PyArray1<f64>>);
+
 /// Apply Savitzky-Golay filter to data.
 ///
 /// This filter is used for smoothing signals and computing derivatives.
@@ -504,6 +3095,7 @@ fn merge_splits(splits: Vec<String>, token_counts: Vec<usize>, chunk_size: usize
 ///     >>> from chonkie_core import savgol_filter
 ///     >>> data = np.array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0])
 ///     >>> smoothed = savgol_filter(data, window_length=5, poly_order=2)
+#[cfg(feature = "numpy")]
 #[pyfunction]
 #[pyo3(signature = (data, window_length=5, poly_order=3, deriv=0))]
 fn savgol_filter<'py>(
@@ -542,6 +3134,7 @@ fn savgol_filter<'py>(
 ///     >>> from chonkie_core import find_local_minima_interpolated
 ///     >>> data = np.array([x**2 for x in range(-10, 11)], dtype=np.float64)
 ///     >>> indices, values = find_local_minima_interpolated(data)
+#[cfg(feature = "numpy")]
 #[pyfunction]
 #[pyo3(signature = (data, window_size=11, poly_order=2, tolerance=0.2))]
 fn find_local_minima_interpolated<'py>(
@@ -550,7 +3143,7 @@ fn find_local_minima_interpolated<'py>(
     window_size: usize,
     poly_order: usize,
     tolerance: f64,
-) -> PyResult<(Bound<'py, PyArray1<i64>>, Bound<'py, PyArray1<f64>>)> {
+) -> PyResult<IndicesAndValues<'py>> {
     let data_slice = data.as_slice()?;
     let result = rust_find_local_minima(data_slice, window_size, poly_order, tolerance)
         .ok_or_else(|| {
@@ -582,6 +3175,7 @@ fn find_local_minima_interpolated<'py>(
 ///     >>> from chonkie_core import windowed_cross_similarity
 ///     >>> embeddings = np.array([[1.0, 0.0], [1.0, 0.0], [0.0, 1.0]])
 ///     >>> similarities = windowed_cross_similarity(embeddings, window_size=3)
+#[cfg(feature = "numpy")]
 #[pyfunction]
 #[pyo3(signature = (embeddings, window_size=3))]
 fn windowed_cross_similarity<'py>(
@@ -629,6 +3223,7 @@ fn windowed_cross_similarity<'py>(
 ///     >>> indices = np.array([0, 5, 8, 15, 20])
 ///     >>> values = np.array([0.1, 0.3, 0.2, 0.5, 0.4])
 ///     >>> filtered_idx, filtered_val = filter_split_indices(indices, values, threshold=0.5)
+#[cfg(feature = "numpy")]
 #[pyfunction]
 #[pyo3(signature = (indices, values, threshold=0.5, min_distance=2))]
 fn filter_split_indices<'py>(
@@ -637,7 +3232,7 @@ fn filter_split_indices<'py>(
     values: PyReadonlyArray1<'py, f64>,
     threshold: f64,
     min_distance: usize,
-) -> PyResult<(Bound<'py, PyArray1<i64>>, Bound<'py, PyArray1<f64>>)> {
+) -> PyResult<IndicesAndValues<'py>> {
     let indices_slice = indices.as_slice()?;
     let values_slice = values.as_slice()?;
 
@@ -656,19 +3251,46 @@ fn filter_split_indices<'py>(
 
 #[pymodule]
 fn _chunk(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<ArrowChunks>()?;
+    m.add_class::<CancelToken>()?;
+    m.add_class::<Chunk>()?;
     m.add_class::<Chunker>()?;
+    m.add_class::<ChunkPipeline>()?;
+    m.add_class::<ChunkerConfig>()?;
+    m.add_class::<CodeChunker>()?;
+    m.add_class::<Delimiters>()?;
     m.add_class::<MergeResult>()?;
     m.add_class::<PatternSplitter>()?;
+    m.add_class::<RecursiveChunker>()?;
+    m.add_class::<Redactor>()?;
+    m.add_class::<RegexChunker>()?;
+    m.add_class::<SentenceChunker>()?;
+    m.add_class::<StreamChunker>()?;
+    m.add_function(wrap_pyfunction!(chunk_arrow, m)?)?;
+    m.add_function(wrap_pyfunction!(chunk_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_language, m)?)?;
+    m.add_function(wrap_pyfunction!(count_tokens, m)?)?;
+    m.add_function(wrap_pyfunction!(estimate_tokens, m)?)?;
     m.add_function(wrap_pyfunction!(chunk_offsets, m)?)?;
+    m.add_function(wrap_pyfunction!(chunk_offsets_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(chunk_str_offsets, m)?)?;
+    m.add_function(wrap_pyfunction!(chunk_text, m)?)?;
+    m.add_function(wrap_pyfunction!(regex_chunk_offsets, m)?)?;
     m.add_function(wrap_pyfunction!(split_offsets, m)?)?;
     m.add_function(wrap_pyfunction!(split_pattern_offsets, m)?)?;
+    m.add_function(wrap_pyfunction!(find_batch_duplicates, m)?)?;
+    m.add_function(wrap_pyfunction!(find_duplicates, m)?)?;
     m.add_function(wrap_pyfunction!(find_merge_indices, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_chunks, m)?)?;
     m.add_function(wrap_pyfunction!(merge_splits, m)?)?;
     // Savitzky-Golay functions
-    m.add_function(wrap_pyfunction!(savgol_filter, m)?)?;
-    m.add_function(wrap_pyfunction!(find_local_minima_interpolated, m)?)?;
-    m.add_function(wrap_pyfunction!(windowed_cross_similarity, m)?)?;
-    m.add_function(wrap_pyfunction!(filter_split_indices, m)?)?;
+    #[cfg(feature = "numpy")]
+    {
+        m.add_function(wrap_pyfunction!(savgol_filter, m)?)?;
+        m.add_function(wrap_pyfunction!(find_local_minima_interpolated, m)?)?;
+        m.add_function(wrap_pyfunction!(windowed_cross_similarity, m)?)?;
+        m.add_function(wrap_pyfunction!(filter_split_indices, m)?)?;
+    }
     m.add("DEFAULT_TARGET_SIZE", DEFAULT_TARGET_SIZE)?;
     m.add("DEFAULT_DELIMITERS", DEFAULT_DELIMITERS)?;
     Ok(())
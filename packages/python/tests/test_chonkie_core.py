@@ -1,3 +1,6 @@
+import json
+from pathlib import Path
+
 import pytest
 from chonkie_core import Chunker, DEFAULT_TARGET_SIZE, DEFAULT_DELIMITERS
 
@@ -68,6 +71,53 @@ class TestChunker:
         chunks2 = list(chunker)
         assert chunks1 == chunks2
 
+    def test_overlap(self):
+        text = b"Hello. World. Test."
+        chunks = list(Chunker(text, size=10, delimiters=b".", overlap=3))
+        assert chunks == [b"Hello.", b"lo. World.", b"ld. Test."]
+
+    def test_overlap_offsets(self):
+        from chonkie_core import chunk_offsets
+
+        text = b"Hello. World. Test."
+        offsets = chunk_offsets(text, size=10, delimiters=b".", overlap=3)
+        assert offsets == [(0, 6), (3, 13), (10, 19)]
+
+    def test_overlap_ratio(self):
+        from chonkie_core import ChunkerConfig, chunk_offsets
+
+        text = b"Hello. World. Test."
+        assert chunk_offsets(text, size=10, delimiters=b".", overlap_ratio=0.3) == [(0, 6), (3, 13), (10, 19)]
+        config = ChunkerConfig(size=10, delimiters=b".", overlap_ratio=0.3)
+        assert config.overlap_ratio == 0.3
+        assert "overlap_ratio=0.3" in repr(config)
+        with pytest.raises(ValueError, match="overlap ratio"):
+            ChunkerConfig(size=10, overlap_ratio=1.0)
+
+    def test_delimiter_class(self):
+        from chonkie_core import ChunkerConfig, Delimiters, StreamChunker, chunk_offsets
+
+        text = b"Split these words"
+        expected = [(0, 6), (6, 12), (12, 17)]
+        assert chunk_offsets(text, size=10, delimiters=Delimiters.WHITESPACE) == expected
+        assert chunk_offsets(text, size=10, delimiters="whitespace") == expected
+        assert Delimiters("Markdown-Headings") == Delimiters.MARKDOWN_HEADINGS
+        assert repr(Delimiters.NEWLINES) == "Delimiters.NEWLINES"
+        assert ChunkerConfig(delimiters="sentences").delimiters == b".?!\n"
+        markdown = b"# One\nIntro.\n## Two\nMore text.\n# Three\n"
+        stream = StreamChunker(size=20, delimiters=Delimiters.MARKDOWN_HEADINGS)
+        chunks = stream.push(markdown) + stream.finish()
+        assert chunks[0] == b"# One\nIntro."
+        with pytest.raises(ValueError, match="unknown delimiter class"):
+            Delimiters("words")
+
+    def test_cjk_delimiters(self):
+        from chonkie_core import Delimiters, chunk_text
+
+        text = "今日は晴れ。明日は雨！本当？"
+        assert chunk_text(text, size=20, delimiters=Delimiters.CJK) == ["今日は晴れ。", "明日は雨！", "本当？"]
+        assert chunk_text("Hi. 你好。Ok", size=10, delimiters="multilingual") == ["Hi.", " 你好。", "Ok"]
+
     def test_four_delimiters(self):
         """Test that 4+ delimiters work (uses lookup table internally)."""
         text = b"A. B? C! D; E"
@@ -75,6 +125,255 @@ class TestChunker:
         assert len(chunks) >= 2
 
 
+class TestCopyFalse:
+    """Test that copy=False yields memoryview slices of the original buffer."""
+
+    def test_yields_memoryviews(self):
+        text = b"Hello. World. Test."
+        chunks = list(Chunker(text, size=10, delimiters=b".", copy=False))
+        assert all(isinstance(c, memoryview) for c in chunks)
+        assert [bytes(c) for c in chunks] == [b"Hello.", b" World.", b" Test."]
+        assert all(c.obj is text for c in chunks)
+
+    def test_views_see_the_buffer(self):
+        text = bytearray(b"Hello. World.")
+        chunks = Chunker(text, size=8, delimiters=b".", copy=False).collect_chunks()
+        text[0:5] = b"HELLO"
+        assert bytes(chunks[0]) == b"HELLO."
+
+    def test_str_input(self):
+        chunker = Chunker("Café. Thé.", size=7, delimiters=".", copy=False)
+        assert [bytes(c).decode() for c in chunker] == ["Café.", " Thé."]
+        chunker.reset()
+        assert [bytes(c) for c in chunker.collect_chunks()] == list(Chunker("Café. Thé.", size=7, delimiters="."))
+
+    def test_numpy_array(self):
+        np = pytest.importorskip("numpy")
+
+        arr = np.frombuffer(b"Hello. World.", dtype=np.uint8).reshape(1, -1)
+        chunks = list(Chunker(arr, size=8, delimiters=b".", copy=False))
+        assert [bytes(c) for c in chunks] == [b"Hello.", b" World."]
+
+
+class TestChunkerConfig:
+    def test_matches_keyword_arguments(self):
+        from chonkie_core import ChunkerConfig, chunk_offsets
+
+        text = "Héllo. Wörld? Next line\nand more. End."
+        kwargs = {"size": 9, "delimiters": ".?", "patterns": ["\n"], "prefix": True, "overlap": 2, "max_size": 12}
+        config = ChunkerConfig(**kwargs)
+        expected = chunk_offsets(text, **kwargs)
+        assert chunk_offsets(text, config=config) == expected
+        assert Chunker(text, config=config).collect_offsets() == expected
+
+    def test_config_replaces_keywords(self):
+        from chonkie_core import ChunkerConfig, chunk_offsets
+
+        config = ChunkerConfig(size=8, delimiters=".")
+        text = b"Hello. World. Test."
+        assert chunk_offsets(text, size=100, config=config) == [(0, 6), (6, 13), (13, 19)]
+
+    def test_batch(self):
+        from chonkie_core import ChunkerConfig, chunk_offsets, chunk_offsets_batch
+
+        config = ChunkerConfig(size=8, delimiters=b".", overlap=2)
+        texts = [b"Hello. World.", b"One. Two. Three."]
+        assert chunk_offsets_batch(texts, config=config) == [chunk_offsets(t, config=config) for t in texts]
+
+    def test_utf8_safe_default_follows_input(self):
+        from chonkie_core import ChunkerConfig, chunk_offsets
+
+        config = ChunkerConfig(size=3)
+        assert config.utf8_safe is None
+        assert chunk_offsets("ééé", config=config) == [(0, 2), (2, 4), (4, 6)]
+        assert chunk_offsets("ééé".encode(), config=config) == [(0, 3), (3, 6)]
+
+    def test_attributes_and_repr(self):
+        from chonkie_core import ChunkerConfig
+
+        config = ChunkerConfig(size=512, pattern="▁", prefix=True, unit="chars", max_size=1024)
+        assert config.size == 512
+        assert config.delimiters == DEFAULT_DELIMITERS
+        assert config.pattern == "▁".encode()
+        assert config.patterns == []
+        assert config.prefix and not config.consecutive
+        assert config.unit == "chars"
+        assert config.max_size == 1024
+        assert config == ChunkerConfig(size=512, pattern="▁", prefix=True, unit="chars", max_size=1024)
+        assert config != ChunkerConfig(size=512)
+        assert repr(config) == (
+            'ChunkerConfig(size=512, delimiters=b"\\n.?", pattern=b"\\xe2\\x96\\x81", '
+            'prefix=True, unit="chars", max_size=1024)'
+        )
+        with pytest.raises(AttributeError):
+            config.size = 1
+
+    def test_invalid_unit(self):
+        from chonkie_core import ChunkerConfig
+
+        with pytest.raises(ValueError):
+            ChunkerConfig(unit="tokens")
+
+    def test_invalid_settings(self):
+        from chonkie_core import Chunker, ChunkerConfig, chunk_offsets
+
+        with pytest.raises(ValueError, match="size must be at least 1"):
+            ChunkerConfig(size=0)
+        with pytest.raises(ValueError, match=r"overlap \(8\) must be smaller than size \(8\)"):
+            Chunker(b"Hello.", size=8, overlap=8)
+        with pytest.raises(ValueError):
+            chunk_offsets(b"Hello.", size=0)
+        # A char is at least a byte, so the overlap is compared to a size in chars too
+        with pytest.raises(ValueError, match=r"overlap \(4\) must be smaller than size \(4\)"):
+            chunk_offsets(b"Hello.", size=4, overlap=4, unit="chars")
+        with pytest.raises(ValueError, match="no delimiters or patterns to split at"):
+            chunk_offsets(b"abcd", size=2, delimiters=b"")
+        assert chunk_offsets(b"abcd", size=2, delimiters=b"", patterns=["c"]) == [(0, 2), (2, 4)]
+
+
+class TestConfigFiles:
+    PROFILES = """
+[rag-default]
+size = 1024
+overlap = 128
+
+[logs]
+size = 10
+delimiters = "\\n"
+"""
+
+    def test_toml_profile(self):
+        from chonkie_core import ChunkerConfig
+
+        config = ChunkerConfig.from_toml(self.PROFILES, profile="logs")
+        assert config == ChunkerConfig(size=10, delimiters="\n")
+        assert ChunkerConfig.from_toml("size = 64\n").size == 64
+
+    def test_json_profile(self):
+        from chonkie_core import ChunkerConfig
+
+        config = ChunkerConfig.from_json('{"rag-default": {"size": 1024, "overlap": 128}}', profile="rag-default")
+        assert config == ChunkerConfig.from_toml(self.PROFILES, profile="rag-default")
+
+    def test_chunker_from_config(self):
+        import os
+        import tempfile
+
+        from chonkie_core import ChunkerConfig
+
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "profiles.toml")
+            with open(path, "w") as f:
+                f.write(self.PROFILES)
+            text = b"line one\nline two\n"
+            chunker = Chunker.from_config(path, text, profile="logs")
+            assert chunker.collect_offsets() == [(0, 9), (9, 18)]
+            assert ChunkerConfig.from_file(path, "logs").size == 10
+
+    def test_errors(self):
+        from chonkie_core import ChunkerConfig
+
+        with pytest.raises(ValueError, match="no profile named `code`"):
+            ChunkerConfig.from_toml(self.PROFILES, profile="code")
+        with pytest.raises(ValueError, match="unknown field `sise`"):
+            ChunkerConfig.from_toml("sise = 10\n")
+        with pytest.raises(ValueError, match="line 1"):
+            ChunkerConfig.from_json("{")
+        with pytest.raises(FileNotFoundError):
+            ChunkerConfig.from_file("/nonexistent/profiles.toml")
+
+
+class TestReversed:
+    def test_reversed_matches_forward(self):
+        text = "Héllo. Wörld? Next line\nand more. End."
+        forward = list(Chunker(text, size=9, delimiters=".?\n", overlap=2))
+        assert list(reversed(Chunker(text, size=9, delimiters=".?\n", overlap=2))) == forward[::-1]
+        assert list(Chunker(text, size=9, delimiters=".?\n", overlap=2).reversed()) == forward[::-1]
+
+    def test_reversed_after_forward_and_reset(self):
+        chunker = Chunker(b"a. b. c. d.", size=3, delimiters=b".")
+        assert next(chunker) == b"a."
+        assert list(chunker.reversed()) == [b" d.", b" c.", b" b."]
+        chunker.reset()
+        assert next(chunker) == b"a."
+
+    def test_reversed_copy_false(self):
+        chunks = list(reversed(Chunker(b"Hello. World.", size=8, delimiters=b".", copy=False)))
+        assert [bytes(c) for c in chunks] == [b" World.", b"Hello."]
+
+
+class TestLen:
+    def test_len_matches_chunks(self):
+        text = "Héllo. Wörld? Next line\nand more. End."
+        for size in [1, 4, 9, 100]:
+            overlap = min(2, size - 1)
+            chunker = Chunker(text, size=size, delimiters=".?\n", overlap=overlap)
+            assert len(chunker) == len(chunker.collect_offsets())
+            next(chunker)
+            assert len(chunker) == len(list(Chunker(text, size=size, delimiters=".?\n", overlap=overlap)))
+
+    def test_len_empty(self):
+        assert len(Chunker(b"")) == 0
+
+    def test_len_after_lookups(self):
+        text = b"One. Two. Six. Ten. Red. Big. Old. New."
+        fresh = len(Chunker(text, size=20, delimiters=b"."))
+        chunker = Chunker(text, size=20, delimiters=b".")
+        assert chunker.chunk_at(100) is None
+        assert chunker.seek(3) == 0
+        assert len(chunker) == fresh == len(chunker.collect_offsets())
+
+
+class TestRandomAccess:
+    def test_chunk_at(self):
+        text = b"One. Two. Three. Four."
+        chunker = Chunker(text, size=7, delimiters=b".")
+        all_chunks = list(Chunker(text, size=7, delimiters=b"."))
+        chunk = chunker.chunk_at(2)
+        assert (chunk.index, chunk.bytes) == (2, all_chunks[2])
+        assert chunker.chunk_at(len(all_chunks)) is None
+        assert next(chunker) == all_chunks[0]
+
+    def test_seek(self):
+        text = b"One. Two. Three. Four."
+        chunker = Chunker(text, size=7, delimiters=b".")
+        assert chunker.seek(text.index(b"Four")) == 3
+        assert list(chunker) == [b" Four."]
+        assert chunker.seek(len(text)) is None
+
+
+class TestChunkInfo:
+    def test_next_chunk_info(self):
+        chunker = Chunker(b"Hello. World.", size=8, delimiters=b".")
+        first = chunker.next_chunk_info()
+        assert (first.index, first.start, first.end) == (0, 0, 6)
+        assert first.bytes == b"Hello."
+        assert first.text == "Hello."
+        assert len(first) == 6
+        assert repr(first) == "Chunk(index=0, start=0, end=6)"
+        assert next(chunker) == b" World."
+        assert chunker.next_chunk_info() is None
+
+    def test_iter_chunks(self):
+        text = b"Hello. World. Test."
+        chunker = Chunker(text, size=10, delimiters=b".")
+        chunks = chunker.iter_chunks()
+        assert [c.index for c in chunks] == [0, 1, 2]
+        assert [(c.start, c.end) for c in chunks] == chunker.collect_offsets()
+        assert [c.bytes for c in chunks] == list(Chunker(text, size=10, delimiters=b"."))
+
+    def test_collect_chunks(self):
+        text = b"Hello. World. Test."
+        chunker = Chunker(text, size=10, delimiters=b".")
+        assert chunker.collect_chunks() == [b"Hello.", b" World.", b" Test."]
+        assert chunker.collect_chunks() == list(chunker)
+
+    def test_text_invalid_utf8(self):
+        chunk = Chunker("ééé".encode(), size=3).next_chunk_info()
+        with pytest.raises(ValueError):
+            chunk.text
+
+
 class TestStrInput:
     """Test that str input works (encoded as UTF-8)."""
 
@@ -105,6 +404,75 @@ class TestStrInput:
         assert chunks[0] == "Café.".encode("utf-8")
 
 
+class TestBufferInput:
+    """Test that bytes-like objects are chunked in place."""
+
+    def test_bytes_like_inputs(self):
+        from chonkie_core import chunk_offsets
+
+        text = b"Hello. World. Test."
+        expected = chunk_offsets(text, size=10, delimiters=b".")
+        for buf in (bytearray(text), memoryview(text), memoryview(text)[0:]):
+            assert chunk_offsets(buf, size=10, delimiters=b".") == expected
+            assert list(Chunker(buf, size=10, delimiters=b".")) == [b"Hello.", b" World.", b" Test."]
+
+    def test_numpy_uint8_array(self):
+        np = pytest.importorskip("numpy")
+        from chonkie_core import chunk_offsets
+
+        arr = np.frombuffer(b"Hello. World. Test.", dtype=np.uint8)
+        assert chunk_offsets(arr, size=10, delimiters=b".") == [(0, 6), (6, 13), (13, 19)]
+
+    def test_collect_offsets_np(self):
+        np = pytest.importorskip("numpy")
+        text = b"Hello. World. Test."
+        offsets = Chunker(text, size=10, delimiters=b".").collect_offsets_np()
+        assert offsets.dtype == np.uint64
+        assert offsets.shape == (3, 2)
+        assert offsets.tolist() == [[0, 6], [6, 13], [13, 19]]
+        assert Chunker(b"", size=10).collect_offsets_np().shape == (0, 2)
+
+    def test_to_jsonl(self):
+        import json
+        import os
+        import tempfile
+
+        text = 'Héllo. "Wörld".\nTest.'
+        chunker = Chunker(text, size=10, delimiters=".\n")
+        expected = chunker.collect_offsets()
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "chunks.jsonl")
+            assert chunker.to_jsonl(path) == len(expected)
+            with open(path, encoding="utf-8") as f:
+                records = [json.loads(line) for line in f]
+            with pytest.raises(FileNotFoundError):
+                chunker.to_jsonl(os.path.join(tmp, "missing", "chunks.jsonl"))
+        encoded = text.encode()
+        assert [(r["start"], r["end"]) for r in records] == expected
+        assert [r["index"] for r in records] == list(range(len(expected)))
+        assert [r["text"] for r in records] == [encoded[s:e].decode() for s, e in expected]
+        # The iterator hasn't moved
+        assert bytes(next(chunker)) == encoded[: expected[0][1]]
+
+    def test_chunker_holds_buffer(self):
+        text = bytearray(b"Hello. World. Test.")
+        chunker = Chunker(text, size=10, delimiters=b".")
+        # The exported buffer pins the bytearray, so it can't be resized away
+        with pytest.raises(BufferError):
+            text.extend(b" More.")
+        assert chunker.collect_chunks() == [b"Hello.", b" World.", b" Test."]
+        del chunker
+        text.extend(b" More.")
+
+    def test_non_contiguous_rejected(self):
+        with pytest.raises(BufferError):
+            Chunker(memoryview(b"Hello. World.")[::2])
+
+    def test_rejects_non_text(self):
+        with pytest.raises(TypeError):
+            Chunker(42)
+
+
 class TestPatterns:
     """Test multi-byte pattern support via .patterns() API."""
 
@@ -158,6 +526,860 @@ class TestPatterns:
             c.decode("utf-8")  # raises if invalid UTF-8
 
 
+    def test_patterns_longest_match(self):
+        text = "One\n\nTwo\nThree"
+        chunks = list(
+            Chunker(text, size=6, delimiters="", patterns=["\n", "\n\n"], prefix=True)
+        )
+        assert chunks[0] == b"One"
+        assert chunks[1].startswith(b"\n\n")
+
+    def test_patterns_non_utf8_bytes(self):
+        text = b"abc\xff\xfedef\xff\xfeghi"
+        chunks = list(Chunker(text, size=8, delimiters=b"", patterns=[b"\xff\xfe"]))
+        assert chunks[0] == b"abc\xff\xfe"
+        assert b"".join(chunks) == text
+
+
+class TestUtf8Safe:
+    def test_str_input_is_utf8_safe_by_default(self):
+        text = "a€b€"
+        chunks = list(Chunker(text, size=3, delimiters="."))
+        assert [c.decode("utf-8") for c in chunks] == ["a", "€", "b", "€"]
+
+    def test_bytes_input_is_not_utf8_safe_by_default(self):
+        from chonkie_core import chunk_offsets
+
+        text = "a€b€".encode()
+        assert chunk_offsets(text, size=3, delimiters=b".")[0] == (0, 3)
+
+    def test_opt_in_and_out(self):
+        from chonkie_core import chunk_offsets
+
+        text = "a€b€"
+        assert chunk_offsets(text.encode(), size=3, delimiters=b".", utf8_safe=True)[0] == (0, 1)
+        assert chunk_offsets(text, size=3, delimiters=".", utf8_safe=False)[0] == (0, 3)
+
+    def test_chunk_convenience(self):
+        from chonkie_core import chunk
+
+        chunks = [bytes(c).decode("utf-8") for c in chunk("a€b€", size=3, delimiters=".")]
+        assert chunks == ["a", "€", "b", "€"]
+
+
+class TestSizeUnit:
+    def test_chars_unit(self):
+        text = "ああああ いいいい"
+        chunks = [c.decode("utf-8") for c in Chunker(text, size=5, delimiters=" ", unit="chars")]
+        assert chunks == ["ああああ ", "いいいい"]
+
+    def test_chunk_offsets_chars_unit(self):
+        from chonkie_core import chunk_offsets
+
+        text = "ああああ いいいい"
+        assert chunk_offsets(text, size=5, delimiters=" ", unit="chars") == [(0, 13), (13, 25)]
+
+    def test_invalid_unit(self):
+        with pytest.raises(ValueError):
+            Chunker("text", unit="tokens")
+
+
+class TestMinSize:
+    def test_merges_tiny_tail(self):
+        text = b"Hello. World. Hi."
+        assert list(Chunker(text, size=14, delimiters=b".")) == [b"Hello. World.", b" Hi."]
+        assert list(Chunker(text, size=14, delimiters=b".", min_size=5)) == [text]
+
+    def test_chunk_offsets_min_size(self):
+        from chonkie_core import chunk_offsets
+
+        assert chunk_offsets(b"Hello. World. Hi.", size=14, delimiters=b".", min_size=5) == [(0, 17)]
+
+
+class TestMergeChunks:
+    def test_parent_child(self):
+        chunker = Chunker(b"One. Two. Three. Four.", size=8, delimiters=b".")
+        parents, children, parent_of = chunker.collect_parent_child(10)
+        assert children == [(0, 4), (4, 9), (9, 16), (16, 22)]
+        assert parents == [(0, 9), (9, 16), (16, 22)]
+        assert parent_of == [0, 0, 1, 2]
+
+    def test_second_pass(self):
+        from chonkie_core import chunk_offsets, merge_chunks
+
+        sentences = chunk_offsets(b"One. Two. Three. Four.", size=8, delimiters=".")
+        assert merge_chunks(sentences, 10) == [(0, 9), (9, 16), (16, 22)]
+        assert merge_chunks(sentences, max_size=100) == [(0, 22)]
+        assert merge_chunks([], 10) == []
+
+
+class TestMaxSize:
+    def test_caps_forward_fallback(self):
+        text = b"averyveryverylongword next word"
+        chunks = list(Chunker(text, size=8, pattern=b" ", forward_fallback=True, max_size=10))
+        assert all(len(c) <= 10 for c in chunks)
+        assert b"".join(chunks) == text
+
+    def test_chunk_offsets_max_size(self):
+        from chonkie_core import chunk_offsets
+
+        offsets = chunk_offsets(b"Hello. World. Test.", size=10, delimiters=b".", overlap=5, max_size=9)
+        assert offsets == [(0, 6), (4, 13), (10, 19)]
+
+
+class TestTrim:
+    def test_strips_whitespace(self):
+        text = b"\n\n  First para.\n\n   Second para.  \n"
+        chunks = list(Chunker(text, size=18, delimiters=b"\n", trim=True))
+        assert chunks == [b"First para.", b"Second para."]
+
+    def test_offsets_and_config(self):
+        from chonkie_core import ChunkerConfig, chunk_offsets, chunk_str_offsets
+
+        text = "  Café.   Thé."
+        config = ChunkerConfig(size=8, delimiters=".", trim=True)
+        assert config.trim
+        assert repr(config).endswith("trim=True)")
+        offsets = chunk_offsets(text, config=config)
+        assert [text.encode()[s:e] for s, e in offsets] == ["Café.".encode(), "Thé.".encode()]
+        str_offsets = chunk_str_offsets(text, size=8, delimiters=".", trim=True)
+        assert [text[s:e] for s, e in str_offsets] == ["Café.", "Thé."]
+
+
+class TestSkipEmpty:
+    def test_skips_blank_chunks(self):
+        from chonkie_core import ChunkerConfig, chunk_offsets
+
+        text = b"One\n\n\n\n\nTwo\n  \n"
+        chunker = Chunker(text, size=4, delimiters=b"\n", skip_empty=True)
+        assert len(chunker) == 2
+        assert list(chunker) == [b"One\n", b"Two\n"]
+        config = ChunkerConfig(size=4, delimiters=b"\n", skip_empty=True)
+        assert repr(config).endswith("skip_empty=True)")
+        assert chunk_offsets(text, config=config) == [(0, 4), (8, 12)]
+
+
+class TestDelimiterPriority:
+    def test_earlier_delimiters_win(self):
+        from chonkie_core import ChunkerConfig, chunk_offsets
+
+        text = b"One. Two\nThree. Four? Five."
+        assert chunk_offsets(text, size=20, delimiters=b"\n.?") == [(0, 15), (15, 27)]
+        assert chunk_offsets(text, size=20, delimiters=b"\n.?", delimiter_priority=True) == [(0, 9), (9, 27)]
+        chunker = Chunker(text, size=20, delimiters=b"\n.?", delimiter_priority=True)
+        assert list(chunker) == [b"One. Two\n", b"Three. Four? Five."]
+        config = ChunkerConfig(size=20, delimiters=b"\n.?", delimiter_priority=True)
+        assert config.delimiter_priority
+        assert repr(config).endswith("delimiter_priority=True)")
+
+
+class TestSlack:
+    def test_nearest_boundary_wins(self):
+        from chonkie_core import ChunkerConfig, chunk_offsets
+
+        text = b"aaaaaaa.bb.cccccc.dd"
+        assert chunk_offsets(text, size=10, delimiters=b".") == [(0, 8), (8, 18), (18, 20)]
+        assert chunk_offsets(text, size=10, delimiters=b".", slack=3) == [(0, 11), (11, 20)]
+        config = ChunkerConfig(size=10, delimiters=b".", slack=3)
+        assert config.slack == 3
+        assert repr(config).endswith("slack=3)")
+        assert list(Chunker(text, config=config)) == [b"aaaaaaa.bb.", b"cccccc.dd"]
+
+
+class TestBalanced:
+    def test_sizes_even_out(self):
+        from chonkie_core import ChunkerConfig
+
+        text = b"aaaa bbbb cccc dddd eeee ffff g"
+        assert list(Chunker(text, size=15, delimiters=b" "))[-1] == b"g"
+        chunks = list(Chunker(text, size=15, delimiters=b" ", balanced=True))
+        assert chunks == [b"aaaa bbbb ", b"cccc dddd ", b"eeee ffff g"]
+        config = ChunkerConfig(size=15, balanced=True)
+        assert config.balanced
+        assert repr(config).endswith("balanced=True)")
+
+
+class TestLinesPerChunk:
+    def test_whole_lines(self):
+        from chonkie_core import ChunkerConfig, chunk_offsets
+
+        text = b"one\ntwo\nthree\nfour\nfive"
+        chunks = list(Chunker(text, lines_per_chunk=2))
+        assert chunks == [b"one\ntwo\n", b"three\nfour\n", b"five"]
+        assert chunk_offsets(text, size=10, lines_per_chunk=2) == [(0, 8), (8, 14), (14, 23)]
+        config = ChunkerConfig(lines_per_chunk=2)
+        assert config.lines_per_chunk == 2
+        assert repr(config).endswith("lines_per_chunk=2)")
+
+
+class TestChunkHash:
+    def test_hashed_offsets(self):
+        from chonkie_core import chunk_hash
+
+        text = b"Same. Same. Other."
+        hashed = Chunker(text, size=7, delimiters=b".", trim=True).collect_hashed_offsets()
+        assert [(s, e) for s, e, _ in hashed] == [(0, 5), (6, 11), (12, 18)]
+        # Byte-identical chunks share a hash
+        assert hashed[0][2] == hashed[1][2] == chunk_hash(b"Same.")
+        assert hashed[2][2] == chunk_hash("Other.")
+        assert chunk_hash(b"") == 0x2D06800538D394C2
+
+
+class TestTokens:
+    """Test token counting and estimates."""
+
+    def test_count_tokens(self):
+        from chonkie_core import count_tokens
+
+        class Encoding:
+            def encode(self, text):
+                return text.split()
+
+        assert count_tokens("one two three", str.split) == 3
+        assert count_tokens(b"one two", Encoding()) == 2
+        assert count_tokens("one two", lambda text: len(text)) == 7
+
+    def test_estimate_tokens(self):
+        from chonkie_core import estimate_tokens
+
+        assert estimate_tokens("Hello, world! How are you?") == 7
+        assert estimate_tokens(b"a b c d e f g h i j") == 13
+        assert estimate_tokens("") == 0
+
+
+class TestLanguage:
+    """Test language detection per chunk."""
+
+    def test_language_offsets(self):
+        from chonkie_core import detect_language
+
+        text = "The weather is lovely today. Das Wetter ist heute wirklich schön."
+        langs = Chunker(text, size=40, delimiters=b".", trim=True).collect_language_offsets()
+        assert [lang for _, _, lang in langs] == ["eng", "deu"]
+        assert detect_language(text[:28]) == "eng"
+        assert detect_language(b"1234.") is None
+
+
+class TestChunkPipeline:
+    """Test per-chunk transforms and filters."""
+
+    def test_run(self):
+        from chonkie_core import ChunkPipeline, chunk_offsets
+
+        text = b"<p>One</p>\n<p>TWO</p>\n<br>\n"
+        pipeline = ChunkPipeline(strip_html=True, trim=True, lowercase=True, min_length=1)
+        chunks = pipeline.run(text, chunk_offsets(text, size=12, delimiters="\n"))
+        assert [(c.index, c.start, c.end, c.bytes) for c in chunks] == [
+            (0, 0, 11, b"one"),
+            (1, 11, 22, b"two"),
+        ]
+        with pytest.raises(ValueError):
+            pipeline.run(text, [(0, 100)])
+
+    def test_apply(self):
+        from chonkie_core import ChunkPipeline
+
+        pipeline = ChunkPipeline(redact=["secret", b"key"], replacement="***")
+        assert pipeline.apply("a secret key") == b"a *** ***"
+        assert ChunkPipeline().apply("as is") == b"as is"
+
+        pipeline = ChunkPipeline(min_density=0.3, drop_matching=[r"^Home \|"])
+        assert pipeline.apply("-------- * * * --------") is None
+        assert pipeline.apply("Home | About | Contact") is None
+        assert pipeline.apply("Real content here.") == b"Real content here."
+        with pytest.raises(ValueError):
+            ChunkPipeline(drop_matching=["("])
+
+        pipeline = ChunkPipeline(languages=["eng"])
+        assert pipeline.apply("The weather is lovely today, isn't it?") is not None
+        assert pipeline.apply("Das Wetter ist heute wirklich schön.") is None
+
+
+class TestRedactor:
+    """Test PII redaction."""
+
+    def test_redact(self):
+        from chonkie_core import Redactor
+
+        redactor = Redactor(emails=True, phone_numbers=True, patterns=[("id", r"E-[0-9]{6}", "[ID]")])
+        text, spans = redactor.redact("Mail ana@example.com or call 555-123-4567 (E-004217).")
+        assert text == b"Mail [EMAIL] or call [PHONE] ([ID])."
+        assert spans == [(5, 20, "email"), (29, 41, "phone"), (43, 51, "id")]
+        with pytest.raises(ValueError):
+            Redactor(patterns=[("bad", "(", "")])
+
+    def test_redact_chunks(self):
+        from chonkie_core import ChunkPipeline, Redactor, chunk_offsets
+
+        text = b"Hi ana@example.com.\nCall 555-123-4567.\nBye."
+        offsets = chunk_offsets(text, size=20, delimiters="\n")
+        redactor = Redactor(emails=True, phone_numbers=True)
+        chunks = redactor.redact_chunks(text, offsets)
+        assert [c[2] for c in chunks] == [b"Hi [EMAIL].\n", b"Call [PHONE].\n", b"Bye."]
+        assert [text[s:e] for _, _, _, spans in chunks for s, e, _ in spans] == [
+            b"ana@example.com",
+            b"555-123-4567",
+        ]
+
+        pipeline = ChunkPipeline(redactor=redactor, trim=True)
+        assert [c.bytes for c in pipeline.run(text, offsets)] == [b"Hi [EMAIL].", b"Call [PHONE].", b"Bye."]
+
+
+class TestScorer:
+    def test_highest_score_wins(self):
+        text = b"a.bb.ccc.dddd.eeeee"
+        seen = []
+
+        def earliest(start, end):
+            seen.append((start, end))
+            return -end
+
+        chunker = Chunker(text, size=10, delimiters=b".", scorer=earliest)
+        assert chunker.collect_offsets()[:2] == [(0, 2), (2, 5)]
+        assert seen[:3] == [(0, 9), (0, 5), (0, 2)]
+
+    def test_exceptions_propagate(self):
+        def broken(start, end):
+            raise ValueError("no score")
+
+        chunker = Chunker(b"a.bb.ccc.dddd.eeeee", size=10, delimiters=b".", scorer=broken)
+        with pytest.raises(ValueError, match="no score"):
+            list(chunker)
+
+
+class TestChunkOffsetsBatch:
+    def test_matches_chunk_offsets(self):
+        from chonkie_core import chunk_offsets, chunk_offsets_batch
+
+        texts = [b"Hello. World. Test.", "Héllo. Wörld.", b"", b"no delimiters here at all"]
+        batch = chunk_offsets_batch(texts, size=8, delimiters=b".", overlap=2)
+        assert batch == [chunk_offsets(t, size=8, delimiters=b".", overlap=2, utf8_safe=False) for t in texts]
+
+    def test_utf8_safe_default_for_str(self):
+        from chonkie_core import chunk_offsets_batch
+
+        text = "ééé"
+        assert chunk_offsets_batch([text], size=3) == [[(0, 2), (2, 4), (4, 6)]]
+        assert chunk_offsets_batch([text.encode()], size=3) == [[(0, 3), (3, 6)]]
+
+    def test_rejects_non_text(self):
+        from chonkie_core import chunk_offsets_batch
+
+        with pytest.raises(TypeError):
+            chunk_offsets_batch([b"ok", 42])
+
+
+class TestCancel:
+    def test_uncancelled_matches_batch(self):
+        from chonkie_core import CancelToken, chunk_offsets_batch
+
+        texts = [b"Hello. World.", b"One. Two. Three."]
+        token = CancelToken()
+        assert not token.cancelled
+        assert chunk_offsets_batch(texts, size=8, cancel=token) == chunk_offsets_batch(texts, size=8)
+
+    def test_cancelled_token_raises(self):
+        from chonkie_core import CancelToken, chunk_offsets_batch
+
+        token = CancelToken()
+        token.cancel()
+        assert token.cancelled
+        with pytest.raises(InterruptedError):
+            chunk_offsets_batch([b"Hello. World."], size=8, cancel=token)
+
+    def test_cancel_from_another_thread(self):
+        import threading
+
+        from chonkie_core import CancelToken, chunk_offsets_batch
+
+        token = CancelToken()
+        threading.Timer(0.01, token.cancel).start()
+        with pytest.raises(InterruptedError):
+            chunk_offsets_batch([b"word. " * 1_000_000] * 64, size=8, cancel=token)
+
+    def test_signal_handler_exception_stops_batch(self):
+        import signal
+
+        from chonkie_core import CancelToken, chunk_offsets_batch
+
+        class Stop(Exception):
+            pass
+
+        def handler(signum, frame):
+            raise Stop
+
+        previous = signal.signal(signal.SIGALRM, handler)
+        try:
+            signal.setitimer(signal.ITIMER_REAL, 0.01)
+            with pytest.raises(Stop):
+                chunk_offsets_batch([b"word. " * 1_000_000] * 64, size=8, cancel=CancelToken())
+        finally:
+            signal.setitimer(signal.ITIMER_REAL, 0)
+            signal.signal(signal.SIGALRM, previous)
+
+
+class TestSequence:
+    def test_getitem(self):
+        chunker = Chunker(b"One. Two. Three.", size=7, delimiters=b".")
+        assert next(chunker) == b"One."
+        assert (chunker[0], chunker[-1]) == (b"One.", b" Three.")
+        assert chunker[1:] == [b" Two.", b" Three."]
+        assert chunker[::-2] == [b" Three.", b"One."]
+        # Indexing doesn't move the iteration position
+        assert list(chunker) == [b" Two.", b" Three."]
+        for index in (3, -4):
+            with pytest.raises(IndexError):
+                chunker[index]
+        with pytest.raises(TypeError):
+            chunker["0"]
+
+    def test_repr(self):
+        assert repr(Chunker(b"Hello.", size=10, delimiters=b".", overlap=2)) == (
+            'Chunker(text=<6 bytes>, size=10, delimiters=b".", overlap=2, utf8_safe=False)'
+        )
+
+    def test_context_manager_releases_buffer(self):
+        text = bytearray(b"Hello. World.")
+        with Chunker(text, size=7, delimiters=b".") as chunker:
+            assert list(chunker) == [b"Hello.", b" World."]
+            with pytest.raises(BufferError):
+                text.extend(b" More.")
+        text.extend(b" More.")
+        assert list(chunker) == [] and len(chunker) == 0
+
+
+class TestPickle:
+    def test_round_trip_keeps_position(self):
+        import pickle
+
+        chunker = Chunker(b"One. Two. Three. Four. Five.", size=8, delimiters=b".")
+        assert next(chunker) == b"One."
+        restored = pickle.loads(pickle.dumps(chunker))
+        assert list(restored) == list(chunker) == [b" Two.", b" Three.", b" Four.", b" Five."]
+
+        backwards = Chunker(b"One. Two. Three. Four.", size=6, delimiters=b".", copy=False).reversed()
+        next(backwards)
+        restored = pickle.loads(pickle.dumps(backwards))
+        assert [bytes(c) for c in restored] == [bytes(c) for c in backwards]
+
+    def test_round_trip_keeps_config(self):
+        import pickle
+
+        from chonkie_core import ChunkerConfig
+
+        # utf8_safe defaulted on for the str stays on for the pickled bytes
+        chunker = Chunker("ééé", size=3)
+        assert list(pickle.loads(pickle.dumps(chunker))) == list(chunker)
+
+        config = ChunkerConfig.from_toml('size = 64\npatterns = ["\\n\\n", "\\n"]\n')
+        assert pickle.loads(pickle.dumps(config)) == config
+        assert pickle.loads(pickle.dumps(ChunkerConfig(utf8_safe=False))).utf8_safe is False
+
+    def test_multiprocessing(self):
+        import multiprocessing
+
+        chunker = Chunker(b"Hello. World. Test.", size=10, delimiters=b".")
+        with multiprocessing.get_context().Pool(1) as pool:
+            assert pool.apply(list, (chunker,)) == [b"Hello.", b" World.", b" Test."]
+
+
+class TestChunkArrow:
+    def test_lists_and_exploded(self):
+        pa = pytest.importorskip("pyarrow")
+        from chonkie_core import chunk_arrow, chunk_offsets_batch
+
+        texts = ["Hello. World.", None, "One. Two. Three."]
+        column = pa.array(texts, type=pa.large_string())
+        expected = chunk_offsets_batch([t or "" for t in texts], size=8, delimiters=b".")
+        lists = pa.array(chunk_arrow(column, size=8, delimiters=b"."))
+        assert [[(c["start"], c["end"]) for c in doc] for doc in lists.to_pylist()] == expected
+
+        table = pa.table(chunk_arrow(pa.chunked_array([column[:1], column[1:]]), size=8, delimiters=b".", explode=True))
+        assert table.column_names == ["doc_id", "start", "end"]
+        rows = list(zip(*(table.column(name).to_pylist() for name in table.column_names)))
+        assert rows == [(doc, s, e) for doc, offsets in enumerate(expected) for s, e in offsets]
+
+    def test_rejects_non_text(self):
+        from chonkie_core import chunk_arrow
+
+        with pytest.raises(TypeError):
+            chunk_arrow([b"not", b"arrow"])
+        # Arrow, but not strings
+        pa = pytest.importorskip("pyarrow")
+        with pytest.raises(TypeError):
+            chunk_arrow(pa.array([1, 2]))
+
+
+class TestFindDuplicates:
+    def test_canonical_indices(self):
+        from chonkie_core import find_duplicates
+
+        assert find_duplicates([b"a", "b", b"a", "c", "b"]) == [0, 1, 0, 3, 1]
+        assert find_duplicates([]) == []
+
+    def test_batch(self):
+        from chonkie_core import chunk_offsets_batch, find_batch_duplicates
+
+        texts = [b"Intro. Alpha.", b"Intro. Beta."]
+        offsets = chunk_offsets_batch(texts, size=7, delimiters=".")
+        assert find_batch_duplicates(texts, offsets) == [0, 1, 0, 3]
+        with pytest.raises(ValueError):
+            find_batch_duplicates(texts, [[(0, 100)]])
+
+
+class TestFromFile:
+    def test_path_and_file_object(self):
+        import os
+        import pathlib
+        import tempfile
+
+        text = "Héllo. Wörld. Test.".encode()
+        expected = Chunker(text, size=8, delimiters=b".").collect_offsets()
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "doc.txt")
+            with open(path, "wb") as f:
+                f.write(text)
+            # Paths are memory-mapped in Rust
+            chunker = Chunker.from_file(path, size=8, delimiters=b".")
+            assert chunker.collect_offsets() == expected
+            assert b"".join(Chunker.from_file(pathlib.Path(path), size=8)) == text
+            with open(path, "rb") as f:
+                assert Chunker.from_file(f, size=8, delimiters=b".").collect_offsets() == expected
+            # Text-mode files are utf8-safe by default, like str
+            with open(path, encoding="utf-8") as f:
+                chunks = Chunker.from_file(f, size=3).collect_chunks()
+            assert all(bytes(c).decode() for c in chunks)
+
+            empty = os.path.join(tmp, "empty.txt")
+            open(empty, "wb").close()
+            assert list(Chunker.from_file(empty)) == []
+            with pytest.raises(FileNotFoundError):
+                Chunker.from_file(os.path.join(tmp, "missing.txt"))
+
+
+class TestStreamChunker:
+    def test_matches_chunker(self):
+        from chonkie_core import StreamChunker
+
+        text = b"Hello. World. Test. Another sentence here."
+        stream = StreamChunker(size=10, delimiters=b".")
+        chunks = []
+        for i in range(0, len(text), 7):
+            chunks += stream.push(text[i : i + 7])
+        assert stream.pending > 0
+        chunks += stream.finish()
+        assert chunks == [bytes(c) for c in Chunker(text, size=10, delimiters=b".")]
+        assert stream.pending == 0
+
+
+class TestStreamCheckpoint:
+    def test_resume(self):
+        import json
+
+        import pytest
+
+        from chonkie_core import StreamChunker
+
+        text = b"One. Two. Six. Ten. End."
+        stream = StreamChunker(size=5, delimiters=".", overlap=2)
+        before = stream.push(text[:12])
+        state = json.loads(json.dumps(stream.save_state()))
+        assert state["chunks"] == len(before)
+
+        resumed = StreamChunker(size=5, delimiters=".", overlap=2)
+        resumed.restore_state(state)
+        after = resumed.push(text[state["resume_from"] :]) + resumed.finish()
+        full = StreamChunker(size=5, delimiters=".", overlap=2)
+        assert before + after == full.push(text) + full.finish()
+
+        other = StreamChunker(size=8, delimiters=".")
+        with pytest.raises(ValueError):
+            other.restore_state(state)
+        assert other.push(b"Hello. World.") == [b"Hello."]
+        with pytest.raises(KeyError):
+            other.restore_state({"position": 0})
+
+
+class TestAiterChunks:
+    def test_asyncio_stream(self):
+        import asyncio
+
+        from chonkie_core import aiter_chunks
+
+        text = b"Hello. World. Test. Another sentence here."
+
+        async def run():
+            reader = asyncio.StreamReader()
+            reader.feed_data(text)
+            reader.feed_eof()
+            return [c async for c in aiter_chunks(reader, size=10, delimiters=".", read_size=5)]
+
+        chunks = asyncio.run(run())
+        assert chunks == [bytes(c) for c in Chunker(text, size=10, delimiters=b".")]
+
+    def test_sync_text_file(self):
+        import asyncio
+        import io
+
+        from chonkie_core import aiter_chunks
+
+        async def run(f):
+            return [c async for c in aiter_chunks(f, size=3, read_size=2)]
+
+        # A str stream is utf8-safe by default
+        chunks = asyncio.run(run(io.StringIO("ééé")))
+        assert chunks == ["é".encode()] * 3
+        assert asyncio.run(run(io.BytesIO(b""))) == []
+
+
+class TestChunkStrOffsets:
+    def test_slices_str(self):
+        from chonkie_core import chunk_str_offsets
+
+        text = "Café. Thé. Fin."
+        offsets = chunk_str_offsets(text, size=7, delimiters=".")
+        assert [text[start:end] for start, end in offsets] == ["Café.", " Thé.", " Fin."]
+
+    def test_matches_byte_offsets(self):
+        from chonkie_core import chunk_offsets, chunk_str_offsets
+
+        text = "日本語のテキスト。次の文。😀 emoji\nend"
+        encoded = text.encode()
+        for kwargs in ({"size": 10}, {"size": 12, "overlap": 4}, {"size": 3, "unit": "chars", "max_size": 9}):
+            byte_chunks = [encoded[s:e].decode() for s, e in chunk_offsets(text, **kwargs)]
+            str_chunks = [text[s:e] for s, e in chunk_str_offsets(text, **kwargs)]
+            assert str_chunks == byte_chunks
+
+    def test_requires_str(self):
+        from chonkie_core import chunk_str_offsets
+
+        with pytest.raises(TypeError):
+            chunk_str_offsets(b"bytes")
+
+
+class TestChunkText:
+    def test_returns_strs(self):
+        from chonkie_core import chunk_text
+
+        assert chunk_text("Café. Thé. Fin.", size=7, delimiters=".") == ["Café.", " Thé.", " Fin."]
+        assert chunk_text("") == []
+
+    def test_matches_str_offsets(self):
+        from chonkie_core import chunk_str_offsets, chunk_text
+
+        text = "日本語のテキスト。次の文。😀 emoji\nend"
+        for kwargs in ({"size": 10}, {"size": 12, "overlap": 4}, {"size": 3, "unit": "chars", "max_size": 9}):
+            expected = [text[s:e] for s, e in chunk_str_offsets(text, **kwargs)]
+            assert chunk_text(text, **kwargs) == expected
+
+    def test_never_splits_chars(self):
+        from chonkie_core import ChunkerConfig, chunk_text
+
+        text = "é" * 10
+        chunks = chunk_text(text, config=ChunkerConfig(size=3, utf8_safe=False))
+        assert "".join(chunks) == text
+        assert all(len(chunk.encode()) <= 3 for chunk in chunks)
+
+    def test_requires_str(self):
+        from chonkie_core import chunk_text
+
+        with pytest.raises(TypeError):
+            chunk_text(b"bytes")
+
+    def test_template(self):
+        from chonkie_core import chunk_text
+
+        chunks = chunk_text(
+            "One. Two.",
+            size=5,
+            delimiters=".",
+            template="[doc: {title} | chunk {i}/{n}] {{{start}}}\n",
+            metadata={"title": "Notes", "year": 2024},
+        )
+        assert chunks == ["[doc: Notes | chunk 1/2] {0}\nOne.", "[doc: Notes | chunk 2/2] {4}\n Two."]
+        assert chunk_text("Année", template="{year}: ", metadata={"year": 2024}) == ["2024: Année"]
+
+    def test_template_errors(self):
+        from chonkie_core import chunk_text
+
+        with pytest.raises(ValueError, match="title"):
+            chunk_text("text", template="{title}")
+        with pytest.raises(ValueError):
+            chunk_text("text", template="{i")
+
+
+class TestProgress:
+    def test_callable(self):
+        from chonkie_core import chunk_offsets
+
+        text = b"One. Two. Six. Ten. End."
+        reports = []
+        offsets = chunk_offsets(text, size=5, delimiters=b".", progress=lambda *p: reports.append(p), progress_every=2)
+        assert offsets == chunk_offsets(text, size=5, delimiters=b".")
+        assert reports == [(offsets[1][1], 2), (offsets[3][1], 4), (len(text), 5)]
+
+    def test_tqdm_style_bar(self):
+        from chonkie_core import chunk_offsets
+
+        class Bar:
+            total = None
+            n = 0
+
+            def update(self, n):
+                self.n += n
+
+        bar = Bar()
+        text = "Hello. World. Test. " * 100
+        chunk_offsets(text, size=32, delimiters=".", progress=bar, progress_every=7)
+        assert bar.total == bar.n == len(text)
+
+    def test_hook_errors_stop_chunking(self):
+        from chonkie_core import chunk_offsets
+
+        def fail(bytes, chunks):
+            raise KeyboardInterrupt
+
+        with pytest.raises(KeyboardInterrupt):
+            chunk_offsets(b"Hello. World. " * 100, size=16, progress=fail, progress_every=1)
+
+
+class TestRecursiveChunker:
+    def test_descends_only_when_needed(self):
+        from chonkie_core import RecursiveChunker
+
+        text = b"Intro.\n\nOne. Two. Three. Four."
+        chunker = RecursiveChunker([[b"\n\n"], [b". "]], size=12)
+        chunks = [text[s:e] for s, e in chunker.chunk_offsets(text)]
+        assert chunks == [b"Intro.\n\n", b"One. Two. ", b"Three. Four."]
+
+    def test_hard_split_fallback(self):
+        from chonkie_core import RecursiveChunker
+
+        chunker = RecursiveChunker([["\n\n"], [" "]], size=5)
+        assert chunker.chunk_offsets("abcdefghijklmnop") == [(0, 5), (5, 10), (10, 15), (15, 16)]
+
+    def test_prefix(self):
+        from chonkie_core import RecursiveChunker
+
+        text = b"aaaa\nbbbb\ncccc"
+        chunker = RecursiveChunker([[b"\n"]], size=6, prefix=True)
+        chunks = [text[s:e] for s, e in chunker.chunk_offsets(text)]
+        assert chunks == [b"aaaa", b"\nbbbb", b"\ncccc"]
+
+    def test_paragraphs_preset(self):
+        from chonkie_core import RecursiveChunker
+
+        text = b"Para one.\n\nPara two is a bit longer. Yes it is."
+        chunker = RecursiveChunker.paragraphs(size=30)
+        chunks = [text[s:e] for s, e in chunker.chunk_offsets(text)]
+        assert chunks == [b"Para one.\n\n", b"Para two is a bit longer. ", b"Yes it is."]
+
+
+class TestRegexChunker:
+    def test_prefix_at_timestamps(self):
+        from chonkie_core import RegexChunker
+
+        log = b"2024-01-01 a\n2024-01-02 bb\n2024-01-03 ccc\n"
+        chunker = RegexChunker(r"(?m)^\d{4}-\d{2}-\d{2}", size=30, prefix=True)
+        chunks = [log[s:e] for s, e in chunker.chunk_offsets(log)]
+        assert chunks == [b"2024-01-01 a\n2024-01-02 bb\n", b"2024-01-03 ccc\n"]
+        assert chunker.pattern == r"(?m)^\d{4}-\d{2}-\d{2}"
+
+    def test_offsets_function(self):
+        from chonkie_core import regex_chunk_offsets
+
+        text = "Hello there. How are you? Fine."
+        assert regex_chunk_offsets(text, r"[.!?] ", size=13) == [(0, 13), (13, 26), (26, 31)]
+        # Same pattern again comes from the cache
+        assert regex_chunk_offsets(text, r"[.!?] ", size=100) == [(0, 31)]
+
+    def test_invalid_pattern(self):
+        from chonkie_core import RegexChunker
+
+        with pytest.raises(ValueError):
+            RegexChunker(r"(unclosed")
+
+
+class TestCodeChunker:
+    def test_python_definitions(self):
+        from chonkie_core import CodeChunker
+
+        src = "def a():\n    return 1\n\n\nclass B:\n    def c(self):\n        pass\n"
+        chunks = [src[s:e] for s, e in CodeChunker("python", size=40).chunk_offsets(src)]
+        assert chunks == ["def a():\n    return 1\n\n\n", "class B:\n    def c(self):\n        pass\n"]
+
+    def test_braces_in_strings_ignored(self):
+        from chonkie_core import CodeChunker
+
+        src = b'fn a() {\n    let s = "}";\n}\n\nfn b() {\n    1\n}\n'
+        chunks = [src[s:e] for s, e in CodeChunker("rs", size=30).chunk_offsets(src)]
+        assert chunks == [b'fn a() {\n    let s = "}";\n}\n\n', b"fn b() {\n    1\n}\n"]
+
+    def test_unknown_language(self):
+        from chonkie_core import CodeChunker
+
+        with pytest.raises(ValueError):
+            CodeChunker("cobol")
+
+
+class TestSentenceChunker:
+    def test_abbreviations_and_decimals(self):
+        from chonkie_core import SentenceChunker
+
+        text = "Dr. Smith paid $3.14 for it. Then he left."
+        chunker = SentenceChunker()
+        sentences = [text[s:e] for s, e in chunker.sentence_offsets(text)]
+        assert sentences == ["Dr. Smith paid $3.14 for it. ", "Then he left."]
+
+    def test_packing(self):
+        from chonkie_core import SentenceChunker
+
+        text = b"One. Two. Three. Four."
+        chunks = [text[s:e] for s, e in SentenceChunker(size=10).chunk_offsets(text)]
+        assert chunks == [b"One. Two. ", b"Three. ", b"Four."]
+
+    def test_sentences_per_chunk(self):
+        from chonkie_core import SentenceChunker
+
+        text = b"One. Two. Three. Four. Five."
+        chunker = SentenceChunker(sentences_per_chunk=2)
+        chunks = [text[s:e] for s, e in chunker.chunk_offsets(text)]
+        assert chunks == [b"One. Two. ", b"Three. Four. ", b"Five."]
+
+        chunker = SentenceChunker(sentences_per_chunk=3, sentence_overlap=1)
+        chunks = [text[s:e] for s, e in chunker.chunk_offsets(text)]
+        assert chunks == [b"One. Two. Three. ", b"Three. Four. Five."]
+
+    def test_custom_abbreviations(self):
+        from chonkie_core import SentenceChunker
+
+        chunker = SentenceChunker(abbreviations=["Dr."])
+        assert chunker.abbreviations == ["Dr"]
+        assert chunker.sentence_offsets("See Dr. Who. Or not.") == [(0, 13), (13, 20)]
+
+    def test_invalid_utf8(self):
+        from chonkie_core import SentenceChunker
+
+        with pytest.raises(ValueError):
+            SentenceChunker().chunk_offsets(b"\xff\xfe")
+
+
+class TestConformance:
+    """Shared golden files, also run by the Rust crate and the WASM package."""
+
+    def test_fixtures(self):
+        from chonkie_core import chunk_offsets
+
+        fixtures = Path(__file__).resolve().parents[3] / "tests" / "conformance"
+        if not fixtures.is_dir():
+            pytest.skip("conformance fixtures are only in the repository")
+        for path in sorted(fixtures.glob("*.json")):
+            for case in json.loads(path.read_text(encoding="utf-8"))["cases"]:
+                offsets = chunk_offsets(case["text"].encode(), **case["options"])
+                expected = [tuple(pair) for pair in case["offsets"]]
+                assert offsets == expected, f"{path.name}: {case['name']}"
+
+
 class TestConstants:
     def test_default_target_size(self):
         assert DEFAULT_TARGET_SIZE == 4096
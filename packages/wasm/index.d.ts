@@ -0,0 +1,274 @@
+/**
+ * Type declarations for @chonkiejs/chunk.
+ */
+
+/** Text input: strings are UTF-8 encoded, bytes are used as-is. */
+export type TextInput = string | Uint8Array;
+
+/** Output type follows the input: strings for strings, bytes for bytes. */
+export type Output<T extends TextInput> = T extends string ? string : Uint8Array;
+
+/** A [start, end) byte offset pair into the UTF-8 encoded text. */
+export type Offset = [start: number, end: number];
+
+/** Unit the target size is measured in. */
+export enum SizeUnit {
+    /** Count bytes (default). */
+    Bytes = 0,
+    /** Count Unicode characters. */
+    Chars = 1,
+}
+
+/** Named delimiter classes, to pass as `delimiters` instead of the characters. */
+export declare const Delimiters: {
+    /** Sentence ends and line breaks: `.`, `?`, `!` and `\n`. */
+    readonly SENTENCES: "sentences";
+    /** Spaces, tabs and line breaks, so chunks end between words. */
+    readonly WHITESPACE: "whitespace";
+    /** Line breaks. */
+    readonly NEWLINES: "newlines";
+    /** Markdown headings: chunks start at a line beginning with `#`. */
+    readonly MARKDOWN_HEADINGS: "markdown_headings";
+    /** Chinese and Japanese sentence ends and line breaks: `。`, `！`, `？`, `、` and `\n`. */
+    readonly CJK: "cjk";
+    /** SENTENCES and CJK together, for text that mixes scripts. */
+    readonly MULTILINGUAL: "multilingual";
+};
+
+/** Options for `chunk`, `chunk_offsets` and `Chunker`. */
+export interface ChunkOptions {
+    /** Target chunk size in bytes (default: 4096). */
+    size?: number;
+    /** Delimiter characters, or a class name from `Delimiters` (default: "\n.?"). */
+    delimiters?: string;
+    /** Multi-byte pattern to split on instead of delimiters. */
+    pattern?: TextInput;
+    /** Multi-byte patterns, composable with delimiters (longest match wins). */
+    patterns?: TextInput[];
+    /** Put delimiter/pattern at start of next chunk (default: false). */
+    prefix?: boolean;
+    /** Split at START of consecutive runs (default: false). */
+    consecutive?: boolean;
+    /** Search forward if no pattern in backward window (default: false). */
+    forwardFallback?: boolean;
+    /** Prefer earlier delimiters over later ones, in the order given (default: false). */
+    delimiterPriority?: boolean;
+    /** Split at the boundary nearest the size, up to this far either side (default: 0). */
+    slack?: number;
+    /** Even out chunk sizes instead of filling chunks greedily (default: false). */
+    balanced?: boolean;
+    /** Group up to this many complete lines per chunk, with the size as a cap (default: none). */
+    linesPerChunk?: number;
+    /** Bytes of trailing context carried into the next chunk (default: 0). */
+    overlap?: number;
+    /** Overlap as a share of the size instead, from 0 up to (not including) 1; replaces `overlap`. */
+    overlapRatio?: number;
+    /** Never split inside a multi-byte character (default: true for strings). */
+    utf8Safe?: boolean;
+    /** Unit the size is measured in (default: "bytes"). */
+    unit?: "bytes" | "chars" | SizeUnit;
+    /** Merge a trailing chunk smaller than this into the previous one (default: 0). */
+    minSize?: number;
+    /** Strict cap on chunk length in bytes (default: none). */
+    maxSize?: number;
+    /** Strip leading and trailing whitespace from chunks (default: false). */
+    trim?: boolean;
+    /** Leave out empty and whitespace-only chunks (default: false). */
+    skipEmpty?: boolean;
+}
+
+/** Options for `split` and `split_offsets`. */
+export interface SplitOptions {
+    /** Delimiter characters (default: "\n.?"). */
+    delimiters?: string;
+    /** Where to attach the delimiter (default: "prev"). */
+    includeDelim?: "prev" | "next" | "none";
+    /** Minimum characters per segment; shorter segments are merged (default: 0). */
+    minChars?: number;
+}
+
+/** Options for `RecursiveChunker`. */
+export interface RecursiveOptions {
+    /** Target chunk size in bytes (default: 4096). */
+    size?: number;
+    /** Put delimiter at start of next chunk (default: false). */
+    prefix?: boolean;
+    /** Never hard split inside a multi-byte character (default: false). */
+    utf8Safe?: boolean;
+}
+
+/** Options for `SentenceChunker`. */
+export interface SentenceOptions {
+    /** Target chunk size in bytes (default: 4096). */
+    size?: number;
+    /** Words that don't end a sentence (default: built-in list). */
+    abbreviations?: string[];
+}
+
+/** Options for `StreamChunker` and `chunkStream`. */
+export type StreamOptions = Pick<
+    ChunkOptions,
+    | "size"
+    | "delimiters"
+    | "pattern"
+    | "patterns"
+    | "prefix"
+    | "consecutive"
+    | "forwardFallback"
+    | "overlap"
+    | "utf8Safe"
+    | "minSize"
+    | "maxSize"
+>;
+
+/** Chunks packed back to back into one buffer, from `Chunker.collectPacked`. */
+export interface PackedChunks {
+    /** Every chunk's bytes, back to back. */
+    bytes: Uint8Array;
+    /** Flat [start1, end1, start2, end2, ...] offsets into `bytes`. */
+    offsets: Uint32Array;
+}
+
+/** Result of `Chunker.collectParentChild`. */
+export interface ParentChildChunks {
+    /** Offsets of the parent chunks. */
+    parents: Offset[];
+    /** Offsets of the child chunks. */
+    children: Offset[];
+    /** For every child, the index of its parent in `parents`. */
+    parentOf: number[];
+}
+
+/** Result of `merge_splits`. */
+export interface MergeResult {
+    /** End index (exclusive) of each merged chunk. */
+    indices: number[];
+    /** Token count of each merged chunk. */
+    tokenCounts: number[];
+}
+
+/** Initialize the WASM module. Must be called before anything else. */
+export function init(): Promise<void>;
+
+/** Get the default target size (4096 bytes). */
+export function default_target_size(): number;
+
+/** Get the default delimiters ("\n.?") as bytes. */
+export function default_delimiters(): Uint8Array;
+
+/** Split text into chunks at delimiter boundaries. */
+export function chunk<T extends TextInput>(text: T, options?: ChunkOptions): Generator<Output<T>, void, undefined>;
+
+/** Get chunk offsets without creating views. */
+export function chunk_offsets(text: TextInput, options?: ChunkOptions): Offset[];
+
+/** Split text at every delimiter occurrence. */
+export function split<T extends TextInput>(text: T, options?: SplitOptions): Generator<Output<T>, void, undefined>;
+
+/** Get split offsets without creating views. */
+export function split_offsets(text: TextInput, options?: SplitOptions): Offset[];
+
+/** Views of packed chunks (from `Chunker.collectPacked`); works without init. */
+export function unpackChunks(packed: { bytes: Uint8Array; offsets: ArrayLike<number> }): Uint8Array[];
+
+/** Merge segments based on token counts, respecting chunk size limits. */
+export function merge_splits(
+    tokenCounts: number[] | Uint32Array,
+    chunkSize: number,
+    combineWhitespace?: boolean,
+): MergeResult;
+
+/** Coalesce runs of adjacent chunks into chunks of at most `maxSize` bytes. */
+export function merge_chunks(offsets: Offset[], maxSize: number): Offset[];
+
+/** Count the tokens in a text with a tokenizer (an object with `encode`, or a function). */
+export function count_tokens(
+    text: TextInput,
+    tokenizer: ((text: string) => number | ArrayLike<unknown>) | { encode(text: string): ArrayLike<unknown> },
+): number;
+
+/** A fast estimate of the tokens in a text: the larger of bytes/4 and 1.3 per word, rounded up. */
+export function estimate_tokens(text: TextInput): number;
+
+/** Chunker splits text at delimiter boundaries. */
+export class Chunker<T extends TextInput = TextInput> implements Iterable<Output<T>> {
+    constructor(text: T, options?: ChunkOptions);
+    /** Get the next chunk (same type as input), or undefined if exhausted. */
+    next(): Output<T> | undefined;
+    /** Get the next chunk as a string, or undefined if exhausted. */
+    nextText(): string | undefined;
+    /** Reset the chunker to iterate from the beginning. */
+    reset(): void;
+    /** Get the chunk at `index` without iterating to it. */
+    chunkAt(index: number): Output<T> | undefined;
+    /** Count all chunks without creating them. */
+    countChunks(): number;
+    /** Move to the chunk containing a UTF-8 byte offset; returns its index. */
+    seek(offset: number): number | undefined;
+    /** Collect all chunk offsets in a single WASM call. */
+    collectOffsets(): Offset[];
+    /** Children grouped into parents of at most `parentSize` bytes. */
+    collectParentChild(parentSize: number): ParentChildChunks;
+    /** Collect all chunk offsets in UTF-16 code units, for `text.slice(start, end)`. */
+    collectUtf16Offsets(): Offset[];
+    /** Collect all chunks (same type as input) in a single WASM call. */
+    collectChunks(): Output<T>[];
+    /** Collect all chunks as strings in a single WASM call. */
+    collectTexts(): string[];
+    /** Collect all chunks packed into one transferable buffer. */
+    collectPacked(): PackedChunks;
+    /** Free the underlying WASM memory. */
+    free(): void;
+    [Symbol.iterator](): Iterator<Output<T>>;
+}
+
+/** Recursive chunker over ordered delimiter levels. */
+export class RecursiveChunker {
+    constructor(levels: TextInput[][], options?: RecursiveOptions);
+    /** Blank lines first, then sentence ends, then hard splits. */
+    static paragraphs(options?: Omit<RecursiveOptions, "prefix">): RecursiveChunker;
+    /** Chunk text and return [start, end] byte offsets. */
+    chunkOffsets(text: TextInput): Offset[];
+    /** Chunk text, returning the same type as the input. */
+    chunk<T extends TextInput>(text: T): Generator<Output<T>, void, undefined>;
+    /** Free the underlying WASM memory. */
+    free(): void;
+}
+
+/** Sentence-aware chunker. */
+export class SentenceChunker {
+    constructor(options?: SentenceOptions);
+    /** Chunk text and return [start, end] byte offsets. */
+    chunkOffsets(text: TextInput): Offset[];
+    /** Find sentence boundaries and return [start, end] byte offsets. */
+    sentenceOffsets(text: TextInput): Offset[];
+    /** Chunk text, returning the same type as the input. */
+    chunk<T extends TextInput>(text: T): Generator<Output<T>, void, undefined>;
+    /** Free the underlying WASM memory. */
+    free(): void;
+}
+
+/** Chunker for data that arrives in pieces. */
+export class StreamChunker {
+    constructor(options?: StreamOptions);
+    /** Bytes buffered but not yet emitted. */
+    readonly pending: number;
+    /** Add data and return the chunks that are now complete. */
+    push(data: TextInput): Uint8Array[];
+    /** Emit whatever is left at the end of the input. */
+    finish(): Uint8Array[];
+    /** Free the underlying WASM memory. */
+    free(): void;
+}
+
+/** Options for `chunkStream`. */
+export interface ChunkStreamOptions extends StreamOptions {
+    /** Stops the chunking when aborted; the generator throws its reason. */
+    signal?: AbortSignal;
+}
+
+/** Chunk a `ReadableStream` as it arrives, yielding the stream's value type. */
+export function chunkStream<T extends TextInput>(
+    stream: ReadableStream<T>,
+    options?: ChunkStreamOptions,
+): AsyncGenerator<Output<T>, void, undefined>;
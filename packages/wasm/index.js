@@ -22,20 +22,59 @@
 
 import initWasm, {
     Chunker as WasmChunker,
+    RecursiveChunker as WasmRecursiveChunker,
+    SentenceChunker as WasmSentenceChunker,
+    StreamChunker as WasmStreamChunker,
     default_target_size,
     default_delimiters,
-    chunk_offsets as wasmChunkOffsets,
-    chunk_offsets_pattern as wasmChunkOffsetsPattern,
+    chunk_offsets_with_options as wasmChunkOffsets,
     split_offsets as wasmSplitOffsets,
     merge_splits as wasmMergeSplits,
+    merge_chunks as wasmMergeChunks,
+    estimate_tokens as wasmEstimateTokens,
+    SizeUnit,
     initSync as initWasmSync,
 } from './pkg/chonkiejs_chunk.js';
 
-export { default_target_size, default_delimiters };
+export { default_target_size, default_delimiters, SizeUnit };
+
+/**
+ * Named delimiter classes, to pass as `delimiters` instead of the characters.
+ *
+ * @example
+ * chunk("Split these words", { size: 10, delimiters: Delimiters.WHITESPACE });
+ */
+export const Delimiters = Object.freeze({
+    /** Sentence ends and line breaks: `.`, `?`, `!` and `\n`. */
+    SENTENCES: "sentences",
+    /** Spaces, tabs and line breaks, so chunks end between words. */
+    WHITESPACE: "whitespace",
+    /** Line breaks. */
+    NEWLINES: "newlines",
+    /** Markdown headings: chunks start at a line beginning with `#`. */
+    MARKDOWN_HEADINGS: "markdown_headings",
+    /** Chinese and Japanese sentence ends and line breaks: `。`, `！`, `？`, `、` and `\n`. */
+    CJK: "cjk",
+    /** SENTENCES and CJK together, for text that mixes scripts. */
+    MULTILINGUAL: "multilingual",
+});
 
 const encoder = new TextEncoder();
 const decoder = new TextDecoder();
 
+/**
+ * Turn a flat [start1, end1, start2, end2, ...] array into pairs.
+ * @param {ArrayLike<number>} flat
+ * @returns {Array<[number, number]>}
+ */
+function toPairs(flat) {
+    const pairs = [];
+    for (let i = 0; i < flat.length; i += 2) {
+        pairs.push([flat[i], flat[i + 1]]);
+    }
+    return pairs;
+}
+
 /**
  * Convert input to bytes if it's a string.
  * @param {string | Uint8Array} input
@@ -45,6 +84,29 @@ function toBytes(input) {
     return typeof input === 'string' ? encoder.encode(input) : input;
 }
 
+/**
+ * Turn packed chunks (from `Chunker.collectPacked`) into Uint8Array views.
+ * The views share `packed.bytes`, so nothing is copied, and this works
+ * without the WASM module (e.g. in a Worker that only received the data).
+ *
+ * @param {{ bytes: Uint8Array, offsets: ArrayLike<number> }} packed - Packed chunks
+ * @returns {Uint8Array[]} One view per chunk
+ *
+ * @example
+ * // In the Worker
+ * onmessage = ({ data }) => {
+ *     const texts = unpackChunks(data).map((slice) => decoder.decode(slice));
+ * };
+ */
+export function unpackChunks(packed) {
+    const { bytes, offsets } = packed;
+    const chunks = [];
+    for (let i = 0; i < offsets.length; i += 2) {
+        chunks.push(bytes.subarray(offsets[i], offsets[i + 1]));
+    }
+    return chunks;
+}
+
 /**
  * Split text into chunks at delimiter boundaries.
  * Accepts strings or Uint8Array. Returns the same type as input.
@@ -52,12 +114,26 @@ function toBytes(input) {
  * @param {string | Uint8Array} text - The text to chunk
  * @param {Object} [options] - Options
  * @param {number} [options.size=4096] - Target chunk size in bytes
- * @param {string} [options.delimiters="\n.?"] - Delimiter characters
+ * @param {string} [options.delimiters="\n.?"] - Delimiter characters, or a class name from `Delimiters`
  * @param {string | Uint8Array} [options.pattern] - Multi-byte pattern to split on
+ * @param {Array<string | Uint8Array>} [options.patterns] - Multi-byte patterns, composable with delimiters (longest match wins)
  * @param {boolean} [options.prefix=false] - Put delimiter/pattern at start of next chunk
  * @param {boolean} [options.consecutive=false] - Split at START of consecutive runs
  * @param {boolean} [options.forwardFallback=false] - Search forward if no pattern in backward window
+ * @param {boolean} [options.delimiterPriority=false] - Prefer earlier delimiters over later ones
+ * @param {number} [options.slack=0] - Split at the boundary nearest the size, up to this far either side
+ * @param {boolean} [options.balanced=false] - Even out chunk sizes instead of filling chunks greedily
+ * @param {number} [options.linesPerChunk] - Group up to this many complete lines per chunk, with the size as a cap
+ * @param {number} [options.overlap=0] - Bytes of trailing context carried into the next chunk
+ * @param {number} [options.overlapRatio] - Overlap as a share of the size instead, from 0 up to (not including) 1
+ * @param {boolean} [options.utf8Safe] - Never split inside a multi-byte character (default: true for strings)
+ * @param {"bytes" | "chars" | SizeUnit} [options.unit="bytes"] - Unit the size is measured in
+ * @param {number} [options.minSize=0] - Merge a trailing chunk smaller than this into the previous one
+ * @param {number} [options.maxSize] - Strict cap on chunk length in bytes
+ * @param {boolean} [options.trim=false] - Strip leading and trailing whitespace from chunks
+ * @param {boolean} [options.skipEmpty=false] - Leave out empty and whitespace-only chunks
  * @yields {string | Uint8Array} Chunks (same type as input)
+ * @throws {Error} If size is 0, or overlap isn't smaller than a size in bytes
  *
  * @example
  * // String input returns strings
@@ -74,15 +150,7 @@ function toBytes(input) {
 export function* chunk(text, options = {}) {
     const isString = typeof text === 'string';
     const bytes = toBytes(text);
-    const { size, delimiters, pattern, patterns, prefix, consecutive, forwardFallback } = options;
-
-    let flat;
-    if (pattern) {
-        const patternBytes = toBytes(pattern);
-        flat = wasmChunkOffsetsPattern(bytes, size ?? 4096, patternBytes, prefix, consecutive, forwardFallback);
-    } else {
-        flat = wasmChunkOffsets(bytes, size, delimiters, prefix, consecutive, forwardFallback, patterns);
-    }
+    const flat = wasmChunkOffsets(bytes, { ...options, utf8Safe: options.utf8Safe ?? isString });
 
     for (let i = 0; i < flat.length; i += 2) {
         const slice = bytes.subarray(flat[i], flat[i + 1]);
@@ -97,25 +165,30 @@ export function* chunk(text, options = {}) {
  * @param {string | Uint8Array} text - The text to chunk
  * @param {Object} [options] - Options
  * @param {number} [options.size=4096] - Target chunk size in bytes
- * @param {string} [options.delimiters="\n.?"] - Delimiter characters
+ * @param {string} [options.delimiters="\n.?"] - Delimiter characters, or a class name from `Delimiters`
  * @param {string | Uint8Array} [options.pattern] - Multi-byte pattern to split on
- * @param {string[]} [options.patterns] - Multi-byte patterns, composable with delimiters
+ * @param {Array<string | Uint8Array>} [options.patterns] - Multi-byte patterns, composable with delimiters (longest match wins)
  * @param {boolean} [options.prefix=false] - Put delimiter/pattern at start of next chunk
  * @param {boolean} [options.consecutive=false] - Split at START of consecutive runs
  * @param {boolean} [options.forwardFallback=false] - Search forward if no pattern in backward window
+ * @param {boolean} [options.delimiterPriority=false] - Prefer earlier delimiters over later ones
+ * @param {number} [options.slack=0] - Split at the boundary nearest the size, up to this far either side
+ * @param {boolean} [options.balanced=false] - Even out chunk sizes instead of filling chunks greedily
+ * @param {number} [options.linesPerChunk] - Group up to this many complete lines per chunk, with the size as a cap
+ * @param {number} [options.overlap=0] - Bytes of trailing context carried into the next chunk
+ * @param {number} [options.overlapRatio] - Overlap as a share of the size instead, from 0 up to (not including) 1
+ * @param {boolean} [options.utf8Safe] - Never split inside a multi-byte character (default: true for strings)
+ * @param {"bytes" | "chars" | SizeUnit} [options.unit="bytes"] - Unit the size is measured in
+ * @param {number} [options.minSize=0] - Merge a trailing chunk smaller than this into the previous one
+ * @param {number} [options.maxSize] - Strict cap on chunk length in bytes
+ * @param {boolean} [options.trim=false] - Strip leading and trailing whitespace from chunks
+ * @param {boolean} [options.skipEmpty=false] - Leave out empty and whitespace-only chunks
  * @returns {Array<[number, number]>} Array of [start, end] byte offset pairs
  */
 export function chunk_offsets(text, options = {}) {
+    const isString = typeof text === 'string';
     const bytes = toBytes(text);
-    const { size, delimiters, pattern, patterns, prefix, consecutive, forwardFallback } = options;
-
-    let flat;
-    if (pattern) {
-        const patternBytes = toBytes(pattern);
-        flat = wasmChunkOffsetsPattern(bytes, size ?? 4096, patternBytes, prefix, consecutive, forwardFallback);
-    } else {
-        flat = wasmChunkOffsets(bytes, size, delimiters, prefix, consecutive, forwardFallback, patterns);
-    }
+    const flat = wasmChunkOffsets(bytes, { ...options, utf8Safe: options.utf8Safe ?? isString });
 
     const pairs = [];
     for (let i = 0; i < flat.length; i += 2) {
@@ -218,6 +291,58 @@ export function merge_splits(tokenCounts, chunkSize, combineWhitespace = false)
     return { indices, tokenCounts: counts };
 }
 
+/**
+ * Coalesce runs of adjacent chunks into chunks of at most `maxSize` bytes,
+ * e.g. a second pass packing sentence chunks into larger windows.
+ *
+ * Each merged chunk spans from the start of its first chunk to the end of its
+ * last, gaps included; a chunk longer than `maxSize` alone is kept as it is.
+ *
+ * @param {Array<[number, number]>} offsets - [start, end] byte offset pairs, in order
+ * @param {number} maxSize - Maximum bytes per merged chunk
+ * @returns {Array<[number, number]>} The merged chunks' [start, end] pairs
+ *
+ * @example
+ * const sentences = chunk_offsets("One. Two. Three. Four.", { size: 8, delimiters: "." });
+ * merge_chunks(sentences, 10);
+ * // [[0, 9], [9, 16], [16, 22]]
+ */
+export function merge_chunks(offsets, maxSize) {
+    return toPairs(wasmMergeChunks(new Uint32Array(offsets.flat()), maxSize));
+}
+
+/**
+ * Count the tokens in a text with a tokenizer, e.g. to budget a prompt.
+ *
+ * @param {string | Uint8Array} text - The text to count (bytes are decoded as UTF-8)
+ * @param {Function | {encode: Function}} tokenizer - Anything with an `encode` method
+ *     (e.g. a js-tiktoken encoding), or a function returning the tokens or their count
+ * @returns {number} The token count
+ *
+ * @example
+ * count_tokens("one two three", (text) => text.split(" ")); // 3
+ */
+export function count_tokens(text, tokenizer) {
+    const str = typeof text === 'string' ? text : decoder.decode(text);
+    const tokens = typeof tokenizer.encode === 'function' ? tokenizer.encode(str) : tokenizer(str);
+    return typeof tokens === 'number' ? tokens : tokens.length;
+}
+
+/**
+ * A fast estimate of the tokens in a text, without a tokenizer: the larger of
+ * one token per 4 bytes and 1.3 tokens per word, rounded up. Close to BPE counts
+ * for English prose, so leave some headroom elsewhere.
+ *
+ * @param {string | Uint8Array} text - The text to estimate
+ * @returns {number} The estimated token count
+ *
+ * @example
+ * estimate_tokens("Hello, world! How are you?"); // 7
+ */
+export function estimate_tokens(text) {
+    return wasmEstimateTokens(toBytes(text));
+}
+
 let initialized = false;
 
 /**
@@ -274,24 +399,28 @@ export class Chunker {
      * @param {string | Uint8Array} text - The text to chunk
      * @param {Object} [options] - Options
      * @param {number} [options.size=4096] - Target chunk size in bytes
-     * @param {string} [options.delimiters="\n.?"] - Delimiter characters
+     * @param {string} [options.delimiters="\n.?"] - Delimiter characters, or a class name from `Delimiters`
      * @param {string | Uint8Array} [options.pattern] - Multi-byte pattern to split on
-     * @param {string[]} [options.patterns] - Multi-byte patterns, composable with delimiters
+     * @param {Array<string | Uint8Array>} [options.patterns] - Multi-byte patterns, composable with delimiters (longest match wins)
      * @param {boolean} [options.prefix=false] - Put delimiter/pattern at start of next chunk
      * @param {boolean} [options.consecutive=false] - Split at START of consecutive runs
      * @param {boolean} [options.forwardFallback=false] - Search forward if no pattern in backward window
+     * @param {boolean} [options.delimiterPriority=false] - Prefer earlier delimiters over later ones
+     * @param {number} [options.slack=0] - Split at the boundary nearest the size, up to this far either side
+     * @param {boolean} [options.balanced=false] - Even out chunk sizes instead of filling chunks greedily
+     * @param {number} [options.linesPerChunk] - Group up to this many complete lines per chunk, with the size as a cap
+     * @param {number} [options.overlap=0] - Bytes of trailing context carried into the next chunk
+     * @param {number} [options.overlapRatio] - Overlap as a share of the size instead, from 0 up to (not including) 1
+     * @param {boolean} [options.utf8Safe] - Never split inside a multi-byte character (default: true for strings)
+     * @param {"bytes" | "chars" | SizeUnit} [options.unit="bytes"] - Unit the size is measured in
+     * @param {number} [options.minSize=0] - Merge a trailing chunk smaller than this into the previous one
+     * @param {number} [options.maxSize] - Strict cap on chunk length in bytes
+     * @param {boolean} [options.trim=false] - Strip leading and trailing whitespace from chunks
+ * @param {boolean} [options.skipEmpty=false] - Leave out empty and whitespace-only chunks
      */
     constructor(text, options = {}) {
         this._isString = typeof text === 'string';
-        const bytes = toBytes(text);
-        const { size, delimiters, pattern, patterns, prefix, consecutive, forwardFallback } = options;
-
-        if (pattern) {
-            const patternBytes = toBytes(pattern);
-            this._chunker = WasmChunker.with_pattern(bytes, size ?? 4096, patternBytes, prefix, consecutive, forwardFallback);
-        } else {
-            this._chunker = new WasmChunker(bytes, size, delimiters, prefix, consecutive, forwardFallback, patterns);
-        }
+        this._chunker = WasmChunker.withOptions(text, options);
     }
 
     /**
@@ -304,6 +433,15 @@ export class Chunker {
         return this._isString ? decoder.decode(chunk) : chunk;
     }
 
+    /**
+     * Get the next chunk as a string, or undefined if exhausted.
+     * Decoded in WASM for any input type; invalid UTF-8 becomes U+FFFD.
+     * @returns {string | undefined}
+     */
+    nextText() {
+        return this._chunker.next_text();
+    }
+
     /**
      * Reset the chunker to iterate from the beginning.
      */
@@ -311,18 +449,100 @@ export class Chunker {
         this._chunker.reset();
     }
 
+    /**
+     * Get the chunk at `index` without iterating to it (e.g. to render one
+     * page). Boundaries are cached, and the iterator position doesn't move.
+     * @param {number} index - Zero-based chunk index
+     * @returns {string | Uint8Array | undefined} The chunk (same type as input)
+     */
+    chunkAt(index) {
+        const chunk = this._chunker.chunk_at(index);
+        if (chunk === undefined) return undefined;
+        return this._isString ? decoder.decode(chunk) : chunk;
+    }
+
+    /**
+     * Count all chunks without creating them (for page counts, progress bars).
+     * @returns {number}
+     */
+    countChunks() {
+        return this._chunker.count_chunks();
+    }
+
+    /**
+     * Move to the chunk containing a UTF-8 byte offset, so `next()` returns it.
+     * @param {number} offset - Byte offset into the (UTF-8 encoded) text
+     * @returns {number | undefined} The chunk's index, or undefined past the end
+     */
+    seek(offset) {
+        return this._chunker.seek(offset);
+    }
+
     /**
      * Collect all chunk offsets as an array of [start, end] pairs.
      * This is faster than iterating as it makes a single WASM call.
      * @returns {Array<[number, number]>}
      */
     collectOffsets() {
-        const flat = this._chunker.collect_offsets();
-        const pairs = [];
-        for (let i = 0; i < flat.length; i += 2) {
-            pairs.push([flat[i], flat[i + 1]]);
-        }
-        return pairs;
+        return toPairs(this._chunker.collect_offsets());
+    }
+
+    /**
+     * Collect all chunk offsets as [start, end] pairs in UTF-16 code units,
+     * so `text.slice(start, end)` works on the original JS string.
+     * @returns {Array<[number, number]>}
+     */
+    collectUtf16Offsets() {
+        return toPairs(this._chunker.collect_offsets_utf16());
+    }
+
+    /**
+     * Collect all chunks in a single WASM call.
+     * @returns {Array<string | Uint8Array>} Chunks (same type as input)
+     */
+    collectChunks() {
+        const chunks = this._chunker.collect_chunks();
+        return this._isString ? chunks.map((chunk) => decoder.decode(chunk)) : chunks;
+    }
+
+    /**
+     * Collect all chunks packed into one buffer, ready to transfer to a Worker.
+     * `bytes` holds every chunk back to back and `offsets` is a flat
+     * [start1, end1, ...] array into it; see `unpackChunks` for the chunks.
+     * @returns {{ bytes: Uint8Array, offsets: Uint32Array }}
+     *
+     * @example
+     * const packed = chunker.collectPacked();
+     * worker.postMessage(packed, [packed.bytes.buffer, packed.offsets.buffer]);
+     */
+    collectPacked() {
+        return this._chunker.collect_packed();
+    }
+
+    /**
+     * Collect the chunks as children grouped into parents of at most
+     * `parentSize` bytes, for parent document retrieval: search the small
+     * children, then hand the model the parent holding each hit. Every
+     * parent is a run of whole children.
+     * @param {number} parentSize - Maximum bytes per parent chunk
+     * @returns {{parents: Array<[number, number]>, children: Array<[number, number]>, parentOf: number[]}}
+     *
+     * @example
+     * const chunker = new Chunker("One. Two. Three. Four.", { size: 8, delimiters: "." });
+     * const { parents, parentOf } = chunker.collectParentChild(10);
+     * // parents = [[0, 9], [9, 16], [16, 22]], parentOf = [0, 0, 1, 2]
+     */
+    collectParentChild(parentSize) {
+        const { parents, children, parentOf } = this._chunker.collect_parent_child(parentSize);
+        return { parents: toPairs(parents), children: toPairs(children), parentOf: Array.from(parentOf) };
+    }
+
+    /**
+     * Collect all chunks as strings in a single WASM call, for any input type.
+     * @returns {string[]}
+     */
+    collectTexts() {
+        return this._chunker.collect_texts();
     }
 
     /**
@@ -342,3 +562,274 @@ export class Chunker {
         }
     }
 }
+
+/**
+ * Recursive chunker over ordered delimiter levels.
+ * Splits at the first level, packs pieces up to `size`, and descends to the
+ * next level only for pieces that are still too large.
+ *
+ * @example
+ * const chunker = new RecursiveChunker([["\n\n"], ["\n"], [". ", "? "]], { size: 1024 });
+ * for (const slice of chunker.chunk(text)) {
+ *     console.log(slice);
+ * }
+ * chunker.free();
+ */
+export class RecursiveChunker {
+    /**
+     * Create a new RecursiveChunker.
+     * @param {Array<Array<string | Uint8Array>>} levels - Delimiter levels, coarsest first
+     * @param {Object} [options] - Options
+     * @param {number} [options.size=4096] - Target chunk size in bytes
+     * @param {boolean} [options.prefix=false] - Put delimiter at start of next chunk
+     * @param {boolean} [options.utf8Safe=false] - Never hard split inside a multi-byte character
+     */
+    constructor(levels, options = {}) {
+        const { size, prefix, utf8Safe } = options;
+        this._chunker = new WasmRecursiveChunker(levels, size, prefix, utf8Safe);
+    }
+
+    /**
+     * Create a paragraph-priority chunker: blank lines first, then sentence
+     * ends, then hard splits.
+     * @param {Object} [options] - Options
+     * @param {number} [options.size=4096] - Target chunk size in bytes
+     * @param {boolean} [options.utf8Safe=false] - Never hard split inside a multi-byte character
+     * @returns {RecursiveChunker}
+     */
+    static paragraphs(options = {}) {
+        const { size, utf8Safe } = options;
+        const chunker = Object.create(RecursiveChunker.prototype);
+        chunker._chunker = WasmRecursiveChunker.paragraphs(size, utf8Safe);
+        return chunker;
+    }
+
+    /**
+     * Chunk text and return an array of [start, end] byte offsets.
+     * @param {string | Uint8Array} text - The text to chunk
+     * @returns {Array<[number, number]>}
+     */
+    chunkOffsets(text) {
+        return toPairs(this._chunker.chunk_offsets(toBytes(text)));
+    }
+
+    /**
+     * Chunk text. Accepts strings or Uint8Array. Returns the same type as input.
+     * @param {string | Uint8Array} text - The text to chunk
+     * @yields {string | Uint8Array} Chunks (same type as input)
+     */
+    *chunk(text) {
+        const isString = typeof text === 'string';
+        const bytes = toBytes(text);
+        const flat = this._chunker.chunk_offsets(bytes);
+        for (let i = 0; i < flat.length; i += 2) {
+            const slice = bytes.subarray(flat[i], flat[i + 1]);
+            yield isString ? decoder.decode(slice) : slice;
+        }
+    }
+
+    /**
+     * Free the underlying WASM memory.
+     */
+    free() {
+        this._chunker.free();
+    }
+}
+
+/**
+ * Sentence-aware chunker.
+ * Packs whole sentences (Unicode sentence segmentation plus an abbreviation
+ * list, so "Dr. Smith" and "3.14" stay intact) into chunks of `size` bytes.
+ *
+ * @example
+ * const chunker = new SentenceChunker({ size: 1024 });
+ * for (const slice of chunker.chunk(text)) {
+ *     console.log(slice);
+ * }
+ * chunker.free();
+ */
+export class SentenceChunker {
+    /**
+     * Create a new SentenceChunker.
+     * @param {Object} [options] - Options
+     * @param {number} [options.size=4096] - Target chunk size in bytes
+     * @param {string[]} [options.abbreviations] - Words that don't end a sentence (default: built-in list)
+     */
+    constructor(options = {}) {
+        const { size, abbreviations } = options;
+        this._chunker = new WasmSentenceChunker(size, abbreviations);
+    }
+
+    /**
+     * Chunk text and return an array of [start, end] byte offsets.
+     * @param {string | Uint8Array} text - The text to chunk (UTF-8 if bytes)
+     * @returns {Array<[number, number]>}
+     */
+    chunkOffsets(text) {
+        return toPairs(this._chunker.chunk_offsets(toBytes(text)));
+    }
+
+    /**
+     * Find sentence boundaries and return an array of [start, end] byte offsets.
+     * @param {string | Uint8Array} text - The text to segment (UTF-8 if bytes)
+     * @returns {Array<[number, number]>}
+     */
+    sentenceOffsets(text) {
+        return toPairs(this._chunker.sentence_offsets(toBytes(text)));
+    }
+
+    /**
+     * Chunk text. Accepts strings or Uint8Array. Returns the same type as input.
+     * @param {string | Uint8Array} text - The text to chunk
+     * @yields {string | Uint8Array} Chunks (same type as input)
+     */
+    *chunk(text) {
+        const isString = typeof text === 'string';
+        const bytes = toBytes(text);
+        const flat = this._chunker.chunk_offsets(bytes);
+        for (let i = 0; i < flat.length; i += 2) {
+            const slice = bytes.subarray(flat[i], flat[i + 1]);
+            yield isString ? decoder.decode(slice) : slice;
+        }
+    }
+
+    /**
+     * Free the underlying WASM memory.
+     */
+    free() {
+        this._chunker.free();
+    }
+}
+
+/**
+ * Chunker for data that arrives in pieces (fetch bodies, file streams).
+ * Feed it data with `push`, which returns the chunks whose boundaries are
+ * known so far, then call `finish` for the rest. The chunks are the same as
+ * `Chunker` gives for the whole input, however it is split across `push`
+ * calls; only the unfinished tail is buffered. See `chunkStream` for
+ * reading a `ReadableStream`.
+ *
+ * @example
+ * const stream = new StreamChunker({ size: 1024 });
+ * const chunks = [...stream.push(part1), ...stream.push(part2), ...stream.finish()];
+ * stream.free();
+ */
+export class StreamChunker {
+    /**
+     * Create a new StreamChunker.
+     * @param {Object} [options] - Options
+     * @param {number} [options.size=4096] - Target chunk size in bytes
+     * @param {string} [options.delimiters="\n.?"] - Delimiter characters, or a class name from `Delimiters`
+     * @param {string | Uint8Array} [options.pattern] - Multi-byte pattern to split on
+     * @param {Array<string | Uint8Array>} [options.patterns] - Multi-byte patterns, composable with delimiters (longest match wins)
+     * @param {boolean} [options.prefix=false] - Put delimiter/pattern at start of next chunk
+     * @param {boolean} [options.consecutive=false] - Split at START of consecutive runs
+     * @param {boolean} [options.forwardFallback=false] - Search forward if no pattern in backward window
+     * @param {number} [options.overlap=0] - Bytes of trailing context carried into the next chunk
+     * @param {boolean} [options.utf8Safe=false] - Never split inside a multi-byte character
+     * @param {number} [options.minSize=0] - Merge a trailing chunk smaller than this into the previous one
+     * @param {number} [options.maxSize] - Strict cap on chunk length in bytes
+     */
+    constructor(options = {}) {
+        this._chunker = new WasmStreamChunker(options);
+    }
+
+    /**
+     * Bytes buffered but not yet emitted.
+     * @type {number}
+     */
+    get pending() {
+        return this._chunker.pending;
+    }
+
+    /**
+     * Add data and return the chunks that are now complete.
+     * @param {string | Uint8Array} data - Next piece of input (strings are UTF-8 encoded)
+     * @returns {Uint8Array[]} Complete chunks, possibly none
+     */
+    push(data) {
+        return this._chunker.push(toBytes(data));
+    }
+
+    /**
+     * Emit whatever is left at the end of the input.
+     * The chunker can be reused afterwards.
+     * @returns {Uint8Array[]} Remaining chunks, possibly none
+     */
+    finish() {
+        return this._chunker.finish();
+    }
+
+    /**
+     * Free the underlying WASM memory.
+     */
+    free() {
+        this._chunker.free();
+    }
+}
+
+/**
+ * Chunk a WHATWG `ReadableStream` as it arrives.
+ * Chunks are yielded as soon as their boundaries are known, so a large
+ * `fetch` body or file stream never has to be held in memory at once.
+ * Streams of strings (e.g. piped through `TextDecoderStream`) yield strings
+ * and default `utf8Safe` to true; streams of bytes yield Uint8Arrays.
+ *
+ * Pass an `AbortSignal` as `options.signal` to stop part way: once it's
+ * aborted, the stream is cancelled (unblocking a pending read) and the
+ * generator throws the signal's reason before yielding another chunk.
+ *
+ * @param {ReadableStream<string | Uint8Array>} stream - The stream to read
+ * @param {Object} [options] - Options, as for `StreamChunker`
+ * @param {AbortSignal} [options.signal] - Stops the chunking when aborted
+ * @yields {string | Uint8Array} Chunks (strings for a stream of strings)
+ *
+ * @example
+ * const response = await fetch(url);
+ * for await (const slice of chunkStream(response.body, { size: 4096 })) {
+ *     console.log(slice); // Uint8Array
+ * }
+ *
+ * @example
+ * const controller = new AbortController();
+ * setTimeout(() => controller.abort(), 10_000);
+ * for await (const slice of chunkStream(body, { signal: controller.signal })) {
+ *     await index(slice);
+ * }
+ */
+export async function* chunkStream(stream, options = {}) {
+    const { signal, ...chunkerOptions } = options;
+    signal?.throwIfAborted();
+    const reader = stream.getReader();
+    const onAbort = () => reader.cancel(signal.reason).catch(() => {});
+    signal?.addEventListener('abort', onAbort, { once: true });
+    let chunker = null;
+    let isString = false;
+    try {
+        for (;;) {
+            const { done, value } = await reader.read();
+            signal?.throwIfAborted();
+            if (done) {
+                break;
+            }
+            if (chunker === null) {
+                isString = typeof value === 'string';
+                chunker = new WasmStreamChunker({ utf8Safe: isString, ...chunkerOptions });
+            }
+            for (const slice of chunker.push(toBytes(value))) {
+                signal?.throwIfAborted();
+                yield isString ? decoder.decode(slice) : slice;
+            }
+        }
+        if (chunker !== null) {
+            for (const slice of chunker.finish()) {
+                signal?.throwIfAborted();
+                yield isString ? decoder.decode(slice) : slice;
+            }
+        }
+    } finally {
+        signal?.removeEventListener('abort', onAbort);
+        chunker?.free();
+        reader.releaseLock();
+    }
+}
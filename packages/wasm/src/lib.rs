@@ -1,10 +1,177 @@
+#[cfg(feature = "sentence")]
+use chunk::SentenceChunker as RustSentenceChunker;
 use chunk::{
-    DEFAULT_DELIMITERS, DEFAULT_TARGET_SIZE, IncludeDelim, OwnedChunker,
-    find_merge_indices as rust_find_merge_indices, split_at_delimiters,
+    ChunkerConfig, DEFAULT_DELIMITERS, DEFAULT_TARGET_SIZE, Delimiters, IncludeDelim, OwnedChunker,
+    RecursiveChunker as RustRecursiveChunker, SizeUnit as RustSizeUnit,
+    StreamChunker as RustStreamChunker, estimate_tokens as rust_estimate_tokens,
+    find_merge_indices as rust_find_merge_indices, merge_chunks as rust_merge_chunks,
+    split_at_delimiters,
 };
-use js_sys::Array;
+use js_sys::{Array, Object, Reflect, Uint8Array, Uint32Array};
 use wasm_bindgen::prelude::*;
 
+/// Convert a JS string or Uint8Array into bytes.
+fn bytes_value(value: &JsValue) -> Option<Vec<u8>> {
+    match value.as_string() {
+        Some(s) => Some(s.into_bytes()),
+        None => value.dyn_ref::<Uint8Array>().map(|a| a.to_vec()),
+    }
+}
+
+/// Convert a JS array of strings or Uint8Arrays into byte patterns.
+fn extract_patterns(patterns: &Array) -> Vec<Vec<u8>> {
+    patterns
+        .iter()
+        .filter_map(|val| bytes_value(&val))
+        .collect()
+}
+
+/// Read a JS options object (the wrapper's `ChunkOptions`) into a config.
+///
+/// Missing or `undefined` options keep their defaults; `utf8Safe` defaults to
+/// `str_input`.
+fn config_from_options(options: &JsValue, str_input: bool) -> Result<ChunkerConfig, JsError> {
+    let mut config = ChunkerConfig::new().utf8_safe(str_input);
+    if options.is_undefined() || options.is_null() {
+        return Ok(config);
+    }
+    let get = |key: &str| -> Result<Option<JsValue>, JsError> {
+        let value = Reflect::get(options, &JsValue::from_str(key))
+            .map_err(|_| JsError::new("options must be an object"))?;
+        Ok((!value.is_undefined() && !value.is_null()).then_some(value))
+    };
+    let number = |key: &str| -> Result<Option<usize>, JsError> {
+        get(key)?
+            .map(|v| v.as_f64().map(|n| n as usize))
+            .map(|n| n.ok_or_else(|| JsError::new(&[key, " must be a number"].concat())))
+            .transpose()
+    };
+    let flag = |key: &str| -> Result<bool, JsError> {
+        get(key)?
+            .map(|v| v.as_bool())
+            .map(|b| b.ok_or_else(|| JsError::new(&[key, " must be a boolean"].concat())))
+            .transpose()
+            .map(|b| b.unwrap_or(false))
+    };
+
+    if let Some(size) = number("size")? {
+        config = config.size(size);
+    }
+    if let Some(delimiters) = get("delimiters")? {
+        let delimiters = delimiters
+            .as_string()
+            .ok_or_else(|| JsError::new("delimiters must be a string"))?;
+        // A class name such as "sentences" picks that class
+        config = match delimiters.parse::<Delimiters>() {
+            Ok(class) => config.delimiter_class(class),
+            Err(_) => config.delimiters(delimiters.into_bytes()),
+        };
+    }
+    if let Some(pattern) = get("pattern")? {
+        let pattern = bytes_value(&pattern)
+            .ok_or_else(|| JsError::new("pattern must be a string or Uint8Array"))?;
+        if !pattern.is_empty() {
+            config = config.pattern(pattern);
+        }
+    }
+    if let Some(patterns) = get("patterns")? {
+        let patterns = patterns
+            .dyn_ref::<Array>()
+            .ok_or_else(|| JsError::new("patterns must be an array"))?;
+        config = config.patterns(&extract_patterns(patterns));
+    }
+    if let Some(utf8_safe) = get("utf8Safe")? {
+        let utf8_safe = utf8_safe
+            .as_bool()
+            .ok_or_else(|| JsError::new("utf8Safe must be a boolean"))?;
+        config = config.utf8_safe(utf8_safe);
+    }
+    if let Some(unit) = get("unit")? {
+        let unit = match (unit.as_string().as_deref(), unit.as_f64()) {
+            (Some("bytes"), _) => RustSizeUnit::Bytes,
+            (Some("chars"), _) => RustSizeUnit::Chars,
+            (_, Some(n)) if n == SizeUnit::Bytes as u32 as f64 => RustSizeUnit::Bytes,
+            (_, Some(n)) if n == SizeUnit::Chars as u32 as f64 => RustSizeUnit::Chars,
+            _ => return Err(JsError::new("unit must be 'bytes', 'chars' or a SizeUnit")),
+        };
+        config = config.unit(unit);
+    }
+    // A delimiter class may have turned prefix mode on already
+    let prefix = config.prefix || flag("prefix")?;
+    config = config
+        .prefix(prefix)
+        .consecutive(flag("consecutive")?)
+        .forward_fallback(flag("forwardFallback")?)
+        .delimiter_priority(flag("delimiterPriority")?)
+        .slack(number("slack")?.unwrap_or(0))
+        .balanced(flag("balanced")?)
+        .lines_per_chunk(number("linesPerChunk")?)
+        .overlap(number("overlap")?.unwrap_or(0))
+        .min_size(number("minSize")?.unwrap_or(0))
+        .max_size(number("maxSize")?)
+        .trim(flag("trim")?)
+        .skip_empty(flag("skipEmpty")?);
+    if let Some(ratio) = get("overlapRatio")? {
+        let ratio = ratio
+            .as_f64()
+            .ok_or_else(|| JsError::new("overlapRatio must be a number"))?;
+        config = config.overlap_ratio(ratio);
+    }
+    config
+        .validate()
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(config)
+}
+
+/// Running byte-to-UTF-16 position in UTF-8 text.
+///
+/// Chunk starts and ends each only move forward, so a cursor per side counts
+/// every byte once.
+#[derive(Default)]
+struct Utf16Cursor {
+    byte: usize,
+    unit: usize,
+}
+
+impl Utf16Cursor {
+    /// UTF-16 offset of byte offset `to`.
+    fn seek(&mut self, text: &[u8], to: usize) -> usize {
+        if to < self.byte {
+            *self = Self::default();
+        }
+        // Continuation bytes add nothing; 4-byte sequences are surrogate pairs
+        self.unit += text[self.byte..to]
+            .iter()
+            .map(|&b| match b {
+                0x80..=0xBF => 0,
+                0xF0.. => 2,
+                _ => 1,
+            })
+            .sum::<usize>();
+        self.byte = to;
+        self.unit
+    }
+}
+
+/// Unit the target size is measured in.
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub enum SizeUnit {
+    /// Count bytes (default).
+    Bytes = 0,
+    /// Count Unicode characters.
+    Chars = 1,
+}
+
+impl From<SizeUnit> for RustSizeUnit {
+    fn from(unit: SizeUnit) -> Self {
+        match unit {
+            SizeUnit::Bytes => RustSizeUnit::Bytes,
+            SizeUnit::Chars => RustSizeUnit::Chars,
+        }
+    }
+}
+
 /// Chunker splits text at delimiter boundaries.
 ///
 /// @example Single-byte delimiters
@@ -16,6 +183,12 @@ use wasm_bindgen::prelude::*;
 /// }
 /// ```
 ///
+/// @example JS string input, sliced with UTF-16 offsets
+/// ```javascript
+/// const chunker = Chunker.fromString("Café. Thé.", 7, ".");
+/// const offsets = chunker.collect_offsets_utf16(); // [0, 5, 5, 10]
+/// ```
+///
 /// @example Multi-byte pattern (e.g., metaspace for SentencePiece)
 /// ```javascript
 /// const encoder = new TextEncoder();
@@ -37,7 +210,14 @@ impl Chunker {
     /// @param prefix - Put delimiter at start of next chunk (default: false)
     /// @param consecutive - Split at START of consecutive runs (default: false)
     /// @param forward_fallback - Search forward if no delimiter in backward window (default: false)
+    /// @param patterns - Multi-byte delimiters (strings or Uint8Arrays), composable with delimiters; longest match wins
+    /// @param overlap - Bytes of trailing context carried into the next chunk (default: 0)
+    /// @param utf8_safe - Never split inside a multi-byte UTF-8 sequence (default: false)
+    /// @param unit - Unit of size: SizeUnit.Bytes (default) or SizeUnit.Chars
+    /// @param min_size - Merge a trailing chunk smaller than this into the previous one (default: 0)
+    /// @param max_size - Strict cap on chunk length in bytes (default: none)
     #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         text: &[u8],
         size: Option<usize>,
@@ -46,30 +226,80 @@ impl Chunker {
         consecutive: Option<bool>,
         forward_fallback: Option<bool>,
         patterns: Option<Array>,
+        overlap: Option<usize>,
+        utf8_safe: Option<bool>,
+        unit: Option<SizeUnit>,
+        min_size: Option<usize>,
+        max_size: Option<usize>,
     ) -> Chunker {
-        let target_size = size.unwrap_or(DEFAULT_TARGET_SIZE);
-        let delims = delimiters
-            .map(|s| s.into_bytes())
-            .unwrap_or_else(|| DEFAULT_DELIMITERS.to_vec());
-        let mut inner = OwnedChunker::new(text.to_vec())
-            .size(target_size)
-            .delimiters(delims);
-        if let Some(pats) = patterns {
-            let pattern_strings: Vec<String> =
-                pats.iter().filter_map(|val| val.as_string()).collect();
-            let pattern_refs: Vec<&str> = pattern_strings.iter().map(|s| s.as_str()).collect();
-            inner = inner.patterns(&pattern_refs);
-        }
-        if prefix.unwrap_or(false) {
-            inner = inner.prefix();
-        }
-        if consecutive.unwrap_or(false) {
-            inner = inner.consecutive();
-        }
-        if forward_fallback.unwrap_or(false) {
-            inner = inner.forward_fallback();
-        }
-        Chunker { inner }
+        Self::with_delimiters(
+            OwnedChunker::new(text.to_vec()),
+            size,
+            delimiters,
+            prefix,
+            consecutive,
+            forward_fallback,
+            patterns,
+            overlap,
+            utf8_safe.unwrap_or(false),
+            unit,
+            min_size,
+            max_size,
+        )
+    }
+
+    /// Create a new Chunker from a JS string, encoded to UTF-8 on the way in.
+    ///
+    /// Takes the same options as the constructor, except that `utf8_safe`
+    /// defaults to true. Use [`collect_offsets_utf16`](Self::collect_offsets_utf16)
+    /// for offsets that index the original string.
+    #[wasm_bindgen(js_name = fromString)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_string(
+        text: String,
+        size: Option<usize>,
+        delimiters: Option<String>,
+        prefix: Option<bool>,
+        consecutive: Option<bool>,
+        forward_fallback: Option<bool>,
+        patterns: Option<Array>,
+        overlap: Option<usize>,
+        utf8_safe: Option<bool>,
+        unit: Option<SizeUnit>,
+        min_size: Option<usize>,
+        max_size: Option<usize>,
+    ) -> Chunker {
+        Self::with_delimiters(
+            OwnedChunker::from_source(text.into_bytes()),
+            size,
+            delimiters,
+            prefix,
+            consecutive,
+            forward_fallback,
+            patterns,
+            overlap,
+            utf8_safe.unwrap_or(true),
+            unit,
+            min_size,
+            max_size,
+        )
+    }
+
+    /// Create a new Chunker from a string or Uint8Array and an options object.
+    ///
+    /// Takes the same options as the JS wrapper (`size`, `delimiters`,
+    /// `pattern`, `patterns`, `prefix`, `consecutive`, `forwardFallback`,
+    /// `delimiterPriority`, `slack`, `balanced`, `linesPerChunk`, `overlap`, `utf8Safe`, `unit`, `minSize`,
+    /// `maxSize`, `trim`, `skipEmpty`), read into one `ChunkerConfig` shared with the other
+    /// bindings. `utf8Safe` defaults to true for strings.
+    #[wasm_bindgen(js_name = withOptions)]
+    pub fn with_options(text: JsValue, options: JsValue) -> Result<Chunker, JsError> {
+        let bytes = bytes_value(&text)
+            .ok_or_else(|| JsError::new("text must be a string or Uint8Array"))?;
+        let config = config_from_options(&options, text.is_string())?;
+        Ok(Chunker {
+            inner: config.owned_chunker(bytes),
+        })
     }
 
     /// Create a new Chunker with a multi-byte pattern.
@@ -80,7 +310,13 @@ impl Chunker {
     /// @param prefix - Put pattern at start of next chunk (default: false)
     /// @param consecutive - Split at START of consecutive runs (default: false)
     /// @param forward_fallback - Search forward if no pattern in backward window (default: false)
+    /// @param overlap - Bytes of trailing context carried into the next chunk (default: 0)
+    /// @param utf8_safe - Never split inside a multi-byte UTF-8 sequence (default: false)
+    /// @param unit - Unit of size: SizeUnit.Bytes (default) or SizeUnit.Chars
+    /// @param min_size - Merge a trailing chunk smaller than this into the previous one (default: 0)
+    /// @param max_size - Strict cap on chunk length in bytes (default: none)
     #[wasm_bindgen]
+    #[allow(clippy::too_many_arguments)]
     pub fn with_pattern(
         text: &[u8],
         size: usize,
@@ -88,6 +324,11 @@ impl Chunker {
         prefix: Option<bool>,
         consecutive: Option<bool>,
         forward_fallback: Option<bool>,
+        overlap: Option<usize>,
+        utf8_safe: Option<bool>,
+        unit: Option<SizeUnit>,
+        min_size: Option<usize>,
+        max_size: Option<usize>,
     ) -> Chunker {
         let mut inner = OwnedChunker::new(text.to_vec())
             .size(size)
@@ -101,6 +342,19 @@ impl Chunker {
         if forward_fallback.unwrap_or(false) {
             inner = inner.forward_fallback();
         }
+        if let Some(overlap) = overlap {
+            inner = inner.overlap(overlap);
+        }
+        inner = inner.utf8_safe(utf8_safe.unwrap_or(false));
+        if let Some(unit) = unit {
+            inner = inner.size_unit(unit.into());
+        }
+        if let Some(min_size) = min_size {
+            inner = inner.min_size(min_size);
+        }
+        if let Some(max_size) = max_size {
+            inner = inner.max_size(max_size);
+        }
         Chunker { inner }
     }
 
@@ -111,12 +365,46 @@ impl Chunker {
         self.inner.next_chunk()
     }
 
+    /// Get the next chunk decoded as a string, or undefined if exhausted.
+    ///
+    /// Invalid UTF-8 (e.g. a hard split inside a character without
+    /// `utf8_safe`) is replaced with U+FFFD, like `TextDecoder`.
+    #[wasm_bindgen]
+    pub fn next_text(&mut self) -> Option<String> {
+        self.inner
+            .next_chunk_info()
+            .map(|chunk| String::from_utf8_lossy(chunk.bytes()).into_owned())
+    }
+
     /// Reset the chunker to iterate from the beginning.
     #[wasm_bindgen]
     pub fn reset(&mut self) {
         self.inner.reset();
     }
 
+    /// Get chunk `index` without iterating to it, or undefined if out of
+    /// range. Boundaries are cached as far as lookups reach, so paging back
+    /// and forth is cheap; the iterator position doesn't move.
+    #[wasm_bindgen]
+    pub fn chunk_at(&mut self, index: usize) -> Option<Vec<u8>> {
+        self.inner
+            .chunk_at(index)
+            .map(|chunk| chunk.bytes().to_vec())
+    }
+
+    /// Count all chunks without creating them, e.g. for a page count.
+    #[wasm_bindgen]
+    pub fn count_chunks(&mut self) -> usize {
+        self.inner.count_chunks()
+    }
+
+    /// Move to the chunk containing byte `offset` and return its index, or
+    /// undefined if `offset` is past the end. `next()` then returns that chunk.
+    #[wasm_bindgen]
+    pub fn seek(&mut self, offset: usize) -> Option<usize> {
+        self.inner.seek(offset)
+    }
+
     /// Collect all chunk offsets as a flat array [start1, end1, start2, end2, ...].
     /// This is faster than iterating as it makes a single WASM call.
     #[wasm_bindgen]
@@ -127,6 +415,369 @@ impl Chunker {
             .flat_map(|(start, end)| [start, end])
             .collect()
     }
+
+    /// Collect all chunk offsets in UTF-16 code units, as a flat array.
+    ///
+    /// These index the JS string the text was encoded from, so
+    /// `text.slice(start, end)` is each chunk. Needs chunk boundaries at
+    /// character boundaries, which `utf8_safe` guarantees.
+    #[wasm_bindgen]
+    pub fn collect_offsets_utf16(&mut self) -> Vec<usize> {
+        let offsets = self.inner.collect_offsets();
+        let text = self.inner.text();
+        let (mut starts, mut ends) = (Utf16Cursor::default(), Utf16Cursor::default());
+        offsets
+            .into_iter()
+            .flat_map(|(start, end)| [starts.seek(text, start), ends.seek(text, end)])
+            .collect()
+    }
+
+    /// Collect all chunks as an array of Uint8Arrays in a single WASM call.
+    #[wasm_bindgen]
+    pub fn collect_chunks(&mut self) -> Array {
+        self.inner
+            .iter_chunks()
+            .map(|chunk| Uint8Array::from(chunk.bytes()))
+            .collect()
+    }
+
+    /// Collect all chunks packed into one buffer, in a single WASM call.
+    ///
+    /// Returns `{ bytes, offsets }`: `bytes` is a Uint8Array holding every
+    /// chunk back to back and `offsets` a Uint32Array [start1, end1, ...]
+    /// into it. The chunk data leaves WASM memory in one copy, and both
+    /// arrays own their buffers, so they can be transferred to a Worker.
+    #[wasm_bindgen]
+    pub fn collect_packed(&mut self) -> Result<Object, JsError> {
+        let mut bytes = Vec::new();
+        let mut offsets = Vec::new();
+        for chunk in self.inner.iter_chunks() {
+            let start = bytes.len();
+            bytes.extend_from_slice(chunk.bytes());
+            offsets.extend([start, bytes.len()].map(|offset| offset as u32));
+        }
+        let packed = Object::new();
+        let set = |key: &str, value: JsValue| {
+            Reflect::set(&packed, &JsValue::from_str(key), &value)
+                .map_err(|_| JsError::new("failed to build packed chunks"))
+        };
+        set("bytes", Uint8Array::from(bytes.as_slice()).into())?;
+        set("offsets", Uint32Array::from(offsets.as_slice()).into())?;
+        Ok(packed)
+    }
+
+    /// Collect the chunks as children grouped into parents of at most
+    /// `parent_size` bytes, as `{ parents, children, parentOf }`: flat
+    /// `Uint32Array` offsets of the parents and children, and every child's
+    /// parent index.
+    #[wasm_bindgen]
+    pub fn collect_parent_child(&mut self, parent_size: usize) -> Result<Object, JsError> {
+        let chunks = self.inner.collect_parent_child(parent_size);
+        let flat = |offsets: &[(usize, usize)]| -> Vec<u32> {
+            offsets
+                .iter()
+                .flat_map(|&(start, end)| [start as u32, end as u32])
+                .collect()
+        };
+        let parent_of: Vec<u32> = chunks.parent_of.iter().map(|&p| p as u32).collect();
+        let result = Object::new();
+        let set = |key: &str, value: JsValue| {
+            Reflect::set(&result, &JsValue::from_str(key), &value)
+                .map_err(|_| JsError::new("failed to build parent and child chunks"))
+        };
+        set(
+            "parents",
+            Uint32Array::from(flat(&chunks.parents).as_slice()).into(),
+        )?;
+        set(
+            "children",
+            Uint32Array::from(flat(&chunks.children).as_slice()).into(),
+        )?;
+        set("parentOf", Uint32Array::from(parent_of.as_slice()).into())?;
+        Ok(result)
+    }
+
+    /// Collect all chunks as strings in a single WASM call.
+    ///
+    /// Decodes like [`next_text`](Self::next_text).
+    #[wasm_bindgen]
+    pub fn collect_texts(&mut self) -> Vec<String> {
+        self.inner
+            .iter_chunks()
+            .map(|chunk| String::from_utf8_lossy(chunk.bytes()).into_owned())
+            .collect()
+    }
+}
+
+impl Chunker {
+    /// Apply the constructor options to a chunker over `inner`'s text.
+    #[allow(clippy::too_many_arguments)]
+    fn with_delimiters(
+        inner: OwnedChunker,
+        size: Option<usize>,
+        delimiters: Option<String>,
+        prefix: Option<bool>,
+        consecutive: Option<bool>,
+        forward_fallback: Option<bool>,
+        patterns: Option<Array>,
+        overlap: Option<usize>,
+        utf8_safe: bool,
+        unit: Option<SizeUnit>,
+        min_size: Option<usize>,
+        max_size: Option<usize>,
+    ) -> Chunker {
+        let target_size = size.unwrap_or(DEFAULT_TARGET_SIZE);
+        let delims = delimiters
+            .map(|s| s.into_bytes())
+            .unwrap_or_else(|| DEFAULT_DELIMITERS.to_vec());
+        let mut inner = inner.size(target_size).delimiters(delims);
+        if let Some(pats) = patterns {
+            inner = inner.patterns(&extract_patterns(&pats));
+        }
+        if prefix.unwrap_or(false) {
+            inner = inner.prefix();
+        }
+        if consecutive.unwrap_or(false) {
+            inner = inner.consecutive();
+        }
+        if forward_fallback.unwrap_or(false) {
+            inner = inner.forward_fallback();
+        }
+        if let Some(overlap) = overlap {
+            inner = inner.overlap(overlap);
+        }
+        inner = inner.utf8_safe(utf8_safe);
+        if let Some(unit) = unit {
+            inner = inner.size_unit(unit.into());
+        }
+        if let Some(min_size) = min_size {
+            inner = inner.min_size(min_size);
+        }
+        if let Some(max_size) = max_size {
+            inner = inner.max_size(max_size);
+        }
+        Chunker { inner }
+    }
+}
+
+/// RecursiveChunker splits at coarse delimiter levels first and descends to
+/// finer levels only for pieces that still exceed the target size.
+///
+/// @example
+/// ```javascript
+/// const chunker = new RecursiveChunker([["\n\n"], ["\n"], [". ", "? "]], 1024);
+/// const offsets = chunker.chunk_offsets(textBytes);
+/// ```
+#[wasm_bindgen]
+pub struct RecursiveChunker {
+    inner: RustRecursiveChunker,
+}
+
+#[wasm_bindgen]
+impl RecursiveChunker {
+    /// Create a new RecursiveChunker.
+    ///
+    /// @param levels - Array of delimiter levels, each an array of strings or Uint8Arrays
+    /// @param size - Target chunk size in bytes (default: 4096)
+    /// @param prefix - Put delimiter at start of next chunk (default: false)
+    /// @param utf8_safe - Never hard split inside a multi-byte UTF-8 sequence (default: false)
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        levels: &Array,
+        size: Option<usize>,
+        prefix: Option<bool>,
+        utf8_safe: Option<bool>,
+    ) -> RecursiveChunker {
+        let mut inner = RustRecursiveChunker::new()
+            .size(size.unwrap_or(DEFAULT_TARGET_SIZE))
+            .utf8_safe(utf8_safe.unwrap_or(false));
+        for level in levels.iter() {
+            if let Some(level) = level.dyn_ref::<Array>() {
+                inner = inner.level(&extract_patterns(level));
+            }
+        }
+        if prefix.unwrap_or(false) {
+            inner = inner.prefix();
+        }
+        RecursiveChunker { inner }
+    }
+
+    /// Create a paragraph-priority chunker: blank lines first, then sentence
+    /// ends, then hard splits.
+    ///
+    /// @param size - Target chunk size in bytes (default: 4096)
+    /// @param utf8_safe - Never hard split inside a multi-byte UTF-8 sequence (default: false)
+    #[wasm_bindgen]
+    pub fn paragraphs(size: Option<usize>, utf8_safe: Option<bool>) -> RecursiveChunker {
+        let inner = RustRecursiveChunker::paragraphs()
+            .size(size.unwrap_or(DEFAULT_TARGET_SIZE))
+            .utf8_safe(utf8_safe.unwrap_or(false));
+        RecursiveChunker { inner }
+    }
+
+    /// Chunk text, returning a flat array [start1, end1, start2, end2, ...].
+    #[wasm_bindgen]
+    pub fn chunk_offsets(&self, text: &[u8]) -> Vec<usize> {
+        self.inner
+            .chunk_offsets(text)
+            .into_iter()
+            .flat_map(|(start, end)| [start, end])
+            .collect()
+    }
+}
+
+/// SentenceChunker packs whole sentences (UAX #29 plus an abbreviation list)
+/// into chunks of a target size.
+///
+/// @example
+/// ```javascript
+/// const chunker = new SentenceChunker(1024);
+/// const offsets = chunker.chunk_offsets(textBytes);
+/// ```
+#[wasm_bindgen]
+pub struct SentenceChunker {
+    #[cfg(feature = "sentence")]
+    inner: RustSentenceChunker,
+}
+
+#[cfg(not(feature = "sentence"))]
+#[wasm_bindgen]
+impl SentenceChunker {
+    /// Always fails: this build was made without the `sentence` feature.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        _size: Option<usize>,
+        _abbreviations: Option<Vec<String>>,
+    ) -> Result<SentenceChunker, JsError> {
+        Err(JsError::new(
+            "SentenceChunker is not available in this build (no sentence feature)",
+        ))
+    }
+}
+
+#[cfg(feature = "sentence")]
+#[wasm_bindgen]
+impl SentenceChunker {
+    /// Create a new SentenceChunker.
+    ///
+    /// @param size - Target chunk size in bytes (default: 4096)
+    /// @param abbreviations - Words that don't end a sentence, e.g. ["Dr", "e.g"] (default: built-in list)
+    #[wasm_bindgen(constructor)]
+    pub fn new(size: Option<usize>, abbreviations: Option<Vec<String>>) -> SentenceChunker {
+        let mut inner = RustSentenceChunker::new().size(size.unwrap_or(DEFAULT_TARGET_SIZE));
+        if let Some(abbreviations) = abbreviations {
+            inner = inner.abbreviations(&abbreviations);
+        }
+        SentenceChunker { inner }
+    }
+
+    /// Chunk UTF-8 text, returning a flat array [start1, end1, start2, end2, ...].
+    #[wasm_bindgen]
+    pub fn chunk_offsets(&self, text: &[u8]) -> Result<Vec<usize>, JsError> {
+        let text =
+            std::str::from_utf8(text).map_err(|_| JsError::new("text must be valid UTF-8"))?;
+        Ok(self
+            .inner
+            .chunk_offsets(text)
+            .into_iter()
+            .flat_map(|(start, end)| [start, end])
+            .collect())
+    }
+
+    /// Find sentence boundaries, returning a flat array [start1, end1, ...].
+    #[wasm_bindgen]
+    pub fn sentence_offsets(&self, text: &[u8]) -> Result<Vec<usize>, JsError> {
+        let text =
+            std::str::from_utf8(text).map_err(|_| JsError::new("text must be valid UTF-8"))?;
+        Ok(self
+            .inner
+            .sentence_offsets(text)
+            .into_iter()
+            .flat_map(|(start, end)| [start, end])
+            .collect())
+    }
+}
+
+/// Chunker for data that arrives in pieces, e.g. a `ReadableStream`.
+///
+/// `push` returns the chunks whose boundaries are known so far and `finish`
+/// returns the rest. The chunks are the same as `Chunker` gives for the
+/// whole input, however it is split across `push` calls.
+///
+/// @example
+/// ```javascript
+/// const stream = new StreamChunker({ size: 1024 });
+/// const chunks = [...stream.push(bytes1), ...stream.push(bytes2), ...stream.finish()];
+/// ```
+#[wasm_bindgen]
+pub struct StreamChunker {
+    inner: RustStreamChunker,
+}
+
+#[wasm_bindgen]
+impl StreamChunker {
+    /// Create a new StreamChunker.
+    ///
+    /// @param options - `size`, `delimiters`, `pattern`, `patterns`, `prefix`,
+    /// `consecutive`, `forwardFallback`, `overlap`, `utf8Safe`, `minSize` and
+    /// `maxSize`, as for `Chunker`; other options are ignored
+    #[wasm_bindgen(constructor)]
+    pub fn new(options: JsValue) -> Result<StreamChunker, JsError> {
+        let config = config_from_options(&options, false)?;
+        let mut inner = RustStreamChunker::new()
+            .size(config.size)
+            .delimiters(config.delimiters)
+            .overlap(config.overlap)
+            .utf8_safe(config.utf8_safe)
+            .min_size(config.min_size);
+        if let Some(pattern) = config.pattern {
+            inner = inner.pattern(pattern);
+        }
+        if !config.patterns.is_empty() {
+            inner = inner.patterns(&config.patterns);
+        }
+        if config.prefix {
+            inner = inner.prefix();
+        }
+        if config.consecutive {
+            inner = inner.consecutive();
+        }
+        if config.forward_fallback {
+            inner = inner.forward_fallback();
+        }
+        if let Some(max_size) = config.max_size {
+            inner = inner.max_size(max_size);
+        }
+        Ok(StreamChunker { inner })
+    }
+
+    /// Bytes buffered but not yet emitted.
+    #[wasm_bindgen(getter)]
+    pub fn pending(&self) -> usize {
+        self.inner.pending()
+    }
+
+    /// Add data and return the chunks that are now complete, as Uint8Arrays.
+    #[wasm_bindgen]
+    pub fn push(&mut self, data: &[u8]) -> Array {
+        to_byte_arrays(self.inner.push(data))
+    }
+
+    /// Emit whatever is left at the end of the input, as Uint8Arrays.
+    ///
+    /// The chunker can be reused afterwards.
+    #[wasm_bindgen]
+    pub fn finish(&mut self) -> Array {
+        to_byte_arrays(self.inner.finish())
+    }
+}
+
+/// Convert chunks into a JS array of Uint8Arrays.
+fn to_byte_arrays(chunks: Vec<Vec<u8>>) -> Array {
+    chunks
+        .iter()
+        .map(|chunk| JsValue::from(Uint8Array::from(chunk.as_slice())))
+        .collect()
 }
 
 /// Get the default target size (4096 bytes).
@@ -141,6 +792,20 @@ pub fn default_delimiters() -> Vec<u8> {
     DEFAULT_DELIMITERS.to_vec()
 }
 
+/// Chunk offsets for `text` with an options object, as a flat array.
+///
+/// Takes the same options as [`Chunker::with_options`]; `utf8Safe` defaults
+/// to false, since the text is always bytes here.
+#[wasm_bindgen]
+pub fn chunk_offsets_with_options(text: &[u8], options: JsValue) -> Result<Vec<usize>, JsError> {
+    Ok(config_from_options(&options, false)?
+        .chunker(text)
+        .collect_offsets()
+        .into_iter()
+        .flat_map(|(start, end)| [start, end])
+        .collect())
+}
+
 /// Fast chunking function that returns offsets in a single call.
 /// Returns a flat array [start1, end1, start2, end2, ...].
 /// Use this with subarray for maximum performance.
@@ -154,6 +819,7 @@ pub fn default_delimiters() -> Vec<u8> {
 /// }
 /// ```
 #[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
 pub fn chunk_offsets(
     text: &[u8],
     size: Option<usize>,
@@ -162,6 +828,11 @@ pub fn chunk_offsets(
     consecutive: Option<bool>,
     forward_fallback: Option<bool>,
     patterns: Option<Array>,
+    overlap: Option<usize>,
+    utf8_safe: Option<bool>,
+    unit: Option<SizeUnit>,
+    min_size: Option<usize>,
+    max_size: Option<usize>,
 ) -> Vec<usize> {
     let target_size = size.unwrap_or(DEFAULT_TARGET_SIZE);
     let delims = delimiters
@@ -171,9 +842,7 @@ pub fn chunk_offsets(
         .size(target_size)
         .delimiters(delims);
     if let Some(pats) = patterns {
-        let pattern_strings: Vec<String> = pats.iter().filter_map(|val| val.as_string()).collect();
-        let pattern_refs: Vec<&str> = pattern_strings.iter().map(|s| s.as_str()).collect();
-        chunker = chunker.patterns(&pattern_refs);
+        chunker = chunker.patterns(&extract_patterns(&pats));
     }
     if prefix.unwrap_or(false) {
         chunker = chunker.prefix();
@@ -184,6 +853,19 @@ pub fn chunk_offsets(
     if forward_fallback.unwrap_or(false) {
         chunker = chunker.forward_fallback();
     }
+    if let Some(overlap) = overlap {
+        chunker = chunker.overlap(overlap);
+    }
+    chunker = chunker.utf8_safe(utf8_safe.unwrap_or(false));
+    if let Some(unit) = unit {
+        chunker = chunker.size_unit(unit.into());
+    }
+    if let Some(min_size) = min_size {
+        chunker = chunker.min_size(min_size);
+    }
+    if let Some(max_size) = max_size {
+        chunker = chunker.max_size(max_size);
+    }
     chunker
         .collect_offsets()
         .into_iter()
@@ -201,6 +883,7 @@ pub fn chunk_offsets(
 /// const offsets = chunk_offsets_pattern(textBytes, 4096, metaspace, true, true, true);
 /// ```
 #[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
 pub fn chunk_offsets_pattern(
     text: &[u8],
     size: usize,
@@ -208,6 +891,11 @@ pub fn chunk_offsets_pattern(
     prefix: Option<bool>,
     consecutive: Option<bool>,
     forward_fallback: Option<bool>,
+    overlap: Option<usize>,
+    utf8_safe: Option<bool>,
+    unit: Option<SizeUnit>,
+    min_size: Option<usize>,
+    max_size: Option<usize>,
 ) -> Vec<usize> {
     let mut chunker = OwnedChunker::new(text.to_vec())
         .size(size)
@@ -221,6 +909,19 @@ pub fn chunk_offsets_pattern(
     if forward_fallback.unwrap_or(false) {
         chunker = chunker.forward_fallback();
     }
+    if let Some(overlap) = overlap {
+        chunker = chunker.overlap(overlap);
+    }
+    chunker = chunker.utf8_safe(utf8_safe.unwrap_or(false));
+    if let Some(unit) = unit {
+        chunker = chunker.size_unit(unit.into());
+    }
+    if let Some(min_size) = min_size {
+        chunker = chunker.min_size(min_size);
+    }
+    if let Some(max_size) = max_size {
+        chunker = chunker.max_size(max_size);
+    }
     chunker
         .collect_offsets()
         .into_iter()
@@ -295,6 +996,29 @@ pub fn find_merge_indices(token_counts: &[usize], chunk_size: usize) -> Vec<usiz
     rust_find_merge_indices(token_counts, chunk_size)
 }
 
+/// Coalesce runs of adjacent chunks into chunks of at most `max_size` bytes.
+///
+/// @param offsets - Flat array [start1, end1, start2, end2, ...]
+/// @param max_size - Maximum bytes per merged chunk
+/// @returns Flat array of the merged chunks' offsets
+#[wasm_bindgen]
+pub fn merge_chunks(offsets: &[usize], max_size: usize) -> Vec<usize> {
+    let pairs: Vec<(usize, usize)> = offsets.chunks_exact(2).map(|p| (p[0], p[1])).collect();
+    rust_merge_chunks(&pairs, max_size)
+        .into_iter()
+        .flat_map(|(start, end)| [start, end])
+        .collect()
+}
+
+/// A fast estimate of the tokens in UTF-8 text, without a tokenizer: the
+/// larger of one token per 4 bytes and 1.3 tokens per word, rounded up.
+///
+/// @param text - Text as bytes
+#[wasm_bindgen]
+pub fn estimate_tokens(text: &[u8]) -> usize {
+    rust_estimate_tokens(text)
+}
+
 /// Merge segments based on token counts, respecting chunk size limits.
 /// Returns a flat array [endIndex1, tokenCount1, endIndex2, tokenCount2, ...].
 ///
@@ -123,3 +123,27 @@ test('total bytes preserved', () => {
     assert.strictEqual(total, text.length);
     chunker.free();
 });
+
+test('fromString takes JS strings', () => {
+    const text = "Café. Thé. 😀 fin.";
+    const chunker = Chunker.fromString(text, 10, ".");
+    const texts = [];
+    let chunk;
+    while ((chunk = chunker.next_text()) !== undefined) {
+        texts.push(chunk);
+    }
+    assert.deepStrictEqual(texts, ["Café.", " Thé.", " 😀 fin."]);
+
+    // UTF-16 offsets slice the original string
+    const offsets = Array.from(chunker.collect_offsets_utf16());
+    assert.deepStrictEqual(offsets, [0, 5, 5, 10, 10, 18]);
+    assert.strictEqual(text.slice(offsets[4], offsets[5]), " 😀 fin.");
+    chunker.free();
+});
+
+test('fromString defaults to utf8 safe', () => {
+    const chunker = Chunker.fromString("ééé", 3);
+    assert.deepStrictEqual(Array.from(chunker.collect_offsets()), [0, 2, 2, 4, 4, 6]);
+    assert.deepStrictEqual(Array.from(chunker.collect_offsets_utf16()), [0, 1, 1, 2, 2, 3]);
+    chunker.free();
+});
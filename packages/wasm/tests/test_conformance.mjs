@@ -0,0 +1,38 @@
+// Shared golden files, also run by the Rust crate and the Python package.
+import { test } from 'node:test';
+import assert from 'node:assert';
+import { readFile, readdir } from 'node:fs/promises';
+import { fileURLToPath } from 'node:url';
+import { dirname, join } from 'node:path';
+
+const __dirname = dirname(fileURLToPath(import.meta.url));
+
+const wasmPath = join(__dirname, '..', 'pkg', 'chonkiejs_chunk_bg.wasm');
+import { initSync } from '../pkg/chonkiejs_chunk.js';
+initSync({ module: await readFile(wasmPath) });
+
+import { chunk_offsets } from '../index.js';
+
+const fixtures = join(__dirname, '..', '..', '..', 'tests', 'conformance');
+const encoder = new TextEncoder();
+
+// Fixture options are snake_case, the JS options camelCase
+const camelCase = (key) => key.replace(/_(\w)/g, (_, c) => c.toUpperCase());
+
+test('conformance: golden files', async () => {
+    const files = (await readdir(fixtures)).filter(f => f.endsWith('.json')).sort();
+    assert.ok(files.length > 0);
+    for (const file of files) {
+        const { cases } = JSON.parse(await readFile(join(fixtures, file), 'utf8'));
+        for (const { name, text, options, offsets } of cases) {
+            const jsOptions = Object.fromEntries(
+                Object.entries(options).map(([key, value]) => [camelCase(key), value])
+            );
+            assert.deepStrictEqual(
+                chunk_offsets(encoder.encode(text), jsOptions),
+                offsets,
+                `${file}: ${name}`
+            );
+        }
+    }
+});
@@ -15,7 +15,7 @@ import { initSync } from '../pkg/chonkiejs_chunk.js';
 initSync({ module: wasmBuffer });
 
 // Now import our wrapper
-import { chunk, chunk_offsets, split, split_offsets, Chunker, default_target_size, default_delimiters } from '../index.js';
+import { chunk, chunk_offsets, split, split_offsets, Chunker, RecursiveChunker, SentenceChunker, StreamChunker, chunkStream, unpackChunks, SizeUnit, Delimiters, merge_chunks, count_tokens, estimate_tokens, default_target_size, default_delimiters } from '../index.js';
 
 const encoder = new TextEncoder();
 const decoder = new TextDecoder();
@@ -89,6 +89,231 @@ test('wrapper: chunk_offsets function', () => {
     assert.strictEqual(chunks[2], " Test.");
 });
 
+test('wrapper: Chunker.collectChunks method', () => {
+    const chunker = new Chunker("Hello. World. Test.", { size: 10, delimiters: "." });
+    assert.deepStrictEqual(chunker.collectChunks(), ["Hello.", " World.", " Test."]);
+    chunker.free();
+
+    const bytes = new Chunker(encoder.encode("Hello. World."), { size: 8, delimiters: "." });
+    const chunks = bytes.collectChunks();
+    assert.ok(chunks[0] instanceof Uint8Array);
+    assert.strictEqual(decoder.decode(chunks[1]), " World.");
+    bytes.free();
+});
+
+test('wrapper: Chunker.chunkAt and seek jump without iterating', () => {
+    const text = "One. Two. Three. Four.";
+    const chunker = new Chunker(text, { size: 7, delimiters: "." });
+    const all = new Chunker(text, { size: 7, delimiters: "." }).collectChunks();
+    assert.strictEqual(chunker.chunkAt(2), all[2]);
+    assert.strictEqual(chunker.chunkAt(0), all[0]);
+    assert.strictEqual(chunker.chunkAt(all.length), undefined);
+    assert.strictEqual(chunker.countChunks(), all.length);
+    assert.strictEqual(chunker.seek(text.indexOf("Four")), 3);
+    assert.strictEqual(chunker.next(), all[3]);
+    assert.strictEqual(chunker.seek(text.length), undefined);
+    chunker.free();
+});
+
+test('wrapper: Chunker.nextText and collectTexts decode in WASM', () => {
+    const chunker = new Chunker(encoder.encode("Héllo. Wörld."), { size: 8, delimiters: "." });
+    assert.strictEqual(chunker.nextText(), "Héllo.");
+    assert.strictEqual(chunker.nextText(), " Wörld.");
+    assert.strictEqual(chunker.nextText(), undefined);
+    assert.deepStrictEqual(chunker.collectTexts(), ["Héllo.", " Wörld."]);
+    chunker.free();
+
+    // A hard split inside a character decodes to U+FFFD, like TextDecoder
+    const raw = new Chunker(encoder.encode("ééé"), { size: 3 });
+    assert.deepStrictEqual(raw.collectTexts(), ["é\uFFFD", "\uFFFDé"]);
+    raw.free();
+});
+
+test('wrapper: Chunker.collectUtf16Offsets slices the JS string', () => {
+    const text = "日本語。テキスト。😀😀。";
+    const chunker = new Chunker(text, { size: 16, delimiters: "。" });
+    const slices = chunker.collectUtf16Offsets().map(([start, end]) => text.slice(start, end));
+    assert.deepStrictEqual(slices, chunker.collectChunks());
+    chunker.free();
+});
+
+test('wrapper: options are read into one config in WASM', () => {
+    const text = "Hello. World. Test.";
+    const expected = chunk_offsets(text, { size: 10, delimiters: "." });
+    assert.deepStrictEqual(new Chunker(text, { size: 10, delimiters: "." }).collectOffsets(), expected);
+    assert.deepStrictEqual(chunk_offsets(text, { size: 10, delimiters: ".", unit: SizeUnit.Chars }), expected);
+    assert.deepStrictEqual(chunk_offsets(text, { size: 10, delimiters: ".", maxSize: undefined }), expected);
+
+    assert.throws(() => chunk_offsets(text, { size: "big" }), /size must be a number/);
+    assert.throws(() => new Chunker(text, { prefix: "yes" }), /prefix must be a boolean/);
+    assert.throws(() => new Chunker(text, { unit: "tokens" }), /unit must be/);
+});
+
+test('wrapper: trim strips whitespace around chunks', () => {
+    const text = "\n\n  First para.\n\n   Second para.  \n";
+    const chunks = [...chunk(text, { size: 18, delimiters: "\n", trim: true })];
+    assert.deepStrictEqual(chunks, ["First para.", "Second para."]);
+    const offsets = chunk_offsets(text, { size: 18, delimiters: "\n", trim: true });
+    assert.strictEqual(text.slice(...offsets[1]), "Second para.");
+});
+
+test('wrapper: skipEmpty leaves out blank chunks', () => {
+    const text = "One\n\n\n\n\nTwo\n  \n";
+    const options = { size: 4, delimiters: "\n", skipEmpty: true };
+    assert.deepStrictEqual([...chunk(text, options)], ["One\n", "Two\n"]);
+    assert.strictEqual(new Chunker(text, options).countChunks(), 2);
+});
+
+test('wrapper: delimiterPriority prefers earlier delimiters', () => {
+    const text = "One. Two\nThree. Four? Five.";
+    const options = { size: 20, delimiters: "\n.?" };
+    assert.deepStrictEqual([...chunk(text, options)], ["One. Two\nThree.", " Four? Five."]);
+    const prioritized = { ...options, delimiterPriority: true };
+    assert.deepStrictEqual([...chunk(text, prioritized)], ["One. Two\n", "Three. Four? Five."]);
+});
+
+test('wrapper: slack splits at the boundary nearest the size', () => {
+    const text = "aaaaaaa.bb.cccccc.dd";
+    assert.deepStrictEqual(chunk_offsets(text, { size: 10, delimiters: "." }), [[0, 8], [8, 18], [18, 20]]);
+    assert.deepStrictEqual(chunk_offsets(text, { size: 10, delimiters: ".", slack: 3 }), [[0, 11], [11, 20]]);
+});
+
+test('wrapper: balanced evens out chunk sizes', () => {
+    const text = "aaaa bbbb cccc dddd eeee ffff g";
+    assert.deepStrictEqual([...chunk(text, { size: 15, delimiters: " " })].at(-1), "g");
+    const chunks = [...chunk(text, { size: 15, delimiters: " ", balanced: true })];
+    assert.deepStrictEqual(chunks, ["aaaa bbbb ", "cccc dddd ", "eeee ffff g"]);
+});
+
+test('wrapper: linesPerChunk groups whole lines', () => {
+    const text = "one\ntwo\nthree\nfour\nfive";
+    const chunks = [...chunk(text, { linesPerChunk: 2 })];
+    assert.deepStrictEqual(chunks, ["one\ntwo\n", "three\nfour\n", "five"]);
+    assert.deepStrictEqual(chunk_offsets(text, { size: 10, linesPerChunk: 2 }), [[0, 8], [8, 14], [14, 23]]);
+});
+
+test('wrapper: StreamChunker matches Chunker across pushes', () => {
+    const text = "Hello. World. Test. More text here.";
+    const expected = [...chunk(text, { size: 10, delimiters: "." })];
+    const stream = new StreamChunker({ size: 10, delimiters: "." });
+    const chunks = [];
+    for (let i = 0; i < text.length; i += 4) {
+        chunks.push(...stream.push(text.slice(i, i + 4)));
+    }
+    assert.ok(stream.pending > 0);
+    chunks.push(...stream.finish());
+    assert.strictEqual(stream.pending, 0);
+    assert.ok(chunks.every(c => c instanceof Uint8Array));
+    assert.deepStrictEqual(chunks.map(c => new TextDecoder().decode(c)), expected);
+    stream.free();
+});
+
+test('wrapper: chunkStream reads a ReadableStream', async () => {
+    const text = "Hello. World. Test. More text here.";
+    const expected = [...chunk(text, { size: 10, delimiters: "." })];
+    const bytes = new TextEncoder().encode(text);
+    const streamOf = (parts) => new ReadableStream({
+        start(controller) {
+            parts.forEach(part => controller.enqueue(part));
+            controller.close();
+        },
+    });
+
+    const byteChunks = [];
+    const parts = [bytes.subarray(0, 5), bytes.subarray(5, 17), bytes.subarray(17)];
+    for await (const slice of chunkStream(streamOf(parts), { size: 10, delimiters: "." })) {
+        assert.ok(slice instanceof Uint8Array);
+        byteChunks.push(new TextDecoder().decode(slice));
+    }
+    assert.deepStrictEqual(byteChunks, expected);
+
+    // Streams of strings yield strings
+    const strChunks = [];
+    for await (const slice of chunkStream(streamOf(["Hello. Wor", "ld. Test. More text here."]), { size: 10, delimiters: "." })) {
+        strChunks.push(slice);
+    }
+    assert.deepStrictEqual(strChunks, expected);
+
+    const empty = [];
+    for await (const slice of chunkStream(streamOf([]))) {
+        empty.push(slice);
+    }
+    assert.deepStrictEqual(empty, []);
+});
+
+test('wrapper: chunkStream stops when its signal aborts', async () => {
+    const encoder = new TextEncoder();
+    // Never closes: only the abort can end it
+    const endless = () => new ReadableStream({
+        pull(controller) {
+            controller.enqueue(encoder.encode("Hello. World. "));
+        },
+    });
+
+    const controller = new AbortController();
+    const seen = [];
+    await assert.rejects(async () => {
+        for await (const slice of chunkStream(endless(), { size: 10, delimiters: ".", signal: controller.signal })) {
+            seen.push(slice);
+            if (seen.length === 3) {
+                controller.abort(new Error("stop"));
+            }
+        }
+    }, /stop/);
+    assert.strictEqual(seen.length, 3);
+
+    // A pending read is unblocked by the abort
+    const stalled = new ReadableStream({ pull() { return new Promise(() => {}); } });
+    const pending = new AbortController();
+    setTimeout(() => pending.abort(), 10);
+    await assert.rejects(async () => {
+        for await (const _ of chunkStream(stalled, { signal: pending.signal })) { /* nothing arrives */ }
+    }, { name: 'AbortError' });
+
+    await assert.rejects(async () => {
+        for await (const _ of chunkStream(endless(), { signal: AbortSignal.abort() })) { /* never runs */ }
+    }, { name: 'AbortError' });
+});
+
+test('wrapper: Chunker.collectPacked packs chunks into one buffer', () => {
+    const text = "Hello. World. Test.";
+    const options = { size: 10, delimiters: ".", overlap: 2 };
+    const packed = new Chunker(text, options).collectPacked();
+    assert.ok(packed.bytes instanceof Uint8Array);
+    assert.ok(packed.offsets instanceof Uint32Array);
+    assert.notStrictEqual(packed.bytes.buffer, packed.offsets.buffer);
+
+    // Views share the packed buffer and match the collected chunks
+    const chunks = unpackChunks(packed);
+    assert.ok(chunks.every(c => c.buffer === packed.bytes.buffer));
+    assert.deepStrictEqual(
+        chunks.map(c => new TextDecoder().decode(c)),
+        new Chunker(text, options).collectChunks(),
+    );
+
+    // Both buffers survive a structured clone with transfer
+    const moved = structuredClone(packed, { transfer: [packed.bytes.buffer, packed.offsets.buffer] });
+    assert.strictEqual(packed.bytes.byteLength, 0);
+    assert.strictEqual(unpackChunks(moved).length, chunks.length);
+
+    const empty = new Chunker("", options).collectPacked();
+    assert.deepStrictEqual(unpackChunks(empty), []);
+});
+
+test('wrapper: invalid options throw', () => {
+    assert.throws(() => chunk_offsets("Hello.", { size: 0 }), /size must be at least 1/);
+    assert.throws(
+        () => new Chunker("Hello.", { size: 8, overlap: 8 }),
+        /overlap \(8\) must be smaller than size \(8\)/
+    );
+    assert.throws(() => new StreamChunker({ size: 0 }), /size must be at least 1/);
+    assert.throws(
+        () => chunk_offsets("Hello.", { size: 4, overlap: 4, unit: "chars" }),
+        /overlap \(4\) must be smaller than size \(4\)/
+    );
+    assert.throws(() => chunk_offsets("abcd", { size: 2, delimiters: "" }), /no delimiters or patterns to split at/);
+});
+
 test('wrapper: Chunker.collectOffsets method', () => {
     const text = encoder.encode("Hello. World. Test.");
     const chunker = new Chunker(text, { size: 10, delimiters: "." });
@@ -233,3 +458,170 @@ test('wrapper: patterns composable with delimiters', () => {
     const total = offsets.reduce((sum, [start, end]) => sum + (end - start), 0);
     assert.strictEqual(total, bytes.length);
 });
+
+test('wrapper: patterns longest match wins', () => {
+    const text = "One\n\nTwo\nThree";
+    const results = [...chunk(text, { size: 6, delimiters: "", patterns: ["\n", "\n\n"], prefix: true })];
+    assert.strictEqual(results[0], "One");
+    assert.ok(results[1].startsWith("\n\n"));
+});
+
+test('wrapper: patterns accept Uint8Array', () => {
+    const text = new Uint8Array([97, 98, 99, 0xff, 0xfe, 100, 101, 102, 0xff, 0xfe, 103]);
+    const offsets = chunk_offsets(text, { size: 8, delimiters: "", patterns: [new Uint8Array([0xff, 0xfe])] });
+    assert.deepStrictEqual(offsets[0], [0, 5]);
+});
+
+test('wrapper: overlap carries trailing context', () => {
+    const results = [...chunk("Hello. World. Test.", { size: 10, delimiters: ".", overlap: 3 })];
+    assert.deepStrictEqual(results, ["Hello.", "lo. World.", "ld. Test."]);
+
+    const chunker = new Chunker("Hello. World. Test.", { size: 10, delimiters: ".", overlap: 3 });
+    assert.deepStrictEqual(chunker.collectOffsets(), [[0, 6], [3, 13], [10, 19]]);
+    chunker.free();
+});
+
+test('wrapper: overlapRatio sets the overlap as a share of the size', () => {
+    const results = [...chunk("Hello. World. Test.", { size: 10, delimiters: ".", overlapRatio: 0.3 })];
+    assert.deepStrictEqual(results, ["Hello.", "lo. World.", "ld. Test."]);
+    assert.throws(() => new Chunker("Hello.", { overlapRatio: 1 }), /overlap ratio/);
+});
+
+test('wrapper: delimiters can name a class', () => {
+    const text = "Split these words";
+    assert.deepStrictEqual([...chunk(text, { size: 10, delimiters: Delimiters.WHITESPACE })], ["Split ", "these ", "words"]);
+    assert.deepStrictEqual(chunk_offsets(text, { size: 10, delimiters: "whitespace" }), [[0, 6], [6, 12], [12, 17]]);
+    const stream = new StreamChunker({ size: 20, delimiters: Delimiters.MARKDOWN_HEADINGS });
+    const chunks = [...stream.push("# One\nIntro.\n## Two\nMore text.\n# Three\n"), ...stream.finish()];
+    assert.strictEqual(decoder.decode(chunks[0]), "# One\nIntro.");
+    assert.deepStrictEqual([...chunk("今日は晴れ。明日は雨！", { size: 20, delimiters: Delimiters.CJK })], ["今日は晴れ。", "明日は雨！"]);
+    assert.deepStrictEqual([...chunk("Hi. 你好。Ok", { size: 10, delimiters: "multilingual" })], ["Hi.", " 你好。", "Ok"]);
+});
+
+test('wrapper: count_tokens and estimate_tokens', () => {
+    assert.strictEqual(count_tokens("one two three", (text) => text.split(" ")), 3);
+    assert.strictEqual(count_tokens(encoder.encode("one two"), { encode: (text) => text.split(" ") }), 2);
+    assert.strictEqual(count_tokens("one two", (text) => text.length), 7);
+    assert.strictEqual(estimate_tokens("Hello, world! How are you?"), 7);
+    assert.strictEqual(estimate_tokens(encoder.encode("a b c d e f g h i j")), 13);
+});
+
+test('wrapper: string input is utf8-safe by default', () => {
+    const results = [...chunk("a€b€", { size: 3, delimiters: "." })];
+    assert.deepStrictEqual(results, ["a", "€", "b", "€"]);
+
+    const chunker = new Chunker("a€b€", { size: 3, delimiters: "." });
+    assert.deepStrictEqual(chunker.collectOffsets()[0], [0, 1]);
+    chunker.free();
+});
+
+test('wrapper: utf8Safe opt-in for bytes and opt-out for strings', () => {
+    const bytes = encoder.encode("a€b€");
+    assert.deepStrictEqual(chunk_offsets(bytes, { size: 3, delimiters: "." })[0], [0, 3]);
+    assert.deepStrictEqual(chunk_offsets(bytes, { size: 3, delimiters: ".", utf8Safe: true })[0], [0, 1]);
+    assert.deepStrictEqual(chunk_offsets("a€b€", { size: 3, delimiters: ".", utf8Safe: false })[0], [0, 3]);
+});
+
+test('wrapper: size measured in chars', () => {
+    const text = "ああああ いいいい";
+    assert.deepStrictEqual(chunk_offsets(text, { size: 5, delimiters: " ", unit: "chars" }), [[0, 13], [13, 25]]);
+    assert.deepStrictEqual(chunk_offsets(text, { size: 5, delimiters: " ", unit: SizeUnit.Chars }), [[0, 13], [13, 25]]);
+
+    const chunker = new Chunker(text, { size: 5, delimiters: " ", unit: "chars" });
+    assert.deepStrictEqual([...chunker], ["ああああ ", "いいいい"]);
+    chunker.free();
+});
+
+test('wrapper: merge_chunks packs sentences into larger windows', () => {
+    const sentences = chunk_offsets("One. Two. Three. Four.", { size: 8, delimiters: "." });
+    assert.deepStrictEqual(merge_chunks(sentences, 10), [[0, 9], [9, 16], [16, 22]]);
+    assert.deepStrictEqual(merge_chunks([], 10), []);
+});
+
+test('wrapper: Chunker.collectParentChild maps children to parents', () => {
+    const chunker = new Chunker("One. Two. Three. Four.", { size: 8, delimiters: "." });
+    const { parents, children, parentOf } = chunker.collectParentChild(10);
+    assert.deepStrictEqual(children, [[0, 4], [4, 9], [9, 16], [16, 22]]);
+    assert.deepStrictEqual(parents, [[0, 9], [9, 16], [16, 22]]);
+    assert.deepStrictEqual(parentOf, [0, 0, 1, 2]);
+    chunker.free();
+});
+
+test('wrapper: minSize merges tiny trailing chunk', () => {
+    const text = "Hello. World. Hi.";
+    assert.deepStrictEqual([...chunk(text, { size: 14, delimiters: "." })], ["Hello. World.", " Hi."]);
+    assert.deepStrictEqual([...chunk(text, { size: 14, delimiters: ".", minSize: 5 })], [text]);
+
+    const chunker = new Chunker(text, { size: 14, delimiters: ".", minSize: 5 });
+    assert.deepStrictEqual(chunker.collectOffsets(), [[0, 17]]);
+    chunker.free();
+});
+
+test('wrapper: maxSize is a strict cap', () => {
+    const text = "averyveryverylongword next word";
+    const results = [...chunk(text, { size: 8, pattern: " ", forwardFallback: true, maxSize: 10 })];
+    assert.ok(results.every(r => encoder.encode(r).length <= 10));
+    assert.strictEqual(results.join(""), text);
+
+    const chunker = new Chunker("Hello. World. Test.", { size: 10, delimiters: ".", overlap: 5, maxSize: 9 });
+    assert.deepStrictEqual(chunker.collectOffsets(), [[0, 6], [4, 13], [10, 19]]);
+    chunker.free();
+});
+
+// ============ RecursiveChunker tests ============
+
+test('wrapper: RecursiveChunker descends only when needed', () => {
+    const chunker = new RecursiveChunker([["\n\n"], [". "]], { size: 12 });
+    const results = [...chunker.chunk("Intro.\n\nOne. Two. Three. Four.")];
+    assert.deepStrictEqual(results, ["Intro.\n\n", "One. Two. ", "Three. Four."]);
+    chunker.free();
+});
+
+test('wrapper: RecursiveChunker hard split fallback', () => {
+    const chunker = new RecursiveChunker([["\n\n"], [" "]], { size: 5 });
+    assert.deepStrictEqual(chunker.chunkOffsets("abcdefghijklmnop"), [[0, 5], [5, 10], [10, 15], [15, 16]]);
+    chunker.free();
+});
+
+test('wrapper: RecursiveChunker with Uint8Array input and prefix', () => {
+    const text = encoder.encode("aaaa\nbbbb\ncccc");
+    const chunker = new RecursiveChunker([[new Uint8Array([10])]], { size: 6, prefix: true });
+    const results = [...chunker.chunk(text)].map(c => decoder.decode(c));
+    assert.deepStrictEqual(results, ["aaaa", "\nbbbb", "\ncccc"]);
+    chunker.free();
+});
+
+test('wrapper: RecursiveChunker.paragraphs preset', () => {
+    const chunker = RecursiveChunker.paragraphs({ size: 30 });
+    const chunks = [...chunker.chunk("Para one.\n\nPara two is a bit longer. Yes it is.")];
+    assert.deepStrictEqual(chunks, ["Para one.\n\n", "Para two is a bit longer. ", "Yes it is."]);
+    chunker.free();
+});
+
+// ============ SentenceChunker tests ============
+
+test('wrapper: SentenceChunker keeps abbreviations and decimals', () => {
+    const text = "Dr. Smith paid $3.14 for it. Then he left.";
+    const chunker = new SentenceChunker();
+    const sentences = chunker.sentenceOffsets(text).map(([s, e]) => text.slice(s, e));
+    assert.deepStrictEqual(sentences, ["Dr. Smith paid $3.14 for it. ", "Then he left."]);
+    chunker.free();
+});
+
+test('wrapper: SentenceChunker packs sentences up to size', () => {
+    const chunker = new SentenceChunker({ size: 10 });
+    assert.deepStrictEqual([...chunker.chunk("One. Two. Three. Four.")], ["One. Two. ", "Three. ", "Four."]);
+    chunker.free();
+});
+
+test('wrapper: SentenceChunker custom abbreviations', () => {
+    const chunker = new SentenceChunker({ abbreviations: ["Dr."] });
+    assert.deepStrictEqual(chunker.sentenceOffsets("See Dr. Who. Or not."), [[0, 13], [13, 20]]);
+    chunker.free();
+});
+
+test('wrapper: SentenceChunker rejects invalid UTF-8', () => {
+    const chunker = new SentenceChunker();
+    assert.throws(() => chunker.chunkOffsets(new Uint8Array([0xff, 0xfe])));
+    chunker.free();
+});
@@ -0,0 +1,545 @@
+//! Chunking the files inside tar and zip archives (requires the `archive`
+//! feature).
+//!
+//! This module provides [`chunk_archive`], which reads the entries of a
+//! `.tar`, `.tar.gz` or `.zip` file, as dataset dumps often come, and chunks
+//! each text file in it, each result carrying the entry's name.
+
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+use crate::cancel::CancelToken;
+use crate::config::ChunkerConfig;
+use crate::reader::Decompressed;
+use crate::zip;
+
+/// The chunks of one file from [`chunk_archive`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// The entry's path in the archive, lossily decoded as UTF-8.
+    pub name: String,
+    /// The file's contents.
+    pub text: Vec<u8>,
+    /// (start, end) byte offsets of each chunk in `text`.
+    pub offsets: Vec<(usize, usize)>,
+}
+
+impl ArchiveEntry {
+    /// The chunks as slices of `text`.
+    pub fn chunks(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.offsets
+            .iter()
+            .map(|&(start, end)| &self.text[start..end])
+    }
+}
+
+/// How many leading bytes are checked for a NUL to tell binary files apart.
+const BINARY_SNIFF_LEN: usize = 8000;
+
+/// Whether `data` looks like a binary file rather than text.
+fn is_binary(data: &[u8]) -> bool {
+    memchr::memchr(0, &data[..data.len().min(BINARY_SNIFF_LEN)]).is_some()
+}
+
+/// Chunk each text file in the archive at `path`, with `config`.
+///
+/// The format is told from the file's first bytes: a zip archive, or a tar
+/// archive, gzip-compressed or not (ustar, GNU and pax long names are
+/// understood). Directories, links and other special entries are skipped,
+/// and so are binary files, told apart by a NUL byte near their start.
+///
+/// A tar archive is read as a stream, one entry at a time, as the iterator
+/// advances. A zip archive keeps its index at the end, so it's read into
+/// memory up front; only stored and deflated entries can be read.
+///
+/// # Example
+///
+/// ```no_run
+/// use chunk::{ChunkerConfig, chunk_archive};
+///
+/// let config = ChunkerConfig::new().size(1024);
+/// for entry in chunk_archive("dump.tar.gz", &config)? {
+///     let entry = entry?;
+///     for chunk in entry.chunks() {
+///         println!("{}: {} bytes", entry.name, chunk.len());
+///     }
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn chunk_archive(path: impl AsRef<Path>, config: &ChunkerConfig) -> io::Result<ArchiveChunks> {
+    let path = path.as_ref();
+    let mut magic = [0; 4];
+    let read = File::open(path)?.read(&mut magic)?;
+    let source = if zip::is_zip(&magic[..read]) {
+        let data = std::fs::read(path)?;
+        let entries = zip::entries(&data).ok_or_else(|| invalid("not a valid zip archive"))?;
+        Source::Zip {
+            data,
+            entries: entries.into_iter(),
+        }
+    } else {
+        Source::Tar(Tar::new(Decompressed::open(path)?))
+    };
+    Ok(ArchiveChunks {
+        source,
+        config: config.clone(),
+        cancel: None,
+    })
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Iterator over the chunks of each text file in an archive, created by
+/// [`chunk_archive`].
+///
+/// A zip entry that can't be read yields an error and the iteration goes on;
+/// after an error reading a tar archive, the iterator is exhausted.
+#[derive(Debug)]
+pub struct ArchiveChunks {
+    source: Source,
+    config: ChunkerConfig,
+    cancel: Option<CancelToken>,
+}
+
+#[derive(Debug)]
+enum Source {
+    Tar(Tar<Decompressed<BufReader<File>>>),
+    Zip {
+        data: Vec<u8>,
+        entries: std::vec::IntoIter<zip::Entry>,
+    },
+}
+
+impl ArchiveChunks {
+    /// Stop chunking once `token` is cancelled.
+    ///
+    /// The token is checked after every chunk. Once it's cancelled, the
+    /// iterator yields one [`Interrupted`](io::ErrorKind::Interrupted) error
+    /// and ends.
+    pub fn with_cancel(mut self, token: &CancelToken) -> Self {
+        self.cancel = Some(token.clone());
+        self
+    }
+
+    /// The next text file in the archive, as (name, contents).
+    fn next_file(&mut self) -> Option<io::Result<(String, Vec<u8>)>> {
+        match &mut self.source {
+            Source::Tar(tar) => loop {
+                match tar.next_file() {
+                    Ok(Some((_, data))) if is_binary(&data) => {}
+                    file => return file.transpose(),
+                }
+            },
+            Source::Zip { data, entries } => loop {
+                let entry = entries.next()?;
+                if entry.is_dir() {
+                    continue;
+                }
+                match entry.read(data) {
+                    Ok(text) if is_binary(&text) => {}
+                    Ok(text) => return Some(Ok((entry.name, text))),
+                    Err(zip::ReadError::Invalid) => {
+                        return Some(Err(invalid(format!("{}: truncated entry", entry.name))));
+                    }
+                    Err(zip::ReadError::UnsupportedCompression(method)) => {
+                        return Some(Err(invalid(format!(
+                            "{}: unsupported compression method {method}",
+                            entry.name
+                        ))));
+                    }
+                }
+            },
+        }
+    }
+}
+
+impl Iterator for ArchiveChunks {
+    type Item = io::Result<ArchiveEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cancel = self.cancel.clone();
+        let entry = match cancel.as_ref().map(CancelToken::check) {
+            Some(Err(cancelled)) => Err(cancelled),
+            _ => match self.next_file()? {
+                Ok((name, text)) => {
+                    let offsets = match &cancel {
+                        Some(token) => self.config.chunker(&text).collect_offsets_until(token),
+                        None => Ok(self.config.chunker(&text).collect_offsets()),
+                    };
+                    offsets.map(|offsets| ArchiveEntry {
+                        name,
+                        text,
+                        offsets,
+                    })
+                }
+                Err(err) => return Some(Err(err)),
+            },
+        };
+        if entry.is_err() {
+            // Nothing more is read once cancelled
+            self.cancel = None;
+            self.source = Source::Zip {
+                data: Vec::new(),
+                entries: Vec::new().into_iter(),
+            };
+        }
+        Some(entry.map_err(Into::into))
+    }
+}
+
+/// Size of a tar header and the unit entries are padded to.
+const BLOCK: usize = 512;
+
+/// A streaming reader of the regular files in a tar archive.
+#[derive(Debug)]
+struct Tar<R> {
+    reader: R,
+    /// No header has been read yet, so a bad one means this isn't tar.
+    first: bool,
+    /// The end, or an error, has been reached.
+    done: bool,
+}
+
+impl<R: Read> Tar<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            first: true,
+            done: false,
+        }
+    }
+
+    /// The next regular file, as (name, contents), or `None` at the end.
+    fn next_file(&mut self) -> io::Result<Option<(String, Vec<u8>)>> {
+        if self.done {
+            return Ok(None);
+        }
+        let file = self.read_file();
+        if !matches!(file, Ok(Some(_))) {
+            self.done = true;
+        }
+        file
+    }
+
+    fn read_file(&mut self) -> io::Result<Option<(String, Vec<u8>)>> {
+        // A name for the next entry from a GNU or pax long-name entry
+        let mut long_name = None;
+        loop {
+            let mut header = [0; BLOCK];
+            let read = match self.read_block(&mut header) {
+                // Shorter than a header, so not tar at all
+                Err(err) if self.first && err.kind() == io::ErrorKind::UnexpectedEof => {
+                    return Err(invalid("not a tar or zip archive"));
+                }
+                read => read?,
+            };
+            if !read || header.iter().all(|&b| b == 0) {
+                return Ok(None);
+            }
+            let size = checksum_ok(&header)
+                .then(|| entry_size(&header[124..136]))
+                .flatten();
+            let Some(size) = size else {
+                let message = if self.first {
+                    "not a tar or zip archive"
+                } else {
+                    "invalid tar header"
+                };
+                return Err(invalid(message));
+            };
+            self.first = false;
+            match header[156] {
+                // Regular and contiguous files
+                b'0' | 0 | b'7' => {
+                    let data = self.read_data(size)?;
+                    let name = long_name.unwrap_or_else(|| header_name(&header));
+                    return Ok(Some((name, data)));
+                }
+                b'L' => long_name = Some(until_nul(&self.read_data(size)?)),
+                b'x' => long_name = pax_path(&self.read_data(size)?).or(long_name),
+                // Directories, links, devices, global pax headers
+                _ => self.skip_data(size)?,
+            }
+        }
+    }
+
+    /// Fill `block`, or return `false` at the end of the input.
+    fn read_block(&mut self, block: &mut [u8; BLOCK]) -> io::Result<bool> {
+        let mut filled = 0;
+        while filled < BLOCK {
+            match self.reader.read(&mut block[filled..]) {
+                Ok(0) if filled == 0 => return Ok(false),
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => filled += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Read an entry's `size` bytes of data and its padding.
+    fn read_data(&mut self, size: u64) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        (&mut self.reader).take(size).read_to_end(&mut data)?;
+        if (data.len() as u64) < size {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        self.skip(padding(size))?;
+        Ok(data)
+    }
+
+    /// Skip an entry's `size` bytes of data and its padding.
+    fn skip_data(&mut self, size: u64) -> io::Result<()> {
+        self.skip(size + padding(size))
+    }
+
+    fn skip(&mut self, len: u64) -> io::Result<()> {
+        let skipped = io::copy(&mut (&mut self.reader).take(len), &mut io::sink())?;
+        if skipped < len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+}
+
+/// Bytes of padding after `size` bytes of data, up to the next block.
+fn padding(size: u64) -> u64 {
+    (BLOCK as u64 - size % BLOCK as u64) % BLOCK as u64
+}
+
+/// Whether the header's checksum, the sum of its bytes with the checksum
+/// field taken as spaces, matches.
+fn checksum_ok(header: &[u8; BLOCK]) -> bool {
+    let sum: u64 = header
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| if (148..156).contains(&i) { b' ' } else { b } as u64)
+        .sum();
+    octal(&header[148..156]) == Some(sum)
+}
+
+/// An entry's size: octal, or base-256 big-endian when the high bit is set.
+fn entry_size(field: &[u8]) -> Option<u64> {
+    if field[0] & 0x80 != 0 {
+        let first = u64::from(field[0] & 0x7f);
+        return field[1..].iter().try_fold(first, |size, &b| {
+            size.checked_mul(256).map(|size| size | u64::from(b))
+        });
+    }
+    octal(field)
+}
+
+/// An octal number padded with spaces or NULs.
+fn octal(field: &[u8]) -> Option<u64> {
+    let digits = until_nul(field);
+    let digits = digits.trim_matches(' ');
+    if digits.is_empty() {
+        return Some(0);
+    }
+    u64::from_str_radix(digits, 8).ok()
+}
+
+/// The name in a header, with the ustar prefix in front.
+fn header_name(header: &[u8; BLOCK]) -> String {
+    let name = until_nul(&header[..100]);
+    let prefix = until_nul(&header[345..500]);
+    // GNU headers keep other fields where the ustar prefix goes
+    if &header[257..263] == b"ustar\0" && !prefix.is_empty() {
+        return format!("{prefix}/{name}");
+    }
+    name
+}
+
+/// The `path` of a pax extended header, records like `"30 path=a/b.txt\n"`.
+fn pax_path(data: &[u8]) -> Option<String> {
+    let mut rest = data;
+    while let Some(space) = memchr::memchr(b' ', rest) {
+        let len: usize = std::str::from_utf8(&rest[..space]).ok()?.parse().ok()?;
+        let record = rest.get(space + 1..len)?;
+        let record = record.strip_suffix(b"\n").unwrap_or(record);
+        if let Some(path) = record.strip_prefix(b"path=") {
+            return Some(String::from_utf8_lossy(path).into_owned());
+        }
+        rest = &rest[len..];
+    }
+    None
+}
+
+/// The bytes of `field` before the first NUL, lossily decoded.
+fn until_nul(field: &[u8]) -> String {
+    let end = memchr::memchr(0, field).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    /// A tar header for `name`, of entry type `kind` and `size` bytes.
+    fn header(name: &str, kind: u8, size: usize) -> [u8; BLOCK] {
+        let mut header = [0; BLOCK];
+        let (prefix, name) = match name.len() {
+            0..=100 => ("", name),
+            _ => name.rsplit_once('/').unwrap(),
+        };
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[100..107].copy_from_slice(b"0000644");
+        header[124..135].copy_from_slice(format!("{size:011o}").as_bytes());
+        header[156] = kind;
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+        header[148..156].fill(b' ');
+        let sum: u32 = header.iter().map(|&b| u32::from(b)).sum();
+        header[148..155].copy_from_slice(format!("{sum:06o}\0").as_bytes());
+        header
+    }
+
+    /// A tar archive of `(name, entry type, contents)` entries.
+    fn tar(entries: &[(&str, u8, &[u8])]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for &(name, kind, data) in entries {
+            out.extend(header(name, kind, data.len()));
+            out.extend(data);
+            out.resize(out.len() + padding(data.len() as u64) as usize, 0);
+        }
+        out.extend([0; 2 * BLOCK]);
+        out
+    }
+
+    /// A file that removes itself.
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, data: &[u8]) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("chunk-archive-{}-{name}", std::process::id()));
+            std::fs::write(&path, data).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn chunked(file: &TempFile) -> Vec<(String, Vec<String>)> {
+        let config = ChunkerConfig::new().size(6).delimiters(b".");
+        chunk_archive(&file.0, &config)
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                let chunks = entry
+                    .chunks()
+                    .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+                    .collect();
+                (entry.name, chunks)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chunk_tar() {
+        let long = format!("{}/deep.txt", "d".repeat(120));
+        let pax = b"20 path=pax/name.md\n";
+        let data = tar(&[
+            ("docs/", b'5', b""),
+            ("docs/a.txt", b'0', b"One. Two."),
+            ("docs/logo.png", b'0', b"\x89PNG\0\0"),
+            ("docs/link", b'2', b""),
+            (&long, b'0', b"Deep."),
+            ("././@LongLink", b'L', b"gnu/long/name.txt\0"),
+            ("gnu/long/na", b'0', b"Gnu."),
+            ("PaxHeaders/name.md", b'x', pax),
+            ("name.md", b'0', b"Pax."),
+        ]);
+        let expected = vec![
+            (
+                "docs/a.txt".to_string(),
+                vec!["One.".into(), " Two.".into()],
+            ),
+            (long, vec!["Deep.".into()]),
+            ("gnu/long/name.txt".into(), vec!["Gnu.".into()]),
+            ("pax/name.md".into(), vec!["Pax.".into()]),
+        ];
+        assert_eq!(chunked(&TempFile::new("plain.tar", &data)), expected);
+
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gz.write_all(&data).unwrap();
+        let gz = TempFile::new("dump.tar.gz", &gz.finish().unwrap());
+        assert_eq!(chunked(&gz), expected);
+    }
+
+    #[test]
+    fn test_chunk_zip() {
+        let data = zip::build(&[
+            ("wiki/", "", false),
+            ("wiki/a.txt", "One. Two.", true),
+            ("wiki/b.bin", "\0\u{1}", false),
+            ("wiki/c.txt", "Six.", false),
+        ]);
+        assert_eq!(
+            chunked(&TempFile::new("wiki.zip", &data)),
+            vec![
+                (
+                    "wiki/a.txt".to_string(),
+                    vec!["One.".into(), " Two.".into()]
+                ),
+                ("wiki/c.txt".into(), vec!["Six.".into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chunk_archive_cancel() {
+        let data = tar(&[("a.txt", b'0', b"One."), ("b.txt", b'0', b"Two.")]);
+        let file = TempFile::new("cancel.tar", &data);
+        let token = CancelToken::new();
+        let mut entries = chunk_archive(&file.0, &ChunkerConfig::new())
+            .unwrap()
+            .with_cancel(&token);
+        assert_eq!(entries.next().unwrap().unwrap().name, "a.txt");
+        token.cancel();
+        let err = entries.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn test_chunk_archive_errors() {
+        let config = ChunkerConfig::new();
+        for (name, data) in [("text.txt", &[b'x'; 600][..]), ("short.txt", b"Hello.")] {
+            let file = TempFile::new(name, data);
+            let err = chunk_archive(&file.0, &config).unwrap().next().unwrap();
+            assert_eq!(err.unwrap_err().to_string(), "not a tar or zip archive");
+        }
+
+        let mut data = tar(&[("a.txt", b'0', b"One."), ("b.txt", b'0', &[b'x'; 700])]);
+        data.truncate(3 * BLOCK);
+        let truncated = TempFile::new("truncated.tar", &data);
+        let mut entries = chunk_archive(&truncated.0, &config).unwrap();
+        assert_eq!(entries.next().unwrap().unwrap().name, "a.txt");
+        let err = entries.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert!(entries.next().is_none());
+
+        let empty = TempFile::new("empty.tar", b"");
+        assert_eq!(chunk_archive(&empty.0, &config).unwrap().count(), 0);
+        assert_eq!(
+            pax_path(b"8 a=bcd\n20 path=pax/name.md\n"),
+            Some("pax/name.md".into())
+        );
+        assert_eq!(
+            entry_size(&[0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 1]),
+            Some(513)
+        );
+    }
+}
@@ -0,0 +1,906 @@
+//! Chunking Arrow string and binary columns (requires the `arrow` feature).
+//!
+//! This module speaks the [Arrow C data interface] directly, without an Arrow
+//! library: [`TextColumn`] reads a `utf8`, `large_utf8`, `binary`,
+//! `large_binary` or view array exported by pyarrow, Polars, DuckDB or
+//! arrow-rs, and [`ChunkTable`] collects the chunks of its documents and
+//! exports them back as an Arrow array or stream.
+//!
+//! [Arrow C data interface]: https://arrow.apache.org/docs/format/CDataInterface.html
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ffi::{CStr, c_char, c_int, c_void};
+use core::ops::Range;
+use core::{fmt, ptr, slice};
+
+use crate::chunk::Chunker;
+
+/// `ArrowSchema` from the C data interface: the type of an array.
+///
+/// An owned schema releases itself when dropped.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrowSchema {
+    format: *const c_char,
+    name: *const c_char,
+    metadata: *const c_char,
+    flags: i64,
+    n_children: i64,
+    children: *mut *mut ArrowSchema,
+    dictionary: *mut ArrowSchema,
+    release: Option<unsafe extern "C" fn(*mut ArrowSchema)>,
+    private_data: *mut c_void,
+}
+
+/// `ArrowArray` from the C data interface: the buffers of an array.
+///
+/// An owned array releases itself when dropped.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrowArray {
+    length: i64,
+    null_count: i64,
+    offset: i64,
+    n_buffers: i64,
+    n_children: i64,
+    buffers: *mut *const c_void,
+    children: *mut *mut ArrowArray,
+    dictionary: *mut ArrowArray,
+    release: Option<unsafe extern "C" fn(*mut ArrowArray)>,
+    private_data: *mut c_void,
+}
+
+/// `ArrowArrayStream` from the C stream interface: a sequence of arrays of
+/// one type.
+///
+/// An owned stream releases itself when dropped.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrowArrayStream {
+    get_schema: Option<unsafe extern "C" fn(*mut ArrowArrayStream, *mut ArrowSchema) -> c_int>,
+    get_next: Option<unsafe extern "C" fn(*mut ArrowArrayStream, *mut ArrowArray) -> c_int>,
+    get_last_error: Option<unsafe extern "C" fn(*mut ArrowArrayStream) -> *const c_char>,
+    release: Option<unsafe extern "C" fn(*mut ArrowArrayStream)>,
+    private_data: *mut c_void,
+}
+
+macro_rules! owned_ffi_struct {
+    ($name:ident) => {
+        impl $name {
+            /// Take ownership of the value at `ptr`, marking it released as
+            /// the C data interface's move semantics require.
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must point to a valid, writable value produced by an
+            /// implementation of the C data interface.
+            pub unsafe fn take(ptr: *mut Self) -> Self {
+                // SAFETY: the caller guarantees `ptr` is valid
+                unsafe {
+                    let value = ptr::read(ptr);
+                    (*ptr).release = None;
+                    value
+                }
+            }
+
+            /// Whether the value was released (or moved out).
+            pub fn is_released(&self) -> bool {
+                self.release.is_none()
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                if let Some(release) = self.release {
+                    // SAFETY: an unreleased value is owned, so it's released once
+                    unsafe { release(self) };
+                }
+            }
+        }
+
+        // SAFETY: the C data interface requires release to be callable from
+        // any thread
+        unsafe impl Send for $name {}
+    };
+}
+
+owned_ffi_struct!(ArrowSchema);
+owned_ffi_struct!(ArrowArray);
+owned_ffi_struct!(ArrowArrayStream);
+
+impl ArrowSchema {
+    /// A released schema, for a producer to write into.
+    pub fn empty() -> Self {
+        Self {
+            format: ptr::null(),
+            name: ptr::null(),
+            metadata: ptr::null(),
+            flags: 0,
+            n_children: 0,
+            children: ptr::null_mut(),
+            dictionary: ptr::null_mut(),
+            release: None,
+            private_data: ptr::null_mut(),
+        }
+    }
+
+    /// The type's format string, e.g. `"u"` for `utf8`.
+    ///
+    /// Returns None for a released schema.
+    pub fn format(&self) -> Option<&str> {
+        if self.is_released() || self.format.is_null() {
+            return None;
+        }
+        // SAFETY: an unreleased schema has a valid format string
+        unsafe { CStr::from_ptr(self.format) }.to_str().ok()
+    }
+}
+
+impl ArrowArray {
+    /// A released array, for a producer to write into.
+    pub fn empty() -> Self {
+        Self {
+            length: 0,
+            null_count: 0,
+            offset: 0,
+            n_buffers: 0,
+            n_children: 0,
+            buffers: ptr::null_mut(),
+            children: ptr::null_mut(),
+            dictionary: ptr::null_mut(),
+            release: None,
+            private_data: ptr::null_mut(),
+        }
+    }
+
+    /// The number of rows.
+    pub fn len(&self) -> usize {
+        self.length.max(0) as usize
+    }
+
+    /// Whether the array has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Buffer `index`, or null if it's absent.
+    fn buffer(&self, index: usize) -> *const c_void {
+        if index >= self.n_buffers.max(0) as usize || self.buffers.is_null() {
+            return ptr::null();
+        }
+        // SAFETY: an unreleased array has n_buffers buffer pointers
+        unsafe { *self.buffers.add(index) }
+    }
+}
+
+impl ArrowArrayStream {
+    /// A released stream, for a producer to write into.
+    pub fn empty() -> Self {
+        Self {
+            get_schema: None,
+            get_next: None,
+            get_last_error: None,
+            release: None,
+            private_data: ptr::null_mut(),
+        }
+    }
+
+    /// The schema of the stream's arrays.
+    pub fn schema(&mut self) -> Result<ArrowSchema, ArrowError> {
+        let get_schema = self
+            .get_schema
+            .filter(|_| !self.is_released())
+            .ok_or(ArrowError::Released)?;
+        let mut schema = ArrowSchema::empty();
+        // SAFETY: an unreleased stream has valid callbacks
+        let code = unsafe { get_schema(self, &mut schema) };
+        self.check(code)?;
+        Ok(schema)
+    }
+
+    /// The next array, or None at the end of the stream.
+    pub fn next_array(&mut self) -> Result<Option<ArrowArray>, ArrowError> {
+        let get_next = self
+            .get_next
+            .filter(|_| !self.is_released())
+            .ok_or(ArrowError::Released)?;
+        let mut array = ArrowArray::empty();
+        // SAFETY: an unreleased stream has valid callbacks
+        let code = unsafe { get_next(self, &mut array) };
+        self.check(code)?;
+        Ok((!array.is_released()).then_some(array))
+    }
+
+    /// Turn a callback's return code into an error with the stream's
+    /// message.
+    fn check(&mut self, code: c_int) -> Result<(), ArrowError> {
+        if code == 0 {
+            return Ok(());
+        }
+        let message = self
+            .get_last_error
+            // SAFETY: an unreleased stream has valid callbacks
+            .map(|get_last_error| unsafe { get_last_error(self) })
+            .filter(|message| !message.is_null())
+            // SAFETY: a non-null error is a valid string until the next call
+            .map(|message| {
+                unsafe { CStr::from_ptr(message) }
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .unwrap_or_default();
+        Err(ArrowError::Stream { code, message })
+    }
+}
+
+/// Error reading an Arrow array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArrowError {
+    /// The array, schema or stream was already released.
+    Released,
+    /// The array isn't a string or binary array; holds its format string.
+    UnsupportedType(String),
+    /// The array's buffers don't make a valid array of its type.
+    Malformed(&'static str),
+    /// A stream callback failed with an `errno` code.
+    Stream {
+        /// The `errno` code.
+        code: i32,
+        /// The stream's error message, if any.
+        message: String,
+    },
+}
+
+impl fmt::Display for ArrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrowError::Released => write!(f, "arrow array was already released"),
+            ArrowError::UnsupportedType(format) => write!(
+                f,
+                "unsupported arrow type {format:?}, expected a string or binary array"
+            ),
+            ArrowError::Malformed(reason) => write!(f, "malformed arrow array: {reason}"),
+            ArrowError::Stream { code, message } if message.is_empty() => {
+                write!(f, "arrow stream failed with error code {code}")
+            }
+            ArrowError::Stream { message, .. } => write!(f, "arrow stream failed: {message}"),
+        }
+    }
+}
+
+impl core::error::Error for ArrowError {}
+
+/// Where a [`TextColumn`]'s values are.
+#[derive(Debug, Clone)]
+enum Values<'a> {
+    /// Offsets into one data buffer (`utf8`, `binary`).
+    Small(&'a [i32], &'a [u8]),
+    /// 64-bit offsets (`large_utf8`, `large_binary`).
+    Large(&'a [i64], &'a [u8]),
+    /// 16-byte views, inline or into the data buffers (`utf8_view`,
+    /// `binary_view`).
+    Views(&'a [[u8; 16]], Vec<&'a [u8]>),
+}
+
+/// A string or binary Arrow array, read in place: one document per row.
+///
+/// # Example
+///
+/// ```no_run
+/// use chunk::{ArrowArray, ArrowLayout, ArrowSchema, ChunkTable, TextColumn};
+///
+/// # fn export() -> (*mut ArrowSchema, *mut ArrowArray) { unimplemented!() }
+/// // Pointers from a producer, e.g. pyarrow's `Array._export_to_c`
+/// let (schema, array) = export();
+/// let (schema, array) = unsafe { (ArrowSchema::take(schema), ArrowArray::take(array)) };
+/// let column = TextColumn::new(&array, &schema)?;
+///
+/// let mut table = ChunkTable::new();
+/// table.push_column(&column, |c| c.size(1024).delimiters(b"\n.?"));
+/// let (schema, array) = table.to_arrow(ArrowLayout::Lists);
+/// # Ok::<(), chunk::ArrowError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextColumn<'a> {
+    len: usize,
+    /// Validity bitmap and the bit offset of the first row, if any row is
+    /// null.
+    validity: Option<(&'a [u8], usize)>,
+    values: Values<'a>,
+    utf8: bool,
+}
+
+impl<'a> TextColumn<'a> {
+    /// Read `array`, whose type is `schema`.
+    ///
+    /// Fails unless the type is `utf8`, `large_utf8`, `binary`,
+    /// `large_binary`, `utf8_view` or `binary_view`.
+    pub fn new(array: &'a ArrowArray, schema: &ArrowSchema) -> Result<Self, ArrowError> {
+        if array.is_released() {
+            return Err(ArrowError::Released);
+        }
+        let format = schema.format().ok_or(ArrowError::Released)?;
+        let utf8 = matches!(format, "u" | "U" | "vu");
+        if !matches!(format, "u" | "U" | "z" | "Z" | "vu" | "vz") {
+            return Err(ArrowError::UnsupportedType(format.to_string()));
+        }
+        if array.length < 0 || array.offset < 0 {
+            return Err(ArrowError::Malformed("negative length or offset"));
+        }
+        let len = array.len();
+        let offset = array.offset as usize;
+        let validity = array.buffer(0).cast::<u8>();
+        let validity = (array.null_count != 0 && !validity.is_null() && len > 0).then(|| {
+            // SAFETY: the bitmap has a bit for every row up to offset + len
+            (
+                unsafe { slice::from_raw_parts(validity, (offset + len).div_ceil(8)) },
+                offset,
+            )
+        });
+
+        let values = match format {
+            _ if len == 0 => Values::Small(&[], &[]),
+            "u" | "z" => {
+                // SAFETY: the offsets buffer has offset + len + 1 entries
+                let offsets = unsafe { buffer_slice::<i32>(array, 1, offset + len + 1)? };
+                let offsets = &offsets[offset..];
+                check_offsets(offsets.iter().map(|&o| o as i64))?;
+                let end = offsets[len] as usize;
+                // SAFETY: the data buffer holds the values up to the last offset
+                Values::Small(offsets, unsafe { buffer_slice::<u8>(array, 2, end)? })
+            }
+            "U" | "Z" => {
+                // SAFETY: as above, with 64-bit offsets
+                let offsets = unsafe { buffer_slice::<i64>(array, 1, offset + len + 1)? };
+                let offsets = &offsets[offset..];
+                check_offsets(offsets.iter().copied())?;
+                let end = offsets[len] as usize;
+                // SAFETY: as above
+                Values::Large(offsets, unsafe { buffer_slice::<u8>(array, 2, end)? })
+            }
+            _ => {
+                // Validity, views, the data buffers, then their sizes
+                let n_data = (array.n_buffers.max(3) - 3) as usize;
+                // SAFETY: the sizes buffer has an i64 per data buffer
+                let sizes = unsafe { buffer_slice::<i64>(array, 2 + n_data, n_data)? };
+                let data = sizes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &size)| {
+                        let size = usize::try_from(size)
+                            .map_err(|_| ArrowError::Malformed("negative buffer size"))?;
+                        // SAFETY: data buffer i holds `size` bytes
+                        unsafe { buffer_slice::<u8>(array, 2 + i, size) }
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                // SAFETY: the views buffer has offset + len views
+                let views = unsafe { buffer_slice::<[u8; 16]>(array, 1, offset + len)? };
+                let views = &views[offset..];
+                if !views.iter().all(|view| view_range(view, &data).is_some()) {
+                    return Err(ArrowError::Malformed("view out of bounds"));
+                }
+                Values::Views(views, data)
+            }
+        };
+        Ok(Self {
+            len,
+            validity,
+            values,
+            utf8,
+        })
+    }
+
+    /// The number of rows.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the column has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the column holds strings rather than binary.
+    pub fn is_utf8(&self) -> bool {
+        self.utf8
+    }
+
+    /// Row `index`, or None if it's null.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&'a [u8]> {
+        assert!(index < self.len, "row {index} out of bounds");
+        if let Some((bitmap, offset)) = self.validity {
+            let bit = offset + index;
+            if bitmap[bit / 8] & (1 << (bit % 8)) == 0 {
+                return None;
+            }
+        }
+        Some(match &self.values {
+            Values::Small(offsets, data) => {
+                &data[offsets[index] as usize..offsets[index + 1] as usize]
+            }
+            Values::Large(offsets, data) => {
+                &data[offsets[index] as usize..offsets[index + 1] as usize]
+            }
+            Values::Views(views, data) => {
+                let view: &'a [u8; 16] = &views[index];
+                match view_range(view, data).expect("views are checked") {
+                    (None, range) => &view[range],
+                    (Some(buffer), range) => &data[buffer][range],
+                }
+            }
+        })
+    }
+
+    /// The rows in order, None for nulls.
+    pub fn iter(&self) -> impl Iterator<Item = Option<&'a [u8]>> + '_ {
+        (0..self.len).map(|index| self.get(index))
+    }
+}
+
+/// Buffer `index` of `array` as a slice of `len` values.
+///
+/// # Safety
+///
+/// The buffer must hold at least `len` values of `T`.
+unsafe fn buffer_slice<T>(
+    array: &ArrowArray,
+    index: usize,
+    len: usize,
+) -> Result<&[T], ArrowError> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    let buffer = array.buffer(index).cast::<T>();
+    if buffer.is_null() {
+        return Err(ArrowError::Malformed("missing buffer"));
+    }
+    if !buffer.is_aligned() {
+        return Err(ArrowError::Malformed("misaligned buffer"));
+    }
+    // SAFETY: the caller guarantees the length
+    Ok(unsafe { slice::from_raw_parts(buffer, len) })
+}
+
+/// Check that offsets start at zero or more and never decrease.
+fn check_offsets(mut offsets: impl Iterator<Item = i64>) -> Result<(), ArrowError> {
+    let mut last = offsets.next().unwrap_or(0);
+    if last < 0 {
+        return Err(ArrowError::Malformed("negative offset"));
+    }
+    for offset in offsets {
+        if offset < last {
+            return Err(ArrowError::Malformed("decreasing offsets"));
+        }
+        last = offset;
+    }
+    Ok(())
+}
+
+/// The bytes a view points at: inline in the view itself (None) or in a
+/// data buffer, or None if that's out of bounds.
+fn view_range(view: &[u8; 16], data: &[&[u8]]) -> Option<(Option<usize>, Range<usize>)> {
+    let word = |i: usize| u32::from_ne_bytes(view[i..i + 4].try_into().unwrap()) as usize;
+    let len = word(0);
+    if len <= 12 {
+        return Some((None, 4..4 + len));
+    }
+    let (buffer, start) = (word(8), word(12));
+    (start + len <= data.get(buffer)?.len()).then_some((Some(buffer), start..start + len))
+}
+
+/// How [`ChunkTable::to_arrow`] lays out the chunks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ArrowLayout {
+    /// One row per document, `large_list<struct<start: uint64, end:
+    /// uint64>>`: the chunks of each document.
+    #[default]
+    Lists,
+    /// One row per chunk, `struct<doc_id: uint64, start: uint64, end:
+    /// uint64>`, which imports as a table.
+    Exploded,
+}
+
+/// Chunk offsets of the documents of one or more [`TextColumn`]s.
+///
+/// Documents are numbered from 0 across every pushed column, so a stream of
+/// arrays can be chunked one array at a time. A null document has no
+/// chunks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkTable {
+    /// The document of each chunk.
+    pub doc_ids: Vec<u64>,
+    /// The start of each chunk, a byte offset into its document.
+    pub starts: Vec<u64>,
+    /// The end of each chunk (exclusive).
+    pub ends: Vec<u64>,
+    /// The number of documents pushed.
+    pub docs: usize,
+}
+
+impl ChunkTable {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of chunks.
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Whether the table has no chunks.
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Chunk every document of `column` and append the chunks.
+    ///
+    /// `config` sets up the [`Chunker`] for a single document, as for
+    /// [`chunk_batch`](crate::chunk_batch). With the `rayon` feature the
+    /// documents are chunked in parallel.
+    pub fn push_column<'a, F>(&mut self, column: &TextColumn<'a>, config: F)
+    where
+        F: Fn(Chunker<'a>) -> Chunker<'a> + Sync,
+    {
+        let texts: Vec<&'a [u8]> = column.iter().map(Option::unwrap_or_default).collect();
+        #[cfg(feature = "rayon")]
+        let offsets = crate::batch::chunk_batch(&texts, config);
+        #[cfg(not(feature = "rayon"))]
+        let offsets: Vec<_> = texts
+            .iter()
+            .map(|text| config(crate::chunk::chunk(text)).collect_offsets())
+            .collect();
+
+        for (doc, offsets) in offsets.into_iter().enumerate() {
+            let doc_id = (self.docs + doc) as u64;
+            for (start, end) in offsets {
+                self.doc_ids.push(doc_id);
+                self.starts.push(start as u64);
+                self.ends.push(end as u64);
+            }
+        }
+        self.docs += texts.len();
+    }
+
+    /// Export the chunks as one Arrow array laid out as `layout`.
+    pub fn to_arrow(&self, layout: ArrowLayout) -> (ArrowSchema, ArrowArray) {
+        let column =
+            |values: &[u64]| export_array(values.len(), vec![None, Some(values.to_vec())], vec![]);
+        let array = match layout {
+            ArrowLayout::Lists => {
+                let chunks = export_array(
+                    self.len(),
+                    vec![None],
+                    vec![column(&self.starts), column(&self.ends)],
+                );
+                // Document d's chunks start at the first chunk with doc_id >= d
+                let offsets = (0..=self.docs as u64)
+                    .map(|doc| self.doc_ids.partition_point(|&id| id < doc) as u64)
+                    .collect();
+                export_array(self.docs, vec![None, Some(offsets)], vec![chunks])
+            }
+            ArrowLayout::Exploded => export_array(
+                self.len(),
+                vec![None],
+                vec![
+                    column(&self.doc_ids),
+                    column(&self.starts),
+                    column(&self.ends),
+                ],
+            ),
+        };
+        (export_schema(layout), array)
+    }
+
+    /// Export the chunks as an Arrow stream of one array laid out as
+    /// `layout`.
+    pub fn to_arrow_stream(&self, layout: ArrowLayout) -> ArrowArrayStream {
+        let (_, array) = self.to_arrow(layout);
+        let data = Box::new(StreamData {
+            layout,
+            array: Some(array),
+        });
+        ArrowArrayStream {
+            get_schema: Some(stream_get_schema),
+            get_next: Some(stream_get_next),
+            get_last_error: Some(stream_get_last_error),
+            release: Some(release_stream),
+            private_data: Box::into_raw(data).cast(),
+        }
+    }
+}
+
+/// Buffers and children owned by an exported array.
+struct ArrayData {
+    /// Kept alive for `pointers`; u64 words keep every buffer 8-byte
+    /// aligned.
+    _buffers: Vec<Option<Vec<u64>>>,
+    pointers: Vec<*const c_void>,
+    children: Vec<*mut ArrowArray>,
+}
+
+/// Export an array of `length` rows owning `buffers` (None for an absent
+/// validity bitmap) and `children`.
+fn export_array(
+    length: usize,
+    buffers: Vec<Option<Vec<u64>>>,
+    children: Vec<ArrowArray>,
+) -> ArrowArray {
+    let pointers = buffers
+        .iter()
+        .map(|buffer| buffer.as_ref().map_or(ptr::null(), |b| b.as_ptr().cast()))
+        .collect();
+    let children = children
+        .into_iter()
+        .map(|child| Box::into_raw(Box::new(child)))
+        .collect();
+    let mut data = Box::new(ArrayData {
+        _buffers: buffers,
+        pointers,
+        children,
+    });
+    ArrowArray {
+        length: length as i64,
+        null_count: 0,
+        offset: 0,
+        n_buffers: data.pointers.len() as i64,
+        n_children: data.children.len() as i64,
+        buffers: data.pointers.as_mut_ptr(),
+        children: data.children.as_mut_ptr(),
+        dictionary: ptr::null_mut(),
+        release: Some(release_array),
+        private_data: Box::into_raw(data).cast(),
+    }
+}
+
+unsafe extern "C" fn release_array(array: *mut ArrowArray) {
+    // SAFETY: set only by export_array, whose private data is an ArrayData
+    unsafe {
+        let data = Box::from_raw((*array).private_data.cast::<ArrayData>());
+        for &child in &data.children {
+            drop(Box::from_raw(child));
+        }
+        (*array).release = None;
+    }
+}
+
+/// The exported schema of `layout`.
+fn export_schema(layout: ArrowLayout) -> ArrowSchema {
+    let uint64 = |name: &'static CStr| schema(c"L", name, vec![]);
+    match layout {
+        ArrowLayout::Lists => {
+            let chunk = schema(c"+s", c"item", vec![uint64(c"start"), uint64(c"end")]);
+            schema(c"+L", c"", vec![chunk])
+        }
+        ArrowLayout::Exploded => schema(
+            c"+s",
+            c"",
+            vec![uint64(c"doc_id"), uint64(c"start"), uint64(c"end")],
+        ),
+    }
+}
+
+/// A non-nullable field of type `format` named `name`.
+fn schema(format: &'static CStr, name: &'static CStr, children: Vec<ArrowSchema>) -> ArrowSchema {
+    let mut children: Box<Vec<*mut ArrowSchema>> = Box::new(
+        children
+            .into_iter()
+            .map(|child| Box::into_raw(Box::new(child)))
+            .collect(),
+    );
+    ArrowSchema {
+        format: format.as_ptr(),
+        name: name.as_ptr(),
+        metadata: ptr::null(),
+        flags: 0,
+        n_children: children.len() as i64,
+        children: children.as_mut_ptr(),
+        dictionary: ptr::null_mut(),
+        release: Some(release_schema),
+        private_data: Box::into_raw(children).cast(),
+    }
+}
+
+unsafe extern "C" fn release_schema(schema: *mut ArrowSchema) {
+    // SAFETY: set only by schema(), whose private data is its children
+    unsafe {
+        let children = Box::from_raw((*schema).private_data.cast::<Vec<*mut ArrowSchema>>());
+        for &child in children.iter() {
+            drop(Box::from_raw(child));
+        }
+        (*schema).release = None;
+    }
+}
+
+/// State of a stream from [`ChunkTable::to_arrow_stream`].
+struct StreamData {
+    layout: ArrowLayout,
+    array: Option<ArrowArray>,
+}
+
+unsafe extern "C" fn stream_get_schema(
+    stream: *mut ArrowArrayStream,
+    out: *mut ArrowSchema,
+) -> c_int {
+    // SAFETY: set only by to_arrow_stream, whose private data is a
+    // StreamData, and `out` is writable
+    unsafe {
+        let data = &*(*stream).private_data.cast::<StreamData>();
+        ptr::write(out, export_schema(data.layout));
+    }
+    0
+}
+
+unsafe extern "C" fn stream_get_next(stream: *mut ArrowArrayStream, out: *mut ArrowArray) -> c_int {
+    // SAFETY: as above
+    unsafe {
+        let data = &mut *(*stream).private_data.cast::<StreamData>();
+        ptr::write(out, data.array.take().unwrap_or_else(ArrowArray::empty));
+    }
+    0
+}
+
+unsafe extern "C" fn stream_get_last_error(_: *mut ArrowArrayStream) -> *const c_char {
+    ptr::null()
+}
+
+unsafe extern "C" fn release_stream(stream: *mut ArrowArrayStream) {
+    // SAFETY: as above
+    unsafe {
+        drop(Box::from_raw((*stream).private_data.cast::<StreamData>()));
+        (*stream).release = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pack bytes into native-endian u64 words, as export_array wants.
+    fn words(bytes: &[u8]) -> Vec<u64> {
+        bytes
+            .chunks(8)
+            .map(|chunk| {
+                let mut word = [0; 8];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u64::from_ne_bytes(word)
+            })
+            .collect()
+    }
+
+    /// A `large_utf8` array of `values`.
+    fn large_utf8(values: &[Option<&str>]) -> (ArrowSchema, ArrowArray) {
+        let mut offsets = vec![0u64];
+        let mut data = Vec::new();
+        let mut validity = vec![0u8; values.len().div_ceil(8)];
+        for (i, value) in values.iter().enumerate() {
+            if let Some(value) = value {
+                data.extend_from_slice(value.as_bytes());
+                validity[i / 8] |= 1 << (i % 8);
+            }
+            offsets.push(data.len() as u64);
+        }
+        let mut array = export_array(
+            values.len(),
+            vec![Some(words(&validity)), Some(offsets), Some(words(&data))],
+            vec![],
+        );
+        array.null_count = values.iter().filter(|v| v.is_none()).count() as i64;
+        (schema(c"U", c"", vec![]), array)
+    }
+
+    /// Buffer `index` of `array` as `len` u64 values.
+    fn u64s(array: &ArrowArray, index: usize, len: usize) -> &[u64] {
+        unsafe { buffer_slice(array, index, len) }.unwrap()
+    }
+
+    /// Child `index` of `array`.
+    fn child(array: &ArrowArray, index: usize) -> &ArrowArray {
+        assert!(index < array.n_children as usize);
+        unsafe { &**array.children.add(index) }
+    }
+
+    #[test]
+    fn test_text_column() {
+        let (schema, mut array) = large_utf8(&[Some("skip"), Some("a b"), None, Some("")]);
+        array.offset = 1;
+        array.length = 3;
+        let column = TextColumn::new(&array, &schema).unwrap();
+        assert!(column.is_utf8());
+        let rows: Vec<_> = column.iter().collect();
+        assert_eq!(rows, vec![Some(&b"a b"[..]), None, Some(&b""[..])]);
+
+        let (_, table) = ChunkTable::new().to_arrow(ArrowLayout::Exploded);
+        let struct_schema = export_schema(ArrowLayout::Exploded);
+        assert_eq!(
+            TextColumn::new(&table, &struct_schema).unwrap_err(),
+            ArrowError::UnsupportedType("+s".into())
+        );
+        let released = ArrowArray::empty();
+        assert_eq!(
+            TextColumn::new(&released, &schema).unwrap_err(),
+            ArrowError::Released
+        );
+    }
+
+    #[test]
+    fn test_view_column() {
+        let long = b"a string over twelve bytes";
+        let mut inline = [0u8; 16];
+        inline[..4].copy_from_slice(&5u32.to_ne_bytes());
+        inline[4..9].copy_from_slice(b"short");
+        let mut outline = [0u8; 16];
+        outline[..4].copy_from_slice(&(long.len() as u32 - 2).to_ne_bytes());
+        outline[4..8].copy_from_slice(&long[2..6]);
+        outline[12..].copy_from_slice(&2u32.to_ne_bytes());
+        let views = [inline, outline].concat();
+        let array = export_array(
+            2,
+            vec![
+                None,
+                Some(words(&views)),
+                Some(words(long)),
+                Some(vec![long.len() as u64]),
+            ],
+            vec![],
+        );
+        let schema = schema(c"vz", c"", vec![]);
+        let column = TextColumn::new(&array, &schema).unwrap();
+        assert!(!column.is_utf8());
+        assert_eq!(column.get(0), Some(&b"short"[..]));
+        assert_eq!(column.get(1), Some(&long[2..]));
+    }
+
+    #[test]
+    fn test_chunk_table_layouts() {
+        let (schema, array) = large_utf8(&[Some("One. Two."), None, Some("Three.")]);
+        let column = TextColumn::new(&array, &schema).unwrap();
+        let mut table = ChunkTable::new();
+        table.push_column(&column, |c| c.size(6).delimiters(b"."));
+        table.push_column(&column, |c| c.size(6).delimiters(b"."));
+        assert_eq!(table.docs, 6);
+        assert_eq!(table.doc_ids, vec![0, 0, 2, 3, 3, 5]);
+        assert_eq!(table.starts, vec![0, 4, 0, 0, 4, 0]);
+        assert_eq!(table.ends, vec![4, 9, 6, 4, 9, 6]);
+
+        let (schema, lists) = table.to_arrow(ArrowLayout::Lists);
+        assert_eq!(schema.format(), Some("+L"));
+        assert_eq!(lists.len(), 6);
+        assert_eq!(u64s(&lists, 1, 7), &[0, 2, 2, 3, 5, 5, 6]);
+        let chunks = child(&lists, 0);
+        assert_eq!(u64s(child(chunks, 1), 1, 6), &table.ends[..]);
+
+        let (schema, exploded) = table.to_arrow(ArrowLayout::Exploded);
+        assert_eq!(schema.format(), Some("+s"));
+        let name = unsafe { CStr::from_ptr((**schema.children).name) };
+        assert_eq!(name, c"doc_id");
+        assert_eq!(u64s(child(&exploded, 0), 1, 6), &table.doc_ids[..]);
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut table = ChunkTable::new();
+        table.doc_ids.push(0);
+        table.starts.push(0);
+        table.ends.push(3);
+        table.docs = 1;
+        let mut exported = table.to_arrow_stream(ArrowLayout::Exploded);
+        let mut stream = unsafe { ArrowArrayStream::take(&mut exported) };
+        assert!(exported.is_released());
+        assert_eq!(stream.schema().unwrap().format(), Some("+s"));
+        assert_eq!(stream.next_array().unwrap().map(|a| a.len()), Some(1));
+        assert!(stream.next_array().unwrap().is_none());
+        drop(stream);
+        assert_eq!(
+            ArrowArrayStream::empty().next_array().unwrap_err(),
+            ArrowError::Released
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Parallel chunking of many documents (requires the `rayon` feature).
+//!
+//! This module provides [`chunk_batch`], which chunks each document on the
+//! rayon thread pool with the same [`Chunker`] configuration, and
+//! [`chunk_batch_until`], which can be stopped part way with a
+//! [`CancelToken`].
+
+use rayon::prelude::*;
+
+use crate::cancel::{CancelToken, Cancelled};
+use crate::chunk::{Chunker, chunk};
+
+/// Chunk many documents in parallel, returning each one's (start, end) offsets.
+///
+/// `config` sets up the [`Chunker`] for a single document and is called once
+/// per document, so any builder option works. Results are in input order.
+///
+/// # Example
+///
+/// ```
+/// use chunk::chunk_batch;
+///
+/// let docs: &[&[u8]] = &[b"Hello. World.", b"One. Two. Three."];
+/// let offsets = chunk_batch(docs, |c| c.size(8).delimiters(b"."));
+/// assert_eq!(offsets[0], vec![(0, 6), (6, 13)]);
+/// assert_eq!(offsets[1], vec![(0, 4), (4, 9), (9, 16)]);
+/// ```
+pub fn chunk_batch<'a, F>(texts: &[&'a [u8]], config: F) -> Vec<Vec<(usize, usize)>>
+where
+    F: Fn(Chunker<'a>) -> Chunker<'a> + Sync,
+{
+    texts
+        .par_iter()
+        .map(|text| config(chunk(text)).collect_offsets())
+        .collect()
+}
+
+/// Chunk many documents in parallel like [`chunk_batch`], until `token` is
+/// cancelled.
+///
+/// Every worker checks the token after each chunk, so cancelling stops the
+/// whole batch promptly, even in the middle of a huge document, and the
+/// offsets found so far are dropped.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{CancelToken, chunk_batch_until};
+///
+/// let docs: &[&[u8]] = &[b"Hello. World.", b"One. Two. Three."];
+/// let token = CancelToken::new();
+/// // e.g. from a signal handler or another thread
+/// let stop = token.clone();
+/// stop.cancel();
+/// assert!(chunk_batch_until(docs, |c| c.size(8), &token).is_err());
+/// ```
+pub fn chunk_batch_until<'a, F>(
+    texts: &[&'a [u8]],
+    config: F,
+    token: &CancelToken,
+) -> Result<Vec<Vec<(usize, usize)>>, Cancelled>
+where
+    F: Fn(Chunker<'a>) -> Chunker<'a> + Sync,
+{
+    texts
+        .par_iter()
+        .map(|text| config(chunk(text)).collect_offsets_until(token))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_matches_sequential() {
+        let docs: Vec<Vec<u8>> = (0..64)
+            .map(|i| "Some sentence. ".repeat(i).into_bytes())
+            .collect();
+        let texts: Vec<&[u8]> = docs.iter().map(|d| d.as_slice()).collect();
+        let delimiters = b".".to_vec();
+
+        let batch = chunk_batch(&texts, |c| c.size(40).delimiters(&delimiters).overlap(5));
+        assert_eq!(batch.len(), texts.len());
+        for (text, offsets) in texts.iter().zip(&batch) {
+            let expected = chunk(text)
+                .size(40)
+                .delimiters(&delimiters)
+                .overlap(5)
+                .collect_offsets();
+            assert_eq!(offsets, &expected);
+        }
+    }
+
+    #[test]
+    fn test_batch_until() {
+        let docs: Vec<Vec<u8>> = (0..16)
+            .map(|i| "Some sentence. ".repeat(i * 10).into_bytes())
+            .collect();
+        let texts: Vec<&[u8]> = docs.iter().map(|d| d.as_slice()).collect();
+        let token = CancelToken::new();
+        assert_eq!(
+            chunk_batch_until(&texts, |c| c.size(40), &token),
+            Ok(chunk_batch(&texts, |c| c.size(40)))
+        );
+
+        // Cancelled part way through, from inside the run
+        let seen = std::sync::atomic::AtomicUsize::new(0);
+        let result = chunk_batch_until(
+            &texts,
+            |c| {
+                if seen.fetch_add(1, std::sync::atomic::Ordering::Relaxed) == 3 {
+                    token.cancel();
+                }
+                c.size(40)
+            },
+            &token,
+        );
+        assert_eq!(result, Err(Cancelled));
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn test_batch_empty() {
+        assert!(chunk_batch(&[], |c| c).is_empty());
+        assert_eq!(chunk_batch(&[b""], |c| c), vec![vec![]]);
+    }
+}
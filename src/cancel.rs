@@ -0,0 +1,68 @@
+//! Cooperative cancellation of long chunking runs.
+//!
+//! This module provides [`CancelToken`], a flag shared between the code
+//! running a batch or corpus and whoever may want to stop it (a signal
+//! handler, a UI, a timeout), which the chunkers check after every chunk.
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A shared cancellation flag; clones share it.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{CancelToken, chunk};
+///
+/// let token = CancelToken::new();
+/// let text = b"Hello. World. Test.";
+/// assert!(chunk(text).size(10).collect_offsets_until(&token).is_ok());
+/// token.clone().cancel();
+/// assert!(chunk(text).size(10).collect_offsets_until(&token).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// A token that hasn't been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask everything holding the token to stop, from any thread.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`cancel`](CancelToken::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// `Err(Cancelled)` once cancelled.
+    pub(crate) fn check(&self) -> Result<(), Cancelled> {
+        match self.is_cancelled() {
+            true => Err(Cancelled),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Error returned when a [`CancelToken`] stopped the chunking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "chunking was cancelled")
+    }
+}
+
+impl core::error::Error for Cancelled {}
+
+impl From<Cancelled> for std::io::Error {
+    fn from(cancelled: Cancelled) -> Self {
+        std::io::Error::new(std::io::ErrorKind::Interrupted, cancelled)
+    }
+}
@@ -0,0 +1,170 @@
+//! Checkpoints for resuming long chunking runs.
+//!
+//! This module provides [`Checkpoint`], a few numbers saved from a
+//! [`StreamChunker`](crate::StreamChunker), a
+//! [`ReaderChunker`](crate::ReaderChunker) or a `chunk_dir` walk with
+//! `save_state`, from which a new one picks up with `restore_state` after a
+//! crash instead of chunking everything again, and [`CheckpointMismatch`],
+//! the error when the settings have changed in between.
+
+use core::fmt;
+
+/// Where a chunking run got to.
+///
+/// With the `serde` feature the checkpoint is `Serialize` and `Deserialize`,
+/// so it can be written next to the chunks it covers.
+///
+/// # Example
+///
+/// ```
+/// use chunk::StreamChunker;
+///
+/// let text = b"One. Two. Six. Ten.";
+/// let settings = || StreamChunker::new().size(5).delimiters(b".".to_vec());
+/// let mut stream = settings();
+/// let before = stream.push(&text[..12]);
+/// let checkpoint = stream.save_state();
+///
+/// // After a crash: feed the input again from `resume_from`
+/// let mut stream = settings().restore_state(&checkpoint)?;
+/// let mut after = stream.push(&text[checkpoint.resume_from as usize..]);
+/// after.extend(stream.finish());
+/// assert_eq!([before, after].concat(), vec![&b"One."[..], b" Two.", b" Six.", b" Ten."]);
+/// # Ok::<(), chunk::CheckpointMismatch>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint {
+    /// Where the next chunk's boundary search starts: a byte offset into the
+    /// input, or for a directory walk, how many files are done.
+    pub position: u64,
+    /// Where input has to be fed from again on resuming, at or before
+    /// `position`: the bytes in between are context (e.g. overlap) for the
+    /// next chunk. The same as `position` for a directory walk.
+    pub resume_from: u64,
+    /// Chunks emitted before the checkpoint, e.g. to carry on numbering them.
+    pub chunks: u64,
+    /// Checksum of the settings the checkpoint was saved with (and for a
+    /// directory walk, of the files found).
+    pub config: u64,
+}
+
+/// Error restoring a [`Checkpoint`] saved with different settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckpointMismatch {
+    /// The checksum in the checkpoint.
+    pub checkpoint: u64,
+    /// The checksum of the chunker restoring it.
+    pub chunker: u64,
+}
+
+impl fmt::Display for CheckpointMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "checkpoint was saved with different settings (checksum {:016x}, expected {:016x})",
+            self.checkpoint, self.chunker
+        )
+    }
+}
+
+impl core::error::Error for CheckpointMismatch {}
+
+impl Checkpoint {
+    /// `Err` unless the checkpoint was saved with settings checksumming to
+    /// `config`.
+    pub(crate) fn check(&self, config: u64) -> Result<(), CheckpointMismatch> {
+        match self.config == config {
+            true => Ok(()),
+            false => Err(CheckpointMismatch {
+                checkpoint: self.config,
+                chunker: config,
+            }),
+        }
+    }
+}
+
+/// A stable checksum of chunker settings (64-bit FNV-1a), the same on every
+/// platform and run.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Checksum(u64);
+
+impl Checksum {
+    pub(crate) fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(mut self, bytes: &[u8]) -> Self {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+        self
+    }
+
+    /// Add a number (or a flag).
+    pub(crate) fn number(self, n: impl Into<u64>) -> Self {
+        self.write(&n.into().to_le_bytes())
+    }
+
+    /// Add a size.
+    pub(crate) fn size(self, n: usize) -> Self {
+        self.number(n as u64)
+    }
+
+    /// Add an optional size.
+    pub(crate) fn option(self, n: Option<usize>) -> Self {
+        match n {
+            Some(n) => self.number(true).size(n),
+            None => self.number(false),
+        }
+    }
+
+    /// Add a byte string, length first so adjacent strings can't run
+    /// together.
+    pub(crate) fn bytes(self, bytes: &[u8]) -> Self {
+        self.size(bytes.len()).write(bytes)
+    }
+
+    pub(crate) fn finish(self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_stable() {
+        // Pinned value: checkpoints must keep restoring across releases
+        assert_eq!(
+            Checksum::new().bytes(b"chunk").finish(),
+            0xf8b7_68bd_4e35_be5d
+        );
+        let a = Checksum::new().bytes(b"ab").bytes(b"c").finish();
+        let b = Checksum::new().bytes(b"a").bytes(b"bc").finish();
+        assert_ne!(a, b);
+        assert_ne!(
+            Checksum::new().option(None).finish(),
+            Checksum::new().option(Some(0)).finish()
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let checkpoint = Checkpoint {
+            config: 1,
+            ..Checkpoint::default()
+        };
+        assert!(checkpoint.check(1).is_ok());
+        let err = checkpoint.check(2).unwrap_err();
+        assert_eq!(
+            err,
+            CheckpointMismatch {
+                checkpoint: 1,
+                chunker: 2
+            }
+        );
+        assert!(err.to_string().contains("different settings"));
+    }
+}
@@ -3,18 +3,36 @@
 //! This module provides the [`Chunker`] and [`OwnedChunker`] types for splitting
 //! text into chunks of a target size, preferring to break at delimiter boundaries.
 
+use alloc::borrow::Cow;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Bound, RangeBounds};
+
+#[cfg(feature = "std")]
+use crate::cancel::{CancelToken, Cancelled};
+use crate::config::ChunkerConfig;
 use crate::delim::{
-    DEFAULT_DELIMITERS, DEFAULT_TARGET_SIZE, MultiPatternSearcher, build_table, compute_split_at,
-    compute_split_at_combined,
+    Boundaries, DEFAULT_DELIMITERS, DEFAULT_TARGET_SIZE, MultiPatternSearcher, build_table,
+    compute_split_at, compute_split_at_combined, find_priority_split, utf8_next_boundary,
+    utf8_prev_boundary, utf8_split_boundary,
 };
+use crate::delimiters::Delimiters;
+use crate::metric::{SizeMetric, SizeUnit};
+use crate::parent::ParentChildChunks;
+use crate::position::Positioned;
+use crate::progress::{Progress, WithProgress};
+use crate::score::BoundaryScorer;
+use crate::stats::ChunkStats;
 
 /// Chunk text at delimiter boundaries.
 ///
 /// Returns a builder that can be configured with `.size()` and `.delimiters()`,
 /// or used directly as an iterator with defaults (4KB chunks, `\n.?` delimiters).
 ///
-/// - For 1-3 delimiters: uses SIMD-accelerated memchr
-/// - For 4+ delimiters: uses lookup table
+/// - For 1-6 delimiters: uses SIMD-accelerated memchr
+/// - For 7+ delimiters: uses lookup table
 ///
 /// # Example
 ///
@@ -39,649 +57,3650 @@ pub fn chunk(text: &[u8]) -> Chunker<'_> {
     Chunker::new(text)
 }
 
-/// Chunker splits text at delimiter boundaries.
+/// A chunk with its position in the text.
 ///
-/// Created via [`chunk()`], can be configured with `.size()` and `.delimiters()`.
-/// For multi-byte delimiters, use `.pattern()` instead.
-pub struct Chunker<'a> {
-    text: &'a [u8],
+/// Returned by [`Chunker::next_chunk_info`], [`Chunker::iter_chunks`] and their
+/// [`OwnedChunker`] counterparts.
+///
+/// With the `serde` feature a chunk serializes as `index`, `start`, `end` and
+/// `text` (a string when valid UTF-8). It borrows from the input, so it isn't
+/// `Deserialize`; store the offsets and slice the text again instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Chunk<'a> {
+    /// Zero-based position of the chunk in the sequence.
+    pub index: usize,
+    /// Byte offset where the chunk starts.
+    pub start: usize,
+    /// Byte offset where the chunk ends (exclusive).
+    pub end: usize,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "text", serialize_with = "crate::serde_bytes::serialize")
+    )]
+    bytes: &'a [u8],
+}
+
+impl<'a> Chunk<'a> {
+    /// The chunk bytes, borrowed from the input.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// The chunk as a string, if it is valid UTF-8.
+    ///
+    /// Enable [`utf8_safe`](Chunker::utf8_safe) on UTF-8 input so that every
+    /// chunk decodes.
+    pub fn text(&self) -> Result<&'a str, core::str::Utf8Error> {
+        core::str::from_utf8(self.bytes)
+    }
+
+    /// Stable content hash of the chunk bytes, see [`chunk_hash`](crate::chunk_hash).
+    #[cfg(feature = "hash")]
+    pub fn hash(&self) -> u64 {
+        crate::hash::chunk_hash(self.bytes)
+    }
+
+    /// ISO 639-3 code of the chunk's language, see
+    /// [`detect_language`](crate::detect_language).
+    #[cfg(feature = "lang-detect")]
+    pub fn language(&self) -> Option<&'static str> {
+        crate::lang::detect_language(self.bytes)
+    }
+
+    /// Chunk length in bytes.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether the chunk is empty (never true for emitted chunks).
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Where the delimiter a chunk is split at ends up.
+///
+/// Set with [`Chunker::delimiter_mode`]; [`prefix`](Chunker::prefix) and
+/// [`suffix`](Chunker::suffix) are shorthands for the two keep modes.
+///
+/// ```
+/// use chunk::{DelimiterMode, chunk};
+/// let text = b"One. Two. Three.";
+/// let chunks = |mode| {
+///     chunk(text).size(8).delimiters(b".").delimiter_mode(mode).collect::<Vec<_>>()
+/// };
+/// assert_eq!(chunks(DelimiterMode::KeepTrailing), vec![&b"One."[..], b" Two.", b" Three."]);
+/// assert_eq!(chunks(DelimiterMode::KeepLeading), vec![&b"One"[..], b". Two", b". Three."]);
+/// assert_eq!(chunks(DelimiterMode::Drop), vec![&b"One"[..], b" Two", b" Three"]);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum DelimiterMode {
+    /// The delimiter ends the chunk before the split (default).
+    #[default]
+    KeepTrailing,
+    /// The delimiter starts the chunk after the split.
+    KeepLeading,
+    /// The delimiter is in neither chunk.
+    Drop,
+}
+
+/// What to do when a chunk's window has no delimiter to split at.
+///
+/// Set with [`Chunker::fallback`]. The split is then forced somewhere inside
+/// the window; [`forward_fallback`](Chunker::forward_fallback), when on, is
+/// tried first.
+///
+/// ```
+/// use chunk::{FallbackStrategy, chunk};
+/// let text = "Um café quente".as_bytes();
+/// let chunks = |strategy| {
+///     chunk(text).size(7).delimiters(b".").fallback(strategy).collect::<Vec<_>>()
+/// };
+/// assert_eq!(chunks(FallbackStrategy::Hard)[0], b"Um caf\xc3");
+/// assert_eq!(chunks(FallbackStrategy::Whitespace)[0], b"Um ");
+/// assert_eq!(chunks(FallbackStrategy::Utf8Boundary)[0], b"Um caf");
+///
+/// let mut chunker = chunk(text).size(7).delimiters(b".").fallback(FallbackStrategy::Error);
+/// assert!(chunker.try_next_chunk().unwrap().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum FallbackStrategy {
+    /// Split at the end of the window (default).
+    #[default]
+    Hard,
+    /// Split after the last whitespace in the window, or at its end if it
+    /// has none.
+    Whitespace,
+    /// Split at the start of the character the window ends in, so a
+    /// multi-byte character isn't cut in two, as `utf8_safe` does for every
+    /// split.
+    Utf8Boundary,
+    /// Stop with [`ChunkError::NoBoundary`], see
+    /// [`Chunker::try_next_chunk`].
+    Error,
+    /// Split at the last word boundary in the window, or at its end if it
+    /// has none. Words in scripts written without spaces (Thai, Lao, Khmer,
+    /// Burmese, Chinese, Japanese) are found with a dictionary, so a forced
+    /// split doesn't cut one in half.
+    #[cfg(feature = "segmenter")]
+    WordBoundary,
+}
+
+/// Invalid chunker settings, from the validating [`Chunker::build`],
+/// [`OwnedChunker::build`], [`OwnedChunker::try_new`] and
+/// [`ChunkerConfig::validate`], or text those settings can't chunk, from
+/// [`Chunker::try_next_chunk`] and friends.
+///
+/// Without validation these settings still chunk: a size of 0 is taken as 1,
+/// an overlap can reach back to the start of the previous chunk, and an empty
+/// delimiter set without patterns gives fixed-size chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkError {
+    /// The target size is 0.
+    ZeroSize,
+    /// The overlap in bytes is at least the target size, so a chunk can
+    /// repeat most of the previous one.
+    ///
+    /// Every size unit is at least a byte, so the size is compared as a
+    /// number of bytes whatever the unit: with [`SizeUnit::Chars`] or tokens
+    /// a chunk of single-byte units would otherwise be all overlap.
+    OverlapTooLarge {
+        /// The overlap, in bytes.
+        overlap: usize,
+        /// The target size, in its unit.
+        size: usize,
+    },
+    /// The overlap ratio isn't at least 0 and less than 1.
+    InvalidOverlapRatio,
+    /// There are no delimiters or patterns to split at (and no lines per
+    /// chunk), so every split would be a fallback.
+    NoDelimiters,
+    /// A window had no delimiter to split at, with
+    /// [`FallbackStrategy::Error`].
+    NoBoundary {
+        /// Where the chunk that couldn't be split starts its boundary search
+        /// (the end of the previous chunk), as a byte offset into the text.
+        position: usize,
+    },
+}
+
+impl fmt::Display for ChunkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChunkError::ZeroSize => write!(f, "size must be at least 1"),
+            ChunkError::OverlapTooLarge { overlap, size } => {
+                write!(f, "overlap ({overlap}) must be smaller than size ({size})")
+            }
+            ChunkError::InvalidOverlapRatio => {
+                write!(f, "overlap ratio must be at least 0 and less than 1")
+            }
+            ChunkError::NoDelimiters => write!(f, "no delimiters or patterns to split at"),
+            ChunkError::NoBoundary { position } => {
+                write!(
+                    f,
+                    "no delimiter within the size limit after byte {position}"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for ChunkError {}
+
+/// Check the settings [`ChunkError`] describes. `splits` is whether there's
+/// anything besides the fallback to split at.
+pub(crate) fn validate(
+    size: usize,
+    overlap: usize,
+    overlap_ratio: Option<f64>,
+    splits: bool,
+) -> Result<(), ChunkError> {
+    if size == 0 {
+        return Err(ChunkError::ZeroSize);
+    }
+    if overlap_ratio.is_some_and(|ratio| !(0.0..1.0).contains(&ratio)) {
+        return Err(ChunkError::InvalidOverlapRatio);
+    }
+    if overlap >= size {
+        return Err(ChunkError::OverlapTooLarge { overlap, size });
+    }
+    if !splits {
+        return Err(ChunkError::NoDelimiters);
+    }
+    Ok(())
+}
+
+/// Bytes of overlap for `ratio` of a target `size`: rounded to the nearest
+/// byte (so 0.3 of 10 is 3, not 2.999...), and kept smaller than the size so
+/// that chunks always move forward.
+pub(crate) fn ratio_overlap(ratio: f64, size: usize) -> usize {
+    // The cast saturates, taking NaN and negative ratios to 0
+    ((ratio * size as f64 + 0.5) as usize).min(size.saturating_sub(1))
+}
+
+/// Where to force the split of the chunk at `pos` whose window ends at
+/// `split_at` without a delimiter.
+fn fall_back(
+    text: &[u8],
+    pos: usize,
+    split_at: usize,
+    strategy: FallbackStrategy,
+) -> Result<usize, ChunkError> {
+    match strategy {
+        FallbackStrategy::Hard => Ok(split_at),
+        // Whitespace the chunk starts with would leave nothing before it
+        FallbackStrategy::Whitespace => Ok(text[pos + 1..split_at]
+            .iter()
+            .rposition(u8::is_ascii_whitespace)
+            .map_or(split_at, |at| pos + 1 + at + 1)),
+        FallbackStrategy::Utf8Boundary => Ok(utf8_split_boundary(text, pos, split_at)),
+        FallbackStrategy::Error => Err(ChunkError::NoBoundary { position: pos }),
+        #[cfg(feature = "segmenter")]
+        FallbackStrategy::WordBoundary => Ok(word_boundary(text, pos, split_at)),
+    }
+}
+
+/// The last word boundary after `pos` and at or before `split_at`, or
+/// `split_at` if there is none.
+#[cfg(feature = "segmenter")]
+fn word_boundary(text: &[u8], pos: usize, split_at: usize) -> usize {
+    use icu_segmenter::WordSegmenter;
+    use icu_segmenter::options::WordBreakInvariantOptions;
+
+    // Enough of what follows to finish the word the window ends in, so its
+    // end isn't taken for a boundary
+    const LOOKAHEAD: usize = 64;
+    let end = (split_at + LOOKAHEAD).min(text.len());
+    WordSegmenter::new_dictionary(WordBreakInvariantOptions::default())
+        .segment_utf8(&text[pos..end])
+        .map(|at| pos + at)
+        .take_while(|&at| at <= split_at)
+        .filter(|&at| at > pos)
+        .last()
+        .unwrap_or(split_at)
+}
+
+/// Chunk offsets computed on demand, for random access.
+///
+/// Offsets are appended in order as far as a lookup needs, so jumping near
+/// the start of a large text never scans the rest of it.
+#[derive(Debug, Clone, Default)]
+struct OffsetCache {
+    offsets: Vec<(usize, usize)>,
+    /// Iterator state (pos, prev_pos) each cached chunk was computed from
+    states: Vec<(usize, usize)>,
+    /// Boundary-search start of the next chunk to compute
+    pos: usize,
+    /// Boundary-search start of the last computed chunk
+    prev_pos: usize,
+}
+
+impl OffsetCache {
+    /// An empty cache whose first chunk's boundary search begins at `pos`.
+    fn starting_at(pos: usize) -> Self {
+        Self {
+            pos,
+            prev_pos: pos,
+            ..Self::default()
+        }
+    }
+
+    /// Compute offsets with `next` until `done` holds or `len` is reached.
+    ///
+    /// `next(pos, prev_pos)` returns the (start, end) of the chunk whose
+    /// boundary search begins at `pos`, or `None` where chunking stops early;
+    /// chunks failing `keep` aren't cached.
+    fn fill(
+        &mut self,
+        len: usize,
+        mut next: impl FnMut(usize, usize) -> Option<(usize, usize)>,
+        keep: impl Fn(usize, usize) -> bool,
+        done: impl Fn(&[(usize, usize)]) -> bool,
+    ) {
+        while self.pos < len && !done(&self.offsets) {
+            let Some((start, end)) = next(self.pos, self.prev_pos) else {
+                break;
+            };
+            if keep(start, end) {
+                self.offsets.push((start, end));
+                self.states.push((self.pos, self.prev_pos));
+            }
+            self.prev_pos = self.pos;
+            self.pos = end;
+        }
+    }
+
+    /// Index of the first chunk ending after `offset` (the first containing it).
+    fn find(&self, offset: usize) -> Option<usize> {
+        let i = self.offsets.partition_point(|&(_, end)| end <= offset);
+        (i < self.offsets.len()).then_some(i)
+    }
+
+    /// Iterator state (pos, prev_pos) just before chunk `index` is emitted.
+    fn position(&self, index: usize) -> (usize, usize) {
+        self.states[index]
+    }
+}
+
+/// The (start, end) of `range` within a text of `len` bytes.
+fn bounds(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.saturating_add(1),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    }
+    .min(len);
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    (start.min(end), end)
+}
+
+/// Start of the chunk whose boundary search began at `pos`, moved back by
+/// `overlap` bytes but never before `prev_pos` (the previous chunk's start).
+#[inline]
+pub(crate) fn overlap_start(pos: usize, prev_pos: usize, overlap: usize) -> usize {
+    pos.saturating_sub(overlap).max(prev_pos)
+}
+
+/// End of the chunk starting at `pos` that holds up to `lines` complete
+/// lines and, unless the first line alone is longer, ends by `end`.
+fn line_split(text: &[u8], pos: usize, end: usize, lines: usize) -> usize {
+    let mut split_at = pos;
+    for _ in 0..lines {
+        let next =
+            memchr::memchr(b'\n', &text[split_at..]).map_or(text.len(), |i| split_at + i + 1);
+        if next > end && split_at > pos {
+            break;
+        }
+        split_at = next;
+        if split_at == text.len() {
+            break;
+        }
+    }
+    split_at
+}
+
+/// Offsets of `start..split_at` with `removed` delimiter bytes cut from the
+/// end and, if `trim`, surrounding ASCII whitespace, unless that would leave
+/// nothing.
+#[inline]
+fn span(text: &[u8], start: usize, split_at: usize, removed: usize, trim: bool) -> (usize, usize) {
+    let end = match split_at - removed {
+        end if end > start => end,
+        _ => split_at,
+    };
+    if !trim {
+        return (start, end);
+    }
+    let chunk = &text[start..end];
+    let trimmed = chunk.trim_ascii();
+    if trimmed.is_empty() {
+        return (start, end);
+    }
+    let lead = chunk.len() - chunk.trim_ascii_start().len();
+    (start + lead, start + lead + trimmed.len())
+}
+
+/// Settings of a [`Chunker`] or [`OwnedChunker`], with the split logic both
+/// run over the text they chunk.
+///
+/// `M` and `S` are the size metric and boundary scorer trait objects: `Send`
+/// and `Sync` for an [`OwnedChunker`], anything for a [`Chunker`].
+struct Settings<'a, M: ?Sized, S: ?Sized> {
     target_size: usize,
-    delimiters: &'a [u8],
-    pattern: Option<&'a [u8]>,
+    delimiters: Cow<'a, [u8]>,
+    pattern: Option<Cow<'a, [u8]>>,
     /// Multi-byte patterns for combined search with delimiters.
-    multi_searcher: Option<MultiPatternSearcher>,
-    pos: usize,
+    multi_searcher: Option<Arc<MultiPatternSearcher>>,
+    /// Lookup table for large delimiter sets, built when they're set
     table: Option<[bool; 256]>,
-    initialized: bool,
     prefix_mode: bool,
+    /// When true, chunks end before the delimiter they were split at
+    drop_delimiters: bool,
+    /// When true, leading and trailing whitespace is left out of chunks
+    trim: bool,
+    /// When true, whitespace-only chunks are not emitted
+    skip_empty: bool,
     /// When true, find the START of consecutive pattern runs (not middle)
     consecutive: bool,
     /// When true, search forward if no pattern found in backward window
     forward_fallback: bool,
+    /// Where to split when the window has no delimiter
+    fallback: FallbackStrategy,
+    /// When true, earlier delimiters win over later ones in the window
+    delimiter_priority: bool,
+    /// Units either side of the target size to look for the nearest boundary
+    slack: usize,
+    /// When true, the target size is evened out over the chunks left
+    balanced: bool,
+    /// Complete lines per chunk, replacing the delimiter search when set
+    lines: Option<usize>,
+    /// Bytes of trailing context carried into the next chunk
+    overlap: usize,
+    /// Overlap as a share of the target size, replacing `overlap` when set
+    overlap_ratio: Option<f64>,
+    /// When true, never split inside a multi-byte UTF-8 sequence
+    utf8_safe: bool,
+    /// Unit the target size is measured in (bytes when `None`)
+    metric: Option<Arc<M>>,
+    /// Ranks the delimiters in the window (the last one wins when `None`)
+    scorer: Option<Arc<S>>,
+    /// Trailing chunks smaller than this are merged into the previous chunk
+    min_size: usize,
+    /// Hard cap on emitted chunk length in bytes
+    max_size: Option<usize>,
 }
 
-impl<'a> Chunker<'a> {
-    fn new(text: &'a [u8]) -> Self {
+/// Settings of a [`Chunker`], borrowing its delimiters.
+type BorrowedSettings<'a> = Settings<'a, dyn SizeMetric + 'a, dyn BoundaryScorer + 'a>;
+
+/// Settings of an [`OwnedChunker`], shared by its clones.
+type SharedSettings =
+    Settings<'static, dyn SizeMetric + Send + Sync, dyn BoundaryScorer + Send + Sync>;
+
+impl<M: ?Sized, S: ?Sized> Clone for Settings<'_, M, S> {
+    fn clone(&self) -> Self {
+        Self {
+            delimiters: self.delimiters.clone(),
+            pattern: self.pattern.clone(),
+            multi_searcher: self.multi_searcher.clone(),
+            metric: self.metric.clone(),
+            scorer: self.scorer.clone(),
+            ..*self
+        }
+    }
+}
+
+impl<'a, M: ?Sized, S: ?Sized> Settings<'a, M, S> {
+    fn new() -> Self {
         Self {
-            text,
             target_size: DEFAULT_TARGET_SIZE,
-            delimiters: DEFAULT_DELIMITERS,
+            delimiters: Cow::Borrowed(DEFAULT_DELIMITERS),
             pattern: None,
             multi_searcher: None,
-            pos: 0,
-            table: None,
-            initialized: false,
+            table: build_table(DEFAULT_DELIMITERS),
             prefix_mode: false,
+            drop_delimiters: false,
+            trim: false,
+            skip_empty: false,
             consecutive: false,
             forward_fallback: false,
+            fallback: FallbackStrategy::Hard,
+            delimiter_priority: false,
+            slack: 0,
+            balanced: false,
+            lines: None,
+            overlap: 0,
+            overlap_ratio: None,
+            utf8_safe: false,
+            metric: None,
+            scorer: None,
+            min_size: 0,
+            max_size: None,
         }
     }
 
-    /// Set the target chunk size in bytes.
-    pub fn size(mut self, size: usize) -> Self {
+    fn set_size(&mut self, size: usize) {
         self.target_size = size;
-        self
+        if let Some(ratio) = self.overlap_ratio {
+            self.overlap = ratio_overlap(ratio, size);
+        }
     }
 
-    /// Set single-byte delimiters to split on.
-    ///
-    /// Mutually exclusive with `pattern()` - last one set wins.
-    pub fn delimiters(mut self, delimiters: &'a [u8]) -> Self {
+    fn set_delimiters(&mut self, delimiters: Cow<'a, [u8]>) {
+        self.table = build_table(&delimiters);
         self.delimiters = delimiters;
         self.pattern = None; // Clear pattern mode
-        self
     }
 
-    /// Set a multi-byte pattern to split on.
-    ///
-    /// Use this for multi-byte delimiters like UTF-8 characters (e.g., metaspace `▁`).
-    /// Mutually exclusive with `delimiters()` - last one set wins.
-    ///
-    /// ```
-    /// use chunk::chunk;
-    /// let metaspace = "▁".as_bytes(); // [0xE2, 0x96, 0x81]
-    /// let chunks: Vec<_> = chunk(b"Hello\xE2\x96\x81World\xE2\x96\x81Test")
-    ///     .size(15)
-    ///     .pattern(metaspace)
-    ///     .prefix()
-    ///     .collect();
-    /// assert_eq!(chunks[0], b"Hello");
-    /// assert_eq!(chunks[1], b"\xE2\x96\x81World\xE2\x96\x81Test");
-    /// ```
-    pub fn pattern(mut self, pattern: &'a [u8]) -> Self {
+    fn set_pattern(&mut self, pattern: Cow<'a, [u8]>) {
         self.pattern = Some(pattern);
-        self.delimiters = &[]; // Clear single-byte delimiters
-        self
+        // Clear single-byte delimiters
+        self.delimiters = Cow::Borrowed(&[]);
+        self.table = None;
     }
 
-    /// Set multiple multi-byte patterns to split on, composable with `.delimiters()`.
-    ///
-    /// Unlike `.pattern()` (single pattern, mutually exclusive with delimiters),
-    /// `.patterns()` works **alongside** `.delimiters()`. The chunker will search for
-    /// both single-byte delimiters and multi-byte patterns, picking the best split point.
-    ///
-    /// Automatically selects the optimal search strategy:
-    /// - 1-3 patterns: SIMD-accelerated memmem (parallel searches)
-    /// - 4+ patterns: Aho-Corasick automaton (single pass)
-    ///
-    /// ```
-    /// use chunk::chunk;
-    /// let text = "Hello. World。Test".as_bytes();
-    /// let chunks: Vec<_> = chunk(text)
-    ///     .size(12)
-    ///     .delimiters(b".")
-    ///     .patterns(&["。"])
-    ///     .collect();
-    /// assert_eq!(chunks.len(), 3);
-    /// ```
-    pub fn patterns(mut self, patterns: &[&str]) -> Self {
-        if patterns.is_empty() {
-            self.multi_searcher = None;
-        } else {
-            self.multi_searcher = Some(MultiPatternSearcher::from_strs(patterns));
-        }
-        self
+    fn set_delimiter_mode(&mut self, mode: DelimiterMode) {
+        self.prefix_mode = mode == DelimiterMode::KeepLeading;
+        self.drop_delimiters = mode == DelimiterMode::Drop;
     }
 
-    /// Put delimiter at the start of the next chunk (prefix mode).
-    ///
-    /// ```
-    /// use chunk::chunk;
-    /// let chunks: Vec<_> = chunk(b"Hello World").size(8).delimiters(b" ").prefix().collect();
-    /// assert_eq!(chunks, vec![b"Hello".as_slice(), b" World".as_slice()]);
-    /// ```
-    pub fn prefix(mut self) -> Self {
-        self.prefix_mode = true;
-        self
+    fn set_overlap(&mut self, overlap: usize) {
+        self.overlap = overlap;
+        self.overlap_ratio = None;
     }
 
-    /// Put delimiter at the end of the current chunk (suffix mode, default).
-    ///
-    /// ```
-    /// use chunk::chunk;
-    /// let chunks: Vec<_> = chunk(b"Hello World").size(8).delimiters(b" ").suffix().collect();
-    /// assert_eq!(chunks, vec![b"Hello ".as_slice(), b"World".as_slice()]);
-    /// ```
-    pub fn suffix(mut self) -> Self {
-        self.prefix_mode = false;
-        self
+    fn set_overlap_ratio(&mut self, ratio: f64) {
+        self.overlap = ratio_overlap(ratio, self.target_size);
+        self.overlap_ratio = Some(ratio);
     }
 
-    /// Enable consecutive delimiter/pattern handling.
-    ///
-    /// When splitting, ensures we split at the START of a consecutive run
-    /// of the same delimiter/pattern, not in the middle. For example:
-    /// - With pattern: "word▁▁▁next" splits as ["word"]["▁▁▁next"]
-    /// - With delimiter: "word\n\n\nnext" splits as ["word"]["\\n\\n\\nnext"]
-    ///
-    /// This is useful for patterns that can merge (like BPE tokenization)
-    /// or when consecutive delimiters have semantic meaning (like `\n\n`
-    /// for paragraph breaks).
-    ///
-    /// Works with both `.pattern()` and `.delimiters()`.
-    ///
-    /// ```
-    /// use chunk::chunk;
-    ///
-    /// // With pattern
-    /// let text = b"word\xE2\x96\x81\xE2\x96\x81\xE2\x96\x81next"; // word▁▁▁next
-    /// let metaspace = b"\xE2\x96\x81";
-    /// let chunks: Vec<_> = chunk(text)
-    ///     .pattern(metaspace)
-    ///     .size(10)
-    ///     .prefix()
-    ///     .consecutive()
-    ///     .collect();
-    /// assert_eq!(chunks[0], b"word");
-    ///
-    /// // With delimiters
-    /// let text = b"Hello\n\n\nWorld";
-    /// let chunks: Vec<_> = chunk(text)
-    ///     .delimiters(b"\n")
-    ///     .size(8)
-    ///     .prefix()
-    ///     .consecutive()
-    ///     .collect();
-    /// assert_eq!(chunks[0], b"Hello");
-    /// assert_eq!(chunks[1], b"\n\n\nWorld");
-    /// ```
-    pub fn consecutive(mut self) -> Self {
-        self.consecutive = true;
-        self
+    fn validate(&self) -> Result<(), ChunkError> {
+        validate(
+            self.target_size,
+            self.overlap,
+            self.overlap_ratio,
+            !self.delimiters.is_empty()
+                || self.pattern.is_some()
+                || self.multi_searcher.is_some()
+                || self.lines.is_some(),
+        )
     }
 
-    /// Enable forward fallback search.
-    ///
-    /// When no delimiter/pattern is found in the backward search window,
-    /// search forward from target_end instead of doing a hard split.
-    ///
-    /// This ensures splits always occur at semantic boundaries when possible,
-    /// even if the nearest boundary is past the target size.
-    ///
-    /// Works with both `.pattern()` and `.delimiters()`.
-    ///
-    /// ```
-    /// use chunk::chunk;
-    ///
-    /// // With pattern
-    /// let text = b"verylongword\xE2\x96\x81short"; // verylongword▁short
-    /// let metaspace = b"\xE2\x96\x81";
-    /// let chunks: Vec<_> = chunk(text)
-    ///     .pattern(metaspace)
-    ///     .size(6)
-    ///     .prefix()
-    ///     .forward_fallback()
-    ///     .collect();
-    /// // Without forward_fallback: hard split at position 6
-    /// // With forward_fallback: finds ▁ at position 12
-    /// assert_eq!(chunks[0], b"verylongword");
-    ///
-    /// // With delimiters
-    /// let text = b"verylongword next";
-    /// let chunks: Vec<_> = chunk(text)
-    ///     .delimiters(b" ")
-    ///     .size(6)
-    ///     .prefix()
-    ///     .forward_fallback()
-    ///     .collect();
-    /// assert_eq!(chunks[0], b"verylongword");
-    /// assert_eq!(chunks[1], b" next");
-    /// ```
-    pub fn forward_fallback(mut self) -> Self {
-        self.forward_fallback = true;
-        self
+    /// The split rules, for finding the delimiter at a boundary.
+    fn boundaries(&self) -> Boundaries<'_> {
+        Boundaries {
+            pattern: self.pattern.as_deref(),
+            delimiters: &self.delimiters,
+            multi_searcher: self.multi_searcher.as_deref(),
+            prefix: self.prefix_mode,
+        }
     }
+}
 
-    /// Initialize lookup table if needed (called on first iteration).
-    fn init(&mut self) {
-        if !self.initialized {
-            self.table = build_table(self.delimiters);
-            self.initialized = true;
+impl<'a, M, S> Settings<'a, M, S>
+where
+    M: SizeMetric + ?Sized,
+    S: BoundaryScorer + ?Sized,
+{
+    /// The chunks of `text` whose boundary search starts at `pos`, after a
+    /// chunk whose search started at `prev_pos`.
+    fn splits<'s>(&'s self, text: &'s [u8], pos: usize, prev_pos: usize) -> Splits<'s, 'a, M, S> {
+        Splits {
+            settings: self,
+            text,
+            pos,
+            prev_pos,
         }
     }
-}
 
-impl<'a> Iterator for Chunker<'a> {
-    type Item = &'a [u8];
+    /// Offsets of the chunks of `text` from `start`, stopping before a
+    /// chunk that can't be split.
+    fn spans<'s>(
+        &'s self,
+        text: &'s [u8],
+        start: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + 's {
+        self.splits(text, start, start)
+            .map_while(Result::ok)
+            .filter(move |&(start, split_at)| !self.skips(text, start, split_at))
+            .map(move |(start, split_at)| self.span(text, start, split_at))
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.init();
+    fn collect_offsets(&self, text: &[u8], start: usize) -> Vec<(usize, usize)> {
+        self.spans(text, start).collect()
+    }
 
-        if self.pos >= self.text.len() {
-            return None;
+    fn try_collect_offsets(
+        &self,
+        text: &[u8],
+        start: usize,
+    ) -> Result<Vec<(usize, usize)>, ChunkError> {
+        let mut offsets = Vec::new();
+        for split in self.splits(text, start, start) {
+            let (start, split_at) = split?;
+            if !self.skips(text, start, split_at) {
+                offsets.push(self.span(text, start, split_at));
+            }
+        }
+        Ok(offsets)
+    }
+
+    #[cfg(feature = "std")]
+    fn collect_offsets_until(
+        &self,
+        text: &[u8],
+        start: usize,
+        token: &CancelToken,
+    ) -> Result<Vec<(usize, usize)>, Cancelled> {
+        let mut offsets = Vec::new();
+        let mut splits = self.splits(text, start, start);
+        while splits.pos < text.len() {
+            token.check()?;
+            let Some(Ok((start, split_at))) = splits.next() else {
+                break;
+            };
+            if !self.skips(text, start, split_at) {
+                offsets.push(self.span(text, start, split_at));
+            }
+        }
+        Ok(offsets)
+    }
+
+    #[cfg(feature = "std")]
+    fn write_jsonl<W: std::io::Write>(
+        &self,
+        text: &[u8],
+        start: usize,
+        mut writer: W,
+    ) -> std::io::Result<usize> {
+        let mut count = 0;
+        for (start, end) in self.spans(text, start) {
+            crate::jsonl::write_record(&mut writer, count, start, end, &text[start..end])?;
+            count += 1;
+        }
+        writer.flush()?;
+        Ok(count)
+    }
+
+    fn count_chunks(&self, text: &[u8], start: usize) -> usize {
+        self.splits(text, start, start)
+            .map_while(Result::ok)
+            .filter(|&(start, split_at)| !self.skips(text, start, split_at))
+            .count()
+    }
+
+    fn stats(&self, text: &[u8], start: usize) -> ChunkStats {
+        let boundaries = self.boundaries();
+        let mut sizes = Vec::new();
+        let mut forced = 0;
+        for (start, split_at) in self.splits(text, start, start).map_while(Result::ok) {
+            if !self.skips(text, start, split_at) {
+                let (start, end) = self.span(text, start, split_at);
+                sizes.push(end - start);
+            }
+            // Judged at the boundary, before any delimiter or whitespace is cut
+            if split_at < text.len() && !boundaries.is_delimited(text, split_at) {
+                forced += 1;
+            }
+        }
+        ChunkStats::new(sizes, forced)
+    }
+
+    /// Compute the end of the chunk starting at `pos`, or the error from a
+    /// forced split with [`FallbackStrategy::Error`].
+    fn split_at(&self, text: &[u8], pos: usize) -> Result<usize, ChunkError> {
+        let mut split_at = self.find_split(text, pos);
+        if self.utf8_safe && split_at < text.len() {
+            split_at = utf8_split_boundary(text, pos, split_at);
+        }
+        if self.is_undersized_tail(text, split_at) {
+            split_at = text.len();
+        }
+        if self.is_forced(text, split_at) {
+            split_at = fall_back(text, pos, split_at, self.fallback)?;
+        }
+        match self.max_size {
+            Some(max_size) if split_at - pos > max_size => self.capped_split(text, pos, max_size),
+            _ => Ok(split_at),
+        }
+    }
+
+    /// Split for a chunk at `pos` that must not exceed `max_size` bytes.
+    fn capped_split(&self, text: &[u8], pos: usize, max_size: usize) -> Result<usize, ChunkError> {
+        let cap = pos + max_size;
+        let mut split_at = self.search(text, pos, cap, false).min(cap);
+        if self.utf8_safe && split_at == cap {
+            // Forced split: back up to a code point boundary if that leaves anything
+            split_at = match utf8_prev_boundary(text, pos, split_at) {
+                at if at > pos => at,
+                _ => split_at,
+            };
+        }
+        if self.is_forced(text, split_at) {
+            // Moving forward past a character would break the cap
+            split_at = fall_back(text, pos, split_at, self.fallback)?.min(cap);
+        }
+        Ok(split_at)
+    }
+
+    /// Whether a split at `at` was forced and a [`FallbackStrategy`] other
+    /// than the default applies to it.
+    fn is_forced(&self, text: &[u8], at: usize) -> bool {
+        self.fallback != FallbackStrategy::Hard
+            && at < text.len()
+            && !self.boundaries().is_delimited(text, at)
+    }
+
+    /// Whether the text after `at` is a non-empty tail shorter than `min_size`.
+    fn is_undersized_tail(&self, text: &[u8], at: usize) -> bool {
+        if self.min_size == 0 || at >= text.len() {
+            return false;
+        }
+        match &self.metric {
+            Some(metric) => metric.window_end(text, at, self.min_size - 1) >= text.len(),
+            None => text.len() - at < self.min_size,
+        }
+    }
+
+    /// Start of the chunk whose boundary search began at `pos` and ends at `split_at`.
+    fn chunk_start(&self, text: &[u8], pos: usize, prev_pos: usize, split_at: usize) -> usize {
+        let mut start = overlap_start(pos, prev_pos, self.overlap);
+        if let Some(max_size) = self.max_size {
+            start = start.max(split_at.saturating_sub(max_size));
+        }
+        if self.utf8_safe {
+            utf8_next_boundary(text, start, pos)
+        } else {
+            start
+        }
+    }
+
+    /// Emitted offsets of the chunk from `start` to the boundary `split_at`:
+    /// without the delimiter there when delimiters are dropped, and without
+    /// surrounding whitespace when trimming.
+    fn span(&self, text: &[u8], start: usize, split_at: usize) -> (usize, usize) {
+        span(
+            text,
+            start,
+            split_at,
+            self.dropped(text, start, split_at),
+            self.trim,
+        )
+    }
+
+    /// Length of the delimiter dropped from the end of `start..split_at`.
+    fn dropped(&self, text: &[u8], start: usize, split_at: usize) -> usize {
+        if !self.drop_delimiters {
+            return 0;
+        }
+        self.boundaries()
+            .delimiter_len_before(&text[start..split_at])
+            .unwrap_or(0)
+    }
+
+    /// Whether `skip_empty` leaves out the chunk from `start` to `split_at`.
+    fn skips(&self, text: &[u8], start: usize, split_at: usize) -> bool {
+        self.skip_empty
+            && text[start..split_at - self.dropped(text, start, split_at)]
+                .trim_ascii()
+                .is_empty()
+    }
+
+    /// The chunk at `index` from `start` to the boundary `split_at`.
+    fn emit<'t>(&self, text: &'t [u8], index: usize, start: usize, split_at: usize) -> Chunk<'t> {
+        let (start, end) = self.span(text, start, split_at);
+        Chunk {
+            index,
+            start,
+            end,
+            bytes: &text[start..end],
+        }
+    }
+
+    /// End of the target-size window starting at `pos`.
+    fn window_end(&self, text: &[u8], pos: usize) -> usize {
+        self.reach(text, pos, self.target(text, pos))
+    }
+
+    /// Target size of the chunk starting at `pos`: an equal share of the
+    /// rest of the text when balanced.
+    fn target(&self, text: &[u8], pos: usize) -> usize {
+        // A size of 0 would never advance, so it's taken as 1 (`build` rejects it)
+        let size = self.target_size.max(1);
+        if !self.balanced {
+            return size;
+        }
+        let rest = match &self.metric {
+            Some(metric) => metric.measure(&text[pos..]),
+            None => text.len() - pos,
+        };
+        let count = rest.div_ceil(size).max(1);
+        rest.div_ceil(count)
+    }
+
+    /// End of a window of `size` units starting at `pos`.
+    fn reach(&self, text: &[u8], pos: usize, size: usize) -> usize {
+        match &self.metric {
+            Some(metric) => metric.window_end(text, pos, size).max(pos + 1),
+            None => pos.saturating_add(size).min(text.len()),
         }
+    }
 
-        let remaining = self.text.len() - self.pos;
+    /// Compute the raw end of the chunk starting at `pos`.
+    fn find_split(&self, text: &[u8], pos: usize) -> usize {
+        let end = self.window_end(text, pos);
+        if let Some(lines) = self.lines {
+            return line_split(text, pos, end, lines);
+        }
 
         // Last chunk - return remainder
-        if remaining <= self.target_size {
-            let chunk = &self.text[self.pos..];
-            self.pos = self.text.len();
-            return Some(chunk);
+        if end >= text.len() {
+            return text.len();
+        }
+
+        if let Some(scorer) = &self.scorer {
+            if let Some(split_at) = self.scored_split(text, scorer.as_ref(), pos, end) {
+                return split_at;
+            }
+        } else if self.slack > 0
+            && let Some(split_at) = self.slack_split(text, pos, end)
+        {
+            return split_at;
+        }
+        self.search(text, pos, end, self.forward_fallback)
+    }
+
+    /// The delimiter boundary in the window `pos..end` that `scorer` ranks
+    /// highest (the later one on a tie).
+    fn scored_split(&self, text: &[u8], scorer: &S, pos: usize, end: usize) -> Option<usize> {
+        let boundaries = self.boundaries();
+        let mut best: Option<(usize, f64)> = None;
+        let mut window_end = end;
+        let mut last = usize::MAX;
+        // Walk back through the boundaries, one search per candidate
+        while window_end > pos {
+            let at = self.search_in(text, pos, window_end, false);
+            if at <= pos || at >= last || !boundaries.is_delimited(text, at) {
+                break;
+            }
+            let score = scorer.score(text, pos, at);
+            if best.is_none_or(|(_, best)| score > best) {
+                best = Some((at, score));
+            }
+            last = at;
+            window_end = if self.prefix_mode { at } else { at - 1 };
         }
+        best.map(|(at, _)| at)
+    }
+
+    /// The boundary nearest the target `end` within `slack` units of it.
+    fn slack_split(&self, text: &[u8], pos: usize, end: usize) -> Option<usize> {
+        let size = self.target(text, pos);
+        let low = self.reach(text, pos, size.saturating_sub(self.slack));
+        let high = self.reach(text, pos, size.saturating_add(self.slack));
+        let boundaries = self.boundaries();
+        let delimited = |at: usize| boundaries.is_delimited(text, at);
+        let before =
+            Some(self.search_in(text, pos, end, false)).filter(|&at| at >= low && delimited(at));
+        let after = Some(self.search_in(&text[..high], end, end, true))
+            .filter(|&at| at >= end && delimited(at));
+        match (before, after) {
+            (Some(before), Some(after)) if after - end < end - before => Some(after),
+            (None, after) => after,
+            (before, _) => before,
+        }
+    }
 
-        let end = self.pos + self.target_size;
+    /// Find the chunk end for the window `pos..end`.
+    fn search(&self, text: &[u8], pos: usize, end: usize, forward_fallback: bool) -> usize {
+        if self.delimiter_priority && self.pattern.is_none() {
+            let split_at = find_priority_split(
+                text,
+                pos,
+                end,
+                &self.delimiters,
+                self.prefix_mode,
+                self.consecutive,
+            );
+            if let Some(split_at) = split_at {
+                return split_at;
+            }
+        }
+        self.search_in(text, pos, end, forward_fallback)
+    }
 
-        let split_at = if self.multi_searcher.is_some() {
+    /// Find the chunk end for the window `pos..end` of `text`, with every
+    /// delimiter and pattern ranked the same.
+    fn search_in(&self, text: &[u8], pos: usize, end: usize, forward_fallback: bool) -> usize {
+        if self.multi_searcher.is_some() {
             // Combined mode: delimiters + multi-byte patterns
             compute_split_at_combined(
-                self.text,
-                self.pos,
+                text,
+                pos,
                 end,
-                self.delimiters,
+                &self.delimiters,
                 self.table.as_ref(),
-                self.multi_searcher.as_ref(),
+                self.multi_searcher.as_deref(),
                 self.prefix_mode,
                 self.consecutive,
-                self.forward_fallback,
+                forward_fallback,
             )
         } else {
             // Legacy mode: single pattern or delimiters only
             compute_split_at(
-                self.text,
-                self.pos,
+                text,
+                pos,
                 end,
-                self.pattern,
-                self.delimiters,
+                self.pattern.as_deref(),
+                &self.delimiters,
                 self.table.as_ref(),
                 self.prefix_mode,
                 self.consecutive,
-                self.forward_fallback,
+                forward_fallback,
             )
-        };
+        }
+    }
+}
+
+/// Chunk boundaries as (start, split_at), before [`Settings::span`] and
+/// with skipped chunks included.
+///
+/// A chunk that can't be split is an error, returned again on every call
+/// without moving on.
+struct Splits<'s, 'a, M: ?Sized, S: ?Sized> {
+    settings: &'s Settings<'a, M, S>,
+    text: &'s [u8],
+    /// Boundary-search start of the next chunk
+    pos: usize,
+    /// Boundary-search start of the previous chunk (overlap never reaches past it)
+    prev_pos: usize,
+}
 
-        let chunk = &self.text[self.pos..split_at];
+impl<M, S> Iterator for Splits<'_, '_, M, S>
+where
+    M: SizeMetric + ?Sized,
+    S: BoundaryScorer + ?Sized,
+{
+    type Item = Result<(usize, usize), ChunkError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.text.len() {
+            return None;
+        }
+        let split_at = match self.settings.split_at(self.text, self.pos) {
+            Ok(split_at) => split_at,
+            Err(err) => return Some(Err(err)),
+        };
+        let start = self
+            .settings
+            .chunk_start(self.text, self.pos, self.prev_pos, split_at);
+        self.prev_pos = self.pos;
         self.pos = split_at;
-        Some(chunk)
+        Some(Ok((start, split_at)))
     }
 }
 
-/// Owned chunker for FFI bindings (Python, WASM).
-///
-/// Unlike [`Chunker`], this owns its data and returns owned chunks.
-/// Use this when you need to cross FFI boundaries where lifetimes can't be tracked.
-///
-/// # Example
-///
-/// ```
-/// use chunk::OwnedChunker;
-///
-/// let text = b"Hello world. How are you?".to_vec();
-/// let mut chunker = OwnedChunker::new(text)
-///     .size(15)
-///     .delimiters(b"\n.?".to_vec());
-///
-/// while let Some(chunk) = chunker.next_chunk() {
-///     println!("{:?}", chunk);
-/// }
-/// ```
-pub struct OwnedChunker {
-    text: Vec<u8>,
-    target_size: usize,
-    delimiters: Vec<u8>,
-    pattern: Option<Vec<u8>>,
-    multi_searcher: Option<MultiPatternSearcher>,
+/// Where a [`Chunker`] or [`OwnedChunker`] is in its text.
+#[derive(Debug, Clone, Default)]
+struct State {
+    /// Boundary-search start of the next chunk
     pos: usize,
-    table: Option<[bool; 256]>,
-    initialized: bool,
-    prefix_mode: bool,
-    consecutive: bool,
-    forward_fallback: bool,
+    /// Boundary-search start of the previous chunk (overlap never reaches past it)
+    prev_pos: usize,
+    /// Index of the next chunk to emit
+    index: usize,
+    /// Remaining (start, end) offsets, computed by the first `next_back`
+    back: Option<VecDeque<(usize, usize)>>,
+    /// Chunks taken from the back since the last reset or seek
+    taken_back: usize,
+    /// Offsets found by `chunk_at` and `seek`
+    cache: OffsetCache,
+    /// Where chunking starts, from `range`
+    start: usize,
+    /// Where chunking stops, from `range`
+    end: usize,
 }
 
-impl OwnedChunker {
-    /// Create a new owned chunker with the given text.
-    pub fn new(text: Vec<u8>) -> Self {
+impl State {
+    /// The state before the first chunk of `start..end`.
+    fn new(start: usize, end: usize) -> Self {
+        Self {
+            pos: start,
+            prev_pos: start,
+            cache: OffsetCache::starting_at(start),
+            start,
+            end,
+            ..Self::default()
+        }
+    }
+
+    /// Go back to the first chunk (keeping the cache).
+    fn reset(&mut self) {
+        self.pos = self.start;
+        self.prev_pos = self.start;
+        self.index = 0;
+        self.back = None;
+        self.taken_back = 0;
+    }
+
+    /// The next chunk of `text` (up to where chunking stops), or the error
+    /// where it can't be split.
+    fn try_next<'t, M, S>(
+        &mut self,
+        text: &'t [u8],
+        settings: &Settings<'_, M, S>,
+    ) -> Option<Result<Chunk<'t>, ChunkError>>
+    where
+        M: SizeMetric + ?Sized,
+        S: BoundaryScorer + ?Sized,
+    {
+        if let Some(back) = &mut self.back {
+            let (start, split_at) = back.pop_front()?;
+            self.index += 1;
+            return Some(Ok(settings.emit(text, self.index - 1, start, split_at)));
+        }
+        let mut splits = settings.splits(text, self.pos, self.prev_pos);
+        let split = splits.find(|split| {
+            !split
+                .as_ref()
+                .is_ok_and(|&(start, split_at)| settings.skips(text, start, split_at))
+        })?;
+        (self.pos, self.prev_pos) = (splits.pos, splits.prev_pos);
+        Some(split.map(|(start, split_at)| {
+            self.index += 1;
+            settings.emit(text, self.index - 1, start, split_at)
+        }))
+    }
+
+    /// The last remaining chunk of `text`.
+    fn next_back<'t, M, S>(
+        &mut self,
+        text: &'t [u8],
+        settings: &Settings<'_, M, S>,
+    ) -> Option<Chunk<'t>>
+    where
+        M: SizeMetric + ?Sized,
+        S: BoundaryScorer + ?Sized,
+    {
+        let back = self.remaining(text, settings);
+        let (start, split_at) = back.pop_back()?;
+        let before = back.len();
+        self.taken_back += 1;
+        Some(settings.emit(text, self.index + before, start, split_at))
+    }
+
+    /// Offsets of the chunks not yet emitted from either end.
+    fn remaining<M, S>(
+        &mut self,
+        text: &[u8],
+        settings: &Settings<'_, M, S>,
+    ) -> &mut VecDeque<(usize, usize)>
+    where
+        M: SizeMetric + ?Sized,
+        S: BoundaryScorer + ?Sized,
+    {
+        if self.back.is_none() {
+            let mut splits = settings.splits(text, self.pos, self.prev_pos);
+            let offsets = splits
+                .by_ref()
+                .map_while(Result::ok)
+                .filter(|&(start, split_at)| !settings.skips(text, start, split_at))
+                .collect();
+            (self.pos, self.prev_pos) = (splits.pos, splits.prev_pos);
+            self.back = Some(offsets);
+        }
+        self.back.get_or_insert_default()
+    }
+
+    /// Chunk `index` of `text`, from the cache.
+    fn chunk_at<'t, M, S>(
+        &mut self,
+        text: &'t [u8],
+        settings: &Settings<'_, M, S>,
+        index: usize,
+    ) -> Option<Chunk<'t>>
+    where
+        M: SizeMetric + ?Sized,
+        S: BoundaryScorer + ?Sized,
+    {
+        let offsets = self.cached(text, settings, |offsets| offsets.len() > index);
+        let (start, split_at) = *offsets.get(index)?;
+        Some(settings.emit(text, index, start, split_at))
+    }
+
+    /// Move to the chunk containing byte `offset`, returning its index.
+    fn seek<M, S>(
+        &mut self,
+        text: &[u8],
+        settings: &Settings<'_, M, S>,
+        offset: usize,
+    ) -> Option<usize>
+    where
+        M: SizeMetric + ?Sized,
+        S: BoundaryScorer + ?Sized,
+    {
+        self.cached(text, settings, |offsets| {
+            offsets.last().is_some_and(|&(_, end)| end > offset)
+        });
+        let index = self.cache.find(offset)?;
+        (self.pos, self.prev_pos) = self.cache.position(index);
+        self.index = index;
+        self.back = None;
+        self.taken_back = 0;
+        Some(index)
+    }
+
+    /// Cached offsets, extended until `done` holds or the text runs out.
+    fn cached<M, S>(
+        &mut self,
+        text: &[u8],
+        settings: &Settings<'_, M, S>,
+        done: impl Fn(&[(usize, usize)]) -> bool,
+    ) -> &[(usize, usize)]
+    where
+        M: SizeMetric + ?Sized,
+        S: BoundaryScorer + ?Sized,
+    {
+        self.cache.fill(
+            text.len(),
+            |pos, prev_pos| {
+                let split_at = settings.split_at(text, pos).ok()?;
+                Some((
+                    settings.chunk_start(text, pos, prev_pos, split_at),
+                    split_at,
+                ))
+            },
+            |start, split_at| !settings.skips(text, start, split_at),
+            done,
+        );
+        &self.cache.offsets
+    }
+
+    /// Number of chunks in `text`, from the cache once it holds them all.
+    fn count_chunks<M, S>(&self, text: &[u8], settings: &Settings<'_, M, S>) -> usize
+    where
+        M: SizeMetric + ?Sized,
+        S: BoundaryScorer + ?Sized,
+    {
+        if self.cache.pos >= text.len() {
+            return self.cache.offsets.len();
+        }
+        settings.count_chunks(text, self.start)
+    }
+}
+
+/// Chunker splits text at delimiter boundaries.
+///
+/// Created via [`chunk()`], can be configured with `.size()` and `.delimiters()`.
+/// For multi-byte delimiters, use `.pattern()` instead. Changing a setting
+/// starts over from the beginning and forgets offsets cached by
+/// [`chunk_at`](Self::chunk_at) and [`seek`](Self::seek).
+pub struct Chunker<'a> {
+    text: &'a [u8],
+    settings: BorrowedSettings<'a>,
+    state: State,
+}
+
+impl<'a> Chunker<'a> {
+    fn new(text: &'a [u8]) -> Self {
         Self {
             text,
-            target_size: DEFAULT_TARGET_SIZE,
-            delimiters: DEFAULT_DELIMITERS.to_vec(),
-            pattern: None,
-            multi_searcher: None,
-            pos: 0,
-            table: None,
-            initialized: false,
-            prefix_mode: false,
-            consecutive: false,
-            forward_fallback: false,
+            settings: Settings::new(),
+            state: State::new(0, text.len()),
         }
     }
 
     /// Set the target chunk size in bytes.
-    pub fn size(mut self, size: usize) -> Self {
-        self.target_size = size;
-        self
+    pub fn size(self, size: usize) -> Self {
+        self.with(|s| s.set_size(size))
     }
 
     /// Set single-byte delimiters to split on.
     ///
     /// Mutually exclusive with `pattern()` - last one set wins.
-    pub fn delimiters(mut self, delimiters: Vec<u8>) -> Self {
-        self.delimiters = delimiters;
-        self.pattern = None; // Clear pattern mode
-        self
+    pub fn delimiters(self, delimiters: &'a [u8]) -> Self {
+        self.with(|s| s.set_delimiters(Cow::Borrowed(delimiters)))
     }
 
     /// Set a multi-byte pattern to split on.
     ///
     /// Use this for multi-byte delimiters like UTF-8 characters (e.g., metaspace `▁`).
     /// Mutually exclusive with `delimiters()` - last one set wins.
-    pub fn pattern(mut self, pattern: Vec<u8>) -> Self {
-        self.pattern = Some(pattern);
-        self.delimiters = vec![]; // Clear single-byte delimiters
-        self
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let metaspace = "▁".as_bytes(); // [0xE2, 0x96, 0x81]
+    /// let chunks: Vec<_> = chunk(b"Hello\xE2\x96\x81World\xE2\x96\x81Test")
+    ///     .size(15)
+    ///     .pattern(metaspace)
+    ///     .prefix()
+    ///     .collect();
+    /// assert_eq!(chunks[0], b"Hello");
+    /// assert_eq!(chunks[1], b"\xE2\x96\x81World\xE2\x96\x81Test");
+    /// ```
+    pub fn pattern(self, pattern: &'a [u8]) -> Self {
+        self.with(|s| s.set_pattern(Cow::Borrowed(pattern)))
     }
 
-    /// Set multiple multi-byte patterns, composable with `.delimiters()`.
+    /// Set multiple multi-byte patterns to split on, composable with `.delimiters()`.
     ///
-    /// See [`Chunker::patterns`] for details.
-    pub fn patterns(mut self, patterns: &[&str]) -> Self {
-        if patterns.is_empty() {
-            self.multi_searcher = None;
-        } else {
-            self.multi_searcher = Some(MultiPatternSearcher::from_strs(patterns));
-        }
-        self
+    /// Unlike `.pattern()` (single pattern, mutually exclusive with delimiters),
+    /// `.patterns()` works **alongside** `.delimiters()`. The chunker will search for
+    /// both single-byte delimiters and multi-byte patterns, picking the best split point.
+    ///
+    /// Automatically selects the optimal search strategy:
+    /// - 1-3 patterns: SIMD-accelerated memmem (parallel searches)
+    /// - 4+ patterns: Aho-Corasick automaton (single pass)
+    ///
+    /// Patterns can be strings or raw byte strings. When patterns overlap
+    /// (e.g. `"\n"` and `"\n\n"`), the longest match wins.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = "Hello. World。Test".as_bytes();
+    /// let chunks: Vec<_> = chunk(text)
+    ///     .size(12)
+    ///     .delimiters(b".")
+    ///     .patterns(&["。"])
+    ///     .collect();
+    /// assert_eq!(chunks.len(), 3);
+    ///
+    /// // Paragraph breaks take precedence over the newline they contain
+    /// let chunks: Vec<_> = chunk(b"One\n\nTwo\nThree")
+    ///     .size(6)
+    ///     .delimiters(b"")
+    ///     .patterns(&[b"\n".as_slice(), b"\n\n".as_slice()])
+    ///     .prefix()
+    ///     .collect();
+    /// assert_eq!(chunks[0], b"One");
+    /// ```
+    pub fn patterns<P: AsRef<[u8]>>(self, patterns: &[P]) -> Self {
+        self.with(|s| {
+            s.multi_searcher = MultiPatternSearcher::from_patterns(patterns).map(Arc::new)
+        })
+    }
+
+    /// Split at a named class of delimiters, see [`Delimiters`].
+    ///
+    /// Sets the delimiters and patterns, replacing any set before, and puts
+    /// the delimiter at the start or end of chunks as the class says.
+    ///
+    /// ```
+    /// use chunk::{Delimiters, chunk};
+    /// let chunks: Vec<_> = chunk(b"One line\nand another")
+    ///     .size(12)
+    ///     .delimiter_class(Delimiters::NEWLINES)
+    ///     .collect();
+    /// assert_eq!(chunks, vec![&b"One line\n"[..], b"and another"]);
+    /// ```
+    pub fn delimiter_class(self, class: Delimiters) -> Self {
+        let mode = match class.prefix() {
+            true => DelimiterMode::KeepLeading,
+            false => DelimiterMode::KeepTrailing,
+        };
+        self.delimiters(class.bytes())
+            .patterns(class.patterns())
+            .delimiter_mode(mode)
     }
 
     /// Put delimiter at the start of the next chunk (prefix mode).
-    pub fn prefix(mut self) -> Self {
-        self.prefix_mode = true;
-        self
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let chunks: Vec<_> = chunk(b"Hello World").size(8).delimiters(b" ").prefix().collect();
+    /// assert_eq!(chunks, vec![b"Hello".as_slice(), b" World".as_slice()]);
+    /// ```
+    pub fn prefix(self) -> Self {
+        self.delimiter_mode(DelimiterMode::KeepLeading)
     }
 
     /// Put delimiter at the end of the current chunk (suffix mode, default).
-    pub fn suffix(mut self) -> Self {
-        self.prefix_mode = false;
-        self
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let chunks: Vec<_> = chunk(b"Hello World").size(8).delimiters(b" ").suffix().collect();
+    /// assert_eq!(chunks, vec![b"Hello ".as_slice(), b"World".as_slice()]);
+    /// ```
+    pub fn suffix(self) -> Self {
+        self.delimiter_mode(DelimiterMode::KeepTrailing)
     }
 
-    /// Enable consecutive delimiter/pattern handling.
+    /// Choose where the delimiter at each split goes, see [`DelimiterMode`].
     ///
-    /// When splitting, ensures we split at the START of a consecutive run
-    /// of the same delimiter/pattern, not in the middle.
-    /// Works with both `.pattern()` and `.delimiters()`.
-    pub fn consecutive(mut self) -> Self {
-        self.consecutive = true;
-        self
+    /// With [`DelimiterMode::Drop`], boundaries are found as in the default
+    /// mode and each chunk then ends before the delimiter (or pattern) it was
+    /// split at, so offsets no longer cover the whole text. A chunk that is
+    /// nothing but the delimiter keeps it, so no chunk is ever empty.
+    ///
+    /// ```
+    /// use chunk::{DelimiterMode, chunk};
+    /// let offsets = chunk(b"Hello World")
+    ///     .size(8)
+    ///     .delimiters(b" ")
+    ///     .delimiter_mode(DelimiterMode::Drop)
+    ///     .collect_offsets();
+    /// assert_eq!(offsets, vec![(0, 5), (6, 11)]);
+    /// ```
+    pub fn delimiter_mode(self, mode: DelimiterMode) -> Self {
+        self.with(|s| s.set_delimiter_mode(mode))
     }
 
-    /// Enable forward fallback search.
+    /// Enable consecutive delimiter/pattern handling.
     ///
-    /// When no delimiter/pattern is found in the backward search window,
-    /// search forward from target_end instead of doing a hard split.
+    /// When splitting, ensures we split at the START of a consecutive run
+    /// of the same delimiter/pattern, not in the middle. For example:
+    /// - With pattern: "word▁▁▁next" splits as ["word"]["▁▁▁next"]
+    /// - With delimiter: "word\n\n\nnext" splits as ["word"]["\\n\\n\\nnext"]
+    ///
+    /// This is useful for patterns that can merge (like BPE tokenization)
+    /// or when consecutive delimiters have semantic meaning (like `\n\n`
+    /// for paragraph breaks).
+    ///
+    /// Works with both `.pattern()` and `.delimiters()`.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    ///
+    /// // With pattern
+    /// let text = b"word\xE2\x96\x81\xE2\x96\x81\xE2\x96\x81next"; // word▁▁▁next
+    /// let metaspace = b"\xE2\x96\x81";
+    /// let chunks: Vec<_> = chunk(text)
+    ///     .pattern(metaspace)
+    ///     .size(10)
+    ///     .prefix()
+    ///     .consecutive()
+    ///     .collect();
+    /// assert_eq!(chunks[0], b"word");
+    ///
+    /// // With delimiters
+    /// let text = b"Hello\n\n\nWorld";
+    /// let chunks: Vec<_> = chunk(text)
+    ///     .delimiters(b"\n")
+    ///     .size(8)
+    ///     .prefix()
+    ///     .consecutive()
+    ///     .collect();
+    /// assert_eq!(chunks[0], b"Hello");
+    /// assert_eq!(chunks[1], b"\n\n\nWorld");
+    /// ```
+    pub fn consecutive(self) -> Self {
+        self.with(|s| s.consecutive = true)
+    }
+
+    /// Enable forward fallback search.
+    ///
+    /// When no delimiter/pattern is found in the backward search window,
+    /// search forward from target_end instead of doing a hard split.
+    ///
+    /// This ensures splits always occur at semantic boundaries when possible,
+    /// even if the nearest boundary is past the target size.
+    ///
+    /// Works with both `.pattern()` and `.delimiters()`.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    ///
+    /// // With pattern
+    /// let text = b"verylongword\xE2\x96\x81short"; // verylongword▁short
+    /// let metaspace = b"\xE2\x96\x81";
+    /// let chunks: Vec<_> = chunk(text)
+    ///     .pattern(metaspace)
+    ///     .size(6)
+    ///     .prefix()
+    ///     .forward_fallback()
+    ///     .collect();
+    /// // Without forward_fallback: hard split at position 6
+    /// // With forward_fallback: finds ▁ at position 12
+    /// assert_eq!(chunks[0], b"verylongword");
+    ///
+    /// // With delimiters
+    /// let text = b"verylongword next";
+    /// let chunks: Vec<_> = chunk(text)
+    ///     .delimiters(b" ")
+    ///     .size(6)
+    ///     .prefix()
+    ///     .forward_fallback()
+    ///     .collect();
+    /// assert_eq!(chunks[0], b"verylongword");
+    /// assert_eq!(chunks[1], b" next");
+    /// ```
+    pub fn forward_fallback(self) -> Self {
+        self.with(|s| s.forward_fallback = true)
+    }
+
+    /// Choose where to split when the window has no delimiter, see
+    /// [`FallbackStrategy`].
+    ///
+    /// The default hard split at the end of the window can cut a word, or
+    /// with `utf8_safe` off a character, in two. With `max_size` the strategy
+    /// also applies to splits forced by the cap.
+    ///
+    /// ```
+    /// use chunk::{FallbackStrategy, chunk};
+    /// let chunks: Vec<_> = chunk(b"Split these words")
+    ///     .size(10)
+    ///     .delimiters(b".")
+    ///     .fallback(FallbackStrategy::Whitespace)
+    ///     .collect();
+    /// assert_eq!(chunks, vec![&b"Split "[..], b"these ", b"words"]);
+    /// ```
+    pub fn fallback(self, strategy: FallbackStrategy) -> Self {
+        self.with(|s| s.fallback = strategy)
+    }
+
+    /// Treat the order of `.delimiters()` as a priority.
+    ///
+    /// By default a chunk ends at the last delimiter in the window, whichever
+    /// it is. With priority on, the window is searched for the first
+    /// delimiter, then the second, and so on: the last occurrence of the
+    /// highest-priority delimiter present wins, so `b"\n.?"` ends chunks at
+    /// line breaks when there are any, and only falls back to sentence ends
+    /// when there are none. Patterns from `.patterns()` and forward fallback
+    /// are tried only when the window holds none of the delimiters. Has no
+    /// effect with `.pattern()`.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"Line one.\nLine two. Three. Four.";
+    /// let chunks: Vec<_> = chunk(text).size(24).delimiters(b"\n.").collect();
+    /// assert_eq!(chunks[0], b"Line one.\nLine two.");
+    ///
+    /// let chunks: Vec<_> = chunk(text)
+    ///     .size(24)
+    ///     .delimiters(b"\n.")
+    ///     .delimiter_priority(true)
+    ///     .collect();
+    /// assert_eq!(chunks[0], b"Line one.\n");
+    /// ```
+    pub fn delimiter_priority(self, enabled: bool) -> Self {
+        self.with(|s| s.delimiter_priority = enabled)
+    }
+
+    /// Split at the boundary nearest the target size, up to `slack` units
+    /// either side of it.
+    ///
+    /// By default a chunk ends at the last boundary before the target size,
+    /// which can be far back when delimiters are sparse. With slack, the
+    /// boundary closest to the target within `size - slack..=size + slack`
+    /// wins, whichever side it is on (the earlier one on a tie), so chunk
+    /// sizes stay close to `size`. Chunks can therefore grow to
+    /// `size + slack`. When the zone holds no boundary, the usual search
+    /// (with delimiter priority and forward fallback) applies. Slack is
+    /// measured in the size unit.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"One two three. Four five. Six";
+    /// let chunks: Vec<_> = chunk(text).size(22).delimiters(b".").collect();
+    /// assert_eq!(chunks[0], b"One two three.");
+    ///
+    /// let chunks: Vec<_> = chunk(text).size(22).delimiters(b".").slack(4).collect();
+    /// assert_eq!(chunks[0], b"One two three. Four five.");
+    /// ```
+    pub fn slack(self, slack: usize) -> Self {
+        self.with(|s| s.slack = slack)
+    }
+
+    /// Even out chunk sizes instead of filling each chunk greedily.
+    ///
+    /// Greedy chunking fills every chunk up to the target size and leaves
+    /// whatever is left to the last one, which can be tiny. Balanced
+    /// chunking works out how many chunks of at most `size` the rest of the
+    /// text needs, and aims each chunk at an equal share of it, still
+    /// splitting at delimiters. The share is recomputed at every chunk, so
+    /// a boundary that lands short is made up for by the chunks after it.
+    /// With a size unit other than bytes, the rest of the text is measured
+    /// once per chunk.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"aaaa bbbb cccc dddd eeee ffff g";
+    /// let greedy: Vec<_> = chunk(text).size(15).delimiters(b" ").collect();
+    /// assert_eq!(greedy, vec![&b"aaaa bbbb cccc "[..], b"dddd eeee ffff ", b"g"]);
+    ///
+    /// let balanced: Vec<_> = chunk(text).size(15).delimiters(b" ").balanced(true).collect();
+    /// assert_eq!(balanced, vec![&b"aaaa bbbb "[..], b"cccc dddd ", b"eeee ffff g"]);
+    /// ```
+    pub fn balanced(self, enabled: bool) -> Self {
+        self.with(|s| s.balanced = enabled)
+    }
+
+    /// Group up to `lines` complete lines per chunk, for logs and code.
+    ///
+    /// Chunks end only after a `\n` (or at the end of the text), replacing
+    /// the delimiter and pattern search. The target size stays a secondary
+    /// cap: a chunk ends early at the last line that fits in the window,
+    /// but a single line longer than the window is kept whole (use
+    /// [`max_size`](Self::max_size) to force-split those). Balanced mode
+    /// still sets the window.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"a\nbb\nccc\ndddddddddd\ne";
+    /// let chunks: Vec<_> = chunk(text).size(8).lines_per_chunk(2).collect();
+    /// assert_eq!(chunks, vec![&b"a\nbb\n"[..], b"ccc\n", b"dddddddddd\n", b"e"]);
+    /// ```
+    pub fn lines_per_chunk(self, lines: usize) -> Self {
+        self.with(|s| s.lines = Some(lines.max(1)))
+    }
+
+    /// Carry `overlap` bytes of trailing context from each chunk into the next.
+    ///
+    /// Boundaries are found exactly as without overlap; every chunk after the
+    /// first then starts up to `overlap` bytes earlier, but never before the
+    /// start of the previous chunk. Chunks can therefore grow to
+    /// `size + overlap` bytes.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let chunks: Vec<_> = chunk(b"Hello. World. Test.")
+    ///     .size(10)
+    ///     .delimiters(b".")
+    ///     .overlap(3)
+    ///     .collect();
+    /// assert_eq!(chunks, vec![b"Hello.".as_slice(), b"lo. World.", b"ld. Test."]);
+    /// ```
+    pub fn overlap(self, overlap: usize) -> Self {
+        self.with(|s| s.set_overlap(overlap))
+    }
+
+    /// Overlap by `ratio` of the target size instead of a fixed number of
+    /// bytes, so the same setting suits any size.
+    ///
+    /// The overlap is `ratio * size` bytes, rounded to the nearest byte and
+    /// kept below the size, whichever order `.size()` and this are called
+    /// in. It replaces
+    /// [`overlap`](Self::overlap), last one set wins. A ratio that isn't at
+    /// least 0 and less than 1 is clamped to that range (NaN counts as 0),
+    /// and [`build`](Self::build) rejects it.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"Hello. World. Test.";
+    /// let by_ratio: Vec<_> = chunk(text).overlap_ratio(0.3).size(10).delimiters(b".").collect();
+    /// let by_bytes: Vec<_> = chunk(text).size(10).delimiters(b".").overlap(3).collect();
+    /// assert_eq!(by_ratio, by_bytes);
+    /// ```
+    pub fn overlap_ratio(self, ratio: f64) -> Self {
+        self.with(|s| s.set_overlap_ratio(ratio))
+    }
+
+    /// Never split inside a multi-byte UTF-8 sequence.
+    ///
+    /// Forced splits that would land inside a code point back up to its start
+    /// (or move past it if backing up would leave the chunk empty), and
+    /// overlapping starts move forward to the next code point. Off by default
+    /// since input is not required to be UTF-8.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = "ééééé".as_bytes(); // 2 bytes per character
+    /// let chunks: Vec<_> = chunk(text).size(3).delimiters(b"").utf8_safe(true).collect();
+    /// assert!(chunks.iter().all(|c| std::str::from_utf8(c).is_ok()));
+    /// ```
+    pub fn utf8_safe(self, enabled: bool) -> Self {
+        self.with(|s| s.utf8_safe = enabled)
+    }
+
+    /// Strip leading and trailing ASCII whitespace from every chunk.
+    ///
+    /// Boundaries are found as without trimming; each chunk's offsets then
+    /// shrink to its non-whitespace content, so the whitespace between chunks
+    /// belongs to none of them. A chunk that is only whitespace is kept as is,
+    /// so no chunk is ever empty.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"Intro.\n\n   Body text.\n";
+    /// let chunks: Vec<_> = chunk(text).size(16).delimiters(b"\n").trim(true).collect();
+    /// assert_eq!(chunks, vec![&b"Intro."[..], b"Body text."]);
+    /// ```
+    pub fn trim(self, enabled: bool) -> Self {
+        self.with(|s| s.trim = enabled)
+    }
+
+    /// Leave out chunks that are empty or only ASCII whitespace.
+    ///
+    /// Runs of delimiters (`"\n\n\n"`) or blank stretches longer than the
+    /// target size can produce chunks with nothing to embed; with this set
+    /// they are skipped everywhere (iteration, offsets, counts, random
+    /// access), and the remaining chunks are indexed without gaps. A chunk
+    /// that is only its dropped delimiter (see [`DelimiterMode::Drop`])
+    /// counts as empty. Off by default, so offsets cover the whole text.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"Title\n   \n   \nBody";
+    /// let chunks: Vec<_> = chunk(text).size(6).delimiters(b"\n").skip_empty(true).collect();
+    /// assert_eq!(chunks, vec![&b"Title\n"[..], b"Body"]);
+    /// ```
+    pub fn skip_empty(self, enabled: bool) -> Self {
+        self.with(|s| s.skip_empty = enabled)
+    }
+
+    /// Merge a trailing chunk smaller than `min_size` into the previous chunk.
+    ///
+    /// Measured in the same unit as [`size`](Self::size), so the merged last
+    /// chunk can be up to `size + min_size - 1` long.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let chunks: Vec<_> = chunk(b"Hello. World. Hi.")
+    ///     .size(14)
+    ///     .delimiters(b".")
+    ///     .min_size(5)
+    ///     .collect();
+    /// assert_eq!(chunks, vec![b"Hello. World. Hi.".as_slice()]);
+    /// ```
+    pub fn min_size(self, min_size: usize) -> Self {
+        self.with(|s| s.min_size = min_size)
+    }
+
+    /// Never emit a chunk longer than `max_size` bytes.
+    ///
+    /// Unlike [`size`](Self::size), which is a target, this is a strict cap for
+    /// APIs with hard payload limits. Chunks that would exceed it (through
+    /// forward fallback, tail merging, overlap or a character wider than the
+    /// target) are re-split at the last delimiter within `max_size` bytes,
+    /// or force-split at a code point boundary when there is none. Overlap
+    /// shrinks as needed to fit.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"averyveryverylongword next";
+    /// let chunks: Vec<_> = chunk(text)
+    ///     .size(8)
+    ///     .pattern(b" ")
+    ///     .forward_fallback()
+    ///     .max_size(10)
+    ///     .collect();
+    /// assert!(chunks.iter().all(|c| c.len() <= 10));
+    /// ```
+    pub fn max_size(self, max_size: usize) -> Self {
+        self.with(|s| s.max_size = Some(max_size.max(1)))
+    }
+
+    /// Check the settings, for when they come from user input.
+    ///
+    /// Returns the chunker unchanged, or the [`ChunkError`] for a size of 0,
+    /// an overlap of at least the size, or nothing to split at.
+    ///
+    /// ```
+    /// use chunk::{ChunkError, chunk};
+    ///
+    /// let chunker = chunk(b"Hello. World.").size(8).overlap(2).build().unwrap();
+    /// assert_eq!(chunker.count(), 2);
+    /// assert_eq!(chunk(b"Hello.").size(0).build().err(), Some(ChunkError::ZeroSize));
+    /// ```
+    pub fn build(self) -> Result<Self, ChunkError> {
+        self.settings.validate()?;
+        Ok(self)
+    }
+
+    /// Measure the target size in `unit` (bytes by default).
+    ///
+    /// With [`SizeUnit::Chars`], a size of 4096 means 4096 Unicode characters
+    /// regardless of how many bytes each takes; with the `tiktoken` feature,
+    /// [`SizeUnit::Cl100kBase`] and [`SizeUnit::O200kBase`] count OpenAI
+    /// tokens the way the models do. Offsets and overlap are still in bytes.
+    ///
+    /// ```
+    /// use chunk::{SizeUnit, chunk};
+    /// let text = "日本語のテキスト。次の文。".as_bytes();
+    /// let chunks: Vec<_> = chunk(text)
+    ///     .size(9)
+    ///     .patterns(&["。"])
+    ///     .size_unit(SizeUnit::Chars)
+    ///     .collect();
+    /// assert_eq!(chunks[0], "日本語のテキスト。".as_bytes());
+    /// ```
+    pub fn size_unit(self, unit: SizeUnit) -> Self {
+        self.with(|s| {
+            s.metric = match unit {
+                SizeUnit::Bytes => None,
+                _ => Some(Arc::new(unit)),
+            }
+        })
+    }
+
+    /// Measure the target size with `metric` instead of in bytes.
+    ///
+    /// The window searched for a delimiter is the longest prefix of the
+    /// remaining text that measures at most `size` units; see [`SizeMetric`]
+    /// for built-in metrics and closures. Overlap is still counted in bytes.
+    ///
+    /// ```
+    /// use chunk::{Chars, chunk};
+    /// let text = "ééééé ééééé".as_bytes();
+    /// let chunks: Vec<_> = chunk(text).size(6).delimiters(b" ").size_metric(Chars).collect();
+    /// assert_eq!(chunks[0], "ééééé ".as_bytes());
+    /// ```
+    pub fn size_metric(self, metric: impl SizeMetric + 'a) -> Self {
+        self.with(|s| s.metric = Some(Arc::new(metric)))
+    }
+
+    /// End each chunk at the delimiter `scorer` ranks highest.
+    ///
+    /// Every delimiter boundary in the target-size window is a candidate,
+    /// scored once per chunk; see [`BoundaryScorer`] for the arguments and
+    /// an example. This replaces the choice `slack` and
+    /// `delimiter_priority` would make. When the window holds no delimiter,
+    /// the usual search (forward fallback, hard split) applies, and
+    /// `max_size`, `min_size` and `utf8_safe` still adjust the result.
+    pub fn boundary_scorer(self, scorer: impl BoundaryScorer + 'a) -> Self {
+        self.with(|s| s.scorer = Some(Arc::new(scorer)))
+    }
+
+    /// Chunk only the bytes in `range` of the text, e.g. the body after a
+    /// header or what's left of an interrupted job.
+    ///
+    /// Offsets are still into the whole text, and the range is used as
+    /// given: it is clamped to the text, but not moved to a boundary, and
+    /// the last one set wins.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"Title\n\nOne. Two. Six.";
+    /// let offsets = chunk(text).size(5).delimiters(b".").range(7..).collect_offsets();
+    /// assert_eq!(offsets, vec![(7, 11), (11, 16), (16, 21)]);
+    /// ```
+    pub fn range(mut self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = bounds(range, self.text.len());
+        self.state = State::new(start, end);
+        self
+    }
+
+    /// Reset the chunker to start from the beginning.
+    pub fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    /// Apply a settings change, starting over from the beginning of the
+    /// range without any cached offsets, as the boundaries may have moved.
+    fn with(mut self, change: impl FnOnce(&mut BorrowedSettings<'a>)) -> Self {
+        change(&mut self.settings);
+        self.state = State::new(self.state.start, self.state.end);
+        self
+    }
+
+    /// The text up to where chunking stops.
+    fn data(&self) -> &'a [u8] {
+        &self.text[..self.state.end]
+    }
+
+    /// Get the next chunk with its index and offsets, or None if exhausted.
+    ///
+    /// Advances the same position as iterating, so the two can be mixed.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let mut chunker = chunk(b"Hello. World.").size(8).delimiters(b".");
+    /// let first = chunker.next_chunk_info().unwrap();
+    /// assert_eq!((first.index, first.start, first.end), (0, 0, 6));
+    /// assert_eq!(first.text(), Ok("Hello."));
+    /// assert_eq!(chunker.next_chunk_info().unwrap().bytes(), b" World.");
+    /// assert!(chunker.next_chunk_info().is_none());
+    /// ```
+    pub fn next_chunk_info(&mut self) -> Option<Chunk<'a>> {
+        self.try_next_chunk()?.ok()
+    }
+
+    /// Get the next chunk like [`next_chunk_info`](Self::next_chunk_info),
+    /// or the error where a window has no delimiter to split at with
+    /// [`FallbackStrategy::Error`].
+    ///
+    /// The other ways of chunking can't return the error, so they stop
+    /// before that chunk, as if the text ended there. The chunker doesn't
+    /// move past the error: calling again returns it again.
+    ///
+    /// ```
+    /// use chunk::{ChunkError, FallbackStrategy, chunk};
+    /// let mut chunker = chunk(b"Short. Unsplittable")
+    ///     .size(8)
+    ///     .delimiters(b".")
+    ///     .fallback(FallbackStrategy::Error);
+    /// assert_eq!(chunker.try_next_chunk().unwrap().unwrap().bytes(), b"Short.");
+    /// let err = chunker.try_next_chunk().unwrap().unwrap_err();
+    /// assert_eq!(err, ChunkError::NoBoundary { position: 6 });
+    /// assert!(chunker.next().is_none());
+    /// ```
+    pub fn try_next_chunk(&mut self) -> Option<Result<Chunk<'a>, ChunkError>> {
+        self.state.try_next(self.data(), &self.settings)
+    }
+
+    /// Get the last remaining chunk, or None if exhausted.
+    ///
+    /// Chunks are the same as going forward, just in reverse order: the first
+    /// call finds the boundaries of every remaining chunk (offsets only, no
+    /// copies), and both ends then pop from them. Mixing with
+    /// [`next_chunk_info`](Self::next_chunk_info) works like any
+    /// [`DoubleEndedIterator`].
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let mut chunker = chunk(b"One. Two. Three.").size(7).delimiters(b".");
+    /// let last = chunker.next_chunk_back().unwrap();
+    /// assert_eq!((last.index, last.bytes()), (2, &b" Three."[..]));
+    /// assert_eq!(chunker.next(), Some(&b"One."[..]));
+    /// assert_eq!(chunker.next_back(), Some(&b" Two."[..]));
+    /// assert_eq!(chunker.next(), None);
+    /// ```
+    pub fn next_chunk_back(&mut self) -> Option<Chunk<'a>> {
+        self.state.next_back(self.data(), &self.settings)
+    }
+
+    /// Get chunk `index` without iterating to it, or None if out of range.
+    ///
+    /// Offsets are computed up to `index` on the first lookup and cached, so
+    /// later lookups of earlier chunks are a vector index. The iterator's own
+    /// position doesn't move.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let mut chunker = chunk(b"One. Two. Three.").size(7).delimiters(b".");
+    /// assert_eq!(chunker.chunk_at(1).unwrap().bytes(), b" Two.");
+    /// assert!(chunker.chunk_at(3).is_none());
+    /// assert_eq!(chunker.next(), Some(&b"One."[..]));
+    /// ```
+    pub fn chunk_at(&mut self, index: usize) -> Option<Chunk<'a>> {
+        self.state.chunk_at(self.data(), &self.settings, index)
+    }
+
+    /// Move to the chunk containing byte `offset`, returning its index.
+    ///
+    /// The next chunk emitted is that chunk (the first one containing
+    /// `offset`, when chunks overlap). Returns None and leaves the position
+    /// unchanged if `offset` is past the end of the text.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let mut chunker = chunk(b"One. Two. Three.").size(7).delimiters(b".");
+    /// assert_eq!(chunker.seek(12), Some(2));
+    /// assert_eq!(chunker.next(), Some(&b" Three."[..]));
+    /// assert_eq!(chunker.seek(16), None);
+    /// ```
+    pub fn seek(&mut self, offset: usize) -> Option<usize> {
+        self.state.seek(self.data(), &self.settings, offset)
+    }
+
+    /// Iterate over all chunks as [`Chunk`]s.
+    ///
+    /// Like [`collect_offsets`](Self::collect_offsets), this always starts
+    /// from the beginning, however far the chunker has already advanced, as
+    /// [`OwnedChunker::iter_chunks`] does.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let mut chunker = chunk(b"a. b. c.").size(3).delimiters(b".");
+    /// chunker.next();
+    /// let starts: Vec<usize> = chunker.iter_chunks().map(|c| c.start).collect();
+    /// assert_eq!(starts, vec![0, 2, 5]);
+    /// ```
+    pub fn iter_chunks(mut self) -> Chunks<'a> {
+        self.reset();
+        Chunks { chunker: self }
+    }
+
+    /// Collect all chunk offsets as (start, end) pairs.
+    ///
+    /// Offsets always cover the whole text, regardless of how far the
+    /// iterator has already advanced.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"Hello. World. Test.";
+    /// let offsets = chunk(text).size(10).delimiters(b".").collect_offsets();
+    /// assert_eq!(offsets, vec![(0, 6), (6, 13), (13, 19)]);
+    /// ```
+    pub fn collect_offsets(&mut self) -> Vec<(usize, usize)> {
+        self.settings.collect_offsets(self.data(), self.state.start)
+    }
+
+    /// Collect all chunk offsets like [`collect_offsets`](Self::collect_offsets),
+    /// or the error where a window has no delimiter to split at with
+    /// [`FallbackStrategy::Error`].
+    ///
+    /// ```
+    /// use chunk::{ChunkError, FallbackStrategy, chunk};
+    /// let mut chunker = chunk(b"Short. Unsplittable").size(8).delimiters(b".");
+    /// assert_eq!(chunker.try_collect_offsets(), Ok(vec![(0, 6), (6, 14), (14, 19)]));
+    ///
+    /// let mut chunker = chunker.fallback(FallbackStrategy::Error);
+    /// assert_eq!(chunker.try_collect_offsets(), Err(ChunkError::NoBoundary { position: 6 }));
+    /// assert_eq!(chunker.collect_offsets(), vec![(0, 6)]);
+    /// ```
+    pub fn try_collect_offsets(&mut self) -> Result<Vec<(usize, usize)>, ChunkError> {
+        self.settings
+            .try_collect_offsets(self.data(), self.state.start)
+    }
+
+    /// Collect all chunk offsets like [`collect_offsets`](Chunker::collect_offsets),
+    /// checking `token` after every chunk and giving up once it's cancelled.
+    #[cfg(feature = "std")]
+    pub fn collect_offsets_until(
+        &mut self,
+        token: &CancelToken,
+    ) -> Result<Vec<(usize, usize)>, Cancelled> {
+        self.settings
+            .collect_offsets_until(self.data(), self.state.start, token)
+    }
+
+    /// Collect all chunk offsets with a stable content hash of each chunk,
+    /// as (start, end, hash) triples (requires the `hash` feature).
+    ///
+    /// Hashes come from [`chunk_hash`](crate::chunk_hash), so byte-identical
+    /// chunks share one across texts and runs, for deduplicating and
+    /// upserting into vector stores.
+    #[cfg(feature = "hash")]
+    pub fn collect_hashed_offsets(&mut self) -> Vec<(usize, usize, u64)> {
+        let text = self.data();
+        self.collect_offsets()
+            .into_iter()
+            .map(|(start, end)| (start, end, crate::hash::chunk_hash(&text[start..end])))
+            .collect()
+    }
+
+    /// Collect all chunk offsets with the detected language of each chunk,
+    /// as (start, end, ISO 639-3 code) triples (requires the `lang-detect`
+    /// feature).
+    ///
+    /// Languages come from [`detect_language`](crate::detect_language), so
+    /// chunks of a multilingual corpus can be routed to the right embedding
+    /// model.
+    #[cfg(feature = "lang-detect")]
+    pub fn collect_language_offsets(&mut self) -> Vec<(usize, usize, Option<&'static str>)> {
+        let text = self.data();
+        self.collect_offsets()
+            .into_iter()
+            .map(|(start, end)| (start, end, crate::lang::detect_language(&text[start..end])))
+            .collect()
+    }
+
+    /// Collect the chunks as children grouped into parents of at most
+    /// `parent_size` bytes, for parent document retrieval: search the small
+    /// children, then hand the model the parent holding each hit.
+    ///
+    /// The children are the chunks [`collect_offsets`](Chunker::collect_offsets)
+    /// gives, so set the child size with [`size`](Chunker::size). See
+    /// [`ParentChildChunks`] for how they're grouped.
+    pub fn collect_parent_child(&mut self, parent_size: usize) -> ParentChildChunks {
+        ParentChildChunks::from_children(self.collect_offsets(), parent_size)
+    }
+
+    /// Write every chunk to `writer` as a JSON line,
+    /// `{"index":..,"start":..,"end":..,"text":..}`, returning how many were
+    /// written (requires the `std` feature).
+    ///
+    /// Records are written as the chunks are found, so a corpus streams to
+    /// disk without collecting its chunks first. Invalid UTF-8 in `text` is
+    /// replaced with U+FFFD. Like [`collect_offsets`](Self::collect_offsets),
+    /// this covers the whole text and doesn't move the iterator.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let mut out = Vec::new();
+    /// let count = chunk(b"Hello. \"World\".").size(9).delimiters(b".").write_jsonl(&mut out)?;
+    /// assert_eq!(count, 2);
+    /// assert_eq!(
+    ///     String::from_utf8(out).unwrap(),
+    ///     "{\"index\":0,\"start\":0,\"end\":6,\"text\":\"Hello.\"}\n\
+    ///      {\"index\":1,\"start\":6,\"end\":15,\"text\":\" \\\"World\\\".\"}\n"
+    /// );
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_jsonl<W: std::io::Write>(&mut self, writer: W) -> std::io::Result<usize> {
+        self.settings
+            .write_jsonl(self.data(), self.state.start, writer)
+    }
+
+    /// Count all chunks without collecting them.
+    ///
+    /// Finds the same boundaries as [`collect_offsets`](Self::collect_offsets)
+    /// but allocates nothing, for progress bars and cost estimates. Like
+    /// `collect_offsets`, this covers the whole text and doesn't move the
+    /// iterator.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let mut chunker = chunk(b"Hello. World. Test.").size(10).delimiters(b".");
+    /// assert_eq!(chunker.count_chunks(), 3);
+    /// assert_eq!(chunker.next(), Some(&b"Hello."[..]));
+    /// ```
+    pub fn count_chunks(&mut self) -> usize {
+        self.state.count_chunks(self.data(), &self.settings)
+    }
+
+    /// Chunk size statistics over the whole text, see [`ChunkStats`].
+    ///
+    /// Like [`collect_offsets`](Self::collect_offsets), this doesn't move
+    /// the iterator.
+    pub fn stats(&mut self) -> ChunkStats {
+        self.settings.stats(self.data(), self.state.start)
+    }
+}
+
+impl<'a> Iterator for Chunker<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_chunk_info().map(|chunk| chunk.bytes())
+    }
+}
+
+impl DoubleEndedIterator for Chunker<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_chunk_back().map(|chunk| chunk.bytes())
+    }
+}
+
+/// Iterator over [`Chunk`]s, created by [`Chunker::iter_chunks`].
+pub struct Chunks<'a> {
+    chunker: Chunker<'a>,
+}
+
+impl<'a> Chunks<'a> {
+    /// Pair every chunk with the line and column where it starts.
+    ///
+    /// Newlines are counted as the chunks go by, so it takes no extra pass
+    /// over the text.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"INFO start\nWARN disk\nINFO done\n";
+    /// let lines: Vec<usize> = chunk(text)
+    ///     .size(12)
+    ///     .delimiters(b"\n")
+    ///     .iter_chunks()
+    ///     .with_positions()
+    ///     .map(|(_, position)| position.line)
+    ///     .collect();
+    /// assert_eq!(lines, vec![1, 2, 3]);
+    /// ```
+    pub fn with_positions(self) -> Positioned<'a, Self> {
+        Positioned::new(self.chunker.text, self)
+    }
+
+    /// Call `callback` with the [`Progress`] so far every `every` chunks,
+    /// and once more when the chunks run out, with all of the text
+    /// processed.
+    ///
+    /// ```
+    /// use chunk::chunk;
+    /// let text = b"One. Two. Six.";
+    /// let mut reports = Vec::new();
+    /// let chunks = chunk(text)
+    ///     .size(6)
+    ///     .delimiters(b".")
+    ///     .iter_chunks()
+    ///     .with_progress(2, |progress| reports.push((progress.bytes, progress.chunks)))
+    ///     .count();
+    /// assert_eq!(chunks, 3);
+    /// assert_eq!(reports, vec![(9, 2), (14, 3)]);
+    /// ```
+    pub fn with_progress<F: FnMut(Progress)>(
+        self,
+        every: usize,
+        callback: F,
+    ) -> WithProgress<Self, F> {
+        WithProgress::new(self.chunker.data().len(), self, every, callback)
+    }
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = Chunk<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunker.next_chunk_info()
+    }
+}
+
+impl DoubleEndedIterator for Chunks<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.chunker.next_chunk_back()
+    }
+}
+
+/// Owned chunker for FFI bindings (Python, WASM).
+///
+/// Unlike [`Chunker`], this owns its data and returns owned chunks.
+/// Use this when you need to cross FFI boundaries where lifetimes can't be tracked.
+///
+/// # Example
+///
+/// ```
+/// use chunk::OwnedChunker;
+///
+/// let text = b"Hello world. How are you?".to_vec();
+/// let mut chunker = OwnedChunker::new(text)
+///     .size(15)
+///     .delimiters(b"\n.?".to_vec());
+///
+/// while let Some(chunk) = chunker.next_chunk() {
+///     println!("{:?}", chunk);
+/// }
+/// ```
+///
+/// Cloning shares the text and settings rather than copying them, so one
+/// text can be chunked from several threads; see
+/// [`split_range`](Self::split_range).
+#[derive(Clone)]
+pub struct OwnedChunker {
+    text: Arc<dyn AsRef<[u8]> + Send + Sync>,
+    settings: Arc<SharedSettings>,
+    state: State,
+}
+
+impl OwnedChunker {
+    /// Create a new owned chunker with the given text.
+    pub fn new(text: Vec<u8>) -> Self {
+        Self::from_source(text)
+    }
+
+    /// Create an owned chunker over `text` with `config`, once the config
+    /// passes [`ChunkerConfig::validate`].
+    ///
+    /// ```
+    /// use chunk::{ChunkError, ChunkerConfig, OwnedChunker};
+    ///
+    /// let config = ChunkerConfig::new().size(8).delimiters(b".");
+    /// let mut chunker = OwnedChunker::try_new(b"Hello. World.".to_vec(), &config).unwrap();
+    /// assert_eq!(chunker.collect_offsets(), vec![(0, 6), (6, 13)]);
+    ///
+    /// let config = config.overlap(8);
+    /// let err = OwnedChunker::try_new(b"Hello.".to_vec(), &config).err();
+    /// assert_eq!(err, Some(ChunkError::OverlapTooLarge { overlap: 8, size: 8 }));
+    /// ```
+    pub fn try_new(text: Vec<u8>, config: &ChunkerConfig) -> Result<Self, ChunkError> {
+        config.validate()?;
+        Ok(config.owned_chunker(text))
+    }
+
+    /// Create an owned chunker over any byte source, without copying it.
+    ///
+    /// The source is kept alive for the chunker's lifetime, so e.g. a binding
+    /// can hold a foreign buffer instead of copying it into a `Vec`.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use chunk::OwnedChunker;
+    ///
+    /// let shared: Arc<[u8]> = Arc::from(&b"Hello. World."[..]);
+    /// let mut chunker = OwnedChunker::from_source(shared.clone())
+    ///     .size(8)
+    ///     .delimiters(b".".to_vec());
+    /// assert_eq!(chunker.collect_offsets(), vec![(0, 6), (6, 13)]);
+    /// ```
+    pub fn from_source<T: AsRef<[u8]> + Send + Sync + 'static>(text: T) -> Self {
+        let end = text.as_ref().len();
+        Self {
+            text: Arc::new(text),
+            settings: Arc::new(Settings::new()),
+            state: State::new(0, end),
+        }
+    }
+
+    /// Set the target chunk size in bytes.
+    pub fn size(self, size: usize) -> Self {
+        self.with(|s| s.set_size(size))
+    }
+
+    /// Set single-byte delimiters to split on.
+    ///
+    /// Mutually exclusive with `pattern()` - last one set wins.
+    pub fn delimiters(self, delimiters: Vec<u8>) -> Self {
+        self.with(|s| s.set_delimiters(Cow::Owned(delimiters)))
+    }
+
+    /// Set a multi-byte pattern to split on.
+    ///
+    /// Use this for multi-byte delimiters like UTF-8 characters (e.g., metaspace `▁`).
+    /// Mutually exclusive with `delimiters()` - last one set wins.
+    pub fn pattern(self, pattern: Vec<u8>) -> Self {
+        self.with(|s| s.set_pattern(Cow::Owned(pattern)))
+    }
+
+    /// Set multiple multi-byte patterns, composable with `.delimiters()`.
+    ///
+    /// See [`Chunker::patterns`] for details.
+    pub fn patterns<P: AsRef<[u8]>>(self, patterns: &[P]) -> Self {
+        self.with(|s| {
+            s.multi_searcher = MultiPatternSearcher::from_patterns(patterns).map(Arc::new)
+        })
+    }
+
+    /// Split at a named class of delimiters.
+    ///
+    /// See [`Chunker::delimiter_class`] for details.
+    pub fn delimiter_class(self, class: Delimiters) -> Self {
+        let mode = match class.prefix() {
+            true => DelimiterMode::KeepLeading,
+            false => DelimiterMode::KeepTrailing,
+        };
+        self.delimiters(class.bytes().to_vec())
+            .patterns(class.patterns())
+            .delimiter_mode(mode)
+    }
+
+    /// Put delimiter at the start of the next chunk (prefix mode).
+    pub fn prefix(self) -> Self {
+        self.delimiter_mode(DelimiterMode::KeepLeading)
+    }
+
+    /// Put delimiter at the end of the current chunk (suffix mode, default).
+    pub fn suffix(self) -> Self {
+        self.delimiter_mode(DelimiterMode::KeepTrailing)
+    }
+
+    /// Choose where the delimiter at each split goes.
+    ///
+    /// See [`Chunker::delimiter_mode`] for details.
+    pub fn delimiter_mode(self, mode: DelimiterMode) -> Self {
+        self.with(|s| s.set_delimiter_mode(mode))
+    }
+
+    /// Enable consecutive delimiter/pattern handling.
+    ///
+    /// When splitting, ensures we split at the START of a consecutive run
+    /// of the same delimiter/pattern, not in the middle.
+    /// Works with both `.pattern()` and `.delimiters()`.
+    pub fn consecutive(self) -> Self {
+        self.with(|s| s.consecutive = true)
+    }
+
+    /// Enable forward fallback search.
+    ///
+    /// When no delimiter/pattern is found in the backward search window,
+    /// search forward from target_end instead of doing a hard split.
     /// Works with both `.pattern()` and `.delimiters()`.
-    pub fn forward_fallback(mut self) -> Self {
-        self.forward_fallback = true;
+    pub fn forward_fallback(self) -> Self {
+        self.with(|s| s.forward_fallback = true)
+    }
+
+    /// Choose where to split when the window has no delimiter.
+    ///
+    /// See [`Chunker::fallback`] for details.
+    pub fn fallback(self, strategy: FallbackStrategy) -> Self {
+        self.with(|s| s.fallback = strategy)
+    }
+
+    /// Treat the order of `.delimiters()` as a priority.
+    ///
+    /// See [`Chunker::delimiter_priority`] for details.
+    pub fn delimiter_priority(self, enabled: bool) -> Self {
+        self.with(|s| s.delimiter_priority = enabled)
+    }
+
+    /// Split at the boundary nearest the target size, up to `slack` units
+    /// either side of it.
+    ///
+    /// See [`Chunker::slack`] for details.
+    pub fn slack(self, slack: usize) -> Self {
+        self.with(|s| s.slack = slack)
+    }
+
+    /// Even out chunk sizes instead of filling each chunk greedily.
+    ///
+    /// See [`Chunker::balanced`] for details.
+    pub fn balanced(self, enabled: bool) -> Self {
+        self.with(|s| s.balanced = enabled)
+    }
+
+    /// Group up to `lines` complete lines per chunk.
+    ///
+    /// See [`Chunker::lines_per_chunk`] for details.
+    pub fn lines_per_chunk(self, lines: usize) -> Self {
+        self.with(|s| s.lines = Some(lines.max(1)))
+    }
+
+    /// Carry `overlap` bytes of trailing context from each chunk into the next.
+    ///
+    /// See [`Chunker::overlap`] for details.
+    pub fn overlap(self, overlap: usize) -> Self {
+        self.with(|s| s.set_overlap(overlap))
+    }
+
+    /// Overlap by `ratio` of the target size.
+    ///
+    /// See [`Chunker::overlap_ratio`] for details.
+    pub fn overlap_ratio(self, ratio: f64) -> Self {
+        self.with(|s| s.set_overlap_ratio(ratio))
+    }
+
+    /// Never split inside a multi-byte UTF-8 sequence.
+    ///
+    /// See [`Chunker::utf8_safe`] for details.
+    pub fn utf8_safe(self, enabled: bool) -> Self {
+        self.with(|s| s.utf8_safe = enabled)
+    }
+
+    /// Strip leading and trailing ASCII whitespace from every chunk.
+    ///
+    /// See [`Chunker::trim`] for details.
+    pub fn trim(self, enabled: bool) -> Self {
+        self.with(|s| s.trim = enabled)
+    }
+
+    /// Leave out chunks that are empty or only ASCII whitespace.
+    ///
+    /// See [`Chunker::skip_empty`] for details.
+    pub fn skip_empty(self, enabled: bool) -> Self {
+        self.with(|s| s.skip_empty = enabled)
+    }
+
+    /// Merge a trailing chunk smaller than `min_size` into the previous chunk.
+    ///
+    /// See [`Chunker::min_size`] for details.
+    pub fn min_size(self, min_size: usize) -> Self {
+        self.with(|s| s.min_size = min_size)
+    }
+
+    /// Never emit a chunk longer than `max_size` bytes.
+    ///
+    /// See [`Chunker::max_size`] for details.
+    pub fn max_size(self, max_size: usize) -> Self {
+        self.with(|s| s.max_size = Some(max_size.max(1)))
+    }
+
+    /// Check the settings, for when they come from user input.
+    ///
+    /// See [`Chunker::build`] for details.
+    pub fn build(self) -> Result<Self, ChunkError> {
+        self.settings.validate()?;
+        Ok(self)
+    }
+
+    /// Measure the target size in `unit` (bytes by default).
+    ///
+    /// See [`Chunker::size_unit`] for details.
+    pub fn size_unit(self, unit: SizeUnit) -> Self {
+        self.with(|s| {
+            s.metric = match unit {
+                SizeUnit::Bytes => None,
+                _ => Some(Arc::new(unit)),
+            }
+        })
+    }
+
+    /// Measure the target size with `metric` instead of in bytes.
+    ///
+    /// See [`Chunker::size_metric`] for details.
+    pub fn size_metric(self, metric: impl SizeMetric + Send + Sync + 'static) -> Self {
+        self.with(|s| s.metric = Some(Arc::new(metric)))
+    }
+
+    /// End each chunk at the delimiter `scorer` ranks highest.
+    ///
+    /// See [`Chunker::boundary_scorer`] for details.
+    pub fn boundary_scorer(self, scorer: impl BoundaryScorer + Send + Sync + 'static) -> Self {
+        self.with(|s| s.scorer = Some(Arc::new(scorer)))
+    }
+
+    /// Apply a settings change, copying the settings first if a clone
+    /// shares them.
+    ///
+    /// See [`Chunker::with`].
+    fn with(mut self, change: impl FnOnce(&mut SharedSettings)) -> Self {
+        change(Arc::make_mut(&mut self.settings));
+        self.state = State::new(self.state.start, self.state.end);
+        self
+    }
+
+    /// The text up to where chunking stops, the settings and the state,
+    /// borrowed apart.
+    fn parts(&mut self) -> (&[u8], &SharedSettings, &mut State) {
+        let text = &(*self.text).as_ref()[..self.state.end];
+        (text, &self.settings, &mut self.state)
+    }
+
+    /// Get the next chunk, or None if exhausted.
+    pub fn next_chunk(&mut self) -> Option<Vec<u8>> {
+        self.next_chunk_info().map(|chunk| chunk.bytes().to_vec())
+    }
+
+    /// Get the next chunk with its index and offsets, or None if exhausted.
+    ///
+    /// See [`Chunker::next_chunk_info`] for details.
+    pub fn next_chunk_info(&mut self) -> Option<Chunk<'_>> {
+        self.try_next_chunk()?.ok()
+    }
+
+    /// Get the next chunk, or the error where a window has no delimiter to
+    /// split at with [`FallbackStrategy::Error`].
+    ///
+    /// See [`Chunker::try_next_chunk`] for details.
+    pub fn try_next_chunk(&mut self) -> Option<Result<Chunk<'_>, ChunkError>> {
+        let (text, settings, state) = self.parts();
+        state.try_next(text, settings)
+    }
+
+    /// Get the last remaining chunk, or None if exhausted.
+    ///
+    /// See [`Chunker::next_chunk_back`] for details.
+    pub fn next_chunk_back(&mut self) -> Option<Chunk<'_>> {
+        let (text, settings, state) = self.parts();
+        state.next_back(text, settings)
+    }
+
+    /// How many chunks have been taken from the front and from the back.
+    ///
+    /// With the text and configuration this is all of the iteration state,
+    /// e.g. to rebuild a chunker elsewhere with
+    /// [`set_cursor`](Self::set_cursor).
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.state.index, self.state.taken_back)
+    }
+
+    /// Move to where `front` chunks have been taken from the front and
+    /// `back` from the back, as [`cursor`](Self::cursor) reports.
+    ///
+    /// ```
+    /// use chunk::OwnedChunker;
+    ///
+    /// let text = b"One. Two. Three. Four.".to_vec();
+    /// let mut chunker = OwnedChunker::new(text.clone()).size(6).delimiters(b".".to_vec());
+    /// chunker.next_chunk();
+    /// chunker.next_chunk_back();
+    ///
+    /// let mut copy = OwnedChunker::new(text).size(6).delimiters(b".".to_vec());
+    /// copy.set_cursor(chunker.cursor().0, chunker.cursor().1);
+    /// assert_eq!(copy.next_chunk(), chunker.next_chunk());
+    /// assert_eq!(copy.next_chunk_back(), chunker.next_chunk_back());
+    /// ```
+    pub fn set_cursor(&mut self, front: usize, back: usize) {
+        self.reset();
+        for _ in 0..front {
+            if self.next_chunk_info().is_none() {
+                break;
+            }
+        }
+        for _ in 0..back {
+            if self.next_chunk_back().is_none() {
+                break;
+            }
+        }
+    }
+
+    /// Get chunk `index` without iterating to it, or None if out of range.
+    ///
+    /// See [`Chunker::chunk_at`] for details.
+    pub fn chunk_at(&mut self, index: usize) -> Option<Chunk<'_>> {
+        let (text, settings, state) = self.parts();
+        state.chunk_at(text, settings, index)
+    }
+
+    /// Move to the chunk containing byte `offset`, returning its index.
+    ///
+    /// See [`Chunker::seek`] for details.
+    pub fn seek(&mut self, offset: usize) -> Option<usize> {
+        let (text, settings, state) = self.parts();
+        state.seek(text, settings, offset)
+    }
+
+    /// Iterate over all chunks as [`Chunk`]s, borrowing the text.
+    ///
+    /// Like [`collect_offsets`](Self::collect_offsets), this always starts
+    /// from the beginning and doesn't move the chunker's own position. See
+    /// [`Chunker::iter_chunks`].
+    pub fn iter_chunks(&mut self) -> OwnedChunks<'_> {
+        let start = self.state.start;
+        OwnedChunks {
+            splits: self.settings.splits(self.data(), start, start),
+            index: 0,
+        }
+    }
+
+    /// Chunk only the bytes in `range` of the text.
+    ///
+    /// See [`Chunker::range`] for details.
+    pub fn range(mut self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = bounds(range, self.text().len());
+        self.state = State::new(start, end);
         self
     }
 
-    /// Initialize lookup table if needed.
-    fn init(&mut self) {
-        if !self.initialized {
-            self.table = build_table(&self.delimiters);
-            self.initialized = true;
-        }
+    /// Reset the chunker to start from the beginning.
+    pub fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    /// Get a reference to the underlying text.
+    pub fn text(&self) -> &[u8] {
+        (*self.text).as_ref()
+    }
+
+    /// The text up to where chunking stops.
+    fn data(&self) -> &[u8] {
+        &self.text()[..self.state.end]
+    }
+
+    /// A chunker over `start..end` of the same text and with the same
+    /// settings, each end moved forward to the next delimiter boundary.
+    ///
+    /// The piece shares the text instead of copying it, so e.g. an
+    /// `Arc<[u8]>` can be cut into ranges chunked on separate threads.
+    /// Adjacent ranges move the end they share to the same boundary, so the
+    /// pieces cover the text without gaps or overlap, though chunks next to a
+    /// cut can differ from chunking the text whole. The range is clamped to
+    /// this chunker's own, the piece starts from the beginning of it, and its
+    /// offsets are still into the whole [`text`](Self::text).
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use chunk::OwnedChunker;
+    ///
+    /// let text: Arc<[u8]> = Arc::from(&b"One. Two. Six. Ten."[..]);
+    /// let chunker = OwnedChunker::from_source(text).size(5).delimiters(b".".to_vec());
+    /// let pieces = [chunker.split_range(0, 7), chunker.split_range(7, 19)];
+    /// let offsets: Vec<_> = std::thread::scope(|scope| {
+    ///     let handles: Vec<_> = pieces
+    ///         .into_iter()
+    ///         .map(|mut piece| scope.spawn(move || piece.collect_offsets()))
+    ///         .collect();
+    ///     handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+    /// });
+    /// assert_eq!(offsets, vec![(0, 4), (4, 9), (9, 14), (14, 19)]);
+    /// ```
+    pub fn split_range(&self, start: usize, end: usize) -> Self {
+        let (first, last) = (self.state.start, self.state.end);
+        let end = self.align(end.clamp(first, last));
+        let start = self.align(start.clamp(first, last));
+        self.clone().range(start..end)
+    }
+
+    /// The first delimiter boundary at or after `at`: where this chunker
+    /// starts, just past a delimiter (just before one in prefix mode), or the
+    /// end of the text.
+    fn align(&self, at: usize) -> usize {
+        let text = self.data();
+        let boundaries = self.settings.boundaries();
+        (at..text.len())
+            .find(|&at| at == self.state.start || boundaries.is_delimited(text, at))
+            .unwrap_or(text.len())
+    }
+
+    /// Collect all chunks as owned byte vectors.
+    ///
+    /// Like [`collect_offsets`](Self::collect_offsets), this covers the whole
+    /// text in one call, for bindings that want materialized chunks without
+    /// crossing the FFI boundary per chunk.
+    ///
+    /// ```
+    /// use chunk::OwnedChunker;
+    /// let mut chunker = OwnedChunker::new(b"Hello. World.".to_vec())
+    ///     .size(8)
+    ///     .delimiters(b".".to_vec());
+    /// assert_eq!(chunker.collect_chunks(), vec![b"Hello.".to_vec(), b" World.".to_vec()]);
+    /// ```
+    pub fn collect_chunks(&mut self) -> Vec<Vec<u8>> {
+        self.iter_chunks()
+            .map(|chunk| chunk.bytes().to_vec())
+            .collect()
+    }
+
+    /// Count all chunks without collecting them.
+    ///
+    /// See [`Chunker::count_chunks`] for details.
+    pub fn count_chunks(&mut self) -> usize {
+        let (text, settings, state) = self.parts();
+        state.count_chunks(text, settings)
+    }
+
+    /// Chunk size statistics over the whole text.
+    ///
+    /// See [`Chunker::stats`] for details.
+    pub fn stats(&mut self) -> ChunkStats {
+        self.settings.stats(self.data(), self.state.start)
+    }
+
+    /// Collect all chunk offsets with a stable content hash of each chunk.
+    ///
+    /// See [`Chunker::collect_hashed_offsets`] for details.
+    #[cfg(feature = "hash")]
+    pub fn collect_hashed_offsets(&mut self) -> Vec<(usize, usize, u64)> {
+        let offsets = self.collect_offsets();
+        let text = self.data();
+        offsets
+            .into_iter()
+            .map(|(start, end)| (start, end, crate::hash::chunk_hash(&text[start..end])))
+            .collect()
+    }
+
+    /// Collect all chunk offsets with the detected language of each chunk.
+    ///
+    /// See [`Chunker::collect_language_offsets`] for details.
+    #[cfg(feature = "lang-detect")]
+    pub fn collect_language_offsets(&mut self) -> Vec<(usize, usize, Option<&'static str>)> {
+        let offsets = self.collect_offsets();
+        let text = self.data();
+        offsets
+            .into_iter()
+            .map(|(start, end)| (start, end, crate::lang::detect_language(&text[start..end])))
+            .collect()
+    }
+
+    /// Collect the chunks as children grouped into parents of at most
+    /// `parent_size` bytes.
+    ///
+    /// See [`Chunker::collect_parent_child`] for details.
+    pub fn collect_parent_child(&mut self, parent_size: usize) -> ParentChildChunks {
+        ParentChildChunks::from_children(self.collect_offsets(), parent_size)
+    }
+
+    /// Write every chunk to `writer` as a JSON line, returning how many were
+    /// written.
+    ///
+    /// See [`Chunker::write_jsonl`] for details.
+    #[cfg(feature = "std")]
+    pub fn write_jsonl<W: std::io::Write>(&mut self, writer: W) -> std::io::Result<usize> {
+        self.settings
+            .write_jsonl(self.data(), self.state.start, writer)
+    }
+
+    /// Collect all chunk offsets as (start, end) pairs.
+    /// This is more efficient for FFI as it returns all offsets in one call.
+    pub fn collect_offsets(&mut self) -> Vec<(usize, usize)> {
+        self.settings.collect_offsets(self.data(), self.state.start)
+    }
+
+    /// Collect all chunk offsets, or the error where a window has no
+    /// delimiter to split at with [`FallbackStrategy::Error`].
+    ///
+    /// See [`Chunker::try_collect_offsets`] for details.
+    pub fn try_collect_offsets(&mut self) -> Result<Vec<(usize, usize)>, ChunkError> {
+        self.settings
+            .try_collect_offsets(self.data(), self.state.start)
+    }
+}
+
+/// Iterates over (start, end) offsets, advancing the same position as
+/// [`next_chunk`](OwnedChunker::next_chunk).
+///
+/// The chunker owns its text, so it can't hand out borrowed slices from
+/// `next`; slice [`text`](OwnedChunker::text) with the offsets, or use
+/// [`iter_chunks`](OwnedChunker::iter_chunks) for [`Chunk`]s.
+///
+/// ```
+/// use chunk::OwnedChunker;
+/// let chunker = OwnedChunker::new(b"Hello. World. Test.".to_vec())
+///     .size(8)
+///     .delimiters(b".".to_vec());
+/// let lengths: Vec<usize> = chunker.map(|(start, end)| end - start).collect();
+/// assert_eq!(lengths, vec![6, 7, 6]);
+/// ```
+impl Iterator for OwnedChunker {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_chunk_info().map(|chunk| (chunk.start, chunk.end))
+    }
+}
+
+impl DoubleEndedIterator for OwnedChunker {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_chunk_back().map(|chunk| (chunk.start, chunk.end))
+    }
+}
+
+/// Iterator over [`Chunk`]s, created by [`OwnedChunker::iter_chunks`].
+pub struct OwnedChunks<'c> {
+    splits: Splits<'c, 'static, dyn SizeMetric + Send + Sync, dyn BoundaryScorer + Send + Sync>,
+    index: usize,
+}
+
+impl<'c> OwnedChunks<'c> {
+    /// Pair every chunk with the line and column where it starts.
+    ///
+    /// See [`Chunks::with_positions`].
+    pub fn with_positions(self) -> Positioned<'c, Self> {
+        Positioned::new(self.splits.text, self)
+    }
+
+    /// Report the [`Progress`] so far every `every` chunks.
+    ///
+    /// See [`Chunks::with_progress`].
+    pub fn with_progress<F: FnMut(Progress)>(
+        self,
+        every: usize,
+        callback: F,
+    ) -> WithProgress<Self, F> {
+        WithProgress::new(self.splits.text.len(), self, every, callback)
+    }
+}
+
+impl<'c> Iterator for OwnedChunks<'c> {
+    type Item = Chunk<'c>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (settings, text) = (self.splits.settings, self.splits.text);
+        let (start, split_at) = self
+            .splits
+            .by_ref()
+            .map_while(Result::ok)
+            .find(|&(start, split_at)| !settings.skips(text, start, split_at))?;
+        self.index += 1;
+        Some(settings.emit(text, self.index - 1, start, split_at))
     }
+}
 
-    /// Get the next chunk, or None if exhausted.
-    pub fn next_chunk(&mut self) -> Option<Vec<u8>> {
-        self.init();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if self.pos >= self.text.len() {
-            return None;
-        }
+    #[test]
+    fn test_basic_chunking() {
+        let text = b"Hello. World. Test.";
+        let chunks: Vec<_> = chunk(text).size(10).delimiters(b".").collect();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0], b"Hello.");
+        assert_eq!(chunks[1], b" World.");
+        assert_eq!(chunks[2], b" Test.");
+    }
 
-        let remaining = self.text.len() - self.pos;
+    #[test]
+    fn test_newline_delimiter() {
+        let text = b"Line one\nLine two\nLine three";
+        let chunks: Vec<_> = chunk(text).size(15).delimiters(b"\n").collect();
+        assert_eq!(chunks[0], b"Line one\n");
+        assert_eq!(chunks[1], b"Line two\n");
+        assert_eq!(chunks[2], b"Line three");
+    }
 
-        // Last chunk - return remainder
-        if remaining <= self.target_size {
-            let chunk = self.text[self.pos..].to_vec();
-            self.pos = self.text.len();
-            return Some(chunk);
+    #[test]
+    fn test_multiple_delimiters() {
+        let text = b"Hello? World. Yes!";
+        let chunks: Vec<_> = chunk(text).size(10).delimiters(b".?!").collect();
+        assert_eq!(chunks[0], b"Hello?");
+    }
+
+    #[test]
+    fn test_four_delimiters() {
+        let text = b"A. B? C! D; E";
+        let chunks: Vec<_> = chunk(text).size(5).delimiters(b".?!;").collect();
+        assert!(chunks.len() >= 2);
+    }
+
+    #[test]
+    fn test_seven_delimiters_uses_table() {
+        let text = b"A. B? C! D; E: F, G";
+        let chunks: Vec<_> = chunk(text).size(5).delimiters(b".?!;:, ").collect();
+        assert_eq!(chunks.concat(), text.to_vec());
+        assert!(chunks.iter().all(|c| c.len() <= 5));
+    }
+
+    #[test]
+    fn test_no_delimiter_hard_split() {
+        let text = b"abcdefghij";
+        let chunks: Vec<_> = chunk(text).size(5).delimiters(b".").collect();
+        assert_eq!(chunks[0], b"abcde");
+        assert_eq!(chunks[1], b"fghij");
+    }
+
+    #[test]
+    fn test_empty_text() {
+        let text = b"";
+        let chunks: Vec<_> = chunk(text).size(10).delimiters(b".").collect();
+        assert_eq!(chunks.len(), 0);
+    }
+
+    #[test]
+    fn test_text_smaller_than_target() {
+        let text = b"Small";
+        let chunks: Vec<_> = chunk(text).size(100).delimiters(b".").collect();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0], b"Small");
+    }
+
+    #[test]
+    fn test_total_bytes_preserved() {
+        let text = b"The quick brown fox jumps over the lazy dog. How vexingly quick!";
+        let chunks: Vec<_> = chunk(text).size(20).delimiters(b"\n.?!").collect();
+        let total: usize = chunks.iter().map(|c| c.len()).sum();
+        assert_eq!(total, text.len());
+    }
+
+    #[test]
+    fn test_defaults() {
+        let text = b"Hello world. This is a test.";
+        let chunks: Vec<_> = chunk(text).collect();
+        assert!(!chunks.is_empty());
+    }
+
+    #[test]
+    fn test_prefix_mode() {
+        let text = b"Hello World Test";
+        let chunks: Vec<_> = chunk(text).size(8).delimiters(b" ").prefix().collect();
+        assert_eq!(chunks[0], b"Hello");
+        assert_eq!(chunks[1], b" World");
+        assert_eq!(chunks[2], b" Test");
+    }
+
+    #[test]
+    fn test_suffix_mode() {
+        let text = b"Hello World Test";
+        let chunks: Vec<_> = chunk(text).size(8).delimiters(b" ").suffix().collect();
+        assert_eq!(chunks[0], b"Hello ");
+        assert_eq!(chunks[1], b"World ");
+        assert_eq!(chunks[2], b"Test");
+    }
+
+    #[test]
+    fn test_drop_delimiters() {
+        let text = b"Hello World Test";
+        let chunks: Vec<_> = chunk(text)
+            .size(8)
+            .delimiters(b" ")
+            .delimiter_mode(DelimiterMode::Drop)
+            .collect();
+        assert_eq!(chunks, vec![&b"Hello"[..], b"World", b"Test"]);
+
+        let text = "one▁two▁three▁".as_bytes();
+        let chunks: Vec<_> = chunk(text)
+            .size(8)
+            .pattern("▁".as_bytes())
+            .delimiter_mode(DelimiterMode::Drop)
+            .collect();
+        assert_eq!(chunks, vec![&b"one"[..], b"two", b"three"]);
+
+        // Only the (longest) delimiter at the split is dropped
+        let chunks: Vec<_> = chunk(b"a\n\nb..c")
+            .size(3)
+            .delimiters(b".")
+            .patterns(&["\n", "\n\n"])
+            .delimiter_mode(DelimiterMode::Drop)
+            .collect();
+        assert_eq!(chunks, vec![&b"a"[..], b"b.", b"c"]);
+
+        // A chunk of only a delimiter keeps it
+        let chunks: Vec<_> = chunk(b"a..b")
+            .size(1)
+            .delimiters(b".")
+            .delimiter_mode(DelimiterMode::Drop)
+            .collect();
+        assert_eq!(chunks, vec![&b"a"[..], b".", b".", b"b"]);
+
+        // Prefix and suffix switch back to keeping the delimiter
+        let chunks: Vec<_> = chunk(b"Hello World")
+            .size(8)
+            .delimiters(b" ")
+            .delimiter_mode(DelimiterMode::Drop)
+            .prefix()
+            .collect();
+        assert_eq!(chunks, vec![&b"Hello"[..], b" World"]);
+    }
+
+    #[test]
+    fn test_drop_delimiters_all_access_paths() {
+        let text = b"One. Two. Three. Four.";
+        let chunker = || {
+            chunk(text)
+                .size(7)
+                .delimiters(b".")
+                .overlap(2)
+                .delimiter_mode(DelimiterMode::Drop)
+        };
+        let offsets = chunker().collect_offsets();
+        let forward: Vec<_> = chunker().iter_chunks().map(|c| (c.start, c.end)).collect();
+        assert_eq!(forward, offsets);
+        let mut backward: Vec<_> = chunker()
+            .iter_chunks()
+            .rev()
+            .map(|c| (c.start, c.end))
+            .collect();
+        backward.reverse();
+        assert_eq!(backward, offsets);
+        let mut random = chunker();
+        let at: Vec<_> = (0..offsets.len())
+            .map(|i| random.chunk_at(i).map(|c| (c.start, c.end)).unwrap())
+            .collect();
+        assert_eq!(at, offsets);
+        assert!(
+            offsets
+                .iter()
+                .all(|&(start, end)| text[end - 1] != b'.' && end > start)
+        );
+        assert_eq!(chunker().stats().forced_splits, 0);
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(7)
+            .delimiters(b".".to_vec())
+            .overlap(2)
+            .delimiter_mode(DelimiterMode::Drop);
+        assert_eq!(owned.collect_offsets(), offsets);
+        let owned_chunks: Vec<_> = owned.iter_chunks().map(|c| (c.start, c.end)).collect();
+        assert_eq!(owned_chunks, offsets);
+        assert_eq!(
+            owned.by_ref().rev().collect::<Vec<_>>().len(),
+            offsets.len()
+        );
+    }
+
+    #[test]
+    fn test_trim() {
+        let text = b"\n\n  First para.\n\n   Second para.  \n\n\n";
+        let chunker = || chunk(text).size(18).delimiters(b"\n").trim(true);
+        let chunks: Vec<_> = chunker().collect();
+        // The trailing blank lines are only whitespace, so they stay
+        assert_eq!(chunks, vec![&b"First para."[..], b"Second para.", b"\n\n"]);
+        let offsets = chunker().collect_offsets();
+        assert_eq!(&text[offsets[1].0..offsets[1].1], b"Second para.");
+        let reversed: Vec<_> = chunker().rev().collect();
+        assert_eq!(reversed, chunks.iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!(chunker().chunk_at(1).unwrap().bytes(), b"Second para.");
+        // Whitespace delimiters still count as delimited boundaries
+        assert_eq!(chunker().stats().forced_splits, 0);
+
+        let dropped: Vec<_> = chunk(b"One  .  Two  .")
+            .size(8)
+            .delimiters(b".")
+            .delimiter_mode(DelimiterMode::Drop)
+            .trim(true)
+            .collect();
+        assert_eq!(dropped, vec![&b"One"[..], b"Two"]);
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(18)
+            .delimiters(b"\n".to_vec())
+            .trim(true);
+        assert_eq!(owned.collect_offsets(), offsets);
+        assert_eq!(owned.collect_chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_skip_empty() {
+        let text = b"One\n\n\n\n\nTwo\n  \n";
+        let chunker = || chunk(text).size(4).delimiters(b"\n").skip_empty(true);
+        let chunks: Vec<_> = chunker().iter_chunks().collect();
+        let bytes: Vec<_> = chunks.iter().map(|c| c.bytes()).collect();
+        assert_eq!(bytes, vec![&b"One\n"[..], b"Two\n"]);
+        assert_eq!(
+            chunks.iter().map(|c| c.index).collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+
+        let offsets = chunker().collect_offsets();
+        assert_eq!(offsets, vec![(0, 4), (8, 12)]);
+        assert_eq!(chunker().count_chunks(), 2);
+        assert_eq!(chunker().stats().count, 2);
+        let mut reversed: Vec<_> = chunker().rev().collect();
+        reversed.reverse();
+        assert_eq!(reversed, bytes);
+
+        let mut chunker = chunker();
+        assert_eq!(chunker.chunk_at(1).unwrap().bytes(), b"Two\n");
+        assert!(chunker.chunk_at(2).is_none());
+        assert_eq!(chunker.count_chunks(), 2);
+        // Offsets inside a skipped chunk seek to the next kept one
+        assert_eq!(chunker.seek(6), Some(1));
+        assert_eq!(chunker.next(), Some(&b"Two\n"[..]));
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(4)
+            .delimiters(b"\n".to_vec())
+            .skip_empty(true);
+        assert_eq!(owned.collect_offsets(), offsets);
+        assert_eq!(owned.count_chunks(), 2);
+        assert_eq!(owned.chunk_at(1).unwrap().bytes(), b"Two\n");
+        assert_eq!(owned.rev().collect::<Vec<_>>(), vec![(8, 12), (0, 4)]);
+    }
+
+    #[test]
+    fn test_skip_empty_dropped_delimiter() {
+        let chunks: Vec<_> = chunk(b"a..b")
+            .size(1)
+            .delimiters(b".")
+            .delimiter_mode(DelimiterMode::Drop)
+            .skip_empty(true)
+            .collect();
+        assert_eq!(chunks, vec![&b"a"[..], b"b"]);
+        // Off by default: every byte is in some chunk
+        let chunks: Vec<_> = chunk(b"a\n\n").size(1).delimiters(b"\n").collect();
+        assert_eq!(chunks.len(), 3);
+    }
+
+    #[test]
+    fn test_delimiter_priority() {
+        let text = b"One. Two\nThree. Four? Five.";
+        let offsets = |mut chunker: Chunker| chunker.collect_offsets();
+        assert_eq!(
+            offsets(chunk(text).size(20).delimiters(b"\n.?")),
+            vec![(0, 15), (15, 27)]
+        );
+        let prioritized = || {
+            chunk(text)
+                .size(20)
+                .delimiters(b"\n.?")
+                .delimiter_priority(true)
+        };
+        assert_eq!(offsets(prioritized()), vec![(0, 9), (9, 27)]);
+        assert_eq!(offsets(prioritized().prefix()), vec![(0, 8), (8, 27)]);
+        let reversed: Vec<_> = prioritized().rev().collect();
+        assert_eq!(reversed, vec![&b"Three. Four? Five."[..], b"One. Two\n"]);
+
+        // The order of the delimiters is the priority
+        assert_eq!(
+            offsets(
+                chunk(text)
+                    .size(22)
+                    .delimiters(b"?.\n")
+                    .delimiter_priority(true)
+            ),
+            vec![(0, 21), (21, 27)]
+        );
+
+        // Patterns are tried when the window holds no delimiter
+        let chunks: Vec<_> = chunk(b"aaaa||bbbb.cc")
+            .size(8)
+            .delimiters(b".")
+            .patterns(&["||"])
+            .delimiter_priority(true)
+            .collect();
+        assert_eq!(chunks[0], b"aaaa||");
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(20)
+            .delimiters(b"\n.?".to_vec())
+            .delimiter_priority(true);
+        assert_eq!(owned.collect_offsets(), offsets(prioritized()));
+    }
+
+    #[test]
+    fn test_slack() {
+        let text = b"aaaaaaa.bb.cccccc.dd";
+        let offsets = |mut chunker: Chunker| chunker.collect_offsets();
+        assert_eq!(
+            offsets(chunk(text).size(10).delimiters(b".")),
+            vec![(0, 8), (8, 18), (18, 20)]
+        );
+        // The boundary after the target is closer
+        let slack = || chunk(text).size(10).delimiters(b".").slack(3);
+        assert_eq!(offsets(slack()), vec![(0, 11), (11, 20)]);
+        assert_eq!(offsets(slack().prefix()), vec![(0, 10), (10, 20)]);
+        let reversed: Vec<_> = slack().rev().collect();
+        assert_eq!(reversed, vec![&b"cccccc.dd"[..], b"aaaaaaa.bb."]);
+        assert_eq!(slack().stats().forced_splits, 0);
+
+        // On a tie the earlier boundary wins
+        assert_eq!(
+            offsets(
+                chunk(b"aaaaaaa.aaa.aaaa")
+                    .size(10)
+                    .delimiters(b".")
+                    .slack(2)
+            ),
+            vec![(0, 8), (8, 16)]
+        );
+
+        // Nothing in the zone: the usual search applies
+        let sparse = b"aaa.aaaaaaaaaaaaaa.aa";
+        assert_eq!(
+            offsets(chunk(sparse).size(10).delimiters(b".").slack(2)),
+            offsets(chunk(sparse).size(10).delimiters(b"."))
+        );
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(10)
+            .delimiters(b".".to_vec())
+            .slack(3);
+        assert_eq!(owned.collect_offsets(), offsets(slack()));
+    }
+
+    #[test]
+    fn test_balanced() {
+        let text = b"aaaa bbbb cccc dddd eeee ffff g";
+        let balanced = || chunk(text).size(15).delimiters(b" ").balanced(true);
+        let offsets = balanced().collect_offsets();
+        assert_eq!(offsets, vec![(0, 10), (10, 20), (20, 31)]);
+        let reversed: Vec<_> = balanced()
+            .iter_chunks()
+            .rev()
+            .map(|c| (c.start, c.end))
+            .collect();
+        assert_eq!(reversed, offsets.iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!(balanced().count_chunks(), 3);
+        let chars = balanced().size_unit(SizeUnit::Chars).collect_offsets();
+        assert_eq!(chars, offsets);
+
+        // Sizes even out over a longer text instead of leaving a tiny tail
+        let words = "word ".repeat(41);
+        let sizes = |balanced| {
+            chunk(words.as_bytes())
+                .size(100)
+                .delimiters(b" ")
+                .balanced(balanced)
+                .map(<[u8]>::len)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sizes(false), vec![100, 100, 5]);
+        let even = sizes(true);
+        assert_eq!(even.len(), 3);
+        assert!(even.iter().all(|&size| (65..=70).contains(&size)));
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(15)
+            .delimiters(b" ".to_vec())
+            .balanced(true);
+        assert_eq!(owned.collect_offsets(), offsets);
+    }
+
+    #[test]
+    fn test_lines_per_chunk() {
+        let text = b"one\ntwo\nthree\nfour\nfive";
+        let offsets = |mut chunker: Chunker| chunker.collect_offsets();
+        let lines = || chunk(text).delimiters(b" ").lines_per_chunk(2);
+        assert_eq!(offsets(lines()), vec![(0, 8), (8, 19), (19, 23)]);
+        let reversed: Vec<_> = lines().rev().collect();
+        assert_eq!(
+            reversed,
+            vec![&b"five"[..], b"three\nfour\n", b"one\ntwo\n"]
+        );
+
+        // The size caps the line count, but never splits a line
+        assert_eq!(offsets(lines().size(10)), vec![(0, 8), (8, 14), (14, 23)]);
+        assert_eq!(
+            offsets(chunk(text).size(2).lines_per_chunk(3)),
+            vec![(0, 4), (4, 8), (8, 14), (14, 19), (19, 23)]
+        );
+        // Unless max_size forces it
+        let capped = offsets(chunk(text).size(2).lines_per_chunk(3).max_size(4));
+        assert!(capped.iter().all(|&(start, end)| end - start <= 4));
+
+        let mut owned = OwnedChunker::new(text.to_vec()).lines_per_chunk(2);
+        assert_eq!(owned.collect_offsets(), offsets(lines()));
+    }
+
+    #[test]
+    fn test_consecutive_delimiters() {
+        let text = b"Hello\n\n\nWorld";
+        let chunks: Vec<_> = chunk(text)
+            .delimiters(b"\n")
+            .size(8)
+            .prefix()
+            .consecutive()
+            .collect();
+        assert_eq!(chunks[0], b"Hello");
+        assert_eq!(chunks[1], b"\n\n\nWorld");
+    }
+
+    #[test]
+    fn test_forward_fallback() {
+        let text = b"verylongword next";
+        let chunks: Vec<_> = chunk(text)
+            .delimiters(b" ")
+            .size(6)
+            .prefix()
+            .forward_fallback()
+            .collect();
+        assert_eq!(chunks[0], b"verylongword");
+        assert_eq!(chunks[1], b" next");
+    }
+
+    #[test]
+    fn test_pattern_metaspace() {
+        let metaspace = "▁".as_bytes();
+        let text = "Hello▁World▁Test".as_bytes();
+        let chunks: Vec<_> = chunk(text).size(15).pattern(metaspace).prefix().collect();
+        assert_eq!(chunks[0], "Hello".as_bytes());
+        assert_eq!(chunks[1], "▁World▁Test".as_bytes());
+    }
+
+    #[test]
+    fn test_borrowed_collect_offsets() {
+        let text = b"Hello. World. Test.";
+        let mut chunker = chunk(text).size(10).delimiters(b".");
+        let offsets = chunker.collect_offsets();
+        assert_eq!(offsets, vec![(0, 6), (6, 13), (13, 19)]);
+
+        // Offsets match the zero-copy slices yielded by iteration
+        let chunks: Vec<_> = chunker.collect();
+        for (c, (s, e)) in chunks.iter().zip(&offsets) {
+            assert_eq!(*c, &text[*s..*e]);
         }
+    }
 
-        let end = self.pos + self.target_size;
+    #[test]
+    fn test_borrowed_reset() {
+        let text = b"Hello. World. Test.";
+        let mut chunker = chunk(text).size(10).delimiters(b".");
+        assert_eq!(chunker.next(), Some(b"Hello.".as_slice()));
+        chunker.reset();
+        assert_eq!(chunker.next(), Some(b"Hello.".as_slice()));
+    }
 
-        let split_at = if self.multi_searcher.is_some() {
-            compute_split_at_combined(
-                &self.text,
-                self.pos,
-                end,
-                &self.delimiters,
-                self.table.as_ref(),
-                self.multi_searcher.as_ref(),
-                self.prefix_mode,
-                self.consecutive,
-                self.forward_fallback,
-            )
-        } else {
-            compute_split_at(
-                &self.text,
-                self.pos,
-                end,
-                self.pattern.as_deref(),
-                &self.delimiters,
-                self.table.as_ref(),
-                self.prefix_mode,
-                self.consecutive,
-                self.forward_fallback,
-            )
+    #[test]
+    fn test_reverse_matches_forward() {
+        let text = "Héllo wörld. Fine day? Yes.\nNext line here. End".as_bytes();
+        let build = || {
+            chunk(text)
+                .size(9)
+                .delimiters(b".?\n")
+                .overlap(3)
+                .utf8_safe(true)
+                .min_size(4)
+                .max_size(12)
+        };
+        let mut forward: Vec<_> = build().iter_chunks().collect();
+        forward.reverse();
+        let backward: Vec<_> = build().iter_chunks().rev().collect();
+        assert_eq!(backward, forward);
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(9)
+            .delimiters(b".?\n".to_vec())
+            .overlap(3)
+            .utf8_safe(true)
+            .min_size(4)
+            .max_size(12);
+        let offsets: Vec<_> = owned.by_ref().rev().collect();
+        let expected: Vec<_> = forward.iter().map(|c| (c.start, c.end)).collect();
+        assert_eq!(offsets, expected);
+        owned.reset();
+        assert_eq!(owned.next_chunk_back().unwrap().index, forward.len() - 1);
+    }
+
+    #[test]
+    fn test_chunk_at_and_seek_match_iteration() {
+        let text = "Héllo wörld. Fine day? Yes.\nNext line here. End".as_bytes();
+        let build = || {
+            chunk(text)
+                .size(9)
+                .delimiters(b".?\n")
+                .overlap(3)
+                .utf8_safe(true)
+                .min_size(4)
         };
+        let all: Vec<_> = build().iter_chunks().collect();
+        let mut chunker = build();
+        for i in (0..all.len()).rev() {
+            assert_eq!(chunker.chunk_at(i), Some(all[i]));
+        }
+        assert_eq!(chunker.chunk_at(all.len()), None);
+
+        for offset in 0..text.len() {
+            let mut chunker = build();
+            let index = chunker.seek(offset).unwrap();
+            let first = all.iter().position(|c| c.end > offset).unwrap();
+            assert_eq!(index, first);
+            assert!((all[index].start..all[index].end).contains(&offset));
+            let rest: Vec<_> = core::iter::from_fn(|| chunker.next_chunk_info()).collect();
+            assert_eq!(rest, all[index..]);
+        }
+        assert_eq!(build().seek(text.len()), None);
+    }
+
+    #[test]
+    fn test_owned_chunk_at_and_seek() {
+        let mut chunker = OwnedChunker::new(b"a. b. c. d. e.".to_vec())
+            .size(3)
+            .delimiters(b".".to_vec());
+        assert_eq!(chunker.chunk_at(3).unwrap().bytes(), b" d.");
+        assert_eq!(chunker.next(), Some((0, 2)));
+        assert_eq!(chunker.seek(7), Some(2));
+        assert_eq!(chunker.next_chunk_info().unwrap().index, 2);
+        assert_eq!(chunker.next(), Some((8, 11)));
+        chunker.next_chunk_back();
+        assert_eq!(chunker.seek(0), Some(0));
+        assert_eq!(chunker.by_ref().count(), 5);
+        assert_eq!(chunker.seek(99), None);
+    }
+
+    #[test]
+    fn test_count_chunks() {
+        let text = "Héllo wörld. Fine day? Yes.\nNext line here. End".as_bytes();
+        for size in [1, 5, 9, 64] {
+            let mut chunker = chunk(text)
+                .size(size)
+                .delimiters(b".?\n")
+                .overlap(2)
+                .utf8_safe(true)
+                .min_size(3);
+            let expected = chunker.collect_offsets().len();
+            chunker.next();
+            assert_eq!(chunker.count_chunks(), expected);
+            chunker.seek(text.len() - 1);
+            assert_eq!(chunker.count_chunks(), expected);
+
+            let mut owned = OwnedChunker::new(text.to_vec())
+                .size(size)
+                .delimiters(b".?\n".to_vec());
+            assert_eq!(owned.count_chunks(), owned.collect_offsets().len());
+        }
+        assert_eq!(chunk(b"").count_chunks(), 0);
+    }
+
+    #[test]
+    fn test_settings_change_drops_cached_offsets() {
+        let text = b"One. Two. Six. Ten. Red. Big. Old. New.";
+        let fresh = chunk(text).size(20).delimiters(b".").collect_offsets();
 
-        let chunk = self.text[self.pos..split_at].to_vec();
-        self.pos = split_at;
-        Some(chunk)
+        let mut chunker = chunk(text).size(6).delimiters(b".");
+        assert!(chunker.chunk_at(100).is_none());
+        chunker.next();
+        let mut chunker = chunker.size(20);
+        assert_eq!(chunker.count_chunks(), fresh.len());
+        assert!(chunker.chunk_at(100).is_none());
+        assert_eq!(chunker.count_chunks(), fresh.len());
+        assert_eq!(
+            chunker.chunk_at(1).map(|c| (c.start, c.end)),
+            Some(fresh[1])
+        );
+        assert_eq!(chunker.seek(0), Some(0));
+        assert_eq!(chunker.by_ref().count(), fresh.len());
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(6)
+            .delimiters(b".".to_vec());
+        assert_eq!(owned.seek(text.len() - 1), Some(7));
+        let mut owned = owned.size(20).range(..);
+        assert_eq!(owned.count_chunks(), fresh.len());
+        // Offsets cached again under the new settings count from the cache
+        assert!(owned.chunk_at(100).is_none());
+        assert_eq!(owned.count_chunks(), fresh.len());
+        let mut owned = owned.delimiters(b" ".to_vec()).delimiters(b".".to_vec());
+        assert_eq!(owned.next(), Some(fresh[0]));
+        assert_eq!(owned.chunk_at(1).map(|c| (c.start, c.end)), Some(fresh[1]));
     }
 
-    /// Reset the chunker to start from the beginning.
-    pub fn reset(&mut self) {
-        self.pos = 0;
+    #[test]
+    fn test_reverse_mixed_with_forward() {
+        let text = b"a. b. c. d. e.";
+        let mut chunker = chunk(text).size(3).delimiters(b".");
+        assert_eq!(chunker.next(), Some(&b"a."[..]));
+        let last = chunker.next_chunk_back().unwrap();
+        assert_eq!((last.index, last.bytes()), (4, &b" e."[..]));
+        assert_eq!(chunker.next_chunk_info().unwrap().index, 1);
+        assert_eq!(chunker.next_back(), Some(&b" d."[..]));
+        assert_eq!(chunker.next(), Some(&b" c."[..]));
+        assert_eq!(chunker.next(), None);
+        assert_eq!(chunker.next_back(), None);
+        chunker.reset();
+        assert_eq!(chunker.count(), 5);
     }
 
-    /// Get a reference to the underlying text.
-    pub fn text(&self) -> &[u8] {
-        &self.text
+    #[test]
+    fn test_overlap() {
+        let text = b"Hello. World. Test.";
+        let chunks: Vec<_> = chunk(text).size(10).delimiters(b".").overlap(3).collect();
+        assert_eq!(chunks[0], b"Hello.");
+        assert_eq!(chunks[1], b"lo. World.");
+        assert_eq!(chunks[2], b"ld. Test.");
     }
 
-    /// Collect all chunk offsets as (start, end) pairs.
-    /// This is more efficient for FFI as it returns all offsets in one call.
-    pub fn collect_offsets(&mut self) -> Vec<(usize, usize)> {
-        self.init();
+    #[test]
+    fn test_overlap_clamped_to_previous_chunk() {
+        let text = b"Hello. World. Test.";
+        let offsets = chunk(text)
+            .size(10)
+            .delimiters(b".")
+            .overlap(100)
+            .collect_offsets();
+        assert_eq!(offsets, vec![(0, 6), (0, 13), (6, 19)]);
+    }
 
-        let mut offsets = Vec::new();
-        let mut pos = 0;
+    #[test]
+    fn test_overlap_zero_is_contiguous() {
+        let text = b"Hello. World. Test.";
+        let with: Vec<_> = chunk(text).size(10).delimiters(b".").overlap(0).collect();
+        let without: Vec<_> = chunk(text).size(10).delimiters(b".").collect();
+        assert_eq!(with, without);
+    }
 
-        while pos < self.text.len() {
-            let remaining = self.text.len() - pos;
+    #[test]
+    fn test_utf8_safe_backs_up_forced_split() {
+        // "a€b€" - € is 3 bytes, so a hard split at 3 lands inside it
+        let text = "a€b€".as_bytes();
+        let unsafe_offsets = chunk(text).size(3).delimiters(b"").collect_offsets();
+        assert_eq!(unsafe_offsets[0], (0, 3));
 
-            if remaining <= self.target_size {
-                offsets.push((pos, self.text.len()));
-                break;
-            }
+        let offsets = chunk(text)
+            .size(3)
+            .delimiters(b"")
+            .utf8_safe(true)
+            .collect_offsets();
+        assert_eq!(offsets, vec![(0, 1), (1, 4), (4, 5), (5, 8)]);
+    }
 
-            let end = pos + self.target_size;
-
-            let split_at = if self.multi_searcher.is_some() {
-                compute_split_at_combined(
-                    &self.text,
-                    pos,
-                    end,
-                    &self.delimiters,
-                    self.table.as_ref(),
-                    self.multi_searcher.as_ref(),
-                    self.prefix_mode,
-                    self.consecutive,
-                    self.forward_fallback,
-                )
-            } else {
-                compute_split_at(
-                    &self.text,
-                    pos,
-                    end,
-                    self.pattern.as_deref(),
-                    &self.delimiters,
-                    self.table.as_ref(),
-                    self.prefix_mode,
-                    self.consecutive,
-                    self.forward_fallback,
-                )
-            };
+    #[test]
+    fn test_utf8_safe_moves_forward_when_char_exceeds_size() {
+        let text = "€€".as_bytes();
+        let offsets = chunk(text)
+            .size(2)
+            .delimiters(b"")
+            .utf8_safe(true)
+            .collect_offsets();
+        assert_eq!(offsets, vec![(0, 3), (3, 6)]);
+    }
 
-            offsets.push((pos, split_at));
-            pos = split_at;
+    #[test]
+    fn test_utf8_safe_overlap_start() {
+        let text = "ab. €€cd. ef.".as_bytes();
+        let chunks: Vec<_> = chunk(text)
+            .size(8)
+            .delimiters(b".")
+            .overlap(2)
+            .utf8_safe(true)
+            .collect();
+        for c in &chunks {
+            assert!(std::str::from_utf8(c).is_ok(), "{:?}", c);
         }
+    }
 
-        offsets
+    #[test]
+    fn test_utf8_safe_owned_matches_borrowed() {
+        let text = "héllo wörld ünïcödé téxt".as_bytes();
+        let borrowed = chunk(text)
+            .size(5)
+            .delimiters(b" ")
+            .overlap(2)
+            .utf8_safe(true)
+            .collect_offsets();
+        let owned = OwnedChunker::new(text.to_vec())
+            .size(5)
+            .delimiters(b" ".to_vec())
+            .overlap(2)
+            .utf8_safe(true)
+            .collect_offsets();
+        assert_eq!(borrowed, owned);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_size_metric_closure() {
+        let words = |s: &[u8]| s.split(|&b| b == b' ').filter(|w| !w.is_empty()).count();
+        let text = b"a b c. d e f g h. i j";
+        let chunks: Vec<_> = chunk(text)
+            .size(4)
+            .delimiters(b".")
+            .size_metric(words)
+            .collect();
+        // The window extends as far as the word count allows, trailing space included
+        assert_eq!(chunks, vec![b"a b c.".as_slice(), b" d e f g ", b"h. i j"]);
+    }
 
     #[test]
-    fn test_basic_chunking() {
-        let text = b"Hello. World. Test.";
-        let chunks: Vec<_> = chunk(text).size(10).delimiters(b".").collect();
-        assert_eq!(chunks.len(), 3);
-        assert_eq!(chunks[0], b"Hello.");
-        assert_eq!(chunks[1], b" World.");
-        assert_eq!(chunks[2], b" Test.");
+    fn test_size_metric_bytes_matches_default() {
+        let text = b"Hello. World. Test. Another sentence.";
+        let with: Vec<_> = chunk(text).size(10).size_metric(crate::Bytes).collect();
+        let without: Vec<_> = chunk(text).size(10).collect();
+        assert_eq!(with, without);
     }
 
     #[test]
-    fn test_newline_delimiter() {
-        let text = b"Line one\nLine two\nLine three";
-        let chunks: Vec<_> = chunk(text).size(15).delimiters(b"\n").collect();
-        assert_eq!(chunks[0], b"Line one\n");
-        assert_eq!(chunks[1], b"Line two\n");
-        assert_eq!(chunks[2], b"Line three");
+    fn test_owned_size_metric() {
+        let text = "ééééé ééééé".as_bytes();
+        let offsets = OwnedChunker::new(text.to_vec())
+            .size(6)
+            .delimiters(b" ".to_vec())
+            .size_metric(crate::Chars)
+            .collect_offsets();
+        assert_eq!(offsets, vec![(0, 11), (11, 21)]);
     }
 
     #[test]
-    fn test_multiple_delimiters() {
-        let text = b"Hello? World. Yes!";
-        let chunks: Vec<_> = chunk(text).size(10).delimiters(b".?!").collect();
-        assert_eq!(chunks[0], b"Hello?");
+    fn test_size_unit_chars() {
+        // 3-byte characters: 4 chars is 12 bytes
+        let text = "ああああ いいいい".as_bytes();
+        let offsets = chunk(text)
+            .size(5)
+            .delimiters(b" ")
+            .size_unit(SizeUnit::Chars)
+            .collect_offsets();
+        assert_eq!(offsets, vec![(0, 13), (13, 25)]);
+
+        let bytes = chunk(text).size(5).delimiters(b" ").collect_offsets();
+        assert!(bytes.len() > 2);
     }
 
     #[test]
-    fn test_four_delimiters_uses_table() {
-        let text = b"A. B? C! D; E";
-        let chunks: Vec<_> = chunk(text).size(5).delimiters(b".?!;").collect();
-        assert!(chunks.len() >= 2);
+    fn test_size_unit_bytes_resets_metric() {
+        let text = "ああああ いいいい".as_bytes();
+        let offsets = chunk(text)
+            .size(5)
+            .delimiters(b" ")
+            .size_unit(SizeUnit::Chars)
+            .size_unit(SizeUnit::Bytes)
+            .collect_offsets();
+        assert_eq!(
+            offsets,
+            chunk(text).size(5).delimiters(b" ").collect_offsets()
+        );
     }
 
     #[test]
-    fn test_no_delimiter_hard_split() {
-        let text = b"abcdefghij";
-        let chunks: Vec<_> = chunk(text).size(5).delimiters(b".").collect();
-        assert_eq!(chunks[0], b"abcde");
-        assert_eq!(chunks[1], b"fghij");
+    fn test_min_size_merges_tail() {
+        let text = b"Hello. World. Hi.";
+        let without = chunk(text).size(14).delimiters(b".").collect_offsets();
+        assert_eq!(without, vec![(0, 13), (13, 17)]);
+
+        let with = chunk(text)
+            .size(14)
+            .delimiters(b".")
+            .min_size(5)
+            .collect_offsets();
+        assert_eq!(with, vec![(0, 17)]);
     }
 
     #[test]
-    fn test_empty_text() {
-        let text = b"";
-        let chunks: Vec<_> = chunk(text).size(10).delimiters(b".").collect();
-        assert_eq!(chunks.len(), 0);
+    fn test_min_size_keeps_large_enough_tail() {
+        let text = b"Hello. World. Test.";
+        let offsets = chunk(text)
+            .size(10)
+            .delimiters(b".")
+            .min_size(6)
+            .collect_offsets();
+        assert_eq!(offsets, vec![(0, 6), (6, 13), (13, 19)]);
     }
 
     #[test]
-    fn test_text_smaller_than_target() {
-        let text = b"Small";
-        let chunks: Vec<_> = chunk(text).size(100).delimiters(b".").collect();
-        assert_eq!(chunks.len(), 1);
-        assert_eq!(chunks[0], b"Small");
+    fn test_min_size_in_chars() {
+        // Tail "いい" is 2 chars (6 bytes)
+        let text = "ああああ いい".as_bytes();
+        let chars = chunk(text)
+            .size(5)
+            .delimiters(b" ")
+            .size_unit(SizeUnit::Chars)
+            .min_size(3)
+            .collect_offsets();
+        assert_eq!(chars, vec![(0, 19)]);
     }
 
     #[test]
-    fn test_total_bytes_preserved() {
-        let text = b"The quick brown fox jumps over the lazy dog. How vexingly quick!";
-        let chunks: Vec<_> = chunk(text).size(20).delimiters(b"\n.?!").collect();
-        let total: usize = chunks.iter().map(|c| c.len()).sum();
-        assert_eq!(total, text.len());
+    fn test_owned_min_size() {
+        let mut chunker = OwnedChunker::new(b"Hello. World. Hi.".to_vec())
+            .size(14)
+            .delimiters(b".".to_vec())
+            .min_size(5);
+        assert_eq!(chunker.collect_offsets(), vec![(0, 17)]);
+        assert_eq!(chunker.next_chunk().unwrap(), b"Hello. World. Hi.");
+        assert!(chunker.next_chunk().is_none());
     }
 
     #[test]
-    fn test_defaults() {
-        let text = b"Hello world. This is a test.";
-        let chunks: Vec<_> = chunk(text).collect();
-        assert!(!chunks.is_empty());
+    fn test_max_size_caps_forward_fallback() {
+        let text = b"averyveryverylongword next word";
+        let uncapped: Vec<_> = chunk(text)
+            .size(8)
+            .pattern(b" ")
+            .forward_fallback()
+            .collect();
+        assert!(uncapped[0].len() > 10);
+
+        let capped: Vec<_> = chunk(text)
+            .size(8)
+            .pattern(b" ")
+            .forward_fallback()
+            .max_size(10)
+            .collect();
+        assert!(capped.iter().all(|c| c.len() <= 10));
+        assert_eq!(capped.concat(), text.to_vec());
     }
 
     #[test]
-    fn test_prefix_mode() {
-        let text = b"Hello World Test";
-        let chunks: Vec<_> = chunk(text).size(8).delimiters(b" ").prefix().collect();
-        assert_eq!(chunks[0], b"Hello");
-        assert_eq!(chunks[1], b" World");
-        assert_eq!(chunks[2], b" Test");
+    fn test_max_size_prefers_delimiter_in_range() {
+        // The tail merge would produce 17 bytes; the cap re-splits at a delimiter
+        let text = b"Hello. World. Hi.";
+        let offsets = chunk(text)
+            .size(14)
+            .delimiters(b".")
+            .min_size(5)
+            .max_size(15)
+            .collect_offsets();
+        assert_eq!(offsets, vec![(0, 13), (13, 17)]);
     }
 
     #[test]
-    fn test_suffix_mode() {
-        let text = b"Hello World Test";
-        let chunks: Vec<_> = chunk(text).size(8).delimiters(b" ").suffix().collect();
-        assert_eq!(chunks[0], b"Hello ");
-        assert_eq!(chunks[1], b"World ");
-        assert_eq!(chunks[2], b"Test");
+    fn test_max_size_shrinks_overlap() {
+        let text = b"Hello. World. Test.";
+        let offsets = chunk(text)
+            .size(10)
+            .delimiters(b".")
+            .overlap(5)
+            .max_size(9)
+            .collect_offsets();
+        assert_eq!(offsets, vec![(0, 6), (4, 13), (10, 19)]);
+        assert!(offsets.iter().all(|(s, e)| e - s <= 9));
     }
 
     #[test]
-    fn test_consecutive_delimiters() {
-        let text = b"Hello\n\n\nWorld";
-        let chunks: Vec<_> = chunk(text)
-            .delimiters(b"\n")
-            .size(8)
-            .prefix()
-            .consecutive()
-            .collect();
-        assert_eq!(chunks[0], b"Hello");
-        assert_eq!(chunks[1], b"\n\n\nWorld");
+    fn test_max_size_utf8_safe_forced_split() {
+        let text = "€€€".as_bytes();
+        let offsets = chunk(text)
+            .size(2)
+            .delimiters(b"")
+            .utf8_safe(true)
+            .collect_offsets();
+        assert_eq!(offsets[0], (0, 3));
+
+        // A cap of 4 still fits one character, so the split stays on a boundary
+        let offsets = chunk(text)
+            .size(2)
+            .delimiters(b"")
+            .utf8_safe(true)
+            .max_size(4)
+            .collect_offsets();
+        assert_eq!(offsets, vec![(0, 3), (3, 6), (6, 9)]);
+
+        // A cap below the character width wins over UTF-8 safety
+        let offsets = chunk(text)
+            .size(2)
+            .delimiters(b"")
+            .utf8_safe(true)
+            .max_size(2)
+            .collect_offsets();
+        assert!(offsets.iter().all(|(s, e)| e - s <= 2));
     }
 
     #[test]
-    fn test_forward_fallback() {
-        let text = b"verylongword next";
-        let chunks: Vec<_> = chunk(text)
-            .delimiters(b" ")
-            .size(6)
-            .prefix()
+    fn test_owned_max_size() {
+        let text = b"averyveryverylongword next word".to_vec();
+        let borrowed = chunk(&text)
+            .size(8)
+            .pattern(b" ")
             .forward_fallback()
-            .collect();
-        assert_eq!(chunks[0], b"verylongword");
-        assert_eq!(chunks[1], b" next");
+            .max_size(10)
+            .collect_offsets();
+        let owned = OwnedChunker::new(text.clone())
+            .size(8)
+            .pattern(b" ".to_vec())
+            .forward_fallback()
+            .max_size(10)
+            .collect_offsets();
+        assert_eq!(borrowed, owned);
     }
 
     #[test]
-    fn test_pattern_metaspace() {
-        let metaspace = "▁".as_bytes();
-        let text = "Hello▁World▁Test".as_bytes();
-        let chunks: Vec<_> = chunk(text).size(15).pattern(metaspace).prefix().collect();
-        assert_eq!(chunks[0], "Hello".as_bytes());
-        assert_eq!(chunks[1], "▁World▁Test".as_bytes());
+    fn test_owned_chunker_overlap() {
+        let text = b"Hello. World. Test.".to_vec();
+        let mut chunker = OwnedChunker::new(text.clone())
+            .size(10)
+            .delimiters(b".".to_vec())
+            .overlap(3);
+
+        let offsets = chunker.collect_offsets();
+        assert_eq!(offsets, vec![(0, 6), (3, 13), (10, 19)]);
+
+        let mut chunks = Vec::new();
+        while let Some(c) = chunker.next_chunk() {
+            chunks.push(c);
+        }
+        assert_eq!(chunks[1], b"lo. World.");
+
+        chunker.reset();
+        assert_eq!(chunker.next_chunk().unwrap(), b"Hello.");
     }
 
     #[test]
@@ -709,4 +3728,454 @@ mod tests {
         assert_eq!(offsets.len(), 3);
         assert_eq!(&text[offsets[0].0..offsets[0].1], b"Hello.");
     }
+
+    #[test]
+    fn test_chunk_info_matches_offsets() {
+        let text = b"Hello. World. Test.";
+        let offsets = chunk(text)
+            .size(10)
+            .delimiters(b".")
+            .overlap(2)
+            .collect_offsets();
+        let infos: Vec<Chunk> = chunk(text)
+            .size(10)
+            .delimiters(b".")
+            .overlap(2)
+            .iter_chunks()
+            .collect();
+
+        assert_eq!(infos.len(), offsets.len());
+        for (i, (info, &(start, end))) in infos.iter().zip(&offsets).enumerate() {
+            assert_eq!((info.index, info.start, info.end), (i, start, end));
+            assert_eq!(info.bytes(), &text[start..end]);
+            assert_eq!(info.len(), end - start);
+        }
+    }
+
+    #[test]
+    fn test_chunk_info_mixes_with_iteration_and_reset() {
+        let mut chunker = chunk(b"a. b. c.").size(3).delimiters(b".");
+        assert_eq!(chunker.next(), Some(&b"a."[..]));
+        let info = chunker.next_chunk_info().unwrap();
+        assert_eq!((info.index, info.start), (1, 2));
+        chunker.reset();
+        assert_eq!(chunker.next_chunk_info().unwrap().index, 0);
+    }
+
+    #[test]
+    fn test_chunk_text_invalid_utf8() {
+        let text = "ééé".as_bytes();
+        let mut chunker = chunk(text).size(3);
+        assert!(chunker.next_chunk_info().unwrap().text().is_err());
+
+        let mut safe = chunk(text).size(3).utf8_safe(true);
+        assert_eq!(safe.next_chunk_info().unwrap().text(), Ok("é"));
+    }
+
+    #[test]
+    fn test_iter_chunks_starts_from_the_beginning() {
+        let text = b"One. Two. Six.";
+        let mut chunker = chunk(text).size(5).delimiters(b".");
+        let all: Vec<_> = chunk(text).size(5).delimiters(b".").iter_chunks().collect();
+        assert_eq!(chunker.next(), Some(&b"One."[..]));
+        chunker.next_chunk_back();
+        let chunks: Vec<_> = chunker.iter_chunks().collect();
+        assert_eq!(chunks, all);
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(5)
+            .delimiters(b".".to_vec());
+        assert_eq!(owned.next(), Some((0, 4)));
+        let chunks: Vec<_> = owned.iter_chunks().collect();
+        assert_eq!(chunks, all);
+        assert_eq!(owned.next(), Some((4, 9)));
+    }
+
+    #[test]
+    fn test_owned_chunker_chunk_info() {
+        let text = b"Hello. World. Test.".to_vec();
+        let mut chunker = OwnedChunker::new(text).size(10).delimiters(b".".to_vec());
+
+        assert_eq!(chunker.next_chunk().unwrap(), b"Hello.");
+        let info = chunker.next_chunk_info().unwrap();
+        assert_eq!((info.index, info.start, info.end), (1, 6, 13));
+
+        // iter_chunks always covers the whole text
+        let all: Vec<(usize, usize, usize)> = chunker
+            .iter_chunks()
+            .map(|c| (c.index, c.start, c.end))
+            .collect();
+        assert_eq!(all, vec![(0, 0, 6), (1, 6, 13), (2, 13, 19)]);
+        assert_eq!(chunker.next_chunk().unwrap(), b" Test.");
+    }
+
+    #[test]
+    fn test_owned_chunker_iterator() {
+        let mut chunker = OwnedChunker::new(b"Hello. World. Test.".to_vec())
+            .size(10)
+            .delimiters(b".".to_vec());
+        let expected = chunker.collect_offsets();
+
+        let first = chunker.next().unwrap();
+        assert_eq!(first, expected[0]);
+        assert_eq!(chunker.next_chunk().unwrap(), b" World.");
+        let rest: Vec<_> = chunker.by_ref().collect();
+        assert_eq!(rest, expected[2..]);
+
+        chunker.reset();
+        let zipped: Vec<_> = (&mut chunker).zip(expected.iter()).take(2).collect();
+        assert!(zipped.iter().all(|(got, want)| got == *want));
+    }
+
+    #[test]
+    fn test_owned_chunker_collect_chunks() {
+        let text = b"Hello. World. Test.".to_vec();
+        let mut chunker = OwnedChunker::new(text.clone())
+            .size(10)
+            .delimiters(b".".to_vec())
+            .overlap(2);
+
+        let chunks = chunker.collect_chunks();
+        let offsets = chunker.collect_offsets();
+        assert_eq!(chunks.len(), offsets.len());
+        for (chunk, (start, end)) in chunks.iter().zip(offsets) {
+            assert_eq!(chunk, &text[start..end]);
+        }
+        // Doesn't consume the iterator
+        assert_eq!(chunker.next_chunk().unwrap(), b"Hello.");
+    }
+
+    #[test]
+    fn test_range() {
+        let text = b"Head. One. Two. Six. Ten.";
+        let build = || chunk(text).size(5).delimiters(b".").range(6..20);
+        let body: Vec<_> = chunk(&text[6..20])
+            .size(5)
+            .delimiters(b".")
+            .collect_offsets()
+            .into_iter()
+            .map(|(start, end)| (start + 6, end + 6))
+            .collect();
+        assert_eq!(body, vec![(6, 10), (10, 15), (15, 20)]);
+        assert_eq!(build().collect_offsets(), body);
+        assert_eq!(build().count_chunks(), 3);
+        assert_eq!(build().stats().count, 3);
+        assert_eq!(build().chunk_at(1).unwrap().bytes(), b" Two.");
+        assert_eq!(build().next_back(), Some(&b" Six."[..]));
+        let mut chunker = build();
+        assert_eq!(chunker.seek(15), Some(2));
+        chunker.reset();
+        assert_eq!(chunker.next_chunk_info().unwrap().start, 6);
+
+        // Clamped to the text, last one wins
+        let wide = chunk(text)
+            .size(5)
+            .delimiters(b".")
+            .range(..)
+            .range(20..=99);
+        assert_eq!(wide.collect::<Vec<_>>(), vec![&b" Ten."[..]]);
+        assert_eq!(chunk(text).range(30..40).count(), 0);
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(5)
+            .delimiters(b".".to_vec())
+            .range(6..20);
+        assert_eq!(owned.collect_offsets(), body);
+        assert_eq!(owned.by_ref().collect::<Vec<_>>(), body);
+        assert_eq!(owned.text(), text);
+    }
+
+    #[test]
+    fn test_owned_split_range() {
+        let text: Arc<[u8]> = Arc::from(&b"a. b. c. d. e. f."[..]);
+        let chunker = OwnedChunker::from_source(text)
+            .size(3)
+            .delimiters(b".".to_vec())
+            .overlap(1);
+        let copy = chunker.clone();
+        assert!(core::ptr::eq(copy.text(), chunker.text()));
+
+        // Cuts move forward to the next boundary
+        let mut piece = chunker.split_range(4, 13);
+        assert_eq!(piece.collect_offsets(), vec![(5, 8), (7, 11), (10, 14)]);
+        assert_eq!(piece.count_chunks(), 3);
+        assert_eq!(piece.chunk_at(1).unwrap().bytes(), b". d.");
+        assert_eq!(piece.seek(12), Some(2));
+        assert_eq!(piece.next(), Some((10, 14)));
+        assert_eq!(piece.next(), None);
+        piece.reset();
+        assert_eq!(piece.next_chunk_back().unwrap().bytes(), b". e.");
+        assert_eq!(piece.next(), Some((5, 8)));
+
+        // Adjacent pieces cover the text once
+        let cuts = [0, 3, 4, 9, 17];
+        let mut starts = Vec::new();
+        for window in cuts.windows(2) {
+            let mut piece = chunker.split_range(window[0], window[1]);
+            starts.extend(piece.collect_offsets().first().map(|&(start, _)| start));
+            assert_eq!(
+                piece.split_range(0, 99).collect_offsets(),
+                piece.collect_offsets()
+            );
+        }
+        assert_eq!(starts, vec![0, 5, 11]);
+        assert!(chunker.split_range(16, 17).collect_offsets().is_empty());
+    }
+
+    #[test]
+    fn test_overlap_ratio() {
+        let text = b"One. Two. Six. Ten. End.";
+        let by_bytes = |size, overlap| {
+            chunk(text)
+                .size(size)
+                .delimiters(b".")
+                .overlap(overlap)
+                .collect_offsets()
+        };
+        for size in [1, 5, 8, 16] {
+            let ratio = |ratio| {
+                chunk(text)
+                    .delimiters(b".")
+                    .overlap_ratio(ratio)
+                    .size(size)
+                    .collect_offsets()
+            };
+            assert_eq!(ratio(0.25), by_bytes(size, size / 4));
+            assert_eq!(ratio(0.3), by_bytes(size, (size * 3 + 5) / 10));
+            // Clamped below the size, with NaN and negative ratios as 0
+            assert_eq!(ratio(5.0), by_bytes(size, size - 1));
+            assert_eq!(ratio(f64::NAN), by_bytes(size, 0));
+            assert_eq!(ratio(-1.0), by_bytes(size, 0));
+        }
+        // Last one set wins
+        let mut chunker = chunk(text).size(8).overlap_ratio(0.5).overlap(1);
+        assert_eq!(chunker.collect_offsets(), by_bytes(8, 1));
+        let owned = OwnedChunker::new(text.to_vec())
+            .delimiters(b".".to_vec())
+            .overlap(1)
+            .overlap_ratio(0.5)
+            .size(8);
+        assert_eq!(owned.collect::<Vec<_>>(), by_bytes(8, 4));
+
+        let err = |ratio| chunk(text).overlap_ratio(ratio).build().err();
+        assert_eq!(err(1.0), Some(ChunkError::InvalidOverlapRatio));
+        assert_eq!(err(f64::NAN), Some(ChunkError::InvalidOverlapRatio));
+        assert_eq!(err(-0.1), Some(ChunkError::InvalidOverlapRatio));
+        assert_eq!(err(0.0), None);
+        assert!(
+            OwnedChunker::new(text.to_vec())
+                .overlap_ratio(0.9)
+                .size(1)
+                .build()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_build_validates() {
+        let text = b"Hello. World.";
+        assert_eq!(
+            chunk(text).size(0).build().err(),
+            Some(ChunkError::ZeroSize)
+        );
+        assert_eq!(
+            chunk(text).size(4).overlap(4).build().err(),
+            Some(ChunkError::OverlapTooLarge {
+                overlap: 4,
+                size: 4
+            })
+        );
+        assert!(chunk(text).size(4).overlap(3).build().is_ok());
+        // A char is at least a byte, so the overlap is compared to the size
+        // in chars too
+        let chars = || chunk(text).size(4).size_unit(SizeUnit::Chars);
+        assert_eq!(
+            chars().overlap(4).build().err(),
+            Some(ChunkError::OverlapTooLarge {
+                overlap: 4,
+                size: 4
+            })
+        );
+        assert!(chars().overlap(3).build().is_ok());
+        // A ratio's overlap is always kept under the size
+        assert!(chars().overlap_ratio(0.9).build().is_ok());
+        // An empty delimiter set only splits at the fallback
+        assert_eq!(
+            chunk(text).size(4).delimiters(b"").build().err(),
+            Some(ChunkError::NoDelimiters)
+        );
+        assert!(
+            chunk(text)
+                .size(4)
+                .delimiters(b"")
+                .pattern(b". ")
+                .build()
+                .is_ok()
+        );
+        assert!(
+            chunk(text)
+                .size(4)
+                .delimiters(b"")
+                .patterns(&[b". "])
+                .build()
+                .is_ok()
+        );
+        assert!(
+            chunk(text)
+                .size(4)
+                .delimiters(b"")
+                .lines_per_chunk(1)
+                .build()
+                .is_ok()
+        );
+        // ... but still gives fixed-size chunks without validation
+        assert_eq!(
+            chunk(text).size(4).delimiters(b"").collect_offsets(),
+            vec![(0, 4), (4, 8), (8, 12), (12, 13)]
+        );
+
+        let owned = OwnedChunker::new(text.to_vec()).size(0).build();
+        assert_eq!(owned.err(), Some(ChunkError::ZeroSize));
+        let config = ChunkerConfig::new().size(0);
+        let owned = OwnedChunker::try_new(text.to_vec(), &config);
+        assert_eq!(owned.err(), Some(ChunkError::ZeroSize));
+        assert_eq!(
+            ChunkError::OverlapTooLarge {
+                overlap: 8,
+                size: 4
+            }
+            .to_string(),
+            "overlap (8) must be smaller than size (4)"
+        );
+    }
+
+    #[test]
+    fn test_zero_size_without_build() {
+        // Taken as 1 instead of never advancing
+        let text = b"ab.c";
+        assert_eq!(
+            chunk(text).size(0).collect_offsets(),
+            vec![(0, 1), (1, 2), (2, 3), (3, 4)]
+        );
+        let mut owned = OwnedChunker::new(text.to_vec()).size(0).balanced(true);
+        assert_eq!(owned.collect_offsets().len(), 4);
+    }
+
+    #[test]
+    fn test_fallback_strategy() {
+        let text = b"One two three. Four";
+        let offsets = |strategy| {
+            chunk(text)
+                .size(9)
+                .delimiters(b".")
+                .fallback(strategy)
+                .try_collect_offsets()
+        };
+        let hard = vec![(0, 9), (9, 14), (14, 19)];
+        let whitespace = vec![(0, 8), (8, 14), (14, 19)];
+        assert_eq!(offsets(FallbackStrategy::Hard), Ok(hard.clone()));
+        assert_eq!(offsets(FallbackStrategy::Utf8Boundary), Ok(hard));
+        assert_eq!(
+            offsets(FallbackStrategy::Whitespace),
+            Ok(whitespace.clone())
+        );
+        assert_eq!(
+            offsets(FallbackStrategy::Error),
+            Err(ChunkError::NoBoundary { position: 0 })
+        );
+
+        // Splits forced by the cap follow the strategy too, without breaking it
+        let capped = chunk(text)
+            .size(30)
+            .delimiters(b".")
+            .max_size(9)
+            .fallback(FallbackStrategy::Whitespace)
+            .collect_offsets();
+        assert_eq!(capped, whitespace);
+        let euro = || {
+            chunk("ab€".as_bytes())
+                .size(2)
+                .delimiters(b"")
+                .fallback(FallbackStrategy::Utf8Boundary)
+        };
+        assert_eq!(euro().collect_offsets(), vec![(0, 2), (2, 5)]);
+        // A character wider than the cap is still cut
+        let capped = euro().max_size(2).collect_offsets();
+        assert_eq!(capped, vec![(0, 2), (2, 4), (4, 5)]);
+
+        // Forward fallback is tried first
+        let forward = chunk(text)
+            .size(9)
+            .delimiters(b".")
+            .forward_fallback()
+            .fallback(FallbackStrategy::Error)
+            .try_collect_offsets();
+        assert_eq!(forward, Ok(vec![(0, 14), (14, 19)]));
+    }
+
+    #[cfg(feature = "segmenter")]
+    #[test]
+    fn test_fallback_word_boundary() {
+        // "every two weeks": ทุก | สอง | สัปดาห์, with no spaces between
+        let text = "ทุกสองสัปดาห์".as_bytes();
+        let chunks = |strategy| {
+            chunk(text)
+                .size(25)
+                .delimiters(b".")
+                .fallback(strategy)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(chunks(FallbackStrategy::Whitespace)[0].len(), 25);
+        assert_eq!(
+            chunks(FallbackStrategy::WordBoundary),
+            vec!["ทุกสอง".as_bytes(), "สัปดาห์".as_bytes()]
+        );
+
+        // Spaced text splits between words as well
+        let chunks: Vec<_> = chunk(b"Hello world")
+            .size(8)
+            .delimiters(b".")
+            .fallback(FallbackStrategy::WordBoundary)
+            .collect();
+        assert_eq!(chunks, vec![&b"Hello "[..], b"world"]);
+    }
+
+    #[test]
+    fn test_fallback_error_stops_chunking() {
+        let text = b"Hi. Unsplittable.";
+        let chunker = || {
+            chunk(text)
+                .size(6)
+                .delimiters(b".")
+                .fallback(FallbackStrategy::Error)
+        };
+        let err = ChunkError::NoBoundary { position: 3 };
+        let mut forward = chunker();
+        assert_eq!(forward.try_next_chunk().unwrap().unwrap().bytes(), b"Hi.");
+        assert_eq!(forward.try_next_chunk().unwrap().unwrap_err(), err);
+        assert_eq!(forward.try_next_chunk().unwrap().unwrap_err(), err);
+        assert_eq!(forward.next(), None);
+
+        // Everything else stops before the chunk that can't be split
+        assert_eq!(chunker().collect::<Vec<_>>(), vec![b"Hi."]);
+        assert_eq!(chunker().next_back(), Some(&b"Hi."[..]));
+        assert_eq!(chunker().count_chunks(), 1);
+        assert_eq!(chunker().stats().count, 1);
+        assert!(chunker().chunk_at(1).is_none());
+        assert_eq!(chunker().try_collect_offsets(), Err(err));
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(6)
+            .delimiters(b".".to_vec())
+            .fallback(FallbackStrategy::Error);
+        assert_eq!(owned.try_collect_offsets(), Err(err));
+        assert_eq!(owned.collect_offsets(), vec![(0, 3)]);
+        assert_eq!(owned.iter_chunks().count(), 1);
+        assert_eq!(owned.try_next_chunk().unwrap().unwrap().bytes(), b"Hi.");
+        assert_eq!(owned.try_next_chunk().unwrap().unwrap_err(), err);
+        assert_eq!(
+            err.to_string(),
+            "no delimiter within the size limit after byte 3"
+        );
+    }
 }
@@ -0,0 +1,408 @@
+//! Source-code-aware chunking.
+//!
+//! This module provides [`CodeChunker`], which splits source code at
+//! function/class boundaries using a lightweight per-language heuristic:
+//! bracket nesting for C-like languages, indentation for Python. Strings and
+//! comments are skipped, so braces inside them don't count.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::delim::{DEFAULT_TARGET_SIZE, utf8_split_boundary};
+
+/// A programming language supported by [`CodeChunker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// Rust (`//`, `/* */`, char literals vs lifetimes).
+    Rust,
+    /// Python (indentation based, `#` comments, triple-quoted strings).
+    Python,
+    /// JavaScript (template literals with backticks).
+    JavaScript,
+    /// TypeScript (same syntax rules as JavaScript).
+    TypeScript,
+    /// Go (raw strings with backticks).
+    Go,
+    /// Java.
+    Java,
+    /// C.
+    C,
+    /// C++.
+    Cpp,
+}
+
+/// Error returned when parsing an unknown [`Language`] name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownLanguage(pub String);
+
+impl fmt::Display for UnknownLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown language: {:?}", self.0)
+    }
+}
+
+impl core::error::Error for UnknownLanguage {}
+
+impl FromStr for Language {
+    type Err = UnknownLanguage;
+
+    /// Parse a language name or common file extension (case-insensitive),
+    /// e.g. `"rust"`, `"py"`, `"ts"`, `"c++"`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "rust" | "rs" => Ok(Language::Rust),
+            "python" | "py" => Ok(Language::Python),
+            "javascript" | "js" | "jsx" | "mjs" => Ok(Language::JavaScript),
+            "typescript" | "ts" | "tsx" => Ok(Language::TypeScript),
+            "go" | "golang" => Ok(Language::Go),
+            "java" => Ok(Language::Java),
+            "c" | "h" => Ok(Language::C),
+            "cpp" | "c++" | "cc" | "cxx" | "hpp" => Ok(Language::Cpp),
+            _ => Err(UnknownLanguage(name.to_string())),
+        }
+    }
+}
+
+impl Language {
+    /// Whether block structure comes from indentation rather than braces.
+    fn indented(self) -> bool {
+        self == Language::Python
+    }
+
+    /// Quote bytes that open string literals.
+    fn quotes(self) -> &'static [u8] {
+        match self {
+            Language::Python => b"\"'",
+            Language::JavaScript | Language::TypeScript | Language::Go => b"\"'`",
+            // Rust `'` is handled separately (char literal vs lifetime)
+            Language::Rust => b"\"",
+            Language::Java | Language::C | Language::Cpp => b"\"'",
+        }
+    }
+
+    /// Whether `//` and `/* */` are comments (otherwise `#`).
+    fn c_comments(self) -> bool {
+        self != Language::Python
+    }
+}
+
+/// A line start and how good a split point it is.
+#[derive(Debug, Clone, Copy)]
+struct Line {
+    start: usize,
+    /// Nesting depth (brackets or indentation); `usize::MAX` inside a string,
+    /// comment or bracketed continuation.
+    depth: usize,
+    blank: bool,
+    after_blank: bool,
+}
+
+/// A chunker that splits source code at definition boundaries.
+///
+/// Each chunk ends at the candidate line start within the target size with
+/// the shallowest nesting, so top-level items (functions, classes, impls)
+/// are kept whole whenever they fit and small items are packed together.
+/// Among equally shallow lines, one following a blank line wins, so doc
+/// comments and decorators stay with their definition; remaining ties go to
+/// the latest line. Lines longer than the target are hard split.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{CodeChunker, Language};
+///
+/// let src = b"fn a() {\n    let s = \"}\";\n}\n\nfn b() {\n    1\n}\n";
+/// let chunker = CodeChunker::new(Language::Rust).size(30);
+///
+/// let chunks = chunker.chunks(src);
+/// assert_eq!(chunks[0], b"fn a() {\n    let s = \"}\";\n}\n\n");
+/// assert_eq!(chunks[1], b"fn b() {\n    1\n}\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CodeChunker {
+    language: Language,
+    target_size: usize,
+    utf8_safe: bool,
+}
+
+impl CodeChunker {
+    /// Create a code chunker for `language` with the default target size.
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            target_size: DEFAULT_TARGET_SIZE,
+            utf8_safe: false,
+        }
+    }
+
+    /// Set the target chunk size in bytes.
+    pub fn size(mut self, size: usize) -> Self {
+        self.target_size = size;
+        self
+    }
+
+    /// Never hard split inside a multi-byte UTF-8 sequence.
+    ///
+    /// See [`Chunker::utf8_safe`](crate::Chunker::utf8_safe) for details.
+    pub fn utf8_safe(mut self, enabled: bool) -> Self {
+        self.utf8_safe = enabled;
+        self
+    }
+
+    /// Get the language.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Chunk `text`, returning (start, end) byte offsets.
+    pub fn chunk_offsets(&self, text: &[u8]) -> Vec<(usize, usize)> {
+        let size = self.target_size.max(1);
+        let lines = scan_lines(text, self.language);
+        let mut offsets = Vec::new();
+        let mut pos = 0;
+
+        while pos < text.len() {
+            let limit = pos + size;
+            let split = if limit >= text.len() {
+                text.len()
+            } else {
+                let first = lines.partition_point(|l| l.start <= pos);
+                let last = lines.partition_point(|l| l.start <= limit);
+                lines[first..last]
+                    .iter()
+                    .filter(|l| !l.blank)
+                    .min_by_key(|l| (l.depth, !l.after_blank, usize::MAX - l.start))
+                    .map(|l| l.start)
+                    .unwrap_or_else(|| {
+                        if self.utf8_safe {
+                            utf8_split_boundary(text, pos, limit)
+                        } else {
+                            limit
+                        }
+                    })
+            };
+            offsets.push((pos, split));
+            pos = split;
+        }
+        offsets
+    }
+
+    /// Chunk `text`, returning slices borrowed from it.
+    pub fn chunks<'t>(&self, text: &'t [u8]) -> Vec<&'t [u8]> {
+        self.chunk_offsets(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect()
+    }
+}
+
+/// Record every line start in `text` with its nesting depth.
+fn scan_lines(text: &[u8], language: Language) -> Vec<Line> {
+    let quotes = language.quotes();
+    let mut lines = Vec::new();
+    let mut brackets = 0usize;
+    // Open string: (quote byte, triple quoted)
+    let mut string: Option<(u8, bool)> = None;
+    let mut block_comment = false;
+    let mut continuation = false;
+    let mut prev_blank = false;
+    let mut i = 0;
+
+    while i <= text.len() {
+        // Line start
+        let line_end = memchr::memchr(b'\n', &text[i..]).map_or(text.len(), |n| i + n);
+        let line = &text[i..line_end];
+        let blank = string.is_none() && !block_comment && line.iter().all(u8::is_ascii_whitespace);
+        let inside = string.is_some() || block_comment || continuation;
+        let depth = if inside {
+            usize::MAX
+        } else if language.indented() {
+            if brackets > 0 {
+                usize::MAX
+            } else {
+                line.iter()
+                    .take_while(|&&b| b == b' ' || b == b'\t')
+                    .map(|&b| if b == b'\t' { 4 } else { 1 })
+                    .sum()
+            }
+        } else {
+            brackets
+        };
+        if i < text.len() {
+            lines.push(Line {
+                start: i,
+                depth,
+                blank,
+                after_blank: prev_blank,
+            });
+        }
+        prev_blank = blank;
+        continuation = false;
+
+        // Scan the line body
+        while i < line_end {
+            let b = text[i];
+            if block_comment {
+                if text[i..].starts_with(b"*/") {
+                    block_comment = false;
+                    i += 1;
+                }
+            } else if let Some((quote, triple)) = string {
+                if b == b'\\' {
+                    i += 1;
+                } else if b == quote {
+                    if !triple {
+                        string = None;
+                    } else if text[i..].starts_with(&[quote; 3]) {
+                        string = None;
+                        i += 2;
+                    }
+                }
+            } else if language.c_comments() && text[i..].starts_with(b"//") {
+                i = line_end;
+                continue;
+            } else if language.c_comments() && text[i..].starts_with(b"/*") {
+                block_comment = true;
+                i += 1;
+            } else if !language.c_comments() && b == b'#' {
+                i = line_end;
+                continue;
+            } else if language == Language::Rust && b == b'\'' {
+                i += rust_char_literal_len(&text[i..]).saturating_sub(1);
+            } else if quotes.contains(&b) {
+                let triple = language.indented() && text[i..].starts_with(&[b; 3]);
+                string = Some((b, triple));
+                if triple {
+                    i += 2;
+                }
+            } else if b == b'\\' && i + 1 == line_end {
+                continuation = true;
+            } else {
+                match b {
+                    b'{' | b'(' | b'[' => brackets += 1,
+                    b'}' | b')' | b']' => brackets = brackets.saturating_sub(1),
+                    _ => {}
+                }
+            }
+            i += 1;
+        }
+
+        // Single-quoted strings don't span lines (except after `\`)
+        if let Some((quote, false)) = string
+            && quote != b'`'
+            && !text[..line_end].ends_with(b"\\")
+        {
+            string = None;
+        }
+        i = line_end + 1;
+    }
+    lines
+}
+
+/// Length of a Rust char literal at the start of `text`, or 1 for a lifetime.
+fn rust_char_literal_len(text: &[u8]) -> usize {
+    if text.get(1) == Some(&b'\\') {
+        // Escaped char: '\n', '\'', '\u{1F600}'
+        match memchr::memchr(b'\'', &text[3.min(text.len())..]) {
+            Some(n) if n < 10 => 3 + n + 1,
+            _ => 1,
+        }
+    } else {
+        // Plain char (possibly multi-byte): closing quote within 5 bytes
+        match text.iter().skip(1).take(5).position(|&b| b == b'\'') {
+            Some(n) if n > 0 => n + 2,
+            _ => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_packs_top_level_items() {
+        let src = b"fn a() {}\nfn b() {}\nfn c() {\n    body();\n}\n";
+        let chunks = CodeChunker::new(Language::Rust).size(24).chunks(src);
+        assert_eq!(chunks[0], b"fn a() {}\nfn b() {}\n");
+        assert_eq!(chunks[1], b"fn c() {\n    body();\n}\n");
+    }
+
+    #[test]
+    fn test_code_keeps_doc_comments_with_item() {
+        let src = b"fn a() {\n    x();\n}\n\n/// Docs.\nfn b() {}\n";
+        let chunks = CodeChunker::new(Language::Rust).size(32).chunks(src);
+        assert_eq!(chunks[0], b"fn a() {\n    x();\n}\n\n");
+        assert_eq!(chunks[1], b"/// Docs.\nfn b() {}\n");
+    }
+
+    #[test]
+    fn test_code_ignores_braces_in_strings_and_comments() {
+        let src = b"fn a() {\n    let c = '{';\n    // }\n    /* } */\n    \"}\";\n}\nfn b() {}\n";
+        let lines = scan_lines(src, Language::Rust);
+        let depths: Vec<usize> = lines.iter().map(|l| l.depth).collect();
+        assert_eq!(depths, vec![0, 1, 1, 1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_code_rust_lifetimes() {
+        let src = b"fn a<'a>(x: &'a str) {\n    x\n}\nfn b() {}\n";
+        let lines = scan_lines(src, Language::Rust);
+        let depths: Vec<usize> = lines.iter().map(|l| l.depth).collect();
+        assert_eq!(depths, vec![0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_code_python_indentation() {
+        let src = b"def a():\n    return 1\n\n\nclass B:\n    def c(self):\n        pass\n";
+        let chunks = CodeChunker::new(Language::Python).size(40).chunks(src);
+        assert_eq!(chunks[0], b"def a():\n    return 1\n\n\n");
+        assert_eq!(chunks[1], b"class B:\n    def c(self):\n        pass\n");
+    }
+
+    #[test]
+    fn test_code_python_strings_and_brackets() {
+        let src = b"x = \"\"\"\ndef fake():\n\"\"\"\ny = [\n1,\n]\n# def nope(): {\nz = 1\n";
+        let lines = scan_lines(src, Language::Python);
+        let depths: Vec<usize> = lines.iter().map(|l| l.depth).collect();
+        assert_eq!(
+            depths,
+            vec![0, usize::MAX, usize::MAX, 0, usize::MAX, usize::MAX, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_code_js_template_literal() {
+        let src = b"const s = `\n}\n`;\nfunction f() {}\n";
+        let lines = scan_lines(src, Language::JavaScript);
+        let depths: Vec<usize> = lines.iter().map(|l| l.depth).collect();
+        assert_eq!(depths, vec![0, usize::MAX, usize::MAX, 0]);
+    }
+
+    #[test]
+    fn test_code_hard_split_long_line() {
+        let src = b"aaaaaaaaaaaaaaaaaaaa";
+        let chunker = CodeChunker::new(Language::C).size(8);
+        assert_eq!(chunker.chunk_offsets(src), vec![(0, 8), (8, 16), (16, 20)]);
+    }
+
+    #[test]
+    fn test_code_language_from_str() {
+        assert_eq!("Rust".parse::<Language>(), Ok(Language::Rust));
+        assert_eq!("py".parse::<Language>(), Ok(Language::Python));
+        assert_eq!("c++".parse::<Language>(), Ok(Language::Cpp));
+        assert!("cobol".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn test_code_covers_input() {
+        let src = b"int main() {\n  return 0;\n}\n\nvoid f() {}\n";
+        for size in 1..src.len() + 2 {
+            let chunks = CodeChunker::new(Language::C).size(size).chunks(src);
+            assert_eq!(chunks.concat(), src.to_vec());
+            assert!(chunks.iter().all(|c| !c.is_empty()));
+        }
+    }
+}
@@ -0,0 +1,863 @@
+//! Plain-data chunker configuration.
+//!
+//! This module provides [`ChunkerConfig`], every [`Chunker`] option in one
+//! value, so the bindings (and anything that stores chunking parameters)
+//! share a single description of how text is chunked.
+
+use alloc::vec::Vec;
+#[cfg(feature = "profiles")]
+use std::fmt;
+#[cfg(feature = "profiles")]
+use std::path::Path;
+
+use crate::checkpoint::Checksum;
+use crate::chunk::{
+    ChunkError, Chunker, DelimiterMode, FallbackStrategy, OwnedChunker, chunk, validate,
+};
+use crate::delim::{DEFAULT_DELIMITERS, DEFAULT_TARGET_SIZE};
+use crate::delimiters::Delimiters;
+use crate::metric::SizeUnit;
+
+/// All [`Chunker`] options as one value.
+///
+/// Fields are public and every field also has a builder method of the same
+/// name. Apply a config with [`chunker`](Self::chunker) or
+/// [`owned_chunker`](Self::owned_chunker); the result is the same as calling
+/// the matching builder methods by hand.
+///
+/// With the `serde` feature the config is `Serialize` and `Deserialize`.
+/// Delimiters and patterns are written as strings when they are valid UTF-8,
+/// and missing fields take their default. With the `profiles` feature a
+/// config can be loaded from a TOML or JSON file, see
+/// [`from_file`](Self::from_file).
+///
+/// # Example
+///
+/// ```
+/// use chunk::ChunkerConfig;
+///
+/// let config = ChunkerConfig::new().size(8).delimiters(b".").overlap(2);
+/// let text = b"Hello. World. Test.";
+/// let chunks: Vec<_> = config.chunker(text).collect();
+/// assert_eq!(chunks, vec![&b"Hello."[..], b"o. World.", b"d. Test."]);
+///
+/// let mut owned = config.owned_chunker(text.to_vec());
+/// assert_eq!(owned.collect_offsets(), vec![(0, 6), (4, 13), (11, 19)]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct ChunkerConfig {
+    /// Target chunk size, in `unit`s.
+    pub size: usize,
+    /// Single-byte delimiters, ignored when `pattern` is set.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bytes"))]
+    pub delimiters: Vec<u8>,
+    /// Multi-byte pattern to split on instead of `delimiters`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            with = "crate::serde_bytes::option",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub pattern: Option<Vec<u8>>,
+    /// Multi-byte patterns searched alongside `delimiters`.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bytes::seq"))]
+    pub patterns: Vec<Vec<u8>>,
+    /// Put the delimiter at the start of the next chunk.
+    pub prefix: bool,
+    /// Leave the delimiter out of both chunks (overrides `prefix`).
+    pub drop_delimiters: bool,
+    /// Split at the start of consecutive delimiter runs.
+    pub consecutive: bool,
+    /// Search forward when the backward window has no delimiter.
+    pub forward_fallback: bool,
+    /// Where to split when the window has no delimiter.
+    pub fallback: FallbackStrategy,
+    /// Prefer earlier `delimiters` over later ones within the window.
+    pub delimiter_priority: bool,
+    /// Units either side of `size` to look for the nearest boundary.
+    pub slack: usize,
+    /// Even out chunk sizes over the chunks left.
+    pub balanced: bool,
+    /// Complete lines per chunk, with `size` as a secondary cap.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub lines_per_chunk: Option<usize>,
+    /// Bytes of trailing context carried into the next chunk.
+    pub overlap: usize,
+    /// Overlap as a share of `size`, used instead of `overlap` when set.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub overlap_ratio: Option<f64>,
+    /// Never split inside a multi-byte UTF-8 sequence.
+    pub utf8_safe: bool,
+    /// Strip leading and trailing whitespace from chunks.
+    pub trim: bool,
+    /// Leave out empty and whitespace-only chunks.
+    pub skip_empty: bool,
+    /// Unit the target size is measured in.
+    pub unit: SizeUnit,
+    /// Trailing chunks smaller than this are merged into the previous one.
+    pub min_size: usize,
+    /// Hard cap on chunk length in bytes.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_size: Option<usize>,
+}
+
+impl Default for ChunkerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChunkerConfig {
+    /// Create a config with the [`Chunker`] defaults.
+    pub fn new() -> Self {
+        Self {
+            size: DEFAULT_TARGET_SIZE,
+            delimiters: DEFAULT_DELIMITERS.to_vec(),
+            pattern: None,
+            patterns: Vec::new(),
+            prefix: false,
+            drop_delimiters: false,
+            consecutive: false,
+            forward_fallback: false,
+            fallback: FallbackStrategy::Hard,
+            delimiter_priority: false,
+            slack: 0,
+            balanced: false,
+            lines_per_chunk: None,
+            overlap: 0,
+            overlap_ratio: None,
+            utf8_safe: false,
+            trim: false,
+            skip_empty: false,
+            unit: SizeUnit::Bytes,
+            min_size: 0,
+            max_size: None,
+        }
+    }
+
+    /// Set the target chunk size.
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set single-byte delimiters, clearing any pattern.
+    pub fn delimiters(mut self, delimiters: impl Into<Vec<u8>>) -> Self {
+        self.delimiters = delimiters.into();
+        self.pattern = None;
+        self
+    }
+
+    /// Set a multi-byte pattern to split on instead of delimiters.
+    pub fn pattern(mut self, pattern: impl Into<Vec<u8>>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    /// Set multi-byte patterns, composable with delimiters.
+    pub fn patterns<P: AsRef<[u8]>>(mut self, patterns: &[P]) -> Self {
+        self.patterns = patterns.iter().map(|p| p.as_ref().to_vec()).collect();
+        self
+    }
+
+    /// Set delimiters, patterns and prefix mode from a named class, as
+    /// [`Chunker::delimiter_class`] does.
+    pub fn delimiter_class(self, class: Delimiters) -> Self {
+        let mode = match class.prefix() {
+            true => DelimiterMode::KeepLeading,
+            false => DelimiterMode::KeepTrailing,
+        };
+        self.delimiters(class.bytes())
+            .patterns(class.patterns())
+            .delimiter_mode(mode)
+    }
+
+    /// Set prefix mode.
+    pub fn prefix(mut self, enabled: bool) -> Self {
+        self.prefix = enabled;
+        self
+    }
+
+    /// Set whether delimiters are dropped from the chunks.
+    pub fn drop_delimiters(mut self, enabled: bool) -> Self {
+        self.drop_delimiters = enabled;
+        self
+    }
+
+    /// Set where the delimiter goes, as `prefix` and `drop_delimiters`.
+    pub fn delimiter_mode(self, mode: DelimiterMode) -> Self {
+        self.prefix(mode == DelimiterMode::KeepLeading)
+            .drop_delimiters(mode == DelimiterMode::Drop)
+    }
+
+    /// The [`DelimiterMode`] that `prefix` and `drop_delimiters` describe.
+    fn mode(&self) -> DelimiterMode {
+        if self.drop_delimiters {
+            DelimiterMode::Drop
+        } else if self.prefix {
+            DelimiterMode::KeepLeading
+        } else {
+            DelimiterMode::KeepTrailing
+        }
+    }
+
+    /// Set consecutive run handling.
+    pub fn consecutive(mut self, enabled: bool) -> Self {
+        self.consecutive = enabled;
+        self
+    }
+
+    /// Set forward fallback search.
+    pub fn forward_fallback(mut self, enabled: bool) -> Self {
+        self.forward_fallback = enabled;
+        self
+    }
+
+    /// Set where to split when the window has no delimiter.
+    pub fn fallback(mut self, strategy: FallbackStrategy) -> Self {
+        self.fallback = strategy;
+        self
+    }
+
+    /// Set whether the order of `delimiters` is a priority.
+    pub fn delimiter_priority(mut self, enabled: bool) -> Self {
+        self.delimiter_priority = enabled;
+        self
+    }
+
+    /// Set the slack around the target size.
+    pub fn slack(mut self, slack: usize) -> Self {
+        self.slack = slack;
+        self
+    }
+
+    /// Set balanced chunking.
+    pub fn balanced(mut self, enabled: bool) -> Self {
+        self.balanced = enabled;
+        self
+    }
+
+    /// Set (or clear) the number of lines per chunk.
+    pub fn lines_per_chunk(mut self, lines: Option<usize>) -> Self {
+        self.lines_per_chunk = lines;
+        self
+    }
+
+    /// Set the overlap in bytes, clearing any overlap ratio.
+    pub fn overlap(mut self, overlap: usize) -> Self {
+        self.overlap = overlap;
+        self.overlap_ratio = None;
+        self
+    }
+
+    /// Set the overlap as a share of the size.
+    ///
+    /// See [`Chunker::overlap_ratio`] for details.
+    pub fn overlap_ratio(mut self, ratio: f64) -> Self {
+        self.overlap_ratio = Some(ratio);
+        self
+    }
+
+    /// Set UTF-8 safe splitting.
+    pub fn utf8_safe(mut self, enabled: bool) -> Self {
+        self.utf8_safe = enabled;
+        self
+    }
+
+    /// Set whitespace trimming.
+    pub fn trim(mut self, enabled: bool) -> Self {
+        self.trim = enabled;
+        self
+    }
+
+    /// Set whether empty chunks are skipped.
+    pub fn skip_empty(mut self, enabled: bool) -> Self {
+        self.skip_empty = enabled;
+        self
+    }
+
+    /// Set the unit the target size is measured in.
+    pub fn unit(mut self, unit: SizeUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Set the minimum size of a trailing chunk.
+    pub fn min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Set (or clear) the hard cap on chunk length.
+    pub fn max_size(mut self, max_size: Option<usize>) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Check the config, for when it comes from user input.
+    ///
+    /// The size must be at least 1, the overlap smaller than the size (in
+    /// any unit), the overlap ratio at least 0 and less than 1, and there
+    /// must be delimiters, patterns or lines per chunk to split at. Chunkers
+    /// made from an invalid config still chunk; see [`ChunkError`].
+    ///
+    /// ```
+    /// use chunk::{ChunkError, ChunkerConfig};
+    ///
+    /// assert_eq!(ChunkerConfig::new().size(0).validate(), Err(ChunkError::ZeroSize));
+    /// assert!(ChunkerConfig::new().size(64).overlap(16).validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), ChunkError> {
+        let splits = !self.delimiters.is_empty()
+            || self.pattern.is_some()
+            || !self.patterns.is_empty()
+            || self.lines_per_chunk.is_some();
+        validate(self.size, self.overlap, self.overlap_ratio, splits)
+    }
+
+    /// A stable checksum of every option, the same on every platform and
+    /// run, e.g. to tell whether stored chunks were made with this config.
+    ///
+    /// ```
+    /// use chunk::ChunkerConfig;
+    ///
+    /// let config = ChunkerConfig::new().size(512);
+    /// assert_eq!(config.checksum(), config.clone().checksum());
+    /// assert_ne!(config.checksum(), config.size(513).checksum());
+    /// ```
+    pub fn checksum(&self) -> u64 {
+        let patterns = self
+            .patterns
+            .iter()
+            .fold(Checksum::new(), |checksum, pattern| checksum.bytes(pattern));
+        Checksum::new()
+            .size(self.size)
+            .bytes(&self.delimiters)
+            .number(self.pattern.is_some())
+            .bytes(self.pattern.as_deref().unwrap_or_default())
+            .size(self.patterns.len())
+            .number(patterns.finish())
+            .number(self.prefix)
+            .number(self.drop_delimiters)
+            .number(self.consecutive)
+            .number(self.forward_fallback)
+            .number(self.fallback as u8)
+            .number(self.delimiter_priority)
+            .size(self.slack)
+            .number(self.balanced)
+            .option(self.lines_per_chunk)
+            .size(self.overlap)
+            .number(self.overlap_ratio.is_some())
+            .number(self.overlap_ratio.unwrap_or_default().to_bits())
+            .number(self.utf8_safe)
+            .number(self.trim)
+            .number(self.skip_empty)
+            .number(self.unit as u8)
+            .size(self.min_size)
+            .option(self.max_size)
+            .finish()
+    }
+
+    /// Create a [`Chunker`] over `text` with this config.
+    pub fn chunker<'a>(&'a self, text: &'a [u8]) -> Chunker<'a> {
+        self.apply(chunk(text))
+    }
+
+    /// Apply this config to an existing `chunker`, e.g. inside a
+    /// `chunk_batch` closure.
+    pub fn apply<'a>(&'a self, mut chunker: Chunker<'a>) -> Chunker<'a> {
+        chunker = match &self.pattern {
+            Some(pattern) => chunker.pattern(pattern),
+            None => chunker.delimiters(&self.delimiters),
+        };
+        if !self.patterns.is_empty() {
+            chunker = chunker.patterns(&self.patterns);
+        }
+        chunker = chunker.delimiter_mode(self.mode());
+        if self.consecutive {
+            chunker = chunker.consecutive();
+        }
+        if self.forward_fallback {
+            chunker = chunker.forward_fallback();
+        }
+        if let Some(max_size) = self.max_size {
+            chunker = chunker.max_size(max_size);
+        }
+        if let Some(lines) = self.lines_per_chunk {
+            chunker = chunker.lines_per_chunk(lines);
+        }
+        chunker = match self.overlap_ratio {
+            Some(ratio) => chunker.overlap_ratio(ratio),
+            None => chunker.overlap(self.overlap),
+        };
+        chunker
+            .size(self.size)
+            .size_unit(self.unit)
+            .fallback(self.fallback)
+            .delimiter_priority(self.delimiter_priority)
+            .slack(self.slack)
+            .balanced(self.balanced)
+            .utf8_safe(self.utf8_safe)
+            .trim(self.trim)
+            .skip_empty(self.skip_empty)
+            .min_size(self.min_size)
+    }
+
+    /// Create an [`OwnedChunker`] over `text` with this config.
+    pub fn owned_chunker<T: AsRef<[u8]> + Send + Sync + 'static>(&self, text: T) -> OwnedChunker {
+        let mut chunker = match &self.pattern {
+            Some(pattern) => OwnedChunker::from_source(text).pattern(pattern.clone()),
+            None => OwnedChunker::from_source(text).delimiters(self.delimiters.clone()),
+        };
+        if !self.patterns.is_empty() {
+            chunker = chunker.patterns(&self.patterns);
+        }
+        chunker = chunker.delimiter_mode(self.mode());
+        if self.consecutive {
+            chunker = chunker.consecutive();
+        }
+        if self.forward_fallback {
+            chunker = chunker.forward_fallback();
+        }
+        if let Some(max_size) = self.max_size {
+            chunker = chunker.max_size(max_size);
+        }
+        if let Some(lines) = self.lines_per_chunk {
+            chunker = chunker.lines_per_chunk(lines);
+        }
+        chunker = match self.overlap_ratio {
+            Some(ratio) => chunker.overlap_ratio(ratio),
+            None => chunker.overlap(self.overlap),
+        };
+        chunker
+            .size(self.size)
+            .size_unit(self.unit)
+            .fallback(self.fallback)
+            .delimiter_priority(self.delimiter_priority)
+            .slack(self.slack)
+            .balanced(self.balanced)
+            .utf8_safe(self.utf8_safe)
+            .trim(self.trim)
+            .skip_empty(self.skip_empty)
+            .min_size(self.min_size)
+    }
+}
+
+/// Error loading a [`ChunkerConfig`] from a file or string.
+#[cfg(feature = "profiles")]
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file couldn't be read.
+    Io(std::io::Error),
+    /// The document isn't valid TOML or JSON.
+    Syntax {
+        /// 1-based line of the error.
+        line: usize,
+        /// What went wrong.
+        message: String,
+    },
+    /// The document parsed, but isn't a valid config (unknown key, wrong type).
+    Invalid(String),
+    /// The requested profile isn't in the document.
+    UnknownProfile(String),
+}
+
+#[cfg(feature = "profiles")]
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(err) => write!(f, "{err}"),
+            ConfigError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            ConfigError::Invalid(message) => write!(f, "invalid config: {message}"),
+            ConfigError::UnknownProfile(name) => write!(f, "no profile named `{name}`"),
+        }
+    }
+}
+
+#[cfg(feature = "profiles")]
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "profiles")]
+impl From<std::io::Error> for ConfigError {
+    fn from(err: std::io::Error) -> Self {
+        ConfigError::Io(err)
+    }
+}
+
+/// Loading configs from TOML and JSON.
+///
+/// A document is either one config (its keys are the config fields) or a set
+/// of named profiles, one table per profile:
+///
+/// ```toml
+/// [rag-default]
+/// size = 1024
+/// overlap = 128
+/// utf8_safe = true
+///
+/// [logs]
+/// size = 8192
+/// delimiters = "\n"
+/// ```
+///
+/// Missing fields take their default; unknown keys are an error.
+#[cfg(feature = "profiles")]
+impl ChunkerConfig {
+    /// Parse a config from a JSON object.
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        Self::from_value(parse_json(json)?, None)
+    }
+
+    /// Serialize the config as a JSON object that [`from_json`](Self::from_json)
+    /// reads back.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("configs always serialize")
+    }
+
+    /// Parse the profile `name` from a JSON object of profiles.
+    pub fn from_json_profile(json: &str, name: &str) -> Result<Self, ConfigError> {
+        Self::from_value(parse_json(json)?, Some(name))
+    }
+
+    /// Parse a config from a TOML document.
+    ///
+    /// ```
+    /// use chunk::ChunkerConfig;
+    ///
+    /// let config = ChunkerConfig::from_toml("size = 512\npattern = \"\\n\\n\"\n").unwrap();
+    /// assert_eq!(config, ChunkerConfig::new().size(512).pattern("\n\n"));
+    /// ```
+    pub fn from_toml(toml: &str) -> Result<Self, ConfigError> {
+        Self::from_value(parse_toml(toml)?, None)
+    }
+
+    /// Parse the profile `name` (a `[name]` table) from a TOML document.
+    pub fn from_toml_profile(toml: &str, name: &str) -> Result<Self, ConfigError> {
+        Self::from_value(parse_toml(toml)?, Some(name))
+    }
+
+    /// Load a config, or the profile `profile`, from a file.
+    ///
+    /// Files ending in `.json` are read as JSON, anything else as TOML.
+    pub fn from_file(path: impl AsRef<Path>, profile: Option<&str>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let value = if path.extension().is_some_and(|ext| ext == "json") {
+            parse_json(&text)?
+        } else {
+            parse_toml(&text)?
+        };
+        Self::from_value(value, profile)
+    }
+
+    fn from_value(
+        mut value: serde_json::Value,
+        profile: Option<&str>,
+    ) -> Result<Self, ConfigError> {
+        if let Some(name) = profile {
+            value = value
+                .get_mut(name)
+                .filter(|v| v.is_object())
+                .map(serde_json::Value::take)
+                .ok_or_else(|| ConfigError::UnknownProfile(name.to_string()))?;
+        }
+        serde_json::from_value(value).map_err(|err| ConfigError::Invalid(err.to_string()))
+    }
+}
+
+#[cfg(feature = "profiles")]
+fn parse_json(json: &str) -> Result<serde_json::Value, ConfigError> {
+    serde_json::from_str(json).map_err(|err| ConfigError::Syntax {
+        line: err.line(),
+        message: err.to_string(),
+    })
+}
+
+#[cfg(feature = "profiles")]
+fn parse_toml(toml: &str) -> Result<serde_json::Value, ConfigError> {
+    crate::toml::parse(toml).map_err(|err| ConfigError::Syntax {
+        line: err.line,
+        message: err.message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &[u8] = "Héllo▁wörld. Fine▁day? Yes.\nNext line▁here.".as_bytes();
+
+    #[test]
+    fn test_config_matches_builder() {
+        let config = ChunkerConfig::new()
+            .size(9)
+            .delimiters(b". ")
+            .prefix(true)
+            .forward_fallback(true)
+            .fallback(FallbackStrategy::Whitespace)
+            .delimiter_priority(true)
+            .slack(2)
+            .balanced(true)
+            .overlap(2)
+            .utf8_safe(true)
+            .trim(true)
+            .skip_empty(true)
+            .min_size(3)
+            .max_size(Some(14));
+        let manual: Vec<_> = chunk(TEXT)
+            .size(9)
+            .delimiters(b". ")
+            .prefix()
+            .forward_fallback()
+            .fallback(FallbackStrategy::Whitespace)
+            .delimiter_priority(true)
+            .slack(2)
+            .balanced(true)
+            .overlap(2)
+            .utf8_safe(true)
+            .trim(true)
+            .skip_empty(true)
+            .min_size(3)
+            .max_size(14)
+            .collect();
+        assert_eq!(config.chunker(TEXT).collect::<Vec<_>>(), manual);
+    }
+
+    #[test]
+    fn test_config_pattern_and_patterns() {
+        let config = ChunkerConfig::new()
+            .size(12)
+            .pattern("▁")
+            .consecutive(true)
+            .unit(SizeUnit::Chars);
+        let manual = chunk(TEXT)
+            .size(12)
+            .pattern("▁".as_bytes())
+            .consecutive()
+            .size_unit(SizeUnit::Chars)
+            .collect_offsets();
+        assert_eq!(config.chunker(TEXT).collect_offsets(), manual);
+
+        let config = ChunkerConfig::new().size(10).patterns(&["\n", "? "]);
+        let manual = chunk(TEXT)
+            .size(10)
+            .patterns(&["\n", "? "])
+            .collect_offsets();
+        assert_eq!(config.chunker(TEXT).collect_offsets(), manual);
+    }
+
+    #[test]
+    fn test_config_delimiter_mode() {
+        for mode in [
+            DelimiterMode::KeepTrailing,
+            DelimiterMode::KeepLeading,
+            DelimiterMode::Drop,
+        ] {
+            let config = ChunkerConfig::new()
+                .size(9)
+                .delimiters(b".")
+                .delimiter_mode(mode);
+            let manual = chunk(TEXT).size(9).delimiters(b".").delimiter_mode(mode);
+            assert_eq!(config.mode(), mode);
+            assert_eq!(
+                config.chunker(TEXT).collect::<Vec<_>>(),
+                manual.collect::<Vec<_>>()
+            );
+        }
+        // Dropping wins over prefix
+        let config = ChunkerConfig::new().prefix(true).drop_delimiters(true);
+        assert_eq!(config.mode(), DelimiterMode::Drop);
+    }
+
+    #[test]
+    fn test_config_delimiter_class() {
+        for &class in Delimiters::ALL {
+            let config = ChunkerConfig::new().size(12).delimiter_class(class);
+            let manual = chunk(TEXT).size(12).delimiter_class(class);
+            assert_eq!(
+                config.chunker(TEXT).collect::<Vec<_>>(),
+                manual.collect::<Vec<_>>()
+            );
+        }
+        let config = ChunkerConfig::new().delimiter_class(Delimiters::MARKDOWN_HEADINGS);
+        assert_eq!(config.mode(), DelimiterMode::KeepLeading);
+        assert_eq!(config.patterns, vec![b"\n#".to_vec()]);
+    }
+
+    #[test]
+    fn test_config_owned_matches_borrowed() {
+        let config = ChunkerConfig::new()
+            .size(7)
+            .pattern("▁")
+            .patterns(&["."])
+            .prefix(true)
+            .overlap(3)
+            .max_size(Some(10));
+        let mut owned = config.owned_chunker(TEXT.to_vec());
+        assert_eq!(
+            owned.collect_offsets(),
+            config.chunker(TEXT).collect_offsets()
+        );
+    }
+
+    #[test]
+    fn test_config_overlap_ratio() {
+        let config = ChunkerConfig::new().overlap(3).overlap_ratio(0.25);
+        for size in [4, 8, 20] {
+            let config = config.clone().size(size);
+            let expected = chunk(TEXT).size(size).overlap(size / 4).collect_offsets();
+            assert_eq!(config.chunker(TEXT).collect_offsets(), expected);
+            assert_eq!(config.owned_chunker(TEXT).collect_offsets(), expected);
+        }
+        assert_eq!(config.clone().overlap(3).overlap_ratio, None);
+        assert_ne!(config.checksum(), config.clone().overlap(3).checksum());
+        assert_eq!(
+            config.clone().overlap_ratio(1.5).validate(),
+            Err(ChunkError::InvalidOverlapRatio)
+        );
+    }
+
+    #[test]
+    fn test_config_validate() {
+        let config = ChunkerConfig::new().size(4);
+        assert_eq!(
+            config.clone().unit(SizeUnit::Chars).overlap(4).validate(),
+            Err(ChunkError::OverlapTooLarge {
+                overlap: 4,
+                size: 4
+            })
+        );
+        assert!(
+            config
+                .clone()
+                .unit(SizeUnit::Chars)
+                .overlap(3)
+                .validate()
+                .is_ok()
+        );
+        let empty = config.delimiters(b"");
+        assert_eq!(empty.validate(), Err(ChunkError::NoDelimiters));
+        assert!(empty.clone().pattern(b"\n\n").validate().is_ok());
+        assert!(empty.clone().patterns(&[b"\n\n"]).validate().is_ok());
+        assert!(empty.lines_per_chunk(Some(2)).validate().is_ok());
+    }
+
+    #[cfg(feature = "profiles")]
+    #[test]
+    fn test_config_profiles() {
+        let toml = "[rag-default]\nsize = 1024\noverlap = 128\n\n[logs]\nsize = 8192\ndelimiters = \"\\n\"\n";
+        assert_eq!(
+            ChunkerConfig::from_toml_profile(toml, "logs").unwrap(),
+            ChunkerConfig::new().size(8192).delimiters(b"\n")
+        );
+        let json = r#"{"rag-default": {"size": 1024, "overlap": 128}}"#;
+        assert_eq!(
+            ChunkerConfig::from_json_profile(json, "rag-default").unwrap(),
+            ChunkerConfig::from_toml_profile(toml, "rag-default").unwrap()
+        );
+        assert_eq!(
+            ChunkerConfig::from_toml("overlap_ratio = 0.1\nfallback = \"whitespace\"\n").unwrap(),
+            ChunkerConfig::new()
+                .overlap_ratio(0.1)
+                .fallback(FallbackStrategy::Whitespace)
+        );
+        assert!(matches!(
+            ChunkerConfig::from_toml_profile(toml, "code"),
+            Err(ConfigError::UnknownProfile(name)) if name == "code"
+        ));
+    }
+
+    #[cfg(all(feature = "profiles", feature = "tiktoken"))]
+    #[test]
+    fn test_config_tiktoken_unit() {
+        let config = ChunkerConfig::from_toml("size = 512\nunit = \"o200k_base\"\n").unwrap();
+        assert_eq!(config.unit, SizeUnit::O200kBase);
+        assert_ne!(
+            config.checksum(),
+            config.clone().unit(SizeUnit::Cl100kBase).checksum()
+        );
+        let text = b"One sentence here. Another sentence there.";
+        assert_eq!(
+            config
+                .size(5)
+                .delimiters(b".")
+                .chunker(text)
+                .collect_offsets(),
+            vec![(0, 18), (18, 42)]
+        );
+    }
+
+    #[cfg(feature = "profiles")]
+    #[test]
+    fn test_config_json_round_trip() {
+        let config = ChunkerConfig::new()
+            .size(100)
+            .delimiters(b"\n\xff")
+            .patterns(&["\u{2581}", "\n\n"])
+            .delimiter_mode(DelimiterMode::Drop)
+            .max_size(Some(200))
+            .overlap_ratio(0.1)
+            .fallback(FallbackStrategy::Utf8Boundary)
+            .unit(SizeUnit::Chars);
+        assert_eq!(ChunkerConfig::from_json(&config.to_json()).unwrap(), config);
+        let default = ChunkerConfig::new();
+        assert_eq!(
+            ChunkerConfig::from_json(&default.to_json()).unwrap(),
+            default
+        );
+    }
+
+    #[cfg(feature = "profiles")]
+    #[test]
+    fn test_config_file_errors() {
+        let err = ChunkerConfig::from_toml("size = 10\nsise = 20\n").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid config: unknown field `sise`")
+        );
+        let err = ChunkerConfig::from_toml("size = 10\nsize =\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: expected a value");
+        assert!(matches!(
+            ChunkerConfig::from_json("{\"size\": 10,}"),
+            Err(ConfigError::Syntax { line: 1, .. })
+        ));
+
+        let dir = std::env::temp_dir().join(format!("chunk-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("profiles.json");
+        std::fs::write(&path, r#"{"code": {"size": 2048}}"#).unwrap();
+        assert_eq!(
+            ChunkerConfig::from_file(&path, Some("code")).unwrap(),
+            ChunkerConfig::new().size(2048)
+        );
+        assert!(matches!(
+            ChunkerConfig::from_file(dir.join("missing.toml"), None),
+            Err(ConfigError::Io(_))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_config_default() {
+        let config = ChunkerConfig::default();
+        assert_eq!(config.size, DEFAULT_TARGET_SIZE);
+        assert_eq!(config.delimiters, DEFAULT_DELIMITERS);
+        assert_eq!(
+            config.chunker(TEXT).collect_offsets(),
+            chunk(TEXT).collect_offsets()
+        );
+    }
+}
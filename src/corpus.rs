@@ -0,0 +1,430 @@
+//! Chunking every matching file under a directory (requires the `corpus`
+//! feature).
+//!
+//! This module provides [`chunk_dir`], which walks a directory for files
+//! matching a glob and chunks them one at a time, each result carrying the
+//! file's path. With the `rayon` feature,
+//! [`DirChunks::par_iter`] chunks the files in parallel instead, and
+//! [`DirChunks::with_cancel`] lets a [`CancelToken`] stop either part way,
+//! and [`DirChunks::save_state`] checkpoints a walk to resume later.
+
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+
+use crate::cancel::CancelToken;
+use crate::checkpoint::{Checkpoint, CheckpointMismatch, Checksum};
+use crate::config::ChunkerConfig;
+use crate::reader::Decompressed;
+
+/// Error starting a [`chunk_dir`] walk.
+#[derive(Debug)]
+pub enum CorpusError {
+    /// The glob isn't a valid pattern.
+    Pattern(String),
+    /// A directory couldn't be read.
+    Io {
+        /// The directory.
+        path: PathBuf,
+        /// Why it couldn't be read.
+        error: io::Error,
+    },
+}
+
+impl fmt::Display for CorpusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CorpusError::Pattern(message) => write!(f, "invalid glob: {message}"),
+            CorpusError::Io { path, error } => write!(f, "{}: {error}", path.display()),
+        }
+    }
+}
+
+impl core::error::Error for CorpusError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            CorpusError::Io { error, .. } => Some(error),
+            CorpusError::Pattern(_) => None,
+        }
+    }
+}
+
+/// The chunks of one file from [`chunk_dir`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChunks {
+    /// The file, under the directory that was walked.
+    pub path: PathBuf,
+    /// The file's contents.
+    pub text: Vec<u8>,
+    /// (start, end) byte offsets of each chunk in `text`.
+    pub offsets: Vec<(usize, usize)>,
+}
+
+impl FileChunks {
+    /// The chunks as slices of `text`.
+    pub fn chunks(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.offsets
+            .iter()
+            .map(|&(start, end)| &self.text[start..end])
+    }
+}
+
+/// Chunk every file under `root` whose path relative to it matches `glob`,
+/// with `config`.
+///
+/// `*` and `?` stay within one path component and `**` spans any number of
+/// them, so `"**/*.md"` matches Markdown at any depth. The directory is
+/// walked up front, without following symlinked directories; files are then
+/// read and chunked one at a time, in path order, as the iterator advances.
+/// With the `gzip` feature, gzip files are decompressed as they're read.
+///
+/// # Example
+///
+/// ```no_run
+/// use chunk::{ChunkerConfig, chunk_dir};
+///
+/// let config = ChunkerConfig::new().size(1024);
+/// for file in chunk_dir("docs", "**/*.md", &config)? {
+///     let file = file?;
+///     println!("{}: {} chunks", file.path.display(), file.offsets.len());
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn chunk_dir(
+    root: impl AsRef<Path>,
+    glob: &str,
+    config: &ChunkerConfig,
+) -> Result<DirChunks, CorpusError> {
+    let pattern = Pattern::new(glob).map_err(|err| CorpusError::Pattern(err.to_string()))?;
+    let root = root.as_ref();
+    let mut paths = Vec::new();
+    walk(root, root, &pattern, &mut paths)?;
+    paths.sort_unstable();
+    let checksum = paths.iter().fold(
+        Checksum::new().number(config.checksum()).size(paths.len()),
+        |checksum, path| {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            checksum.bytes(relative.as_os_str().as_encoded_bytes())
+        },
+    );
+    Ok(DirChunks {
+        paths: paths.into_iter(),
+        config: config.clone(),
+        cancel: None,
+        checkpoint: Checkpoint {
+            config: checksum.finish(),
+            ..Checkpoint::default()
+        },
+    })
+}
+
+/// Push the files under `dir` matching `pattern`, relative to `root`.
+fn walk(
+    root: &Path,
+    dir: &Path,
+    pattern: &Pattern,
+    paths: &mut Vec<PathBuf>,
+) -> Result<(), CorpusError> {
+    let io_error = |error| CorpusError::Io {
+        path: dir.to_path_buf(),
+        error,
+    };
+    for entry in std::fs::read_dir(dir).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        let path = entry.path();
+        if entry.file_type().map_err(io_error)?.is_dir() {
+            walk(root, &path, pattern, paths)?;
+            continue;
+        }
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        // Symlinks to files count, to directories don't
+        if pattern.matches_path_with(relative, options) && path.is_file() {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Iterator over the chunks of each file, created by [`chunk_dir`].
+///
+/// A file that can't be read yields an error and the walk goes on.
+#[derive(Debug, Clone)]
+pub struct DirChunks {
+    paths: std::vec::IntoIter<PathBuf>,
+    config: ChunkerConfig,
+    cancel: Option<CancelToken>,
+    /// Files and chunks yielded so far
+    checkpoint: Checkpoint,
+}
+
+impl DirChunks {
+    /// The files not chunked yet.
+    pub fn paths(&self) -> &[PathBuf] {
+        self.paths.as_slice()
+    }
+
+    /// Stop chunking once `token` is cancelled.
+    ///
+    /// The token is checked after every chunk. Once it's cancelled, the file
+    /// being chunked and every file left yield an
+    /// [`Interrupted`](io::ErrorKind::Interrupted) error, without being read.
+    pub fn with_cancel(mut self, token: &CancelToken) -> Self {
+        self.cancel = Some(token.clone());
+        self
+    }
+
+    /// Where the walk has got to, for picking up later with
+    /// [`restore_state`](Self::restore_state).
+    ///
+    /// The position is the number of files yielded, errors included, and
+    /// the checksum covers the config and every file the walk found.
+    pub fn save_state(&self) -> Checkpoint {
+        self.checkpoint
+    }
+
+    /// Skip the files a walk saved in `checkpoint` had already yielded.
+    ///
+    /// Fails if the config differs or the directory no longer holds exactly
+    /// the same matching files, since the count would then skip the wrong
+    /// ones.
+    ///
+    /// ```no_run
+    /// use chunk::{Checkpoint, ChunkerConfig, chunk_dir};
+    ///
+    /// # fn load() -> Checkpoint { Checkpoint::default() }
+    /// let config = ChunkerConfig::new().size(1024);
+    /// let files = chunk_dir("docs", "**/*.md", &config)?.restore_state(&load())?;
+    /// for file in files {
+    ///     println!("{}", file?.path.display());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn restore_state(mut self, checkpoint: &Checkpoint) -> Result<Self, CheckpointMismatch> {
+        checkpoint.check(self.checkpoint.config)?;
+        if let Some(skip) = (checkpoint.position as usize).checked_sub(1) {
+            self.paths.nth(skip);
+        }
+        self.checkpoint = *checkpoint;
+        Ok(self)
+    }
+
+    /// Chunk the remaining files in parallel on the rayon thread pool
+    /// (requires the `rayon` feature).
+    ///
+    /// Collecting the results keeps them in path order.
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(
+        self,
+    ) -> impl rayon::iter::IndexedParallelIterator<Item = io::Result<FileChunks>> {
+        use rayon::prelude::*;
+
+        let (config, cancel) = (self.config, self.cancel);
+        self.paths
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(move |path| read_chunks(&config, cancel.as_ref(), path))
+    }
+}
+
+impl Iterator for DirChunks {
+    type Item = io::Result<FileChunks>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let path = self.paths.next()?;
+        let file = read_chunks(&self.config, self.cancel.as_ref(), path);
+        self.checkpoint.position += 1;
+        self.checkpoint.resume_from = self.checkpoint.position;
+        if let Ok(file) = &file {
+            self.checkpoint.chunks += file.offsets.len() as u64;
+        }
+        Some(file)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.paths.size_hint()
+    }
+}
+
+impl ExactSizeIterator for DirChunks {}
+
+/// Read and chunk the file at `path`, naming it in any error, unless
+/// `cancel` is cancelled.
+fn read_chunks(
+    config: &ChunkerConfig,
+    cancel: Option<&CancelToken>,
+    path: PathBuf,
+) -> io::Result<FileChunks> {
+    if let Some(token) = cancel {
+        token.check()?;
+    }
+    let mut text = Vec::new();
+    Decompressed::open(&path)
+        .and_then(|mut file| file.read_to_end(&mut text))
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))?;
+    let offsets = match cancel {
+        Some(token) => config.chunker(&text).collect_offsets_until(token)?,
+        None => config.chunker(&text).collect_offsets(),
+    };
+    Ok(FileChunks {
+        path,
+        text,
+        offsets,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directory tree that removes itself.
+    struct TempTree(PathBuf);
+
+    impl TempTree {
+        fn new(name: &str, files: &[(&str, &str)]) -> Self {
+            let root =
+                std::env::temp_dir().join(format!("chunk-corpus-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&root);
+            for (path, text) in files {
+                let path = root.join(path);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, text).unwrap();
+            }
+            Self(root)
+        }
+    }
+
+    impl Drop for TempTree {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn relative(tree: &TempTree, files: &[FileChunks]) -> Vec<String> {
+        files
+            .iter()
+            .map(|file| {
+                let path = file.path.strip_prefix(&tree.0).unwrap();
+                path.to_string_lossy().replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chunk_dir_glob() {
+        let tree = TempTree::new(
+            "glob",
+            &[
+                ("a.md", "One. Two."),
+                ("notes.txt", "skip"),
+                ("guide/b.md", "Three. Four."),
+                ("guide/deep/c.md", "Five."),
+            ],
+        );
+        let config = ChunkerConfig::new().size(6).delimiters(b".");
+        let files: Vec<_> = chunk_dir(&tree.0, "**/*.md", &config)
+            .unwrap()
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(
+            relative(&tree, &files),
+            vec!["a.md", "guide/b.md", "guide/deep/c.md"]
+        );
+        let chunks: Vec<_> = files[1].chunks().collect();
+        assert_eq!(chunks, vec![&b"Three."[..], b" Four."]);
+
+        let top: Vec<_> = chunk_dir(&tree.0, "*.md", &config)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(relative(&tree, &top), vec!["a.md"]);
+        assert_eq!(chunk_dir(&tree.0, "*.rs", &config).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_chunk_dir_errors() {
+        let config = ChunkerConfig::new();
+        assert!(matches!(
+            chunk_dir(".", "[", &config),
+            Err(CorpusError::Pattern(_))
+        ));
+        let missing = std::env::temp_dir().join("chunk-corpus-missing");
+        let err = chunk_dir(&missing, "**/*", &config).unwrap_err();
+        assert!(matches!(&err, CorpusError::Io { path, .. } if *path == missing));
+        assert!(err.to_string().starts_with(&missing.display().to_string()));
+    }
+
+    #[test]
+    fn test_chunk_dir_cancel() {
+        let tree = TempTree::new("cancel", &[("a.txt", "One."), ("b.txt", "Two.")]);
+        let token = CancelToken::new();
+        let mut files = chunk_dir(&tree.0, "*", &ChunkerConfig::new())
+            .unwrap()
+            .with_cancel(&token);
+        assert_eq!(files.next().unwrap().unwrap().text, b"One.");
+        token.cancel();
+        let err = files.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        assert!(files.next().is_none());
+    }
+
+    #[test]
+    fn test_chunk_dir_checkpoint() {
+        let tree = TempTree::new(
+            "checkpoint",
+            &[("a.txt", "One. Two."), ("b.txt", "Six."), ("c.txt", "Ten.")],
+        );
+        let config = ChunkerConfig::new().size(5).delimiters(b".");
+        let mut files = chunk_dir(&tree.0, "*.txt", &config).unwrap();
+        files.next();
+        let checkpoint = files.save_state();
+        assert_eq!((checkpoint.position, checkpoint.chunks), (1, 2));
+
+        let rest: Vec<_> = chunk_dir(&tree.0, "*.txt", &config)
+            .unwrap()
+            .restore_state(&checkpoint)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(relative(&tree, &rest), vec!["b.txt", "c.txt"]);
+
+        let other = ChunkerConfig::new().size(6);
+        let walk = chunk_dir(&tree.0, "*.txt", &other).unwrap();
+        assert!(walk.restore_state(&checkpoint).is_err());
+        std::fs::write(tree.0.join("d.txt"), "New.").unwrap();
+        let walk = chunk_dir(&tree.0, "*.txt", &config).unwrap();
+        assert!(walk.restore_state(&checkpoint).is_err());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_chunk_dir_parallel_matches_sequential() {
+        use rayon::prelude::*;
+
+        let files: Vec<(String, String)> = (0..20)
+            .map(|i| (format!("d{}/f{i}.txt", i % 3), "Some text. ".repeat(i)))
+            .collect();
+        let refs: Vec<_> = files
+            .iter()
+            .map(|(p, t)| (p.as_str(), t.as_str()))
+            .collect();
+        let tree = TempTree::new("parallel", &refs);
+        let config = ChunkerConfig::new().size(16).delimiters(b".");
+        let sequential: Vec<_> = chunk_dir(&tree.0, "**/*.txt", &config)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        let parallel: Vec<_> = chunk_dir(&tree.0, "**/*.txt", &config)
+            .unwrap()
+            .par_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(sequential.len(), 20);
+        assert_eq!(parallel, sequential);
+    }
+}
@@ -0,0 +1,197 @@
+//! Row-aware chunking for CSV.
+//!
+//! This module provides [`CsvChunker`], which only splits between complete
+//! records. A small state machine tracks quoted fields, so newlines inside
+//! `"..."` never end a record.
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::delim::DEFAULT_TARGET_SIZE;
+
+/// A chunker for CSV (and other quoted, newline-terminated record formats).
+///
+/// Records are packed greedily while the chunk stays within the target size.
+/// A record is never split, so one longer than the target becomes a chunk of
+/// its own. Quotes follow RFC 4180: a doubled quote inside a quoted field is
+/// an escaped quote.
+///
+/// With [`header`](Self::header), the first record is treated as a header:
+/// offsets cover only the data records, and [`chunks`](Self::chunks) repeats
+/// the header at the top of every chunk (counted against the target size).
+///
+/// # Example
+///
+/// ```
+/// use chunk::CsvChunker;
+///
+/// let csv = b"id,note\n1,\"multi\nline\"\n2,plain\n";
+/// let chunks = CsvChunker::new().size(24).header().chunks(csv);
+/// assert_eq!(&*chunks[0], b"id,note\n1,\"multi\nline\"\n");
+/// assert_eq!(&*chunks[1], b"id,note\n2,plain\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CsvChunker {
+    target_size: usize,
+    quote: u8,
+    header: bool,
+}
+
+impl Default for CsvChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsvChunker {
+    /// Create a CSV chunker with the default target size and `"` quotes.
+    pub fn new() -> Self {
+        Self {
+            target_size: DEFAULT_TARGET_SIZE,
+            quote: b'"',
+            header: false,
+        }
+    }
+
+    /// Set the target chunk size in bytes.
+    pub fn size(mut self, size: usize) -> Self {
+        self.target_size = size;
+        self
+    }
+
+    /// Set the quote character (default: `"`).
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Treat the first record as a header and repeat it in every chunk.
+    pub fn header(mut self) -> Self {
+        self.header = true;
+        self
+    }
+
+    /// Byte range of the header record, if header mode is on.
+    pub fn header_range(&self, text: &[u8]) -> Option<(usize, usize)> {
+        self.header
+            .then(|| records(text, self.quote).next())
+            .flatten()
+    }
+
+    /// Chunk `text`, returning (start, end) byte offsets of the records in
+    /// each chunk (excluding the header in header mode).
+    pub fn chunk_offsets(&self, text: &[u8]) -> Vec<(usize, usize)> {
+        let header_len = self.header_range(text).map_or(0, |(_, end)| end);
+        let budget = self.target_size.saturating_sub(header_len).max(1);
+        let mut offsets = Vec::new();
+        let mut start = header_len;
+        let mut end = header_len;
+
+        for (_, record_end) in records(&text[header_len..], self.quote) {
+            let record_end = header_len + record_end;
+            if record_end - start > budget && end > start {
+                offsets.push((start, end));
+                start = end;
+            }
+            end = record_end;
+        }
+
+        if end > start {
+            offsets.push((start, end));
+        }
+        offsets
+    }
+
+    /// Chunk `text`, returning each chunk's bytes.
+    ///
+    /// Without a header the chunks borrow from `text`; in header mode each one
+    /// is a copy with the header prepended.
+    pub fn chunks<'t>(&self, text: &'t [u8]) -> Vec<Cow<'t, [u8]>> {
+        let header = self
+            .header_range(text)
+            .map(|(start, end)| &text[start..end]);
+        self.chunk_offsets(text)
+            .into_iter()
+            .map(|(start, end)| match header {
+                Some(header) => Cow::Owned([header, &text[start..end]].concat()),
+                None => Cow::Borrowed(&text[start..end]),
+            })
+            .collect()
+    }
+}
+
+/// Iterate over (start, end) spans of each record, including its newline.
+fn records(text: &[u8], quote: u8) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut pos = 0;
+    core::iter::from_fn(move || {
+        if pos >= text.len() {
+            return None;
+        }
+        let start = pos;
+        let mut in_quotes = false;
+        // Only quotes and newlines matter, so jump between them
+        while let Some(i) = memchr::memchr2(quote, b'\n', &text[pos..]) {
+            pos += i + 1;
+            if text[pos - 1] == quote {
+                in_quotes = !in_quotes;
+            } else if !in_quotes {
+                return Some((start, pos));
+            }
+        }
+        pos = text.len();
+        Some((start, pos))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_quoted_newlines() {
+        let csv = b"a,\"x\ny\"\nb,\"he said \"\"hi\n\"\"\"\nc,z\n";
+        let spans: Vec<_> = records(csv, b'"').collect();
+        assert_eq!(spans, vec![(0, 8), (8, 28), (28, 32)]);
+    }
+
+    #[test]
+    fn test_csv_packs_records() {
+        let csv = b"1,a\n2,b\n3,c\n";
+        let chunks = CsvChunker::new().size(8).chunks(csv);
+        assert_eq!(chunks, vec![&b"1,a\n2,b\n"[..], &b"3,c\n"[..]]);
+    }
+
+    #[test]
+    fn test_csv_header_repeated() {
+        let csv = b"h1,h2\n1,a\n2,b\n3,c\n";
+        let chunker = CsvChunker::new().size(14).header();
+        assert_eq!(chunker.header_range(csv), Some((0, 6)));
+        assert_eq!(chunker.chunk_offsets(csv), vec![(6, 14), (14, 18)]);
+        let chunks = chunker.chunks(csv);
+        assert_eq!(&*chunks[0], b"h1,h2\n1,a\n2,b\n");
+        assert_eq!(&*chunks[1], b"h1,h2\n3,c\n");
+        assert!(chunks.iter().all(|c| c.len() <= 14));
+    }
+
+    #[test]
+    fn test_csv_oversized_record_kept_whole() {
+        let csv = b"1,short\n2,\"a much longer\nquoted field\"\n3,x\n";
+        let chunks = CsvChunker::new().size(10).chunks(csv);
+        assert_eq!(chunks[1], &b"2,\"a much longer\nquoted field\"\n"[..]);
+        assert_eq!(chunks.concat(), csv.to_vec());
+    }
+
+    #[test]
+    fn test_csv_custom_quote_and_missing_newline() {
+        let csv = b"1,'a\nb'\n2,c";
+        let chunks = CsvChunker::new().size(4).quote(b'\'').chunks(csv);
+        assert_eq!(chunks, vec![&b"1,'a\nb'\n"[..], &b"2,c"[..]]);
+    }
+
+    #[test]
+    fn test_csv_header_only() {
+        let chunker = CsvChunker::new().header();
+        assert!(chunker.chunks(b"h1,h2\n").is_empty());
+        assert!(chunker.chunks(b"").is_empty());
+    }
+}
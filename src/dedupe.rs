@@ -0,0 +1,141 @@
+//! Duplicate-chunk detection across a batch.
+//!
+//! This module provides [`find_duplicates`], which maps every byte-identical
+//! chunk to the first chunk with the same bytes, so boilerplate (headers,
+//! footers, licenses) repeated across similar documents can be flagged or
+//! dropped before embedding.
+
+use std::collections::HashMap;
+
+/// Duplicate chunks of a batch, from [`find_duplicates`].
+///
+/// Chunks are identified by their index in the batch, in input order. The
+/// canonical chunk of a group of identical chunks is the first one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Duplicates {
+    /// For every chunk, the index of its canonical chunk (its own index when
+    /// it is the first with its bytes).
+    pub canonical: Vec<usize>,
+}
+
+impl Duplicates {
+    /// Whether chunk `index` repeats an earlier chunk.
+    pub fn is_duplicate(&self, index: usize) -> bool {
+        self.canonical[index] != index
+    }
+
+    /// Number of chunks that repeat an earlier chunk.
+    pub fn count(&self) -> usize {
+        (0..self.canonical.len())
+            .filter(|&i| self.is_duplicate(i))
+            .count()
+    }
+
+    /// Indices of the chunks to keep when dropping duplicates.
+    pub fn unique(&self) -> Vec<usize> {
+        (0..self.canonical.len())
+            .filter(|&i| !self.is_duplicate(i))
+            .collect()
+    }
+
+    /// (duplicate, canonical) index pairs, in input order.
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        self.canonical
+            .iter()
+            .enumerate()
+            .filter(|&(i, &canonical)| canonical != i)
+            .map(|(i, &canonical)| (i, canonical))
+            .collect()
+    }
+}
+
+/// Find byte-identical chunks in a batch.
+///
+/// # Example
+///
+/// ```
+/// use chunk::find_duplicates;
+///
+/// let chunks: &[&[u8]] = &[b"Header.", b"Body one.", b"Header.", b"Body two."];
+/// let duplicates = find_duplicates(chunks);
+/// assert_eq!(duplicates.canonical, vec![0, 1, 0, 3]);
+/// assert_eq!(duplicates.pairs(), vec![(2, 0)]);
+/// assert_eq!(duplicates.unique(), vec![0, 1, 3]);
+/// ```
+pub fn find_duplicates<T: AsRef<[u8]>>(chunks: &[T]) -> Duplicates {
+    find_duplicates_iter(chunks.iter().map(AsRef::as_ref))
+}
+
+/// Find byte-identical chunks across many documents, given each document's
+/// chunk offsets (e.g. from `chunk_batch`).
+///
+/// Chunks are numbered in document order, then offset order within each
+/// document, as if the offset lists were concatenated.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{chunk, find_batch_duplicates};
+///
+/// let docs: &[&[u8]] = &[b"Intro. Alpha.", b"Intro. Beta."];
+/// let offsets: Vec<_> = docs
+///     .iter()
+///     .map(|doc| chunk(doc).size(7).delimiters(b".").collect_offsets())
+///     .collect();
+/// let duplicates = find_batch_duplicates(docs, &offsets);
+/// // The second document's "Intro." is chunk 2 overall
+/// assert_eq!(duplicates.pairs(), vec![(2, 0)]);
+/// ```
+pub fn find_batch_duplicates<T: AsRef<[u8]>>(
+    texts: &[T],
+    offsets: &[Vec<(usize, usize)>],
+) -> Duplicates {
+    find_duplicates_iter(texts.iter().zip(offsets).flat_map(|(text, offsets)| {
+        let text = text.as_ref();
+        offsets.iter().map(move |&(start, end)| &text[start..end])
+    }))
+}
+
+fn find_duplicates_iter<'t>(chunks: impl Iterator<Item = &'t [u8]>) -> Duplicates {
+    let mut first: HashMap<&[u8], usize> = HashMap::new();
+    let canonical = chunks
+        .enumerate()
+        .map(|(i, chunk)| *first.entry(chunk).or_insert(i))
+        .collect();
+    Duplicates { canonical }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedupe_groups() {
+        let chunks = ["a", "b", "a", "c", "b", "a"];
+        let duplicates = find_duplicates(&chunks);
+        assert_eq!(duplicates.canonical, vec![0, 1, 0, 3, 1, 0]);
+        assert_eq!(duplicates.count(), 3);
+        assert!(duplicates.is_duplicate(4));
+        assert!(!duplicates.is_duplicate(3));
+        assert_eq!(duplicates.pairs(), vec![(2, 0), (4, 1), (5, 0)]);
+        assert_eq!(duplicates.unique(), vec![0, 1, 3]);
+
+        // Identical only means identical bytes
+        let near = find_duplicates(&["Footer.", "Footer. ", "footer."]);
+        assert_eq!(near.count(), 0);
+        assert_eq!(find_duplicates::<&str>(&[]), Duplicates::default());
+    }
+
+    #[test]
+    fn test_dedupe_batch() {
+        let docs: [&[u8]; 3] = [b"Head. One.", b"Head. Two.", b"Head. One."];
+        let offsets = vec![vec![(0, 5), (5, 10)]; 3];
+        let duplicates = find_batch_duplicates(&docs, &offsets);
+        assert_eq!(duplicates.canonical, vec![0, 1, 0, 3, 0, 1]);
+
+        // Documents without an offsets list are left out
+        let duplicates = find_batch_duplicates(&docs, &offsets[..1]);
+        assert_eq!(duplicates.canonical, vec![0, 1]);
+    }
+}
@@ -1,10 +1,13 @@
 //! Shared delimiter utilities for chunking and splitting.
 //!
 //! This module contains the core delimiter search functions using
-//! SIMD-accelerated memchr (1-3 delimiters) or lookup table (4+ delimiters),
+//! SIMD-accelerated memchr (1-6 delimiters) or lookup table (7+ delimiters),
 //! and multi-byte pattern search using memmem (1-3 patterns) or
 //! Aho-Corasick via daggrs (4+ patterns).
 
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
 use daggrs::{DoubleArrayAhoCorasick, MatchKind, Trie};
 use memchr::memmem;
 
@@ -14,8 +17,18 @@ pub const DEFAULT_TARGET_SIZE: usize = 4096;
 /// Default delimiters: newline, period, question mark.
 pub const DEFAULT_DELIMITERS: &[u8] = b"\n.?";
 
-/// Find last delimiter in window using SIMD-accelerated memchr (1-3 delimiters)
-/// or lookup table (4+ delimiters).
+/// Most delimiters searched with memchr; larger sets use a lookup table.
+///
+/// Sets of 4-6 bytes are searched in two memchr passes, the second limited
+/// to the part of the window before (or after) the first pass's hit.
+const MAX_MEMCHR_DELIMITERS: usize = 6;
+
+/// Block size for the two-pass search, so a first pass that finds nothing
+/// nearby can't scan far past a hit of the second pass.
+const TWO_PASS_BLOCK: usize = 1024;
+
+/// Find last delimiter in window using SIMD-accelerated memchr (1-6 delimiters)
+/// or lookup table (7+ delimiters).
 #[inline]
 pub fn find_last_delimiter(
     window: &[u8],
@@ -23,20 +36,29 @@ pub fn find_last_delimiter(
     table: Option<&[bool; 256]>,
 ) -> Option<usize> {
     if let Some(t) = table {
-        window.iter().rposition(|&b| t[b as usize])
-    } else {
-        match delimiters.len() {
-            1 => memchr::memrchr(delimiters[0], window),
-            2 => memchr::memrchr2(delimiters[0], delimiters[1], window),
-            3 => memchr::memrchr3(delimiters[0], delimiters[1], delimiters[2], window),
-            0 => None,
-            _ => unreachable!(),
+        return window.iter().rposition(|&b| t[b as usize]);
+    }
+    if delimiters.len() <= 3 {
+        return memrchr_any(delimiters, window);
+    }
+    let (head, tail) = delimiters.split_at(3);
+    let mut end = window.len();
+    for block in window.rchunks(TWO_PASS_BLOCK) {
+        let start = end - block.len();
+        let found = match memrchr_any(head, block) {
+            Some(i) => Some(memrchr_any(tail, &block[i + 1..]).map_or(i, |j| i + 1 + j)),
+            None => memrchr_any(tail, block),
+        };
+        if let Some(i) = found {
+            return Some(start + i);
         }
+        end = start;
     }
+    None
 }
 
-/// Find first delimiter in window using SIMD-accelerated memchr (1-3 delimiters)
-/// or lookup table (4+ delimiters).
+/// Find first delimiter in window using SIMD-accelerated memchr (1-6 delimiters)
+/// or lookup table (7+ delimiters).
 #[inline]
 pub fn find_first_delimiter(
     window: &[u8],
@@ -44,22 +66,95 @@ pub fn find_first_delimiter(
     table: Option<&[bool; 256]>,
 ) -> Option<usize> {
     if let Some(t) = table {
-        window.iter().position(|&b| t[b as usize])
-    } else {
-        match delimiters.len() {
-            1 => memchr::memchr(delimiters[0], window),
-            2 => memchr::memchr2(delimiters[0], delimiters[1], window),
-            3 => memchr::memchr3(delimiters[0], delimiters[1], delimiters[2], window),
-            0 => None,
-            _ => unreachable!(),
+        return window.iter().position(|&b| t[b as usize]);
+    }
+    if delimiters.len() <= 3 {
+        return memchr_any(delimiters, window);
+    }
+    let (head, tail) = delimiters.split_at(3);
+    for (n, block) in window.chunks(TWO_PASS_BLOCK).enumerate() {
+        let found = match memchr_any(head, block) {
+            Some(i) => Some(memchr_any(tail, &block[..i]).unwrap_or(i)),
+            None => memchr_any(tail, block),
+        };
+        if let Some(i) = found {
+            return Some(n * TWO_PASS_BLOCK + i);
         }
     }
+    None
+}
+
+/// First occurrence of any of up to 3 bytes.
+#[inline]
+fn memchr_any(bytes: &[u8], window: &[u8]) -> Option<usize> {
+    match *bytes {
+        [a] => memchr::memchr(a, window),
+        [a, b] => memchr::memchr2(a, b, window),
+        [a, b, c] => memchr::memchr3(a, b, c, window),
+        [] => None,
+        _ => unreachable!(),
+    }
+}
+
+/// Last occurrence of any of up to 3 bytes.
+#[inline]
+fn memrchr_any(bytes: &[u8], window: &[u8]) -> Option<usize> {
+    match *bytes {
+        [a] => memchr::memrchr(a, window),
+        [a, b] => memchr::memrchr2(a, b, window),
+        [a, b, c] => memchr::memrchr3(a, b, c, window),
+        [] => None,
+        _ => unreachable!(),
+    }
 }
 
-/// Build lookup table for 4+ delimiters.
+/// Whether `text[at]` is a UTF-8 continuation byte (`0b10xx_xxxx`).
+#[inline]
+fn is_utf8_continuation(text: &[u8], at: usize) -> bool {
+    at < text.len() && (text[at] & 0xC0) == 0x80
+}
+
+/// Move a split point in `text[pos..]` onto a UTF-8 code point boundary.
+///
+/// Backs up to the start of the code point `split` lands in. If that would
+/// leave the chunk empty (the target size is smaller than the code point),
+/// moves forward past the code point instead.
+#[inline]
+pub fn utf8_split_boundary(text: &[u8], pos: usize, split: usize) -> usize {
+    let at = utf8_prev_boundary(text, pos, split);
+    if at > pos {
+        return at;
+    }
+
+    let mut at = split;
+    while is_utf8_continuation(text, at) {
+        at += 1;
+    }
+    at
+}
+
+/// Last UTF-8 code point boundary at or before `at`, but not before `limit`.
+#[inline]
+pub fn utf8_prev_boundary(text: &[u8], limit: usize, mut at: usize) -> usize {
+    while at > limit && is_utf8_continuation(text, at) {
+        at -= 1;
+    }
+    at
+}
+
+/// First UTF-8 code point boundary at or after `at`, capped at `limit`.
+#[inline]
+pub fn utf8_next_boundary(text: &[u8], mut at: usize, limit: usize) -> usize {
+    while at < limit && is_utf8_continuation(text, at) {
+        at += 1;
+    }
+    at
+}
+
+/// Build lookup table for 7+ delimiters.
 #[inline]
 pub fn build_table(delimiters: &[u8]) -> Option<[bool; 256]> {
-    if delimiters.len() > 3 {
+    if delimiters.len() > MAX_MEMCHR_DELIMITERS {
         let mut t = [false; 256];
         for &b in delimiters {
             t[b as usize] = true;
@@ -328,6 +423,7 @@ pub fn find_pattern_boundary(
 // =============================================================================
 
 /// Threshold: use memmem for 1-3 patterns, Aho-Corasick for 4+.
+#[cfg(feature = "std")]
 const MEMMEM_PATTERN_THRESHOLD: usize = 3;
 
 /// Pre-compiled multi-pattern searcher.
@@ -335,10 +431,13 @@ const MEMMEM_PATTERN_THRESHOLD: usize = 3;
 /// Automatically selects the optimal strategy based on pattern count:
 /// - 1-3 patterns: parallel memmem searches (SIMD-accelerated)
 /// - 4+ patterns: Aho-Corasick automaton (single pass, constant in pattern count)
+///
+/// Without the `std` feature, memmem is used for any number of patterns.
 pub enum MultiPatternSearcher {
     /// SIMD memmem for small pattern sets. Stores (forward_finder_needle, reverse_finder_needle) as bytes.
     Memmem { patterns: Vec<Vec<u8>> },
     /// Aho-Corasick for large pattern sets.
+    #[cfg(feature = "std")]
     AhoCorasick {
         daac: DoubleArrayAhoCorasick,
         pattern_lens: Vec<usize>,
@@ -348,19 +447,19 @@ pub enum MultiPatternSearcher {
 impl MultiPatternSearcher {
     /// Build a searcher from pattern byte slices.
     pub fn new(patterns: &[&[u8]]) -> Self {
-        if patterns.len() <= MEMMEM_PATTERN_THRESHOLD {
-            MultiPatternSearcher::Memmem {
-                patterns: patterns.iter().map(|p| p.to_vec()).collect(),
-            }
-        } else {
+        #[cfg(feature = "std")]
+        if patterns.len() > MEMMEM_PATTERN_THRESHOLD {
             let mut trie = Trie::new();
             for (i, &pat) in patterns.iter().enumerate() {
                 trie.add(pat, i as u32);
             }
-            trie.build(MatchKind::LeftmostFirst);
+            trie.build(MatchKind::LeftmostLongest);
             let daac = trie.compile();
             let pattern_lens = patterns.iter().map(|p| p.len()).collect();
-            MultiPatternSearcher::AhoCorasick { daac, pattern_lens }
+            return MultiPatternSearcher::AhoCorasick { daac, pattern_lens };
+        }
+        MultiPatternSearcher::Memmem {
+            patterns: patterns.iter().map(|p| p.to_vec()).collect(),
         }
     }
 
@@ -370,8 +469,27 @@ impl MultiPatternSearcher {
         Self::new(&byte_patterns)
     }
 
+    /// Build a searcher from anything byte-like, skipping empty patterns.
+    ///
+    /// Returns `None` when no non-empty patterns remain.
+    pub fn from_patterns<P: AsRef<[u8]>>(patterns: &[P]) -> Option<Self> {
+        let byte_patterns: Vec<&[u8]> = patterns
+            .iter()
+            .map(|p| p.as_ref())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if byte_patterns.is_empty() {
+            None
+        } else {
+            Some(Self::new(&byte_patterns))
+        }
+    }
+
     /// Find the **last** (rightmost) pattern match in `window`.
     /// Returns `(position, pattern_length)` relative to window start.
+    ///
+    /// A match nested inside a longer pattern match (e.g. `"\n"` inside
+    /// `"\n\n"`) yields to the longer one.
     pub fn find_last(&self, window: &[u8]) -> Option<(usize, usize)> {
         match self {
             MultiPatternSearcher::Memmem { patterns } => {
@@ -381,13 +499,31 @@ impl MultiPatternSearcher {
                     if let Some(pos) = finder.rfind(window) {
                         match best {
                             None => best = Some((pos, pat.len())),
-                            Some((best_pos, _)) if pos > best_pos => best = Some((pos, pat.len())),
+                            Some((best_pos, best_len))
+                                if pos > best_pos || (pos == best_pos && pat.len() > best_len) =>
+                            {
+                                best = Some((pos, pat.len()))
+                            }
                             _ => {}
                         }
                     }
                 }
-                best
+
+                // Longest match: prefer a longer pattern that covers the best match
+                let (pos, len) = best?;
+                let mut longest = (pos, len);
+                for pat in patterns {
+                    if pat.len() <= longest.1 {
+                        continue;
+                    }
+                    let lo = (pos + len).saturating_sub(pat.len());
+                    if let Some(q) = (lo..=pos).find(|&q| window[q..].starts_with(pat)) {
+                        longest = (q, pat.len());
+                    }
+                }
+                Some(longest)
             }
+            #[cfg(feature = "std")]
             MultiPatternSearcher::AhoCorasick { daac, pattern_lens } => {
                 let mut last: Option<(usize, usize)> = None;
                 for m in daac.find_iter(window) {
@@ -409,19 +545,106 @@ impl MultiPatternSearcher {
                     if let Some(pos) = finder.find(window) {
                         match best {
                             None => best = Some((pos, pat.len())),
-                            Some((best_pos, _)) if pos < best_pos => best = Some((pos, pat.len())),
+                            Some((best_pos, best_len))
+                                if pos < best_pos || (pos == best_pos && pat.len() > best_len) =>
+                            {
+                                best = Some((pos, pat.len()))
+                            }
                             _ => {}
                         }
                     }
                 }
                 best
             }
+            #[cfg(feature = "std")]
             MultiPatternSearcher::AhoCorasick { daac, pattern_lens } => daac
                 .find_iter(window)
                 .next()
                 .map(|m| (m.start, pattern_lens[m.pattern_id as usize])),
         }
     }
+
+    /// Length of the longest pattern match ending exactly at the end of `text`.
+    pub(crate) fn match_len_at_end(&self, text: &[u8]) -> Option<usize> {
+        match self {
+            MultiPatternSearcher::Memmem { patterns } => patterns
+                .iter()
+                .filter(|p| text.ends_with(p))
+                .map(|p| p.len())
+                .max(),
+            #[cfg(feature = "std")]
+            MultiPatternSearcher::AhoCorasick { daac, pattern_lens } => {
+                let longest = pattern_lens.iter().copied().max().unwrap_or(0);
+                // A match from `s` to the end is the longest possible there
+                (text.len().saturating_sub(longest)..text.len())
+                    .find(|&s| {
+                        daac.find_iter(&text[s..])
+                            .next()
+                            .is_some_and(|m| m.start == 0 && s + m.end == text.len())
+                    })
+                    .map(|s| text.len() - s)
+            }
+        }
+    }
+
+    /// Whether a pattern match starts at the start of `text`.
+    pub(crate) fn starts_with_match(&self, text: &[u8]) -> bool {
+        match self {
+            MultiPatternSearcher::Memmem { patterns } => {
+                patterns.iter().any(|p| text.starts_with(p))
+            }
+            #[cfg(feature = "std")]
+            MultiPatternSearcher::AhoCorasick { daac, pattern_lens } => {
+                let longest = pattern_lens.iter().copied().max().unwrap_or(0);
+                daac.find_iter(&text[..longest.min(text.len())])
+                    .next()
+                    .is_some_and(|m| m.start == 0)
+            }
+        }
+    }
+}
+
+/// The split rules of a chunker, for telling delimiter splits from forced ones.
+pub(crate) struct Boundaries<'a> {
+    pub(crate) pattern: Option<&'a [u8]>,
+    pub(crate) delimiters: &'a [u8],
+    pub(crate) multi_searcher: Option<&'a MultiPatternSearcher>,
+    pub(crate) prefix: bool,
+}
+
+impl Boundaries<'_> {
+    /// Whether a split at `at` sits next to a delimiter or pattern: after it
+    /// normally, before it in prefix mode.
+    pub(crate) fn is_delimited(&self, text: &[u8], at: usize) -> bool {
+        let (before, after) = text.split_at(at);
+        if !self.prefix {
+            return self.delimiter_len_before(before).is_some();
+        }
+        match self.pattern {
+            Some(pattern) => after.starts_with(pattern),
+            None => {
+                after.first().is_some_and(|b| self.delimiters.contains(b))
+                    || self
+                        .multi_searcher
+                        .is_some_and(|searcher| searcher.starts_with_match(after))
+            }
+        }
+    }
+
+    /// Length of the delimiter or pattern `text` ends with, if any.
+    pub(crate) fn delimiter_len_before(&self, text: &[u8]) -> Option<usize> {
+        if let Some(pattern) = self.pattern {
+            return text.ends_with(pattern).then_some(pattern.len());
+        }
+        let pattern_len = self
+            .multi_searcher
+            .and_then(|searcher| searcher.match_len_at_end(text));
+        let byte_len = text
+            .last()
+            .is_some_and(|b| self.delimiters.contains(b))
+            .then_some(1);
+        pattern_len.max(byte_len)
+    }
 }
 
 /// Compute split position combining single-byte delimiters AND multi-byte patterns.
@@ -471,7 +694,8 @@ pub fn compute_split_at_combined(
                     best_pos = Some(abs_pos);
                     best_pat_len = pat_len;
                 }
-                Some(bp) if abs_pos > bp => {
+                // Later match wins, or a longer pattern covering the delimiter
+                Some(bp) if abs_pos > bp || bp < abs_pos + pat_len => {
                     best_pos = Some(abs_pos);
                     best_pat_len = pat_len;
                 }
@@ -519,7 +743,8 @@ pub fn compute_split_at_combined(
                         first_pos = Some(abs_pos);
                         first_pat_len = pat_len;
                     }
-                    Some(fp) if abs_pos < fp => {
+                    // Earlier match wins; on a tie the pattern is at least as long
+                    Some(fp) if abs_pos <= fp => {
                         first_pos = Some(abs_pos);
                         first_pat_len = pat_len;
                     }
@@ -607,3 +832,116 @@ pub fn compute_split_at(
         }
     }
 }
+
+/// Split for the window `pos..end` at the highest-priority delimiter in it.
+///
+/// `delimiters` are tried one at a time in order, and the rightmost match of
+/// the first one found in the window wins. Returns `None` when the window
+/// holds none of them.
+pub(crate) fn find_priority_split(
+    text: &[u8],
+    pos: usize,
+    end: usize,
+    delimiters: &[u8],
+    prefix_mode: bool,
+    consecutive: bool,
+) -> Option<usize> {
+    delimiters.iter().find_map(|delimiter| {
+        find_delimiter_boundary(
+            text,
+            core::slice::from_ref(delimiter),
+            None,
+            pos,
+            end,
+            consecutive,
+            false,
+        )
+        .map(|found_pos| {
+            if prefix_mode {
+                found_pos
+            } else {
+                found_pos + 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delim_memchr_matches_table() {
+        let text = b"ab.cd?ef!gh;ij:kl,mn\nop qr";
+        let all = b".?!;:,\n ";
+        for n in 1..=all.len() {
+            let delimiters = &all[..n];
+            let mut t = [false; 256];
+            delimiters.iter().for_each(|&b| t[b as usize] = true);
+            let table = build_table(delimiters);
+            for start in 0..text.len() {
+                for end in start..=text.len() {
+                    let window = &text[start..end];
+                    assert_eq!(
+                        find_last_delimiter(window, delimiters, table.as_ref()),
+                        window.iter().rposition(|&b| t[b as usize]),
+                        "last, {n} delimiters, {start}..{end}"
+                    );
+                    assert_eq!(
+                        find_first_delimiter(window, delimiters, table.as_ref()),
+                        window.iter().position(|&b| t[b as usize]),
+                        "first, {n} delimiters, {start}..{end}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_delim_two_pass_across_blocks() {
+        let mut text = vec![b'a'; TWO_PASS_BLOCK * 3];
+        text[TWO_PASS_BLOCK + 7] = b';';
+        text[TWO_PASS_BLOCK * 2 + 3] = b'.';
+        let delimiters = b".?!;";
+        assert_eq!(
+            find_first_delimiter(&text, delimiters, None),
+            Some(TWO_PASS_BLOCK + 7)
+        );
+        assert_eq!(
+            find_last_delimiter(&text, delimiters, None),
+            Some(TWO_PASS_BLOCK * 2 + 3)
+        );
+        assert_eq!(
+            find_last_delimiter(&text[..TWO_PASS_BLOCK * 2], delimiters, None),
+            Some(TWO_PASS_BLOCK + 7)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_delim_memmem_matches_aho_corasick() {
+        // Without std, memmem handles any number of patterns; it must agree
+        let patterns: [&[u8]; 5] = [b"\n\n", b"\n", b". ", b"? ", b"."];
+        let daac = MultiPatternSearcher::new(&patterns);
+        assert!(matches!(daac, MultiPatternSearcher::AhoCorasick { .. }));
+        let memmem = MultiPatternSearcher::Memmem {
+            patterns: patterns.iter().map(|p| p.to_vec()).collect(),
+        };
+        let text = b"One. Two? Three.\n\nFour\nFive. ";
+        for start in 0..text.len() {
+            for end in start..=text.len() {
+                let window = &text[start..end];
+                assert_eq!(memmem.find_last(window), daac.find_last(window));
+                assert_eq!(memmem.find_first(window), daac.find_first(window));
+                assert_eq!(
+                    memmem.match_len_at_end(window),
+                    daac.match_len_at_end(window)
+                );
+                assert_eq!(
+                    memmem.starts_with_match(window),
+                    daac.starts_with_match(window)
+                );
+            }
+        }
+    }
+}
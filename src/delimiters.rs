@@ -0,0 +1,218 @@
+//! Named delimiter classes.
+//!
+//! This module provides [`Delimiters`], ready-made delimiter sets for common
+//! kinds of text that can also be picked by name (e.g. `"sentences"`), so
+//! the bindings and CLI take them as strings, and [`UnknownDelimiters`], the
+//! error for a name that isn't one.
+
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+
+/// Full-width punctuation ending CJK sentences and clauses.
+const CJK_PATTERNS: &[&str] = &["。", "！", "？", "、"];
+
+/// A named set of delimiters and patterns, for
+/// [`Chunker::delimiter_class`](crate::Chunker::delimiter_class).
+///
+/// ```
+/// use chunk::{Delimiters, chunk};
+/// let text = b"Split these words";
+/// let chunks: Vec<_> = chunk(text).size(10).delimiter_class(Delimiters::WHITESPACE).collect();
+/// assert_eq!(chunks, vec![&b"Split "[..], b"these ", b"words"]);
+///
+/// let class: Delimiters = "markdown_headings".parse().unwrap();
+/// assert_eq!(class, Delimiters::MARKDOWN_HEADINGS);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Delimiters {
+    name: &'static str,
+    bytes: &'static [u8],
+    patterns: &'static [&'static str],
+    prefix: bool,
+}
+
+impl Delimiters {
+    /// Sentence ends and line breaks: `.`, `?`, `!` and `\n`.
+    pub const SENTENCES: Self = Self {
+        name: "sentences",
+        bytes: b".?!\n",
+        patterns: &[],
+        prefix: false,
+    };
+
+    /// Spaces, tabs and line breaks, so chunks end between words.
+    pub const WHITESPACE: Self = Self {
+        name: "whitespace",
+        bytes: b" \t\r\n",
+        patterns: &[],
+        prefix: false,
+    };
+
+    /// Line breaks.
+    pub const NEWLINES: Self = Self {
+        name: "newlines",
+        bytes: b"\n",
+        patterns: &[],
+        prefix: false,
+    };
+
+    /// Markdown headings: chunks start at a line beginning with `#`, which is
+    /// kept with the section it heads.
+    pub const MARKDOWN_HEADINGS: Self = Self {
+        name: "markdown_headings",
+        bytes: b"",
+        patterns: &["\n#"],
+        prefix: true,
+    };
+
+    /// Chinese and Japanese sentence ends and line breaks: `。`, `！`, `？`,
+    /// the enumeration comma `、` and `\n`.
+    pub const CJK: Self = Self {
+        name: "cjk",
+        bytes: b"\n",
+        patterns: CJK_PATTERNS,
+        prefix: false,
+    };
+
+    /// [`SENTENCES`](Self::SENTENCES) and [`CJK`](Self::CJK) together, for
+    /// text that mixes scripts.
+    pub const MULTILINGUAL: Self = Self {
+        name: "multilingual",
+        bytes: b".?!\n",
+        patterns: CJK_PATTERNS,
+        prefix: false,
+    };
+
+    /// Every class, in the order above.
+    pub const ALL: &'static [Self] = &[
+        Self::SENTENCES,
+        Self::WHITESPACE,
+        Self::NEWLINES,
+        Self::MARKDOWN_HEADINGS,
+        Self::CJK,
+        Self::MULTILINGUAL,
+    ];
+
+    /// The name the class is parsed from, e.g. `"sentences"`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Single-byte delimiters.
+    pub fn bytes(&self) -> &'static [u8] {
+        self.bytes
+    }
+
+    /// Multi-byte patterns, searched alongside [`bytes`](Self::bytes).
+    pub fn patterns(&self) -> &'static [&'static str] {
+        self.patterns
+    }
+
+    /// Whether the delimiter starts the next chunk rather than ending this
+    /// one.
+    pub fn prefix(&self) -> bool {
+        self.prefix
+    }
+}
+
+impl fmt::Display for Delimiters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+/// Error parsing a [`Delimiters`] class from a name that isn't one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownDelimiters(pub String);
+
+impl fmt::Display for UnknownDelimiters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown delimiter class: {:?} (expected ", self.0)?;
+        for (i, class) in Delimiters::ALL.iter().enumerate() {
+            let sep = if i == 0 { "" } else { ", " };
+            write!(f, "{sep}{class}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl core::error::Error for UnknownDelimiters {}
+
+impl FromStr for Delimiters {
+    type Err = UnknownDelimiters;
+
+    /// Parse a class name (case-insensitive, `-` for `_` allowed), e.g.
+    /// `"sentences"` or `"markdown-headings"`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let normalized = name.to_ascii_lowercase().replace('-', "_");
+        Delimiters::ALL
+            .iter()
+            .find(|class| class.name == normalized)
+            .copied()
+            .ok_or_else(|| UnknownDelimiters(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::chunk;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_parse_delimiters() {
+        for class in Delimiters::ALL {
+            assert_eq!(class.name().parse(), Ok(*class));
+        }
+        assert_eq!(
+            "Markdown-Headings".parse(),
+            Ok(Delimiters::MARKDOWN_HEADINGS)
+        );
+        let err = "words".parse::<Delimiters>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown delimiter class: \"words\" (expected sentences, whitespace, newlines, markdown_headings, cjk, multilingual)"
+        );
+    }
+
+    #[test]
+    fn test_markdown_headings() {
+        let text = b"# One\nIntro.\n## Two\nMore text.\n# Three\n";
+        let chunks: Vec<_> = chunk(text)
+            .size(20)
+            .delimiter_class(Delimiters::MARKDOWN_HEADINGS)
+            .collect();
+        assert_eq!(
+            chunks,
+            vec![
+                &b"# One\nIntro."[..],
+                b"\n## Two\nMore text.",
+                b"\n# Three\n"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cjk() {
+        let text = "今日は晴れ。明日は雨！本当？";
+        let chunks: Vec<_> = chunk(text.as_bytes())
+            .size(20)
+            .delimiter_class(Delimiters::CJK)
+            .collect();
+        let expected = ["今日は晴れ。", "明日は雨！", "本当？"];
+        assert_eq!(chunks, expected.map(str::as_bytes));
+
+        // The default delimiters find nothing to split at
+        let chunks: Vec<_> = chunk(text.as_bytes()).size(20).collect();
+        assert_eq!(chunks.len(), 3);
+        assert_ne!(chunks[0], "今日は晴れ。".as_bytes());
+
+        let text = "Hi. 你好。Ok";
+        let chunks: Vec<_> = chunk(text.as_bytes())
+            .size(10)
+            .delimiter_class(Delimiters::MULTILINGUAL)
+            .collect();
+        assert_eq!(chunks, ["Hi.", " 你好。", "Ok"].map(str::as_bytes));
+    }
+}
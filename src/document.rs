@@ -0,0 +1,524 @@
+//! HTML documents as sections with headings.
+//!
+//! This module provides [`Document`], the readable text of an HTML page (or,
+//! with the `epub` feature, an EPUB book) split into sections at its
+//! headings. Scripts, styles, navigation and the other
+//! [`BOILERPLATE_ELEMENTS`] are left out, and [`Document::chunks`] chunks
+//! each section on its own, so every chunk knows the headings it sits under.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::config::ChunkerConfig;
+use crate::html::{DEFAULT_BLOCK_ELEMENTS, find_close, is_tag_start, is_void, tag_end, tag_name};
+use crate::tree::ChunkTree;
+
+/// Elements whose content is left out of a [`Document`].
+pub const BOILERPLATE_ELEMENTS: &[&str] = &[
+    "aside", "button", "footer", "head", "iframe", "nav", "noscript", "script", "style", "svg",
+    "template",
+];
+
+/// A document's readable text, split into sections at its headings.
+///
+/// Each section starts with its heading (if any) and holds the text up to
+/// the next heading. A heading with no text of its own before a deeper
+/// heading stays with that heading's section, like in
+/// [`MarkdownChunker`](crate::MarkdownChunker).
+///
+/// Whitespace is collapsed outside `<pre>`; block elements end paragraphs
+/// with a blank line, and `<br>` and list items start new lines.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{ChunkerConfig, Document};
+///
+/// let html = b"<title>Guide</title><nav>Home | About</nav>
+///     <h1>Guide</h1><h2>Install</h2><p>Run the installer.</p>
+///     <h2>Usage</h2><p>Call <code>chunk</code>.</p>";
+/// let doc = Document::from_html(html);
+/// assert_eq!(doc.title.as_deref(), Some("Guide"));
+/// assert_eq!(doc.sections[0].heading_path, "Guide > Install");
+/// assert_eq!(doc.sections[0].text, "Guide\n\nInstall\n\nRun the installer.");
+///
+/// let chunks = doc.chunks(&ChunkerConfig::new().size(64));
+/// assert_eq!(chunks[1].heading_path, "Guide > Usage");
+/// assert_eq!(chunks[1].text, b"Usage\n\nCall chunk.");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Document {
+    /// The document title, from `<title>` (or the EPUB metadata).
+    pub title: Option<String>,
+    /// Sections in document order.
+    pub sections: Vec<Section>,
+}
+
+/// A run of a [`Document`] under the same headings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Section {
+    /// Enclosing headings joined with `" > "`, e.g. `"Guide > Install"`.
+    ///
+    /// Empty for text before the first heading.
+    pub heading_path: String,
+    /// The section text, starting with its heading.
+    pub text: String,
+}
+
+/// A chunk of one [`Section`] of a [`Document`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SectionChunk<'d> {
+    /// Index of the section in [`Document::sections`].
+    pub section: usize,
+    /// Byte offset in the section text where the chunk starts.
+    pub start: usize,
+    /// Byte offset in the section text where the chunk ends (exclusive).
+    pub end: usize,
+    /// The chunk text, borrowed from the section.
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::serde_bytes::serialize")
+    )]
+    pub text: &'d [u8],
+    /// The section's heading path.
+    pub heading_path: &'d str,
+}
+
+impl Document {
+    /// Read the text of an HTML page.
+    ///
+    /// Invalid UTF-8 is replaced with U+FFFD.
+    pub fn from_html(html: &[u8]) -> Self {
+        let mut extractor = Extractor::default();
+        extractor.feed(html);
+        extractor.finish()
+    }
+
+    /// Chunk every section with `config`.
+    ///
+    /// Chunks never span sections. Enable
+    /// [`utf8_safe`](ChunkerConfig::utf8_safe) to keep them valid UTF-8.
+    pub fn chunks<'d>(&'d self, config: &ChunkerConfig) -> Vec<SectionChunk<'d>> {
+        let mut chunks = Vec::new();
+        for (index, section) in self.sections.iter().enumerate() {
+            let text = section.text.as_bytes();
+            for (start, end) in config.chunker(text).collect_offsets() {
+                chunks.push(SectionChunk {
+                    section: index,
+                    start,
+                    end,
+                    text: &text[start..end],
+                    heading_path: &section.heading_path,
+                });
+            }
+        }
+        chunks
+    }
+
+    /// Chunk every section with `config`, as a tree of sections with the
+    /// sections of their subheadings as children.
+    ///
+    /// The tree is rebuilt from the heading paths, so levels count depth
+    /// (the heading elements' own levels aren't kept), consecutive sections
+    /// with the same path share a node, and a heading containing `" > "`
+    /// reads as two.
+    ///
+    /// ```
+    /// use chunk::{ChunkerConfig, Document};
+    ///
+    /// let html = b"<h1>Guide</h1><p>Intro.</p><h3>Install</h3><p>Run it.</p>";
+    /// let doc = Document::from_html(html);
+    /// let tree = doc.chunk_tree(&ChunkerConfig::new());
+    /// let guide = &tree.children[0];
+    /// assert_eq!(guide.chunks[0].text, b"Guide\n\nIntro.");
+    /// assert_eq!((guide.children[0].heading.as_str(), guide.children[0].level), ("Install", 2));
+    /// ```
+    pub fn chunk_tree<'d>(&'d self, config: &ChunkerConfig) -> ChunkTree<SectionChunk<'d>> {
+        let mut tree = ChunkTree::default();
+        for chunk in self.chunks(config) {
+            let headings = chunk
+                .heading_path
+                .split(" > ")
+                .filter(|heading| !heading.is_empty())
+                .enumerate()
+                .map(|(depth, heading)| (depth + 1, heading));
+            tree.insert(headings, usize::MAX, chunk);
+        }
+        tree
+    }
+}
+
+/// Whitespace owed before the next text, from weakest to strongest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+enum Break {
+    #[default]
+    None,
+    Space,
+    Line,
+    Paragraph,
+}
+
+/// Builds a [`Document`] from one or more HTML files.
+#[derive(Debug, Default)]
+pub(crate) struct Extractor {
+    title: Option<String>,
+    sections: Vec<Section>,
+    /// Text of the section being read.
+    text: String,
+    /// Whether the section has text besides its headings.
+    body: bool,
+    /// Headings above the current text, with their levels.
+    headings: Vec<(u8, String)>,
+    /// Level and text of the heading element being read.
+    heading: Option<(u8, String)>,
+    pending: Break,
+    /// Open boilerplate elements.
+    skip: usize,
+    /// Open `<pre>` elements.
+    pre: usize,
+}
+
+impl Extractor {
+    /// Read the text of an HTML file, continuing the current section.
+    pub(crate) fn feed(&mut self, html: &[u8]) {
+        let mut i = 0;
+        while i < html.len() {
+            let lt = memchr::memchr(b'<', &html[i..]).map_or(html.len(), |n| i + n);
+            if lt == html.len() || !is_tag_start(html.get(lt + 1).copied()) {
+                let text_end = (lt + 1).min(html.len());
+                self.push_raw(&html[i..text_end]);
+                i = text_end;
+                continue;
+            }
+            self.push_raw(&html[i..lt]);
+            let end = tag_end(html, lt);
+            i = end;
+            let tag = &html[lt..end];
+            if !tag[1].is_ascii_alphabetic() && tag[1] != b'/' {
+                // Comment, doctype or processing instruction
+                continue;
+            }
+            let (closing, name) = tag_name(tag);
+            let name = core::str::from_utf8(name)
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let self_closing = is_void(name.as_bytes()) || tag.ends_with(b"/>");
+
+            if !closing && matches!(name.as_str(), "script" | "style" | "title") {
+                // Raw text that may contain `<`
+                let close = find_close(html, i, name.as_bytes()).unwrap_or(html.len());
+                if name == "title" && self.title.is_none() {
+                    let title = collapse(&decode(&html[i..close]));
+                    self.title = Some(title).filter(|t| !t.is_empty());
+                }
+                i = close;
+            }
+            if name == "body" {
+                // `</head>` is optional
+                self.skip = 0;
+            }
+            if BOILERPLATE_ELEMENTS.contains(&name.as_str()) {
+                if closing {
+                    self.skip = self.skip.saturating_sub(1);
+                } else if !self_closing {
+                    self.skip += 1;
+                }
+                continue;
+            }
+            if self.skip > 0 {
+                continue;
+            }
+            if let Some(level) = heading_level(&name) {
+                if closing {
+                    self.close_heading();
+                } else if self.heading.is_none() {
+                    self.open_heading(level);
+                }
+                continue;
+            }
+            let brk = match name.as_str() {
+                "pre" => {
+                    if closing {
+                        self.pre = self.pre.saturating_sub(1);
+                    } else {
+                        self.pre += 1;
+                    }
+                    Break::Paragraph
+                }
+                "br" | "dd" | "dt" | "li" | "tr" => Break::Line,
+                "td" | "th" => Break::Space,
+                _ if DEFAULT_BLOCK_ELEMENTS.contains(&name.as_str()) => Break::Paragraph,
+                _ => Break::None,
+            };
+            self.pending = self.pending.max(brk);
+        }
+    }
+
+    /// End the current section, so the next text starts a new one (under
+    /// the same headings).
+    pub(crate) fn break_section(&mut self) {
+        self.close_heading();
+        self.flush(true);
+        self.skip = 0;
+        self.pre = 0;
+    }
+
+    pub(crate) fn finish(mut self) -> Document {
+        self.break_section();
+        Document {
+            title: self.title,
+            sections: self.sections,
+        }
+    }
+
+    /// Push the current section if it has any text, or only if it has text
+    /// besides its headings unless `force`.
+    fn flush(&mut self, force: bool) {
+        if self.body || (force && !self.text.is_empty()) {
+            let mut text = core::mem::take(&mut self.text);
+            text.truncate(text.trim_end().len());
+            let heading_path = self
+                .headings
+                .iter()
+                .map(|(_, heading)| heading.as_str())
+                .collect::<Vec<_>>()
+                .join(" > ");
+            self.sections.push(Section { heading_path, text });
+            self.body = false;
+        }
+        self.pending = Break::None;
+    }
+
+    fn open_heading(&mut self, level: u8) {
+        // A bare heading stays with a deeper one, but not with a sibling
+        let sibling = self.headings.last().is_some_and(|&(last, _)| last >= level);
+        self.flush(sibling);
+        self.pending = Break::Paragraph;
+        self.heading = Some((level, String::new()));
+    }
+
+    fn close_heading(&mut self) {
+        if let Some((level, text)) = self.heading.take() {
+            if !text.is_empty() {
+                self.headings.retain(|&(open, _)| open < level);
+                self.headings.push((level, text));
+            }
+            self.pending = Break::Paragraph;
+        }
+    }
+
+    /// Push text from between tags.
+    fn push_raw(&mut self, raw: &[u8]) {
+        if raw.is_empty() || self.skip > 0 {
+            return;
+        }
+        let text = decode(raw);
+        if self.pre > 0 {
+            self.push(&text);
+            return;
+        }
+        if text.starts_with(char::is_whitespace) {
+            self.pending = self.pending.max(Break::Space);
+        }
+        for (n, word) in text.split_whitespace().enumerate() {
+            if n > 0 {
+                self.pending = self.pending.max(Break::Space);
+            }
+            self.push(word);
+        }
+        if text.ends_with(char::is_whitespace) {
+            self.pending = self.pending.max(Break::Space);
+        }
+    }
+
+    fn push(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if !self.text.is_empty() {
+            self.text.push_str(match self.pending {
+                Break::None => "",
+                Break::Space => " ",
+                Break::Line => "\n",
+                Break::Paragraph => "\n\n",
+            });
+        }
+        match &mut self.heading {
+            Some((_, heading)) => {
+                if !heading.is_empty() && self.pending != Break::None {
+                    heading.push(' ');
+                }
+                heading.push_str(text);
+            }
+            None => self.body = true,
+        }
+        self.text.push_str(text);
+        self.pending = Break::None;
+    }
+}
+
+/// Level of a heading element `h1` to `h6`.
+fn heading_level(name: &str) -> Option<u8> {
+    match name.as_bytes() {
+        [b'h', level @ b'1'..=b'6'] => Some(level - b'0'),
+        _ => None,
+    }
+}
+
+/// Text with whitespace runs collapsed to single spaces and trimmed.
+pub(crate) fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Text between tags with character references decoded.
+pub(crate) fn decode(raw: &[u8]) -> Cow<'_, str> {
+    let text = String::from_utf8_lossy(raw);
+    if !text.contains('&') {
+        return text;
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = &*text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp + 1..];
+        let reference = rest
+            .find(';')
+            .filter(|&n| n <= 10)
+            .and_then(|n| Some((entity(&rest[..n])?, n + 1)));
+        match reference {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => out.push('&'),
+        }
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// The character of a numeric or common named reference (without `&;`).
+fn entity(name: &str) -> Option<char> {
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "copy" => '©',
+        "ndash" => '\u{2013}',
+        "mdash" => '\u{2014}',
+        "lsquo" => '\u{2018}',
+        "rsquo" => '\u{2019}',
+        "ldquo" => '\u{201c}',
+        "rdquo" => '\u{201d}',
+        "hellip" => '\u{2026}',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_from_html() {
+        let html = b"<!DOCTYPE html><html><head><title>My  Page</title>
+            <style>p { color: red }</style></head>
+            <body><header><nav><a href=\"/\">Home</a></nav></header>
+            <p>Intro &amp; <i>overview</i>.</p>
+            <h1>Main</h1>
+            <h2>Part <em>one</em></h2>
+            <ul><li>A</li><li>B</li></ul>
+            <pre>let x =  1;\n  x</pre>
+            <script>if (a < b) { document.write('<h1>no</h1>') }</script>
+            <h2>Part two</h2><p>Line<br>break</p><!-- <h3>comment</h3> -->
+            <footer>&copy; 2024</footer></body></html>";
+        let doc = Document::from_html(html);
+        assert_eq!(doc.title.as_deref(), Some("My Page"));
+        let sections: Vec<_> = doc
+            .sections
+            .iter()
+            .map(|s| (s.heading_path.as_str(), s.text.as_str()))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("", "Intro & overview."),
+                (
+                    "Main > Part one",
+                    "Main\n\nPart one\n\nA\nB\n\nlet x =  1;\n  x"
+                ),
+                ("Main > Part two", "Part two\n\nLine\nbreak"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_document_bare_headings() {
+        // A bare heading followed by a sibling keeps its own section
+        let doc = Document::from_html(b"<h2>A</h2><h2>B</h2><p>b</p><h1>C</h1><p>c</p>");
+        let paths: Vec<_> = doc.sections.iter().map(|s| &s.heading_path).collect();
+        assert_eq!(paths, ["A", "B", "C"]);
+        assert_eq!(doc.sections[0].text, "A");
+        // `</head>` may be left out
+        let doc = Document::from_html(b"<head><title>T</title><body><p>Text</p>");
+        assert_eq!(doc.sections[0].text, "Text");
+        assert_eq!(decode(b"&lt;&#65;&#x42;&bogus; & &;"), "<AB&bogus; & &;");
+    }
+
+    #[test]
+    fn test_document_chunks() {
+        let doc =
+            Document::from_html(b"<h1>One</h1><p>Aaa bbb ccc ddd.</p><h1>Two</h1><p>Eee fff.</p>");
+        let chunks = doc.chunks(&ChunkerConfig::new().size(12).delimiters(b" "));
+        for chunk in &chunks {
+            let section = &doc.sections[chunk.section];
+            assert_eq!(chunk.text, &section.text.as_bytes()[chunk.start..chunk.end]);
+            assert_eq!(chunk.heading_path, section.heading_path);
+        }
+        let texts: Vec<_> = chunks.iter().map(|c| (c.section, c.text)).collect();
+        assert_eq!(
+            texts,
+            vec![
+                (0, &b"One\n\nAaa "[..]),
+                (0, b"bbb ccc ddd."),
+                (1, b"Two\n\nEee "),
+                (1, b"fff."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_document_chunk_tree() {
+        let doc = Document::from_html(
+            b"<p>Pre.</p><h1>A</h1><h2>B</h2><p>b</p><h2>C</h2><p>c</p><h1>D</h1><p>d</p>",
+        );
+        let config = ChunkerConfig::new();
+        let tree = doc.chunk_tree(&config);
+        assert_eq!(tree.chunks[0].text, b"Pre.");
+        let outline: Vec<_> = tree
+            .children
+            .iter()
+            .map(|s| {
+                let children: Vec<_> = s.children.iter().map(|c| c.heading.as_str()).collect();
+                (s.heading.as_str(), s.chunks.len(), children)
+            })
+            .collect();
+        assert_eq!(outline, vec![("A", 0, vec!["B", "C"]), ("D", 1, vec![])]);
+        let flat: Vec<_> = tree.flatten().into_iter().cloned().collect();
+        assert_eq!(flat, doc.chunks(&config));
+    }
+}
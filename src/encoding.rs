@@ -0,0 +1,236 @@
+//! Decoding non-UTF-8 input.
+//!
+//! This module provides [`decode_to_utf8`], which detects the encoding of a
+//! file (a byte order mark, valid UTF-8, or else a guess among the legacy
+//! encodings of the web) and decodes it to UTF-8 for chunking, reporting what
+//! it found. Decoding is done by `encoding_rs` and guessing by `chardetng`,
+//! so every encoding and label of the WHATWG Encoding Standard is supported.
+//! Offsets of chunks of the decoded text are into the decoded text, not the
+//! input.
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+
+/// A text encoding [`decode_to_utf8`] can detect and decode: any encoding of
+/// the WHATWG Encoding Standard, e.g. Windows-1252, KOI8-R or Shift_JIS.
+///
+/// Parse one from a label with [`str::parse`]; the common ones are also
+/// constants.
+///
+/// ```
+/// use chunk::Encoding;
+///
+/// let koi8: Encoding = "koi8-r".parse().unwrap();
+/// assert_eq!(koi8.name(), "KOI8-R");
+/// assert_eq!(koi8.decode(b"\xf0\xd2\xc9\xd7\xc5\xd4").text, "Привет");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Encoding(&'static encoding_rs::Encoding);
+
+/// Error returned when parsing an unknown [`Encoding`] label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownEncoding(pub String);
+
+impl fmt::Display for UnknownEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown encoding: {:?}", self.0)
+    }
+}
+
+impl core::error::Error for UnknownEncoding {}
+
+impl FromStr for Encoding {
+    type Err = UnknownEncoding;
+
+    /// Parse a WHATWG encoding label (case-insensitive), e.g. `"utf-8"`,
+    /// `"latin1"`, `"cp1252"` or `"koi8-r"`. Labels of encodings that only
+    /// exist to be refused (`"iso-2022-kr"` and the like) are unknown.
+    fn from_str(label: &str) -> Result<Self, Self::Err> {
+        encoding_rs::Encoding::for_label_no_replacement(label.as_bytes())
+            .map(Encoding)
+            .ok_or_else(|| UnknownEncoding(label.to_string()))
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Encoding {
+    /// UTF-8.
+    pub const UTF_8: Encoding = Encoding(&encoding_rs::UTF_8_INIT);
+    /// UTF-16 little endian.
+    pub const UTF_16LE: Encoding = Encoding(&encoding_rs::UTF_16LE_INIT);
+    /// UTF-16 big endian.
+    pub const UTF_16BE: Encoding = Encoding(&encoding_rs::UTF_16BE_INIT);
+    /// Windows-1252, which is also how Latin-1 (ISO-8859-1) and ASCII
+    /// labelled text is decoded on the web: Latin-1 only differs in
+    /// 0x80..=0x9F, which it leaves to control characters.
+    pub const WINDOWS_1252: Encoding = Encoding(&encoding_rs::WINDOWS_1252_INIT);
+
+    /// The encoding's canonical name, e.g. `"UTF-8"` or `"windows-1252"`.
+    pub fn name(self) -> &'static str {
+        self.0.name()
+    }
+
+    /// The byte order mark of the encoding (empty if it has none).
+    pub fn bom(self) -> &'static [u8] {
+        if self == Encoding::UTF_8 {
+            b"\xef\xbb\xbf"
+        } else if self == Encoding::UTF_16LE {
+            b"\xff\xfe"
+        } else if self == Encoding::UTF_16BE {
+            b"\xfe\xff"
+        } else {
+            b""
+        }
+    }
+
+    /// Guess the encoding of `bytes`.
+    ///
+    /// A byte order mark wins; otherwise valid UTF-8 is UTF-8, and anything
+    /// else is guessed from its byte frequencies among the legacy encodings
+    /// (Windows-1252 when nothing fits better). UTF-16 without a byte order
+    /// mark isn't detected.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if let Some((encoding, _)) = encoding_rs::Encoding::for_bom(bytes) {
+            return Encoding(encoding);
+        }
+        if core::str::from_utf8(bytes).is_ok() {
+            return Encoding::UTF_8;
+        }
+        let mut detector = chardetng::EncodingDetector::new();
+        detector.feed(bytes, true);
+        Encoding(detector.guess(None, false))
+    }
+
+    /// Decode `bytes` as this encoding, skipping its byte order mark.
+    ///
+    /// Malformed input (e.g. invalid UTF-8, unpaired UTF-16 surrogates or a
+    /// byte the encoding leaves unmapped) is replaced with U+FFFD. Valid
+    /// UTF-8, and ASCII in encodings that agree with it, is borrowed.
+    pub fn decode(self, bytes: &[u8]) -> Decoded<'_> {
+        let (text, had_replacements) = self.0.decode_with_bom_removal(bytes);
+        Decoded {
+            text,
+            encoding: self,
+            had_replacements,
+        }
+    }
+}
+
+/// Text decoded to UTF-8 by [`decode_to_utf8`] or [`Encoding::decode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded<'a> {
+    /// The decoded text, borrowed when the input already was UTF-8.
+    pub text: Cow<'a, str>,
+    /// The encoding the input was decoded from.
+    pub encoding: Encoding,
+    /// Whether the decoder replaced malformed input with U+FFFD.
+    pub had_replacements: bool,
+}
+
+/// Detect the encoding of `bytes` and decode them to UTF-8.
+///
+/// See [`Encoding::detect`] for how the encoding is chosen. To chunk, pass
+/// the decoded text's bytes to any chunker.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{Encoding, chunk, decode_to_utf8};
+///
+/// // "café – naïve" in Windows-1252
+/// let decoded = decode_to_utf8(b"caf\xe9 \x96 na\xefve");
+/// assert_eq!(decoded.encoding, Encoding::WINDOWS_1252);
+/// assert_eq!(decoded.text, "café – naïve");
+/// assert!(!decoded.had_replacements);
+///
+/// let chunks: Vec<_> = chunk(decoded.text.as_bytes()).size(8).delimiters(b" ").collect();
+/// assert_eq!(chunks[0], "café ".as_bytes());
+/// ```
+pub fn decode_to_utf8(bytes: &[u8]) -> Decoded<'_> {
+    Encoding::detect(bytes).decode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_decode() {
+        let utf8 = decode_to_utf8("€ price".as_bytes());
+        assert_eq!(utf8.encoding, Encoding::UTF_8);
+        assert!(matches!(utf8.text, Cow::Borrowed("€ price")));
+
+        let latin1 = decode_to_utf8(b"\x80 price, na\xefve caf\xe9 \x81");
+        assert_eq!(latin1.encoding, Encoding::WINDOWS_1252);
+        assert_eq!(latin1.text, "€ price, naïve café \u{81}");
+        assert!(!latin1.had_replacements);
+
+        let bom = decode_to_utf8(b"\xef\xbb\xbfhi");
+        assert_eq!((bom.encoding, &*bom.text), (Encoding::UTF_8, "hi"));
+
+        let utf16 = decode_to_utf8(b"\xff\xfeh\0i\0");
+        assert_eq!((utf16.encoding, &*utf16.text), (Encoding::UTF_16LE, "hi"));
+        let utf16 = decode_to_utf8(b"\xfe\xff\0h\xd8\0");
+        assert_eq!(
+            (utf16.encoding, &*utf16.text),
+            (Encoding::UTF_16BE, "h\u{fffd}")
+        );
+        assert!(utf16.had_replacements);
+
+        assert_eq!(decode_to_utf8(b"").encoding, Encoding::UTF_8);
+    }
+
+    #[test]
+    fn test_detect_legacy_encodings() {
+        let russian = "Съешь же ещё этих мягких французских булок, да выпей чаю.";
+        let (koi8, ..) = encoding_rs::KOI8_R.encode(russian);
+        let decoded = decode_to_utf8(&koi8);
+        // KOI8-U, a superset of KOI8-R that decodes it the same
+        assert_eq!(decoded.encoding.name(), "KOI8-U");
+        assert_eq!(decoded.text, russian);
+
+        let japanese = "いろはにほへと ちりぬるを わかよたれそ つねならむ";
+        let (sjis, ..) = encoding_rs::SHIFT_JIS.encode(japanese);
+        let decoded = decode_to_utf8(&sjis);
+        assert_eq!(decoded.encoding.name(), "Shift_JIS");
+        assert_eq!(decoded.text, japanese);
+    }
+
+    #[test]
+    fn test_decode_as() {
+        let forced = Encoding::UTF_8.decode(b"caf\xe9");
+        assert_eq!(forced.text, "caf\u{fffd}");
+        assert!(forced.had_replacements);
+        // Every Windows-1252 byte decodes
+        let all: Vec<u8> = (0..=255).collect();
+        let decoded = Encoding::WINDOWS_1252.decode(&all);
+        assert_eq!(decoded.text.chars().count(), 256);
+        assert!(!decoded.had_replacements);
+        // ... but not every Shift_JIS sequence
+        let sjis: Encoding = "shift_jis".parse().unwrap();
+        assert!(sjis.decode(b"\x82\xa0\x81").had_replacements);
+
+        assert_eq!("Latin1".parse(), Ok(Encoding::WINDOWS_1252));
+        assert_eq!(" UTF-8 ".parse(), Ok(Encoding::UTF_8));
+        assert_eq!("utf-16".parse(), Ok(Encoding::UTF_16LE));
+        let koi8: Encoding = "koi8-r".parse().unwrap();
+        assert_eq!(koi8.to_string(), "KOI8-R");
+        assert_eq!(koi8.bom(), b"");
+        assert_eq!(
+            "iso-2022-kr".parse::<Encoding>(),
+            Err(UnknownEncoding("iso-2022-kr".into()))
+        );
+        assert_eq!(
+            "klingon".parse::<Encoding>(),
+            Err(UnknownEncoding("klingon".into()))
+        );
+        assert_eq!(Encoding::UTF_16BE.to_string(), "UTF-16BE");
+    }
+}
@@ -0,0 +1,289 @@
+//! EPUB books as documents.
+//!
+//! This module adds [`Document::from_epub`], which reads the spine of an EPUB
+//! book with the crate's minimal zip reader (stored and deflated entries, no
+//! ZIP64 or encryption), so the `epub` feature only pulls in a deflate
+//! decoder.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::document::{Document, Extractor, collapse, decode};
+use crate::html::{tag_attr, tag_end, tag_name};
+use crate::zip;
+
+/// Error reading an EPUB book.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EpubError {
+    /// The data isn't a well-formed zip archive.
+    InvalidArchive,
+    /// An entry is compressed with a method other than stored or deflate.
+    UnsupportedCompression { name: String, method: u16 },
+    /// A file the book refers to is missing from the archive.
+    MissingFile(String),
+    /// `META-INF/container.xml` names no package document.
+    MissingPackage,
+}
+
+impl fmt::Display for EpubError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EpubError::InvalidArchive => write!(f, "not a valid zip archive"),
+            EpubError::UnsupportedCompression { name, method } => {
+                write!(f, "{name} uses unsupported compression method {method}")
+            }
+            EpubError::MissingFile(name) => write!(f, "{name} is missing from the archive"),
+            EpubError::MissingPackage => {
+                write!(f, "META-INF/container.xml names no package document")
+            }
+        }
+    }
+}
+
+impl core::error::Error for EpubError {}
+
+impl Document {
+    /// Read the text of an EPUB book.
+    ///
+    /// The (X)HTML files of the spine are read in reading order, each
+    /// starting a new section; headings carry over from one file to the
+    /// next. Files marked `linear="no"` are skipped. The title comes from
+    /// the book's metadata.
+    pub fn from_epub(epub: &[u8]) -> Result<Self, EpubError> {
+        let archive = Archive::parse(epub)?;
+        let container = archive.read("META-INF/container.xml")?;
+        let package_path = tags(&container)
+            .find(|tag| is_element(tag, b"rootfile"))
+            .and_then(|tag| attr(tag, b"full-path"))
+            .filter(|path| !path.is_empty())
+            .ok_or(EpubError::MissingPackage)?;
+        let package = Package::parse(&archive.read(&package_path)?, &package_path);
+
+        let mut extractor = Extractor::default();
+        for path in &package.spine {
+            extractor.feed(&archive.read(path)?);
+            extractor.break_section();
+        }
+        let mut document = extractor.finish();
+        if package.title.is_some() {
+            document.title = package.title;
+        }
+        Ok(document)
+    }
+}
+
+/// The parts of an EPUB package document (`.opf`) that matter for reading.
+#[derive(Debug, Default)]
+struct Package {
+    title: Option<String>,
+    /// Archive paths of the (X)HTML files in reading order.
+    spine: Vec<String>,
+}
+
+impl Package {
+    fn parse(opf: &[u8], path: &str) -> Self {
+        let dir = path.rsplit_once('/').map_or("", |(dir, _)| dir);
+        let mut title = None;
+        // (id, path) of (X)HTML manifest items
+        let mut manifest = Vec::new();
+        let mut itemrefs = Vec::new();
+        let mut i = 0;
+        while let Some(n) = memchr::memchr(b'<', &opf[i..]) {
+            let start = i + n;
+            i = tag_end(opf, start);
+            let tag = &opf[start..i];
+            if is_element(tag, b"title") && title.is_none() {
+                let end = memchr::memchr(b'<', &opf[i..]).map_or(opf.len(), |n| i + n);
+                title = Some(collapse(&decode(&opf[i..end]))).filter(|t| !t.is_empty());
+            } else if is_element(tag, b"item") {
+                let media_type = attr(tag, b"media-type").unwrap_or_default();
+                if matches!(media_type.as_str(), "application/xhtml+xml" | "text/html")
+                    && let (Some(id), Some(href)) = (attr(tag, b"id"), attr(tag, b"href"))
+                {
+                    manifest.push((id, resolve(dir, &href)));
+                }
+            } else if is_element(tag, b"itemref") && attr(tag, b"linear").as_deref() != Some("no") {
+                itemrefs.extend(attr(tag, b"idref"));
+            }
+        }
+        let spine = itemrefs
+            .iter()
+            .filter_map(|idref| manifest.iter().find(|(id, _)| id == idref))
+            .map(|(_, path)| path.clone())
+            .collect();
+        Self { title, spine }
+    }
+}
+
+/// Tags in XML, skipping the text between them.
+fn tags(xml: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut i = 0;
+    core::iter::from_fn(move || {
+        let start = i + memchr::memchr(b'<', &xml[i..])?;
+        i = tag_end(xml, start);
+        Some(&xml[start..i])
+    })
+}
+
+/// Whether `tag` opens element `name`, ignoring any namespace prefix.
+fn is_element(tag: &[u8], name: &[u8]) -> bool {
+    let (closing, tag_name) = tag_name(tag);
+    let local = tag_name.rsplit(|&b| b == b':').next().unwrap_or_default();
+    !closing && local.eq_ignore_ascii_case(name)
+}
+
+/// Value of attribute `name`, with character references decoded.
+fn attr(tag: &[u8], name: &[u8]) -> Option<String> {
+    tag_attr(tag, name).map(|value| decode(value).into_owned())
+}
+
+/// Archive path of `href` relative to directory `dir`, percent-decoded and
+/// without a fragment.
+fn resolve(dir: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or_default();
+    let mut parts: Vec<&str> = Vec::new();
+    if !href.starts_with('/') {
+        parts.extend(dir.split('/'));
+    }
+    for part in href.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.retain(|part| !part.is_empty());
+    let path = parts.join("/");
+
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| core::str::from_utf8(h).ok());
+        match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+            Some(byte) if bytes[i] == b'%' => {
+                decoded.push(byte);
+                i += 3;
+            }
+            _ => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The entries of a zip archive.
+#[derive(Debug)]
+struct Archive<'a> {
+    data: &'a [u8],
+    entries: Vec<zip::Entry>,
+}
+
+impl<'a> Archive<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self, EpubError> {
+        zip::entries(data)
+            .map(|entries| Self { data, entries })
+            .ok_or(EpubError::InvalidArchive)
+    }
+
+    /// The contents of the file at `name`.
+    fn read(&self, name: &str) -> Result<Vec<u8>, EpubError> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| EpubError::MissingFile(name.to_string()))?;
+        entry.read(self.data).map_err(|err| match err {
+            zip::ReadError::Invalid => EpubError::InvalidArchive,
+            zip::ReadError::UnsupportedCompression(method) => EpubError::UnsupportedCompression {
+                name: name.to_string(),
+                method,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zip::build as zip;
+
+    const CONTAINER: &str = r#"<?xml version="1.0"?>
+        <container xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+          <rootfiles><rootfile full-path="OEBPS/content.opf"
+            media-type="application/oebps-package+xml"/></rootfiles>
+        </container>"#;
+
+    const PACKAGE: &str = r#"<package xmlns="http://www.idpf.org/2007/opf">
+          <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+            <dc:title>A &amp; B</dc:title></metadata>
+          <manifest>
+            <item id="c2" href="text/ch%202.xhtml" media-type="application/xhtml+xml"/>
+            <item id="c1" href="text/ch1.xhtml#top" media-type="application/xhtml+xml"/>
+            <item id="toc" href="nav.xhtml" media-type="application/xhtml+xml"/>
+            <item id="css" href="style.css" media-type="text/css"/>
+          </manifest>
+          <spine><itemref idref="toc" linear="no"/><itemref idref="c1"/>
+            <itemref idref="css"/><itemref idref="c2"/></spine>
+        </package>"#;
+
+    #[test]
+    fn test_epub_spine() {
+        let epub = zip(&[
+            ("mimetype", "application/epub+zip", false),
+            ("META-INF/container.xml", CONTAINER, true),
+            ("OEBPS/content.opf", PACKAGE, true),
+            ("OEBPS/nav.xhtml", "<nav>Contents</nav>", false),
+            (
+                "OEBPS/text/ch1.xhtml",
+                "<html><head><title>One</title></head><body>\
+                 <h1>Part I</h1><h2>Chapter 1</h2><p>It begins.</p></body></html>",
+                true,
+            ),
+            (
+                "OEBPS/text/ch 2.xhtml",
+                "<html><body><p>Still chapter 1.</p><h2>Chapter 2</h2><p>More.</p></body></html>",
+                false,
+            ),
+        ]);
+        let doc = Document::from_epub(&epub).unwrap();
+        assert_eq!(doc.title.as_deref(), Some("A & B"));
+        let sections: Vec<_> = doc
+            .sections
+            .iter()
+            .map(|s| (s.heading_path.as_str(), s.text.as_str()))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("Part I > Chapter 1", "Part I\n\nChapter 1\n\nIt begins."),
+                ("Part I > Chapter 1", "Still chapter 1."),
+                ("Part I > Chapter 2", "Chapter 2\n\nMore."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_epub_errors() {
+        assert_eq!(
+            Document::from_epub(b"not a zip"),
+            Err(EpubError::InvalidArchive)
+        );
+        let epub = zip(&[("META-INF/container.xml", CONTAINER, false)]);
+        assert_eq!(
+            Document::from_epub(&epub),
+            Err(EpubError::MissingFile("OEBPS/content.opf".into()))
+        );
+        let epub = zip(&[("META-INF/container.xml", "<container/>", false)]);
+        assert_eq!(Document::from_epub(&epub), Err(EpubError::MissingPackage));
+        assert_eq!(resolve("OEBPS", "../images/a%20b.png"), "images/a b.png");
+        assert_eq!(resolve("", "/text/c.xhtml#x"), "text/c.xhtml");
+    }
+}
@@ -0,0 +1,67 @@
+//! Stable content hashes for chunks (requires the `hash` feature).
+//!
+//! This module provides [`chunk_hash`], the 64-bit XXH3 hash (seed 0) of a
+//! chunk's bytes. The value only depends on the bytes, not on the platform,
+//! the chunker settings or the crate version, so it can key chunks in a
+//! vector store for deduplication and idempotent upserts.
+
+use xxhash_rust::xxh3::xxh3_64;
+
+/// Stable 64-bit content hash of `bytes` (XXH3, seed 0).
+///
+/// The same function backs [`Chunk::hash`](crate::Chunk::hash) and
+/// [`Chunker::collect_hashed_offsets`](crate::Chunker::collect_hashed_offsets),
+/// so hashes computed elsewhere from the chunk bytes match.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{chunk, chunk_hash};
+///
+/// let text = b"Same. Same. Other.";
+/// let hashed = chunk(text).size(7).delimiters(b".").trim(true).collect_hashed_offsets();
+/// // Equal chunks get equal hashes, wherever they are
+/// assert_eq!(hashed[0].2, hashed[1].2);
+/// assert_eq!(hashed[0].2, chunk_hash(b"Same."));
+/// ```
+#[inline]
+pub fn chunk_hash(bytes: &[u8]) -> u64 {
+    xxh3_64(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OwnedChunker, chunk};
+
+    #[test]
+    fn test_hash_stable() {
+        // Pinned values: the hash must never change between releases
+        assert_eq!(chunk_hash(b""), 0x2D06_8005_38D3_94C2);
+        assert_eq!(chunk_hash(b"Hello."), chunk_hash(&b"Hello. World."[..6]));
+        assert_ne!(chunk_hash(b"Hello."), chunk_hash(b"Hello!"));
+    }
+
+    #[test]
+    fn test_hash_offsets() {
+        let text = b"Same. Same. Other.";
+        let hashed = chunk(text)
+            .size(6)
+            .delimiters(b".")
+            .collect_hashed_offsets();
+        let offsets = chunk(text).size(6).delimiters(b".").collect_offsets();
+        assert_eq!(hashed.len(), offsets.len());
+        for (&(start, end, hash), &offset) in hashed.iter().zip(&offsets) {
+            assert_eq!((start, end), offset);
+            assert_eq!(hash, chunk_hash(&text[start..end]));
+        }
+
+        let infos: Vec<_> = chunk(text).size(6).delimiters(b".").iter_chunks().collect();
+        assert_eq!(infos[1].hash(), hashed[1].2);
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(6)
+            .delimiters(b".".to_vec());
+        assert_eq!(owned.collect_hashed_offsets(), hashed);
+    }
+}
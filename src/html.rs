@@ -0,0 +1,442 @@
+//! HTML/XML-aware chunking.
+//!
+//! This module provides [`HtmlChunker`], which never splits inside a tag (or
+//! comment) and prefers the edges of block elements like `</p>`, `</div>` and
+//! `</li>` as split points.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::delim::{DEFAULT_TARGET_SIZE, utf8_split_boundary};
+
+/// Default block elements whose edges are preferred split points.
+pub const DEFAULT_BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "br",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+];
+
+/// How good a split point is; lower is better.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Priority {
+    /// Before or after a block element.
+    Block,
+    /// Before or after any other element, comment or declaration.
+    Tag,
+    /// After whitespace in text.
+    Space,
+}
+
+/// Split candidates and tag spans found in one scan.
+#[derive(Debug, Default)]
+struct Scan {
+    /// (position, priority), sorted by position.
+    candidates: Vec<(usize, Priority)>,
+    /// (start, end) of every tag and comment, and of every script and style
+    /// element as a whole, sorted.
+    tags: Vec<(usize, usize)>,
+}
+
+/// A chunker for HTML and XML markup.
+///
+/// Tags, comments and `<script>`/`<style>` tags are atomic: a split point
+/// never falls inside `<...>`. Each chunk ends at the best candidate within
+/// the target size: block element edges first, then any tag edge, then
+/// whitespace, taking the latest candidate of the best kind. Without any
+/// candidate the chunk is hard split, moved off any tag it would cut.
+///
+/// For XML, pass the record element names to
+/// [`block_elements`](Self::block_elements).
+///
+/// # Example
+///
+/// ```
+/// use chunk::HtmlChunker;
+///
+/// let html = b"<p>First <a href=\"/x\">link</a>.</p><p>Second paragraph.</p>";
+/// let chunks = HtmlChunker::new().size(40).chunks(html);
+/// assert_eq!(chunks[0], &b"<p>First <a href=\"/x\">link</a>.</p>"[..]);
+/// assert_eq!(chunks[1], &b"<p>Second paragraph.</p>"[..]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HtmlChunker {
+    target_size: usize,
+    block_elements: Vec<String>,
+    utf8_safe: bool,
+}
+
+impl Default for HtmlChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HtmlChunker {
+    /// Create an HTML chunker with [`DEFAULT_BLOCK_ELEMENTS`].
+    pub fn new() -> Self {
+        Self {
+            target_size: DEFAULT_TARGET_SIZE,
+            block_elements: DEFAULT_BLOCK_ELEMENTS
+                .iter()
+                .map(|e| e.to_string())
+                .collect(),
+            utf8_safe: false,
+        }
+    }
+
+    /// Set the target chunk size in bytes.
+    pub fn size(mut self, size: usize) -> Self {
+        self.target_size = size;
+        self
+    }
+
+    /// Replace the block element names (matched case-insensitively).
+    pub fn block_elements<S: AsRef<str>>(mut self, elements: &[S]) -> Self {
+        self.block_elements = elements
+            .iter()
+            .map(|e| e.as_ref().to_ascii_lowercase())
+            .collect();
+        self
+    }
+
+    /// Never hard split inside a multi-byte UTF-8 sequence.
+    ///
+    /// See [`Chunker::utf8_safe`](crate::Chunker::utf8_safe) for details.
+    pub fn utf8_safe(mut self, enabled: bool) -> Self {
+        self.utf8_safe = enabled;
+        self
+    }
+
+    /// Chunk `text`, returning (start, end) byte offsets.
+    pub fn chunk_offsets(&self, text: &[u8]) -> Vec<(usize, usize)> {
+        let size = self.target_size.max(1);
+        let scan = self.scan(text);
+        let mut offsets = Vec::new();
+        let mut pos = 0;
+
+        while pos < text.len() {
+            let limit = pos + size;
+            let split = if limit >= text.len() {
+                text.len()
+            } else {
+                let first = scan.candidates.partition_point(|&(p, _)| p <= pos);
+                let last = scan.candidates.partition_point(|&(p, _)| p <= limit);
+                scan.candidates[first..last]
+                    .iter()
+                    .min_by_key(|&&(p, priority)| (priority, usize::MAX - p))
+                    .map(|&(p, _)| p)
+                    .unwrap_or_else(|| self.hard_split(text, &scan.tags, pos, limit))
+            };
+            offsets.push((pos, split));
+            pos = split;
+        }
+        offsets
+    }
+
+    /// Chunk `text`, returning slices borrowed from it.
+    pub fn chunks<'t>(&self, text: &'t [u8]) -> Vec<&'t [u8]> {
+        self.chunk_offsets(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect()
+    }
+
+    /// Hard split at `limit`, moved off any tag it would cut.
+    fn hard_split(&self, text: &[u8], tags: &[(usize, usize)], pos: usize, limit: usize) -> usize {
+        let i = tags.partition_point(|&(start, _)| start < limit);
+        if let Some(&(start, end)) = i.checked_sub(1).map(|i| &tags[i])
+            && end > limit
+        {
+            // Back up to the tag start, or take the whole tag if it starts the chunk
+            return if start > pos { start } else { end };
+        }
+        if self.utf8_safe {
+            utf8_split_boundary(text, pos, limit)
+        } else {
+            limit
+        }
+    }
+
+    /// Find tags, comments and split candidates.
+    fn scan(&self, text: &[u8]) -> Scan {
+        let mut scan = Scan::default();
+        let mut i = 0;
+
+        while i < text.len() {
+            match text[i] {
+                b'<' if is_tag_start(text.get(i + 1).copied()) => {
+                    let end = tag_end(text, i);
+                    let tag = &text[i..end];
+                    let (closing, name) = tag_name(tag);
+                    let block = self
+                        .block_elements
+                        .iter()
+                        .any(|e| e.as_bytes().eq_ignore_ascii_case(name));
+
+                    // Split before an element starts or after it ends, never
+                    // between a tag and the content it wraps
+                    let kind = if block {
+                        Priority::Block
+                    } else {
+                        Priority::Tag
+                    };
+                    let self_closing = tag.ends_with(b"/>") || is_void(name) || name.is_empty();
+                    if !closing {
+                        push_candidate(&mut scan.candidates, i, kind);
+                    }
+                    if closing || self_closing {
+                        push_candidate(&mut scan.candidates, end, kind);
+                    }
+                    scan.tags.push((i, end));
+                    i = end;
+
+                    // Raw text elements: skip past the closing tag, keeping the
+                    // whole element as one span so a hard split can't cut it
+                    if !closing
+                        && (name.eq_ignore_ascii_case(b"script")
+                            || name.eq_ignore_ascii_case(b"style"))
+                    {
+                        i = match find_close(text, i, name) {
+                            Some(close) => {
+                                let end = tag_end(text, close);
+                                push_candidate(&mut scan.candidates, end, kind);
+                                end
+                            }
+                            None => text.len(),
+                        };
+                        scan.tags.last_mut().expect("tag was just pushed").1 = i;
+                    }
+                }
+                b if b.is_ascii_whitespace() => {
+                    if text.get(i + 1).is_some_and(|b| !b.is_ascii_whitespace()) {
+                        push_candidate(&mut scan.candidates, i + 1, Priority::Space);
+                    }
+                    i += 1;
+                }
+                _ => i += 1,
+            }
+        }
+        scan
+    }
+}
+
+/// Add a candidate, keeping only the best priority per position.
+fn push_candidate(candidates: &mut Vec<(usize, Priority)>, pos: usize, priority: Priority) {
+    match candidates.last_mut() {
+        Some(last) if last.0 == pos => last.1 = last.1.min(priority),
+        _ => candidates.push((pos, priority)),
+    }
+}
+
+/// Whether the byte after `<` starts a tag, comment or declaration.
+pub(crate) fn is_tag_start(next: Option<u8>) -> bool {
+    next.is_some_and(|b| b.is_ascii_alphabetic() || matches!(b, b'/' | b'!' | b'?'))
+}
+
+/// End (exclusive) of the tag or comment starting at `start`.
+pub(crate) fn tag_end(text: &[u8], start: usize) -> usize {
+    if text[start..].starts_with(b"<!--") {
+        return memchr::memmem::find(&text[start + 4..], b"-->")
+            .map_or(text.len(), |i| start + 4 + i + 3);
+    }
+    let mut quote = None;
+    for (i, &b) in text.iter().enumerate().skip(start + 1) {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'"' || b == b'\'' => quote = Some(b),
+            None if b == b'>' => return i + 1,
+            None => {}
+        }
+    }
+    text.len()
+}
+
+/// Whether the tag is a closing tag, and its name.
+pub(crate) fn tag_name(tag: &[u8]) -> (bool, &[u8]) {
+    let closing = tag.get(1) == Some(&b'/');
+    let name = &tag[1 + closing as usize..];
+    let len = name
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b':' | b'_'))
+        .count();
+    (closing, &name[..len])
+}
+
+/// Raw value of attribute `name` (case-insensitive) in a tag, without quotes.
+///
+/// An attribute without a value gives an empty value.
+#[cfg(feature = "epub")]
+pub(crate) fn tag_attr<'t>(tag: &'t [u8], name: &[u8]) -> Option<&'t [u8]> {
+    let (closing, tag_name) = tag_name(tag);
+    let end = tag.len() - tag.ends_with(b">") as usize;
+    let mut i = (1 + closing as usize + tag_name.len()).min(end);
+    let skip_space = |i: &mut usize| {
+        while *i < end && tag[*i].is_ascii_whitespace() {
+            *i += 1;
+        }
+    };
+    while i < end {
+        if tag[i].is_ascii_whitespace() || tag[i] == b'/' {
+            i += 1;
+            continue;
+        }
+        let key_start = i;
+        while i < end && !tag[i].is_ascii_whitespace() && !matches!(tag[i], b'=' | b'/') {
+            i += 1;
+        }
+        let key = &tag[key_start..i];
+        skip_space(&mut i);
+        let mut value = &tag[i..i];
+        if i < end && tag[i] == b'=' {
+            i += 1;
+            skip_space(&mut i);
+            if i < end && matches!(tag[i], b'"' | b'\'') {
+                let start = i + 1;
+                let len = memchr::memchr(tag[i], &tag[start..end]).unwrap_or(end - start);
+                value = &tag[start..start + len];
+                i = (start + len + 1).min(end);
+            } else {
+                let start = i;
+                while i < end && !tag[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                value = &tag[start..i];
+            }
+        }
+        if key.eq_ignore_ascii_case(name) {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// HTML void elements that never have a closing tag.
+pub(crate) fn is_void(name: &[u8]) -> bool {
+    [b"br".as_slice(), b"hr", b"img", b"input", b"meta", b"link"]
+        .iter()
+        .any(|v| v.eq_ignore_ascii_case(name))
+}
+
+/// Start of the closing tag `</name` at or after `from` (case-insensitive).
+pub(crate) fn find_close(text: &[u8], from: usize, name: &[u8]) -> Option<usize> {
+    let mut i = from;
+    while let Some(n) = memchr::memmem::find(&text[i..], b"</") {
+        let at = i + n;
+        let rest = &text[at + 2..];
+        if rest.len() >= name.len() && rest[..name.len()].eq_ignore_ascii_case(name) {
+            return Some(at);
+        }
+        i = at + 2;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_prefers_block_edges() {
+        let html = b"<div><p>One two three.</p><p>Four <b>five</b> six.</p></div>";
+        let chunks = HtmlChunker::new().size(30).chunks(html);
+        assert_eq!(chunks[0], b"<div><p>One two three.</p>");
+        assert_eq!(chunks[1], b"<p>Four <b>five</b> six.</p>");
+        assert_eq!(chunks.concat(), html.to_vec());
+    }
+
+    #[test]
+    fn test_html_never_splits_inside_tag() {
+        let html = b"<a href=\"https://example.com/a/very/long/path\">x</a>";
+        for size in 1..html.len() {
+            let offsets = HtmlChunker::new().size(size).chunk_offsets(html);
+            for (start, end) in offsets {
+                assert!(!(1..47).contains(&start), "size {size}: split at {start}");
+                assert!(!(1..47).contains(&end), "size {size}: split at {end}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_html_quoted_gt_in_attribute() {
+        let html = b"<p title=\"a > b\">text</p>";
+        let scan = HtmlChunker::new().scan(html);
+        assert_eq!(scan.tags, vec![(0, 17), (21, 25)]);
+    }
+
+    #[test]
+    fn test_html_comments_and_scripts() {
+        let html = b"<!-- <p> -->text<script>if (a < b) { x = '</p>'; }</script>";
+        let scan = HtmlChunker::new().scan(html);
+        assert_eq!(scan.tags, vec![(0, 12), (16, 59)]);
+    }
+
+    #[test]
+    fn test_html_hard_split_keeps_scripts_whole() {
+        let html = b"<p>Hi</p><script>let x = 1; let y = 2; let z = 3;</script><p>Bye</p>";
+        for size in [8, 16, 32] {
+            let offsets = HtmlChunker::new().size(size).chunk_offsets(html);
+            assert!(offsets.contains(&(9, 58)), "size {size}: {offsets:?}");
+        }
+    }
+
+    #[test]
+    fn test_html_whitespace_fallback() {
+        let html = b"<p>aaaa bbbb cccc dddd</p>";
+        let chunks = HtmlChunker::new().size(12).chunks(html);
+        assert_eq!(chunks[0], b"<p>aaaa ");
+        assert_eq!(chunks.concat(), html.to_vec());
+    }
+
+    #[test]
+    fn test_xml_block_elements() {
+        let xml = b"<items><item>a</item><item>b</item><item>c</item></items>";
+        let chunker = HtmlChunker::new().size(30).block_elements(&["item"]);
+        let chunks = chunker.chunks(xml);
+        assert_eq!(chunks[0], b"<items><item>a</item>");
+        assert_eq!(chunks[1], b"<item>b</item><item>c</item>");
+    }
+
+    #[test]
+    fn test_html_void_block_element() {
+        let html = b"line one<br>line two<br/>three";
+        let chunks = HtmlChunker::new().size(14).chunks(html);
+        assert_eq!(chunks[0], b"line one<br>");
+        assert_eq!(chunks[1], b"line two<br/>");
+    }
+
+    #[test]
+    fn test_html_literal_less_than() {
+        let html = b"1 < 2 and 3 <4";
+        let scan = HtmlChunker::new().scan(html);
+        assert!(scan.tags.is_empty());
+    }
+}
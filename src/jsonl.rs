@@ -0,0 +1,296 @@
+//! Record-aware chunking for JSON lines (NDJSON).
+//!
+//! This module provides [`JsonlChunker`], which packs whole newline-delimited
+//! records into chunks up to a target size and never splits a record, and
+//! the record writer behind [`Chunker::write_jsonl`](crate::Chunker::write_jsonl).
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::delim::DEFAULT_TARGET_SIZE;
+
+/// Error for a record that doesn't fit in a chunk on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OversizedRecord {
+    /// Zero-based line number of the record.
+    pub line: usize,
+    /// Byte offset where the record starts.
+    pub start: usize,
+    /// Byte offset where the record ends, not counting its newline.
+    pub end: usize,
+    /// The target size it exceeds.
+    pub limit: usize,
+}
+
+impl fmt::Display for OversizedRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "record on line {} is {} bytes, larger than the {} byte limit",
+            self.line + 1,
+            self.end - self.start,
+            self.limit
+        )
+    }
+}
+
+impl core::error::Error for OversizedRecord {}
+
+/// A chunker for newline-delimited JSON records.
+///
+/// Records (lines, including their `\n`) are packed greedily while the chunk
+/// stays within the target size, not counting the newline that ends it, so a
+/// record of exactly the target size fits. A record is never split: one
+/// larger than the target is an [`OversizedRecord`] error, or, with
+/// [`allow_oversized`](Self::allow_oversized), a chunk of its own.
+///
+/// Records are not parsed, so this works for any line-oriented format.
+///
+/// # Example
+///
+/// ```
+/// use chunk::JsonlChunker;
+///
+/// let data = b"{\"id\":1}\n{\"id\":2}\n{\"id\":3}\n";
+/// let offsets = JsonlChunker::new().size(20).chunk_offsets(data).unwrap();
+/// assert_eq!(offsets, vec![(0, 18), (18, 27)]);
+///
+/// assert!(JsonlChunker::new().size(5).chunk_offsets(data).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonlChunker {
+    target_size: usize,
+    allow_oversized: bool,
+}
+
+impl Default for JsonlChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonlChunker {
+    /// Create a JSON lines chunker with the default target size.
+    pub fn new() -> Self {
+        Self {
+            target_size: DEFAULT_TARGET_SIZE,
+            allow_oversized: false,
+        }
+    }
+
+    /// Set the target chunk size in bytes.
+    pub fn size(mut self, size: usize) -> Self {
+        self.target_size = size;
+        self
+    }
+
+    /// Emit oversized records as their own chunk instead of failing.
+    ///
+    /// Such chunks are the only ones longer than the target size, so they can
+    /// be flagged with a length check.
+    pub fn allow_oversized(mut self) -> Self {
+        self.allow_oversized = true;
+        self
+    }
+
+    /// Chunk `text`, returning (start, end) byte offsets.
+    pub fn chunk_offsets(&self, text: &[u8]) -> Result<Vec<(usize, usize)>, OversizedRecord> {
+        let size = self.target_size.max(1);
+        let mut offsets = Vec::new();
+        let mut start = 0;
+        let mut end = 0;
+
+        for (line, (record_start, record_end)) in lines(text).enumerate() {
+            // The newline ending the chunk doesn't count against the size
+            let content_end = record_end - usize::from(text[record_end - 1] == b'\n');
+            if content_end - start <= size {
+                end = record_end;
+                continue;
+            }
+            if end > start {
+                offsets.push((start, end));
+                start = end;
+            }
+            if content_end - start > size && !self.allow_oversized {
+                return Err(OversizedRecord {
+                    line,
+                    start: record_start,
+                    end: content_end,
+                    limit: size,
+                });
+            }
+            end = record_end;
+        }
+
+        if end > start {
+            offsets.push((start, end));
+        }
+        Ok(offsets)
+    }
+
+    /// Chunk `text`, returning slices borrowed from it.
+    pub fn chunks<'t>(&self, text: &'t [u8]) -> Result<Vec<&'t [u8]>, OversizedRecord> {
+        Ok(self
+            .chunk_offsets(text)?
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect())
+    }
+}
+
+/// Iterate over (start, end) spans of each line, including its newline.
+fn lines(text: &[u8]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut pos = 0;
+    core::iter::from_fn(move || {
+        if pos >= text.len() {
+            return None;
+        }
+        let start = pos;
+        pos = memchr::memchr(b'\n', &text[pos..]).map_or(text.len(), |i| pos + i + 1);
+        Some((start, pos))
+    })
+}
+
+/// Write one `{"index":..,"start":..,"end":..,"text":..}` line, with
+/// invalid UTF-8 in `text` replaced by U+FFFD.
+#[cfg(feature = "std")]
+pub(crate) fn write_record(
+    out: &mut impl std::io::Write,
+    index: usize,
+    start: usize,
+    end: usize,
+    text: &[u8],
+) -> std::io::Result<()> {
+    write!(
+        out,
+        "{{\"index\":{index},\"start\":{start},\"end\":{end},\"text\":\""
+    )?;
+    for chunk in text.utf8_chunks() {
+        let valid = chunk.valid().as_bytes();
+        let mut from = 0;
+        for (i, &b) in valid.iter().enumerate() {
+            if b != b'"' && b != b'\\' && b >= 0x20 {
+                continue;
+            }
+            out.write_all(&valid[from..i])?;
+            match b {
+                b'"' => out.write_all(b"\\\"")?,
+                b'\\' => out.write_all(b"\\\\")?,
+                b'\n' => out.write_all(b"\\n")?,
+                b'\r' => out.write_all(b"\\r")?,
+                b'\t' => out.write_all(b"\\t")?,
+                _ => write!(out, "\\u{b:04x}")?,
+            }
+            from = i + 1;
+        }
+        out.write_all(&valid[from..])?;
+        if !chunk.invalid().is_empty() {
+            out.write_all("\u{fffd}".as_bytes())?;
+        }
+    }
+    out.write_all(b"\"}\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &[u8] = b"{\"a\":1}\n{\"b\":22}\n{\"c\":333}\n";
+
+    #[test]
+    fn test_jsonl_packs_whole_records() {
+        let chunks = JsonlChunker::new().size(17).chunks(DATA).unwrap();
+        assert_eq!(
+            chunks,
+            vec![&b"{\"a\":1}\n{\"b\":22}\n"[..], &b"{\"c\":333}\n"[..]]
+        );
+    }
+
+    #[test]
+    fn test_jsonl_oversized_record_errors() {
+        let err = JsonlChunker::new().size(8).chunk_offsets(DATA).unwrap_err();
+        assert_eq!(
+            err,
+            OversizedRecord {
+                line: 2,
+                start: 17,
+                end: 26,
+                limit: 8
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "record on line 3 is 9 bytes, larger than the 8 byte limit"
+        );
+    }
+
+    #[test]
+    fn test_jsonl_record_of_exactly_size() {
+        // {"c":333} is 9 bytes before its newline
+        let offsets = JsonlChunker::new().size(9).chunk_offsets(DATA).unwrap();
+        assert_eq!(offsets, vec![(0, 8), (8, 17), (17, 27)]);
+        let offsets = JsonlChunker::new().size(16).chunk_offsets(DATA).unwrap();
+        assert_eq!(offsets, vec![(0, 17), (17, 27)]);
+    }
+
+    #[test]
+    fn test_jsonl_allow_oversized() {
+        let offsets = JsonlChunker::new()
+            .size(8)
+            .allow_oversized()
+            .chunk_offsets(DATA)
+            .unwrap();
+        assert_eq!(offsets, vec![(0, 8), (8, 17), (17, 27)]);
+    }
+
+    #[test]
+    fn test_jsonl_blank_lines_and_missing_newline() {
+        let data = b"{\"a\":1}\n\n\n{\"b\":2}";
+        let chunks = JsonlChunker::new().size(9).chunks(data).unwrap();
+        assert_eq!(chunks, vec![&b"{\"a\":1}\n\n\n"[..], &b"{\"b\":2}"[..]]);
+    }
+
+    #[test]
+    fn test_jsonl_empty() {
+        assert!(JsonlChunker::new().chunk_offsets(b"").unwrap().is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_jsonl_escapes() {
+        let text = b"tab\there \"q\" \\ \x01 \xff\xfe caf\xc3\xa9\n";
+        let mut out = Vec::new();
+        let count = crate::chunk(text)
+            .size(8)
+            .utf8_safe(true)
+            .write_jsonl(&mut out)
+            .unwrap();
+        let records: Vec<serde_json::Value> = out
+            .split(|&b| b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), count);
+        let mut joined = String::new();
+        for (index, record) in records.iter().enumerate() {
+            assert_eq!(record["index"], index);
+            let (start, end) = (
+                record["start"].as_u64().unwrap(),
+                record["end"].as_u64().unwrap(),
+            );
+            let expected = String::from_utf8_lossy(&text[start as usize..end as usize]);
+            assert_eq!(record["text"], *expected);
+            joined += record["text"].as_str().unwrap();
+        }
+        assert_eq!(joined, String::from_utf8_lossy(text));
+
+        let mut owned = Vec::new();
+        crate::OwnedChunker::new(text.to_vec())
+            .size(8)
+            .utf8_safe(true)
+            .write_jsonl(&mut owned)
+            .unwrap();
+        assert_eq!(owned, out);
+    }
+}
@@ -0,0 +1,78 @@
+//! Language detection for chunks (requires the `lang-detect` feature).
+//!
+//! This module provides [`detect_language`], the ISO 639-3 code of the
+//! language a chunk is most likely written in, detected with whatlang from
+//! trigram statistics, so the chunks of a multilingual corpus can be routed
+//! to the right embedding model.
+
+/// ISO 639-3 code of the language `bytes` are most likely written in, e.g.
+/// `"eng"` or `"tha"`, or `None` if there's no text to tell by (only digits,
+/// punctuation or whitespace).
+///
+/// The same function backs [`Chunk::language`](crate::Chunk::language) and
+/// [`Chunker::collect_language_offsets`](crate::Chunker::collect_language_offsets).
+/// Invalid UTF-8 is replaced with U+FFFD first. Short chunks give weaker
+/// guesses, so detect on chunks of a sentence or more.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{chunk, detect_language};
+///
+/// let text = "The weather is lovely today. Das Wetter ist heute wirklich schön.".as_bytes();
+/// let langs = chunk(text).size(40).delimiters(b".").trim(true).collect_language_offsets();
+/// assert_eq!(langs[0].2, Some("eng"));
+/// assert_eq!(langs[1].2, Some("deu"));
+/// assert_eq!(detect_language(b"1234."), None);
+/// ```
+pub fn detect_language(bytes: &[u8]) -> Option<&'static str> {
+    let text = String::from_utf8_lossy(bytes);
+    whatlang::detect_lang(&text).map(|lang| lang.code())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OwnedChunker, chunk};
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language("Ceci est une phrase en français.".as_bytes()),
+            Some("fra")
+        );
+        assert_eq!(
+            detect_language("今日はとても良い天気ですね。".as_bytes()),
+            Some("jpn")
+        );
+        assert_eq!(detect_language(b""), None);
+        assert_eq!(detect_language(b"\xff\xfe 42"), None);
+    }
+
+    #[test]
+    fn test_language_offsets() {
+        let text = "The weather is lovely today. Das Wetter ist heute wirklich schön.".as_bytes();
+        let langs = chunk(text)
+            .size(40)
+            .delimiters(b".")
+            .collect_language_offsets();
+        let offsets = chunk(text).size(40).delimiters(b".").collect_offsets();
+        assert_eq!(langs.len(), offsets.len());
+        for (&(start, end, lang), &offset) in langs.iter().zip(&offsets) {
+            assert_eq!((start, end), offset);
+            assert_eq!(lang, detect_language(&text[start..end]));
+        }
+
+        let infos: Vec<_> = chunk(text)
+            .size(40)
+            .delimiters(b".")
+            .iter_chunks()
+            .collect();
+        assert_eq!(infos[1].language(), Some("deu"));
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(40)
+            .delimiters(b".".to_vec());
+        assert_eq!(owned.collect_language_offsets(), langs);
+    }
+}
@@ -1,6 +1,6 @@
 //! The fastest semantic text chunking library — up to 1TB/s chunking throughput.
 //!
-//! This crate provides three main functionalities:
+//! This crate provides four main functionalities:
 //!
 //! 1. **Size-based chunking** ([`chunk`] module): Split text into chunks of a target size,
 //!    preferring to break at delimiter boundaries.
@@ -11,6 +11,9 @@
 //! 3. **Token-aware merging** ([`merge`] module): Merge segments based on token counts,
 //!    equivalent to Cython's `_merge_splits` function.
 //!
+//! 4. **Recursive chunking** ([`RecursiveChunker`]): Split at coarse delimiters first
+//!    (paragraphs), descending to finer levels only for pieces that are still too large.
+//!
 //! # Examples
 //!
 //! ## Size-based chunking
@@ -59,28 +62,265 @@
 //! assert_eq!(result.merged, vec!["abc", "def", "g"]);
 //! assert_eq!(result.token_counts, vec![3, 3, 1]);
 //! ```
-
+//!
+//! ## `no_std`
+//!
+//! With `default-features = false` the crate builds with `#![no_std]` and
+//! `alloc`: the boundary scanners and the in-memory chunkers are all there.
+//! Multi-pattern search falls back to memmem for any number of patterns.
+//! Reading from files and `std::io`, the Aho-Corasick splitters
+//! ([`PatternSplitter`], [`split_at_patterns`] and [`RecursiveChunker`]),
+//! duplicate detection, [`CancelToken`], the Savitzky-Golay helpers and the
+//! `archive`, `corpus`, `mmap`, `parquet`, `pdf`, `profiles`, `rayon`,
+//! `redact`, `regex`, `serde`, `tokenizers` and `zstd` features need the `std`
+//! feature (on by default).
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "archive")]
+mod archive;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "rayon")]
+mod batch;
+#[cfg(feature = "std")]
+mod cancel;
+mod checkpoint;
 mod chunk;
+mod code;
+mod config;
+#[cfg(feature = "corpus")]
+mod corpus;
+mod csv;
+#[cfg(feature = "std")]
+mod dedupe;
 mod delim;
+mod delimiters;
+#[cfg(feature = "documents")]
+mod document;
+#[cfg(feature = "encoding")]
+mod encoding;
+#[cfg(feature = "epub")]
+mod epub;
+#[cfg(feature = "hash")]
+mod hash;
+mod html;
+mod jsonl;
+#[cfg(feature = "lang-detect")]
+mod lang;
+mod locate;
+mod markdown;
 mod merge;
+mod metric;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod normalize;
+mod pack;
+mod pages;
+mod parent;
+#[cfg(feature = "parquet")]
+mod parquet;
+#[cfg(feature = "pdf")]
+mod pdf;
+mod pipeline;
+mod position;
+mod progress;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+mod recursive;
+#[cfg(feature = "redact")]
+mod redact;
+#[cfg(feature = "regex")]
+mod regex_chunk;
+#[cfg(feature = "std")]
 mod savgol;
+mod score;
+#[cfg(feature = "sentence")]
+mod sentence;
+#[cfg(feature = "serde")]
+mod serde_bytes;
 mod split;
+mod stats;
+mod stream;
+mod template;
+#[cfg(feature = "profiles")]
+mod toml;
+mod tree;
+mod window;
+#[cfg(any(feature = "archive", feature = "epub"))]
+mod zip;
+
+// Re-export from archive module
+#[cfg(feature = "archive")]
+pub use crate::archive::{ArchiveChunks, ArchiveEntry, chunk_archive};
+
+// Re-export from arrow module
+#[cfg(feature = "arrow")]
+pub use crate::arrow::{
+    ArrowArray, ArrowArrayStream, ArrowError, ArrowLayout, ArrowSchema, ChunkTable, TextColumn,
+};
+
+// Re-export from batch module
+#[cfg(feature = "rayon")]
+pub use crate::batch::{chunk_batch, chunk_batch_until};
+
+// Re-export from cancel module
+#[cfg(feature = "std")]
+pub use crate::cancel::{CancelToken, Cancelled};
+
+// Re-export from checkpoint module
+pub use crate::checkpoint::{Checkpoint, CheckpointMismatch};
 
 // Re-export from chunk module
-pub use crate::chunk::{Chunker, OwnedChunker, chunk};
+pub use crate::chunk::{
+    Chunk, ChunkError, Chunker, Chunks, DelimiterMode, FallbackStrategy, OwnedChunker, OwnedChunks,
+    chunk,
+};
+
+// Re-export from code module
+pub use crate::code::{CodeChunker, Language, UnknownLanguage};
+
+// Re-export from config module
+pub use crate::config::ChunkerConfig;
+#[cfg(feature = "profiles")]
+pub use crate::config::ConfigError;
+
+// Re-export from corpus module
+#[cfg(feature = "corpus")]
+pub use crate::corpus::{CorpusError, DirChunks, FileChunks, chunk_dir};
+
+// Re-export from csv module
+pub use crate::csv::CsvChunker;
+
+// Re-export from dedupe module
+#[cfg(feature = "std")]
+pub use crate::dedupe::{Duplicates, find_batch_duplicates, find_duplicates};
+
+// Re-export from delimiters module
+pub use crate::delimiters::{Delimiters, UnknownDelimiters};
+
+// Re-export from document module
+#[cfg(feature = "documents")]
+pub use crate::document::{BOILERPLATE_ELEMENTS, Document, Section, SectionChunk};
+
+// Re-export from encoding module
+#[cfg(feature = "encoding")]
+pub use crate::encoding::{Decoded, Encoding, UnknownEncoding, decode_to_utf8};
+
+// Re-export from epub module
+#[cfg(feature = "epub")]
+pub use crate::epub::EpubError;
 
 // Re-export from split module
-pub use crate::split::{
-    IncludeDelim, PatternSplitter, Splitter, split, split_at_delimiters, split_at_patterns,
-};
+pub use crate::split::{IncludeDelim, Splitter, split, split_at_delimiters};
+#[cfg(feature = "std")]
+pub use crate::split::{PatternSplitter, split_at_patterns};
+
+// Re-export from hash module
+#[cfg(feature = "hash")]
+pub use crate::hash::chunk_hash;
+
+// Re-export from html module
+pub use crate::html::{DEFAULT_BLOCK_ELEMENTS, HtmlChunker};
+
+// Re-export from jsonl module
+pub use crate::jsonl::{JsonlChunker, OversizedRecord};
+
+// Re-export from lang module
+#[cfg(feature = "lang-detect")]
+pub use crate::lang::detect_language;
+
+// Re-export from locate module
+pub use crate::locate::ChunkMap;
+
+// Re-export from markdown module
+pub use crate::markdown::{MarkdownChunk, MarkdownChunker};
 
 // Re-export from merge module
-pub use crate::merge::{MergeResult, find_merge_indices, merge_splits};
+pub use crate::merge::{MergeResult, find_merge_indices, merge_chunks, merge_splits};
+
+// Re-export from metric module
+pub use crate::metric::{Bytes, Chars, SizeMetric, SizeUnit, count_tokens, estimate_tokens};
+
+// Re-export from mmap module
+#[cfg(feature = "mmap")]
+pub use crate::mmap::MappedText;
+
+// Re-export from normalize module
+pub use crate::normalize::{Normalized, Normalizer, OffsetSpace};
+
+// Re-export from pack module
+pub use crate::pack::{PackStrategy, Packing, pack};
+
+// Re-export from pages module
+pub use crate::pages::{PAGE_BREAK, PageChunk, PagedText};
+
+// Re-export from parent module
+pub use crate::parent::ParentChildChunks;
+
+// Re-export from parquet module
+#[cfg(feature = "parquet")]
+pub use crate::parquet::{DEFAULT_ROW_GROUP_BYTES, ParquetWriter};
+
+// Re-export from pdf module
+#[cfg(feature = "pdf")]
+pub use crate::pdf::{PdfError, chunk_pdf};
+
+// Re-export from pipeline module
+pub use crate::pipeline::ChunkPipeline;
+
+// Re-export from position module
+pub use crate::position::{LineCounter, Position, Positioned};
+
+// Re-export from progress module
+pub use crate::progress::{Progress, WithProgress};
+
+// Re-export from reader module
+#[cfg(feature = "std")]
+pub use crate::reader::{DEFAULT_READ_BUFFER_SIZE, Decompressed, ReaderChunker};
+
+// Re-export from recursive module
+#[cfg(feature = "std")]
+pub use crate::recursive::{PARAGRAPH_DELIMITERS, RecursiveChunker, SENTENCE_DELIMITERS};
+
+// Re-export from redact module
+#[cfg(feature = "redact")]
+pub use crate::redact::{RedactedChunk, Redaction, Redactor};
+
+// Re-export from regex_chunk module
+#[cfg(feature = "regex")]
+pub use crate::regex_chunk::RegexChunker;
+
+// Re-export from score module
+pub use crate::score::BoundaryScorer;
+
+// Re-export from sentence module
+#[cfg(feature = "sentence")]
+pub use crate::sentence::{DEFAULT_ABBREVIATIONS, SentenceChunker};
+
+// Re-export from stats module
+pub use crate::stats::ChunkStats;
+
+// Re-export from stream module
+pub use crate::stream::StreamChunker;
+
+// Re-export from template module
+pub use crate::template::{ChunkTemplate, TemplateError};
+
+// Re-export from tree module
+pub use crate::tree::ChunkTree;
+
+// Re-export from window module
+pub use crate::window::WindowChunker;
 
 // Re-export constants and types from delim module
 pub use crate::delim::{DEFAULT_DELIMITERS, DEFAULT_TARGET_SIZE, MultiPatternSearcher};
 
 // Re-export from savgol module
+#[cfg(feature = "std")]
 pub use crate::savgol::{
     FilteredIndices, MinimaResult, filter_split_indices, find_local_minima_interpolated,
     savgol_filter, windowed_cross_similarity,
@@ -439,6 +679,74 @@ mod integration_tests {
         }
     }
 
+    #[test]
+    fn test_patterns_longest_match_memmem() {
+        // "\n" nested in "\n\n" must not win just because it starts later
+        let text = b"One\n\nTwo\nThree";
+        let chunks: Vec<_> = chunk(text)
+            .size(6)
+            .delimiters(b"")
+            .patterns(&[b"\n".as_slice(), b"\n\n".as_slice()])
+            .prefix()
+            .collect();
+        assert_eq!(chunks[0], b"One");
+        assert!(chunks[1].starts_with(b"\n\n"));
+        let total: usize = chunks.iter().map(|c| c.len()).sum();
+        assert_eq!(total, text.len());
+    }
+
+    #[test]
+    fn test_patterns_longest_match_aho_corasick() {
+        let text = b"One\n\nTwo\nThree";
+        let chunks: Vec<_> = chunk(text)
+            .size(6)
+            .delimiters(b"")
+            .patterns(&["\n", "\n\n", "。", "，", "！"])
+            .prefix()
+            .collect();
+        assert_eq!(chunks[0], b"One");
+        assert!(chunks[1].starts_with(b"\n\n"));
+    }
+
+    #[test]
+    fn test_patterns_longest_match_over_delimiter() {
+        // Single-byte delimiter nested in a multi-byte pattern defers to the pattern
+        let text = b"One\n\nTwo";
+        let chunks: Vec<_> = chunk(text)
+            .size(6)
+            .delimiters(b"\n")
+            .patterns(&["\n\n"])
+            .prefix()
+            .collect();
+        assert_eq!(chunks[0], b"One");
+        assert_eq!(chunks[1], b"\n\nTwo");
+    }
+
+    #[test]
+    fn test_patterns_byte_strings() {
+        let sep: &[u8] = &[0xFF, 0xFE];
+        let text = b"abc\xFF\xFEdef\xFF\xFEghi";
+        let chunks: Vec<_> = chunk(text)
+            .size(8)
+            .delimiters(b"")
+            .patterns(&[sep])
+            .collect();
+        assert_eq!(chunks[0], b"abc\xFF\xFE");
+        let total: usize = chunks.iter().map(|c| c.len()).sum();
+        assert_eq!(total, text.len());
+    }
+
+    #[test]
+    fn test_patterns_empty_pattern_ignored() {
+        let text = b"Hello. World. Test.";
+        let chunks: Vec<_> = chunk(text)
+            .size(10)
+            .delimiters(b".")
+            .patterns(&["", ""])
+            .collect();
+        assert_eq!(chunks[0], b"Hello.");
+    }
+
     #[test]
     fn test_patterns_owned_chunker() {
         let text = "Hello。World，Test".as_bytes().to_vec();
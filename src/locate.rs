@@ -0,0 +1,138 @@
+//! Mapping offsets between a document and its chunks.
+//!
+//! This module provides [`ChunkMap`], which finds the chunks holding a byte
+//! offset or range of a document and converts between chunk-relative and
+//! document offsets, e.g. to highlight a span found in a retrieved chunk.
+
+use core::ops::Range;
+
+/// Chunk offsets of one document, searchable by document offset.
+///
+/// The offsets must come in document order, as every chunker here emits
+/// them: starts strictly increasing and ends never decreasing. Chunks may
+/// overlap or leave gaps (e.g. with [`trim`](crate::Chunker::trim)).
+///
+/// # Example
+///
+/// ```
+/// use chunk::{ChunkMap, chunk};
+///
+/// let text = b"One. Two. Three.";
+/// let offsets = chunk(text).size(7).delimiters(b".").collect_offsets();
+/// let map = ChunkMap::new(&offsets);
+/// assert_eq!(map.chunk_containing(12), Some(2));
+/// // "Two. Th" spans the second and third chunks
+/// assert_eq!(map.chunks_overlapping(5..12), 1..3);
+/// // "Two" is at 1..4 in the second chunk
+/// assert_eq!(map.to_absolute(1, 1..4), Some(5..8));
+/// assert_eq!(&text[5..8], b"Two");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkMap<'o> {
+    offsets: &'o [(usize, usize)],
+}
+
+impl<'o> ChunkMap<'o> {
+    /// Map the chunks at `offsets`, (start, end) byte offsets in document
+    /// order.
+    pub fn new(offsets: &'o [(usize, usize)]) -> Self {
+        Self { offsets }
+    }
+
+    /// The chunk offsets.
+    pub fn offsets(&self) -> &'o [(usize, usize)] {
+        self.offsets
+    }
+
+    /// Index of the first chunk containing document offset `offset`.
+    ///
+    /// Returns None past the last chunk or in a gap between chunks.
+    pub fn chunk_containing(&self, offset: usize) -> Option<usize> {
+        let index = self.offsets.partition_point(|&(_, end)| end <= offset);
+        let &(start, _) = self.offsets.get(index)?;
+        (start <= offset).then_some(index)
+    }
+
+    /// Indices of the chunks sharing at least one byte with document range
+    /// `range`.
+    ///
+    /// An empty range overlaps no chunks.
+    pub fn chunks_overlapping(&self, range: Range<usize>) -> Range<usize> {
+        let first = self.offsets.partition_point(|&(_, end)| end <= range.start);
+        if range.is_empty() {
+            return first..first;
+        }
+        let last = self
+            .offsets
+            .partition_point(|&(start, _)| start < range.end);
+        first..last.max(first)
+    }
+
+    /// Document range of range `range` within chunk `index`.
+    ///
+    /// Returns None if there's no such chunk or the range doesn't fit in it.
+    pub fn to_absolute(&self, index: usize, range: Range<usize>) -> Option<Range<usize>> {
+        let &(start, end) = self.offsets.get(index)?;
+        (range.start <= range.end && range.end <= end - start)
+            .then(|| start + range.start..start + range.end)
+    }
+
+    /// Range within chunk `index` of document range `range`.
+    ///
+    /// Returns None if there's no such chunk or the range isn't inside it.
+    pub fn to_relative(&self, index: usize, range: Range<usize>) -> Option<Range<usize>> {
+        let &(start, end) = self.offsets.get(index)?;
+        (start <= range.start && range.start <= range.end && range.end <= end)
+            .then(|| range.start - start..range.end - start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_map_gaps_and_overlap() {
+        // Overlapping chunks, then a gap at 12..14
+        let offsets = [(0, 6), (4, 10), (8, 12), (14, 20)];
+        let map = ChunkMap::new(&offsets);
+        let containing: Vec<_> = [0, 5, 9, 11, 12, 13, 14, 19, 20]
+            .iter()
+            .map(|&offset| map.chunk_containing(offset))
+            .collect();
+        assert_eq!(
+            containing,
+            vec![
+                Some(0),
+                Some(0),
+                Some(1),
+                Some(2),
+                None,
+                None,
+                Some(3),
+                Some(3),
+                None
+            ]
+        );
+        assert_eq!(map.chunks_overlapping(5..9), 0..3);
+        assert_eq!(map.chunks_overlapping(12..14), 3..3);
+        assert_eq!(map.chunks_overlapping(0..100), 0..4);
+        assert!(map.chunks_overlapping(7..7).is_empty());
+        assert_eq!(ChunkMap::new(&[]).chunks_overlapping(0..5), 0..0);
+    }
+
+    #[test]
+    fn test_chunk_map_relative() {
+        let offsets = [(0, 6), (6, 10)];
+        let map = ChunkMap::new(&offsets);
+        assert_eq!(map.to_absolute(1, 0..4), Some(6..10));
+        assert_eq!(map.to_absolute(1, 2..5), None);
+        assert_eq!(map.to_absolute(2, 0..0), None);
+        assert_eq!(map.to_relative(1, 7..9), Some(1..3));
+        assert_eq!(map.to_relative(0, 5..7), None);
+        for (index, &(start, end)) in offsets.iter().enumerate() {
+            let relative = map.to_relative(index, start..end).unwrap();
+            assert_eq!(map.to_absolute(index, relative), Some(start..end));
+        }
+    }
+}
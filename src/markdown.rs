@@ -0,0 +1,472 @@
+//! Markdown-aware chunking.
+//!
+//! This module provides [`MarkdownChunker`], which starts a new chunk at every
+//! ATX heading (`#` .. `######`), packs the blocks of a section up to a target
+//! size, and never splits inside fenced code blocks or tables.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::delim::{DEFAULT_TARGET_SIZE, utf8_split_boundary};
+use crate::tree::ChunkTree;
+
+/// A Markdown chunk with the headings it sits under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MarkdownChunk<'t> {
+    /// Byte offset where the chunk starts.
+    pub start: usize,
+    /// Byte offset where the chunk ends (exclusive).
+    pub end: usize,
+    /// The chunk text, borrowed from the input.
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::serde_bytes::serialize")
+    )]
+    pub text: &'t [u8],
+    /// Enclosing headings joined with `" > "`, e.g. `"# A > ## B"`.
+    ///
+    /// Empty for text before the first heading.
+    pub heading_path: String,
+}
+
+/// A chunker that follows Markdown structure.
+///
+/// - Every heading starts a new chunk. A heading with no body of its own
+///   (e.g. `# A` directly followed by `## B`) stays with the next section.
+/// - Within a section, blocks (paragraphs, fences, tables) are packed greedily
+///   up to the target size.
+/// - A paragraph that doesn't fit is split at line ends, then hard split.
+/// - Fenced code blocks (```` ``` ```` / `~~~`) and tables (lines starting
+///   with `|`) are never split, even if that makes a chunk exceed the target.
+///
+/// # Example
+///
+/// ```
+/// use chunk::MarkdownChunker;
+///
+/// let doc = b"# Guide\n\nIntro.\n\n## Install\n\n```sh\ncargo add chunk\n```\n";
+/// let chunker = MarkdownChunker::new().size(64);
+///
+/// let chunks = chunker.chunks_with_headings(doc);
+/// assert_eq!(chunks[0].text, b"# Guide\n\nIntro.\n\n");
+/// assert_eq!(chunks[1].text, b"## Install\n\n```sh\ncargo add chunk\n```\n");
+/// assert_eq!(chunks[1].heading_path, "# Guide > ## Install");
+/// ```
+#[derive(Debug, Clone)]
+pub struct MarkdownChunker {
+    target_size: usize,
+    utf8_safe: bool,
+}
+
+/// Kind of a top-level Markdown block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    Heading(usize),
+    Fence,
+    Table,
+    Paragraph,
+}
+
+/// A block spanning `start..end`, including any trailing blank lines.
+#[derive(Debug, Clone, Copy)]
+struct Block {
+    kind: BlockKind,
+    start: usize,
+    end: usize,
+}
+
+impl Default for MarkdownChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownChunker {
+    /// Create a Markdown chunker with the default target size.
+    pub fn new() -> Self {
+        Self {
+            target_size: DEFAULT_TARGET_SIZE,
+            utf8_safe: false,
+        }
+    }
+
+    /// Set the target chunk size in bytes.
+    pub fn size(mut self, size: usize) -> Self {
+        self.target_size = size;
+        self
+    }
+
+    /// Never hard split inside a multi-byte UTF-8 sequence.
+    ///
+    /// See [`Chunker::utf8_safe`](crate::Chunker::utf8_safe) for details.
+    pub fn utf8_safe(mut self, enabled: bool) -> Self {
+        self.utf8_safe = enabled;
+        self
+    }
+
+    /// Chunk `text`, returning (start, end) byte offsets.
+    pub fn chunk_offsets(&self, text: &[u8]) -> Vec<(usize, usize)> {
+        let mut offsets = Vec::new();
+        self.walk(text, |start, end, _| offsets.push((start, end)));
+        offsets
+    }
+
+    /// Chunk `text`, returning slices borrowed from it.
+    pub fn chunks<'t>(&self, text: &'t [u8]) -> Vec<&'t [u8]> {
+        self.chunk_offsets(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect()
+    }
+
+    /// Chunk `text`, attaching the heading path of each chunk.
+    pub fn chunks_with_headings<'t>(&self, text: &'t [u8]) -> Vec<MarkdownChunk<'t>> {
+        let mut chunks = Vec::new();
+        self.walk(text, |start, end, path| {
+            chunks.push(MarkdownChunk {
+                start,
+                end,
+                text: &text[start..end],
+                heading_path: heading_path(path),
+            });
+        });
+        chunks
+    }
+
+    /// Chunk `text` into a tree of sections, one per heading, with the
+    /// sections of its subheadings as children.
+    ///
+    /// The chunks are those of
+    /// [`chunks_with_headings`](Self::chunks_with_headings), each in the
+    /// section of the last heading in its path, so a heading with no body
+    /// of its own before a sibling has no section.
+    pub fn chunk_tree<'t>(&self, text: &'t [u8]) -> ChunkTree<MarkdownChunk<'t>> {
+        let mut tree = ChunkTree::default();
+        self.walk(text, |start, end, path| {
+            // Headings inside the chunk open new sections
+            let known = path.iter().take_while(|&&(_, at, _)| at < start).count();
+            let headings = path
+                .iter()
+                .map(|&(level, _, heading)| (level, String::from_utf8_lossy(heading)));
+            let chunk = MarkdownChunk {
+                start,
+                end,
+                text: &text[start..end],
+                heading_path: heading_path(path),
+            };
+            tree.insert(headings, known, chunk);
+        });
+        tree
+    }
+
+    /// Pack blocks into chunks, calling `emit` with each chunk and the
+    /// (level, offset, heading) path it sits under.
+    fn walk<'t, F>(&self, text: &'t [u8], mut emit: F)
+    where
+        F: FnMut(usize, usize, &[(usize, usize, &'t [u8])]),
+    {
+        let size = self.target_size.max(1);
+        let mut path: Vec<(usize, usize, &'t [u8])> = Vec::new();
+        let mut start = 0;
+        let mut end = 0;
+        let mut has_body = false;
+
+        for block in parse_blocks(text) {
+            if let BlockKind::Heading(level) = block.kind {
+                if has_body {
+                    emit(start, end, &path);
+                    start = end;
+                    has_body = false;
+                }
+                path.retain(|&(l, _, _)| l < level);
+                let heading = heading_text(&text[block.start..block.end]);
+                path.push((level, block.start, heading));
+                end = block.end;
+                continue;
+            }
+
+            if block.end - start <= size {
+                end = block.end;
+                has_body = true;
+                continue;
+            }
+            if has_body {
+                emit(start, end, &path);
+                start = end;
+            }
+
+            match block.kind {
+                _ if block.end - start <= size => {}
+                BlockKind::Fence | BlockKind::Table => {
+                    // Atomic: emit whole, even if oversized
+                    emit(start, block.end, &path);
+                    start = block.end;
+                }
+                _ => {
+                    while block.end - start > size {
+                        let split = self.paragraph_split(text, start, block.start, start + size);
+                        emit(start, split, &path);
+                        start = split;
+                    }
+                }
+            }
+            end = block.end;
+            has_body = end > start;
+        }
+
+        if end > start {
+            emit(start, end, &path);
+        }
+    }
+
+    /// Split point for a paragraph starting at `block_start` in a chunk that
+    /// starts at `start` and may not extend past `limit`.
+    fn paragraph_split(
+        &self,
+        text: &[u8],
+        start: usize,
+        block_start: usize,
+        limit: usize,
+    ) -> usize {
+        // Leading headings already fill the chunk: close it before the paragraph
+        if limit <= block_start {
+            return block_start;
+        }
+        let from = start.max(block_start);
+        match memchr::memrchr(b'\n', &text[from..limit]) {
+            Some(i) => from + i + 1,
+            None if self.utf8_safe => utf8_split_boundary(text, start, limit),
+            None => limit,
+        }
+    }
+}
+
+/// Headings in `path` joined with `" > "`.
+fn heading_path(path: &[(usize, usize, &[u8])]) -> String {
+    path.iter()
+        .map(|&(_, _, heading)| String::from_utf8_lossy(heading))
+        .collect::<Vec<_>>()
+        .join(" > ")
+}
+
+/// Split `text` into top-level blocks covering it exactly.
+fn parse_blocks(text: &[u8]) -> Vec<Block> {
+    let mut blocks: Vec<Block> = Vec::new();
+    // Open fence: (fence byte, run length)
+    let mut fence: Option<(u8, usize)> = None;
+    // Whether the last line was blank (ends paragraphs and tables)
+    let mut after_blank = false;
+    let mut pos = 0;
+
+    while pos < text.len() {
+        let line_end = memchr::memchr(b'\n', &text[pos..]).map_or(text.len(), |i| pos + i + 1);
+        let line = &text[pos..line_end];
+
+        let blank = fence.is_none() && line.iter().all(u8::is_ascii_whitespace);
+        let kind = if let Some((ch, len)) = fence {
+            if fence_run(line).is_some_and(|(c, n, rest)| {
+                c == ch && n >= len && rest.iter().all(u8::is_ascii_whitespace)
+            }) {
+                fence = None;
+            }
+            None
+        } else if let Some((ch, len, _)) = fence_run(line) {
+            fence = Some((ch, len));
+            Some(BlockKind::Fence)
+        } else if blank {
+            None
+        } else if let Some(level) = heading_level(line) {
+            Some(BlockKind::Heading(level))
+        } else if strip_indent(line).is_some_and(|l| l.starts_with(b"|")) {
+            Some(BlockKind::Table)
+        } else {
+            Some(BlockKind::Paragraph)
+        };
+
+        match (kind, blocks.last_mut()) {
+            // Fence bodies, blank lines and continuation lines extend the
+            // current block; headings and fences always start a new one
+            (None, Some(last)) => last.end = line_end,
+            (Some(k), Some(last))
+                if k == last.kind
+                    && matches!(k, BlockKind::Paragraph | BlockKind::Table)
+                    && !after_blank =>
+            {
+                last.end = line_end
+            }
+            (kind, _) => blocks.push(Block {
+                kind: kind.unwrap_or(BlockKind::Paragraph),
+                start: pos,
+                end: line_end,
+            }),
+        }
+        after_blank = blank;
+        pos = line_end;
+    }
+    blocks
+}
+
+/// Strip up to three spaces of indentation; `None` for indented code.
+fn strip_indent(line: &[u8]) -> Option<&[u8]> {
+    let indent = line.iter().take(4).take_while(|&&b| b == b' ').count();
+    (indent < 4).then(|| &line[indent..])
+}
+
+/// Parse an opening/closing code fence: (fence byte, run length, rest of line).
+fn fence_run(line: &[u8]) -> Option<(u8, usize, &[u8])> {
+    let line = strip_indent(line)?;
+    let ch = *line.first().filter(|&&b| b == b'`' || b == b'~')?;
+    let run = line.iter().take_while(|&&b| b == ch).count();
+    (run >= 3).then(|| (ch, run, &line[run..]))
+}
+
+/// Level of an ATX heading line, if it is one.
+fn heading_level(line: &[u8]) -> Option<usize> {
+    let line = strip_indent(line)?;
+    let level = line.iter().take_while(|&&b| b == b'#').count();
+    let after = line.get(level).copied();
+    ((1..=6).contains(&level) && after.is_none_or(|b| b.is_ascii_whitespace())).then_some(level)
+}
+
+/// Heading line without indentation, trailing whitespace or closing hashes.
+fn heading_text(line: &[u8]) -> &[u8] {
+    let line = strip_indent(line).unwrap_or(line).trim_ascii_end();
+    // A closing sequence is a run of `#` preceded by whitespace
+    let body_end = line.iter().rposition(|&b| b != b'#').map_or(0, |i| i + 1);
+    if body_end > 0 && body_end < line.len() && line[body_end - 1].is_ascii_whitespace() {
+        line[..body_end].trim_ascii_end()
+    } else {
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_splits_at_headings() {
+        let doc = b"# A\n\none\n\n# B\n\ntwo\n";
+        let chunker = MarkdownChunker::new();
+        assert_eq!(
+            chunker.chunks(doc),
+            vec![&b"# A\n\none\n\n"[..], &b"# B\n\ntwo\n"[..]]
+        );
+    }
+
+    #[test]
+    fn test_markdown_heading_path() {
+        let doc = b"pre\n# A\n## B\ntext\n### C ###\nmore\n## D\nend";
+        let chunks = MarkdownChunker::new().chunks_with_headings(doc);
+        let paths: Vec<&str> = chunks.iter().map(|c| c.heading_path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["", "# A > ## B", "# A > ## B > ### C", "# A > ## D"]
+        );
+        // A heading with no body joins the next section
+        assert_eq!(chunks[1].text, b"# A\n## B\ntext\n");
+    }
+
+    #[test]
+    fn test_markdown_chunk_tree() {
+        let doc = b"pre\n# A\n## B\ntext\n### C\nmore\n## D\nend\n\nlast\n## D\nagain\n";
+        let chunker = MarkdownChunker::new().size(8);
+        let tree = chunker.chunk_tree(doc);
+        assert_eq!(tree.chunks[0].text, b"pre\n");
+        let a = &tree.children[0];
+        assert_eq!((a.heading.as_str(), a.level), ("# A", 1));
+        // The bare `# A` heading is in the first chunk of `## B`
+        assert!(a.chunks.is_empty());
+        let sections: Vec<_> = a
+            .children
+            .iter()
+            .map(|s| (s.heading.as_str(), s.chunks.len(), s.children.len()))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![("## B", 2, 1), ("## D", 2, 0), ("## D", 2, 0)]
+        );
+        assert_eq!(
+            a.children[0].children[0].chunks[0].heading_path,
+            "# A > ## B > ### C"
+        );
+
+        let flat: Vec<_> = tree.flatten().into_iter().cloned().collect();
+        assert_eq!(flat, chunker.chunks_with_headings(doc));
+        assert!(chunker.chunk_tree(b"").is_empty());
+    }
+
+    #[test]
+    fn test_markdown_never_splits_fences() {
+        let doc = b"# Code\n\n```\nline one\n\n# not a heading\nline two\n```\nafter\n";
+        let chunker = MarkdownChunker::new().size(16);
+        let chunks = chunker.chunks(doc);
+        assert_eq!(
+            chunks[0],
+            b"# Code\n\n```\nline one\n\n# not a heading\nline two\n```\n"
+        );
+        assert_eq!(chunks[1], b"after\n");
+    }
+
+    #[test]
+    fn test_markdown_tilde_fence_needs_matching_close() {
+        let doc = b"~~~~\n```\n# inside\n~~~~\n# Out\n";
+        let chunks = MarkdownChunker::new().chunks(doc);
+        assert_eq!(
+            chunks,
+            vec![&b"~~~~\n```\n# inside\n~~~~\n"[..], &b"# Out\n"[..]]
+        );
+    }
+
+    #[test]
+    fn test_markdown_never_splits_tables() {
+        let doc = b"| a | b |\n|---|---|\n| 1 | 2 |\n\ntext\n";
+        let chunks = MarkdownChunker::new().size(10).chunks(doc);
+        assert_eq!(chunks[0], b"| a | b |\n|---|---|\n| 1 | 2 |\n\n");
+        assert_eq!(chunks[1], b"text\n");
+    }
+
+    #[test]
+    fn test_markdown_packs_paragraphs() {
+        let doc = b"one\n\ntwo\n\nthree\n\nfour\n";
+        let chunks = MarkdownChunker::new().size(11).chunks(doc);
+        assert_eq!(
+            chunks,
+            vec![&b"one\n\ntwo\n\n"[..], &b"three\n\n"[..], &b"four\n"[..]]
+        );
+    }
+
+    #[test]
+    fn test_markdown_long_paragraph_splits_at_lines() {
+        let doc = b"# H\naaaa\nbbbb\ncccccccccc";
+        let chunks = MarkdownChunker::new().size(10).chunks(doc);
+        assert_eq!(chunks[0], b"# H\naaaa\n");
+        assert_eq!(chunks[1], b"bbbb\n");
+        assert_eq!(chunks[2], b"cccccccccc");
+        assert_eq!(chunks.concat(), doc.to_vec());
+    }
+
+    #[test]
+    fn test_markdown_hard_split_utf8_safe() {
+        let doc = "éééééééé".as_bytes();
+        let chunker = MarkdownChunker::new().size(5).utf8_safe(true);
+        let chunks = chunker.chunks(doc);
+        assert!(chunks.iter().all(|c| std::str::from_utf8(c).is_ok()));
+        assert_eq!(chunks.concat(), doc.to_vec());
+    }
+
+    #[test]
+    fn test_markdown_indented_hash_is_code() {
+        assert_eq!(heading_level(b"    # code"), None);
+        assert_eq!(heading_level(b"#hashtag"), None);
+        assert_eq!(heading_level(b"### Title"), Some(3));
+        assert_eq!(heading_text(b"## B ##\n"), b"## B");
+        assert_eq!(heading_text(b"## C#\n"), b"## C#");
+    }
+
+    #[test]
+    fn test_markdown_empty() {
+        assert!(MarkdownChunker::new().chunk_offsets(b"").is_empty());
+    }
+}
@@ -2,7 +2,12 @@
 //!
 //! This module provides functions to merge text segments based on token counts,
 //! equivalent to Chonkie's Cython `merge.pyx`. Used by RecursiveChunker and
-//! other chunkers that need to respect token limits.
+//! other chunkers that need to respect token limits. [`merge_chunks`] does the
+//! same for chunk offsets, by byte length.
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// Find merge indices for combining segments within token limits.
 ///
@@ -96,6 +101,7 @@ fn compute_merged_token_counts(token_counts: &[usize], merge_indices: &[usize])
 
 /// Result of merge_splits operation.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MergeResult {
     /// Merged text segments
     pub merged: Vec<String>,
@@ -171,6 +177,36 @@ pub fn merge_splits(splits: &[&str], token_counts: &[usize], chunk_size: usize)
     }
 }
 
+/// Coalesce runs of adjacent chunks into chunks of at most `max_size` bytes.
+///
+/// A second pass over fine-grained chunks (e.g. sentences): each merged chunk
+/// spans from the start of its first chunk to the end of its last, taking in
+/// any gap between them (e.g. whitespace left out by trimming), and chunks
+/// are added to it in order while that span fits. A chunk longer than
+/// `max_size` on its own is kept as it is. Overlapping chunks merge into
+/// their union.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{chunk, merge_chunks};
+///
+/// let text = b"One. Two. Three. Four.";
+/// let sentences = chunk(text).size(8).delimiters(b".").collect_offsets();
+/// assert_eq!(sentences, vec![(0, 4), (4, 9), (9, 16), (16, 22)]);
+/// assert_eq!(merge_chunks(&sentences, 10), vec![(0, 9), (9, 16), (16, 22)]);
+/// ```
+pub fn merge_chunks(offsets: &[(usize, usize)], max_size: usize) -> Vec<(usize, usize)> {
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(offsets.len());
+    for &(start, end) in offsets {
+        match merged.last_mut() {
+            Some(last) if end.max(last.1) - last.0 <= max_size => last.1 = end.max(last.1),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,6 +246,26 @@ mod tests {
         assert_eq!(indices, vec![1, 2, 3]);
     }
 
+    #[test]
+    fn test_merge_chunks() {
+        let offsets = [(0, 3), (3, 6), (6, 9), (9, 20), (20, 22), (22, 24)];
+        assert_eq!(
+            merge_chunks(&offsets, 6),
+            vec![(0, 6), (6, 9), (9, 20), (20, 24)]
+        );
+        // Gaps count toward the size, overlaps don't count twice
+        assert_eq!(
+            merge_chunks(&[(0, 3), (5, 8), (9, 10)], 8),
+            vec![(0, 8), (9, 10)]
+        );
+        assert_eq!(
+            merge_chunks(&[(0, 5), (3, 8), (6, 12)], 8),
+            vec![(0, 8), (6, 12)]
+        );
+        assert!(merge_chunks(&[], 10).is_empty());
+        assert_eq!(merge_chunks(&[(0, 4), (4, 8)], 0), vec![(0, 4), (4, 8)]);
+    }
+
     #[test]
     fn test_merge_splits_basic() {
         let splits = vec!["a", "b", "c", "d", "e", "f", "g"];
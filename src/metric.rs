@@ -0,0 +1,352 @@
+//! Pluggable measures of chunk size.
+//!
+//! By default chunk sizes are counted in bytes. A [`SizeMetric`] lets the
+//! chunkers count in other units instead — Unicode characters, or tokens from
+//! any counting closure (or a HuggingFace tokenizer with the `tokenizers`
+//! feature) — so chunks line up with LLM context limits. [`count_tokens`]
+//! and [`estimate_tokens`] measure text the same way on its own, e.g. to
+//! budget a prompt.
+
+/// A measure of how "big" a piece of text is.
+///
+/// Implementations must be monotonic: extending a slice never makes it
+/// smaller. Closures `Fn(&[u8]) -> usize` implement this trait, so token
+/// counters can be plugged in directly.
+///
+/// # Example
+///
+/// ```
+/// use chunk::chunk;
+///
+/// // Count whitespace-separated words as "tokens"
+/// let words = |s: &[u8]| s.split(|b| b.is_ascii_whitespace()).filter(|w| !w.is_empty()).count();
+///
+/// let text = b"one two three. four five six. seven";
+/// let chunks: Vec<_> = chunk(text).size(3).delimiters(b".").size_metric(words).collect();
+/// assert_eq!(chunks[0], b"one two three.");
+/// ```
+pub trait SizeMetric {
+    /// Size of `text` in this metric's units.
+    fn measure(&self, text: &[u8]) -> usize;
+
+    /// Largest `end` such that `text[start..end]` measures at most `limit`.
+    ///
+    /// Returns `text.len()` if the whole remainder fits. The default
+    /// implementation gallops then binary searches using [`measure`](Self::measure),
+    /// assuming every unit spans at least one byte.
+    fn window_end(&self, text: &[u8], start: usize, limit: usize) -> usize {
+        search_window_end(self, text, start, limit)
+    }
+}
+
+/// The default [`SizeMetric::window_end`]: gallop then binary search on
+/// `metric.measure`.
+fn search_window_end<M: SizeMetric + ?Sized>(
+    metric: &M,
+    text: &[u8],
+    start: usize,
+    limit: usize,
+) -> usize {
+    let len = text.len();
+
+    // Gallop to find an end that no longer fits
+    let mut lo = start;
+    let mut hi = (start + limit.max(1)).min(len);
+    while metric.measure(&text[start..hi]) <= limit {
+        if hi == len {
+            return len;
+        }
+        lo = hi;
+        hi = (start + (hi - start) * 2).min(len);
+    }
+
+    // Binary search for the last end that fits in (lo, hi]
+    while lo + 1 < hi {
+        let mid = lo + (hi - lo) / 2;
+        if metric.measure(&text[start..mid]) <= limit {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Size in bytes (the default).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bytes;
+
+impl SizeMetric for Bytes {
+    #[inline]
+    fn measure(&self, text: &[u8]) -> usize {
+        text.len()
+    }
+
+    #[inline]
+    fn window_end(&self, text: &[u8], start: usize, limit: usize) -> usize {
+        start.saturating_add(limit).min(text.len())
+    }
+}
+
+/// Size in Unicode scalar values (characters) of UTF-8 text.
+///
+/// Invalid UTF-8 is counted one character per non-continuation byte.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Chars;
+
+impl SizeMetric for Chars {
+    #[inline]
+    fn measure(&self, text: &[u8]) -> usize {
+        text.iter().filter(|&&b| (b & 0xC0) != 0x80).count()
+    }
+
+    fn window_end(&self, text: &[u8], start: usize, limit: usize) -> usize {
+        // The end is the start of character number `limit` (0-based)
+        let mut chars = 0;
+        for (i, &b) in text[start..].iter().enumerate() {
+            if (b & 0xC0) != 0x80 {
+                if chars == limit {
+                    return start + i;
+                }
+                chars += 1;
+            }
+        }
+        text.len()
+    }
+}
+
+/// Built-in units for the target size, see [`Chunker::size_unit`](crate::Chunker::size_unit).
+///
+/// With the `tiktoken` feature, sizes can also be counted in the tokens of
+/// OpenAI's encodings, exactly as tiktoken's `encode_ordinary` counts them.
+/// The vocabularies are compiled in, and each is loaded on first use.
+///
+/// With the `serde` feature the units are `"bytes"`, `"chars"`,
+/// `"cl100k_base"` and `"o200k_base"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum SizeUnit {
+    /// Count bytes (default).
+    #[default]
+    Bytes,
+    /// Count Unicode scalar values of UTF-8 text.
+    Chars,
+    /// Count tokens of the `cl100k_base` encoding (GPT-4, GPT-3.5 and the
+    /// `text-embedding-3` models).
+    #[cfg(feature = "tiktoken")]
+    #[cfg_attr(feature = "serde", serde(rename = "cl100k_base"))]
+    Cl100kBase,
+    /// Count tokens of the `o200k_base` encoding (GPT-4o and later models).
+    #[cfg(feature = "tiktoken")]
+    #[cfg_attr(feature = "serde", serde(rename = "o200k_base"))]
+    O200kBase,
+}
+
+/// Tokens in UTF-8 text (decoded lossily) under a tiktoken encoding.
+#[cfg(feature = "tiktoken")]
+fn bpe_tokens(bpe: &tiktoken_rs::CoreBPE, text: &[u8]) -> usize {
+    bpe.encode_ordinary(&String::from_utf8_lossy(text)).len()
+}
+
+impl SizeMetric for SizeUnit {
+    #[inline]
+    fn measure(&self, text: &[u8]) -> usize {
+        match self {
+            SizeUnit::Bytes => Bytes.measure(text),
+            SizeUnit::Chars => Chars.measure(text),
+            #[cfg(feature = "tiktoken")]
+            SizeUnit::Cl100kBase => bpe_tokens(tiktoken_rs::cl100k_base_singleton(), text),
+            #[cfg(feature = "tiktoken")]
+            SizeUnit::O200kBase => bpe_tokens(tiktoken_rs::o200k_base_singleton(), text),
+        }
+    }
+
+    #[inline]
+    fn window_end(&self, text: &[u8], start: usize, limit: usize) -> usize {
+        match self {
+            SizeUnit::Bytes => Bytes.window_end(text, start, limit),
+            SizeUnit::Chars => Chars.window_end(text, start, limit),
+            #[cfg(feature = "tiktoken")]
+            _ => search_window_end(self, text, start, limit),
+        }
+    }
+}
+
+impl<F: Fn(&[u8]) -> usize> SizeMetric for F {
+    #[inline]
+    fn measure(&self, text: &[u8]) -> usize {
+        self(text)
+    }
+}
+
+/// Tokens in `text` as counted by `tokenizer`, any [`SizeMetric`]: a
+/// counting closure, or a HuggingFace `Tokenizer` with the `tokenizers`
+/// feature.
+///
+/// This is the count [`Chunker::size_metric`](crate::Chunker::size_metric)
+/// sizes chunks by, so a prompt built from chunks can be budgeted with the
+/// same tokenizer.
+///
+/// # Example
+///
+/// ```
+/// use chunk::count_tokens;
+///
+/// let words = |s: &[u8]| s.split(|b| b.is_ascii_whitespace()).filter(|w| !w.is_empty()).count();
+/// assert_eq!(count_tokens(b"one two three", &words), 3);
+/// ```
+pub fn count_tokens<M: SizeMetric + ?Sized>(text: &[u8], tokenizer: &M) -> usize {
+    tokenizer.measure(text)
+}
+
+/// A fast estimate of the tokens in `text`, without a tokenizer: the larger
+/// of one token per 4 bytes and 1.3 tokens per whitespace-separated word,
+/// rounded up.
+///
+/// Close to BPE counts for English prose; text in other scripts or code may
+/// count higher with a real tokenizer, so leave some headroom.
+///
+/// # Example
+///
+/// ```
+/// use chunk::estimate_tokens;
+///
+/// // 26 bytes make 7 tokens, and so do 5 words
+/// assert_eq!(estimate_tokens(b"Hello, world! How are you?"), 7);
+/// // Short words count by words: 10 of them make 13 tokens
+/// assert_eq!(estimate_tokens(b"a b c d e f g h i j"), 13);
+/// ```
+pub fn estimate_tokens(text: &[u8]) -> usize {
+    let words = text
+        .split(|b| b.is_ascii_whitespace())
+        .filter(|word| !word.is_empty())
+        .count();
+    text.len().div_ceil(4).max((words * 13).div_ceil(10))
+}
+
+/// Token counts from a HuggingFace tokenizer (requires the `tokenizers` feature).
+///
+/// Text is decoded as UTF-8 (lossily) and encoded without special tokens.
+#[cfg(feature = "tokenizers")]
+impl SizeMetric for tokenizers::Tokenizer {
+    fn measure(&self, text: &[u8]) -> usize {
+        let text = String::from_utf8_lossy(text);
+        self.encode(text.as_ref(), false)
+            .map(|encoding| encoding.len())
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_window_end() {
+        assert_eq!(Bytes.window_end(b"hello world", 2, 4), 6);
+        assert_eq!(Bytes.window_end(b"hello", 2, 10), 5);
+    }
+
+    #[test]
+    fn test_chars_measure_and_window_end() {
+        let text = "héllo".as_bytes();
+        assert_eq!(Chars.measure(text), 5);
+        assert_eq!(Chars.window_end(text, 0, 2), 3);
+        assert_eq!(Chars.window_end(text, 0, 5), text.len());
+        assert_eq!(Chars.window_end(text, 1, 1), 3);
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(b""), 0);
+        assert_eq!(estimate_tokens(b"   "), 1);
+        assert_eq!(estimate_tokens(b"word"), 2);
+        assert_eq!(estimate_tokens(&[b'x'; 400]), 100);
+        // Extending the text never lowers the estimate
+        let text = b"The quick brown fox jumps over the lazy dog.";
+        for end in 1..=text.len() {
+            assert!(estimate_tokens(&text[..end]) >= estimate_tokens(&text[..end - 1]));
+        }
+        let words = |s: &[u8]| s.split(|&b| b == b' ').count();
+        assert_eq!(count_tokens(text, &words), 9);
+    }
+
+    #[test]
+    fn test_default_window_end_matches_linear_scan() {
+        let words = |s: &[u8]| s.split(|&b| b == b' ').filter(|w| !w.is_empty()).count();
+        let text = b"a bb ccc dddd eeeee ffffff ggggggg";
+        for start in [0, 2, 5] {
+            for limit in 1..8 {
+                let end = words.window_end(text, start, limit);
+                let expected = (start..=text.len())
+                    .rev()
+                    .find(|&e| words(&text[start..e]) <= limit)
+                    .unwrap();
+                assert_eq!(end, expected, "start={start} limit={limit}");
+            }
+        }
+    }
+
+    #[cfg(feature = "tiktoken")]
+    #[test]
+    fn test_tiktoken_units() {
+        assert_eq!(SizeUnit::Cl100kBase.measure(b"hello world"), 2);
+        assert_eq!(SizeUnit::O200kBase.measure(b"hello world"), 2);
+        assert_eq!(SizeUnit::Cl100kBase.measure(b""), 0);
+
+        let text = b"One sentence here. Another sentence there. A third one.";
+        let chunks: Vec<_> = crate::chunk(text)
+            .size(5)
+            .delimiters(b".")
+            .size_unit(SizeUnit::Cl100kBase)
+            .collect();
+        assert_eq!(
+            chunks,
+            vec![
+                &b"One sentence here."[..],
+                b" Another sentence there.",
+                b" A third one."
+            ]
+        );
+        for chunk in chunks {
+            assert!(SizeUnit::Cl100kBase.measure(chunk) <= 5);
+        }
+    }
+
+    #[cfg(feature = "tokenizers")]
+    #[test]
+    fn test_tokenizer_metric() {
+        use tokenizers::Tokenizer;
+        use tokenizers::models::wordlevel::WordLevel;
+        use tokenizers::pre_tokenizers::whitespace::Whitespace;
+
+        let vocab = [("[UNK]", 0), ("one", 1), ("two", 2), ("three", 3), (".", 4)]
+            .into_iter()
+            .map(|(w, id)| (w.to_string(), id))
+            .collect();
+        let model = WordLevel::builder()
+            .vocab(vocab)
+            .unk_token("[UNK]".to_string())
+            .build()
+            .unwrap();
+        let mut tokenizer = Tokenizer::new(model);
+        tokenizer.with_pre_tokenizer(Some(Whitespace {}));
+
+        assert_eq!(tokenizer.measure(b"one two three."), 4);
+
+        let text = b"one two. three one. two three.";
+        let chunks: Vec<_> = crate::chunk(text)
+            .size(4)
+            .delimiters(b".")
+            .size_metric(tokenizer)
+            .collect();
+        assert_eq!(
+            chunks,
+            vec![b"one two.".as_slice(), b" three one.", b" two three."]
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Memory-mapped file chunking (requires the `mmap` feature).
+//!
+//! This module provides [`MappedText`], a read-only memory map of a file that
+//! hands out borrowed [`Chunker`]s, so huge corpora are chunked straight from
+//! the page cache without being copied into RAM.
+
+use std::fs::File;
+use std::io;
+use std::ops::Deref;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::chunk::Chunker;
+
+impl Chunker<'_> {
+    /// Memory-map the file at `path` for zero-copy chunking.
+    ///
+    /// Call [`MappedText::chunker`] on the result to get a [`Chunker`] that
+    /// yields slices borrowed straight from the mapping.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chunk::Chunker;
+    ///
+    /// let mapped = Chunker::from_mmap("corpus.txt")?;
+    /// let offsets = mapped.chunker().size(4096).collect_offsets();
+    /// for chunk in mapped.chunker().size(4096) {
+    ///     // chunk: &[u8] pointing into the mapped file
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn from_mmap<P: AsRef<Path>>(path: P) -> io::Result<MappedText> {
+        MappedText::open(path)
+    }
+}
+
+/// A read-only memory-mapped file.
+///
+/// The file must not be truncated or modified while it is mapped; doing so is
+/// undefined behavior on most platforms.
+pub struct MappedText {
+    mmap: Mmap,
+}
+
+impl MappedText {
+    /// Memory-map the file at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only; callers are documented not to
+        // modify the file while it is mapped.
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(Self { mmap })
+    }
+
+    /// Create a borrowed chunker over the mapped bytes.
+    pub fn chunker(&self) -> Chunker<'_> {
+        crate::chunk::chunk(&self.mmap)
+    }
+
+    /// Get the mapped bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+}
+
+impl Deref for MappedText {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.mmap
+    }
+}
+
+impl AsRef<[u8]> for MappedText {
+    fn as_ref(&self) -> &[u8] {
+        &self.mmap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk;
+
+    fn temp_file(name: &str, contents: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("chunk-{}-{}.txt", name, std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_mmap_matches_in_memory() {
+        let text = b"Hello. World. Test. Another sentence here.";
+        let path = temp_file("mmap", text);
+
+        let mapped = Chunker::from_mmap(&path).unwrap();
+        let chunks: Vec<&[u8]> = mapped.chunker().size(10).delimiters(b".").collect();
+        let expected: Vec<&[u8]> = chunk(text).size(10).delimiters(b".").collect();
+        assert_eq!(chunks, expected);
+        assert_eq!(
+            mapped.chunker().size(10).delimiters(b".").collect_offsets(),
+            chunk(text).size(10).delimiters(b".").collect_offsets()
+        );
+
+        drop(mapped);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mmap_empty_file() {
+        let path = temp_file("mmap-empty", b"");
+        let mapped = Chunker::from_mmap(&path).unwrap();
+        assert!(mapped.is_empty());
+        assert_eq!(mapped.chunker().count(), 0);
+        drop(mapped);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mmap_missing_file() {
+        assert!(Chunker::from_mmap("/nonexistent/chunk/input.txt").is_err());
+    }
+}
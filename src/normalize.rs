@@ -0,0 +1,350 @@
+//! Text normalization before chunking.
+//!
+//! This module provides [`Normalizer`], which strips a UTF-8 byte order mark,
+//! turns `\r\n` (and lone `\r`) line endings into `\n` and, with the `nfc`
+//! feature, applies Unicode NFC. The [`Normalized`] text remembers where its
+//! bytes came from, so chunk offsets can be reported into either the
+//! original or the normalized text.
+
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::config::ChunkerConfig;
+
+/// The UTF-8 byte order mark.
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Which text the offsets from [`Normalized::chunk_offsets`] point into.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum OffsetSpace {
+    /// Offsets into the text as it was given.
+    #[default]
+    Original,
+    /// Offsets into [`Normalized::text`].
+    Normalized,
+}
+
+/// Normalizes text before chunking.
+///
+/// By default the byte order mark is stripped and line endings are
+/// normalized; NFC is off (and needs the `nfc` feature).
+///
+/// # Example
+///
+/// ```
+/// use chunk::{ChunkerConfig, Normalizer, OffsetSpace};
+///
+/// let text = b"\xef\xbb\xbfOne.\r\nTwo.\r\n";
+/// let normalized = Normalizer::new().normalize(text);
+/// assert_eq!(normalized.text(), b"One.\nTwo.\n");
+///
+/// let config = ChunkerConfig::new().size(5).delimiters(b"\n");
+/// let offsets = normalized.chunk_offsets(&config, OffsetSpace::Normalized);
+/// assert_eq!(offsets, vec![(0, 5), (5, 10)]);
+/// // The same chunks in the original, keeping each `\r\n` together
+/// let offsets = normalized.chunk_offsets(&config, OffsetSpace::Original);
+/// assert_eq!(offsets, vec![(3, 9), (9, 15)]);
+/// assert_eq!(&text[3..9], b"One.\r\n");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Normalizer {
+    strip_bom: bool,
+    line_endings: bool,
+    #[cfg(feature = "nfc")]
+    nfc: bool,
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Normalizer {
+    /// Create a normalizer that strips the byte order mark and normalizes
+    /// line endings.
+    pub fn new() -> Self {
+        Self {
+            strip_bom: true,
+            line_endings: true,
+            #[cfg(feature = "nfc")]
+            nfc: false,
+        }
+    }
+
+    /// Strip a leading UTF-8 byte order mark.
+    pub fn strip_bom(mut self, enabled: bool) -> Self {
+        self.strip_bom = enabled;
+        self
+    }
+
+    /// Turn `\r\n` and lone `\r` line endings into `\n`.
+    pub fn line_endings(mut self, enabled: bool) -> Self {
+        self.line_endings = enabled;
+        self
+    }
+
+    /// Apply Unicode NFC (canonical composition), so e.g. `e` followed by a
+    /// combining acute accent becomes `é`.
+    ///
+    /// Bytes that aren't valid UTF-8 are kept as they are.
+    #[cfg(feature = "nfc")]
+    pub fn nfc(mut self, enabled: bool) -> Self {
+        self.nfc = enabled;
+        self
+    }
+
+    /// Normalize `text`.
+    ///
+    /// The result borrows `text` when there's nothing to change.
+    pub fn normalize<'t>(&self, text: &'t [u8]) -> Normalized<'t> {
+        let bom = if self.strip_bom && text.starts_with(BOM) {
+            BOM.len()
+        } else {
+            0
+        };
+        let body = &text[bom..];
+        let unchanged =
+            (!self.line_endings || memchr::memchr(b'\r', body).is_none()) && !self.needs_nfc(body);
+        if unchanged {
+            return Normalized {
+                original: text,
+                text: Cow::Borrowed(body),
+                anchors: vec![(0, bom)],
+            };
+        }
+
+        let mut out = Output {
+            text: Vec::with_capacity(body.len()),
+            anchors: vec![(0, bom)],
+        };
+        let mut start = bom;
+        while start < text.len() {
+            let cr = if self.line_endings {
+                memchr::memchr(b'\r', &text[start..]).map_or(text.len(), |i| start + i)
+            } else {
+                text.len()
+            };
+            self.push_segment(&mut out, text, start, cr);
+            if cr == text.len() {
+                break;
+            }
+            let end = cr + 1 + (text.get(cr + 1) == Some(&b'\n')) as usize;
+            out.replace(cr, end, b"\n");
+            start = end;
+        }
+        Normalized {
+            original: text,
+            text: Cow::Owned(out.text),
+            anchors: out.anchors,
+        }
+    }
+
+    /// Whether NFC would change `text`.
+    fn needs_nfc(&self, text: &[u8]) -> bool {
+        #[cfg(feature = "nfc")]
+        if self.nfc {
+            return text
+                .utf8_chunks()
+                .any(|chunk| !unicode_normalization_alignments::is_nfc(chunk.valid()));
+        }
+        let _ = text;
+        false
+    }
+
+    /// Push `text[start..end]`, which has no line endings to normalize.
+    fn push_segment(&self, out: &mut Output, text: &[u8], start: usize, end: usize) {
+        let segment = &text[start..end];
+        #[cfg(feature = "nfc")]
+        if self.needs_nfc(segment) {
+            push_nfc(out, segment, start);
+            return;
+        }
+        out.copy(start, segment);
+    }
+}
+
+/// Push the NFC form of `segment`, which starts at original offset `start`.
+#[cfg(feature = "nfc")]
+fn push_nfc(out: &mut Output, segment: &[u8], start: usize) {
+    use unicode_normalization_alignments::UnicodeNormalization;
+
+    let mut pos = start;
+    for chunk in segment.utf8_chunks() {
+        let valid = chunk.valid();
+        let end = pos + valid.len();
+        let mut chars = valid.char_indices().map(|(i, c)| (pos + i, c)).peekable();
+        for (c, change) in valid.nfc() {
+            // NFC reports each output char's change in char count: +1 for
+            // an inserted char, -n for one replacing n + 1
+            let consumed = (1 - change).max(0) as usize;
+            let from = chars.peek().map_or(end, |&(i, _)| i);
+            let original = chars.next_if(|_| consumed > 0).map(|(_, c)| c);
+            for _ in 1..consumed {
+                chars.next();
+            }
+            let to = chars.peek().map_or(end, |&(i, _)| i);
+            let mut buf = [0; 4];
+            let encoded = c.encode_utf8(&mut buf).as_bytes();
+            if consumed == 1 && original == Some(c) {
+                out.copy(from, encoded);
+            } else {
+                out.replace(from, to, encoded);
+            }
+        }
+        out.copy(end, chunk.invalid());
+        pos = end + chunk.invalid().len();
+    }
+}
+
+/// Normalized text under construction.
+struct Output {
+    text: Vec<u8>,
+    anchors: Vec<(usize, usize)>,
+}
+
+impl Output {
+    /// Push `bytes`, an unchanged copy of the original at `from`.
+    fn copy(&mut self, from: usize, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+        self.anchor(from);
+        self.text.extend_from_slice(bytes);
+    }
+
+    /// Push `bytes` in place of the original `from..to`.
+    fn replace(&mut self, from: usize, to: usize, bytes: &[u8]) {
+        self.anchor(from);
+        self.text.extend_from_slice(bytes);
+        if bytes.len() != to - from {
+            self.anchor(to);
+        }
+    }
+
+    /// Map the current end of the text to the original offset `original`.
+    fn anchor(&mut self, original: usize) {
+        let here = self.text.len();
+        let &(last, last_original) = self.anchors.last().expect("starts with an anchor");
+        if last == here {
+            self.anchors.pop();
+        } else if last_original + (here - last) == original {
+            return;
+        }
+        self.anchors.push((here, original));
+    }
+}
+
+/// Text normalized by a [`Normalizer`], with a map back to the original.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalized<'t> {
+    original: &'t [u8],
+    text: Cow<'t, [u8]>,
+    /// (normalized, original) offsets where the two stop moving together,
+    /// sorted, starting at normalized offset 0.
+    anchors: Vec<(usize, usize)>,
+}
+
+impl<'t> Normalized<'t> {
+    /// The normalized text.
+    pub fn text(&self) -> &[u8] {
+        &self.text
+    }
+
+    /// The text as it was given.
+    pub fn original(&self) -> &'t [u8] {
+        self.original
+    }
+
+    /// Whether normalizing changed anything.
+    pub fn is_changed(&self) -> bool {
+        matches!(self.text, Cow::Owned(_)) || self.anchors[0].1 > 0
+    }
+
+    /// The original offset of normalized offset `offset`.
+    ///
+    /// Offset 0 maps past a stripped byte order mark. Offsets inside a
+    /// replaced run (a line ending or a recomposed character) map to at
+    /// most its original end.
+    pub fn to_original(&self, offset: usize) -> usize {
+        let i = self.anchors.partition_point(|&(n, _)| n <= offset) - 1;
+        let (normalized, original) = self.anchors[i];
+        let limit = self
+            .anchors
+            .get(i + 1)
+            .map_or(self.original.len(), |&(_, next)| next);
+        (original + (offset - normalized)).min(limit)
+    }
+
+    /// Chunk the normalized text with `config`, returning (start, end) byte
+    /// offsets into the text `space` picks.
+    pub fn chunk_offsets(&self, config: &ChunkerConfig, space: OffsetSpace) -> Vec<(usize, usize)> {
+        let offsets = config.chunker(&self.text).collect_offsets();
+        match space {
+            OffsetSpace::Normalized => offsets,
+            OffsetSpace::Original => offsets
+                .into_iter()
+                .map(|(start, end)| (self.to_original(start), self.to_original(end)))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_line_endings() {
+        let text = b"a\r\nb\rc\n\r\n";
+        let normalized = Normalizer::new().normalize(text);
+        assert_eq!(normalized.text(), b"a\nb\nc\n\n");
+        let map: Vec<_> = (0..=7).map(|i| normalized.to_original(i)).collect();
+        assert_eq!(map, vec![0, 1, 3, 4, 5, 6, 7, 9]);
+
+        let unchanged = Normalizer::new().normalize(b"plain\n");
+        assert!(matches!(unchanged.text, Cow::Borrowed(_)));
+        assert!(!unchanged.is_changed());
+        let kept = Normalizer::new()
+            .strip_bom(false)
+            .line_endings(false)
+            .normalize(b"\xef\xbb\xbfa\r\n");
+        assert_eq!(kept.text(), b"\xef\xbb\xbfa\r\n");
+        assert!(!kept.is_changed());
+    }
+
+    #[test]
+    fn test_normalize_offsets_tile_original() {
+        let text = b"\xef\xbb\xbfOne two.\r\nThree four five.\r\n\r\nSix.";
+        let normalized = Normalizer::new().normalize(text);
+        let config = ChunkerConfig::new().size(8).delimiters(b" \n");
+        let original = normalized.chunk_offsets(&config, OffsetSpace::Original);
+        assert_eq!(original[0].0, 3);
+        assert_eq!(original.last().unwrap().1, text.len());
+        for pair in original.windows(2) {
+            assert_eq!(pair[0].1, pair[1].0);
+        }
+        let chunks: Vec<_> = original.iter().map(|&(s, e)| &text[s..e]).collect();
+        assert_eq!(chunks[1], b"two.\r\n");
+    }
+
+    #[cfg(feature = "nfc")]
+    #[test]
+    fn test_normalize_nfc() {
+        // "e" + combining acute, a precomposed "é", then an invalid byte
+        let text = [" cafe\u{301} é\r\n".as_bytes(), b"\xff!"].concat();
+        let normalized = Normalizer::new().nfc(true).normalize(&text);
+        assert_eq!(
+            normalized.text(),
+            [" café é\n".as_bytes(), b"\xff!"].concat()
+        );
+        let map: Vec<_> = (0..=12).map(|i| normalized.to_original(i)).collect();
+        assert_eq!(map, vec![0, 1, 2, 3, 4, 5, 7, 8, 9, 10, 12, 13, 14]);
+
+        let composed = Normalizer::new().nfc(true).normalize("café".as_bytes());
+        assert!(!composed.is_changed());
+    }
+}
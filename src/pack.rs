@@ -0,0 +1,145 @@
+//! Token-budget packing for prompt assembly.
+//!
+//! This module provides [`pack`], which fits whole chunks into as few prompts
+//! as it can without any prompt going over a token budget, returning which
+//! chunks go into which prompt.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// How [`pack`] assigns chunks to prompts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PackStrategy {
+    /// Fill prompts in chunk order, starting the next prompt when a chunk
+    /// doesn't fit. Every prompt holds a run of consecutive chunks.
+    #[default]
+    InOrder,
+    /// Place chunks, largest first, into the first prompt with room for
+    /// them (first-fit decreasing). Uses fewer prompts than
+    /// [`InOrder`](Self::InOrder), never more than 11/9 of the optimum plus
+    /// one, but a prompt's chunks needn't be consecutive.
+    FirstFitDecreasing,
+}
+
+/// Result of [`pack`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Packing {
+    /// Indices of the chunks in each prompt, ascending.
+    pub prompts: Vec<Vec<usize>>,
+    /// Total tokens of each prompt.
+    pub token_counts: Vec<usize>,
+    /// Indices of chunks over the budget on their own, left out of every
+    /// prompt.
+    pub oversized: Vec<usize>,
+}
+
+/// Pack chunks with the given token counts into prompts of at most `budget`
+/// tokens.
+///
+/// Chunks are never split: a chunk over the budget goes in
+/// [`Packing::oversized`] instead. Prompts are ordered by their first chunk.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{PackStrategy, pack};
+///
+/// let token_counts = [6, 5, 4, 3, 2];
+/// let packing = pack(&token_counts, 10, PackStrategy::InOrder);
+/// assert_eq!(packing.prompts, vec![vec![0], vec![1, 2], vec![3, 4]]);
+///
+/// let packing = pack(&token_counts, 10, PackStrategy::FirstFitDecreasing);
+/// assert_eq!(packing.prompts, vec![vec![0, 2], vec![1, 3, 4]]);
+/// assert_eq!(packing.token_counts, vec![10, 10]);
+/// ```
+pub fn pack(token_counts: &[usize], budget: usize, strategy: PackStrategy) -> Packing {
+    let mut packing = Packing::default();
+    let fitting = (0..token_counts.len()).filter(|&i| {
+        let fits = token_counts[i] <= budget;
+        if !fits {
+            packing.oversized.push(i);
+        }
+        fits
+    });
+
+    match strategy {
+        PackStrategy::InOrder => {
+            for i in fitting {
+                let count = token_counts[i];
+                match packing.token_counts.last_mut() {
+                    Some(total) if *total + count <= budget => {
+                        *total += count;
+                        packing.prompts.last_mut().unwrap().push(i);
+                    }
+                    _ => {
+                        packing.prompts.push(vec![i]);
+                        packing.token_counts.push(count);
+                    }
+                }
+            }
+        }
+        PackStrategy::FirstFitDecreasing => {
+            let mut order: Vec<usize> = fitting.collect();
+            // Stable, so equal chunks keep their order
+            order.sort_by_key(|&i| core::cmp::Reverse(token_counts[i]));
+            let mut prompts: Vec<(Vec<usize>, usize)> = Vec::new();
+            for i in order {
+                let count = token_counts[i];
+                match prompts
+                    .iter_mut()
+                    .find(|(_, total)| total + count <= budget)
+                {
+                    Some((prompt, total)) => {
+                        prompt.push(i);
+                        *total += count;
+                    }
+                    None => prompts.push((vec![i], count)),
+                }
+            }
+            for (prompt, _) in &mut prompts {
+                prompt.sort_unstable();
+            }
+            prompts.sort_unstable_by_key(|(prompt, _)| prompt[0]);
+            (packing.prompts, packing.token_counts) = prompts.into_iter().unzip();
+        }
+    }
+    packing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_in_order() {
+        let packing = pack(&[3, 4, 12, 2, 5, 0, 1], 10, PackStrategy::InOrder);
+        // The oversized chunk is left out without ending the prompt
+        assert_eq!(packing.prompts, vec![vec![0, 1, 3], vec![4, 5, 6]]);
+        assert_eq!(packing.token_counts, vec![9, 6]);
+        assert_eq!(packing.oversized, vec![2]);
+
+        assert_eq!(pack(&[], 10, PackStrategy::InOrder), Packing::default());
+        let zero = pack(&[0, 1, 0], 0, PackStrategy::InOrder);
+        assert_eq!(zero.prompts, vec![vec![0, 2]]);
+        assert_eq!(zero.oversized, vec![1]);
+    }
+
+    #[test]
+    fn test_pack_first_fit_decreasing() {
+        let counts = [2, 5, 4, 7, 1, 3, 8];
+        let packing = pack(&counts, 10, PackStrategy::FirstFitDecreasing);
+        assert_eq!(packing.prompts.len(), 3);
+        let mut all: Vec<usize> = packing.prompts.concat();
+        all.sort_unstable();
+        assert_eq!(all, (0..counts.len()).collect::<Vec<_>>());
+        for (prompt, &total) in packing.prompts.iter().zip(&packing.token_counts) {
+            assert!(prompt.windows(2).all(|w| w[0] < w[1]));
+            assert_eq!(prompt.iter().map(|&i| counts[i]).sum::<usize>(), total);
+            assert!(total <= 10);
+        }
+        assert!(pack(&counts, 10, PackStrategy::InOrder).prompts.len() > 3);
+    }
+}
@@ -0,0 +1,193 @@
+//! Chunking paged text with page numbers.
+//!
+//! This module provides [`PagedText`], the text of a document's pages joined
+//! with form feeds, so every chunk can report the pages it comes from. With
+//! the `pdf` feature, `PagedText::from_pdf` reads the pages of a PDF. Form
+//! feeds are also the page separator that `pdftotext` writes, so its output
+//! can be used as is.
+
+use alloc::vec::Vec;
+
+use crate::config::ChunkerConfig;
+
+/// The byte that ends every page in a [`PagedText`] (form feed, `\x0c`).
+pub const PAGE_BREAK: u8 = 0x0c;
+
+/// A chunk of a [`PagedText`] with the pages it spans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageChunk {
+    /// Byte offset where the chunk starts.
+    pub start: usize,
+    /// Byte offset where the chunk ends (exclusive).
+    pub end: usize,
+    /// Page (1-based) of the chunk's first byte, not counting the form feed
+    /// of a previous page.
+    pub first_page: usize,
+    /// Page (1-based) of the chunk's last byte.
+    pub last_page: usize,
+}
+
+/// The text of a document's pages, each ended by a [`PAGE_BREAK`].
+///
+/// Chunks are found in the whole text, so they can span pages. To prefer
+/// breaking at page ends, add the form feed to the delimiters.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{ChunkerConfig, PagedText};
+///
+/// let doc = PagedText::new(&["Intro. Scope.", "Method. Results."]);
+/// let config = ChunkerConfig::new().size(16).delimiters(b".\x0c");
+/// let chunks = doc.chunks(&config);
+/// assert_eq!(&doc.text()[chunks[0].start..chunks[0].end], b"Intro. Scope.\x0c");
+/// assert_eq!((chunks[0].first_page, chunks[0].last_page), (1, 1));
+/// assert_eq!((chunks[1].first_page, chunks[1].last_page), (2, 2));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PagedText {
+    text: Vec<u8>,
+    /// Byte offset where each page starts.
+    page_starts: Vec<usize>,
+}
+
+impl PagedText {
+    /// Join `pages`, ending each with a [`PAGE_BREAK`].
+    ///
+    /// Pages shouldn't contain form feeds themselves; see
+    /// [`from_form_feeds`](Self::from_form_feeds).
+    pub fn new<P: AsRef<[u8]>>(pages: &[P]) -> Self {
+        let len = pages.iter().map(|p| p.as_ref().len() + 1).sum();
+        let mut text = Vec::with_capacity(len);
+        let mut page_starts = Vec::with_capacity(pages.len());
+        for page in pages {
+            page_starts.push(text.len());
+            text.extend_from_slice(page.as_ref());
+            text.push(PAGE_BREAK);
+        }
+        Self { text, page_starts }
+    }
+
+    /// Pages of `text`, each ending at a form feed (as `pdftotext` writes).
+    ///
+    /// Text after the last form feed is one more page; empty text has none.
+    ///
+    /// ```
+    /// use chunk::PagedText;
+    ///
+    /// let doc = PagedText::from_form_feeds(b"One\x0cTwo\x0c".to_vec());
+    /// assert_eq!(doc.page_count(), 2);
+    /// assert_eq!(doc.page(2), Some(&b"Two"[..]));
+    /// assert_eq!(doc.page_of(4), 2);
+    /// ```
+    pub fn from_form_feeds(text: Vec<u8>) -> Self {
+        let mut page_starts = Vec::new();
+        let mut start = 0;
+        while start < text.len() {
+            page_starts.push(start);
+            start = match memchr::memchr(PAGE_BREAK, &text[start..]) {
+                Some(i) => start + i + 1,
+                None => text.len(),
+            };
+        }
+        Self { text, page_starts }
+    }
+
+    /// The whole text, form feeds included.
+    pub fn text(&self) -> &[u8] {
+        &self.text
+    }
+
+    /// Number of pages.
+    pub fn page_count(&self) -> usize {
+        self.page_starts.len()
+    }
+
+    /// Text of page `number` (1-based), without its form feed.
+    pub fn page(&self, number: usize) -> Option<&[u8]> {
+        let start = *self.page_starts.get(number.checked_sub(1)?)?;
+        let end = self
+            .page_starts
+            .get(number)
+            .copied()
+            .unwrap_or(self.text.len());
+        let page = &self.text[start..end];
+        Some(page.strip_suffix(&[PAGE_BREAK]).unwrap_or(page))
+    }
+
+    /// Page (1-based) that the byte at `offset` is on.
+    ///
+    /// A page's form feed is on that page. Offsets past the end are on the
+    /// last page.
+    pub fn page_of(&self, offset: usize) -> usize {
+        self.page_starts
+            .partition_point(|&start| start <= offset)
+            .max(1)
+    }
+
+    /// Chunk the text with `config`, with the pages of every chunk.
+    pub fn chunks(&self, config: &ChunkerConfig) -> Vec<PageChunk> {
+        config
+            .chunker(&self.text)
+            .collect_offsets()
+            .into_iter()
+            .map(|(start, end)| self.page_chunk(start, end))
+            .collect()
+    }
+
+    /// The chunk at `start..end`, with its pages.
+    pub fn page_chunk(&self, start: usize, end: usize) -> PageChunk {
+        let content = self.text[start..end]
+            .iter()
+            .position(|&b| b != PAGE_BREAK)
+            .map_or(start, |i| start + i);
+        PageChunk {
+            start,
+            end,
+            first_page: self.page_of(content),
+            last_page: self.page_of(end.saturating_sub(1).max(start)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pages_from_form_feeds() {
+        let doc = PagedText::from_form_feeds(b"One\x0cTwo\x0c\x0cFour".to_vec());
+        assert_eq!(doc.page_count(), 4);
+        assert_eq!(doc.page(1), Some(&b"One"[..]));
+        assert_eq!(doc.page(3), Some(&b""[..]));
+        assert_eq!(doc.page(4), Some(&b"Four"[..]));
+        assert_eq!(doc.page(0), None);
+        assert_eq!(doc.page(5), None);
+        // The form feed is on the page it ends
+        assert_eq!(doc.page_of(3), 1);
+        assert_eq!(doc.page_of(4), 2);
+        assert_eq!(doc.page_of(100), 4);
+
+        assert_eq!(PagedText::from_form_feeds(Vec::new()).page_count(), 0);
+        let joined = PagedText::new(&["One", "Two", "", "Four"]);
+        assert_eq!(joined.text(), b"One\x0cTwo\x0c\x0cFour\x0c");
+        assert_eq!(PagedText::from_form_feeds(joined.text().to_vec()), joined);
+    }
+
+    #[test]
+    fn test_pages_chunks() {
+        let doc = PagedText::new(&["Aaa. Bbb.", "Ccc. Ddd."]);
+        let chunks = doc.chunks(&ChunkerConfig::new().size(8).delimiters(b"."));
+        let pages: Vec<_> = chunks.iter().map(|c| (c.first_page, c.last_page)).collect();
+        // "\x0cCcc." starts with the first page's form feed, but is on the second
+        assert_eq!(pages, vec![(1, 1), (1, 1), (2, 2), (2, 2)]);
+        let prefixed = doc.chunks(&ChunkerConfig::new().size(12).delimiters(b" "));
+        assert_eq!(
+            &doc.text()[prefixed[1].start..prefixed[1].end],
+            b"Bbb.\x0cCcc. "
+        );
+        assert_eq!((prefixed[1].first_page, prefixed[1].last_page), (1, 2));
+        assert_eq!(chunks.last().unwrap().end, doc.text().len());
+    }
+}
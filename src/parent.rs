@@ -0,0 +1,106 @@
+//! Parent and child chunks for parent document retrieval.
+//!
+//! This module provides [`ParentChildChunks`], small child chunks to embed
+//! and search together with the larger parent chunks holding them, to hand
+//! to the model, and which parent every child belongs to. Parents are runs
+//! of whole children, so the text is scanned once and every child sits in
+//! exactly one parent.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Child chunks grouped into parent chunks, from
+/// [`Chunker::collect_parent_child`](crate::Chunker::collect_parent_child).
+///
+/// # Example
+///
+/// ```
+/// use chunk::chunk;
+///
+/// let text = b"One. Two. Three. Four.";
+/// let chunks = chunk(text).size(8).delimiters(b".").collect_parent_child(10);
+/// assert_eq!(chunks.children, vec![(0, 4), (4, 9), (9, 16), (16, 22)]);
+/// assert_eq!(chunks.parents, vec![(0, 9), (9, 16), (16, 22)]);
+/// assert_eq!(chunks.parent_of, vec![0, 0, 1, 2]);
+/// assert_eq!(chunks.children(0), 0..2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParentChildChunks {
+    /// Offsets of the parent chunks.
+    pub parents: Vec<(usize, usize)>,
+    /// Offsets of the child chunks.
+    pub children: Vec<(usize, usize)>,
+    /// For every child, the index of its parent in `parents`.
+    pub parent_of: Vec<usize>,
+}
+
+impl ParentChildChunks {
+    /// Group `children` into parents of at most `parent_size` bytes.
+    ///
+    /// Parents are built like [`merge_chunks`](crate::merge_chunks) builds
+    /// its chunks: each spans from the start of its first child to the end
+    /// of its last, and a child longer than `parent_size` is its own parent.
+    pub fn from_children(children: Vec<(usize, usize)>, parent_size: usize) -> Self {
+        let mut parents: Vec<(usize, usize)> = Vec::new();
+        let mut parent_of = Vec::with_capacity(children.len());
+        for &(start, end) in &children {
+            match parents.last_mut() {
+                Some(last) if end.max(last.1) - last.0 <= parent_size => last.1 = end.max(last.1),
+                _ => parents.push((start, end)),
+            }
+            parent_of.push(parents.len() - 1);
+        }
+        Self {
+            parents,
+            children,
+            parent_of,
+        }
+    }
+
+    /// Indices in `children` of the children of `parent`.
+    pub fn children(&self, parent: usize) -> Range<usize> {
+        let start = self.parent_of.partition_point(|&p| p < parent);
+        let end = self.parent_of.partition_point(|&p| p <= parent);
+        start..end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::chunk;
+    use alloc::vec;
+
+    #[test]
+    fn test_from_children() {
+        let chunks = ParentChildChunks::from_children(vec![(0, 3), (3, 20), (20, 22)], 6);
+        assert_eq!(chunks.parents, vec![(0, 3), (3, 20), (20, 22)]);
+        assert_eq!(chunks.parent_of, vec![0, 1, 2]);
+
+        let chunks = ParentChildChunks::from_children(Vec::new(), 6);
+        assert_eq!(chunks, ParentChildChunks::default());
+        assert_eq!(chunks.children(0), 0..0);
+    }
+
+    #[test]
+    fn test_children_nest_in_parents() {
+        let text = b"Alpha beta. Gamma delta. Epsilon. Zeta eta theta. Iota.\nKappa.";
+        let chunks = chunk(text)
+            .size(12)
+            .delimiters(b".\n")
+            .collect_parent_child(30);
+        for (parent, &(start, end)) in chunks.parents.iter().enumerate() {
+            let children = &chunks.children[chunks.children(parent)];
+            assert!(!children.is_empty());
+            assert_eq!(children[0].0, start);
+            assert_eq!(children[children.len() - 1].1, end);
+            assert!(end - start <= 30);
+        }
+        assert_eq!(chunks.parent_of.len(), chunks.children.len());
+        assert_eq!(
+            chunks.children,
+            chunk(text).size(12).delimiters(b".\n").collect_offsets()
+        );
+    }
+}
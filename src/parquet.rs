@@ -0,0 +1,554 @@
+//! Parquet output for chunked corpora (requires the `parquet` feature).
+//!
+//! This module provides [`ParquetWriter`], which writes chunks as the rows of
+//! a Parquet file with `doc_id`, `chunk_index`, `start`, `end` and `text`
+//! columns, the usual input to embedding jobs. Like the `arrow` feature it
+//! needs no Parquet library: pages are uncompressed and PLAIN-encoded, which
+//! every reader supports.
+
+use std::io::{self, Write};
+
+/// Default for [`ParquetWriter::row_group_bytes`]: 64 MiB.
+pub const DEFAULT_ROW_GROUP_BYTES: usize = 64 << 20;
+
+/// The file magic, at both ends of the file.
+const MAGIC: &[u8] = b"PAR1";
+
+// Parquet's physical types and the converted types that annotate them
+const INT64: i32 = 2;
+const BYTE_ARRAY: i32 = 6;
+const UTF8: i32 = 0;
+const UINT_64: i32 = 14;
+
+/// The columns, in file order: name, physical type and converted type.
+const COLUMNS: [(&str, i32, i32); 5] = [
+    ("doc_id", INT64, UINT_64),
+    ("chunk_index", INT64, UINT_64),
+    ("start", INT64, UINT_64),
+    ("end", INT64, UINT_64),
+    ("text", BYTE_ARRAY, UTF8),
+];
+
+/// Writes chunks to a Parquet file, one row per chunk.
+///
+/// Rows are buffered into row groups of about
+/// [`row_group_bytes`](Self::row_group_bytes) of text, each written out when
+/// it fills, so a corpus of any size streams through a bounded buffer. The
+/// file is only readable after [`finish`](Self::finish) writes its footer.
+///
+/// `start` and `end` are byte offsets into the document; invalid UTF-8 in
+/// `text` is replaced with U+FFFD.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{ParquetWriter, chunk};
+///
+/// let docs = [&b"One. Two."[..], b"Six."];
+/// let mut writer = ParquetWriter::new(Vec::new())?;
+/// for (doc_id, text) in docs.iter().enumerate() {
+///     let offsets = chunk(text).size(5).delimiters(b".").collect_offsets();
+///     writer.write_chunks(doc_id as u64, text, &offsets)?;
+/// }
+/// assert_eq!(writer.rows(), 3);
+/// let file = writer.finish()?;
+/// assert!(file.starts_with(b"PAR1") && file.ends_with(b"PAR1"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct ParquetWriter<W: Write> {
+    out: W,
+    /// Bytes written so far.
+    offset: u64,
+    row_group_bytes: usize,
+    rows: Rows,
+    row_groups: Vec<RowGroup>,
+    total_rows: u64,
+}
+
+/// The buffered rows of the next row group.
+#[derive(Debug, Default)]
+struct Rows {
+    doc_ids: Vec<u64>,
+    indices: Vec<u64>,
+    starts: Vec<u64>,
+    ends: Vec<u64>,
+    /// PLAIN-encoded text values: each a u32 length, then the bytes.
+    text: Vec<u8>,
+}
+
+/// Where a written row group's columns are.
+#[derive(Debug)]
+struct RowGroup {
+    rows: u64,
+    /// (offset, size in bytes) of each column chunk, in [`COLUMNS`] order.
+    columns: Vec<(u64, u64)>,
+}
+
+impl<W: Write> ParquetWriter<W> {
+    /// Start a Parquet file on `out`.
+    pub fn new(mut out: W) -> io::Result<Self> {
+        out.write_all(MAGIC)?;
+        Ok(Self {
+            out,
+            offset: MAGIC.len() as u64,
+            row_group_bytes: DEFAULT_ROW_GROUP_BYTES,
+            rows: Rows::default(),
+            row_groups: Vec::new(),
+            total_rows: 0,
+        })
+    }
+
+    /// Write a row group once its text reaches about this many bytes.
+    ///
+    /// Bigger groups read faster; smaller ones hold less in memory while
+    /// writing.
+    pub fn row_group_bytes(mut self, bytes: usize) -> Self {
+        self.row_group_bytes = bytes.max(1);
+        self
+    }
+
+    /// Rows written so far, including ones still buffered.
+    pub fn rows(&self) -> u64 {
+        self.total_rows
+    }
+
+    /// Add the chunks of document `doc_id` at `offsets`, (start, end) byte
+    /// offsets into `text`, as rows numbered from 0 in `chunk_index`.
+    pub fn write_chunks(
+        &mut self,
+        doc_id: u64,
+        text: &[u8],
+        offsets: &[(usize, usize)],
+    ) -> io::Result<()> {
+        for (index, &(start, end)) in offsets.iter().enumerate() {
+            let rows = &mut self.rows;
+            rows.doc_ids.push(doc_id);
+            rows.indices.push(index as u64);
+            rows.starts.push(start as u64);
+            rows.ends.push(end as u64);
+            let value = String::from_utf8_lossy(&text[start..end]);
+            let len = u32::try_from(value.len())
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "chunk over 4 GiB"))?;
+            rows.text.extend_from_slice(&len.to_le_bytes());
+            rows.text.extend_from_slice(value.as_bytes());
+            self.total_rows += 1;
+            if rows.text.len() >= self.row_group_bytes {
+                self.flush_row_group()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the remaining rows and the footer, returning the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush_row_group()?;
+        let footer = self.footer();
+        self.out.write_all(&footer)?;
+        self.out.write_all(&(footer.len() as u32).to_le_bytes())?;
+        self.out.write_all(MAGIC)?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    /// Write the buffered rows as a row group, one data page per column.
+    fn flush_row_group(&mut self) -> io::Result<()> {
+        let rows = core::mem::take(&mut self.rows);
+        let count = rows.doc_ids.len();
+        if count == 0 {
+            return Ok(());
+        }
+        let int64 =
+            |values: &[u64]| -> Vec<u8> { values.iter().flat_map(|v| v.to_le_bytes()).collect() };
+        let pages = [
+            int64(&rows.doc_ids),
+            int64(&rows.indices),
+            int64(&rows.starts),
+            int64(&rows.ends),
+            rows.text,
+        ];
+        let mut columns = Vec::with_capacity(pages.len());
+        for data in &pages {
+            let size = i32::try_from(data.len())
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "page over 2 GiB"))?;
+            let header = page_header(count, size);
+            self.out.write_all(&header)?;
+            self.out.write_all(data)?;
+            let len = (header.len() + data.len()) as u64;
+            columns.push((self.offset, len));
+            self.offset += len;
+        }
+        self.row_groups.push(RowGroup {
+            rows: count as u64,
+            columns,
+        });
+        Ok(())
+    }
+
+    /// The file metadata.
+    fn footer(&self) -> Vec<u8> {
+        let mut t = Compact::default();
+        t.i32(1, 1); // version
+        t.list(2, STRUCT, COLUMNS.len() + 1);
+        t.element();
+        t.binary(4, b"schema");
+        t.i32(5, COLUMNS.len() as i32);
+        t.end();
+        for (name, physical, converted) in COLUMNS {
+            t.element();
+            t.i32(1, physical);
+            t.i32(3, 0); // REQUIRED
+            t.binary(4, name.as_bytes());
+            t.i32(6, converted);
+            t.end();
+        }
+        t.i64(3, self.total_rows as i64);
+        t.list(4, STRUCT, self.row_groups.len());
+        for group in &self.row_groups {
+            t.element();
+            t.list(1, STRUCT, group.columns.len());
+            for (&(offset, len), (name, physical, _)) in group.columns.iter().zip(COLUMNS) {
+                t.element();
+                t.i64(2, offset as i64);
+                t.begin(3);
+                t.i32(1, physical);
+                t.list(2, I32, 1);
+                t.varint(0); // PLAIN
+                t.list(3, BINARY, 1);
+                t.varint(name.len() as u64);
+                t.out.extend_from_slice(name.as_bytes());
+                t.i32(4, 0); // UNCOMPRESSED
+                t.i64(5, group.rows as i64);
+                t.i64(6, len as i64);
+                t.i64(7, len as i64);
+                t.i64(9, offset as i64);
+                t.end();
+                t.end();
+            }
+            let bytes: u64 = group.columns.iter().map(|&(_, len)| len).sum();
+            t.i64(2, bytes as i64);
+            t.i64(3, group.rows as i64);
+            t.end();
+        }
+        t.binary(
+            6,
+            concat!("chunk version ", env!("CARGO_PKG_VERSION")).as_bytes(),
+        );
+        t.out.push(0);
+        t.out
+    }
+}
+
+/// The header of a data page of `count` required values in `size` bytes.
+fn page_header(count: usize, size: i32) -> Vec<u8> {
+    let mut t = Compact::default();
+    t.i32(1, 0); // DATA_PAGE
+    t.i32(2, size);
+    t.i32(3, size);
+    t.begin(5);
+    t.i32(1, count as i32);
+    t.i32(2, 0); // PLAIN
+    t.i32(3, 3); // RLE, for the (absent) definition levels
+    t.i32(4, 3); // and repetition levels
+    t.end();
+    t.out.push(0);
+    t.out
+}
+
+// Thrift compact protocol type ids
+const I32: u8 = 5;
+const I64: u8 = 6;
+const BINARY: u8 = 8;
+const LIST: u8 = 9;
+const STRUCT: u8 = 12;
+
+/// A Thrift compact protocol encoder, for the page headers and footer.
+#[derive(Debug)]
+struct Compact {
+    out: Vec<u8>,
+    /// The last field id written in each open struct.
+    last: Vec<i16>,
+}
+
+impl Default for Compact {
+    fn default() -> Self {
+        Self {
+            out: Vec::new(),
+            last: vec![0],
+        }
+    }
+}
+
+impl Compact {
+    fn field(&mut self, id: i16, kind: u8) {
+        let last = self.last.last_mut().expect("inside a struct");
+        let delta = id - core::mem::replace(last, id);
+        if (1..=15).contains(&delta) {
+            self.out.push(((delta as u8) << 4) | kind);
+        } else {
+            self.out.push(kind);
+            self.varint(zigzag(id.into()));
+        }
+    }
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.out.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.out.push(value as u8);
+    }
+
+    fn i32(&mut self, id: i16, value: i32) {
+        self.field(id, I32);
+        self.varint(zigzag(value.into()));
+    }
+
+    fn i64(&mut self, id: i16, value: i64) {
+        self.field(id, I64);
+        self.varint(zigzag(value));
+    }
+
+    fn binary(&mut self, id: i16, value: &[u8]) {
+        self.field(id, BINARY);
+        self.varint(value.len() as u64);
+        self.out.extend_from_slice(value);
+    }
+
+    /// Start a list of `len` elements of type `kind`.
+    fn list(&mut self, id: i16, kind: u8, len: usize) {
+        self.field(id, LIST);
+        if len < 15 {
+            self.out.push(((len as u8) << 4) | kind);
+        } else {
+            self.out.push(0xf0 | kind);
+            self.varint(len as u64);
+        }
+    }
+
+    /// Start a struct field.
+    fn begin(&mut self, id: i16) {
+        self.field(id, STRUCT);
+        self.last.push(0);
+    }
+
+    /// Start a struct list element.
+    fn element(&mut self) {
+        self.last.push(0);
+    }
+
+    /// End the innermost struct.
+    fn end(&mut self) {
+        self.out.push(0);
+        self.last.pop();
+    }
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A decoded Thrift value.
+    #[derive(Debug, Clone, PartialEq)]
+    enum Value {
+        Int(i64),
+        Binary(Vec<u8>),
+        List(Vec<Value>),
+        Struct(Vec<(i16, Value)>),
+    }
+
+    impl Value {
+        fn get(&self, id: i16) -> &Value {
+            let Value::Struct(fields) = self else {
+                panic!("not a struct: {self:?}")
+            };
+            &fields.iter().find(|(i, _)| *i == id).expect("field").1
+        }
+
+        fn int(&self) -> i64 {
+            let Value::Int(v) = self else { panic!() };
+            *v
+        }
+
+        fn list(&self) -> &[Value] {
+            let Value::List(v) = self else { panic!() };
+            v
+        }
+    }
+
+    /// Decode Thrift compact values from the front of a byte slice.
+    struct Reader<'a>(&'a [u8]);
+
+    impl Reader<'_> {
+        fn byte(&mut self) -> u8 {
+            let (&b, rest) = self.0.split_first().unwrap();
+            self.0 = rest;
+            b
+        }
+
+        fn varint(&mut self) -> u64 {
+            let (mut value, mut shift) = (0, 0);
+            loop {
+                let b = self.byte();
+                value |= u64::from(b & 0x7f) << shift;
+                if b < 0x80 {
+                    return value;
+                }
+                shift += 7;
+            }
+        }
+
+        fn int(&mut self) -> i64 {
+            let v = self.varint();
+            (v >> 1) as i64 ^ -((v & 1) as i64)
+        }
+
+        fn value(&mut self, kind: u8) -> Value {
+            match kind {
+                I32 | I64 => Value::Int(self.int()),
+                BINARY => {
+                    let len = self.varint() as usize;
+                    let (bytes, rest) = self.0.split_at(len);
+                    self.0 = rest;
+                    Value::Binary(bytes.to_vec())
+                }
+                LIST => {
+                    let header = self.byte();
+                    let len = match header >> 4 {
+                        15 => self.varint() as usize,
+                        len => len as usize,
+                    };
+                    Value::List((0..len).map(|_| self.value(header & 0xf)).collect())
+                }
+                STRUCT => {
+                    let mut fields = Vec::new();
+                    let mut last = 0;
+                    loop {
+                        let header = self.byte();
+                        if header == 0 {
+                            return Value::Struct(fields);
+                        }
+                        let id = match header >> 4 {
+                            0 => self.int() as i16,
+                            delta => last + delta as i16,
+                        };
+                        last = id;
+                        fields.push((id, self.value(header & 0xf)));
+                    }
+                }
+                _ => panic!("unexpected type {kind}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parquet_layout() {
+        let text = b"One. Two. \xffThree.";
+        let offsets = crate::chunk(text)
+            .size(5)
+            .delimiters(b".")
+            .collect_offsets();
+        let mut writer = ParquetWriter::new(Vec::new()).unwrap().row_group_bytes(8);
+        writer.write_chunks(7, text, &offsets).unwrap();
+        writer.write_chunks(8, b"", &[]).unwrap();
+        let file = writer.finish().unwrap();
+
+        let footer_len = u32::from_le_bytes(file[file.len() - 8..][..4].try_into().unwrap());
+        let footer = &file[file.len() - 8 - footer_len as usize..file.len() - 8];
+        let mut reader = Reader(footer);
+        let meta = reader.value(STRUCT);
+        assert!(reader.0.is_empty());
+        assert_eq!(meta.get(3).int(), offsets.len() as i64);
+        let names: Vec<_> = meta.get(2).list()[1..]
+            .iter()
+            .map(|element| element.get(4).clone())
+            .collect();
+        assert_eq!(
+            names,
+            COLUMNS
+                .map(|(name, ..)| Value::Binary(name.into()))
+                .to_vec()
+        );
+
+        // Read every column back through its page headers
+        let mut columns: Vec<Vec<u8>> = vec![Vec::new(); COLUMNS.len()];
+        let groups = meta.get(4).list();
+        assert!(groups.len() > 1);
+        let mut end = MAGIC.len() as i64;
+        for group in groups {
+            let rows = group.get(3).int();
+            for (i, chunk) in group.get(1).list().iter().enumerate() {
+                let column = chunk.get(3);
+                let offset = column.get(9).int();
+                assert_eq!(offset, end);
+                assert_eq!(column.get(5).int(), rows);
+                let mut page = Reader(&file[offset as usize..]);
+                let header = page.value(STRUCT);
+                assert_eq!(header.get(5).get(1).int(), rows);
+                let size = header.get(3).int() as usize;
+                columns[i].extend_from_slice(&page.0[..size]);
+                end = offset + column.get(7).int();
+                assert_eq!(end as usize, file.len() - page.0.len() + size);
+            }
+        }
+        let int64s = |bytes: &[u8]| -> Vec<u64> {
+            bytes
+                .chunks(8)
+                .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+                .collect()
+        };
+        assert_eq!(int64s(&columns[0]), vec![7; offsets.len()]);
+        assert_eq!(
+            int64s(&columns[1]),
+            (0..offsets.len() as u64).collect::<Vec<_>>()
+        );
+        let spans: Vec<_> = int64s(&columns[2])
+            .into_iter()
+            .zip(int64s(&columns[3]))
+            .map(|(s, e)| (s as usize, e as usize))
+            .collect();
+        assert_eq!(spans, offsets);
+        let mut texts = Vec::new();
+        let mut values = &columns[4][..];
+        while !values.is_empty() {
+            let len = u32::from_le_bytes(values[..4].try_into().unwrap()) as usize;
+            texts.push(String::from_utf8(values[4..4 + len].to_vec()).unwrap());
+            values = &values[4 + len..];
+        }
+        let expected: Vec<_> = offsets
+            .iter()
+            .map(|&(s, e)| String::from_utf8_lossy(&text[s..e]))
+            .collect();
+        assert_eq!(texts, expected);
+        assert_eq!(texts[2], " \u{fffd}Thr");
+    }
+
+    #[test]
+    fn test_parquet_empty() {
+        let file = ParquetWriter::new(Vec::new()).unwrap().finish().unwrap();
+        let footer = &file[4..file.len() - 8];
+        let meta = Reader(footer).value(STRUCT);
+        assert_eq!(meta.get(3).int(), 0);
+        assert!(meta.get(4).list().is_empty());
+    }
+
+    #[test]
+    fn test_compact_long_lists_and_field_jumps() {
+        let mut t = Compact::default();
+        t.i32(1, -3);
+        t.list(2, I32, 20);
+        for i in 0..20 {
+            t.varint(zigzag(i));
+        }
+        t.i64(40, 1 << 40);
+        t.out.push(0);
+        let value = Reader(&t.out).value(STRUCT);
+        assert_eq!(value.get(1).int(), -3);
+        assert_eq!(value.get(2).list().len(), 20);
+        assert_eq!(value.get(40).int(), 1 << 40);
+    }
+}
@@ -0,0 +1,207 @@
+//! PDF documents as paged text.
+//!
+//! This module adds [`PagedText::from_pdf`], which walks a PDF's page tree
+//! with `lopdf` and extracts the text of every page in order, and
+//! [`chunk_pdf`], which chunks it in the same call, so every chunk comes back
+//! with its first and last page without an external `pdftotext`.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::config::ChunkerConfig;
+use crate::pages::{PAGE_BREAK, PageChunk, PagedText};
+
+/// Error reading a PDF.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PdfError {
+    /// The data isn't a PDF that can be parsed (or it's encrypted).
+    Invalid(String),
+    /// The text of a page couldn't be extracted, e.g. for a font encoding
+    /// that isn't supported.
+    Page {
+        /// The page (1-based).
+        number: usize,
+        /// What went wrong.
+        message: String,
+    },
+}
+
+impl fmt::Display for PdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PdfError::Invalid(message) => write!(f, "not a readable PDF: {message}"),
+            PdfError::Page { number, message } => write!(f, "page {number}: {message}"),
+        }
+    }
+}
+
+impl core::error::Error for PdfError {}
+
+impl PagedText {
+    /// Read the text of every page of a PDF, in page tree order.
+    ///
+    /// Pages without text (scans, blank pages) are kept as empty pages, so
+    /// page numbers match the document's. Form feeds in a page's text are
+    /// dropped, as they end pages here.
+    pub fn from_pdf(pdf: &[u8]) -> Result<Self, PdfError> {
+        let doc =
+            lopdf::Document::load_mem(pdf).map_err(|err| PdfError::Invalid(err.to_string()))?;
+        let pages = doc
+            .get_pages()
+            .into_keys()
+            .map(|number| {
+                let mut text = doc.extract_text(&[number]).map_err(|err| PdfError::Page {
+                    number: number as usize,
+                    message: err.to_string(),
+                })?;
+                text.retain(|c| c != PAGE_BREAK as char);
+                Ok(text)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(&pages))
+    }
+}
+
+/// Chunk the text of a PDF with `config`, with the pages of every chunk.
+///
+/// Returns the text along with the chunks, whose offsets are into
+/// [`PagedText::text`]. See [`PagedText::from_pdf`] for how the text is read.
+///
+/// # Example
+///
+/// ```no_run
+/// use chunk::{ChunkerConfig, chunk_pdf};
+///
+/// let pdf = std::fs::read("report.pdf")?;
+/// let (doc, chunks) = chunk_pdf(&pdf, &ChunkerConfig::new().size(1024))?;
+/// for chunk in chunks {
+///     let text = String::from_utf8_lossy(&doc.text()[chunk.start..chunk.end]);
+///     println!("pages {}-{}: {text}", chunk.first_page, chunk.last_page);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn chunk_pdf(
+    pdf: &[u8],
+    config: &ChunkerConfig,
+) -> Result<(PagedText, Vec<PageChunk>), PdfError> {
+    let doc = PagedText::from_pdf(pdf)?;
+    let chunks = doc.chunks(config);
+    Ok((doc, chunks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::content::{Content, Operation};
+    use lopdf::{Object, Stream, dictionary};
+
+    /// A PDF with a page per string of `pages`, the first two in a nested
+    /// page tree node.
+    fn pdf(pages: &[&str]) -> Vec<u8> {
+        let mut doc = lopdf::Document::with_version("1.5");
+        let root = doc.new_object_id();
+        let nested = doc.new_object_id();
+        let font = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let resources = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font },
+        });
+        let mut kids = Vec::new();
+        let mut nested_kids = Vec::new();
+        for (i, text) in pages.iter().enumerate() {
+            let parent = if i < 2 { nested } else { root };
+            let mut operations = vec![
+                Operation::new("BT", vec![]),
+                Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                Operation::new("Td", vec![72.into(), 700.into()]),
+            ];
+            if !text.is_empty() {
+                operations.push(Operation::new("Tj", vec![Object::string_literal(*text)]));
+            }
+            operations.push(Operation::new("ET", vec![]));
+            let content = Content { operations }.encode().unwrap();
+            let contents = doc.add_object(Stream::new(dictionary! {}, content));
+            let page = doc.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => parent,
+                "Contents" => contents,
+                "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            });
+            if i < 2 {
+                nested_kids.push(page.into());
+            } else {
+                kids.push(page.into());
+            }
+        }
+        let nested_count = nested_kids.len() as i64;
+        doc.objects.insert(
+            nested,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Parent" => root,
+                "Kids" => nested_kids,
+                "Count" => nested_count,
+            }),
+        );
+        kids.insert(0, nested.into());
+        doc.objects.insert(
+            root,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => pages.len() as i64,
+                "Resources" => resources,
+            }),
+        );
+        let catalog = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => root });
+        doc.trailer.set("Root", catalog);
+        let mut out = Vec::new();
+        doc.save_to(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_pdf_pages() {
+        let doc = PagedText::from_pdf(&pdf(&["Aaa. Bbb.", "Ccc.", "", "Ddd."])).unwrap();
+        assert_eq!(doc.page_count(), 4);
+        let page = |number| String::from_utf8_lossy(doc.page(number).unwrap()).into_owned();
+        assert_eq!(page(1).trim(), "Aaa. Bbb.");
+        assert_eq!(page(2).trim(), "Ccc.");
+        assert_eq!(page(3).trim(), "");
+        assert_eq!(page(4).trim(), "Ddd.");
+    }
+
+    #[test]
+    fn test_chunk_pdf() {
+        let file = pdf(&["Aaa. Bbb.", "Ccc.", "Ddd."]);
+        let config = ChunkerConfig::new().size(8).delimiters(b".\x0c");
+        let (doc, chunks) = chunk_pdf(&file, &config).unwrap();
+        assert_eq!(chunks, doc.chunks(&config));
+        let pages: Vec<_> = chunks
+            .iter()
+            .map(|c| {
+                let text = String::from_utf8_lossy(&doc.text()[c.start..c.end]);
+                (text.trim().to_string(), c.first_page, c.last_page)
+            })
+            .filter(|(text, ..)| !text.is_empty())
+            .collect();
+        assert_eq!(
+            pages,
+            vec![
+                ("Aaa.".to_string(), 1, 1),
+                ("Bbb.".to_string(), 1, 1),
+                ("Ccc.".to_string(), 2, 2),
+                ("Ddd.".to_string(), 3, 3),
+            ]
+        );
+
+        assert!(matches!(
+            PagedText::from_pdf(b"not a pdf"),
+            Err(PdfError::Invalid(_))
+        ));
+    }
+}
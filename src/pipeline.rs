@@ -0,0 +1,517 @@
+//! Post-processing of chunks.
+//!
+//! This module provides [`ChunkPipeline`], a list of transforms (trim,
+//! lowercase, strip HTML tags, redact patterns, or any function) and filters
+//! (minimum length, content density, boilerplate patterns, language, or any
+//! predicate) run on every chunk in turn,
+//! so the bindings can clean chunks up before handing them over instead of in
+//! a loop on the other side.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::html::{is_tag_start, tag_end};
+
+type MapFn = Arc<dyn Fn(&[u8]) -> Vec<u8> + Send + Sync>;
+type FilterFn = Arc<dyn Fn(&[u8]) -> bool + Send + Sync>;
+
+/// One step of a pipeline.
+#[derive(Clone)]
+enum Step {
+    Trim,
+    Lowercase,
+    StripHtml,
+    Redact {
+        pattern: Vec<u8>,
+        replacement: Vec<u8>,
+    },
+    #[cfg(feature = "redact")]
+    Redactor(crate::redact::Redactor),
+    Map(MapFn),
+    MinLength(usize),
+    MinDensity(f64),
+    DropContaining(Vec<u8>),
+    #[cfg(feature = "regex")]
+    DropMatching(regex::bytes::Regex),
+    #[cfg(feature = "lang-detect")]
+    Languages(Vec<String>),
+    Filter(FilterFn),
+}
+
+impl fmt::Debug for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Trim => f.write_str("Trim"),
+            Step::Lowercase => f.write_str("Lowercase"),
+            Step::StripHtml => f.write_str("StripHtml"),
+            Step::Redact {
+                pattern,
+                replacement,
+            } => f
+                .debug_struct("Redact")
+                .field("pattern", &String::from_utf8_lossy(pattern))
+                .field("replacement", &String::from_utf8_lossy(replacement))
+                .finish(),
+            #[cfg(feature = "redact")]
+            Step::Redactor(redactor) => f.debug_tuple("Redactor").field(redactor).finish(),
+            Step::Map(_) => f.write_str("Map(..)"),
+            Step::MinLength(n) => f.debug_tuple("MinLength").field(n).finish(),
+            Step::MinDensity(ratio) => f.debug_tuple("MinDensity").field(ratio).finish(),
+            Step::DropContaining(pattern) => f
+                .debug_tuple("DropContaining")
+                .field(&String::from_utf8_lossy(pattern))
+                .finish(),
+            #[cfg(feature = "regex")]
+            Step::DropMatching(regex) => f.debug_tuple("DropMatching").field(regex).finish(),
+            #[cfg(feature = "lang-detect")]
+            Step::Languages(codes) => f.debug_tuple("Languages").field(codes).finish(),
+            Step::Filter(_) => f.write_str("Filter(..)"),
+        }
+    }
+}
+
+/// Transforms and filters run on every chunk, in the order they were added.
+///
+/// A filter sees the chunk as the transforms before it left it, and a chunk
+/// it rejects is dropped without running the later steps.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{ChunkPipeline, chunk};
+///
+/// let text = b"<p>One</p>\n<p>TWO</p>\n<br>\n";
+/// let offsets = chunk(text).size(12).delimiters(b"\n").collect_offsets();
+/// let pipeline = ChunkPipeline::new()
+///     .strip_html()
+///     .trim()
+///     .lowercase()
+///     .min_length(1);
+/// let chunks: Vec<_> = pipeline
+///     .run(text, &offsets)
+///     .into_iter()
+///     .map(|(_, _, chunk)| chunk.into_owned())
+///     .collect();
+/// // The `<br>` line is left empty and dropped
+/// assert_eq!(chunks, vec![b"one".to_vec(), b"two".to_vec()]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChunkPipeline {
+    steps: Vec<Step>,
+}
+
+impl ChunkPipeline {
+    /// Create a pipeline that passes chunks through unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the pipeline has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Trim ASCII whitespace from both ends.
+    pub fn trim(mut self) -> Self {
+        self.steps.push(Step::Trim);
+        self
+    }
+
+    /// Lowercase the chunk: all of it when it's UTF-8, otherwise only the
+    /// ASCII letters.
+    pub fn lowercase(mut self) -> Self {
+        self.steps.push(Step::Lowercase);
+        self
+    }
+
+    /// Remove HTML tags and comments, keeping the text between them.
+    ///
+    /// A tag cut off by the end of the chunk is removed to the end; the rest
+    /// of one cut off by its start is kept.
+    pub fn strip_html(mut self) -> Self {
+        self.steps.push(Step::StripHtml);
+        self
+    }
+
+    /// Replace every occurrence of `pattern` with `replacement`.
+    ///
+    /// An empty pattern is ignored.
+    pub fn redact(mut self, pattern: &[u8], replacement: &[u8]) -> Self {
+        if !pattern.is_empty() {
+            self.steps.push(Step::Redact {
+                pattern: pattern.to_vec(),
+                replacement: replacement.to_vec(),
+            });
+        }
+        self
+    }
+
+    /// Redact personal data with `redactor`, dropping the record of what was
+    /// redacted; use [`Redactor::redact_chunks`](crate::Redactor::redact_chunks)
+    /// to keep it.
+    #[cfg(feature = "redact")]
+    pub fn redactor(mut self, redactor: crate::redact::Redactor) -> Self {
+        self.steps.push(Step::Redactor(redactor));
+        self
+    }
+
+    /// Transform chunks with `f`.
+    pub fn map<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static,
+    {
+        self.steps.push(Step::Map(Arc::new(f)));
+        self
+    }
+
+    /// Drop chunks shorter than `bytes`.
+    pub fn min_length(mut self, bytes: usize) -> Self {
+        self.steps.push(Step::MinLength(bytes));
+        self
+    }
+
+    /// Drop chunks with less than `ratio` (0 to 1) of their characters
+    /// letters or digits, e.g. `0.2` for rules, tables of dashes or lists of
+    /// links that are more punctuation and whitespace than words.
+    ///
+    /// Bytes that aren't valid UTF-8 count as neither.
+    pub fn min_density(mut self, ratio: f64) -> Self {
+        self.steps.push(Step::MinDensity(ratio));
+        self
+    }
+
+    /// Drop chunks containing `pattern`, e.g. a copyright line or cookie
+    /// notice repeated on every page.
+    ///
+    /// An empty pattern is ignored.
+    pub fn drop_containing(mut self, pattern: &[u8]) -> Self {
+        if !pattern.is_empty() {
+            self.steps.push(Step::DropContaining(pattern.to_vec()));
+        }
+        self
+    }
+
+    /// Drop chunks in which the regex `pattern` matches, e.g.
+    /// `(?i)^\s*(home|about|contact)(\s*\|\s*\w+)+\s*$` for a navigation
+    /// menu.
+    #[cfg(feature = "regex")]
+    pub fn drop_matching(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.steps
+            .push(Step::DropMatching(regex::bytes::Regex::new(pattern)?));
+        Ok(self)
+    }
+
+    /// Keep only chunks detected as one of `codes`, ISO 639-3 codes like
+    /// `"eng"` (see [`detect_language`](crate::detect_language)).
+    ///
+    /// Chunks whose language can't be told are dropped.
+    #[cfg(feature = "lang-detect")]
+    pub fn languages<S: AsRef<str>>(mut self, codes: &[S]) -> Self {
+        let codes = codes
+            .iter()
+            .map(|code| String::from(code.as_ref()))
+            .collect();
+        self.steps.push(Step::Languages(codes));
+        self
+    }
+
+    /// Keep only chunks for which `f` returns `true`.
+    pub fn filter<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&[u8]) -> bool + Send + Sync + 'static,
+    {
+        self.steps.push(Step::Filter(Arc::new(f)));
+        self
+    }
+
+    /// Run the pipeline on one chunk, or `None` if a filter drops it.
+    ///
+    /// The result borrows `chunk` until a transform changes it.
+    pub fn apply<'t>(&self, chunk: &'t [u8]) -> Option<Cow<'t, [u8]>> {
+        let mut chunk = Cow::Borrowed(chunk);
+        for step in &self.steps {
+            match step {
+                Step::Trim => {
+                    chunk = match chunk {
+                        Cow::Borrowed(bytes) => Cow::Borrowed(bytes.trim_ascii()),
+                        Cow::Owned(bytes) => Cow::Owned(bytes.trim_ascii().to_vec()),
+                    }
+                }
+                Step::Lowercase => {
+                    if let Some(lower) = lowercase(&chunk) {
+                        chunk = Cow::Owned(lower);
+                    }
+                }
+                Step::StripHtml => {
+                    if let Some(stripped) = strip_html(&chunk) {
+                        chunk = Cow::Owned(stripped);
+                    }
+                }
+                Step::Redact {
+                    pattern,
+                    replacement,
+                } => {
+                    if let Some(redacted) = replace_all(&chunk, pattern, replacement) {
+                        chunk = Cow::Owned(redacted);
+                    }
+                }
+                #[cfg(feature = "redact")]
+                Step::Redactor(redactor) => {
+                    if let (Cow::Owned(redacted), _) = redactor.redact(&chunk) {
+                        chunk = Cow::Owned(redacted);
+                    }
+                }
+                Step::Map(f) => chunk = Cow::Owned(f(&chunk)),
+                Step::MinLength(bytes) => {
+                    if chunk.len() < *bytes {
+                        return None;
+                    }
+                }
+                Step::MinDensity(ratio) => {
+                    if density(&chunk) < *ratio {
+                        return None;
+                    }
+                }
+                Step::DropContaining(pattern) => {
+                    if memchr::memmem::find(&chunk, pattern).is_some() {
+                        return None;
+                    }
+                }
+                #[cfg(feature = "regex")]
+                Step::DropMatching(regex) => {
+                    if regex.is_match(&chunk) {
+                        return None;
+                    }
+                }
+                #[cfg(feature = "lang-detect")]
+                Step::Languages(codes) => {
+                    let language = crate::lang::detect_language(&chunk)?;
+                    if !codes.iter().any(|code| code == language) {
+                        return None;
+                    }
+                }
+                Step::Filter(f) => {
+                    if !f(&chunk) {
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(chunk)
+    }
+
+    /// Run the pipeline on the chunks of `text` at `offsets`, giving the
+    /// offsets of the chunks that are kept with what became of them.
+    pub fn run<'t>(
+        &self,
+        text: &'t [u8],
+        offsets: &[(usize, usize)],
+    ) -> Vec<(usize, usize, Cow<'t, [u8]>)> {
+        offsets
+            .iter()
+            .filter_map(|&(start, end)| Some((start, end, self.apply(&text[start..end])?)))
+            .collect()
+    }
+}
+
+/// Share of the characters in `bytes` that are letters or digits, 0 for an
+/// empty chunk.
+fn density(bytes: &[u8]) -> f64 {
+    let (mut content, mut total) = (0usize, 0usize);
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            content += c.is_alphanumeric() as usize;
+            total += 1;
+        }
+        total += chunk.invalid().len();
+    }
+    match total {
+        0 => 0.0,
+        _ => content as f64 / total as f64,
+    }
+}
+
+/// `bytes` lowercased, or `None` if it already is.
+fn lowercase(bytes: &[u8]) -> Option<Vec<u8>> {
+    match core::str::from_utf8(bytes) {
+        Ok(text) if !text.is_ascii() => {
+            let lower = text.to_lowercase();
+            (lower != text).then(|| lower.into_bytes())
+        }
+        _ => bytes
+            .iter()
+            .any(u8::is_ascii_uppercase)
+            .then(|| bytes.to_ascii_lowercase()),
+    }
+}
+
+/// `bytes` without its HTML tags and comments, or `None` if it has none.
+fn strip_html(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut copied = 0;
+    let mut pos = 0;
+    while let Some(i) = memchr::memchr(b'<', &bytes[pos..]) {
+        let start = pos + i;
+        if !is_tag_start(bytes.get(start + 1).copied()) {
+            pos = start + 1;
+            continue;
+        }
+        out.extend_from_slice(&bytes[copied..start]);
+        pos = tag_end(bytes, start);
+        copied = pos;
+    }
+    if copied == 0 {
+        return None;
+    }
+    out.extend_from_slice(&bytes[copied..]);
+    Some(out)
+}
+
+/// `bytes` with every `pattern` replaced, or `None` if there's none.
+fn replace_all(bytes: &[u8], pattern: &[u8], replacement: &[u8]) -> Option<Vec<u8>> {
+    let mut matches = memchr::memmem::find_iter(bytes, pattern).peekable();
+    matches.peek()?;
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut copied = 0;
+    for start in matches {
+        // find_iter doesn't overlap matches, so `start >= copied`
+        out.extend_from_slice(&bytes[copied..start]);
+        out.extend_from_slice(replacement);
+        copied = start + pattern.len();
+    }
+    out.extend_from_slice(&bytes[copied..]);
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_transforms() {
+        let pipeline = ChunkPipeline::new().trim();
+        assert_eq!(
+            pipeline.apply(b"  One. "),
+            Some(Cow::Borrowed(&b"One."[..]))
+        );
+        assert!(matches!(pipeline.apply(b" a "), Some(Cow::Borrowed(_))));
+
+        let pipeline = ChunkPipeline::new().lowercase();
+        assert_eq!(
+            pipeline.apply("ÉTÉ Ok".as_bytes()).unwrap(),
+            "été ok".as_bytes()
+        );
+        assert_eq!(pipeline.apply(b"AB\xff").unwrap(), &b"ab\xff"[..]);
+        assert!(matches!(pipeline.apply(b"lower"), Some(Cow::Borrowed(_))));
+
+        let pipeline = ChunkPipeline::new().strip_html();
+        assert_eq!(
+            pipeline
+                .apply(b"<p>a < b<!-- note --></p> <br/>c<a href=")
+                .unwrap(),
+            &b"a < b c"[..]
+        );
+        assert!(matches!(pipeline.apply(b"1 < 2"), Some(Cow::Borrowed(_))));
+
+        let pipeline = ChunkPipeline::new()
+            .redact(b"secret", b"[X]")
+            .redact(b"", b"ignored");
+        assert_eq!(
+            pipeline.apply(b"secret and secrets").unwrap(),
+            &b"[X] and [X]s"[..]
+        );
+
+        let pipeline = ChunkPipeline::new().map(|chunk| [chunk, b"!"].concat());
+        assert_eq!(pipeline.apply(b"Hi").unwrap(), &b"Hi!"[..]);
+    }
+
+    #[test]
+    fn test_filters() {
+        // The filter sees the trimmed chunk
+        let pipeline = ChunkPipeline::new().trim().min_length(3);
+        assert_eq!(pipeline.apply(b"  ab  "), None);
+        assert_eq!(pipeline.apply(b" abc ").unwrap(), &b"abc"[..]);
+
+        let pipeline = ChunkPipeline::new().filter(|chunk| chunk.starts_with(b"#"));
+        assert_eq!(pipeline.apply(b"text"), None);
+        assert!(pipeline.apply(b"# Title").is_some());
+
+        let pipeline = ChunkPipeline::new();
+        assert!(pipeline.is_empty());
+        assert!(matches!(pipeline.apply(b""), Some(Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn test_run() {
+        let text = b"Keep this. x. And this.";
+        let offsets = vec![(0, 10), (10, 13), (13, 23)];
+        let chunks = ChunkPipeline::new()
+            .trim()
+            .min_length(3)
+            .run(text, &offsets);
+        assert_eq!(
+            chunks,
+            vec![
+                (0, 10, Cow::Borrowed(&b"Keep this."[..])),
+                (13, 23, Cow::Borrowed(&b"And this."[..]))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_boilerplate() {
+        let pipeline = ChunkPipeline::new().min_density(0.5);
+        assert_eq!(pipeline.apply(b"-------- * * * --------"), None);
+        assert_eq!(pipeline.apply(b""), None);
+        assert!(
+            pipeline
+                .apply("Ünïcode wörds, mostly.".as_bytes())
+                .is_some()
+        );
+        assert_eq!(density(b"ab\xff\xfe"), 0.5);
+
+        let pipeline = ChunkPipeline::new()
+            .drop_containing(b"All rights reserved")
+            .drop_containing(b"");
+        assert_eq!(pipeline.apply(b"(c) 2024 Acme. All rights reserved."), None);
+        assert!(pipeline.apply(b"Body text.").is_some());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_drop_matching() {
+        let pipeline = ChunkPipeline::new()
+            .drop_matching(r"(?i)^\s*(home|about|contact)(\s*\|\s*\w+)+\s*$")
+            .unwrap();
+        assert_eq!(pipeline.apply(b"Home | About | Blog | Contact\n"), None);
+        assert!(pipeline.apply(b"Contact us at the front desk.").is_some());
+        assert!(ChunkPipeline::new().drop_matching("(").is_err());
+    }
+
+    #[cfg(feature = "redact")]
+    #[test]
+    fn test_redactor() {
+        let redactor = crate::redact::Redactor::new().emails();
+        let pipeline = ChunkPipeline::new().redactor(redactor).lowercase();
+        assert_eq!(
+            pipeline.apply(b"Ask ANA@example.com").unwrap(),
+            &b"ask [email]"[..]
+        );
+    }
+
+    #[cfg(feature = "lang-detect")]
+    #[test]
+    fn test_languages() {
+        let pipeline = ChunkPipeline::new().languages(&["eng"]);
+        let english = b"The quick brown fox jumps over the lazy dog near the river.";
+        let german = "Der schnelle braune Fuchs springt über den faulen Hund am Fluss.";
+        assert!(pipeline.apply(english).is_some());
+        assert_eq!(pipeline.apply(german.as_bytes()), None);
+        // Undetected
+        assert_eq!(pipeline.apply(b"12 34"), None);
+    }
+}
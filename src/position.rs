@@ -0,0 +1,172 @@
+//! Line and column positions of chunks.
+//!
+//! This module provides [`LineCounter`], which finds the line and column of
+//! byte offsets while moving through a text, and [`Positioned`], returned by
+//! [`Chunks::with_positions`](crate::Chunks::with_positions), which reports
+//! where each chunk starts as the chunks are emitted.
+
+use crate::chunk::Chunk;
+
+/// A line and column in a text, both 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position {
+    /// Line number; lines end at `\n`.
+    pub line: usize,
+    /// Column in bytes from the start of the line.
+    pub column: usize,
+}
+
+/// Finds the [`Position`] of byte offsets in a text.
+///
+/// Each lookup only scans the bytes between it and the previous one, so
+/// positions of increasing offsets, like chunk starts, cost a single pass
+/// over the text.
+///
+/// ```
+/// use chunk::{LineCounter, Position};
+///
+/// let mut lines = LineCounter::new(b"fn main() {\n    run();\n}\n");
+/// assert_eq!(lines.position(16), Position { line: 2, column: 5 });
+/// assert_eq!(lines.position(23), Position { line: 3, column: 1 });
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineCounter<'t> {
+    text: &'t [u8],
+    /// Offset of the last lookup.
+    offset: usize,
+    /// Line of `offset`.
+    line: usize,
+    /// Offset where that line starts.
+    line_start: usize,
+}
+
+impl<'t> LineCounter<'t> {
+    /// Start counting at the beginning of `text`.
+    pub fn new(text: &'t [u8]) -> Self {
+        Self {
+            text,
+            offset: 0,
+            line: 1,
+            line_start: 0,
+        }
+    }
+
+    /// Position of byte `offset`, clamped to the end of the text.
+    ///
+    /// Offsets can go backwards too, scanning back from the previous one.
+    pub fn position(&mut self, offset: usize) -> Position {
+        let offset = offset.min(self.text.len());
+        if offset >= self.offset {
+            let between = &self.text[self.offset..offset];
+            if let Some(last) = memchr::memrchr(b'\n', between) {
+                self.line += memchr::memchr_iter(b'\n', between).count();
+                self.line_start = self.offset + last + 1;
+            }
+        } else if offset < self.line_start {
+            self.line -= memchr::memchr_iter(b'\n', &self.text[offset..self.offset]).count();
+            self.line_start = memchr::memrchr(b'\n', &self.text[..offset]).map_or(0, |i| i + 1);
+        }
+        self.offset = offset;
+        Position {
+            line: self.line,
+            column: offset - self.line_start + 1,
+        }
+    }
+}
+
+/// Chunks with the [`Position`] where each starts, created by
+/// [`Chunks::with_positions`](crate::Chunks::with_positions) and
+/// [`OwnedChunks::with_positions`](crate::OwnedChunks::with_positions).
+#[derive(Debug, Clone)]
+pub struct Positioned<'t, I> {
+    chunks: I,
+    lines: LineCounter<'t>,
+}
+
+impl<'t, I> Positioned<'t, I> {
+    pub(crate) fn new(text: &'t [u8], chunks: I) -> Self {
+        Self {
+            chunks,
+            lines: LineCounter::new(text),
+        }
+    }
+}
+
+impl<'t, I: Iterator<Item = Chunk<'t>>> Iterator for Positioned<'t, I> {
+    type Item = (Chunk<'t>, Position);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunks.next()?;
+        Some((chunk, self.lines.position(chunk.start)))
+    }
+}
+
+impl<'t, I: DoubleEndedIterator<Item = Chunk<'t>>> DoubleEndedIterator for Positioned<'t, I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunks.next_back()?;
+        Some((chunk, self.lines.position(chunk.start)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::chunk;
+
+    #[test]
+    fn test_line_counter_any_order() {
+        let text = b"ab\ncd\n\nef";
+        let expected: Vec<_> = (0..=text.len())
+            .map(|offset| {
+                let before = &text[..offset];
+                let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+                let line_start = before
+                    .iter()
+                    .rposition(|&b| b == b'\n')
+                    .map_or(0, |i| i + 1);
+                Position {
+                    line,
+                    column: offset - line_start + 1,
+                }
+            })
+            .collect();
+        let mut lines = LineCounter::new(text);
+        for offset in [0, 5, 9, 3, 4, 8, 1, 6, 7, 2, 0, 9] {
+            assert_eq!(lines.position(offset), expected[offset], "offset {offset}");
+        }
+        assert_eq!(lines.position(100), expected[9]);
+    }
+
+    #[test]
+    fn test_chunks_with_positions() {
+        let text = b"line one\nline two\nline three\n";
+        let positions: Vec<_> = chunk(text)
+            .size(7)
+            .delimiters(b"\n ")
+            .iter_chunks()
+            .with_positions()
+            .map(|(chunk, position)| (chunk.start, position.line, position.column))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                (0, 1, 1),
+                (5, 1, 6),
+                (9, 2, 1),
+                (14, 2, 6),
+                (18, 3, 1),
+                (23, 3, 6)
+            ]
+        );
+        let backwards: Vec<_> = chunk(text)
+            .size(7)
+            .delimiters(b"\n ")
+            .iter_chunks()
+            .with_positions()
+            .rev()
+            .map(|(chunk, position)| (chunk.start, position.line, position.column))
+            .collect();
+        assert_eq!(backwards.into_iter().rev().collect::<Vec<_>>(), positions);
+    }
+}
@@ -0,0 +1,140 @@
+//! Progress reports from long chunking runs.
+//!
+//! This module provides [`Progress`], a count of the bytes processed and
+//! chunks emitted so far, and [`WithProgress`], returned by
+//! [`Chunks::with_progress`](crate::Chunks::with_progress), which hands one to
+//! a callback every so many chunks, e.g. to drive a progress bar.
+
+use crate::chunk::Chunk;
+
+/// How far a chunking run has got.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// Bytes of input processed: up to the end of the last chunk, or for a
+    /// reader, everything read so far.
+    pub bytes: usize,
+    /// Chunks emitted.
+    pub chunks: usize,
+}
+
+/// Counts chunks and calls back every `every` of them, and once more at the
+/// end if the last chunks weren't reported.
+#[derive(Debug)]
+pub(crate) struct Tracker<F> {
+    every: usize,
+    callback: F,
+    progress: Progress,
+    finished: bool,
+}
+
+impl<F: FnMut(Progress)> Tracker<F> {
+    pub(crate) fn new(every: usize, callback: F) -> Self {
+        Self {
+            every: every.max(1),
+            callback,
+            progress: Progress::default(),
+            finished: false,
+        }
+    }
+
+    /// A chunk was emitted with `bytes` of the input processed.
+    pub(crate) fn chunk(&mut self, bytes: usize) {
+        self.progress.bytes = bytes;
+        self.progress.chunks += 1;
+        if self.progress.chunks.is_multiple_of(self.every) {
+            (self.callback)(self.progress);
+        }
+    }
+
+    /// The input ran out after `bytes`.
+    pub(crate) fn finish(&mut self, bytes: usize) {
+        if self.finished {
+            return;
+        }
+        self.finished = true;
+        let reported =
+            self.progress.chunks.is_multiple_of(self.every) && self.progress.bytes == bytes;
+        self.progress.bytes = bytes;
+        if !reported || self.progress.chunks == 0 {
+            (self.callback)(self.progress);
+        }
+    }
+}
+
+/// Chunks that report [`Progress`] as they go, created by
+/// [`Chunks::with_progress`](crate::Chunks::with_progress) and
+/// [`OwnedChunks::with_progress`](crate::OwnedChunks::with_progress).
+#[derive(Debug)]
+pub struct WithProgress<I, F> {
+    chunks: I,
+    /// Length of the text, reported once the chunks run out.
+    len: usize,
+    tracker: Tracker<F>,
+}
+
+impl<I, F: FnMut(Progress)> WithProgress<I, F> {
+    pub(crate) fn new(len: usize, chunks: I, every: usize, callback: F) -> Self {
+        Self {
+            chunks,
+            len,
+            tracker: Tracker::new(every, callback),
+        }
+    }
+}
+
+impl<'t, I: Iterator<Item = Chunk<'t>>, F: FnMut(Progress)> Iterator for WithProgress<I, F> {
+    type Item = Chunk<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.chunks.next() {
+            Some(chunk) => {
+                self.tracker.chunk(chunk.end);
+                Some(chunk)
+            }
+            None => {
+                self.tracker.finish(self.len);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::chunk;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_with_progress() {
+        let text = b"One. Two. Six. Ten. End.  ";
+        let mut reports = Vec::new();
+        let count = chunk(text)
+            .size(6)
+            .delimiters(b".")
+            .trim(true)
+            .skip_empty(true)
+            .iter_chunks()
+            .with_progress(2, |progress| reports.push(progress))
+            .count();
+        assert_eq!(count, 5);
+        let reports: Vec<_> = reports.iter().map(|p| (p.bytes, p.chunks)).collect();
+        assert_eq!(reports, vec![(9, 2), (19, 4), (26, 5)]);
+    }
+
+    #[test]
+    fn test_tracker_final_report() {
+        let mut reports = Vec::new();
+        let mut tracker = Tracker::new(2, |progress: Progress| reports.push(progress.chunks));
+        tracker.chunk(5);
+        tracker.chunk(10);
+        tracker.finish(10);
+        tracker.finish(10);
+        // Nothing new to say at the end
+        assert_eq!(reports, vec![2]);
+
+        let mut reports = Vec::new();
+        Tracker::new(0, |progress: Progress| reports.push(progress)).finish(0);
+        assert_eq!(reports, vec![Progress::default()]);
+    }
+}
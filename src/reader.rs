@@ -0,0 +1,669 @@
+//! Chunking from [`Read`] sources such as files.
+//!
+//! This module provides [`ReaderChunker`], which reads input in fixed-size
+//! blocks and feeds it through a [`StreamChunker`], so multi-GB files can be
+//! chunked without loading them into memory, and [`Decompressed`], which
+//! with the `gzip` and `zstd` features decompresses input on the way in.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read};
+use std::path::Path;
+
+use crate::checkpoint::{Checkpoint, CheckpointMismatch};
+use crate::chunk::Chunker;
+use crate::progress::{Progress, Tracker};
+use crate::stream::StreamChunker;
+
+/// Default number of bytes requested from the reader per refill (64 KiB).
+pub const DEFAULT_READ_BUFFER_SIZE: usize = 64 * 1024;
+
+impl Chunker<'_> {
+    /// Chunk everything readable from `reader`.
+    ///
+    /// Returns a [`ReaderChunker`], which yields owned chunks and respects
+    /// delimiter boundaries across buffer refills.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chunk::Chunker;
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(b"Hello. World. Test.".to_vec());
+    /// let chunks: Vec<Vec<u8>> = Chunker::from_reader(reader)
+    ///     .size(10)
+    ///     .delimiters(b".".to_vec())
+    ///     .collect::<std::io::Result<_>>()
+    ///     .unwrap();
+    /// assert_eq!(chunks, vec![b"Hello.".to_vec(), b" World.".to_vec(), b" Test.".to_vec()]);
+    /// ```
+    pub fn from_reader<R: Read>(reader: R) -> ReaderChunker<R> {
+        ReaderChunker::new(reader)
+    }
+
+    /// Open the file at `path` and chunk its contents.
+    ///
+    /// With the `gzip` or `zstd` feature a gzip or zstd file (e.g.
+    /// `corpus.txt.gz`) is decompressed as it's read, see [`Decompressed`]. See
+    /// [`Chunker::from_reader`] for details.
+    pub fn from_path<P: AsRef<Path>>(
+        path: P,
+    ) -> io::Result<ReaderChunker<Decompressed<BufReader<File>>>> {
+        Ok(ReaderChunker::new(Decompressed::open(path)?))
+    }
+}
+
+/// The gzip magic bytes.
+const GZIP_MAGIC: &[u8] = b"\x1f\x8b";
+
+/// The magic bytes of a zstd frame.
+const ZSTD_MAGIC: &[u8] = b"\x28\xb5\x2f\xfd";
+
+/// A reader that decompresses gzip or zstd input on the fly.
+///
+/// Whether the input is compressed is told from its first bytes, not a file
+/// extension. Concatenated gzip members or zstd frames (as `cat a.gz b.gz`
+/// makes) read as one stream. Input in a format whose feature (`gzip` or
+/// `zstd`) is off is passed through as it is.
+///
+/// # Example
+///
+/// ```
+/// use chunk::Decompressed;
+/// use std::io::Read;
+///
+/// let mut text = String::new();
+/// let mut reader = Decompressed::new(&b"plain text"[..])?;
+/// assert!(!reader.is_compressed());
+/// reader.read_to_string(&mut text)?;
+/// assert_eq!(text, "plain text");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Decompressed<R> {
+    inner: Inner<R>,
+}
+
+#[derive(Debug)]
+enum Inner<R> {
+    Plain(R),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::bufread::MultiGzDecoder<R>),
+    #[cfg(feature = "zstd")]
+    Zstd(ZstdDecoder<R>),
+}
+
+impl Decompressed<BufReader<File>> {
+    /// Open the file at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: BufRead> Decompressed<R> {
+    /// Read from `reader`, decompressing it if it starts like gzip or zstd.
+    ///
+    /// Looks at what's buffered without consuming it, so only fails if
+    /// filling the buffer does.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let start = reader.fill_buf()?;
+        let (gzip, zstd) = (start.starts_with(GZIP_MAGIC), start.starts_with(ZSTD_MAGIC));
+        #[cfg(feature = "gzip")]
+        if gzip {
+            return Ok(Self {
+                inner: Inner::Gzip(flate2::bufread::MultiGzDecoder::new(reader)),
+            });
+        }
+        #[cfg(feature = "zstd")]
+        if zstd {
+            return Ok(Self {
+                inner: Inner::Zstd(ZstdDecoder::new(reader)),
+            });
+        }
+        let _ = (gzip, zstd);
+        Ok(Self {
+            inner: Inner::Plain(reader),
+        })
+    }
+
+    /// Whether the input is being decompressed.
+    pub fn is_compressed(&self) -> bool {
+        !matches!(self.inner, Inner::Plain(_))
+    }
+}
+
+impl<R: BufRead> Read for Decompressed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            Inner::Plain(reader) => reader.read(buf),
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(decoder) => decoder.read(buf),
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(decoder) => decoder.read(buf),
+        }
+    }
+}
+
+/// Decodes the zstd frames of a reader one after another, skipping
+/// skippable frames.
+#[cfg(feature = "zstd")]
+struct ZstdDecoder<R> {
+    reader: R,
+    /// Boxed as it's large, so plain readers don't pay for it
+    frame: Box<ruzstd::decoding::FrameDecoder>,
+    /// Whether a frame's header has been read and its content not yet all
+    /// returned
+    in_frame: bool,
+}
+
+#[cfg(feature = "zstd")]
+impl<R> core::fmt::Debug for ZstdDecoder<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ZstdDecoder").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<R: BufRead> ZstdDecoder<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            frame: Box::new(ruzstd::decoding::FrameDecoder::new()),
+            in_frame: false,
+        }
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<R: BufRead> Read for ZstdDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        use ruzstd::decoding::BlockDecodingStrategy;
+        use ruzstd::decoding::errors::{FrameDecoderError, ReadFrameHeaderError};
+
+        let invalid = |err| io::Error::new(ErrorKind::InvalidData, err);
+        loop {
+            if !self.in_frame {
+                if buf.is_empty() || self.reader.fill_buf()?.is_empty() {
+                    return Ok(0);
+                }
+                match self.frame.reset(&mut self.reader) {
+                    Ok(()) => self.in_frame = true,
+                    Err(FrameDecoderError::ReadFrameHeaderError(
+                        ReadFrameHeaderError::SkipFrame { length, .. },
+                    )) => {
+                        let skip = u64::from(length);
+                        if io::copy(&mut (&mut self.reader).take(skip), &mut io::sink())? < skip {
+                            return Err(ErrorKind::UnexpectedEof.into());
+                        }
+                        continue;
+                    }
+                    Err(err) => return Err(invalid(err)),
+                }
+            }
+            while self.frame.can_collect() < buf.len() && !self.frame.is_finished() {
+                let wanted = buf.len() - self.frame.can_collect();
+                self.frame
+                    .decode_blocks(&mut self.reader, BlockDecodingStrategy::UptoBytes(wanted))
+                    .map_err(invalid)?;
+            }
+            let read = self.frame.read(buf)?;
+            if read > 0 {
+                return Ok(read);
+            }
+            // Only a finished frame has nothing left to collect
+            self.in_frame = false;
+        }
+    }
+}
+
+/// Chunker over a [`Read`] source.
+///
+/// Produces the same chunks as [`chunk`](crate::chunk()) would for the reader's
+/// full contents. Iterates over `io::Result<Vec<u8>>`; after an I/O error the
+/// iterator is exhausted.
+pub struct ReaderChunker<R> {
+    reader: R,
+    stream: StreamChunker,
+    buffer: Vec<u8>,
+    /// Chunks found but not returned yet, with the offsets they end at
+    ready: VecDeque<(Vec<u8>, usize)>,
+    done: bool,
+    /// Bytes read from the reader so far
+    read: usize,
+    progress: Option<Tracker<ProgressCallback>>,
+    /// State after the last chunk returned, settings checksum aside
+    checkpoint: Checkpoint,
+    /// Bytes to skip before reading, from a restored checkpoint
+    skip: u64,
+}
+
+/// A callback set with [`ReaderChunker::with_progress`].
+type ProgressCallback = Box<dyn FnMut(Progress) + Send>;
+
+impl<R: Read> ReaderChunker<R> {
+    /// Create a new reader chunker with default settings.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            stream: StreamChunker::new(),
+            buffer: vec![0; DEFAULT_READ_BUFFER_SIZE],
+            ready: VecDeque::new(),
+            done: false,
+            read: 0,
+            progress: None,
+            checkpoint: Checkpoint::default(),
+            skip: 0,
+        }
+    }
+
+    /// Set the target chunk size in bytes.
+    pub fn size(mut self, size: usize) -> Self {
+        self.stream = self.stream.size(size);
+        self
+    }
+
+    /// Set single-byte delimiters to split on.
+    ///
+    /// Mutually exclusive with `pattern()` - last one set wins.
+    pub fn delimiters(mut self, delimiters: Vec<u8>) -> Self {
+        self.stream = self.stream.delimiters(delimiters);
+        self
+    }
+
+    /// Set a multi-byte pattern to split on.
+    ///
+    /// Mutually exclusive with `delimiters()` - last one set wins.
+    pub fn pattern(mut self, pattern: Vec<u8>) -> Self {
+        self.stream = self.stream.pattern(pattern);
+        self
+    }
+
+    /// Set multiple multi-byte patterns, composable with `.delimiters()`.
+    pub fn patterns<P: AsRef<[u8]>>(mut self, patterns: &[P]) -> Self {
+        self.stream = self.stream.patterns(patterns);
+        self
+    }
+
+    /// Put delimiter at the start of the next chunk (prefix mode).
+    pub fn prefix(mut self) -> Self {
+        self.stream = self.stream.prefix();
+        self
+    }
+
+    /// Put delimiter at the end of the current chunk (suffix mode, default).
+    pub fn suffix(mut self) -> Self {
+        self.stream = self.stream.suffix();
+        self
+    }
+
+    /// Enable consecutive delimiter/pattern handling.
+    pub fn consecutive(mut self) -> Self {
+        self.stream = self.stream.consecutive();
+        self
+    }
+
+    /// Enable forward fallback search.
+    pub fn forward_fallback(mut self) -> Self {
+        self.stream = self.stream.forward_fallback();
+        self
+    }
+
+    /// Carry `overlap` bytes of trailing context from each chunk into the next.
+    pub fn overlap(mut self, overlap: usize) -> Self {
+        self.stream = self.stream.overlap(overlap);
+        self
+    }
+
+    /// Never split inside a multi-byte UTF-8 sequence.
+    pub fn utf8_safe(mut self, enabled: bool) -> Self {
+        self.stream = self.stream.utf8_safe(enabled);
+        self
+    }
+
+    /// Merge a trailing chunk smaller than `min_size` bytes into the previous chunk.
+    pub fn min_size(mut self, min_size: usize) -> Self {
+        self.stream = self.stream.min_size(min_size);
+        self
+    }
+
+    /// Never emit a chunk longer than `max_size` bytes.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.stream = self.stream.max_size(max_size);
+        self
+    }
+
+    /// Set how many bytes are requested from the reader per refill.
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        self.buffer = vec![0; size.max(1)];
+        self
+    }
+
+    /// Call `callback` with the [`Progress`] so far every `every` chunks,
+    /// and once more when the reader runs out; the bytes are all those read,
+    /// including any waiting to be chunked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chunk::Chunker;
+    /// use std::io::Cursor;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let chunks = Arc::new(Mutex::new(0));
+    /// let seen = Arc::clone(&chunks);
+    /// let reader = Cursor::new(b"Hello. World. Test.".to_vec());
+    /// Chunker::from_reader(reader)
+    ///     .size(10)
+    ///     .delimiters(b".".to_vec())
+    ///     .with_progress(100, move |progress| *seen.lock().unwrap() = progress.chunks)
+    ///     .for_each(drop);
+    /// assert_eq!(*chunks.lock().unwrap(), 3);
+    /// ```
+    pub fn with_progress(
+        mut self,
+        every: usize,
+        callback: impl FnMut(Progress) + Send + 'static,
+    ) -> Self {
+        self.progress = Some(Tracker::new(every, Box::new(callback)));
+        self
+    }
+
+    /// Where chunking has got to, for picking up later with
+    /// [`restore_state`](Self::restore_state).
+    ///
+    /// Covers every chunk returned so far, whatever has been read ahead.
+    pub fn save_state(&self) -> Checkpoint {
+        Checkpoint {
+            config: self.stream.checksum(),
+            ..self.checkpoint
+        }
+    }
+
+    /// Pick up from `checkpoint`, saved from a reader chunker with the same
+    /// settings over the same input.
+    ///
+    /// Call this before any chunks are taken, with the reader at the start of
+    /// the input: the first [`resume_from`](Checkpoint::resume_from) bytes are
+    /// read past without being chunked, and the chunks carry on from where
+    /// the checkpoint was saved. Fails if the settings have changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chunk::Chunker;
+    /// use std::io::Cursor;
+    ///
+    /// let text = b"One. Two. Six. Ten.".to_vec();
+    /// let chunker = || Chunker::from_reader(Cursor::new(text.clone())).size(5);
+    /// let mut first = chunker();
+    /// first.next();
+    /// let checkpoint = first.save_state();
+    ///
+    /// let rest: Vec<_> = chunker()
+    ///     .restore_state(&checkpoint)?
+    ///     .collect::<std::io::Result<_>>()
+    ///     .unwrap();
+    /// assert_eq!(rest, vec![b" Two.".to_vec(), b" Six.".to_vec(), b" Ten.".to_vec()]);
+    /// # Ok::<(), chunk::CheckpointMismatch>(())
+    /// ```
+    pub fn restore_state(mut self, checkpoint: &Checkpoint) -> Result<Self, CheckpointMismatch> {
+        self.stream = self.stream.restore_state(checkpoint)?;
+        self.checkpoint = *checkpoint;
+        self.skip = checkpoint.resume_from;
+        Ok(self)
+    }
+
+    /// Get the next chunk, `None` once the reader is exhausted.
+    pub fn next_chunk(&mut self) -> Option<io::Result<Vec<u8>>> {
+        if self.skip > 0 {
+            let skip = core::mem::take(&mut self.skip);
+            match io::copy(&mut (&mut self.reader).take(skip), &mut io::sink()) {
+                Ok(skipped) => self.read += skipped as usize,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        loop {
+            if let Some((chunk, end)) = self.ready.pop_front() {
+                if let Some(tracker) = &mut self.progress {
+                    tracker.chunk(self.read);
+                }
+                self.checkpoint.resume_from = self.checkpoint.position;
+                self.checkpoint.position = end as u64;
+                self.checkpoint.chunks += 1;
+                return Some(Ok(chunk));
+            }
+            if self.done {
+                if let Some(tracker) = &mut self.progress {
+                    tracker.finish(self.read);
+                }
+                return None;
+            }
+
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => {
+                    let ready = &mut self.ready;
+                    self.stream
+                        .finish_with_ends(|chunk, end| ready.push_back((chunk, end)));
+                    self.done = true;
+                }
+                Ok(n) => {
+                    self.read += n;
+                    let ready = &mut self.ready;
+                    self.stream.push_with_ends(&self.buffer[..n], |chunk, end| {
+                        ready.push_back((chunk, end))
+                    });
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for ReaderChunker<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_chunk()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk;
+    use std::io::Cursor;
+
+    const TEXT: &[u8] = b"The quick brown fox. Jumps over the lazy dog? Yes.\nAnother line here. And more text follows, without many breaks at all for a while. End.";
+
+    fn read_all<R: Read>(chunker: ReaderChunker<R>) -> Vec<Vec<u8>> {
+        chunker.collect::<io::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn test_reader_progress_counts_bytes_read() {
+        use std::sync::{Arc, Mutex};
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reports);
+        let chunks = Chunker::from_reader(Cursor::new(TEXT))
+            .size(20)
+            .buffer_size(32)
+            .with_progress(3, move |progress| sink.lock().unwrap().push(progress))
+            .count();
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), chunks / 3 + 1);
+        assert!(reports.windows(2).all(|w| w[0].bytes <= w[1].bytes));
+        assert!(
+            reports[..reports.len() - 1]
+                .iter()
+                .all(|p| p.chunks % 3 == 0)
+        );
+        assert_eq!(
+            reports.last(),
+            Some(&Progress {
+                bytes: TEXT.len(),
+                chunks
+            })
+        );
+    }
+
+    #[test]
+    fn test_reader_matches_batch() {
+        let expected: Vec<Vec<u8>> = chunk(TEXT).size(20).map(|c| c.to_vec()).collect();
+        for buffer_size in [1, 7, 19, 64, 4096] {
+            let chunker = Chunker::from_reader(Cursor::new(TEXT))
+                .size(20)
+                .buffer_size(buffer_size);
+            assert_eq!(read_all(chunker), expected);
+        }
+    }
+
+    #[test]
+    fn test_reader_pattern_across_refills() {
+        let text = b"one\xe2\x96\x81two\xe2\x96\x81three\xe2\x96\x81four";
+        let expected: Vec<Vec<u8>> = chunk(text)
+            .size(10)
+            .pattern("▁".as_bytes())
+            .prefix()
+            .map(|c| c.to_vec())
+            .collect();
+        let chunker = Chunker::from_reader(Cursor::new(text))
+            .size(10)
+            .pattern("▁".as_bytes().to_vec())
+            .prefix()
+            .buffer_size(2);
+        assert_eq!(read_all(chunker), expected);
+    }
+
+    #[test]
+    fn test_reader_checkpoint() {
+        let chunker = || {
+            Chunker::from_reader(Cursor::new(TEXT))
+                .size(20)
+                .overlap(4)
+                .buffer_size(16)
+        };
+        let expected = read_all(chunker());
+        for taken in 0..=expected.len() {
+            let mut first = chunker();
+            first.by_ref().take(taken).for_each(drop);
+            let checkpoint = first.save_state();
+            assert_eq!(checkpoint.chunks, taken as u64);
+
+            let rest = read_all(chunker().restore_state(&checkpoint).unwrap());
+            assert_eq!(rest, expected[taken..]);
+            // Checkpoints taken after resuming still line up
+            let mut resumed = chunker().restore_state(&checkpoint).unwrap();
+            resumed.next();
+            let again = read_all(chunker().restore_state(&resumed.save_state()).unwrap());
+            assert_eq!(again, expected[(taken + 1).min(expected.len())..]);
+        }
+        let checkpoint = chunker().save_state();
+        assert!(chunker().size(21).restore_state(&checkpoint).is_err());
+    }
+
+    #[test]
+    fn test_reader_empty() {
+        assert!(read_all(Chunker::from_reader(io::empty())).is_empty());
+    }
+
+    #[test]
+    fn test_reader_error_ends_iteration() {
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("boom"))
+            }
+        }
+        let mut chunker = Chunker::from_reader(Failing);
+        assert!(chunker.next().unwrap().is_err());
+        assert!(chunker.next().is_none());
+    }
+
+    #[test]
+    fn test_from_path() {
+        let path = std::env::temp_dir().join(format!("chunk-from-path-{}.txt", std::process::id()));
+        std::fs::write(&path, TEXT).unwrap();
+        let chunks = read_all(Chunker::from_path(&path).unwrap().size(20));
+        std::fs::remove_file(&path).unwrap();
+
+        let expected: Vec<Vec<u8>> = chunk(TEXT).size(20).map(|c| c.to_vec()).collect();
+        assert_eq!(chunks, expected);
+    }
+
+    #[test]
+    fn test_from_path_missing_file() {
+        assert!(Chunker::from_path("/nonexistent/chunk/input.txt").is_err());
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_from_path_gzip() {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        // Two members, as concatenated .gz files are
+        let mut compressed = Vec::new();
+        for half in TEXT.chunks(TEXT.len() / 2 + 1) {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(half).unwrap();
+            compressed.extend(encoder.finish().unwrap());
+        }
+        let path =
+            std::env::temp_dir().join(format!("chunk-from-path-{}.txt.gz", std::process::id()));
+        std::fs::write(&path, &compressed).unwrap();
+        let chunks = read_all(Chunker::from_path(&path).unwrap().size(20));
+        assert!(Decompressed::open(&path).unwrap().is_compressed());
+        std::fs::remove_file(&path).unwrap();
+
+        let expected: Vec<Vec<u8>> = chunk(TEXT).size(20).map(|c| c.to_vec()).collect();
+        assert_eq!(chunks, expected);
+
+        let mut truncated = Decompressed::new(&compressed[..20]).unwrap();
+        assert!(truncated.read_to_end(&mut Vec::new()).is_err());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_from_path_zstd() {
+        use ruzstd::encoding::{CompressionLevel, compress_to_vec};
+
+        // Two frames with a skippable frame between them
+        let halves: Vec<_> = TEXT.chunks(TEXT.len() / 2 + 1).collect();
+        let mut compressed = compress_to_vec(halves[0], CompressionLevel::Fastest);
+        compressed.extend(b"\x50\x2a\x4d\x18\x03\0\0\0abc");
+        compressed.extend(compress_to_vec(halves[1], CompressionLevel::Fastest));
+        let path =
+            std::env::temp_dir().join(format!("chunk-from-path-{}.txt.zst", std::process::id()));
+        std::fs::write(&path, &compressed).unwrap();
+        let chunks = read_all(Chunker::from_path(&path).unwrap().size(20));
+        assert!(Decompressed::open(&path).unwrap().is_compressed());
+        std::fs::remove_file(&path).unwrap();
+
+        let expected: Vec<Vec<u8>> = chunk(TEXT).size(20).map(|c| c.to_vec()).collect();
+        assert_eq!(chunks, expected);
+
+        // Small buffers all the way through
+        let reader = io::BufReader::with_capacity(7, &compressed[..]);
+        let chunks = read_all(
+            ReaderChunker::new(Decompressed::new(reader).unwrap())
+                .size(20)
+                .buffer_size(5),
+        );
+        assert_eq!(chunks, expected);
+
+        let mut truncated = Decompressed::new(&compressed[..20]).unwrap();
+        assert!(truncated.read_to_end(&mut Vec::new()).is_err());
+        let mut corrupt = Decompressed::new(&b"\x28\xb5\x2f\xfdgarbage"[..]).unwrap();
+        assert!(corrupt.read_to_end(&mut Vec::new()).is_err());
+    }
+}
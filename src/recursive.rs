@@ -0,0 +1,336 @@
+//! Recursive (hierarchical) chunking across ordered delimiter levels.
+//!
+//! This module provides [`RecursiveChunker`], which splits text at the
+//! coarsest delimiter level first (e.g. paragraphs) and only descends to finer
+//! levels (lines, sentences, ...) for pieces that still exceed the target size.
+//! Pieces that fit are greedily packed back together up to the target size.
+
+use crate::delim::{DEFAULT_TARGET_SIZE, utf8_split_boundary};
+use crate::split::{IncludeDelim, PatternSplitter};
+
+/// Blank-line paragraph breaks, the first level of [`RecursiveChunker::paragraphs`].
+pub const PARAGRAPH_DELIMITERS: &[&str] = &["\r\n\r\n", "\n\n"];
+
+/// Sentence ends and line breaks, the second level of [`RecursiveChunker::paragraphs`].
+pub const SENTENCE_DELIMITERS: &[&str] = &[". ", "? ", "! ", "\n"];
+
+/// A compiled recursive chunker.
+///
+/// Each level is a set of multi-byte delimiters, compiled once and reused for
+/// every text. Text is split at the first level; adjacent pieces are merged
+/// while they fit in the target size, and any piece that is still too large is
+/// split again at the next level. Below the last level, pieces are hard split
+/// at the target size.
+///
+/// Offsets are contiguous and cover the whole input.
+///
+/// # Example
+///
+/// ```
+/// use chunk::RecursiveChunker;
+///
+/// let chunker = RecursiveChunker::new()
+///     .size(24)
+///     .level(&["\n\n"])
+///     .level(&["\n"])
+///     .level(&[". ", "? ", "! "]);
+///
+/// let text = b"Short intro.\n\nFirst line. Second line.\nThird line.";
+/// let chunks = chunker.chunks(text);
+/// assert_eq!(chunks[0], b"Short intro.\n\n");
+/// assert_eq!(chunks[1], b"First line. ");
+/// assert_eq!(chunks[2], b"Second line.\n");
+/// assert_eq!(chunks[3], b"Third line.");
+/// ```
+pub struct RecursiveChunker {
+    target_size: usize,
+    levels: Vec<PatternSplitter>,
+    include_delim: IncludeDelim,
+    utf8_safe: bool,
+}
+
+impl Default for RecursiveChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecursiveChunker {
+    /// Create a recursive chunker with no levels (hard splits only).
+    pub fn new() -> Self {
+        Self {
+            target_size: DEFAULT_TARGET_SIZE,
+            levels: Vec::new(),
+            include_delim: IncludeDelim::Prev,
+            utf8_safe: false,
+        }
+    }
+
+    /// Create a paragraph-priority chunker.
+    ///
+    /// Splits at blank lines ([`PARAGRAPH_DELIMITERS`]) first, falls back to
+    /// sentence ends ([`SENTENCE_DELIMITERS`]) for paragraphs that don't fit,
+    /// and hard splits anything left over. This is the usual document-prep
+    /// setup, so it doesn't have to be wired up level by level.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chunk::RecursiveChunker;
+    ///
+    /// let chunker = RecursiveChunker::paragraphs().size(24);
+    /// let text = b"Short intro.\n\nA longer paragraph. It has two sentences.";
+    /// let chunks = chunker.chunks(text);
+    /// assert_eq!(chunks[0], b"Short intro.\n\n");
+    /// assert_eq!(chunks[1], b"A longer paragraph. ");
+    /// assert_eq!(chunks[2], b"It has two sentences.");
+    /// ```
+    pub fn paragraphs() -> Self {
+        Self::new()
+            .level(PARAGRAPH_DELIMITERS)
+            .level(SENTENCE_DELIMITERS)
+    }
+
+    /// Set the target chunk size in bytes.
+    pub fn size(mut self, size: usize) -> Self {
+        self.target_size = size;
+        self
+    }
+
+    /// Append a delimiter level, tried after all previously added levels.
+    ///
+    /// Empty levels are ignored.
+    pub fn level<P: AsRef<[u8]>>(mut self, delimiters: &[P]) -> Self {
+        let patterns: Vec<&[u8]> = delimiters
+            .iter()
+            .map(|p| p.as_ref())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if !patterns.is_empty() {
+            self.levels.push(PatternSplitter::new(&patterns));
+        }
+        self
+    }
+
+    /// Put delimiters at the start of the next chunk (prefix mode).
+    pub fn prefix(mut self) -> Self {
+        self.include_delim = IncludeDelim::Next;
+        self
+    }
+
+    /// Put delimiters at the end of the current chunk (suffix mode, default).
+    pub fn suffix(mut self) -> Self {
+        self.include_delim = IncludeDelim::Prev;
+        self
+    }
+
+    /// Never hard split inside a multi-byte UTF-8 sequence.
+    ///
+    /// See [`Chunker::utf8_safe`](crate::Chunker::utf8_safe) for details.
+    pub fn utf8_safe(mut self, enabled: bool) -> Self {
+        self.utf8_safe = enabled;
+        self
+    }
+
+    /// Number of delimiter levels.
+    pub fn num_levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Chunk `text`, returning (start, end) byte offsets.
+    pub fn chunk_offsets(&self, text: &[u8]) -> Vec<(usize, usize)> {
+        let mut offsets = Vec::new();
+        if !text.is_empty() {
+            self.descend(text, 0, text.len(), 0, &mut offsets);
+        }
+        offsets
+    }
+
+    /// Chunk `text`, returning slices borrowed from it.
+    pub fn chunks<'t>(&self, text: &'t [u8]) -> Vec<&'t [u8]> {
+        self.chunk_offsets(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect()
+    }
+
+    /// Chunk `text[start..end]` starting at delimiter `level`.
+    fn descend(
+        &self,
+        text: &[u8],
+        start: usize,
+        end: usize,
+        level: usize,
+        out: &mut Vec<(usize, usize)>,
+    ) {
+        let size = self.target_size.max(1);
+
+        if end - start <= size {
+            out.push((start, end));
+            return;
+        }
+
+        // Below the last level: hard split at the target size
+        let Some(splitter) = self.levels.get(level) else {
+            let mut pos = start;
+            while pos < end {
+                let mut next = (pos + size).min(end);
+                if self.utf8_safe && next < end {
+                    next = utf8_split_boundary(&text[..end], pos, next);
+                }
+                out.push((pos, next));
+                pos = next;
+            }
+            return;
+        };
+
+        let pieces = splitter.split(&text[start..end], self.include_delim, 0);
+        if pieces.len() <= 1 {
+            self.descend(text, start, end, level + 1, out);
+            return;
+        }
+
+        // Greedily pack pieces that fit; descend into pieces that don't
+        let mut acc: Option<(usize, usize)> = None;
+        for (rel_start, rel_end) in pieces {
+            let (s, e) = (start + rel_start, start + rel_end);
+
+            if e - s > size {
+                if let Some(a) = acc.take() {
+                    out.push(a);
+                }
+                self.descend(text, s, e, level + 1, out);
+                continue;
+            }
+
+            acc = match acc {
+                Some((a_start, _)) if e - a_start <= size => Some((a_start, e)),
+                Some(a) => {
+                    out.push(a);
+                    Some((s, e))
+                }
+                None => Some((s, e)),
+            };
+        }
+        if let Some(a) = acc {
+            out.push(a);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_contiguous(offsets: &[(usize, usize)], len: usize) {
+        assert_eq!(offsets.first().map(|o| o.0), Some(0));
+        assert_eq!(offsets.last().map(|o| o.1), Some(len));
+        for w in offsets.windows(2) {
+            assert_eq!(w[0].1, w[1].0);
+        }
+    }
+
+    #[test]
+    fn test_paragraphs_preset() {
+        let chunker = RecursiveChunker::paragraphs().size(30);
+        assert_eq!(chunker.num_levels(), 2);
+
+        let text = b"Para one.\r\n\r\nPara two is a bit longer. Yes it is.\nEnd.";
+        let chunks = chunker.chunks(text);
+        assert_eq!(chunks[0], b"Para one.\r\n\r\n");
+        assert_eq!(chunks[1], b"Para two is a bit longer. ");
+        assert_eq!(chunks[2], b"Yes it is.\nEnd.");
+    }
+
+    #[test]
+    fn test_paragraphs_hard_split_fallback() {
+        let chunker = RecursiveChunker::paragraphs().size(4);
+        let text = b"abcdefghij";
+        assert_eq!(chunker.chunk_offsets(text), vec![(0, 4), (4, 8), (8, 10)]);
+    }
+
+    #[test]
+    fn test_recursive_fits_in_one_chunk() {
+        let chunker = RecursiveChunker::new().size(100).level(&["\n\n"]);
+        assert_eq!(chunker.chunk_offsets(b"Small text"), vec![(0, 10)]);
+    }
+
+    #[test]
+    fn test_recursive_empty_text() {
+        let chunker = RecursiveChunker::new().level(&["\n\n"]);
+        assert!(chunker.chunk_offsets(b"").is_empty());
+    }
+
+    #[test]
+    fn test_recursive_packs_paragraphs() {
+        let text = b"aaa\n\nbbb\n\nccc\n\nddd";
+        let chunker = RecursiveChunker::new().size(10).level(&["\n\n"]);
+        let chunks = chunker.chunks(text);
+        assert_eq!(chunks, vec![b"aaa\n\nbbb\n\n".as_slice(), b"ccc\n\nddd"]);
+    }
+
+    #[test]
+    fn test_recursive_descends_only_when_needed() {
+        let text = b"Intro.\n\nOne. Two. Three. Four.";
+        let chunker = RecursiveChunker::new()
+            .size(12)
+            .level(&["\n\n"])
+            .level(&[". "]);
+        let chunks = chunker.chunks(text);
+        // First paragraph fits and is kept whole; second is split at sentences
+        assert_eq!(chunks[0], b"Intro.\n\n");
+        assert_eq!(chunks[1], b"One. Two. ");
+        assert_eq!(chunks[2], b"Three. Four.");
+        assert_eq!(chunks.len(), 3);
+    }
+
+    #[test]
+    fn test_recursive_hard_split_fallback() {
+        let text = b"abcdefghijklmnop";
+        let chunker = RecursiveChunker::new()
+            .size(5)
+            .level(&["\n\n"])
+            .level(&[" "]);
+        let offsets = chunker.chunk_offsets(text);
+        assert_eq!(offsets, vec![(0, 5), (5, 10), (10, 15), (15, 16)]);
+    }
+
+    #[test]
+    fn test_recursive_hard_split_utf8_safe() {
+        let text = "ééééé".as_bytes();
+        let chunker = RecursiveChunker::new().size(3).utf8_safe(true);
+        let offsets = chunker.chunk_offsets(text);
+        assert_eq!(offsets, vec![(0, 2), (2, 4), (4, 6), (6, 8), (8, 10)]);
+    }
+
+    #[test]
+    fn test_recursive_prefix_mode() {
+        let text = b"aaaa\nbbbb\ncccc";
+        let chunker = RecursiveChunker::new().size(6).level(&["\n"]).prefix();
+        let chunks = chunker.chunks(text);
+        assert_eq!(chunks, vec![b"aaaa".as_slice(), b"\nbbbb", b"\ncccc"]);
+    }
+
+    #[test]
+    fn test_recursive_preserves_all_bytes() {
+        let text = b"Para one line one.\nPara one line two.\n\nPara two is a single long line without breaks\n\nEnd";
+        let chunker = RecursiveChunker::new()
+            .size(16)
+            .level(&["\n\n"])
+            .level(&["\n"])
+            .level(&[". "])
+            .level(&[" "]);
+        let offsets = chunker.chunk_offsets(text);
+        assert_contiguous(&offsets, text.len());
+        for (s, e) in offsets {
+            assert!(e - s <= 16);
+        }
+    }
+
+    #[test]
+    fn test_recursive_empty_level_ignored() {
+        let empty: [&str; 0] = [];
+        let chunker = RecursiveChunker::new().level(&empty).level(&[""]);
+        assert_eq!(chunker.num_levels(), 0);
+    }
+}
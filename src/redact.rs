@@ -0,0 +1,291 @@
+//! PII redaction in chunks (requires the `redact` feature).
+//!
+//! This module provides [`Redactor`], which replaces email addresses, phone
+//! numbers and matches of any other regexes in chunks with placeholders like
+//! `[EMAIL]`, and reports each span it redacted as a [`Redaction`], e.g. to
+//! keep personal data out of chunks sent to an external API while recording
+//! what was taken out.
+
+use std::borrow::Cow;
+
+use regex::bytes::Regex;
+
+/// Email addresses.
+const EMAIL: &str = r"(?i)\b[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}\b";
+
+/// Phone numbers with at least one separator, optionally with a `+` country
+/// code and a parenthesized area code, e.g. `+1 (555) 123-4567` or
+/// `020 7946 0958`. Dates like `2024-01-15` don't match.
+const PHONE: &str = r"(?:\+[0-9]{1,3}[\s.-]?)?(?:\([0-9]{1,4}\)\s?|\b[0-9]{2,4}[\s.-])[0-9]{3,4}[\s.-]?[0-9]{3,4}\b";
+
+/// A redaction rule.
+#[derive(Debug, Clone)]
+struct Rule {
+    name: String,
+    regex: Regex,
+    replacement: Vec<u8>,
+}
+
+/// One span taken out by a [`Redactor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Redaction {
+    /// Byte offset where the redacted span starts in the input.
+    pub start: usize,
+    /// Byte offset where it ends (exclusive).
+    pub end: usize,
+    /// Name of the rule that matched, e.g. `"email"`.
+    pub rule: String,
+}
+
+/// A chunk after redaction, from [`Redactor::redact_chunks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactedChunk<'t> {
+    /// Byte offset where the chunk starts in the text.
+    pub start: usize,
+    /// Byte offset where the chunk ends (exclusive).
+    pub end: usize,
+    /// The chunk with its redacted spans replaced, borrowed if there were none.
+    pub text: Cow<'t, [u8]>,
+    /// What was redacted, with offsets into the whole text.
+    pub redactions: Vec<Redaction>,
+}
+
+/// Replaces personal data in chunks with placeholders.
+///
+/// Rules are tried together: where matches of several rules overlap, the one
+/// starting first wins (the longest, if they start at the same byte, then
+/// the one added first).
+///
+/// # Example
+///
+/// ```
+/// use chunk::Redactor;
+///
+/// let redactor = Redactor::new()
+///     .emails()
+///     .phone_numbers()
+///     .pattern("employee_id", r"\bE-[0-9]{6}\b", "[ID]")
+///     .unwrap();
+/// let (text, redactions) = redactor.redact(b"Mail ana@example.com or call 555-123-4567 (E-004217).");
+/// assert_eq!(&*text, b"Mail [EMAIL] or call [PHONE] ([ID]).");
+/// assert_eq!((redactions[0].start, redactions[0].end), (5, 20));
+/// let rules: Vec<_> = redactions.iter().map(|r| r.rule.as_str()).collect();
+/// assert_eq!(rules, ["email", "phone", "employee_id"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    rules: Vec<Rule>,
+}
+
+impl Redactor {
+    /// Create a redactor without rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Redact email addresses as `[EMAIL]` (rule `"email"`).
+    pub fn emails(self) -> Self {
+        self.rule("email", Regex::new(EMAIL).expect("email regex"), "[EMAIL]")
+    }
+
+    /// Redact phone numbers as `[PHONE]` (rule `"phone"`).
+    ///
+    /// Numbers need a separator between digit groups, like `555-123-4567`,
+    /// `555 123 4567` or `(555) 1234567`, so long runs of digits such as
+    /// order numbers are left alone.
+    pub fn phone_numbers(self) -> Self {
+        self.rule("phone", Regex::new(PHONE).expect("phone regex"), "[PHONE]")
+    }
+
+    /// Redact matches of `pattern` as `replacement`, reported as rule `name`.
+    pub fn pattern(
+        self,
+        name: &str,
+        pattern: &str,
+        replacement: &str,
+    ) -> Result<Self, regex::Error> {
+        Ok(self.rule(name, Regex::new(pattern)?, replacement))
+    }
+
+    /// Redact matches of an already compiled regex.
+    pub fn rule(mut self, name: &str, regex: Regex, replacement: &str) -> Self {
+        self.rules.push(Rule {
+            name: name.to_string(),
+            regex,
+            replacement: replacement.as_bytes().to_vec(),
+        });
+        self
+    }
+
+    /// Whether the redactor has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The non-overlapping matches in `text`, in order, as (start, end, rule
+    /// index).
+    fn matches(&self, text: &[u8]) -> Vec<(usize, usize, usize)> {
+        let mut matches: Vec<_> = self
+            .rules
+            .iter()
+            .enumerate()
+            .flat_map(|(rule, r)| {
+                r.regex
+                    .find_iter(text)
+                    .map(move |m| (m.start(), m.end(), rule))
+            })
+            .filter(|&(start, end, _)| end > start)
+            .collect();
+        matches.sort_by_key(|&(start, end, rule)| (start, core::cmp::Reverse(end), rule));
+        let mut end = 0;
+        matches.retain(|&(s, e, _)| {
+            let keep = s >= end;
+            if keep {
+                end = e;
+            }
+            keep
+        });
+        matches
+    }
+
+    /// Redact `text`, giving the redacted text (borrowed if nothing matched)
+    /// and the spans taken out, with offsets into `text`.
+    pub fn redact<'t>(&self, text: &'t [u8]) -> (Cow<'t, [u8]>, Vec<Redaction>) {
+        let matches = self.matches(text);
+        if matches.is_empty() {
+            return (Cow::Borrowed(text), Vec::new());
+        }
+        let mut out = Vec::with_capacity(text.len());
+        let mut copied = 0;
+        let redactions = matches
+            .into_iter()
+            .map(|(start, end, rule)| {
+                let rule = &self.rules[rule];
+                out.extend_from_slice(&text[copied..start]);
+                out.extend_from_slice(&rule.replacement);
+                copied = end;
+                Redaction {
+                    start,
+                    end,
+                    rule: rule.name.clone(),
+                }
+            })
+            .collect();
+        out.extend_from_slice(&text[copied..]);
+        (Cow::Owned(out), redactions)
+    }
+
+    /// Redact the chunks of `text` at `offsets`, e.g. from
+    /// [`Chunker::collect_offsets`](crate::Chunker::collect_offsets).
+    ///
+    /// Each chunk is redacted on its own, so a match cut by a chunk boundary
+    /// is only found if the rest of it still matches.
+    pub fn redact_chunks<'t>(
+        &self,
+        text: &'t [u8],
+        offsets: &[(usize, usize)],
+    ) -> Vec<RedactedChunk<'t>> {
+        offsets
+            .iter()
+            .map(|&(start, end)| {
+                let (chunk, mut redactions) = self.redact(&text[start..end]);
+                for redaction in &mut redactions {
+                    redaction.start += start;
+                    redaction.end += start;
+                }
+                RedactedChunk {
+                    start,
+                    end,
+                    text: chunk,
+                    redactions,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::chunk;
+
+    fn redacted(redactor: &Redactor, text: &str) -> String {
+        String::from_utf8(redactor.redact(text.as_bytes()).0.into_owned()).unwrap()
+    }
+
+    #[test]
+    fn test_emails() {
+        let redactor = Redactor::new().emails();
+        assert_eq!(
+            redacted(
+                &redactor,
+                "To: Ana.Lopez+work@mail.example.co.uk, bob@test.io."
+            ),
+            "To: [EMAIL], [EMAIL]."
+        );
+        assert_eq!(redacted(&redactor, "@handle and a@b"), "@handle and a@b");
+    }
+
+    #[test]
+    fn test_phone_numbers() {
+        let redactor = Redactor::new().phone_numbers();
+        for phone in [
+            "555-123-4567",
+            "555.123.4567",
+            "(555) 123-4567",
+            "+1 (555) 123-4567",
+            "+44 20 7946 0958",
+            "020 7946 0958",
+        ] {
+            assert_eq!(
+                redacted(&redactor, &format!("call {phone} now")),
+                "call [PHONE] now"
+            );
+        }
+        for other in ["on 2024-01-15", "order 5551234567", "v1.2.3", "12:30 pm"] {
+            assert_eq!(redacted(&redactor, other), other);
+        }
+    }
+
+    #[test]
+    fn test_overlapping_rules() {
+        // Starts first wins, then the longest
+        let redactor = Redactor::new()
+            .pattern("word", r"[a-z]+", "W")
+            .unwrap()
+            .emails();
+        let (text, redactions) = redactor.redact(b"x ana@example.com");
+        assert_eq!(&*text, b"W [EMAIL]");
+        assert_eq!(
+            redactions[1],
+            Redaction {
+                start: 2,
+                end: 17,
+                rule: "email".to_string()
+            }
+        );
+
+        let (text, redactions) = Redactor::new().emails().redact(b"nothing here");
+        assert!(matches!(text, Cow::Borrowed(_)));
+        assert!(redactions.is_empty());
+    }
+
+    #[test]
+    fn test_redact_chunks() {
+        let text = b"Hi ana@example.com.\nCall 555-123-4567.\nBye.";
+        let offsets = chunk(text).size(20).delimiters(b"\n").collect_offsets();
+        let chunks = Redactor::new()
+            .emails()
+            .phone_numbers()
+            .redact_chunks(text, &offsets);
+        let texts: Vec<_> = chunks.iter().map(|c| &*c.text).collect();
+        assert_eq!(texts, [&b"Hi [EMAIL].\n"[..], b"Call [PHONE].\n", b"Bye."]);
+        let spans: Vec<_> = chunks
+            .iter()
+            .flat_map(|c| &c.redactions)
+            .map(|r| &text[r.start..r.end])
+            .collect();
+        assert_eq!(spans, [&b"ana@example.com"[..], b"555-123-4567"]);
+    }
+}
@@ -0,0 +1,222 @@
+//! Size-based chunking at regular-expression boundaries (requires the `regex` feature).
+//!
+//! This module provides [`RegexChunker`], which works like the delimiter
+//! chunker but treats every match of a user-supplied pattern as a candidate
+//! boundary, e.g. timestamps at the start of log lines.
+
+use regex::bytes::Regex;
+
+use crate::delim::{DEFAULT_TARGET_SIZE, utf8_split_boundary};
+
+/// A compiled regex chunker.
+///
+/// For each chunk, the last boundary within the target size is used; without
+/// one, the chunk is hard split at the target size (or, with
+/// [`forward_fallback`](Self::forward_fallback), extended to the next
+/// boundary). In suffix mode (default) the match stays with the current chunk;
+/// in prefix mode it starts the next one.
+///
+/// Patterns match against raw bytes; use `(?m)` for per-line anchors.
+///
+/// # Example
+///
+/// ```
+/// use chunk::RegexChunker;
+///
+/// let log = b"2024-01-01 boot\nok\n2024-01-02 disk full\n2024-01-03 reboot\n";
+/// let chunker = RegexChunker::new(r"(?m)^\d{4}-\d{2}-\d{2}")
+///     .unwrap()
+///     .size(40)
+///     .prefix();
+///
+/// let chunks = chunker.chunks(log);
+/// assert_eq!(chunks[0], b"2024-01-01 boot\nok\n2024-01-02 disk full\n");
+/// assert_eq!(chunks[1], b"2024-01-03 reboot\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegexChunker {
+    regex: Regex,
+    target_size: usize,
+    prefix_mode: bool,
+    forward_fallback: bool,
+    utf8_safe: bool,
+}
+
+impl RegexChunker {
+    /// Compile `pattern` into a chunker with default settings.
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self::from_regex(Regex::new(pattern)?))
+    }
+
+    /// Create a chunker from an already compiled regex.
+    pub fn from_regex(regex: Regex) -> Self {
+        Self {
+            regex,
+            target_size: DEFAULT_TARGET_SIZE,
+            prefix_mode: false,
+            forward_fallback: false,
+            utf8_safe: false,
+        }
+    }
+
+    /// Set the target chunk size in bytes.
+    pub fn size(mut self, size: usize) -> Self {
+        self.target_size = size;
+        self
+    }
+
+    /// Start the next chunk at the match (prefix mode).
+    pub fn prefix(mut self) -> Self {
+        self.prefix_mode = true;
+        self
+    }
+
+    /// End the current chunk after the match (suffix mode, default).
+    pub fn suffix(mut self) -> Self {
+        self.prefix_mode = false;
+        self
+    }
+
+    /// Enable forward fallback search.
+    ///
+    /// When no boundary is found within the target size, extend the chunk to
+    /// the next boundary instead of doing a hard split.
+    pub fn forward_fallback(mut self) -> Self {
+        self.forward_fallback = true;
+        self
+    }
+
+    /// Never hard split inside a multi-byte UTF-8 sequence.
+    ///
+    /// See [`Chunker::utf8_safe`](crate::Chunker::utf8_safe) for details.
+    pub fn utf8_safe(mut self, enabled: bool) -> Self {
+        self.utf8_safe = enabled;
+        self
+    }
+
+    /// Get the compiled regex.
+    pub fn regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    /// Chunk `text`, returning (start, end) byte offsets.
+    pub fn chunk_offsets(&self, text: &[u8]) -> Vec<(usize, usize)> {
+        let mut offsets = Vec::new();
+        let mut pos = 0;
+        while pos < text.len() {
+            let split_at = self.split_at(text, pos);
+            offsets.push((pos, split_at));
+            pos = split_at;
+        }
+        offsets
+    }
+
+    /// Chunk `text`, returning slices borrowed from it.
+    pub fn chunks<'t>(&self, text: &'t [u8]) -> Vec<&'t [u8]> {
+        self.chunk_offsets(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect()
+    }
+
+    /// Compute the end of the chunk starting at `pos`.
+    fn split_at(&self, text: &[u8], pos: usize) -> usize {
+        let size = self.target_size.max(1);
+        if text.len() - pos <= size {
+            return text.len();
+        }
+        let end = pos + size;
+
+        // Last boundary within the window, first one past it. Matching uses
+        // `find_at` on the whole text so anchors still see their context.
+        let mut last = None;
+        let mut next = None;
+        let mut at = pos;
+        while let Some(m) = self.regex.find_at(text, at) {
+            let boundary = if self.prefix_mode { m.start() } else { m.end() };
+            if boundary > end {
+                next = Some(boundary);
+                break;
+            }
+            if boundary > pos {
+                last = Some(boundary);
+            }
+            // Step past empty matches so the search always advances
+            at = if m.end() > m.start() {
+                m.end()
+            } else {
+                m.end() + 1
+            };
+            if at > text.len() {
+                break;
+            }
+        }
+
+        match (last, next) {
+            (Some(boundary), _) => boundary,
+            (None, Some(boundary)) if self.forward_fallback => boundary,
+            (None, None) if self.forward_fallback => text.len(),
+            _ if self.utf8_safe => utf8_split_boundary(text, pos, end),
+            _ => end,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &[u8] = b"2024-01-01 a\n2024-01-02 bb\ncontinued\n2024-01-03 ccc\n";
+
+    #[test]
+    fn test_regex_prefix_at_timestamps() {
+        let chunker = RegexChunker::new(r"(?m)^\d{4}-\d{2}-\d{2}")
+            .unwrap()
+            .size(16)
+            .prefix();
+        let chunks = chunker.chunks(LOG);
+        assert_eq!(chunks[0], b"2024-01-01 a\n");
+        assert_eq!(chunks[1], b"2024-01-02 bb\nco");
+        assert_eq!(chunks.concat(), LOG.to_vec());
+    }
+
+    #[test]
+    fn test_regex_suffix_mode() {
+        let chunker = RegexChunker::new(r"[.!?] ").unwrap().size(13);
+        let chunks = chunker.chunks(b"Hello there. How are you? Fine.");
+        assert_eq!(chunks[0], b"Hello there. ");
+        assert_eq!(chunks[1], b"How are you? ");
+        assert_eq!(chunks[2], b"Fine.");
+    }
+
+    #[test]
+    fn test_regex_forward_fallback() {
+        let chunker = RegexChunker::new(r"(?m)^\d{4}-\d{2}-\d{2}")
+            .unwrap()
+            .size(16)
+            .prefix()
+            .forward_fallback();
+        let chunks = chunker.chunks(LOG);
+        assert_eq!(chunks[1], b"2024-01-02 bb\ncontinued\n");
+        assert_eq!(chunks[2], b"2024-01-03 ccc\n");
+    }
+
+    #[test]
+    fn test_regex_fits_in_one_chunk() {
+        let chunker = RegexChunker::new(r"\n").unwrap();
+        assert_eq!(chunker.chunk_offsets(LOG), vec![(0, LOG.len())]);
+        assert!(chunker.chunk_offsets(b"").is_empty());
+    }
+
+    #[test]
+    fn test_regex_hard_split_utf8_safe() {
+        let chunker = RegexChunker::new(r"\n").unwrap().size(4).utf8_safe(true);
+        let chunks = chunker.chunks("ééééé".as_bytes());
+        assert!(chunks.iter().all(|c| std::str::from_utf8(c).is_ok()));
+    }
+
+    #[test]
+    fn test_regex_invalid_pattern() {
+        assert!(RegexChunker::new(r"(unclosed").is_err());
+    }
+}
@@ -0,0 +1,115 @@
+//! Pluggable ranking of split points.
+//!
+//! By default a chunk ends at the last delimiter before the target size. A
+//! [`BoundaryScorer`] lets the chunkers rank every delimiter in the window
+//! instead, so semantic signals — an embedding similarity drop between the
+//! sentences on either side, a heading that follows — decide where chunks
+//! end, while the chunker still does the scanning.
+
+/// A ranking of candidate split points, for [`Chunker::boundary_scorer`](crate::Chunker::boundary_scorer).
+///
+/// For each chunk the chunker finds every delimiter boundary in the
+/// target-size window after `start` and asks for its score; the chunk ends
+/// at the highest-scoring one. `text[start..at]` is the chunk (before
+/// overlap) that splitting at `at` would give, and `text[at..]` is what
+/// follows it. Closures `Fn(&[u8], usize, usize) -> f64` implement this
+/// trait.
+///
+/// # Example
+///
+/// ```
+/// use chunk::chunk;
+///
+/// // Prefer splitting before a heading
+/// let before_heading = |text: &[u8], _start: usize, at: usize| {
+///     if text[at..].starts_with(b"#") { 1.0 } else { 0.0 }
+/// };
+///
+/// let text = b"Intro.\n# Setup\nStep one.\nStep two.\n";
+/// let chunks: Vec<_> = chunk(text)
+///     .size(30)
+///     .delimiters(b"\n")
+///     .boundary_scorer(before_heading)
+///     .collect();
+/// assert_eq!(chunks[0], b"Intro.\n");
+/// ```
+pub trait BoundaryScorer {
+    /// Score of splitting `text` at `at` for the chunk starting at `start`.
+    ///
+    /// Higher is better; between equal scores, the later boundary wins.
+    fn score(&self, text: &[u8], start: usize, at: usize) -> f64;
+}
+
+impl<F: Fn(&[u8], usize, usize) -> f64> BoundaryScorer for F {
+    #[inline]
+    fn score(&self, text: &[u8], start: usize, at: usize) -> f64 {
+        self(text, start, at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use crate::{OwnedChunker, chunk};
+
+    #[test]
+    fn test_score_candidates() {
+        let text = b"a.bb.ccc.dddd.eeeee";
+        let seen = RefCell::new(Vec::new());
+        let record = |_: &[u8], start: usize, at: usize| {
+            seen.borrow_mut().push((start, at));
+            0.0
+        };
+        let chunks: Vec<_> = chunk(text)
+            .size(10)
+            .delimiters(b".")
+            .boundary_scorer(record)
+            .collect();
+        // Every delimiter in the window is offered, the last one first
+        assert_eq!(seen.borrow()[..3], [(0, 9), (0, 5), (0, 2)]);
+        // All scores tie, so the last boundary wins as without a scorer
+        assert_eq!(
+            chunks,
+            chunk(text).size(10).delimiters(b".").collect::<Vec<_>>()
+        );
+
+        let prefix = RefCell::new(Vec::new());
+        let _ = chunk(text)
+            .size(10)
+            .delimiters(b".")
+            .prefix()
+            .boundary_scorer(|_: &[u8], _, at| {
+                prefix.borrow_mut().push(at);
+                0.0
+            })
+            .next();
+        assert_eq!(*prefix.borrow(), vec![8, 4, 1]);
+    }
+
+    #[test]
+    fn test_score_highest_wins() {
+        let text = b"a.bb.ccc.dddd.eeeee";
+        let earliest = |_: &[u8], _: usize, at: usize| -(at as f64);
+        let offsets = chunk(text)
+            .size(10)
+            .delimiters(b".")
+            .boundary_scorer(earliest)
+            .collect_offsets();
+        assert_eq!(offsets[..2], [(0, 2), (2, 5)]);
+
+        // Without a delimiter in the window, the split is forced as usual
+        let offsets = chunk(b"abcdefghij.k")
+            .size(5)
+            .delimiters(b".")
+            .boundary_scorer(earliest)
+            .collect_offsets();
+        assert_eq!(offsets[0], (0, 5));
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(10)
+            .delimiters(b".".to_vec())
+            .boundary_scorer(earliest);
+        assert_eq!(owned.collect_offsets()[..2], [(0, 2), (2, 5)]);
+    }
+}
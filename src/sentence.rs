@@ -0,0 +1,320 @@
+//! Sentence-aware chunking (requires the `sentence` feature).
+//!
+//! This module provides [`SentenceChunker`], which packs whole sentences into
+//! chunks of a target size. Sentence boundaries come from Unicode sentence
+//! segmentation (UAX #29), so decimals like "3.14" never split, and a list of
+//! abbreviations keeps "Dr. Smith" together.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::delim::DEFAULT_TARGET_SIZE;
+
+/// Default abbreviations that don't end a sentence when followed by a period.
+///
+/// Matching is case-sensitive and ignores the trailing period, so `"e.g"`
+/// covers "e.g.". Single capital letters (initials such as "J. R. R.") are
+/// always treated as abbreviations.
+pub const DEFAULT_ABBREVIATIONS: &[&str] = &[
+    "Mr", "Mrs", "Ms", "Dr", "Prof", "Sr", "Jr", "St", "Mt", "Gen", "Col", "Capt", "Lt", "Sgt",
+    "Rev", "Hon", "vs", "etc", "e.g", "i.e", "cf", "al", "approx", "Inc", "Ltd", "Co", "Corp",
+    "No", "Vol", "Fig", "Eq", "Jan", "Feb", "Mar", "Apr", "Jun", "Jul", "Aug", "Sep", "Sept",
+    "Oct", "Nov", "Dec",
+];
+
+/// A chunker that ends chunks only at sentence boundaries.
+///
+/// Adjacent sentences are packed greedily while the chunk stays within the
+/// target size. A single sentence longer than the target is hard split at a
+/// character boundary, so every chunk is valid UTF-8.
+///
+/// With [`sentences_per_chunk`](Self::sentences_per_chunk), chunks are sized
+/// by sentence count instead: each holds that many sentences, whatever their
+/// length, and [`sentence_overlap`](Self::sentence_overlap) repeats the last
+/// few sentences of a chunk at the start of the next.
+///
+/// # Example
+///
+/// ```
+/// use chunk::SentenceChunker;
+///
+/// let text = "Dr. Smith measured 3.14 meters. It rained. Nobody minded.";
+/// let chunker = SentenceChunker::new().size(45);
+///
+/// let chunks = chunker.chunks(text);
+/// assert_eq!(chunks, vec!["Dr. Smith measured 3.14 meters. It rained. ", "Nobody minded."]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SentenceChunker {
+    target_size: usize,
+    abbreviations: Vec<String>,
+    per_chunk: Option<usize>,
+    overlap: usize,
+}
+
+impl SentenceChunker {
+    /// Create a chunker with the default size and [`DEFAULT_ABBREVIATIONS`].
+    pub fn new() -> Self {
+        Self {
+            target_size: DEFAULT_TARGET_SIZE,
+            abbreviations: DEFAULT_ABBREVIATIONS
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
+            per_chunk: None,
+            overlap: 0,
+        }
+    }
+
+    /// Set the target chunk size in bytes.
+    pub fn size(mut self, size: usize) -> Self {
+        self.target_size = size;
+        self
+    }
+
+    /// Put `count` sentences in every chunk, ignoring the target size.
+    ///
+    /// The last chunk holds whatever sentences are left.
+    pub fn sentences_per_chunk(mut self, count: usize) -> Self {
+        self.per_chunk = Some(count.max(1));
+        self
+    }
+
+    /// Repeat the last `count` sentences of each chunk at the start of the
+    /// next, with [`sentences_per_chunk`](Self::sentences_per_chunk).
+    ///
+    /// Capped one below the sentences per chunk, so chunks always advance.
+    pub fn sentence_overlap(mut self, count: usize) -> Self {
+        self.overlap = count;
+        self
+    }
+
+    /// Replace the abbreviation list.
+    ///
+    /// Pass an empty list to use plain UAX #29 boundaries (initials are still
+    /// recognized).
+    pub fn abbreviations<S: AsRef<str>>(mut self, abbreviations: &[S]) -> Self {
+        self.abbreviations = abbreviations
+            .iter()
+            .map(|a| a.as_ref().trim_end_matches('.').to_string())
+            .collect();
+        self
+    }
+
+    /// Get the abbreviation list.
+    pub fn abbreviation_list(&self) -> &[String] {
+        &self.abbreviations
+    }
+
+    /// Find sentence boundaries, returning (start, end) byte offsets.
+    ///
+    /// Each sentence includes its trailing whitespace, so the spans cover
+    /// `text` exactly.
+    pub fn sentence_offsets(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut offsets: Vec<(usize, usize)> = Vec::new();
+        let mut joined = false;
+        for (start, sentence) in text.split_sentence_bound_indices() {
+            let end = start + sentence.len();
+            match offsets.last_mut() {
+                Some(last) if joined => last.1 = end,
+                _ => offsets.push((start, end)),
+            }
+            joined = self.ends_with_abbreviation(sentence);
+        }
+        offsets
+    }
+
+    /// Chunk `text`, returning (start, end) byte offsets.
+    pub fn chunk_offsets(&self, text: &str) -> Vec<(usize, usize)> {
+        if let Some(count) = self.per_chunk {
+            return self.count_offsets(text, count);
+        }
+        let size = self.target_size.max(1);
+        let mut offsets = Vec::new();
+        let mut start = 0;
+        let mut end = 0;
+
+        for (_, sentence_end) in self.sentence_offsets(text) {
+            if sentence_end - start > size {
+                if end > start {
+                    offsets.push((start, end));
+                    start = end;
+                }
+                // Sentence alone is too large: hard split at char boundaries
+                while sentence_end - start > size {
+                    let split = char_boundary(text, start, start + size);
+                    offsets.push((start, split));
+                    start = split;
+                }
+            }
+            end = sentence_end;
+        }
+
+        if end > start {
+            offsets.push((start, end));
+        }
+        offsets
+    }
+
+    /// Group every `count` sentences into a chunk, stepping back `overlap`.
+    fn count_offsets(&self, text: &str, count: usize) -> Vec<(usize, usize)> {
+        let sentences = self.sentence_offsets(text);
+        let step = count - self.overlap.min(count - 1);
+        let mut offsets = Vec::new();
+        let mut first = 0;
+        while first < sentences.len() {
+            let last = (first + count).min(sentences.len()) - 1;
+            offsets.push((sentences[first].0, sentences[last].1));
+            if last == sentences.len() - 1 {
+                break;
+            }
+            first += step;
+        }
+        offsets
+    }
+
+    /// Chunk `text`, returning slices borrowed from it.
+    pub fn chunks<'t>(&self, text: &'t str) -> Vec<&'t str> {
+        self.chunk_offsets(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect()
+    }
+
+    /// Check whether a UAX #29 sentence actually ends in an abbreviation.
+    fn ends_with_abbreviation(&self, sentence: &str) -> bool {
+        let Some(body) = sentence.trim_end().strip_suffix('.') else {
+            return false;
+        };
+        let word = body
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or("")
+            .trim_start_matches(|c: char| !c.is_alphanumeric());
+
+        let mut chars = word.chars();
+        let is_initial =
+            matches!((chars.next(), chars.next()), (Some(c), None) if c.is_uppercase());
+        is_initial || self.abbreviations.iter().any(|a| a == word)
+    }
+}
+
+impl Default for SentenceChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Last char boundary at or before `limit`, or the first one after `start`.
+fn char_boundary(text: &str, start: usize, limit: usize) -> usize {
+    let mut split = limit;
+    while split > start && !text.is_char_boundary(split) {
+        split -= 1;
+    }
+    if split == start {
+        split = start + 1;
+        while !text.is_char_boundary(split) {
+            split += 1;
+        }
+    }
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentences<'t>(chunker: &SentenceChunker, text: &'t str) -> Vec<&'t str> {
+        chunker
+            .sentence_offsets(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect()
+    }
+
+    #[test]
+    fn test_sentence_abbreviations_and_decimals() {
+        let text = "Dr. Smith paid $3.14 for it, e.g. a discount. Then he left.";
+        let chunker = SentenceChunker::new();
+        assert_eq!(
+            sentences(&chunker, text),
+            vec![
+                "Dr. Smith paid $3.14 for it, e.g. a discount. ",
+                "Then he left."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sentence_initials() {
+        let text = "J. R. R. Tolkien wrote it. Done.";
+        let chunker = SentenceChunker::new().abbreviations::<&str>(&[]);
+        assert_eq!(
+            sentences(&chunker, text),
+            vec!["J. R. R. Tolkien wrote it. ", "Done."]
+        );
+    }
+
+    #[test]
+    fn test_sentence_custom_abbreviations() {
+        let text = "See Dr. Who. Or not.";
+        let plain = SentenceChunker::new().abbreviations::<&str>(&[]);
+        assert_eq!(
+            sentences(&plain, text),
+            vec!["See Dr. ", "Who. ", "Or not."]
+        );
+
+        let custom = SentenceChunker::new().abbreviations(&["Dr."]);
+        assert_eq!(custom.abbreviation_list(), &["Dr".to_string()]);
+        assert_eq!(sentences(&custom, text), vec!["See Dr. Who. ", "Or not."]);
+    }
+
+    #[test]
+    fn test_sentence_packing() {
+        let text = "One. Two. Three. Four.";
+        let chunker = SentenceChunker::new().size(10);
+        assert_eq!(chunker.chunks(text), vec!["One. Two. ", "Three. ", "Four."]);
+        assert_eq!(chunker.chunks(text).concat(), text);
+    }
+
+    #[test]
+    fn test_sentence_count() {
+        let text = "One. Two. Three. Four. Five.";
+        let chunker = SentenceChunker::new().sentences_per_chunk(2);
+        assert_eq!(
+            chunker.chunks(text),
+            vec!["One. Two. ", "Three. Four. ", "Five."]
+        );
+        // The target size doesn't apply
+        assert_eq!(chunker.clone().size(4).chunks(text), chunker.chunks(text));
+
+        let overlapping = chunker.clone().sentences_per_chunk(3).sentence_overlap(1);
+        assert_eq!(
+            overlapping.chunks(text),
+            vec!["One. Two. Three. ", "Three. Four. Five."]
+        );
+        // Overlap is capped so every chunk moves forward
+        let capped = chunker.sentence_overlap(5);
+        assert_eq!(capped.chunks(text).len(), 4);
+        assert!(capped.chunk_offsets("").is_empty());
+    }
+
+    #[test]
+    fn test_sentence_hard_split_long_sentence() {
+        let text = "Short. Ééééééééé long one. End.";
+        let chunker = SentenceChunker::new().size(8);
+        let chunks = chunker.chunks(text);
+        assert_eq!(chunks[0], "Short. ");
+        assert!(chunks.iter().all(|c| c.len() <= 8));
+        assert_eq!(chunks.concat(), text);
+    }
+
+    #[test]
+    fn test_sentence_empty_and_tiny_size() {
+        let chunker = SentenceChunker::new().size(0);
+        assert!(chunker.chunk_offsets("").is_empty());
+        assert_eq!(chunker.chunks("é."), vec!["é", "."]);
+    }
+}
@@ -0,0 +1,162 @@
+//! Serde helpers for byte strings.
+//!
+//! Delimiters, patterns and chunk text are bytes, but usually UTF-8. They are
+//! written as strings when they decode, so configs stay readable in JSON and
+//! TOML, and as raw bytes otherwise. Reading accepts a string, bytes, or a
+//! sequence of integers.
+
+use std::fmt;
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Serializes as a string when the bytes are valid UTF-8.
+struct Borrowed<'a>(&'a [u8]);
+
+impl Serialize for Borrowed<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match std::str::from_utf8(self.0) {
+            Ok(text) => serializer.serialize_str(text),
+            Err(_) => serializer.serialize_bytes(self.0),
+        }
+    }
+}
+
+/// Deserializes from a string, bytes or a sequence of integers.
+struct Owned(Vec<u8>);
+
+impl<'de> Deserialize<'de> for Owned {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = Owned;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string or a byte array")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Owned, E> {
+                Ok(Owned(v.as_bytes().to_vec()))
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Owned, E> {
+                Ok(Owned(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Owned, E> {
+                Ok(Owned(v))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Owned, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(b) = seq.next_element()? {
+                    bytes.push(b);
+                }
+                Ok(Owned(bytes))
+            }
+        }
+
+        deserializer.deserialize_any(BytesVisitor)
+    }
+}
+
+pub(crate) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    Borrowed(bytes).serialize(serializer)
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    Owned::deserialize(deserializer).map(|b| b.0)
+}
+
+/// The same encoding for `Option<Vec<u8>>`.
+pub(crate) mod option {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        bytes: &Option<Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        bytes.as_deref().map(Borrowed).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<u8>>, D::Error> {
+        Option::<Owned>::deserialize(deserializer).map(|b| b.map(|b| b.0))
+    }
+}
+
+/// The same encoding for `Vec<Vec<u8>>`.
+pub(crate) mod seq {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        items: &[Vec<u8>],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(items.iter().map(|b| Borrowed(b)))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<u8>>, D::Error> {
+        Vec::<Owned>::deserialize(deserializer).map(|v| v.into_iter().map(|b| b.0).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ChunkerConfig, MarkdownChunker, SizeUnit, chunk};
+
+    #[test]
+    fn test_serde_config_round_trip() {
+        let config = ChunkerConfig::new()
+            .size(64)
+            .pattern("▁")
+            .patterns(&[&b"\n\n"[..], b"\xff"])
+            .prefix(true)
+            .unit(SizeUnit::Chars)
+            .max_size(Some(128));
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""pattern":"▁""#));
+        assert!(json.contains(r#""unit":"chars""#));
+        assert_eq!(
+            serde_json::from_str::<ChunkerConfig>(&json).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn test_serde_config_partial_and_raw_bytes() {
+        let config: ChunkerConfig =
+            serde_json::from_str(r#"{"size": 100, "delimiters": [10, 46]}"#).unwrap();
+        assert_eq!(config, ChunkerConfig::new().size(100).delimiters(b"\n."));
+
+        let json = serde_json::to_string(&ChunkerConfig::new().delimiters(vec![0xff])).unwrap();
+        assert!(json.contains(r#""delimiters":[255]"#));
+        assert!(!json.contains(r#""pattern":"#));
+        assert!(serde_json::from_str::<ChunkerConfig>(r#"{"size": "big"}"#).is_err());
+    }
+
+    #[test]
+    fn test_serde_chunk_outputs() {
+        let text = b"Hello. World.";
+        let chunks: Vec<_> = chunk(text).size(8).delimiters(b".").iter_chunks().collect();
+        assert_eq!(
+            serde_json::to_value(chunks[1]).unwrap(),
+            serde_json::json!({"index": 1, "start": 6, "end": 13, "text": " World."})
+        );
+
+        let offsets = chunk(text).size(8).delimiters(b".").collect_offsets();
+        let json = serde_json::to_string(&offsets).unwrap();
+        assert_eq!(json, "[[0,6],[6,13]]");
+        assert_eq!(
+            serde_json::from_str::<Vec<(usize, usize)>>(&json).unwrap(),
+            offsets
+        );
+
+        let md = MarkdownChunker::new().chunks_with_headings(b"# A\n\nBody.\n");
+        assert_eq!(serde_json::to_value(&md[0]).unwrap()["heading_path"], "# A");
+    }
+}
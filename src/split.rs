@@ -4,7 +4,11 @@
 //! equivalent to Cython's `split_text` function. Unlike the [`chunk`](crate::chunk)
 //! module which creates size-based chunks, this splits at **every** delimiter.
 
+use alloc::vec;
+use alloc::vec::Vec;
+
 use crate::delim::{DEFAULT_DELIMITERS, build_table, find_first_delimiter};
+#[cfg(feature = "std")]
 use daggrs::{DoubleArrayAhoCorasick, MatchKind, Trie};
 
 /// Where to include the delimiter in splits.
@@ -65,7 +69,7 @@ pub fn split_at_delimiters(
         return vec![(0, text.len())];
     }
 
-    // Build lookup table for 4+ delimiters
+    // Build lookup table for 7+ delimiters
     let table = build_table(delimiters);
 
     // Optimization #2: Pre-allocate with heuristic estimate
@@ -283,10 +287,12 @@ impl<'a> Splitter<'a> {
 /// let offsets1 = splitter.split(b"Hello. World?", IncludeDelim::Prev, 0);
 /// let offsets2 = splitter.split(b"Another. Text!", IncludeDelim::Prev, 0);
 /// ```
+#[cfg(feature = "std")]
 pub struct PatternSplitter {
     daac: DoubleArrayAhoCorasick,
 }
 
+#[cfg(feature = "std")]
 impl PatternSplitter {
     /// Create a new PatternSplitter with the given patterns.
     ///
@@ -424,6 +430,7 @@ impl PatternSplitter {
 /// let patterns: &[&[u8]] = &[b". ", b"? ", b"! "];
 /// let offsets = split_at_patterns(text, patterns, IncludeDelim::Prev, 0);
 /// ```
+#[cfg(feature = "std")]
 pub fn split_at_patterns(
     text: &[u8],
     patterns: &[&[u8]],
@@ -675,7 +682,7 @@ mod tests {
 
     #[test]
     fn test_split_four_delimiters() {
-        // 4+ delimiters use lookup table instead of memchr
+        // 4-6 delimiters take two memchr passes
         let text = b"A. B? C! D; E";
         let offsets = split_at_delimiters(text, b".?!;", IncludeDelim::Prev, 0);
         assert_eq!(offsets.len(), 5);
@@ -684,6 +691,7 @@ mod tests {
     // Tests for split_at_patterns (multi-byte patterns)
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_split_patterns_basic() {
         let text = b"Hello. World. Test.";
         let patterns: &[&[u8]] = &[b". "];
@@ -695,6 +703,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_split_patterns_include_next() {
         let text = b"Hello. World. Test";
         let patterns: &[&[u8]] = &[b". "];
@@ -706,6 +715,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_split_patterns_include_none() {
         let text = b"Hello. World. Test";
         let patterns: &[&[u8]] = &[b". "];
@@ -717,6 +727,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_split_patterns_multiple() {
         let text = b"Hello. World? Test! Done";
         let patterns: &[&[u8]] = &[b". ", b"? ", b"! "];
@@ -729,6 +740,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_split_patterns_newlines() {
         let text = b"Para 1\n\nPara 2\n\nPara 3";
         let patterns: &[&[u8]] = &[b"\n\n"];
@@ -740,6 +752,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_split_patterns_empty_text() {
         let text = b"";
         let patterns: &[&[u8]] = &[b". "];
@@ -748,6 +761,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_split_patterns_no_match() {
         let text = b"Hello World";
         let patterns: &[&[u8]] = &[b". "];
@@ -757,6 +771,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_split_patterns_preserves_all_bytes() {
         let text = b"The quick brown fox. Jumps over? The lazy dog!";
         let patterns: &[&[u8]] = &[b". ", b"? "];
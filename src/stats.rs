@@ -0,0 +1,135 @@
+//! Chunk size statistics.
+//!
+//! This module provides [`ChunkStats`], a summary of the chunks a
+//! configuration produces, for tuning `size` and `delimiters` on a corpus.
+
+use alloc::vec::Vec;
+
+/// Summary of chunk sizes, from [`Chunker::stats`](crate::Chunker::stats).
+///
+/// Sizes are chunk lengths in bytes, including any overlap. A forced split
+/// is a chunk boundary (other than the end of the text) with no delimiter
+/// or pattern next to it: a hard split at the target size, or one moved by
+/// `max_size` or `utf8_safe`. Many forced splits mean `size` is too small
+/// for the text, or the delimiters are too sparse.
+///
+/// # Example
+///
+/// ```
+/// use chunk::chunk;
+///
+/// let stats = chunk(b"Hello. Worldwide!").size(8).delimiters(b".").stats();
+/// assert_eq!(stats.count, 3);
+/// assert_eq!((stats.min, stats.max), (3, 8));
+/// assert_eq!(stats.forced_splits, 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChunkStats {
+    /// Number of chunks.
+    pub count: usize,
+    /// Smallest chunk, 0 without chunks.
+    pub min: usize,
+    /// Largest chunk, 0 without chunks.
+    pub max: usize,
+    /// Mean chunk size, 0 without chunks.
+    pub mean: f64,
+    /// Median chunk size (mean of the middle two for an even count).
+    pub median: f64,
+    /// Boundaries not at a delimiter or pattern.
+    pub forced_splits: usize,
+}
+
+impl ChunkStats {
+    /// Summarize `sizes` (in any order).
+    pub(crate) fn new(mut sizes: Vec<usize>, forced_splits: usize) -> Self {
+        if sizes.is_empty() {
+            return Self::default();
+        }
+        sizes.sort_unstable();
+        let count = sizes.len();
+        let mid = count / 2;
+        let median = if count.is_multiple_of(2) {
+            (sizes[mid - 1] + sizes[mid]) as f64 / 2.0
+        } else {
+            sizes[mid] as f64
+        };
+        Self {
+            count,
+            min: sizes[0],
+            max: sizes[count - 1],
+            mean: sizes.iter().sum::<usize>() as f64 / count as f64,
+            median,
+            forced_splits,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OwnedChunker, chunk};
+
+    #[test]
+    fn test_stats_sizes() {
+        let stats = ChunkStats::new(vec![4, 1, 3, 10], 2);
+        assert_eq!(
+            stats,
+            ChunkStats {
+                count: 4,
+                min: 1,
+                max: 10,
+                mean: 4.5,
+                median: 3.5,
+                forced_splits: 2,
+            }
+        );
+        assert_eq!(ChunkStats::new(vec![5, 1, 2], 0).median, 2.0);
+        assert_eq!(ChunkStats::new(Vec::new(), 0), ChunkStats::default());
+        assert_eq!(chunk(b"").stats(), ChunkStats::default());
+    }
+
+    #[test]
+    fn test_stats_forced_splits() {
+        let text = b"aaaa bbbbbbbbbbbb. cc. dd";
+        // 0..5 after a space, 5..13 hard split, 13..19 after a space, the rest
+        let stats = chunk(text).size(8).delimiters(b" .").stats();
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.forced_splits, 1);
+
+        let prefix = chunk(text).size(8).delimiters(b" .").prefix().stats();
+        assert_eq!(
+            prefix.forced_splits,
+            chunk(text)
+                .size(8)
+                .delimiters(b" .")
+                .prefix()
+                .collect_offsets()
+                .iter()
+                .filter(|&&(_, end)| end < text.len() && !matches!(text[end], b' ' | b'.'))
+                .count()
+        );
+
+        let mut owned = OwnedChunker::new(text.to_vec())
+            .size(8)
+            .delimiters(b" .".to_vec());
+        assert_eq!(owned.stats(), stats);
+    }
+
+    #[test]
+    fn test_stats_patterns() {
+        let text = "one▁two▁three▁fourfivesix".as_bytes();
+        let stats = chunk(text).size(10).pattern("▁".as_bytes()).stats();
+        assert_eq!(stats.forced_splits, 1);
+
+        let many = ["▁", "||", "##", "%%"];
+        let stats = chunk(text).size(10).delimiters(b"").patterns(&many).stats();
+        assert_eq!(stats.forced_splits, 1);
+        let few = chunk(text)
+            .size(10)
+            .delimiters(b"")
+            .patterns(&many[..1])
+            .stats();
+        assert_eq!(few, stats);
+    }
+}
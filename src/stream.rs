@@ -0,0 +1,669 @@
+//! Incremental chunking of data that arrives in pieces.
+//!
+//! This module provides [`StreamChunker`], which accepts input through
+//! [`push`](StreamChunker::push) and emits chunks as soon as their boundaries
+//! are known, keeping only the unfinished tail buffered.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::checkpoint::{Checkpoint, CheckpointMismatch, Checksum};
+use crate::chunk::overlap_start;
+use crate::delim::{
+    DEFAULT_DELIMITERS, DEFAULT_TARGET_SIZE, MultiPatternSearcher, build_table, compute_split_at,
+    compute_split_at_combined, utf8_next_boundary, utf8_prev_boundary, utf8_split_boundary,
+};
+use crate::delimiters::Delimiters;
+
+/// Streaming chunker for sockets, stdin, HTTP bodies and other incremental input.
+///
+/// Produces exactly the same chunks as [`chunk`](crate::chunk()) would for the
+/// concatenated input, regardless of how it is split across `push` calls.
+/// Memory use is bounded by the target size (plus any data scanned by
+/// forward fallback while waiting for a delimiter).
+///
+/// # Example
+///
+/// ```
+/// use chunk::StreamChunker;
+///
+/// let mut stream = StreamChunker::new().size(10).delimiters(b".".to_vec());
+///
+/// let mut chunks = stream.push(b"Hello. Wor");
+/// chunks.extend(stream.push(b"ld. Test."));
+/// chunks.extend(stream.finish());
+///
+/// assert_eq!(chunks, vec![b"Hello.".to_vec(), b" World.".to_vec(), b" Test.".to_vec()]);
+/// ```
+pub struct StreamChunker {
+    buffer: Vec<u8>,
+    target_size: usize,
+    delimiters: Vec<u8>,
+    pattern: Option<Vec<u8>>,
+    multi_searcher: Option<MultiPatternSearcher>,
+    pos: usize,
+    table: Option<[bool; 256]>,
+    initialized: bool,
+    prefix_mode: bool,
+    consecutive: bool,
+    forward_fallback: bool,
+    overlap: usize,
+    utf8_safe: bool,
+    min_size: usize,
+    max_size: Option<usize>,
+    /// Input offset of `buffer[0]`
+    consumed: usize,
+    /// Chunks emitted since the start of the stream
+    emitted: usize,
+    /// Checksum of the `patterns`, which the searcher doesn't keep
+    patterns_checksum: u64,
+}
+
+impl Default for StreamChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamChunker {
+    /// Create a new streaming chunker with default settings.
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            target_size: DEFAULT_TARGET_SIZE,
+            delimiters: DEFAULT_DELIMITERS.to_vec(),
+            pattern: None,
+            multi_searcher: None,
+            pos: 0,
+            table: None,
+            initialized: false,
+            prefix_mode: false,
+            consecutive: false,
+            forward_fallback: false,
+            overlap: 0,
+            utf8_safe: false,
+            min_size: 0,
+            max_size: None,
+            consumed: 0,
+            emitted: 0,
+            patterns_checksum: Checksum::new().finish(),
+        }
+    }
+
+    /// Set the target chunk size in bytes.
+    pub fn size(mut self, size: usize) -> Self {
+        self.target_size = size;
+        self
+    }
+
+    /// Set single-byte delimiters to split on.
+    ///
+    /// Mutually exclusive with `pattern()` - last one set wins.
+    pub fn delimiters(mut self, delimiters: Vec<u8>) -> Self {
+        self.delimiters = delimiters;
+        self.pattern = None;
+        self
+    }
+
+    /// Set a multi-byte pattern to split on.
+    ///
+    /// Mutually exclusive with `delimiters()` - last one set wins.
+    pub fn pattern(mut self, pattern: Vec<u8>) -> Self {
+        self.pattern = Some(pattern);
+        self.delimiters = vec![];
+        self
+    }
+
+    /// Set multiple multi-byte patterns, composable with `.delimiters()`.
+    ///
+    /// See [`Chunker::patterns`](crate::Chunker::patterns) for details.
+    pub fn patterns<P: AsRef<[u8]>>(mut self, patterns: &[P]) -> Self {
+        self.multi_searcher = MultiPatternSearcher::from_patterns(patterns);
+        let checksum = Checksum::new().size(patterns.len());
+        self.patterns_checksum = patterns
+            .iter()
+            .fold(checksum, |checksum, pattern| {
+                checksum.bytes(pattern.as_ref())
+            })
+            .finish();
+        self
+    }
+
+    /// Split at a named class of delimiters.
+    ///
+    /// See [`Chunker::delimiter_class`](crate::Chunker::delimiter_class) for
+    /// details.
+    pub fn delimiter_class(self, class: Delimiters) -> Self {
+        let chunker = self
+            .delimiters(class.bytes().to_vec())
+            .patterns(class.patterns());
+        match class.prefix() {
+            true => chunker.prefix(),
+            false => chunker.suffix(),
+        }
+    }
+
+    /// Put delimiter at the start of the next chunk (prefix mode).
+    pub fn prefix(mut self) -> Self {
+        self.prefix_mode = true;
+        self
+    }
+
+    /// Put delimiter at the end of the current chunk (suffix mode, default).
+    pub fn suffix(mut self) -> Self {
+        self.prefix_mode = false;
+        self
+    }
+
+    /// Enable consecutive delimiter/pattern handling.
+    pub fn consecutive(mut self) -> Self {
+        self.consecutive = true;
+        self
+    }
+
+    /// Enable forward fallback search.
+    ///
+    /// Chunks whose boundary lies past the target window are held back until
+    /// that boundary (or the end of the stream) arrives.
+    pub fn forward_fallback(mut self) -> Self {
+        self.forward_fallback = true;
+        self
+    }
+
+    /// Carry `overlap` bytes of trailing context from each chunk into the next.
+    ///
+    /// See [`Chunker::overlap`](crate::Chunker::overlap) for details.
+    pub fn overlap(mut self, overlap: usize) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Never split inside a multi-byte UTF-8 sequence.
+    ///
+    /// See [`Chunker::utf8_safe`](crate::Chunker::utf8_safe) for details.
+    pub fn utf8_safe(mut self, enabled: bool) -> Self {
+        self.utf8_safe = enabled;
+        self
+    }
+
+    /// Merge a trailing chunk smaller than `min_size` bytes into the previous chunk.
+    ///
+    /// Chunks are held back until at least `min_size` bytes follow them (or the
+    /// stream ends). See [`Chunker::min_size`](crate::Chunker::min_size).
+    pub fn min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Never emit a chunk longer than `max_size` bytes.
+    ///
+    /// See [`Chunker::max_size`](crate::Chunker::max_size) for details.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size.max(1));
+        self
+    }
+
+    /// Number of bytes pushed but not yet emitted.
+    pub fn pending(&self) -> usize {
+        self.buffer.len().saturating_sub(self.pos)
+    }
+
+    /// Where the stream has got to, for picking up later with
+    /// [`restore_state`](Self::restore_state).
+    ///
+    /// Covers every chunk returned so far; input pushed after
+    /// [`resume_from`](Checkpoint::resume_from) has to be pushed again.
+    /// See [`Checkpoint`] for an example.
+    pub fn save_state(&self) -> Checkpoint {
+        Checkpoint {
+            position: (self.consumed + self.pos) as u64,
+            resume_from: self.consumed as u64,
+            chunks: self.emitted as u64,
+            config: self.checksum(),
+        }
+    }
+
+    /// Pick up from `checkpoint`, dropping anything pushed so far: push the
+    /// input again from [`resume_from`](Checkpoint::resume_from) and the
+    /// chunks carry on as if the stream had never stopped.
+    ///
+    /// Fails if the checkpoint was saved with different settings.
+    pub fn restore_state(mut self, checkpoint: &Checkpoint) -> Result<Self, CheckpointMismatch> {
+        checkpoint.check(self.checksum())?;
+        self.buffer.clear();
+        self.consumed = checkpoint.resume_from as usize;
+        self.pos = checkpoint.position.saturating_sub(checkpoint.resume_from) as usize;
+        self.emitted = checkpoint.chunks as usize;
+        Ok(self)
+    }
+
+    /// Checksum of the settings, for [`Checkpoint::config`].
+    pub(crate) fn checksum(&self) -> u64 {
+        Checksum::new()
+            .size(self.target_size)
+            .bytes(&self.delimiters)
+            .number(self.pattern.is_some())
+            .bytes(self.pattern.as_deref().unwrap_or_default())
+            .number(self.patterns_checksum)
+            .number(self.prefix_mode)
+            .number(self.consecutive)
+            .number(self.forward_fallback)
+            .size(self.overlap)
+            .number(self.utf8_safe)
+            .size(self.min_size)
+            .option(self.max_size)
+            .finish()
+    }
+
+    /// Feed more input, returning every chunk that is now complete.
+    pub fn push(&mut self, data: &[u8]) -> Vec<Vec<u8>> {
+        let mut chunks = Vec::new();
+        self.push_with_ends(data, |chunk, _| chunks.push(chunk));
+        chunks
+    }
+
+    /// Feed more input, passing every chunk that is now complete to `emit`
+    /// with the input offset it ends at.
+    pub(crate) fn push_with_ends(&mut self, data: &[u8], emit: impl FnMut(Vec<u8>, usize)) {
+        self.buffer.extend_from_slice(data);
+        self.drain(false, emit);
+    }
+
+    /// Signal end of input, returning the remaining chunks.
+    ///
+    /// The chunker is reset afterwards and can be reused for a new stream.
+    pub fn finish(&mut self) -> Vec<Vec<u8>> {
+        let mut chunks = Vec::new();
+        self.finish_with_ends(|chunk, _| chunks.push(chunk));
+        chunks
+    }
+
+    /// Signal end of input, passing the remaining chunks to `emit` with the
+    /// input offset each ends at.
+    pub(crate) fn finish_with_ends(&mut self, emit: impl FnMut(Vec<u8>, usize)) {
+        self.drain(true, emit);
+        self.buffer.clear();
+        self.pos = 0;
+        self.consumed = 0;
+        self.emitted = 0;
+    }
+
+    /// Initialize lookup table if needed.
+    fn init(&mut self) {
+        if !self.initialized {
+            self.table = build_table(&self.delimiters);
+            self.initialized = true;
+        }
+    }
+
+    /// Emit chunks whose boundaries are final.
+    ///
+    /// The buffer always starts at the previous chunk's start, so overlap and
+    /// consecutive-run checks can look behind `pos` exactly as the batch
+    /// chunker does.
+    fn drain(&mut self, finished: bool, mut emit: impl FnMut(Vec<u8>, usize)) {
+        self.init();
+
+        while self.pos < self.buffer.len() {
+            let Some(split_at) = self.next_split(finished) else {
+                break;
+            };
+
+            let mut start = overlap_start(self.pos, 0, self.overlap);
+            if let Some(max_size) = self.max_size {
+                start = start.max(split_at.saturating_sub(max_size));
+            }
+            if self.utf8_safe {
+                start = utf8_next_boundary(&self.buffer, start, self.pos);
+            }
+            emit(
+                self.buffer[start..split_at].to_vec(),
+                self.consumed + split_at,
+            );
+            self.emitted += 1;
+
+            self.buffer.drain(..self.pos);
+            self.consumed += self.pos;
+            self.pos = split_at - self.pos;
+        }
+    }
+
+    /// End of the chunk at `self.pos`, or `None` if it depends on data that
+    /// hasn't been pushed yet.
+    fn next_split(&self, finished: bool) -> Option<usize> {
+        let len = self.buffer.len();
+        let pos = self.pos;
+
+        let window_complete = len - pos > self.target_size;
+        let mut split_at = if !window_complete {
+            len
+        } else {
+            let split_at = self.search(pos, pos + self.target_size, self.forward_fallback);
+            if self.utf8_safe {
+                utf8_split_boundary(&self.buffer, pos, split_at)
+            } else {
+                split_at
+            }
+        };
+
+        // Once the window is complete, more data only ever moves the boundary
+        // later, so an over-cap chunk is final
+        if let Some(max_size) = self.max_size
+            && split_at - pos > max_size
+            && (window_complete || finished)
+        {
+            return Some(self.capped_split(pos, max_size));
+        }
+
+        // A boundary at the end of the buffer may move once more data arrives
+        if split_at >= len && !finished {
+            return None;
+        }
+
+        // So may one followed by a tail that could still be undersized
+        if len - split_at < self.min_size {
+            if !finished {
+                return None;
+            }
+            split_at = len;
+            if let Some(max_size) = self.max_size
+                && split_at - pos > max_size
+            {
+                return Some(self.capped_split(pos, max_size));
+            }
+        }
+
+        Some(split_at)
+    }
+
+    /// Split for a chunk at `pos` that must not exceed `max_size` bytes.
+    fn capped_split(&self, pos: usize, max_size: usize) -> usize {
+        let cap = pos + max_size;
+        let split_at = self.search(pos, cap, false).min(cap);
+        if !self.utf8_safe || split_at < cap {
+            return split_at;
+        }
+        match utf8_prev_boundary(&self.buffer, pos, split_at) {
+            at if at > pos => at,
+            _ => split_at,
+        }
+    }
+
+    /// Find the chunk end for the window `pos..end`.
+    fn search(&self, pos: usize, end: usize, forward_fallback: bool) -> usize {
+        if self.multi_searcher.is_some() {
+            compute_split_at_combined(
+                &self.buffer,
+                pos,
+                end,
+                &self.delimiters,
+                self.table.as_ref(),
+                self.multi_searcher.as_ref(),
+                self.prefix_mode,
+                self.consecutive,
+                forward_fallback,
+            )
+        } else {
+            compute_split_at(
+                &self.buffer,
+                pos,
+                end,
+                self.pattern.as_deref(),
+                &self.delimiters,
+                self.table.as_ref(),
+                self.prefix_mode,
+                self.consecutive,
+                forward_fallback,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk;
+
+    /// Push `text` in pieces of `step` bytes and collect every chunk.
+    fn stream_all(mut stream: StreamChunker, text: &[u8], step: usize) -> Vec<Vec<u8>> {
+        let mut chunks = Vec::new();
+        for piece in text.chunks(step) {
+            chunks.extend(stream.push(piece));
+        }
+        chunks.extend(stream.finish());
+        chunks
+    }
+
+    fn batch_all<'a>(chunker: crate::Chunker<'a>) -> Vec<Vec<u8>> {
+        chunker.map(|c| c.to_vec()).collect()
+    }
+
+    const TEXT: &[u8] = b"The quick brown fox. Jumps over the lazy dog? Yes.\nAnother line here. And more text follows, without many breaks at all for a while. End.";
+
+    #[test]
+    fn test_stream_matches_batch() {
+        for step in [1, 3, 7, 16, 64, TEXT.len()] {
+            let stream = StreamChunker::new().size(20);
+            assert_eq!(
+                stream_all(stream, TEXT, step),
+                batch_all(chunk(TEXT).size(20))
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_emits_before_finish() {
+        let mut stream = StreamChunker::new().size(10).delimiters(b".".to_vec());
+        assert!(stream.push(b"Hello.").is_empty());
+        assert_eq!(stream.push(b" World."), vec![b"Hello.".to_vec()]);
+        assert_eq!(stream.pending(), 7);
+    }
+
+    #[test]
+    fn test_stream_retains_only_tail() {
+        let mut stream = StreamChunker::new().size(16).delimiters(b".".to_vec());
+        for _ in 0..1000 {
+            stream.push(b"Sentence here. ");
+        }
+        assert!(stream.buffer.len() <= 2 * 16);
+    }
+
+    #[test]
+    fn test_stream_prefix_and_patterns() {
+        for step in [1, 5, 32] {
+            let stream = StreamChunker::new()
+                .size(24)
+                .patterns(&[". ", "? "])
+                .prefix();
+            assert_eq!(
+                stream_all(stream, TEXT, step),
+                batch_all(chunk(TEXT).size(24).patterns(&[". ", "? "]).prefix())
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_delimiter_class() {
+        let text = b"# One\nIntro. More.\n## Two\nText\n";
+        for &class in Delimiters::ALL {
+            let stream = StreamChunker::new().size(12).delimiter_class(class);
+            assert_eq!(
+                stream_all(stream, text, 5),
+                batch_all(chunk(text).size(12).delimiter_class(class))
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_pattern_consecutive() {
+        let text = b"word   next   word   last";
+        for step in [1, 2, 9] {
+            let stream = StreamChunker::new()
+                .size(8)
+                .pattern(b" ".to_vec())
+                .consecutive();
+            assert_eq!(
+                stream_all(stream, text, step),
+                batch_all(chunk(text).size(8).pattern(b" ").consecutive())
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_forward_fallback() {
+        let text = b"averyveryverylongword next word";
+        for step in [1, 4, 11] {
+            let stream = StreamChunker::new()
+                .size(8)
+                .pattern(b" ".to_vec())
+                .forward_fallback();
+            assert_eq!(
+                stream_all(stream, text, step),
+                batch_all(chunk(text).size(8).pattern(b" ").forward_fallback())
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_overlap() {
+        for step in [1, 6, 50] {
+            let stream = StreamChunker::new().size(20).overlap(5);
+            assert_eq!(
+                stream_all(stream, TEXT, step),
+                batch_all(chunk(TEXT).size(20).overlap(5))
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_utf8_safe() {
+        let text = "héllo wörld ünïcödé téxt".as_bytes();
+        for step in [1, 2, 5] {
+            let stream = StreamChunker::new()
+                .size(5)
+                .delimiters(vec![])
+                .overlap(2)
+                .utf8_safe(true);
+            let chunks = stream_all(stream, text, step);
+            assert_eq!(
+                chunks,
+                batch_all(
+                    chunk(text)
+                        .size(5)
+                        .delimiters(b"")
+                        .overlap(2)
+                        .utf8_safe(true)
+                )
+            );
+            assert!(chunks.iter().all(|c| std::str::from_utf8(c).is_ok()));
+        }
+    }
+
+    #[test]
+    fn test_stream_min_size() {
+        let text = b"Hello. World. Hi.";
+        for step in [1, 4, 17] {
+            let stream = StreamChunker::new()
+                .size(14)
+                .delimiters(b".".to_vec())
+                .min_size(5);
+            assert_eq!(
+                stream_all(stream, text, step),
+                batch_all(chunk(text).size(14).delimiters(b".").min_size(5))
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_max_size() {
+        let text = b"averyveryverylongword next word. Hello. World. Hi.";
+        for step in [1, 3, 8, 64] {
+            let stream = StreamChunker::new()
+                .size(14)
+                .pattern(b" ".to_vec())
+                .forward_fallback()
+                .min_size(5)
+                .overlap(3)
+                .max_size(12);
+            let chunks = stream_all(stream, text, step);
+            assert!(chunks.iter().all(|c| c.len() <= 12));
+            assert_eq!(
+                chunks,
+                batch_all(
+                    chunk(text)
+                        .size(14)
+                        .pattern(b" ")
+                        .forward_fallback()
+                        .min_size(5)
+                        .overlap(3)
+                        .max_size(12)
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_max_size_waits_for_window() {
+        // The prefix split at the cap is only visible once the window is in
+        let text = b"ab cd efghijklmn";
+        for step in [1, 3, 8, 64] {
+            let stream = StreamChunker::new()
+                .size(10)
+                .delimiters(b" ".to_vec())
+                .prefix()
+                .max_size(5);
+            assert_eq!(
+                stream_all(stream, text, step),
+                batch_all(chunk(text).size(10).delimiters(b" ").prefix().max_size(5))
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_finish_resets() {
+        let mut stream = StreamChunker::new().size(10).delimiters(b".".to_vec());
+        stream.push(b"Hello. World.");
+        stream.finish();
+        assert_eq!(stream.pending(), 0);
+        let mut chunks = stream.push(b"Again.");
+        chunks.extend(stream.finish());
+        assert_eq!(chunks, vec![b"Again.".to_vec()]);
+    }
+
+    #[test]
+    fn test_stream_checkpoint() {
+        let settings = || {
+            StreamChunker::new()
+                .size(20)
+                .overlap(5)
+                .patterns(&["? "])
+                .min_size(8)
+        };
+        let expected = stream_all(settings(), TEXT, TEXT.len());
+        for cut in [0, 1, 30, 77, TEXT.len()] {
+            let mut stream = settings();
+            let mut chunks = stream.push(&TEXT[..cut]);
+            let checkpoint = stream.save_state();
+            assert_eq!(checkpoint.chunks, chunks.len() as u64);
+            assert!(checkpoint.resume_from <= checkpoint.position);
+
+            let stream = settings().restore_state(&checkpoint).unwrap();
+            let rest = &TEXT[checkpoint.resume_from as usize..];
+            chunks.extend(stream_all(stream, rest, 3));
+            assert_eq!(chunks, expected);
+        }
+
+        let checkpoint = settings().save_state();
+        let other = settings().patterns(&["! "]);
+        let err = other.restore_state(&checkpoint).err().unwrap();
+        assert_eq!(err.checkpoint, checkpoint.config);
+        assert_ne!(settings().size(21).save_state().config, checkpoint.config);
+    }
+
+    #[test]
+    fn test_stream_empty() {
+        let mut stream = StreamChunker::new();
+        assert!(stream.push(b"").is_empty());
+        assert!(stream.finish().is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+//! Headers rendered in front of chunks.
+//!
+//! This module provides [`ChunkTemplate`], which renders a header like
+//! `"[doc: {title} | chunk {i}/{n}]\n"` for every chunk from document
+//! metadata and the chunk's place in the document, e.g. to give each chunk of
+//! a RAG context its source.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+/// Error parsing a [`ChunkTemplate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// A `{` at this byte offset has no closing `}`.
+    Unclosed(usize),
+    /// A `}` at this byte offset doesn't close a field; write `}}` for a
+    /// literal brace.
+    Unopened(usize),
+    /// A field that's neither built in nor in the metadata.
+    UnknownField(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::Unclosed(at) => write!(f, "unclosed '{{' at byte {at}"),
+            TemplateError::Unopened(at) => write!(f, "unmatched '}}' at byte {at}"),
+            TemplateError::UnknownField(name) => write!(f, "unknown template field: {name:?}"),
+        }
+    }
+}
+
+impl core::error::Error for TemplateError {}
+
+/// One piece of a parsed template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    /// 1-based chunk number.
+    Number,
+    Count,
+    Start,
+    End,
+}
+
+/// A header template for chunks.
+///
+/// Fields are written `{name}`, and `{{` and `}}` are literal braces. The
+/// built-in fields are `{i}`, the chunk number counting from 1, `{n}`, the
+/// number of chunks, and `{start}` and `{end}`, the chunk's byte offsets.
+/// Any other field is filled in from the metadata when the template is
+/// created; metadata named like a built-in field is never used.
+///
+/// # Example
+///
+/// ```
+/// use chunk::{ChunkTemplate, chunk};
+///
+/// let text = b"One. Two.";
+/// let template = ChunkTemplate::new("[{title} {i}/{n}] ", &[("title", "Notes")]).unwrap();
+/// let offsets = chunk(text).size(5).delimiters(b".").collect_offsets();
+/// let chunks = template.render_chunks(text, &offsets);
+/// assert_eq!(chunks, vec![b"[Notes 1/2] One.".to_vec(), b"[Notes 2/2]  Two.".to_vec()]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkTemplate {
+    parts: Vec<Part>,
+}
+
+impl ChunkTemplate {
+    /// Parse `template`, filling in its non-built-in fields from `metadata`,
+    /// (name, value) pairs.
+    pub fn new(template: &str, metadata: &[(&str, &str)]) -> Result<Self, TemplateError> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template.char_indices();
+        while let Some((at, c)) = rest.next() {
+            match c {
+                '{' if template[at + 1..].starts_with('{') => {
+                    rest.next();
+                    literal.push('{');
+                }
+                '}' if template[at + 1..].starts_with('}') => {
+                    rest.next();
+                    literal.push('}');
+                }
+                '}' => return Err(TemplateError::Unopened(at)),
+                '{' => {
+                    let close = template[at..]
+                        .find('}')
+                        .ok_or(TemplateError::Unclosed(at))?
+                        + at;
+                    let name = &template[at + 1..close];
+                    // Skip past the closing brace
+                    while rest.next().is_some_and(|(i, _)| i < close) {}
+                    let part = match name {
+                        "i" => Part::Number,
+                        "n" => Part::Count,
+                        "start" => Part::Start,
+                        "end" => Part::End,
+                        _ => {
+                            let &(_, value) = metadata
+                                .iter()
+                                .find(|&&(key, _)| key == name)
+                                .ok_or_else(|| TemplateError::UnknownField(name.to_string()))?;
+                            literal.push_str(value);
+                            continue;
+                        }
+                    };
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(core::mem::take(&mut literal)));
+                    }
+                    parts.push(part);
+                }
+                _ => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Self { parts })
+    }
+
+    /// The header of chunk `index` (0-based) of `count` chunks, spanning
+    /// `start..end` of the document.
+    pub fn header(&self, index: usize, count: usize, start: usize, end: usize) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            // Writing to a String can't fail
+            let _ = match part {
+                Part::Literal(text) => out.write_str(text),
+                Part::Number => write!(out, "{}", index + 1),
+                Part::Count => write!(out, "{count}"),
+                Part::Start => write!(out, "{start}"),
+                Part::End => write!(out, "{end}"),
+            };
+        }
+        out
+    }
+
+    /// Each chunk of `text` at `offsets`, (start, end) byte offsets, with its
+    /// header in front.
+    pub fn render_chunks(&self, text: &[u8], offsets: &[(usize, usize)]) -> Vec<Vec<u8>> {
+        offsets
+            .iter()
+            .enumerate()
+            .map(|(index, &(start, end))| {
+                let mut chunk = self.header(index, offsets.len(), start, end).into_bytes();
+                chunk.extend_from_slice(&text[start..end]);
+                chunk
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_fields() {
+        let template =
+            ChunkTemplate::new("{{{src}}} {start}-{end} {i}/{n}: ", &[("src", "a.md")]).unwrap();
+        assert_eq!(template.header(0, 3, 10, 20), "{a.md} 10-20 1/3: ");
+        // Metadata can't shadow a built-in
+        let template = ChunkTemplate::new("{i}{ü}", &[("i", "x"), ("ü", "y")]).unwrap();
+        assert_eq!(template.header(4, 5, 0, 0), "5y");
+        assert_eq!(ChunkTemplate::new("", &[]).unwrap().header(0, 1, 0, 0), "");
+        assert_eq!(
+            ChunkTemplate::new("é{n}é", &[]).unwrap().header(0, 2, 0, 0),
+            "é2é"
+        );
+    }
+
+    #[test]
+    fn test_template_errors() {
+        assert_eq!(
+            ChunkTemplate::new("ab {title", &[]),
+            Err(TemplateError::Unclosed(3))
+        );
+        assert_eq!(
+            ChunkTemplate::new("a}b", &[]),
+            Err(TemplateError::Unopened(1))
+        );
+        assert_eq!(
+            ChunkTemplate::new("{title}", &[("name", "x")]),
+            Err(TemplateError::UnknownField("title".into()))
+        );
+    }
+}
@@ -0,0 +1,387 @@
+//! A small TOML reader for config files.
+//!
+//! Covers what a chunking profile needs: `[table]` headers (dotted names
+//! nest), `key = value` pairs, basic and literal strings, integers, finite
+//! floats, booleans, arrays and comments. Multi-line strings, `inf` and
+//! `nan`, dates and inline tables are rejected. Documents are read into a [`serde_json::Value`] so that
+//! configs from either format go through the same `Deserialize` impl.
+
+use serde_json::{Map, Value};
+
+/// A syntax error, with the 1-based line it was found on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TomlError {
+    pub(crate) line: usize,
+    pub(crate) message: String,
+}
+
+/// Parse `text` into a JSON object.
+pub(crate) fn parse(text: &str) -> Result<Value, TomlError> {
+    let mut parser = Parser {
+        text: text.as_bytes(),
+        pos: 0,
+        line: 1,
+    };
+    let mut root = Map::new();
+    let mut table: Vec<String> = Vec::new();
+    let mut defined: Vec<Vec<String>> = Vec::new();
+
+    loop {
+        parser.skip_blank_lines();
+        match parser.peek() {
+            None => break,
+            Some(b'[') => {
+                parser.pos += 1;
+                table = parser.table_header()?;
+                if defined.contains(&table) {
+                    return Err(parser.error(format!("table [{}] defined twice", table.join("."))));
+                }
+                parser.table(&mut root, &table)?;
+                defined.push(table.clone());
+            }
+            Some(_) => {
+                let key = parser.key()?;
+                parser.skip_space();
+                parser.expect(b'=')?;
+                parser.skip_space();
+                let value = parser.value()?;
+                parser.end_of_line()?;
+                let map = parser.table(&mut root, &table)?;
+                if map.contains_key(&key) {
+                    return Err(parser.error(format!("duplicate key `{key}`")));
+                }
+                map.insert(key, value);
+            }
+        }
+    }
+    Ok(Value::Object(root))
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: impl Into<String>) -> TomlError {
+        TomlError {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, b: u8) -> Result<(), TomlError> {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", b as char)))
+        }
+    }
+
+    /// Skip spaces and tabs.
+    fn skip_space(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skip a comment up to (not including) the newline.
+    fn skip_comment(&mut self) {
+        if self.peek() == Some(b'#') {
+            while !matches!(self.peek(), None | Some(b'\n')) {
+                self.pos += 1;
+            }
+        }
+    }
+
+    /// Skip whitespace, comments and newlines.
+    fn skip_blank_lines(&mut self) {
+        loop {
+            self.skip_space();
+            self.skip_comment();
+            match self.peek() {
+                Some(b'\n') => {
+                    self.pos += 1;
+                    self.line += 1;
+                }
+                Some(b'\r') => self.pos += 1,
+                _ => return,
+            }
+        }
+    }
+
+    /// Require nothing but a comment before the end of the line.
+    fn end_of_line(&mut self) -> Result<(), TomlError> {
+        self.skip_space();
+        self.skip_comment();
+        match self.peek() {
+            None | Some(b'\n' | b'\r') => Ok(()),
+            Some(_) => Err(self.error("expected a newline after the value")),
+        }
+    }
+
+    /// A bare or quoted key.
+    fn key(&mut self) -> Result<String, TomlError> {
+        match self.peek() {
+            Some(b'"') => self.basic_string(),
+            Some(b'\'') => self.literal_string(),
+            _ => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+                {
+                    self.pos += 1;
+                }
+                if self.pos == start {
+                    return Err(self.error("expected a key"));
+                }
+                Ok(String::from_utf8_lossy(&self.text[start..self.pos]).into_owned())
+            }
+        }
+    }
+
+    /// The dotted name of a `[table]` header, after the `[`.
+    fn table_header(&mut self) -> Result<Vec<String>, TomlError> {
+        if self.peek() == Some(b'[') {
+            return Err(self.error("arrays of tables are not supported"));
+        }
+        let mut names = Vec::new();
+        loop {
+            self.skip_space();
+            names.push(self.key()?);
+            self.skip_space();
+            match self.peek() {
+                Some(b'.') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    self.end_of_line()?;
+                    return Ok(names);
+                }
+                _ => return Err(self.error("expected `.` or `]` in table header")),
+            }
+        }
+    }
+
+    /// The table at `path`, created if missing.
+    fn table<'m>(
+        &self,
+        root: &'m mut Map<String, Value>,
+        path: &[String],
+    ) -> Result<&'m mut Map<String, Value>, TomlError> {
+        let mut map = root;
+        for name in path {
+            let entry = map
+                .entry(name.clone())
+                .or_insert_with(|| Value::Object(Map::new()));
+            map = match entry {
+                Value::Object(inner) => inner,
+                _ => return Err(self.error(format!("`{name}` is not a table"))),
+            };
+        }
+        Ok(map)
+    }
+
+    fn value(&mut self) -> Result<Value, TomlError> {
+        match self.peek() {
+            Some(b'"') => self.basic_string().map(Value::String),
+            Some(b'\'') => self.literal_string().map(Value::String),
+            Some(b'[') => self.array(),
+            Some(b't' | b'f') => self.boolean(),
+            Some(b'+' | b'-' | b'0'..=b'9') => self.number(),
+            Some(b'{') => Err(self.error("inline tables are not supported")),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn basic_string(&mut self) -> Result<String, TomlError> {
+        self.pos += 1;
+        if self.text[self.pos..].starts_with(b"\"\"") {
+            return Err(self.error("multi-line strings are not supported"));
+        }
+        let mut out = Vec::new();
+        loop {
+            let Some(b) = self.peek() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += 1;
+            match b {
+                b'"' => break,
+                b'\n' => return Err(self.error("unterminated string")),
+                b'\\' => {
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.pos += 1;
+                    match escaped {
+                        b'n' => out.push(b'\n'),
+                        b't' => out.push(b'\t'),
+                        b'r' => out.push(b'\r'),
+                        b'b' => out.push(0x08),
+                        b'f' => out.push(0x0C),
+                        b'"' => out.push(b'"'),
+                        b'\\' => out.push(b'\\'),
+                        b'u' => self.unicode_escape(4, &mut out)?,
+                        b'U' => self.unicode_escape(8, &mut out)?,
+                        other => {
+                            return Err(self.error(format!("invalid escape `\\{}`", other as char)));
+                        }
+                    }
+                }
+                _ => out.push(b),
+            }
+        }
+        String::from_utf8(out).map_err(|_| self.error("string is not valid UTF-8"))
+    }
+
+    fn unicode_escape(&mut self, digits: usize, out: &mut Vec<u8>) -> Result<(), TomlError> {
+        let hex = self
+            .text
+            .get(self.pos..self.pos + digits)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += digits;
+        out.extend_from_slice(hex.encode_utf8(&mut [0; 4]).as_bytes());
+        Ok(())
+    }
+
+    fn literal_string(&mut self) -> Result<String, TomlError> {
+        self.pos += 1;
+        if self.text[self.pos..].starts_with(b"''") {
+            return Err(self.error("multi-line strings are not supported"));
+        }
+        let start = self.pos;
+        let len = memchr::memchr2(b'\'', b'\n', &self.text[start..])
+            .filter(|&i| self.text[start + i] == b'\'')
+            .ok_or_else(|| self.error("unterminated string"))?;
+        self.pos = start + len + 1;
+        String::from_utf8(self.text[start..start + len].to_vec())
+            .map_err(|_| self.error("string is not valid UTF-8"))
+    }
+
+    fn array(&mut self) -> Result<Value, TomlError> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank_lines();
+            if self.peek() == Some(b']') {
+                self.pos += 1;
+                return Ok(Value::Array(items));
+            }
+            items.push(self.value()?);
+            self.skip_blank_lines();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {}
+                _ => return Err(self.error("expected `,` or `]` in array")),
+            }
+        }
+    }
+
+    fn boolean(&mut self) -> Result<Value, TomlError> {
+        for (word, value) in [(&b"true"[..], true), (b"false", false)] {
+            if self.text[self.pos..].starts_with(word) {
+                self.pos += word.len();
+                return Ok(Value::Bool(value));
+            }
+        }
+        Err(self.error("expected a value"))
+    }
+
+    fn number(&mut self) -> Result<Value, TomlError> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'_' | b'.'))
+        {
+            self.pos += 1;
+        }
+        let digits: String = String::from_utf8_lossy(&self.text[start..self.pos]).replace('_', "");
+        if let Ok(integer) = digits.parse::<i64>() {
+            return Ok(Value::from(integer));
+        }
+        // Rust also reads `1.`, which TOML doesn't allow
+        let float = digits.ends_with(|b: char| b.is_ascii_digit());
+        digits
+            .parse::<f64>()
+            .ok()
+            .filter(|value| float && value.is_finite())
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| self.error(format!("`{digits}` is not a supported number")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_toml_profiles() {
+        let text = r#"
+# Shared profiles
+[rag-default]
+size = 1_024
+delimiters = "\n.?"   # sentence ends
+utf8_safe = true
+
+[code]
+size = 2048
+patterns = [
+    "\n\n",
+    'fn ',
+]
+"#;
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({
+                "rag-default": {"size": 1024, "delimiters": "\n.?", "utf8_safe": true},
+                "code": {"size": 2048, "patterns": ["\n\n", "fn "]},
+            })
+        );
+    }
+
+    #[test]
+    fn test_toml_escapes_and_nesting() {
+        let text = "pattern = \"\\u2581\"\n[a.b]\nx = [10, -2, 0.25, 1e-3]\n";
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({"pattern": "▁", "a": {"b": {"x": [10, -2, 0.25, 0.001]}}})
+        );
+    }
+
+    #[test]
+    fn test_toml_errors() {
+        let err = |text| parse(text).unwrap_err();
+        assert_eq!(err("size = 1\nsize = 2\n").line, 2);
+        assert_eq!(err("size = 1\nsize = 2\n").message, "duplicate key `size`");
+        assert_eq!(err("[a]\n[a]\n").message, "table [a] defined twice");
+        assert_eq!(
+            err("x = 1.5.2\n").message,
+            "`1.5.2` is not a supported number"
+        );
+        assert_eq!(err("x = 1.\n").message, "`1.` is not a supported number");
+        assert_eq!(
+            err("x = +inf\n").message,
+            "`+inf` is not a supported number"
+        );
+        assert_eq!(err("x = \"open\n").message, "unterminated string");
+        assert_eq!(
+            err("x = 1 2\n").message,
+            "expected a newline after the value"
+        );
+        assert_eq!(
+            err("x = {a = 1}\n").message,
+            "inline tables are not supported"
+        );
+    }
+}
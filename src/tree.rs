@@ -0,0 +1,163 @@
+//! Chunks as a tree of sections.
+//!
+//! This module provides [`ChunkTree`], the chunks of a document grouped by
+//! the heading they sit under, with the subsections of every heading as its
+//! children, so per-section work (e.g. a summary of each section) can be
+//! rolled up the hierarchy with [`ChunkTree::fold`].
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A section of a document: the chunks directly under one heading, and the
+/// sections under its subheadings.
+///
+/// The root has no heading and level 0, and holds the chunks before the
+/// first heading. A section's own chunks come before those of its children
+/// in the text, so [`flatten`](Self::flatten) gives the chunks back in
+/// order.
+///
+/// Built by [`MarkdownChunker::chunk_tree`](crate::MarkdownChunker::chunk_tree)
+/// and, with the `documents` feature, `Document::chunk_tree`.
+///
+/// # Example
+///
+/// ```
+/// use chunk::MarkdownChunker;
+///
+/// let doc = b"# Guide\n\nIntro.\n\n## Install\n\nRun it.\n\n## Usage\n\nCall it.\n";
+/// let tree = MarkdownChunker::new().size(64).chunk_tree(doc);
+/// let guide = &tree.children[0];
+/// assert_eq!(guide.heading, "# Guide");
+/// assert_eq!(guide.chunks[0].text, b"# Guide\n\nIntro.\n\n");
+/// let headings: Vec<_> = guide.children.iter().map(|s| s.heading.as_str()).collect();
+/// assert_eq!(headings, ["## Install", "## Usage"]);
+///
+/// // Roll up, e.g. the size of every section with its subsections
+/// let size = tree.fold(|section, children: Vec<usize>| {
+///     section.chunks.iter().map(|c| c.text.len()).sum::<usize>() + children.iter().sum::<usize>()
+/// });
+/// assert_eq!(size, doc.len());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ChunkTree<C> {
+    /// The heading, as in the chunks' heading paths. Empty for the root.
+    pub heading: String,
+    /// The heading level, 1 and up. 0 for the root.
+    pub level: usize,
+    /// Chunks under this heading but not under any of its subheadings.
+    pub chunks: Vec<C>,
+    /// Sections under the subheadings, in document order.
+    pub children: Vec<ChunkTree<C>>,
+}
+
+impl<C> Default for ChunkTree<C> {
+    fn default() -> Self {
+        Self {
+            heading: String::new(),
+            level: 0,
+            chunks: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
+
+impl<C> ChunkTree<C> {
+    /// Whether the tree holds no chunks.
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty() && self.children.iter().all(Self::is_empty)
+    }
+
+    /// All chunks in the tree, in document order.
+    pub fn flatten(&self) -> Vec<&C> {
+        let mut chunks = Vec::new();
+        self.collect(&mut chunks);
+        chunks
+    }
+
+    fn collect<'a>(&'a self, chunks: &mut Vec<&'a C>) {
+        chunks.extend(&self.chunks);
+        for child in &self.children {
+            child.collect(chunks);
+        }
+    }
+
+    /// Compute a value for every section from the section and the values of
+    /// its children, bottom up, giving the root's.
+    pub fn fold<T>(&self, mut f: impl FnMut(&Self, Vec<T>) -> T) -> T {
+        self.fold_with(&mut f)
+    }
+
+    fn fold_with<T, F: FnMut(&Self, Vec<T>) -> T>(&self, f: &mut F) -> T {
+        let children = self.children.iter().map(|c| c.fold_with(f)).collect();
+        f(self, children)
+    }
+
+    /// Add `chunk` to the section at `path`, as (level, heading) from the
+    /// top.
+    ///
+    /// The first `known` headings may continue the last sections opened at
+    /// their depth if the heading matches; deeper ones always open new
+    /// sections, e.g. for headings inside the chunk.
+    pub(crate) fn insert<S>(
+        &mut self,
+        path: impl IntoIterator<Item = (usize, S)>,
+        known: usize,
+        chunk: C,
+    ) where
+        S: AsRef<str>,
+    {
+        let mut node = self;
+        for (depth, (level, heading)) in path.into_iter().enumerate() {
+            let heading = heading.as_ref();
+            let open = depth < known
+                && node
+                    .children
+                    .last()
+                    .is_some_and(|last| last.level == level && last.heading == heading);
+            if !open {
+                node.children.push(ChunkTree {
+                    heading: String::from(heading),
+                    level,
+                    ..ChunkTree::default()
+                });
+            }
+            node = node.children.last_mut().expect("section was just pushed");
+        }
+        node.chunks.push(chunk);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_insert() {
+        let mut tree = ChunkTree::default();
+        tree.insert([(1, "A")], 1, 0);
+        tree.insert([(1, "A"), (2, "B")], 1, 1);
+        tree.insert([(1, "A"), (2, "B")], 2, 2);
+        // Same heading again, but a new section
+        tree.insert([(1, "A"), (2, "B")], 1, 3);
+        tree.insert::<&str>([], 0, 4);
+        assert_eq!(tree.chunks, vec![4]);
+        let a = &tree.children[0];
+        assert_eq!((a.level, a.chunks.as_slice()), (1, &[0][..]));
+        let b: Vec<_> = a.children.iter().map(|s| s.chunks.clone()).collect();
+        assert_eq!(b, vec![vec![1, 2], vec![3]]);
+        assert_eq!(tree.flatten(), vec![&4, &0, &1, &2, &3]);
+    }
+
+    #[test]
+    fn test_fold() {
+        let mut tree = ChunkTree::default();
+        assert!(tree.is_empty());
+        tree.insert([(1, "A"), (2, "B"), (3, "C")], 0, ());
+        assert!(!tree.is_empty());
+        let depth =
+            tree.fold(|_, children: Vec<usize>| 1 + children.into_iter().max().unwrap_or(0));
+        assert_eq!(depth, 4);
+    }
+}
@@ -0,0 +1,260 @@
+//! Fixed-stride sliding windows.
+//!
+//! This module provides [`WindowChunker`], which emits windows of a fixed
+//! size that start every `stride` bytes, the usual overlapping baseline for
+//! retrieval. Windows can optionally be snapped to delimiter boundaries.
+
+use alloc::vec::Vec;
+
+use crate::delim::{
+    DEFAULT_TARGET_SIZE, build_table, find_last_delimiter, utf8_prev_boundary, utf8_split_boundary,
+};
+
+/// A chunker that emits fixed-size windows at a fixed stride.
+///
+/// Window `k` nominally covers `k * stride .. k * stride + size`, and the
+/// last window is the first one that reaches the end of the text. A stride
+/// below the size makes consecutive windows overlap; the default stride
+/// equals the size, giving back-to-back windows.
+///
+/// With [`delimiters`](Self::delimiters), each window starts just after the
+/// last delimiter at or before its nominal start (but after the previous
+/// window's start), and ends just after the last delimiter it contains.
+/// Without a delimiter to snap to, the window keeps its nominal edge. Starts
+/// stay on the stride grid, so snapping never accumulates drift.
+///
+/// # Example
+///
+/// ```
+/// use chunk::WindowChunker;
+///
+/// let offsets = WindowChunker::new().size(4).stride(2).chunk_offsets(b"abcdefgh");
+/// assert_eq!(offsets, vec![(0, 4), (2, 6), (4, 8)]);
+///
+/// let text = b"One two three four";
+/// let chunks = WindowChunker::new().size(10).stride(4).delimiters(b" ").chunks(text);
+/// assert_eq!(chunks[0], b"One two ");
+/// assert_eq!(chunks[1], b"two three ");
+/// ```
+#[derive(Debug, Clone)]
+pub struct WindowChunker {
+    target_size: usize,
+    stride: Option<usize>,
+    delimiters: Vec<u8>,
+    utf8_safe: bool,
+}
+
+impl Default for WindowChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WindowChunker {
+    /// Create a window chunker with the default size and a stride equal to it.
+    pub fn new() -> Self {
+        Self {
+            target_size: DEFAULT_TARGET_SIZE,
+            stride: None,
+            delimiters: Vec::new(),
+            utf8_safe: false,
+        }
+    }
+
+    /// Set the window size in bytes.
+    pub fn size(mut self, size: usize) -> Self {
+        self.target_size = size;
+        self
+    }
+
+    /// Set the distance in bytes between the nominal starts of windows.
+    pub fn stride(mut self, stride: usize) -> Self {
+        self.stride = Some(stride);
+        self
+    }
+
+    /// Snap window edges to just after one of these delimiter bytes.
+    ///
+    /// An empty set (the default) keeps windows at their exact offsets.
+    pub fn delimiters(mut self, delimiters: &[u8]) -> Self {
+        self.delimiters = delimiters.to_vec();
+        self
+    }
+
+    /// Never place a window edge inside a multi-byte UTF-8 sequence.
+    ///
+    /// See [`Chunker::utf8_safe`](crate::Chunker::utf8_safe) for details.
+    pub fn utf8_safe(mut self, enabled: bool) -> Self {
+        self.utf8_safe = enabled;
+        self
+    }
+
+    /// Chunk `text`, returning (start, end) byte offsets.
+    pub fn chunk_offsets(&self, text: &[u8]) -> Vec<(usize, usize)> {
+        let size = self.target_size.max(1);
+        let stride = self.stride.unwrap_or(size).max(1);
+        let table = build_table(&self.delimiters);
+
+        let mut offsets = Vec::new();
+        let mut prev = None;
+        let mut nominal = 0;
+        while nominal < text.len() {
+            let start = self.window_start(text, nominal, prev, table.as_ref());
+            let end = self.window_end(text, start, size, table.as_ref());
+            offsets.push((start, end));
+            if end == text.len() {
+                break;
+            }
+            prev = Some(start);
+            nominal += stride;
+            if nominal >= text.len() {
+                // Ends snapped back left a tail past the last nominal start
+                nominal = end;
+            }
+        }
+        offsets
+    }
+
+    /// Chunk `text`, returning the windows as slices.
+    pub fn chunks<'t>(&self, text: &'t [u8]) -> Vec<&'t [u8]> {
+        self.chunk_offsets(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect()
+    }
+
+    /// Start of the window whose nominal start is `nominal`, always after
+    /// the previous window's start `prev`.
+    fn window_start(
+        &self,
+        text: &[u8],
+        nominal: usize,
+        prev: Option<usize>,
+        table: Option<&[bool; 256]>,
+    ) -> usize {
+        let floor = prev.map_or(0, |p| p + 1);
+        if nominal <= floor {
+            return nominal;
+        }
+        if !self.delimiters.is_empty()
+            && let Some(i) = find_last_delimiter(&text[floor..nominal], &self.delimiters, table)
+        {
+            return floor + i + 1;
+        }
+        if self.utf8_safe {
+            return utf8_prev_boundary(text, floor, nominal);
+        }
+        nominal
+    }
+
+    /// End of the window starting at `start`.
+    fn window_end(
+        &self,
+        text: &[u8],
+        start: usize,
+        size: usize,
+        table: Option<&[bool; 256]>,
+    ) -> usize {
+        let end = start.saturating_add(size).min(text.len());
+        if end == text.len() {
+            return end;
+        }
+        if !self.delimiters.is_empty()
+            && let Some(i) = find_last_delimiter(&text[start..end], &self.delimiters, table)
+        {
+            return start + i + 1;
+        }
+        if self.utf8_safe {
+            return utf8_split_boundary(text, start, end);
+        }
+        end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_stride() {
+        let chunker = WindowChunker::new().size(4).stride(3);
+        assert_eq!(
+            chunker.chunk_offsets(b"abcdefghij"),
+            vec![(0, 4), (3, 7), (6, 10)]
+        );
+        // The last window is the first to reach the end, even when short
+        assert_eq!(
+            chunker.chunk_offsets(b"abcdefghijk"),
+            vec![(0, 4), (3, 7), (6, 10), (9, 11)]
+        );
+        // The default stride gives back-to-back windows
+        assert_eq!(
+            WindowChunker::new().size(4).chunk_offsets(b"abcdefghij"),
+            vec![(0, 4), (4, 8), (8, 10)]
+        );
+        // A stride above the size skips bytes between windows
+        assert_eq!(
+            WindowChunker::new()
+                .size(2)
+                .stride(4)
+                .chunk_offsets(b"abcdefghij"),
+            vec![(0, 2), (4, 6), (8, 10)]
+        );
+        assert!(chunker.chunk_offsets(b"").is_empty());
+        assert_eq!(WindowChunker::new().chunk_offsets(b"short"), vec![(0, 5)]);
+    }
+
+    #[test]
+    fn test_window_delimiters() {
+        let text = b"aa bb cc dd ee ff";
+        let offsets = WindowChunker::new()
+            .size(7)
+            .stride(4)
+            .delimiters(b" ")
+            .chunk_offsets(text);
+        // Nominal starts 0, 4, 8, 12 snap back to 0, 3, 6, 12
+        assert_eq!(offsets, vec![(0, 6), (3, 9), (6, 12), (12, 17)]);
+        for &(start, end) in &offsets[..offsets.len() - 1] {
+            assert_eq!(text[end - 1], b' ');
+            assert!(start == 0 || text[start - 1] == b' ');
+        }
+
+        // A window without a delimiter inside keeps its hard end
+        let offsets = WindowChunker::new()
+            .size(6)
+            .stride(2)
+            .delimiters(b" ")
+            .chunk_offsets(b"a bbbbbbbb");
+        assert_eq!(offsets, vec![(0, 2), (2, 8), (4, 10)]);
+
+        // Without a delimiter in range, edges stay where they are
+        let offsets = WindowChunker::new()
+            .size(4)
+            .stride(2)
+            .delimiters(b".")
+            .chunk_offsets(b"abcdefgh");
+        assert_eq!(offsets, vec![(0, 4), (2, 6), (4, 8)]);
+
+        // Snapped ends can leave a tail past the last nominal start, which
+        // still gets a window
+        let offsets = WindowChunker::new()
+            .size(6)
+            .delimiters(b" ")
+            .chunk_offsets(b"abc de fghij");
+        assert_eq!(offsets, vec![(0, 4), (4, 7), (7, 12)]);
+    }
+
+    #[test]
+    fn test_window_utf8_safe() {
+        let text = "aé€bc".as_bytes();
+        let offsets = WindowChunker::new()
+            .size(2)
+            .stride(2)
+            .utf8_safe(true)
+            .chunk_offsets(text);
+        for &(start, end) in &offsets {
+            assert!(std::str::from_utf8(&text[start..end]).is_ok());
+        }
+        assert_eq!(offsets.last().unwrap().1, text.len());
+    }
+}
@@ -0,0 +1,191 @@
+//! A minimal zip reader, shared by the `epub` and `archive` features.
+//!
+//! Only stored and deflated entries are read; ZIP64 and encryption aren't
+//! supported, so the features only pull in a deflate decoder.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A file in a zip archive, from the central directory.
+#[derive(Debug, Clone)]
+pub(crate) struct Entry {
+    /// The path in the archive, lossily decoded as UTF-8.
+    pub(crate) name: String,
+    method: u16,
+    compressed_size: usize,
+    size: usize,
+    /// Offset of the local file header.
+    offset: usize,
+}
+
+/// Why an entry couldn't be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReadError {
+    /// The archive is truncated or malformed.
+    Invalid,
+    /// The entry is compressed with a method other than stored or deflate.
+    UnsupportedCompression(u16),
+}
+
+/// Signature of the end of central directory record.
+const END_SIGNATURE: u32 = 0x0605_4b50;
+/// Signature of a central directory file header.
+const ENTRY_SIGNATURE: u32 = 0x0201_4b50;
+/// Signature of a local file header.
+const LOCAL_SIGNATURE: u32 = 0x0403_4b50;
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<usize> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?) as usize)
+}
+
+/// Whether `data` starts like a zip archive, with a local file header or,
+/// for an empty archive, the end record.
+#[cfg_attr(not(feature = "archive"), allow(dead_code))]
+pub(crate) fn is_zip(data: &[u8]) -> bool {
+    [LOCAL_SIGNATURE, END_SIGNATURE]
+        .iter()
+        .any(|&signature| data.starts_with(&signature.to_le_bytes()))
+}
+
+/// The entries of the zip archive `data`, or `None` if it isn't one.
+pub(crate) fn entries(data: &[u8]) -> Option<Vec<Entry>> {
+    // The end record is 22 bytes plus a comment of up to 64 KiB
+    let search = data.len().saturating_sub(22 + 0xffff);
+    let end = (search..=data.len().checked_sub(22)?)
+        .rev()
+        .find(|&at| read_u32(data, at) == Some(END_SIGNATURE as usize))?;
+    let count = read_u16(data, end + 10)?;
+    let mut at = read_u32(data, end + 16)?;
+
+    let mut entries = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        if read_u32(data, at)? != ENTRY_SIGNATURE as usize {
+            return None;
+        }
+        let name_len = read_u16(data, at + 28)? as usize;
+        let extra_len = read_u16(data, at + 30)? as usize;
+        let comment_len = read_u16(data, at + 32)? as usize;
+        let name = data.get(at + 46..at + 46 + name_len)?;
+        entries.push(Entry {
+            name: String::from_utf8_lossy(name).into_owned(),
+            method: read_u16(data, at + 10)?,
+            compressed_size: read_u32(data, at + 20)?,
+            size: read_u32(data, at + 24)?,
+            offset: read_u32(data, at + 42)?,
+        });
+        at += 46 + name_len + extra_len + comment_len;
+    }
+    Some(entries)
+}
+
+impl Entry {
+    /// Whether the entry is a directory rather than a file.
+    #[cfg_attr(not(feature = "archive"), allow(dead_code))]
+    pub(crate) fn is_dir(&self) -> bool {
+        self.name.ends_with('/')
+    }
+
+    /// Offset of the entry's data in `data`, after its local file header.
+    fn data_start(&self, data: &[u8]) -> Option<usize> {
+        let at = self.offset;
+        if read_u32(data, at)? != LOCAL_SIGNATURE as usize {
+            return None;
+        }
+        let name_len = read_u16(data, at + 26)? as usize;
+        let extra_len = read_u16(data, at + 28)? as usize;
+        Some(at + 30 + name_len + extra_len)
+    }
+
+    /// The entry's contents, from the archive `data` it was listed from.
+    pub(crate) fn read(&self, data: &[u8]) -> Result<Vec<u8>, ReadError> {
+        let compressed = self
+            .data_start(data)
+            .and_then(|start| data.get(start..start + self.compressed_size))
+            .ok_or(ReadError::Invalid)?;
+        match self.method {
+            0 => Ok(compressed.to_vec()),
+            8 => miniz_oxide::inflate::decompress_to_vec_with_limit(compressed, self.size)
+                .map_err(|_| ReadError::Invalid),
+            method => Err(ReadError::UnsupportedCompression(method)),
+        }
+    }
+}
+
+/// A zip archive of `(name, contents, deflate)` files.
+#[cfg(test)]
+pub(crate) fn build(files: &[(&str, &str, bool)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+    for &(name, contents, deflate) in files {
+        let data = if deflate {
+            miniz_oxide::deflate::compress_to_vec(contents.as_bytes(), 6)
+        } else {
+            contents.as_bytes().to_vec()
+        };
+        let method: u16 = if deflate { 8 } else { 0 };
+        let offset = out.len() as u32;
+        // Version, flags, method, time, date and CRC (not checked)
+        let fields = [
+            &20u16.to_le_bytes()[..],
+            &[0; 2],
+            &method.to_le_bytes(),
+            &[0; 8],
+        ];
+        let sizes = [
+            (data.len() as u32).to_le_bytes(),
+            (contents.len() as u32).to_le_bytes(),
+        ];
+        out.extend(LOCAL_SIGNATURE.to_le_bytes());
+        out.extend(fields.concat());
+        out.extend(sizes.concat());
+        out.extend((name.len() as u16).to_le_bytes());
+        out.extend([0; 2]);
+        out.extend(name.as_bytes());
+        out.extend(&data);
+
+        central.extend(ENTRY_SIGNATURE.to_le_bytes());
+        central.extend(20u16.to_le_bytes());
+        central.extend(fields.concat());
+        central.extend(sizes.concat());
+        central.extend((name.len() as u16).to_le_bytes());
+        // Extra and comment lengths, disk, attributes
+        central.extend([0; 12]);
+        central.extend(offset.to_le_bytes());
+        central.extend(name.as_bytes());
+    }
+    let central_offset = out.len() as u32;
+    out.extend(&central);
+    out.extend(END_SIGNATURE.to_le_bytes());
+    out.extend([0; 4]);
+    out.extend((files.len() as u16).to_le_bytes());
+    out.extend((files.len() as u16).to_le_bytes());
+    out.extend((central.len() as u32).to_le_bytes());
+    out.extend(central_offset.to_le_bytes());
+    out.extend([0; 2]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zip_entries() {
+        let data = build(&[("a/", "", false), ("a/b.txt", "Hello, zip.", true)]);
+        assert!(is_zip(&data));
+        let listed = entries(&data).unwrap();
+        let names: Vec<_> = listed
+            .iter()
+            .map(|e| (e.name.as_str(), e.is_dir()))
+            .collect();
+        assert_eq!(names, vec![("a/", true), ("a/b.txt", false)]);
+        assert_eq!(listed[1].read(&data).unwrap(), b"Hello, zip.");
+        assert_eq!(listed[1].read(&data[..40]), Err(ReadError::Invalid));
+        assert!(is_zip(&build(&[])) && entries(&build(&[])).unwrap().is_empty());
+        assert!(!is_zip(b"not a zip") && entries(b"not a zip").is_none());
+    }
+}
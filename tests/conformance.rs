@@ -0,0 +1,78 @@
+//! Golden-file conformance: the fixtures in `tests/conformance/` hold
+//! expected offsets that the Rust crate, the Python package and the WASM
+//! package must all reproduce. See `tests/conformance/README.md`.
+
+use std::fs;
+use std::path::Path;
+
+use chunk::{ChunkerConfig, SizeUnit};
+use serde_json::Value;
+
+/// Apply fixture `options` to a config, the same mapping every runner uses.
+fn config(options: &Value) -> ChunkerConfig {
+    let mut config = ChunkerConfig::new();
+    let options = options.as_object().expect("options must be an object");
+    for (key, value) in options {
+        let number = || value.as_u64().expect("expected a number") as usize;
+        let flag = || value.as_bool().expect("expected a boolean");
+        let string = || value.as_str().expect("expected a string").as_bytes();
+        config = match key.as_str() {
+            "size" => config.size(number()),
+            "delimiters" => config.delimiters(string()),
+            "pattern" => config.pattern(string()),
+            "patterns" => {
+                let patterns: Vec<_> = value
+                    .as_array()
+                    .expect("expected an array")
+                    .iter()
+                    .map(|p| p.as_str().expect("expected a string"))
+                    .collect();
+                config.patterns(&patterns)
+            }
+            "prefix" => config.prefix(flag()),
+            "consecutive" => config.consecutive(flag()),
+            "forward_fallback" => config.forward_fallback(flag()),
+            "delimiter_priority" => config.delimiter_priority(flag()),
+            "slack" => config.slack(number()),
+            "balanced" => config.balanced(flag()),
+            "lines_per_chunk" => config.lines_per_chunk(Some(number())),
+            "overlap" => config.overlap(number()),
+            "utf8_safe" => config.utf8_safe(flag()),
+            "trim" => config.trim(flag()),
+            "skip_empty" => config.skip_empty(flag()),
+            "unit" => config.unit(match value.as_str() {
+                Some("bytes") => SizeUnit::Bytes,
+                Some("chars") => SizeUnit::Chars,
+                _ => panic!("unit must be \"bytes\" or \"chars\""),
+            }),
+            "min_size" => config.min_size(number()),
+            "max_size" => config.max_size(Some(number())),
+            _ => panic!("unknown option `{key}`"),
+        };
+    }
+    config
+}
+
+#[test]
+fn test_conformance_fixtures() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/conformance");
+    let mut files: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    assert!(!files.is_empty(), "no fixtures in {}", dir.display());
+
+    for file in files {
+        let fixture: Value = serde_json::from_str(&fs::read_to_string(&file).unwrap()).unwrap();
+        for case in fixture["cases"].as_array().unwrap() {
+            let name = case["name"].as_str().unwrap();
+            let text = case["text"].as_str().unwrap().as_bytes();
+            let expected: Vec<(usize, usize)> =
+                serde_json::from_value(case["offsets"].clone()).unwrap();
+            let offsets = config(&case["options"]).chunker(text).collect_offsets();
+            assert_eq!(offsets, expected, "{}: {name}", file.display());
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Parquet interop: `tests/fixtures/chunks.parquet` is what [`ParquetWriter`]
+//! writes for a small corpus, checked byte for byte and read back with the
+//! `parquet` crate, an implementation independent of ours. CI also reads it
+//! with pyarrow. Rerun with `UPDATE_FIXTURES=1` after an intended format
+//! change.
+
+use std::fs::{self, File};
+use std::path::Path;
+
+use chunk::{ParquetWriter, chunk};
+use parquet::basic::{ConvertedType, Type};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::RowAccessor;
+
+const FIXTURE: &str = "tests/fixtures/chunks.parquet";
+
+/// Documents covering several row groups, an empty document, non-ASCII text
+/// and invalid UTF-8.
+const DOCS: [&[u8]; 3] = [
+    b"One. Two. Three. Four.",
+    b"",
+    "Caf\u{e9} au lait. \u{4f60}\u{597d}. ".as_bytes(),
+];
+
+fn offsets(text: &[u8]) -> Vec<(usize, usize)> {
+    chunk(text).size(8).delimiters(b".").collect_offsets()
+}
+
+fn write_corpus() -> Vec<u8> {
+    let mut writer = ParquetWriter::new(Vec::new()).unwrap().row_group_bytes(16);
+    for (doc_id, text) in DOCS.iter().enumerate() {
+        writer
+            .write_chunks(doc_id as u64, text, &offsets(text))
+            .unwrap();
+    }
+    writer.write_chunks(3, b"ok \xff.", &[(0, 5)]).unwrap();
+    writer.finish().unwrap()
+}
+
+#[test]
+fn test_parquet_golden_file() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURE);
+    let file = write_corpus();
+    if std::env::var_os("UPDATE_FIXTURES").is_some() {
+        fs::write(&path, &file).unwrap();
+    }
+    let golden = fs::read(&path).expect("missing fixture, rerun with UPDATE_FIXTURES=1");
+    assert!(golden == file, "{FIXTURE} is out of date");
+}
+
+#[test]
+fn test_parquet_crate_reads_fixture() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURE);
+    let reader = SerializedFileReader::new(File::open(path).unwrap()).unwrap();
+
+    let schema = reader.metadata().file_metadata().schema_descr();
+    let columns: Vec<_> = schema
+        .columns()
+        .iter()
+        .map(|column| {
+            (
+                column.name(),
+                column.physical_type(),
+                column.converted_type(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        columns,
+        [
+            ("doc_id", Type::INT64, ConvertedType::UINT_64),
+            ("chunk_index", Type::INT64, ConvertedType::UINT_64),
+            ("start", Type::INT64, ConvertedType::UINT_64),
+            ("end", Type::INT64, ConvertedType::UINT_64),
+            ("text", Type::BYTE_ARRAY, ConvertedType::UTF8),
+        ]
+    );
+    assert!(reader.num_row_groups() > 1);
+
+    let mut expected = Vec::new();
+    for (doc_id, text) in DOCS.iter().enumerate() {
+        for (index, &(start, end)) in offsets(text).iter().enumerate() {
+            let value = String::from_utf8_lossy(&text[start..end]).into_owned();
+            expected.push((doc_id as u64, index as u64, start as u64, end as u64, value));
+        }
+    }
+    expected.push((3, 0, 0, 5, "ok \u{fffd}.".into()));
+
+    let rows: Vec<_> = reader
+        .get_row_iter(None)
+        .unwrap()
+        .map(|row| {
+            let row = row.unwrap();
+            (
+                row.get_ulong(0).unwrap(),
+                row.get_ulong(1).unwrap(),
+                row.get_ulong(2).unwrap(),
+                row.get_ulong(3).unwrap(),
+                row.get_string(4).unwrap().clone(),
+            )
+        })
+        .collect();
+    assert_eq!(rows, expected);
+    assert_eq!(
+        reader.metadata().file_metadata().num_rows(),
+        expected.len() as i64
+    );
+}
@@ -0,0 +1,380 @@
+//! Property tests: boundary invariants over random texts and settings.
+//!
+//! Every case is generated from a seed, and a failing assertion reports it.
+//! Set `CHUNK_PROPERTY_CASES` to run more cases (default 500), and
+//! `CHUNK_PROPERTY_SEED` to replay a single case.
+
+use chunk::{
+    IncludeDelim, OwnedChunker, RecursiveChunker, StreamChunker, WindowChunker, chunk,
+    split_at_delimiters,
+};
+
+/// SplitMix64, so the tests need no extra dependency and replay exactly.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `lo..=hi`.
+    fn range(&mut self, lo: usize, hi: usize) -> usize {
+        lo + (self.next() % (hi - lo + 1) as u64) as usize
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next() % 100 < percent
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() - 1)]
+    }
+}
+
+/// Pieces texts are made of: words, delimiters, runs and multi-byte characters.
+const PIECES: &[&str] = &[
+    "a", "word", "chunk", " ", "  ", ".", ". ", "?", "!", "\n", "\n\n", ",", "é", "€", "日本",
+    "😀", "\t", "...", "x",
+];
+
+fn text(rng: &mut Rng) -> Vec<u8> {
+    let pieces = rng.range(0, 60);
+    let mut text = String::new();
+    for _ in 0..pieces {
+        text.push_str(rng.pick::<&str>(PIECES));
+    }
+    text.into_bytes()
+}
+
+/// Random bytes, for the invariants that must hold on any input.
+fn bytes(rng: &mut Rng) -> Vec<u8> {
+    let len = rng.range(0, 200);
+    (0..len)
+        .map(|_| *rng.pick(b"ab .\n?\xc3\xa9\xff"))
+        .collect()
+}
+
+#[derive(Debug)]
+struct Case {
+    size: usize,
+    delimiters: Vec<u8>,
+    patterns: Vec<Vec<u8>>,
+    prefix: bool,
+    consecutive: bool,
+    forward_fallback: bool,
+    utf8_safe: bool,
+    overlap: usize,
+    max_size: Option<usize>,
+}
+
+impl Case {
+    fn new(rng: &mut Rng) -> Self {
+        let delimiters = [b'.', b'?', b'\n', b' ', b',', b'!', b'\t', b'x']
+            .into_iter()
+            .filter(|_| rng.chance(40))
+            .collect();
+        let patterns = [&b". "[..], b"\n\n", b"...", "日本".as_bytes(), b"word"]
+            .into_iter()
+            .filter(|_| rng.chance(15))
+            .map(<[u8]>::to_vec)
+            .collect();
+        let utf8_safe = rng.chance(50);
+        Case {
+            size: rng.range(1, 48),
+            delimiters,
+            patterns,
+            prefix: rng.chance(30),
+            consecutive: rng.chance(30),
+            forward_fallback: rng.chance(20),
+            utf8_safe,
+            overlap: if rng.chance(30) { rng.range(1, 12) } else { 0 },
+            // A cap below one character can't be kept in UTF-8 mode
+            max_size: rng
+                .chance(30)
+                .then(|| rng.range(if utf8_safe { 4 } else { 1 }, 40)),
+        }
+    }
+
+    /// Whether every chunk (before overlap) must fit in `size`.
+    fn bounded_by_size(&self) -> bool {
+        !self.forward_fallback && (!self.utf8_safe || self.size >= 4)
+    }
+
+    fn offsets(&self, text: &[u8]) -> Vec<(usize, usize)> {
+        let mut chunker = chunk(text)
+            .size(self.size)
+            .delimiters(&self.delimiters)
+            .patterns(&self.patterns)
+            .utf8_safe(self.utf8_safe)
+            .overlap(self.overlap);
+        if self.prefix {
+            chunker = chunker.prefix();
+        }
+        if self.consecutive {
+            chunker = chunker.consecutive();
+        }
+        if self.forward_fallback {
+            chunker = chunker.forward_fallback();
+        }
+        if let Some(max_size) = self.max_size {
+            chunker = chunker.max_size(max_size);
+        }
+        chunker.collect_offsets()
+    }
+
+    fn owned_offsets(&self, text: &[u8]) -> Vec<(usize, usize)> {
+        let mut chunker = OwnedChunker::new(text.to_vec())
+            .size(self.size)
+            .delimiters(self.delimiters.clone())
+            .patterns(&self.patterns)
+            .utf8_safe(self.utf8_safe)
+            .overlap(self.overlap);
+        if self.prefix {
+            chunker = chunker.prefix();
+        }
+        if self.consecutive {
+            chunker = chunker.consecutive();
+        }
+        if self.forward_fallback {
+            chunker = chunker.forward_fallback();
+        }
+        if let Some(max_size) = self.max_size {
+            chunker = chunker.max_size(max_size);
+        }
+        chunker.collect_offsets()
+    }
+
+    /// Chunks from a `StreamChunker` fed `text` in random pieces.
+    fn streamed(&self, text: &[u8], rng: &mut Rng) -> Vec<Vec<u8>> {
+        let mut stream = StreamChunker::new()
+            .size(self.size)
+            .delimiters(self.delimiters.clone())
+            .patterns(&self.patterns)
+            .utf8_safe(self.utf8_safe)
+            .overlap(self.overlap);
+        if self.prefix {
+            stream = stream.prefix();
+        }
+        if self.consecutive {
+            stream = stream.consecutive();
+        }
+        if self.forward_fallback {
+            stream = stream.forward_fallback();
+        }
+        if let Some(max_size) = self.max_size {
+            stream = stream.max_size(max_size);
+        }
+        let mut chunks = Vec::new();
+        let mut rest = text;
+        while !rest.is_empty() {
+            let (piece, tail) = rest.split_at(rng.range(1, rest.len()));
+            chunks.extend(stream.push(piece));
+            rest = tail;
+        }
+        chunks.extend(stream.finish());
+        chunks
+    }
+}
+
+/// Offsets reassemble `len` bytes: each chunk ends after the last, and
+/// starts no later than where the previous one ended.
+fn assert_covers(offsets: &[(usize, usize)], len: usize, context: &dyn std::fmt::Debug) {
+    if len == 0 {
+        assert!(offsets.is_empty(), "{context:?}: chunks of empty text");
+        return;
+    }
+    assert_eq!(offsets.first().map(|o| o.0), Some(0), "{context:?}");
+    assert_eq!(offsets.last().map(|o| o.1), Some(len), "{context:?}");
+    for pair in offsets.windows(2) {
+        let ((start, end), (next_start, next_end)) = (pair[0], pair[1]);
+        assert!(start < end, "{context:?}: empty chunk at {start}");
+        assert!(next_end > end, "{context:?}: {pair:?} doesn't advance");
+        assert!(next_start <= end, "{context:?}: gap in {pair:?}");
+        assert!(next_start >= start, "{context:?}: {pair:?} goes back");
+    }
+}
+
+/// Reassemble the text from overlapping offsets.
+fn reassemble(text: &[u8], offsets: &[(usize, usize)]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for &(start, end) in offsets {
+        let from = out.len().max(start);
+        out.extend_from_slice(&text[from..end]);
+    }
+    out
+}
+
+fn cases() -> impl Iterator<Item = u64> {
+    let seed = std::env::var("CHUNK_PROPERTY_SEED").ok();
+    let count = std::env::var("CHUNK_PROPERTY_CASES")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(500);
+    match seed.and_then(|s| s.parse().ok()) {
+        Some(seed) => seed..seed + 1,
+        None => 0..count,
+    }
+}
+
+#[test]
+fn test_property_chunks_cover_text() {
+    for seed in cases() {
+        let mut rng = Rng(seed);
+        let text = if rng.chance(20) {
+            bytes(&mut rng)
+        } else {
+            text(&mut rng)
+        };
+        let case = Case::new(&mut rng);
+        let context = (seed, &case);
+        let offsets = case.offsets(&text);
+
+        assert_covers(&offsets, text.len(), &context);
+        assert_eq!(reassemble(&text, &offsets), text, "{context:?}");
+        if case.overlap == 0 {
+            for pair in offsets.windows(2) {
+                assert_eq!(pair[0].1, pair[1].0, "{context:?}: overlap without overlap");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_property_sizes() {
+    for seed in cases() {
+        let mut rng = Rng(seed);
+        let text = text(&mut rng);
+        let case = Case::new(&mut rng);
+        let context = (seed, &case);
+        let offsets = case.offsets(&text);
+
+        if let Some(max_size) = case.max_size {
+            for &(start, end) in &offsets {
+                assert!(end - start <= max_size, "{context:?}: {start}..{end}");
+            }
+        }
+        if case.bounded_by_size() {
+            // The part of each chunk after its overlap fits the target size
+            let mut prev_end = 0;
+            for &(start, end) in &offsets {
+                assert!(
+                    end - prev_end.max(start) <= case.size,
+                    "{context:?}: {start}..{end}"
+                );
+                prev_end = end;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_property_utf8_safe() {
+    for seed in cases() {
+        let mut rng = Rng(seed);
+        let text = text(&mut rng);
+        let mut case = Case::new(&mut rng);
+        case.utf8_safe = true;
+        case.max_size = case.max_size.map(|max| max.max(4));
+        let context = (seed, &case);
+
+        for (start, end) in case.offsets(&text) {
+            assert!(
+                std::str::from_utf8(&text[start..end]).is_ok(),
+                "{context:?}: {start}..{end}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_property_access_paths_agree() {
+    for seed in cases() {
+        let mut rng = Rng(seed);
+        let text = text(&mut rng);
+        let case = Case::new(&mut rng);
+        let context = (seed, &case);
+        let offsets = case.offsets(&text);
+
+        assert_eq!(case.owned_offsets(&text), offsets, "{context:?}");
+        let expected: Vec<_> = offsets.iter().map(|&(s, e)| text[s..e].to_vec()).collect();
+        assert_eq!(case.streamed(&text, &mut rng), expected, "{context:?}");
+    }
+}
+
+#[test]
+fn test_property_split_covers_text() {
+    for seed in cases() {
+        let mut rng = Rng(seed);
+        let text = text(&mut rng);
+        let delimiters: Vec<u8> = b".? \n"
+            .iter()
+            .copied()
+            .filter(|_| rng.chance(50))
+            .collect();
+        let include = *rng.pick(&[IncludeDelim::Prev, IncludeDelim::Next]);
+        let context = (seed, &delimiters, include);
+
+        let offsets = split_at_delimiters(&text, &delimiters, include, 0);
+        assert_covers(&offsets, text.len(), &context);
+        assert_eq!(reassemble(&text, &offsets), text, "{context:?}");
+    }
+}
+
+#[test]
+fn test_property_recursive_and_windows() {
+    for seed in cases() {
+        let mut rng = Rng(seed);
+        let text = text(&mut rng);
+        let size = rng.range(4, 48);
+        let context = (seed, size);
+
+        let offsets = RecursiveChunker::paragraphs()
+            .size(size)
+            .utf8_safe(true)
+            .chunk_offsets(&text);
+        assert_covers(&offsets, text.len(), &context);
+        assert_eq!(reassemble(&text, &offsets), text, "{context:?}");
+        for &(start, end) in &offsets {
+            assert!(end - start <= size, "{context:?}: {start}..{end}");
+            assert!(
+                std::str::from_utf8(&text[start..end]).is_ok(),
+                "{context:?}"
+            );
+        }
+
+        let stride = rng.range(1, size);
+        let windows = WindowChunker::new()
+            .size(size)
+            .stride(stride)
+            .delimiters(b" .")
+            .chunk_offsets(&text);
+        // Windows may share an end (snapped to the same delimiter) or leave
+        // gaps (a stride above the size, or an end snapped back)
+        let context = (seed, size, stride);
+        assert_eq!(
+            windows.first().map(|w| w.0),
+            (!text.is_empty()).then_some(0)
+        );
+        assert_eq!(
+            windows.last().map(|w| w.1),
+            (!text.is_empty()).then_some(text.len())
+        );
+        for &(start, end) in &windows {
+            assert!(
+                start < end && end - start <= size,
+                "{context:?}: {start}..{end}"
+            );
+        }
+        for pair in windows.windows(2) {
+            let ((start, end), (next_start, next_end)) = (pair[0], pair[1]);
+            assert!(
+                next_start > start && next_end >= end,
+                "{context:?}: {pair:?}"
+            );
+        }
+    }
+}